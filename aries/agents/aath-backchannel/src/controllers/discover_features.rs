@@ -0,0 +1,139 @@
+use std::{
+    collections::HashMap,
+    sync::{MutexGuard, RwLock},
+};
+
+use actix_web::{get, post, web, Responder};
+use aries_vcx_agent::aries_vcx::messages::{
+    decorators::thread::Thread,
+    msg_fields::protocols::discover_features::{
+        disclose::{Disclose, DiscloseContent, DiscloseDecorators},
+        query::{Query, QueryContent, QueryDecorators},
+        DiscoverFeatures, ProtocolDescriptor,
+    },
+    AriesMessage,
+};
+use uuid::Uuid;
+
+use crate::{
+    controllers::AathRequest,
+    error::{HarnessError, HarnessErrorType, HarnessResult},
+    HarnessAgent,
+};
+
+#[derive(Deserialize, Default)]
+pub struct FeatureQuery {
+    #[serde(default)]
+    query: Option<String>,
+    #[serde(default)]
+    comment: Option<String>,
+}
+
+impl HarnessAgent {
+    fn disclosed_features(
+        &self,
+    ) -> HarnessResult<MutexGuard<HashMap<String, Vec<ProtocolDescriptor>>>> {
+        self.disclosed_features.lock().map_err(|_| {
+            HarnessError::from_msg(
+                HarnessErrorType::InvalidState,
+                "Failed to lock disclosed features",
+            )
+        })
+    }
+
+    pub async fn send_feature_query(
+        &self,
+        connection_id: &str,
+        feature_query: &FeatureQuery,
+    ) -> HarnessResult<String> {
+        let content = QueryContent {
+            query: feature_query.query.clone().unwrap_or("*".to_string()),
+            comment: feature_query.comment.clone(),
+        };
+        let query = Query::builder()
+            .id(Uuid::new_v4().to_string())
+            .content(content)
+            .decorators(QueryDecorators::default())
+            .build();
+        let thread_id = query.id.clone();
+        self.aries_agent
+            .connections()
+            .send_message(connection_id, &AriesMessage::from(query))
+            .await?;
+        Ok(json!({ "connection_id": connection_id, "thread_id": thread_id }).to_string())
+    }
+
+    pub fn get_disclosed_features(&self, connection_id: &str) -> HarnessResult<String> {
+        let protocols = self
+            .disclosed_features()?
+            .get(connection_id)
+            .cloned()
+            .ok_or_else(|| HarnessError::from_kind(HarnessErrorType::NotFoundError))?;
+        Ok(json!({ "connection_id": connection_id, "protocols": protocols }).to_string())
+    }
+
+    /// Queries are answered right away with the protocols of the registry matching them
+    pub async fn handle_discover_features_msg(
+        &self,
+        msg: DiscoverFeatures,
+        connection_id: &str,
+    ) -> HarnessResult<()> {
+        match msg {
+            DiscoverFeatures::Query(query) => {
+                let disclose = Disclose::builder()
+                    .id(Uuid::new_v4().to_string())
+                    .content(
+                        DiscloseContent::builder()
+                            .protocols(query.content.lookup())
+                            .build(),
+                    )
+                    .decorators(
+                        DiscloseDecorators::builder()
+                            .thread(Thread::builder().thid(query.id).build())
+                            .build(),
+                    )
+                    .build();
+                self.aries_agent
+                    .connections()
+                    .send_message(connection_id, &AriesMessage::from(disclose))
+                    .await?;
+            }
+            DiscoverFeatures::Disclose(disclose) => {
+                self.disclosed_features()?
+                    .insert(connection_id.to_string(), disclose.content.protocols);
+            }
+        };
+        Ok(())
+    }
+}
+
+#[post("/send-query")]
+pub async fn send_feature_query(
+    req: web::Json<AathRequest<FeatureQuery>>,
+    agent: web::Data<RwLock<HarnessAgent>>,
+) -> impl Responder {
+    agent
+        .read()
+        .unwrap()
+        .send_feature_query(&req.id, &req.data)
+        .await
+}
+
+#[get("/{connection_id}")]
+pub async fn get_disclosed_features(
+    agent: web::Data<RwLock<HarnessAgent>>,
+    path: web::Path<String>,
+) -> impl Responder {
+    agent
+        .read()
+        .unwrap()
+        .get_disclosed_features(&path.into_inner())
+}
+
+pub fn config(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/command/discover-features")
+            .service(send_feature_query)
+            .service(get_disclosed_features),
+    );
+}
@@ -2,13 +2,18 @@ use std::sync::RwLock;
 
 use actix_web::{get, post, web, Responder};
 use anoncreds_types::data_types::identifiers::cred_def_id::CredentialDefinitionId;
-use aries_vcx_agent::aries_vcx::{
-    handlers::util::OfferInfo,
-    messages::msg_fields::protocols::cred_issuance::v1::{
-        propose_credential::{ProposeCredentialV1, ProposeCredentialV1Content},
-        CredentialPreviewV1 as VcxCredentialPreview, CredentialPreviewV1,
+use aries_vcx_agent::{
+    aries_vcx::{
+        handlers::util::OfferInfo,
+        messages::msg_fields::protocols::cred_issuance::v1::{
+            propose_credential::{ProposeCredentialV1, ProposeCredentialV1Content},
+            CredentialPreviewV1 as VcxCredentialPreview, CredentialPreviewV1,
+        },
+        protocols::issuance::{
+            holder::state_machine::HolderState, issuer::state_machine::IssuerState,
+        },
     },
-    protocols::issuance::{holder::state_machine::HolderState, issuer::state_machine::IssuerState},
+    ProtocolVersion,
 };
 use display_as_json::Display;
 use uuid::Uuid;
@@ -20,7 +25,7 @@ use crate::{
 };
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
-struct CredentialPreview(VcxCredentialPreview);
+pub(super) struct CredentialPreview(pub(super) VcxCredentialPreview);
 
 impl Default for CredentialPreview {
     fn default() -> Self {
@@ -30,21 +35,21 @@ impl Default for CredentialPreview {
 
 #[derive(Serialize, Deserialize, Default, Debug, Display)]
 pub struct CredentialOffer {
-    cred_def_id: String,
-    credential_preview: CredentialPreview,
-    connection_id: String,
+    pub(super) cred_def_id: String,
+    pub(super) credential_preview: CredentialPreview,
+    pub(super) connection_id: String,
 }
 
 #[derive(Serialize, Deserialize, Default, Clone, Debug, Display)]
 pub struct CredentialProposal {
-    schema_issuer_did: String,
-    issuer_did: String,
-    schema_name: String,
-    cred_def_id: String,
-    schema_version: String,
-    credential_proposal: CredentialPreview,
-    connection_id: String,
-    schema_id: String,
+    pub(super) schema_issuer_did: String,
+    pub(super) issuer_did: String,
+    pub(super) schema_name: String,
+    pub(super) cred_def_id: String,
+    pub(super) schema_version: String,
+    pub(super) credential_proposal: CredentialPreview,
+    pub(super) connection_id: String,
+    pub(super) schema_id: String,
 }
 
 #[derive(Serialize, Deserialize, Default)]
@@ -59,16 +64,19 @@ pub struct CredentialId {
     credential_id: String,
 }
 
-fn to_backchannel_state_issuer(state: IssuerState) -> State {
-    match state {
-        IssuerState::Initial => State::Initial,
-        IssuerState::ProposalReceived | IssuerState::OfferSet => State::ProposalReceived,
-        // IssuerState::OfferSent => State::OfferSent, // todo: we used to track "io state" in vcx,
-        // now we don't - will this fail some AATH cases?
-        IssuerState::RequestReceived => State::RequestReceived,
-        IssuerState::CredentialSet => State::CredentialSent,
-        IssuerState::Finished => State::Done,
-        IssuerState::Failed => State::Failure,
+fn to_backchannel_state_issuer(state: IssuerState, version: ProtocolVersion) -> State {
+    match (state, version) {
+        (IssuerState::Initial, _) => State::Initial,
+        (IssuerState::ProposalReceived, _) => State::ProposalReceived,
+        // todo: we used to track "io state" in vcx, now we don't - will this fail some 1.0 AATH
+        // cases?
+        (IssuerState::OfferSet, ProtocolVersion::V1) => State::ProposalReceived,
+        (IssuerState::OfferSet, ProtocolVersion::V2) => State::OfferSent,
+        (IssuerState::RequestReceived, _) => State::RequestReceived,
+        (IssuerState::CredentialSet, ProtocolVersion::V1) => State::CredentialSent,
+        (IssuerState::CredentialSet, ProtocolVersion::V2) => State::CredentialIssued,
+        (IssuerState::Finished, _) => State::Done,
+        (IssuerState::Failed, _) => State::Failure,
     }
 }
 
@@ -125,6 +133,7 @@ impl HarnessAgent {
     pub async fn send_credential_proposal(
         &self,
         cred_proposal: &CredentialProposal,
+        version: ProtocolVersion,
     ) -> HarnessResult<String> {
         info!("issuance::send_credential_proposal >>");
         let proposal_id = Uuid::new_v4().to_string();
@@ -143,7 +152,7 @@ impl HarnessAgent {
         let id = self
             .aries_agent
             .holder()
-            .send_credential_proposal(&cred_proposal.connection_id, proposal_data)
+            .send_credential_proposal(&cred_proposal.connection_id, proposal_data, version)
             .await?;
         let state = to_backchannel_state_holder(self.aries_agent.holder().get_state(&id)?);
         info!("issuance::send_credential_proposal << id: {:?}", id);
@@ -166,6 +175,7 @@ impl HarnessAgent {
         &self,
         cred_offer: &CredentialOffer,
         thread_id: &str,
+        version: ProtocolVersion,
     ) -> HarnessResult<String> {
         info!(
             "issuance::send_credential_offer >> cred_offer: {}, thread_id: {}",
@@ -241,9 +251,9 @@ impl HarnessAgent {
         let id = self
             .aries_agent
             .issuer()
-            .send_credential_offer(id, connection_id, offer_info)
+            .send_credential_offer(id, connection_id, offer_info, version)
             .await?;
-        let state = to_backchannel_state_issuer(self.aries_agent.issuer().get_state(&id)?);
+        let state = to_backchannel_state_issuer(self.aries_agent.issuer().get_state(&id)?, version);
         Ok(json!({ "state": state, "thread_id": id }).to_string())
     }
 
@@ -251,10 +261,11 @@ impl HarnessAgent {
         &self,
         id: &str,
         _credential: &Credential,
+        version: ProtocolVersion,
     ) -> HarnessResult<String> {
         info!("issuance::issue_credential >> id: {:?}", id);
         self.aries_agent.issuer().send_credential(id).await?;
-        let state = to_backchannel_state_issuer(self.aries_agent.issuer().get_state(id)?);
+        let state = to_backchannel_state_issuer(self.aries_agent.issuer().get_state(id)?, version);
         Ok(json!({ "state": state }).to_string())
     }
 
@@ -270,10 +281,14 @@ impl HarnessAgent {
         Ok(json!({ "state": to_backchannel_state_holder(state), "credential_id": id }).to_string())
     }
 
-    pub async fn get_issuer_state(&self, id: &str) -> HarnessResult<String> {
+    pub async fn get_issuer_state(
+        &self,
+        id: &str,
+        version: ProtocolVersion,
+    ) -> HarnessResult<String> {
         info!("issuance::get_issuer_state >> id: {:?}", id);
         let state = if self.aries_agent.issuer().exists_by_id(id) {
-            to_backchannel_state_issuer(self.aries_agent.issuer().get_state(id)?)
+            to_backchannel_state_issuer(self.aries_agent.issuer().get_state(id)?, version)
         } else if self.aries_agent.holder().exists_by_id(id) {
             to_backchannel_state_holder(self.aries_agent.holder().get_state(id)?)
         } else {
@@ -295,7 +310,7 @@ pub async fn send_credential_proposal(
     agent
         .read()
         .unwrap()
-        .send_credential_proposal(&req.data)
+        .send_credential_proposal(&req.data, ProtocolVersion::V1)
         .await
 }
 
@@ -307,7 +322,7 @@ pub async fn send_credential_offer(
     agent
         .read()
         .unwrap()
-        .send_credential_offer(&req.data, &req.id)
+        .send_credential_offer(&req.data, &req.id, ProtocolVersion::V1)
         .await
 }
 
@@ -327,7 +342,7 @@ pub async fn get_issuer_state(
     agent
         .read()
         .unwrap()
-        .get_issuer_state(&path.into_inner())
+        .get_issuer_state(&path.into_inner(), ProtocolVersion::V1)
         .await
 }
 
@@ -339,7 +354,7 @@ pub async fn issue_credential(
     agent
         .read()
         .unwrap()
-        .issue_credential(&req.id, &req.data)
+        .issue_credential(&req.id, &req.data, ProtocolVersion::V1)
         .await
 }
 
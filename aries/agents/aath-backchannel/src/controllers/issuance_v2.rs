@@ -0,0 +1,168 @@
+use std::sync::RwLock;
+
+use actix_web::{get, post, web, Responder};
+use aries_vcx_agent::{
+    aries_vcx::messages::msg_fields::protocols::cred_issuance::{
+        common::CredentialAttr, v1::CredentialPreviewV1,
+    },
+    ProtocolVersion,
+};
+
+use crate::{
+    controllers::{
+        issuance::{CredentialOffer, CredentialPreview, CredentialProposal},
+        AathRequest,
+    },
+    HarnessAgent,
+};
+
+// Issue Credential 2.0 is run by the 1.0 state machines of the agent with the AnonCreds
+// attachment formats, so only the indy filter of the requests is taken into account
+
+#[derive(Deserialize, Default)]
+pub struct CredentialPreviewV2 {
+    #[serde(default)]
+    attributes: Vec<CredentialAttr>,
+}
+
+#[derive(Deserialize, Default)]
+pub struct IndyCredentialFilter {
+    #[serde(default)]
+    schema_issuer_did: String,
+    #[serde(default)]
+    issuer_did: String,
+    #[serde(default)]
+    schema_name: String,
+    #[serde(default)]
+    schema_version: String,
+    #[serde(default)]
+    schema_id: String,
+    #[serde(default)]
+    cred_def_id: String,
+}
+
+#[derive(Deserialize, Default)]
+pub struct CredentialFilter {
+    #[serde(default)]
+    indy: IndyCredentialFilter,
+}
+
+#[derive(Deserialize, Default)]
+pub struct CredentialExchangeV2 {
+    #[serde(default)]
+    connection_id: String,
+    #[serde(default)]
+    credential_preview: CredentialPreviewV2,
+    #[serde(default)]
+    filter: CredentialFilter,
+}
+
+impl CredentialExchangeV2 {
+    fn preview(&self) -> CredentialPreview {
+        CredentialPreview(CredentialPreviewV1::new(
+            self.credential_preview.attributes.clone(),
+        ))
+    }
+}
+
+impl From<&CredentialExchangeV2> for CredentialProposal {
+    fn from(exchange: &CredentialExchangeV2) -> Self {
+        let filter = &exchange.filter.indy;
+        Self {
+            schema_issuer_did: filter.schema_issuer_did.clone(),
+            issuer_did: filter.issuer_did.clone(),
+            schema_name: filter.schema_name.clone(),
+            cred_def_id: filter.cred_def_id.clone(),
+            schema_version: filter.schema_version.clone(),
+            credential_proposal: exchange.preview(),
+            connection_id: exchange.connection_id.clone(),
+            schema_id: filter.schema_id.clone(),
+        }
+    }
+}
+
+impl From<&CredentialExchangeV2> for CredentialOffer {
+    fn from(exchange: &CredentialExchangeV2) -> Self {
+        Self {
+            cred_def_id: exchange.filter.indy.cred_def_id.clone(),
+            credential_preview: exchange.preview(),
+            connection_id: exchange.connection_id.clone(),
+        }
+    }
+}
+
+#[post("/send-proposal")]
+pub async fn send_credential_proposal(
+    req: web::Json<AathRequest<CredentialExchangeV2>>,
+    agent: web::Data<RwLock<HarnessAgent>>,
+) -> impl Responder {
+    agent
+        .read()
+        .unwrap()
+        .send_credential_proposal(&(&req.data).into(), ProtocolVersion::V2)
+        .await
+}
+
+#[post("/send-offer")]
+pub async fn send_credential_offer(
+    req: web::Json<AathRequest<CredentialExchangeV2>>,
+    agent: web::Data<RwLock<HarnessAgent>>,
+) -> impl Responder {
+    agent
+        .read()
+        .unwrap()
+        .send_credential_offer(&(&req.data).into(), &req.id, ProtocolVersion::V2)
+        .await
+}
+
+#[post("/send-request")]
+pub async fn send_credential_request(
+    req: web::Json<AathRequest<serde_json::Value>>,
+    agent: web::Data<RwLock<HarnessAgent>>,
+) -> impl Responder {
+    agent.read().unwrap().send_credential_request(&req.id).await
+}
+
+#[post("/issue")]
+pub async fn issue_credential(
+    req: web::Json<AathRequest<serde_json::Value>>,
+    agent: web::Data<RwLock<HarnessAgent>>,
+) -> impl Responder {
+    agent
+        .read()
+        .unwrap()
+        .issue_credential(&req.id, &Default::default(), ProtocolVersion::V2)
+        .await
+}
+
+#[post("/store")]
+pub async fn store_credential(
+    req: web::Json<AathRequest<serde_json::Value>>,
+    agent: web::Data<RwLock<HarnessAgent>>,
+) -> impl Responder {
+    agent.read().unwrap().store_credential(&req.id).await
+}
+
+#[get("/{issuer_id}")]
+pub async fn get_issuer_state(
+    agent: web::Data<RwLock<HarnessAgent>>,
+    path: web::Path<String>,
+) -> impl Responder {
+    agent
+        .read()
+        .unwrap()
+        .get_issuer_state(&path.into_inner(), ProtocolVersion::V2)
+        .await
+}
+
+pub fn config(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/command/issue-credential-v2")
+            .service(send_credential_proposal)
+            .service(send_credential_offer)
+            .service(get_issuer_state)
+            .service(send_credential_request)
+            .service(issue_credential)
+            .service(store_credential),
+    );
+}
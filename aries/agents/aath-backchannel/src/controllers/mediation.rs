@@ -0,0 +1,333 @@
+use std::{
+    collections::{hash_map::Entry, HashMap},
+    sync::{MutexGuard, RwLock},
+};
+
+use actix_web::{get, post, web, Responder};
+use aries_vcx_agent::aries_vcx::{
+    aries_vcx_wallet::wallet::base_wallet::did_wallet::DidWallet,
+    messages::{
+        decorators::thread::Thread,
+        msg_fields::protocols::{
+            coordinate_mediation::{
+                keylist_update::KeylistUpdateItemAction,
+                keylist_update_response::{KeylistUpdateItemResult, KeylistUpdateResponseItem},
+                CoordinateMediation, KeylistUpdate, KeylistUpdateResponse,
+                KeylistUpdateResponseContent, KeylistUpdateResponseDecorators, MediateDeny,
+                MediateDenyContent, MediateDenyDecorators, MediateGrant, MediateGrantContent,
+                MediateGrantDecorators,
+            },
+            routing::Forward,
+        },
+        AriesMessage,
+    },
+    protocols::mediation::build_mediate_request,
+};
+use uuid::Uuid;
+
+use crate::{
+    controllers::AathRequest,
+    error::{HarnessError, HarnessErrorType, HarnessResult},
+    HarnessAgent,
+};
+
+// A granted mediation hands out a fresh routing key. Messages forwarded with it to one of the
+// recipient keys registered by the mediated agent are delivered to it right away, there is no
+// queue to pick messages up from.
+
+#[derive(Copy, Clone, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum MediationState {
+    RequestSent,
+    RequestReceived,
+    GrantSent,
+    GrantReceived,
+    DenySent,
+    DenyReceived,
+}
+
+/// Mediation over a connection, identified by the thread of its request
+#[derive(Clone, Debug)]
+pub struct MediationRecord {
+    thread_id: String,
+    state: MediationState,
+    // keys registered by the mediated agent, once mediation is granted to it
+    recipient_keys: Vec<String>,
+}
+
+impl HarnessAgent {
+    fn mediations(&self) -> HarnessResult<MutexGuard<HashMap<String, MediationRecord>>> {
+        self.mediations.lock().map_err(|_| {
+            HarnessError::from_msg(
+                HarnessErrorType::InvalidState,
+                "Failed to lock mediation records",
+            )
+        })
+    }
+
+    fn set_mediation_state(
+        &self,
+        connection_id: &str,
+        thread_id: String,
+        state: MediationState,
+    ) -> HarnessResult<String> {
+        match self.mediations()?.entry(connection_id.to_string()) {
+            Entry::Occupied(mut entry) => {
+                let record = entry.get_mut();
+                record.thread_id = thread_id;
+                record.state = state;
+            }
+            Entry::Vacant(entry) => {
+                entry.insert(MediationRecord {
+                    thread_id,
+                    state,
+                    recipient_keys: vec![],
+                });
+            }
+        }
+        Ok(json!({ "connection_id": connection_id, "state": state }).to_string())
+    }
+
+    fn received_mediation_request(&self, connection_id: &str) -> HarnessResult<String> {
+        match self.mediations()?.get(connection_id) {
+            Some(MediationRecord {
+                thread_id,
+                state: MediationState::RequestReceived,
+                ..
+            }) => Ok(thread_id.clone()),
+            _ => Err(HarnessError::from_msg(
+                HarnessErrorType::RequestNotReceived,
+                &format!("No mediation request received on connection {connection_id}"),
+            )),
+        }
+    }
+
+    pub async fn send_mediation_request(&self, connection_id: &str) -> HarnessResult<String> {
+        let request = build_mediate_request();
+        let thread_id = request.id.clone();
+        self.aries_agent
+            .connections()
+            .send_message(connection_id, &request.into())
+            .await?;
+        self.set_mediation_state(connection_id, thread_id, MediationState::RequestSent)
+    }
+
+    pub async fn send_mediation_grant(&self, connection_id: &str) -> HarnessResult<String> {
+        let thread_id = self.received_mediation_request(connection_id)?;
+        let routing_key = self
+            .aries_agent
+            .wallet()
+            .create_and_store_my_did(None, None)
+            .await?;
+        let grant = MediateGrant::builder()
+            .id(Uuid::new_v4().to_string())
+            .content(MediateGrantContent {
+                endpoint: self.service_endpoint.to_string(),
+                routing_keys: vec![routing_key.verkey().base58()],
+            })
+            .decorators(
+                MediateGrantDecorators::builder()
+                    .thread(Thread::builder().thid(thread_id.clone()).build())
+                    .build(),
+            )
+            .build();
+        self.aries_agent
+            .connections()
+            .send_message(connection_id, &AriesMessage::from(grant))
+            .await?;
+        self.set_mediation_state(connection_id, thread_id, MediationState::GrantSent)
+    }
+
+    pub async fn send_mediation_deny(&self, connection_id: &str) -> HarnessResult<String> {
+        let thread_id = self.received_mediation_request(connection_id)?;
+        let deny = MediateDeny::builder()
+            .id(Uuid::new_v4().to_string())
+            .content(MediateDenyContent::default())
+            .decorators(
+                MediateDenyDecorators::builder()
+                    .thread(Thread::builder().thid(thread_id.clone()).build())
+                    .build(),
+            )
+            .build();
+        self.aries_agent
+            .connections()
+            .send_message(connection_id, &AriesMessage::from(deny))
+            .await?;
+        self.set_mediation_state(connection_id, thread_id, MediationState::DenySent)
+    }
+
+    pub fn get_mediation_state(&self, connection_id: &str) -> HarnessResult<String> {
+        let record = self
+            .mediations()?
+            .get(connection_id)
+            .cloned()
+            .ok_or_else(|| HarnessError::from_kind(HarnessErrorType::NotFoundError))?;
+        Ok(json!({ "connection_id": connection_id, "state": record.state }).to_string())
+    }
+
+    /// Applies the updates to the keys of a connection mediation was granted to, answering with
+    /// their results
+    async fn update_keylist(
+        &self,
+        update: KeylistUpdate,
+        connection_id: &str,
+    ) -> HarnessResult<()> {
+        let updated = {
+            let mut mediations = self.mediations()?;
+            let record = match mediations.get_mut(connection_id) {
+                Some(record) if record.state == MediationState::GrantSent => record,
+                _ => {
+                    return Err(HarnessError::from_msg(
+                        HarnessErrorType::InvalidState,
+                        &format!("No mediation granted on connection {connection_id}"),
+                    ))
+                }
+            };
+            update
+                .content
+                .updates
+                .into_iter()
+                .map(|item| {
+                    let position = record
+                        .recipient_keys
+                        .iter()
+                        .position(|key| *key == item.recipient_key);
+                    let result = match (&item.action, position) {
+                        (KeylistUpdateItemAction::Add, None) => {
+                            record.recipient_keys.push(item.recipient_key.clone());
+                            KeylistUpdateItemResult::Success
+                        }
+                        (KeylistUpdateItemAction::Remove, Some(position)) => {
+                            record.recipient_keys.remove(position);
+                            KeylistUpdateItemResult::Success
+                        }
+                        _ => KeylistUpdateItemResult::NoChange,
+                    };
+                    KeylistUpdateResponseItem::builder()
+                        .recipient_key(item.recipient_key)
+                        .action(item.action)
+                        .result(result)
+                        .build()
+                })
+                .collect()
+        };
+        let response = KeylistUpdateResponse::builder()
+            .id(Uuid::new_v4().to_string())
+            .content(
+                KeylistUpdateResponseContent::builder()
+                    .updated(updated)
+                    .build(),
+            )
+            .decorators(
+                KeylistUpdateResponseDecorators::builder()
+                    .thread(Thread::builder().thid(update.id).build())
+                    .build(),
+            )
+            .build();
+        self.aries_agent
+            .connections()
+            .send_message(connection_id, &AriesMessage::from(response))
+            .await?;
+        Ok(())
+    }
+
+    /// Delivers the forwarded message to the mediated agent the recipient key was registered by
+    pub async fn handle_forward(&self, forward: Forward) -> HarnessResult<()> {
+        let connection_id = self
+            .mediations()?
+            .iter()
+            .find(|(_, record)| record.recipient_keys.contains(&forward.content.to))
+            .map(|(connection_id, _)| connection_id.clone())
+            .ok_or_else(|| {
+                HarnessError::from_msg(
+                    HarnessErrorType::NotFoundError,
+                    &format!("No mediation for recipient key {}", forward.content.to),
+                )
+            })?;
+        let message = serde_json::to_vec(&forward.content.msg)?;
+        self.aries_agent
+            .connections()
+            .send_packed_message(&connection_id, message)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn handle_mediation_msg(
+        &self,
+        msg: CoordinateMediation,
+        connection_id: &str,
+    ) -> HarnessResult<()> {
+        match msg {
+            CoordinateMediation::MediateRequest(request) => {
+                self.set_mediation_state(
+                    connection_id,
+                    request.id,
+                    MediationState::RequestReceived,
+                )?;
+            }
+            CoordinateMediation::MediateGrant(grant) => {
+                let thread_id = grant
+                    .decorators
+                    .thread
+                    .map_or(grant.id, |thread| thread.thid);
+                self.set_mediation_state(connection_id, thread_id, MediationState::GrantReceived)?;
+            }
+            CoordinateMediation::MediateDeny(deny) => {
+                let thread_id = deny.decorators.thread.map_or(deny.id, |thread| thread.thid);
+                self.set_mediation_state(connection_id, thread_id, MediationState::DenyReceived)?;
+            }
+            CoordinateMediation::KeylistUpdate(update) => {
+                self.update_keylist(update, connection_id).await?;
+            }
+            m => {
+                warn!("Received unsupported mediation message: {:?}", m);
+            }
+        };
+        Ok(())
+    }
+}
+
+#[post("/send-request")]
+pub async fn send_mediation_request(
+    req: web::Json<AathRequest<serde_json::Value>>,
+    agent: web::Data<RwLock<HarnessAgent>>,
+) -> impl Responder {
+    agent.read().unwrap().send_mediation_request(&req.id).await
+}
+
+#[post("/send-grant")]
+pub async fn send_mediation_grant(
+    req: web::Json<AathRequest<serde_json::Value>>,
+    agent: web::Data<RwLock<HarnessAgent>>,
+) -> impl Responder {
+    agent.read().unwrap().send_mediation_grant(&req.id).await
+}
+
+#[post("/send-deny")]
+pub async fn send_mediation_deny(
+    req: web::Json<AathRequest<serde_json::Value>>,
+    agent: web::Data<RwLock<HarnessAgent>>,
+) -> impl Responder {
+    agent.read().unwrap().send_mediation_deny(&req.id).await
+}
+
+#[get("/{connection_id}")]
+pub async fn get_mediation_state(
+    agent: web::Data<RwLock<HarnessAgent>>,
+    path: web::Path<String>,
+) -> impl Responder {
+    agent
+        .read()
+        .unwrap()
+        .get_mediation_state(&path.into_inner())
+}
+
+pub fn config(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/command/mediation")
+            .service(send_mediation_request)
+            .service(send_mediation_grant)
+            .service(send_mediation_deny)
+            .service(get_mediation_state),
+    );
+}
@@ -4,10 +4,14 @@ pub mod connection;
 pub mod credential_definition;
 pub mod did_exchange;
 pub mod didcomm;
+pub mod discover_features;
 pub mod general;
 pub mod issuance;
+pub mod issuance_v2;
+pub mod mediation;
 pub mod out_of_band;
 pub mod presentation;
+pub mod presentation_v2;
 pub mod revocation;
 pub mod schema;
 
@@ -21,7 +21,8 @@ impl HarnessAgent {
         let id = self
             .aries_agent
             .out_of_band()
-            .receive_invitation(invitation)?;
+            .receive_invitation(invitation)
+            .await?;
         Ok(json!({ "connection_id": id, "state": "invitation-received" }).to_string())
     }
 
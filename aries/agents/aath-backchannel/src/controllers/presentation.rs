@@ -4,16 +4,21 @@ use actix_web::{get, post, web, Responder};
 use anoncreds_types::data_types::messages::pres_request::{
     AttributeInfo, NonRevokedInterval, PredicateInfo, PresentationRequestPayload,
 };
-use aries_vcx_agent::aries_vcx::{
-    aries_vcx_anoncreds::anoncreds::base_anoncreds::BaseAnonCreds,
-    handlers::util::PresentationProposalData,
-    messages::msg_fields::protocols::present_proof::v1::propose::{Predicate, PresentationAttr},
-    protocols::proof_presentation::{
-        prover::state_machine::ProverState,
-        verifier::{
-            state_machine::VerifierState, verification_status::PresentationVerificationStatus,
+use aries_vcx_agent::{
+    aries_vcx::{
+        aries_vcx_anoncreds::anoncreds::base_anoncreds::BaseAnonCreds,
+        handlers::util::PresentationProposalData,
+        messages::msg_fields::protocols::present_proof::v1::propose::{
+            Predicate, PresentationAttr,
+        },
+        protocols::proof_presentation::{
+            prover::state_machine::ProverState,
+            verifier::{
+                state_machine::VerifierState, verification_status::PresentationVerificationStatus,
+            },
         },
     },
+    ProtocolVersion,
 };
 
 use crate::{
@@ -24,14 +29,14 @@ use crate::{
 
 #[derive(Serialize, Deserialize, Default, Debug)]
 pub struct PresentationRequestWrapper {
-    connection_id: String,
-    presentation_request: PresentationRequest,
+    pub(super) connection_id: String,
+    pub(super) presentation_request: PresentationRequest,
 }
 
 #[derive(Serialize, Deserialize, Default, Debug)]
 pub struct PresentationProposalWrapper {
-    connection_id: String,
-    presentation_proposal: PresentationProposal,
+    pub(super) connection_id: String,
+    pub(super) presentation_proposal: PresentationProposal,
 }
 
 // TODO: Remove these structs
@@ -49,9 +54,9 @@ pub struct ProofRequestDataWrapper {
 
 #[derive(Serialize, Deserialize, Default, Debug)]
 pub struct PresentationProposal {
-    comment: String,
-    attributes: Vec<PresentationAttr>,
-    predicates: Vec<Predicate>,
+    pub(super) comment: String,
+    pub(super) attributes: Vec<PresentationAttr>,
+    pub(super) predicates: Vec<Predicate>,
 }
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Clone, Default)]
@@ -89,6 +94,7 @@ impl HarnessAgent {
     pub async fn send_proof_request(
         &self,
         presentation_request: &PresentationRequestWrapper,
+        version: ProtocolVersion,
     ) -> HarnessResult<String> {
         let req_data = presentation_request
             .presentation_request
@@ -106,7 +112,12 @@ impl HarnessAgent {
         let id = self
             .aries_agent
             .verifier()
-            .send_proof_request(&presentation_request.connection_id, request.into_v1(), None)
+            .send_proof_request(
+                &presentation_request.connection_id,
+                request.into_v1(),
+                None,
+                version,
+            )
             .await?;
         let state = self.aries_agent.verifier().get_state(&id)?;
         Ok(json!({ "state": to_backchannel_state_verifier(state), "thread_id": id }).to_string())
@@ -115,6 +126,7 @@ impl HarnessAgent {
     pub async fn send_proof_proposal(
         &self,
         presentation_proposal: &PresentationProposalWrapper,
+        version: ProtocolVersion,
     ) -> HarnessResult<String> {
         let mut proposal_data = PresentationProposalData::default();
         for attr in presentation_proposal
@@ -128,7 +140,7 @@ impl HarnessAgent {
         let id = self
             .aries_agent
             .prover()
-            .send_proof_proposal(&presentation_proposal.connection_id, proposal_data)
+            .send_proof_proposal(&presentation_proposal.connection_id, proposal_data, version)
             .await?;
         let state = self.aries_agent.prover().get_state(&id)?;
         Ok(json!({ "state": to_backchannel_state_prover(state), "thread_id": id }).to_string())
@@ -185,7 +197,11 @@ pub async fn send_proof_request(
     req: web::Json<AathRequest<PresentationRequestWrapper>>,
     agent: web::Data<RwLock<HarnessAgent>>,
 ) -> impl Responder {
-    agent.read().unwrap().send_proof_request(&req.data).await
+    agent
+        .read()
+        .unwrap()
+        .send_proof_request(&req.data, ProtocolVersion::V1)
+        .await
 }
 
 #[post("/send-proposal")]
@@ -193,7 +209,11 @@ pub async fn send_proof_proposal(
     req: web::Json<AathRequest<PresentationProposalWrapper>>,
     agent: web::Data<RwLock<HarnessAgent>>,
 ) -> impl Responder {
-    agent.read().unwrap().send_proof_proposal(&req.data).await
+    agent
+        .read()
+        .unwrap()
+        .send_proof_proposal(&req.data, ProtocolVersion::V1)
+        .await
 }
 
 #[post("/send-presentation")]
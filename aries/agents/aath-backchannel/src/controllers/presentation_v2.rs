@@ -0,0 +1,173 @@
+use std::sync::RwLock;
+
+use actix_web::{get, post, web, Responder};
+use aries_vcx_agent::{
+    aries_vcx::messages::msg_fields::protocols::present_proof::v1::propose::PresentationAttr,
+    ProtocolVersion,
+};
+
+use crate::{
+    controllers::{
+        presentation::{
+            PresentationProposal, PresentationProposalWrapper, PresentationRequest,
+            PresentationRequestWrapper, ProofRequestData, ProofRequestDataWrapper,
+        },
+        AathRequest,
+    },
+    HarnessAgent,
+};
+
+// Present Proof 2.0 is run by the 1.0 state machines of the agent with the AnonCreds attachment
+// formats, the format named in the requests is not looked at
+
+#[derive(Deserialize, Default)]
+pub struct PresentationExchangeV2 {
+    #[serde(default)]
+    comment: Option<String>,
+    #[serde(default)]
+    data: ProofRequestData,
+}
+
+#[derive(Deserialize, Default)]
+pub struct PresentationRequestV2Wrapper {
+    #[serde(default)]
+    connection_id: String,
+    #[serde(default)]
+    presentation_request: PresentationExchangeV2,
+}
+
+#[derive(Deserialize, Default)]
+pub struct PresentationProposalV2Wrapper {
+    #[serde(default)]
+    connection_id: String,
+    #[serde(default)]
+    presentation_proposal: PresentationExchangeV2,
+}
+
+impl From<&PresentationRequestV2Wrapper> for PresentationRequestWrapper {
+    fn from(wrapper: &PresentationRequestV2Wrapper) -> Self {
+        Self {
+            connection_id: wrapper.connection_id.clone(),
+            presentation_request: PresentationRequest {
+                comment: wrapper.presentation_request.comment.clone(),
+                proof_request: ProofRequestDataWrapper {
+                    data: wrapper.presentation_request.data.clone(),
+                },
+            },
+        }
+    }
+}
+
+// Proposals list the requested attributes of a proof request, restricted to a credential
+// definition by their first restriction naming one
+impl From<&PresentationProposalV2Wrapper> for PresentationProposalWrapper {
+    fn from(wrapper: &PresentationProposalV2Wrapper) -> Self {
+        let proposal = &wrapper.presentation_proposal;
+        let attributes = proposal
+            .data
+            .requested_attributes
+            .iter()
+            .flatten()
+            .flat_map(|(_, info)| {
+                let cred_def_id = info
+                    .restrictions
+                    .as_ref()
+                    .and_then(|restrictions| serde_json::to_value(restrictions).ok())
+                    .and_then(|restrictions| find_cred_def_id(&restrictions));
+                let names = info.name.iter().chain(info.names.iter().flatten());
+                names
+                    .map(|name| PresentationAttr {
+                        name: name.clone(),
+                        cred_def_id: cred_def_id.clone(),
+                        mime_type: None,
+                        value: None,
+                        referent: None,
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+        Self {
+            connection_id: wrapper.connection_id.clone(),
+            presentation_proposal: PresentationProposal {
+                comment: proposal.comment.clone().unwrap_or_default(),
+                attributes,
+                predicates: vec![],
+            },
+        }
+    }
+}
+
+fn find_cred_def_id(restrictions: &serde_json::Value) -> Option<String> {
+    match restrictions {
+        serde_json::Value::Object(map) => map
+            .get("cred_def_id")
+            .and_then(|id| id.as_str())
+            .map(str::to_string)
+            .or_else(|| map.values().find_map(find_cred_def_id)),
+        serde_json::Value::Array(items) => items.iter().find_map(find_cred_def_id),
+        _ => None,
+    }
+}
+
+#[post("/send-request")]
+pub async fn send_proof_request(
+    req: web::Json<AathRequest<PresentationRequestV2Wrapper>>,
+    agent: web::Data<RwLock<HarnessAgent>>,
+) -> impl Responder {
+    agent
+        .read()
+        .unwrap()
+        .send_proof_request(&(&req.data).into(), ProtocolVersion::V2)
+        .await
+}
+
+#[post("/send-proposal")]
+pub async fn send_proof_proposal(
+    req: web::Json<AathRequest<PresentationProposalV2Wrapper>>,
+    agent: web::Data<RwLock<HarnessAgent>>,
+) -> impl Responder {
+    agent
+        .read()
+        .unwrap()
+        .send_proof_proposal(&(&req.data).into(), ProtocolVersion::V2)
+        .await
+}
+
+#[post("/send-presentation")]
+pub async fn send_presentation(
+    req: web::Json<AathRequest<serde_json::Value>>,
+    agent: web::Data<RwLock<HarnessAgent>>,
+) -> impl Responder {
+    agent.read().unwrap().send_presentation(&req.id).await
+}
+
+#[post("/verify-presentation")]
+pub async fn verify_presentation(
+    req: web::Json<AathRequest<serde_json::Value>>,
+    agent: web::Data<RwLock<HarnessAgent>>,
+) -> impl Responder {
+    agent.read().unwrap().verify_presentation(&req.id).await
+}
+
+#[get("/{proof_id}")]
+pub async fn get_proof_state(
+    agent: web::Data<RwLock<HarnessAgent>>,
+    path: web::Path<String>,
+) -> impl Responder {
+    agent
+        .read()
+        .unwrap()
+        .get_proof_state(&path.into_inner())
+        .await
+}
+
+pub fn config(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/command/proof-v2")
+            .service(send_proof_request)
+            .service(send_proof_proposal)
+            .service(send_presentation)
+            .service(verify_presentation)
+            .service(get_proof_state),
+    );
+}
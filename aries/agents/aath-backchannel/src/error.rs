@@ -24,8 +24,6 @@ pub enum HarnessErrorType {
     ProtocolError,
     #[display("Invalid state for requested operation")]
     InvalidState,
-    #[display("Encryption error")]
-    EncryptionError,
     #[display("Multiple credential definitions found")]
     MultipleCredDefinitions,
 }
@@ -93,6 +91,18 @@ impl std::convert::From<aries_vcx::aries_vcx_anoncreds::errors::error::VcxAnoncr
     }
 }
 
+impl std::convert::From<aries_vcx::aries_vcx_wallet::errors::error::VcxWalletError>
+    for HarnessError
+{
+    fn from(vcx_err: aries_vcx::aries_vcx_wallet::errors::error::VcxWalletError) -> HarnessError {
+        let kind = HarnessErrorType::InternalServerError;
+        HarnessError {
+            message: vcx_err.to_string(),
+            kind,
+        }
+    }
+}
+
 impl std::convert::From<serde_json::Error> for HarnessError {
     fn from(serde_err: serde_json::Error) -> HarnessError {
         let kind = HarnessErrorType::InternalServerError;
@@ -21,15 +21,19 @@ use std::{
 
 use actix_web::{middleware, web, App, HttpServer};
 use aries_vcx_agent::{
-    aries_vcx::{aries_vcx_wallet::wallet::askar::AskarWallet, messages::AriesMessage},
+    aries_vcx::{
+        aries_vcx_wallet::wallet::askar::AskarWallet,
+        messages::{msg_fields::protocols::discover_features::ProtocolDescriptor, AriesMessage},
+    },
     Agent as AriesAgent,
 };
 use clap::Parser;
-use controllers::out_of_band;
+use controllers::{mediation::MediationRecord, out_of_band};
+use reqwest::Url;
 
 use crate::controllers::{
-    connection, credential_definition, did_exchange, didcomm, general, issuance, presentation,
-    revocation, schema,
+    connection, credential_definition, did_exchange, didcomm, discover_features, general, issuance,
+    issuance_v2, mediation, presentation, presentation_v2, revocation, schema,
 };
 
 #[derive(Parser)]
@@ -59,6 +63,8 @@ enum State {
     RequestSent,
     PresentationSent,
     Done,
+    OfferSent,
+    CredentialIssued,
 }
 
 #[derive(Copy, Clone, Serialize)]
@@ -70,12 +76,17 @@ enum Status {
 pub struct HarnessAgent {
     aries_agent: AriesAgent<AskarWallet>,
     status: Status,
+    service_endpoint: Url,
     // did-exchange specific
     // todo: extra didx specific AATH service
     didx_msg_buffer: RwLock<Vec<AriesMessage>>,
     didx_pthid_to_thid: Mutex<HashMap<String, String>>,
     // A map of DIDExchange thread IDs to the intended recipient
     didx_thid_to_request_recipient_verkey: Mutex<HashMap<String, String>>,
+    // mediations by connection ID
+    mediations: Mutex<HashMap<String, MediationRecord>>,
+    // protocols last disclosed by connection ID
+    disclosed_features: Mutex<HashMap<String, Vec<ProtocolDescriptor>>>,
 }
 
 #[macro_export]
@@ -109,6 +120,7 @@ async fn main() -> std::io::Result<()> {
     let host = std::env::var("HOST").unwrap_or("0.0.0.0".to_string());
 
     let aries_agent = setup::initialize(opts.port).await;
+    let service_endpoint = setup::service_endpoint(opts.port);
 
     info!("Starting aries back-channel on port {}", opts.port);
     HttpServer::new(move || {
@@ -120,9 +132,12 @@ async fn main() -> std::io::Result<()> {
             .app_data(web::Data::new(RwLock::new(HarnessAgent {
                 aries_agent: aries_agent.clone(),
                 status: Status::Active,
+                service_endpoint: service_endpoint.clone(),
                 didx_msg_buffer: Default::default(),
                 didx_pthid_to_thid: Default::default(),
                 didx_thid_to_request_recipient_verkey: Default::default(),
+                mediations: Default::default(),
+                disclosed_features: Default::default(),
             })))
             .app_data(web::Data::new(RwLock::new(Vec::<AriesMessage>::new())))
             .service(
@@ -132,8 +147,12 @@ async fn main() -> std::io::Result<()> {
                     .configure(schema::config)
                     .configure(credential_definition::config)
                     .configure(issuance::config)
+                    .configure(issuance_v2::config)
                     .configure(revocation::config)
                     .configure(presentation::config)
+                    .configure(presentation_v2::config)
+                    .configure(mediation::config)
+                    .configure(discover_features::config)
                     .configure(out_of_band::config)
                     .configure(general::config),
             )
@@ -2,7 +2,7 @@ use std::{io::prelude::*, sync::Arc};
 
 use aries_vcx_agent::{
     aries_vcx::aries_vcx_wallet::wallet::askar::AskarWallet, build_askar_wallet,
-    Agent as AriesAgent, WalletInitConfig,
+    Agent as AriesAgent, AutoAcceptPolicy, WalletInitConfig,
 };
 use rand::{rng, Rng};
 use reqwest::Url;
@@ -100,13 +100,18 @@ async fn download_genesis_file() -> std::result::Result<String, String> {
     }
 }
 
+/// Endpoint the harness agents reach the backchannel's DIDComm service at
+pub fn service_endpoint(port: u32) -> Url {
+    let dockerhost = std::env::var("DOCKERHOST").unwrap_or("localhost".to_string());
+    Url::parse(&format!("http://{}:{}/didcomm", dockerhost, port)).unwrap()
+}
+
 pub async fn initialize(port: u32) -> AriesAgent<AskarWallet> {
     let register_nym_res = get_writer_seed().await;
     let genesis_path = download_genesis_file()
         .await
         .expect("Failed to download the genesis file");
-    let dockerhost = std::env::var("DOCKERHOST").unwrap_or("localhost".to_string());
-    let service_endpoint = Url::parse(&format!("http://{}:{}/didcomm", dockerhost, port)).unwrap();
+    let service_endpoint = service_endpoint(port);
 
     let wallet_config = WalletInitConfig {
         wallet_name: format!("rust_agent_{}", uuid::Uuid::new_v4()),
@@ -126,12 +131,20 @@ pub async fn initialize(port: u32) -> AriesAgent<AskarWallet> {
     .await
     .unwrap();
 
-    AriesAgent::initialize(
+    let agent = AriesAgent::initialize(
         genesis_path,
         wallet.clone(),
         service_endpoint.clone(),
         issuer_did,
     )
     .await
-    .unwrap()
+    .unwrap();
+    // the harness only asks for the verification result
+    agent
+        .set_auto_accept_policy(AutoAcceptPolicy {
+            presentations: true,
+            ..Default::default()
+        })
+        .unwrap();
+    agent
 }
@@ -0,0 +1,92 @@
+use actix_web::{web, HttpResponse};
+use aries_vcx::{
+    handlers::util::AnyInvitation,
+    messages::msg_fields::protocols::connection::invitation::Invitation,
+};
+use aries_vcx_wallet::wallet::base_wallet::BaseWallet;
+use serde_json::json;
+
+use super::{
+    records::{connection_record, did_exchange_record},
+    AdminApi,
+};
+use crate::error::AgentResult;
+
+impl<W: BaseWallet> AdminApi<W> {
+    /// Connections established through DID exchange are identified by the thread id of the
+    /// exchange, just as those established through the connection protocol
+    pub(super) fn connection_response(&self, connection_id: &str) -> AgentResult<HttpResponse> {
+        let record = if self.agent.did_exchange().exists_by_id(connection_id) {
+            let state = self.agent.did_exchange().get_state(connection_id)?;
+            did_exchange_record(connection_id, &state)
+        } else {
+            let state = self.agent.connections().get_state(connection_id)?;
+            connection_record(connection_id, state)
+        };
+        Ok(HttpResponse::Ok().json(record))
+    }
+}
+
+async fn create_invitation<W: BaseWallet + 'static>(
+    api: web::Data<AdminApi<W>>,
+) -> AgentResult<HttpResponse> {
+    let invitation = api.agent.connections().create_invitation(None).await?;
+    Ok(HttpResponse::Ok().json(json!({
+        "connection_id": invitation.id(),
+        "invitation": invitation,
+    })))
+}
+
+async fn receive_invitation<W: BaseWallet + 'static>(
+    api: web::Data<AdminApi<W>>,
+    invitation: web::Json<Invitation>,
+) -> AgentResult<HttpResponse> {
+    let connection_id = api
+        .agent
+        .receive_connection_invitation(AnyInvitation::Con(invitation.into_inner()))
+        .await?;
+    api.connection_response(&connection_id)
+}
+
+async fn accept_invitation<W: BaseWallet + 'static>(
+    api: web::Data<AdminApi<W>>,
+    path: web::Path<String>,
+) -> AgentResult<HttpResponse> {
+    api.agent.connections().send_request(&path).await?;
+    api.connection_response(&path)
+}
+
+async fn accept_request<W: BaseWallet + 'static>(
+    api: web::Data<AdminApi<W>>,
+    path: web::Path<String>,
+) -> AgentResult<HttpResponse> {
+    api.agent.connections().send_response(&path).await?;
+    api.connection_response(&path)
+}
+
+async fn get_connection<W: BaseWallet + 'static>(
+    api: web::Data<AdminApi<W>>,
+    path: web::Path<String>,
+) -> AgentResult<HttpResponse> {
+    api.connection_response(&path)
+}
+
+pub(super) fn config<W: BaseWallet + 'static>(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/connections")
+            .route("/create-invitation", web::post().to(create_invitation::<W>))
+            .route(
+                "/receive-invitation",
+                web::post().to(receive_invitation::<W>),
+            )
+            .route(
+                "/{conn_id}/accept-invitation",
+                web::post().to(accept_invitation::<W>),
+            )
+            .route(
+                "/{conn_id}/accept-request",
+                web::post().to(accept_request::<W>),
+            )
+            .route("/{conn_id}", web::get().to(get_connection::<W>)),
+    );
+}
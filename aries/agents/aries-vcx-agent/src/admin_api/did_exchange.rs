@@ -0,0 +1,124 @@
+use actix_web::{web, HttpResponse};
+use aries_vcx::{
+    did_parser_nom::Did,
+    protocols::did_exchange::state_machine::requester::helpers::{
+        invitation_get_acceptable_did_exchange_version, invitation_get_first_did_service,
+    },
+};
+use aries_vcx_wallet::wallet::base_wallet::BaseWallet;
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use super::{
+    records::{did_exchange_record, did_exchange_request_record},
+    AdminApi,
+};
+use crate::error::AgentResult;
+
+#[derive(Deserialize)]
+struct CreateRequestQuery {
+    their_public_did: String,
+}
+
+impl<W: BaseWallet> AdminApi<W> {
+    fn did_exchange_record(
+        &self,
+        thread_id: &str,
+        invitation_id: Option<String>,
+        my_did: String,
+    ) -> AgentResult<Value> {
+        let state = self.agent.did_exchange().get_state(thread_id)?;
+        let mut record = did_exchange_record(thread_id, &state);
+        record["invitation_msg_id"] = json!(invitation_id);
+        record["my_did"] = json!(my_did);
+        Ok(record)
+    }
+}
+
+async fn accept_invitation<W: BaseWallet + 'static>(
+    api: web::Data<AdminApi<W>>,
+    path: web::Path<String>,
+) -> AgentResult<HttpResponse> {
+    let invitation_id = path.into_inner();
+    let invitation = api.agent.out_of_band().get_invitation(&invitation_id)?;
+    let version = invitation_get_acceptable_did_exchange_version(&invitation)?;
+    let their_did: Did = invitation_get_first_did_service(&invitation)?;
+    let (thread_id, invitation_id, my_did) = api
+        .agent
+        .did_exchange()
+        .handle_msg_invitation(their_did.to_string(), Some(invitation_id), version)
+        .await?;
+    let record = api.did_exchange_record(&thread_id, invitation_id, my_did)?;
+    Ok(HttpResponse::Ok().json(record))
+}
+
+async fn create_request<W: BaseWallet + 'static>(
+    api: web::Data<AdminApi<W>>,
+    query: web::Query<CreateRequestQuery>,
+) -> AgentResult<HttpResponse> {
+    let (thread_id, invitation_id, my_did) = api
+        .agent
+        .did_exchange()
+        .handle_msg_invitation(query.their_public_did.clone(), None, Default::default())
+        .await?;
+    let record = api.did_exchange_record(&thread_id, invitation_id, my_did)?;
+    Ok(HttpResponse::Ok().json(record))
+}
+
+async fn accept_request<W: BaseWallet + 'static>(
+    api: web::Data<AdminApi<W>>,
+    path: web::Path<String>,
+) -> AgentResult<HttpResponse> {
+    let (request, recipient_verkey) = api.take_pending_request(&path)?;
+    let invitation = match request
+        .inner()
+        .decorators
+        .thread
+        .as_ref()
+        .and_then(|thread| thread.pthid.as_ref())
+    {
+        Some(pthid) => Some(api.agent.out_of_band().get_invitation(pthid)?),
+        None => None,
+    };
+    let (thread_id, invitation_id, my_did, their_did) = api
+        .agent
+        .did_exchange()
+        .handle_msg_request(request, recipient_verkey, invitation)
+        .await?;
+    api.agent
+        .did_exchange()
+        .send_response(thread_id.clone())
+        .await?;
+    let mut record = api.did_exchange_record(&thread_id, invitation_id, my_did)?;
+    record["their_did"] = json!(their_did);
+    Ok(HttpResponse::Ok().json(record))
+}
+
+/// Drops a received request without responding to it
+async fn reject_request<W: BaseWallet + 'static>(
+    api: web::Data<AdminApi<W>>,
+    path: web::Path<String>,
+) -> AgentResult<HttpResponse> {
+    let thread_id = path.into_inner();
+    api.take_pending_request(&thread_id)?;
+    let mut record = did_exchange_request_record(&thread_id);
+    record["state"] = json!("abandoned");
+    record["rfc23_state"] = json!("abandoned");
+    Ok(HttpResponse::Ok().json(record))
+}
+
+pub(super) fn config<W: BaseWallet + 'static>(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/didexchange")
+            .route("/create-request", web::post().to(create_request::<W>))
+            .route(
+                "/{invi_msg_id}/accept-invitation",
+                web::post().to(accept_invitation::<W>),
+            )
+            .route(
+                "/{conn_id}/accept-request",
+                web::post().to(accept_request::<W>),
+            )
+            .route("/{conn_id}/reject", web::post().to(reject_request::<W>)),
+    );
+}
@@ -0,0 +1,25 @@
+use actix_web::{http::StatusCode, HttpResponse, ResponseError};
+use serde_json::json;
+
+use crate::error::{AgentError, AgentErrorKind};
+
+impl ResponseError for AgentError {
+    fn status_code(&self) -> StatusCode {
+        match self.kind {
+            AgentErrorKind::NotFound => StatusCode::NOT_FOUND,
+            AgentErrorKind::Unauthorized => StatusCode::UNAUTHORIZED,
+            AgentErrorKind::InvalidArguments | AgentErrorKind::SerializationError => {
+                StatusCode::BAD_REQUEST
+            }
+            AgentErrorKind::InvalidState | AgentErrorKind::CredDefAlreadyCreated => {
+                StatusCode::CONFLICT
+            }
+            _ => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    fn error_response(&self) -> HttpResponse {
+        error!("Admin API request failed: {}", self);
+        HttpResponse::build(self.status_code()).json(json!({ "reason": self.to_string() }))
+    }
+}
@@ -0,0 +1,187 @@
+use actix_web::{web, HttpResponse};
+use anoncreds_types::data_types::identifiers::cred_def_id::CredentialDefinitionId;
+use aries_vcx::{
+    handlers::util::OfferInfo,
+    messages::msg_fields::protocols::cred_issuance::{
+        common::CredentialAttr,
+        v1::{
+            propose_credential::{ProposeCredentialV1, ProposeCredentialV1Content},
+            CredentialPreviewV1,
+        },
+    },
+};
+use aries_vcx_wallet::wallet::base_wallet::BaseWallet;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use super::{
+    records::{holder_record, issuer_record},
+    AdminApi,
+};
+use crate::{error::AgentResult, ProtocolVersion};
+
+#[derive(Deserialize)]
+struct CredentialPreview {
+    attributes: Vec<CredentialAttr>,
+}
+
+#[derive(Deserialize)]
+struct SendProposal {
+    connection_id: String,
+    cred_def_id: String,
+    schema_id: String,
+    credential_proposal: CredentialPreview,
+    comment: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct SendOffer {
+    connection_id: String,
+    cred_def_id: String,
+    credential_preview: CredentialPreview,
+}
+
+impl<W: BaseWallet> AdminApi<W> {
+    /// Credential exchanges are looked up among those where the agent issues first
+    fn credential_exchange_response(&self, thread_id: &str) -> AgentResult<HttpResponse> {
+        let record = if self.agent.issuer().exists_by_id(thread_id) {
+            let issuer = self.agent.issuer();
+            issuer_record(
+                thread_id,
+                &issuer.get_connection_id(thread_id)?,
+                issuer.get_state(thread_id)?,
+            )
+        } else {
+            let holder = self.agent.holder();
+            holder_record(
+                thread_id,
+                &holder.get_connection_id(thread_id)?,
+                holder.get_state(thread_id)?,
+            )
+        };
+        Ok(HttpResponse::Ok().json(record))
+    }
+
+    /// Offers a credential issued with the latest revocation registry of the credential
+    /// definition, if there is any
+    fn offer_info(
+        &self,
+        cred_def_id: &str,
+        attributes: &[CredentialAttr],
+    ) -> AgentResult<OfferInfo> {
+        let rev_reg_id = self
+            .agent
+            .rev_regs()
+            .find_by_cred_def_id(cred_def_id)?
+            .pop();
+        let tails_file = rev_reg_id
+            .as_deref()
+            .map(|rev_reg_id| self.agent.rev_regs().get_tails_dir(rev_reg_id))
+            .transpose()?;
+        Ok(OfferInfo {
+            credential_json: serde_json::to_string(attributes)?,
+            cred_def_id: CredentialDefinitionId::new(cred_def_id)?,
+            rev_reg_id,
+            tails_file,
+        })
+    }
+}
+
+async fn send_proposal<W: BaseWallet + 'static>(
+    api: web::Data<AdminApi<W>>,
+    body: web::Json<SendProposal>,
+) -> AgentResult<HttpResponse> {
+    let body = body.into_inner();
+    let content = ProposeCredentialV1Content {
+        comment: body.comment,
+        credential_proposal: CredentialPreviewV1::new(body.credential_proposal.attributes),
+        schema_id: body.schema_id,
+        cred_def_id: body.cred_def_id,
+    };
+    let proposal = ProposeCredentialV1::builder()
+        .id(Uuid::new_v4().to_string())
+        .content(content)
+        .build();
+    let thread_id = api
+        .agent
+        .holder()
+        .send_credential_proposal(&body.connection_id, proposal, ProtocolVersion::V1)
+        .await?;
+    api.credential_exchange_response(&thread_id)
+}
+
+async fn send_offer<W: BaseWallet + 'static>(
+    api: web::Data<AdminApi<W>>,
+    body: web::Json<SendOffer>,
+) -> AgentResult<HttpResponse> {
+    let offer_info = api.offer_info(&body.cred_def_id, &body.credential_preview.attributes)?;
+    let thread_id = api
+        .agent
+        .issuer()
+        .send_credential_offer(
+            None,
+            Some(body.connection_id.as_str()),
+            offer_info,
+            ProtocolVersion::V1,
+        )
+        .await?;
+    api.credential_exchange_response(&thread_id)
+}
+
+async fn send_offer_for_proposal<W: BaseWallet + 'static>(
+    api: web::Data<AdminApi<W>>,
+    path: web::Path<String>,
+) -> AgentResult<HttpResponse> {
+    let proposal = api.agent.issuer().get_proposal(&path)?;
+    let offer_info = api.offer_info(
+        &proposal.content.cred_def_id,
+        &proposal.content.credential_proposal.attributes,
+    )?;
+    let thread_id = api
+        .agent
+        .issuer()
+        .send_credential_offer(Some(path.as_str()), None, offer_info, ProtocolVersion::V1)
+        .await?;
+    api.credential_exchange_response(&thread_id)
+}
+
+async fn send_request<W: BaseWallet + 'static>(
+    api: web::Data<AdminApi<W>>,
+    path: web::Path<String>,
+) -> AgentResult<HttpResponse> {
+    api.agent.holder().send_credential_request(&path).await?;
+    api.credential_exchange_response(&path)
+}
+
+async fn issue<W: BaseWallet + 'static>(
+    api: web::Data<AdminApi<W>>,
+    path: web::Path<String>,
+) -> AgentResult<HttpResponse> {
+    api.agent.issuer().send_credential(&path).await?;
+    api.credential_exchange_response(&path)
+}
+
+async fn get_record<W: BaseWallet + 'static>(
+    api: web::Data<AdminApi<W>>,
+    path: web::Path<String>,
+) -> AgentResult<HttpResponse> {
+    api.credential_exchange_response(&path)
+}
+
+pub(super) fn config<W: BaseWallet + 'static>(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/issue-credential")
+            .route("/send-proposal", web::post().to(send_proposal::<W>))
+            .route("/send-offer", web::post().to(send_offer::<W>))
+            .route(
+                "/records/{cred_ex_id}/send-offer",
+                web::post().to(send_offer_for_proposal::<W>),
+            )
+            .route(
+                "/records/{cred_ex_id}/send-request",
+                web::post().to(send_request::<W>),
+            )
+            .route("/records/{cred_ex_id}/issue", web::post().to(issue::<W>))
+            .route("/records/{cred_ex_id}", web::get().to(get_record::<W>)),
+    );
+}
@@ -0,0 +1,185 @@
+//! REST API for controlling the agent from another process, modelled after the ACA-Py admin API.
+//!
+//! The endpoints and the records they return follow ACA-Py closely enough for existing ACA-Py
+//! controllers to be pointed at this agent, the spec is served at `/api/docs/openapi.yaml`.
+//! State changes of the protocols are posted to the configured webhook URLs.
+
+mod connections;
+mod did_exchange;
+mod error;
+mod issue_credential;
+mod out_of_band;
+mod pending_requests;
+mod present_proof;
+mod records;
+mod revocation;
+mod webhooks;
+
+use std::sync::Arc;
+
+use actix_web::{
+    body::MessageBody,
+    dev::{ServiceRequest, ServiceResponse},
+    middleware::{from_fn, Next},
+    web, HttpResponse,
+};
+use aries_vcx::messages::msg_fields::protocols::did_exchange::v1_x::request::AnyRequest;
+use aries_vcx_wallet::wallet::base_wallet::BaseWallet;
+use subtle::ConstantTimeEq;
+use tokio::sync::{
+    broadcast::{error::RecvError, Receiver},
+    mpsc::{self, error::TrySendError},
+};
+use url::Url;
+
+use self::{pending_requests::PendingRequests, webhooks::Webhooks};
+use crate::{
+    agent::Agent,
+    error::{AgentError, AgentErrorKind, AgentResult},
+    events::AgentEvent,
+};
+
+const OPENAPI_SPEC: &str = include_str!("openapi.yaml");
+
+/// Header carrying the API key, as with ACA-Py
+pub const API_KEY_HEADER: &str = "x-api-key";
+
+/// Most agent events waiting to be posted to the webhooks, further events are dropped
+const WEBHOOK_QUEUE_SIZE: usize = 1024;
+
+pub struct AdminApi<W> {
+    agent: Agent<W>,
+    pending_requests: Arc<PendingRequests>,
+    api_key: Option<String>,
+}
+
+impl<W: BaseWallet + 'static> AdminApi<W> {
+    /// Starts forwarding the agent events to `webhook_urls`, must be called from within the actix
+    /// runtime
+    pub fn new(agent: Agent<W>, webhook_urls: Vec<Url>) -> Self {
+        let pending_requests = Arc::new(PendingRequests::default());
+        let (sender, receiver) = mpsc::channel(WEBHOOK_QUEUE_SIZE);
+        actix_web::rt::spawn(forward_events(
+            agent.subscribe(),
+            sender,
+            pending_requests.clone(),
+        ));
+        actix_web::rt::spawn(post_webhooks(receiver, Webhooks::new(webhook_urls)));
+        Self {
+            agent,
+            pending_requests,
+            api_key: None,
+        }
+    }
+
+    /// Requires every admin endpoint request to carry `api_key` in the [`API_KEY_HEADER`]
+    /// header. The `/didcomm` endpoint stays open to other agents.
+    pub fn with_api_key(mut self, api_key: String) -> Self {
+        self.api_key = Some(api_key);
+        self
+    }
+
+    fn take_pending_request(&self, thread_id: &str) -> AgentResult<(AnyRequest, String)> {
+        self.pending_requests.take(thread_id)
+    }
+}
+
+/// Keeps the pending DID exchange requests up to date and hands the events over to the webhook
+/// task, so that slow controllers can't make this receiver lag behind the agent. Events the
+/// webhook task can't keep up with are dropped.
+async fn forward_events(
+    mut events: Receiver<AgentEvent>,
+    webhook_events: mpsc::Sender<AgentEvent>,
+    pending_requests: Arc<PendingRequests>,
+) {
+    loop {
+        let event = match events.recv().await {
+            Ok(event) => event,
+            Err(RecvError::Lagged(skipped)) => {
+                warn!("Admin API missed {} agent events", skipped);
+                continue;
+            }
+            Err(RecvError::Closed) => break,
+        };
+        if let AgentEvent::DidExchangeRequestReceived {
+            request,
+            recipient_verkey,
+        } = &event
+        {
+            match &request.inner().decorators.thread {
+                Some(thread) => {
+                    if let Err(err) = pending_requests.insert(
+                        thread.thid.clone(),
+                        request.clone(),
+                        recipient_verkey.clone(),
+                    ) {
+                        warn!("Unable to keep pending DID exchange request: {}", err);
+                    }
+                }
+                None => warn!("Ignoring DID exchange request without thread"),
+            }
+        }
+        match webhook_events.try_send(event) {
+            Ok(()) => {}
+            Err(TrySendError::Full(_)) => warn!("Webhook queue is full, dropping agent event"),
+            Err(TrySendError::Closed(_)) => break,
+        }
+    }
+}
+
+async fn post_webhooks(mut events: mpsc::Receiver<AgentEvent>, webhooks: Webhooks) {
+    while let Some(event) = events.recv().await {
+        webhooks.notify(&event).await;
+    }
+}
+
+/// Rejects requests not carrying the API key the [`AdminApi`] was configured with
+async fn require_api_key<W: BaseWallet + 'static>(
+    req: ServiceRequest,
+    next: Next<impl MessageBody + 'static>,
+) -> Result<ServiceResponse<impl MessageBody>, actix_web::Error> {
+    let api = req
+        .app_data::<web::Data<AdminApi<W>>>()
+        .ok_or_else(|| AgentError::from_msg(AgentErrorKind::InvalidState, "Missing admin API"))?;
+    if let Some(api_key) = &api.api_key {
+        let authorized = req
+            .headers()
+            .get(API_KEY_HEADER)
+            .is_some_and(|key| bool::from(key.as_bytes().ct_eq(api_key.as_bytes())));
+        if !authorized {
+            return Ok(req.error_response(AgentError::from_kind(AgentErrorKind::Unauthorized)));
+        }
+    }
+    Ok(next.call(req).await?.map_into_boxed_body())
+}
+
+async fn receive_message<W: BaseWallet + 'static>(
+    api: web::Data<AdminApi<W>>,
+    payload: web::Bytes,
+) -> AgentResult<HttpResponse> {
+    api.agent.receive_message(&payload).await?;
+    Ok(HttpResponse::Ok().finish())
+}
+
+async fn openapi_spec() -> HttpResponse {
+    HttpResponse::Ok()
+        .content_type("application/yaml")
+        .body(OPENAPI_SPEC)
+}
+
+/// Registers the admin API endpoints, along with the `/didcomm` endpoint receiving inbound
+/// messages, expecting [`AdminApi`] as app data
+pub fn config<W: BaseWallet + 'static>(cfg: &mut web::ServiceConfig) {
+    cfg.route("/didcomm", web::post().to(receive_message::<W>))
+        .service(
+            web::scope("")
+                .wrap(from_fn(require_api_key::<W>))
+                .route("/api/docs/openapi.yaml", web::get().to(openapi_spec))
+                .configure(connections::config::<W>)
+                .configure(out_of_band::config::<W>)
+                .configure(did_exchange::config::<W>)
+                .configure(issue_credential::config::<W>)
+                .configure(present_proof::config::<W>)
+                .configure(revocation::config::<W>),
+        );
+}
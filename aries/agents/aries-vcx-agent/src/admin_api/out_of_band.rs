@@ -0,0 +1,48 @@
+use actix_web::{web, HttpResponse};
+use aries_vcx::messages::{msg_fields::protocols::out_of_band::OutOfBand, AriesMessage};
+use aries_vcx_wallet::wallet::base_wallet::BaseWallet;
+use serde_json::json;
+
+use super::AdminApi;
+use crate::error::AgentResult;
+
+async fn create_invitation<W: BaseWallet + 'static>(
+    api: web::Data<AdminApi<W>>,
+) -> AgentResult<HttpResponse> {
+    let invitation = api.agent.out_of_band().create_invitation().await?;
+    let invitation_id = match &invitation {
+        AriesMessage::OutOfBand(OutOfBand::Invitation(invitation)) => Some(invitation.id.clone()),
+        _ => None,
+    };
+    Ok(HttpResponse::Ok().json(json!({
+        "invi_msg_id": invitation_id,
+        "invitation": invitation,
+        "state": "initial",
+    })))
+}
+
+async fn receive_invitation<W: BaseWallet + 'static>(
+    api: web::Data<AdminApi<W>>,
+    invitation: web::Json<AriesMessage>,
+) -> AgentResult<HttpResponse> {
+    let (invitation_id, connection_id) = api
+        .agent
+        .receive_oob_invitation(invitation.into_inner())
+        .await?;
+    Ok(HttpResponse::Ok().json(json!({
+        "invi_msg_id": invitation_id,
+        "state": "invitation-received",
+        "connection_id": connection_id,
+    })))
+}
+
+pub(super) fn config<W: BaseWallet + 'static>(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/out-of-band")
+            .route("/create-invitation", web::post().to(create_invitation::<W>))
+            .route(
+                "/receive-invitation",
+                web::post().to(receive_invitation::<W>),
+            ),
+    );
+}
@@ -0,0 +1,168 @@
+use std::{
+    collections::HashMap,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use aries_vcx::messages::msg_fields::protocols::did_exchange::v1_x::request::AnyRequest;
+
+use crate::error::{AgentError, AgentErrorKind, AgentResult};
+
+/// Most DID exchange requests kept awaiting acceptance, further requests are dropped
+const MAX_PENDING_REQUESTS: usize = 1000;
+/// Time after which requests neither accepted nor rejected are dropped
+const PENDING_REQUEST_TTL: Duration = Duration::from_secs(60 * 60);
+
+struct PendingRequest {
+    request: AnyRequest,
+    recipient_verkey: String,
+    received_at: Instant,
+}
+
+/// DID exchange requests awaiting acceptance through the API, by thread id, along with the
+/// verkey they were sent to. Anyone can send requests to the agent, so their number and age are
+/// bounded.
+pub(super) struct PendingRequests {
+    requests: Mutex<HashMap<String, PendingRequest>>,
+    capacity: usize,
+    ttl: Duration,
+}
+
+impl Default for PendingRequests {
+    fn default() -> Self {
+        Self::new(MAX_PENDING_REQUESTS, PENDING_REQUEST_TTL)
+    }
+}
+
+impl PendingRequests {
+    fn new(capacity: usize, ttl: Duration) -> Self {
+        Self {
+            requests: Mutex::new(HashMap::new()),
+            capacity,
+            ttl,
+        }
+    }
+
+    /// Keeps the request for acceptance, unless too many requests are already pending
+    pub(super) fn insert(
+        &self,
+        thread_id: String,
+        request: AnyRequest,
+        recipient_verkey: String,
+    ) -> AgentResult<()> {
+        let mut requests = self
+            .requests
+            .lock()
+            .map_err(|_| AgentError::from_kind(AgentErrorKind::LockError))?;
+        requests.retain(|_, pending| pending.received_at.elapsed() < self.ttl);
+        if !requests.contains_key(&thread_id) && requests.len() >= self.capacity {
+            return Err(AgentError::from_msg(
+                AgentErrorKind::InvalidState,
+                &format!("Too many pending DID exchange requests, dropping request {thread_id}"),
+            ));
+        }
+        requests.insert(
+            thread_id,
+            PendingRequest {
+                request,
+                recipient_verkey,
+                received_at: Instant::now(),
+            },
+        );
+        Ok(())
+    }
+
+    /// Removes the request from the pending ones, to be accepted or rejected
+    pub(super) fn take(&self, thread_id: &str) -> AgentResult<(AnyRequest, String)> {
+        self.requests
+            .lock()
+            .map_err(|_| AgentError::from_kind(AgentErrorKind::LockError))?
+            .remove(thread_id)
+            .filter(|pending| pending.received_at.elapsed() < self.ttl)
+            .map(|pending| (pending.request, pending.recipient_verkey))
+            .ok_or_else(|| {
+                AgentError::from_msg(
+                    AgentErrorKind::NotFound,
+                    &format!("No pending DID exchange request with thread id {thread_id}"),
+                )
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aries_vcx::messages::msg_fields::protocols::did_exchange::{
+        v1_0::request::Request,
+        v1_x::request::{RequestContent, RequestDecorators},
+    };
+
+    use super::*;
+
+    fn request() -> AnyRequest {
+        AnyRequest::V1_0(
+            Request::builder()
+                .id("request-id".to_owned())
+                .content(
+                    RequestContent::builder()
+                        .label("requester".to_owned())
+                        .goal_code(None)
+                        .goal(None)
+                        .did("did:peer:requester".to_owned())
+                        .did_doc(None)
+                        .build(),
+                )
+                .decorators(RequestDecorators::default())
+                .build(),
+        )
+    }
+
+    #[test]
+    fn test_take_removes_request() {
+        let pending = PendingRequests::default();
+        pending
+            .insert("thread-1".to_owned(), request(), "verkey".to_owned())
+            .unwrap();
+
+        let (_, recipient_verkey) = pending.take("thread-1").unwrap();
+        assert_eq!(recipient_verkey, "verkey");
+        assert_eq!(
+            pending.take("thread-1").unwrap_err().kind,
+            AgentErrorKind::NotFound
+        );
+    }
+
+    #[test]
+    fn test_requests_beyond_capacity_are_dropped() {
+        let pending = PendingRequests::new(2, PENDING_REQUEST_TTL);
+        pending
+            .insert("thread-1".to_owned(), request(), "verkey".to_owned())
+            .unwrap();
+        pending
+            .insert("thread-2".to_owned(), request(), "verkey".to_owned())
+            .unwrap();
+
+        assert!(pending
+            .insert("thread-3".to_owned(), request(), "verkey".to_owned())
+            .is_err());
+        pending.take("thread-1").unwrap();
+        pending
+            .insert("thread-3".to_owned(), request(), "verkey".to_owned())
+            .unwrap();
+    }
+
+    #[test]
+    fn test_expired_requests_are_evicted() {
+        let pending = PendingRequests::new(1, Duration::ZERO);
+        pending
+            .insert("thread-1".to_owned(), request(), "verkey".to_owned())
+            .unwrap();
+
+        assert_eq!(
+            pending.take("thread-1").unwrap_err().kind,
+            AgentErrorKind::NotFound
+        );
+        pending
+            .insert("thread-2".to_owned(), request(), "verkey".to_owned())
+            .unwrap();
+    }
+}
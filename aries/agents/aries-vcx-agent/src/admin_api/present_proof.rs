@@ -0,0 +1,139 @@
+use std::collections::HashMap;
+
+use actix_web::{web, HttpResponse};
+use anoncreds_types::data_types::messages::pres_request::{
+    AttributeInfo, NonRevokedInterval, PredicateInfo, PresentationRequestPayload,
+};
+use aries_vcx::protocols::proof_presentation::verifier::verification_status::PresentationVerificationStatus;
+use aries_vcx_anoncreds::anoncreds::base_anoncreds::BaseAnonCreds;
+use aries_vcx_wallet::wallet::base_wallet::BaseWallet;
+use serde::Deserialize;
+use serde_json::json;
+
+use super::{
+    records::{prover_record, verifier_record},
+    AdminApi,
+};
+use crate::{error::AgentResult, ProtocolVersion};
+
+#[derive(Deserialize)]
+struct ProofRequest {
+    #[serde(default = "default_proof_request_name")]
+    name: String,
+    #[serde(default)]
+    requested_attributes: HashMap<String, AttributeInfo>,
+    #[serde(default)]
+    requested_predicates: HashMap<String, PredicateInfo>,
+    non_revoked: Option<NonRevokedInterval>,
+}
+
+fn default_proof_request_name() -> String {
+    "proof-request".to_string()
+}
+
+#[derive(Deserialize)]
+struct SendRequest {
+    connection_id: String,
+    proof_request: ProofRequest,
+}
+
+impl<W: BaseWallet> AdminApi<W> {
+    /// Presentation exchanges are looked up among those where the agent verifies first
+    fn presentation_exchange_response(&self, thread_id: &str) -> AgentResult<HttpResponse> {
+        let record = if self.agent.verifier().exists_by_id(thread_id) {
+            let verifier = self.agent.verifier();
+            let mut record = verifier_record(
+                thread_id,
+                &verifier.get_connection_id(thread_id)?,
+                verifier.get_state(thread_id)?,
+            );
+            if verifier.has_received_presentation(thread_id)? {
+                record["state"] = json!("presentation_received");
+            }
+            let verified = match verifier.get_presentation_status(thread_id)? {
+                PresentationVerificationStatus::Valid => Some(true),
+                PresentationVerificationStatus::Invalid => Some(false),
+                PresentationVerificationStatus::Unavailable => None,
+            };
+            record["verified"] = json!(verified);
+            record
+        } else {
+            let prover = self.agent.prover();
+            prover_record(
+                thread_id,
+                &prover.get_connection_id(thread_id)?,
+                prover.get_state(thread_id)?,
+            )
+        };
+        Ok(HttpResponse::Ok().json(record))
+    }
+}
+
+async fn send_request<W: BaseWallet + 'static>(
+    api: web::Data<AdminApi<W>>,
+    body: web::Json<SendRequest>,
+) -> AgentResult<HttpResponse> {
+    let SendRequest {
+        connection_id,
+        proof_request,
+    } = body.into_inner();
+    let nonce = api.agent.anoncreds().generate_nonce().await?;
+    let request = PresentationRequestPayload::builder()
+        .name(proof_request.name)
+        .requested_attributes(proof_request.requested_attributes)
+        .requested_predicates(proof_request.requested_predicates)
+        .non_revoked(proof_request.non_revoked)
+        .nonce(nonce)
+        .build();
+    let thread_id = api
+        .agent
+        .verifier()
+        .send_proof_request(&connection_id, request.into_v1(), None, ProtocolVersion::V1)
+        .await?;
+    api.presentation_exchange_response(&thread_id)
+}
+
+async fn send_presentation<W: BaseWallet + 'static>(
+    api: web::Data<AdminApi<W>>,
+    path: web::Path<String>,
+) -> AgentResult<HttpResponse> {
+    api.agent
+        .prover()
+        .send_proof_prentation(&path, None)
+        .await?;
+    api.presentation_exchange_response(&path)
+}
+
+async fn verify_presentation<W: BaseWallet + 'static>(
+    api: web::Data<AdminApi<W>>,
+    path: web::Path<String>,
+) -> AgentResult<HttpResponse> {
+    api.agent
+        .verifier()
+        .verify_received_presentation(&path)
+        .await?;
+    api.presentation_exchange_response(&path)
+}
+
+async fn get_record<W: BaseWallet + 'static>(
+    api: web::Data<AdminApi<W>>,
+    path: web::Path<String>,
+) -> AgentResult<HttpResponse> {
+    api.presentation_exchange_response(&path)
+}
+
+pub(super) fn config<W: BaseWallet + 'static>(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/present-proof")
+            .route("/send-request", web::post().to(send_request::<W>))
+            .route(
+                "/records/{pres_ex_id}/send-presentation",
+                web::post().to(send_presentation::<W>),
+            )
+            .route(
+                "/records/{pres_ex_id}/verify-presentation",
+                web::post().to(verify_presentation::<W>),
+            )
+            .route("/records/{pres_ex_id}", web::get().to(get_record::<W>)),
+    );
+}
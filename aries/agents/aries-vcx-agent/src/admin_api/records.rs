@@ -0,0 +1,136 @@
+use aries_vcx::protocols::{
+    connection::{State, ThinState as ConnectionState},
+    did_exchange::state_machine::generic::ThinState as DidExchangeState,
+    issuance::{holder::state_machine::HolderState, issuer::state_machine::IssuerState},
+    proof_presentation::{
+        prover::state_machine::ProverState, verifier::state_machine::VerifierState,
+    },
+};
+use serde_json::{json, Value};
+
+// Records returned by the endpoints and posted to webhooks, shaped after the ACA-Py admin API
+// records, so that existing controllers need minimal changes to drive this agent
+
+pub(super) fn connection_record(connection_id: &str, state: ConnectionState) -> Value {
+    let (their_role, state) = match state {
+        ConnectionState::Inviter(state) => ("invitee", state),
+        ConnectionState::Invitee(state) => ("inviter", state),
+    };
+    let state = match state {
+        State::Initial | State::Invited => "invitation",
+        State::Requested => "request",
+        State::Responded => "response",
+        State::Completed => "active",
+    };
+    json!({
+        "connection_id": connection_id,
+        "connection_protocol": "connections/1.0",
+        "their_role": their_role,
+        "state": state,
+    })
+}
+
+pub(super) fn did_exchange_record(thread_id: &str, state: &DidExchangeState) -> Value {
+    let aca_py_state = match state {
+        DidExchangeState::RequestSent => "request",
+        DidExchangeState::ResponseSent => "response",
+        DidExchangeState::Completed => "active",
+        DidExchangeState::Abandoned => "abandoned",
+    };
+    json!({
+        "connection_id": thread_id,
+        "connection_protocol": "didexchange/1.x",
+        "state": aca_py_state,
+        "rfc23_state": state,
+    })
+}
+
+pub(super) fn did_exchange_request_record(thread_id: &str) -> Value {
+    json!({
+        "connection_id": thread_id,
+        "connection_protocol": "didexchange/1.x",
+        "their_role": "invitee",
+        "state": "request",
+        "rfc23_state": "request-received",
+    })
+}
+
+pub(super) fn issuer_record(thread_id: &str, connection_id: &str, state: IssuerState) -> Value {
+    let state = match state {
+        IssuerState::Initial => "initial",
+        IssuerState::ProposalReceived => "proposal_received",
+        IssuerState::OfferSet => "offer_sent",
+        IssuerState::RequestReceived => "request_received",
+        IssuerState::CredentialSet => "credential_issued",
+        IssuerState::Finished => "credential_acked",
+        IssuerState::Failed => "abandoned",
+    };
+    credential_exchange_record(thread_id, connection_id, "issuer", state)
+}
+
+pub(super) fn holder_record(thread_id: &str, connection_id: &str, state: HolderState) -> Value {
+    let state = match state {
+        HolderState::Initial => "initial",
+        HolderState::ProposalSet => "proposal_sent",
+        HolderState::OfferReceived => "offer_received",
+        HolderState::RequestSet => "request_sent",
+        HolderState::Finished => "credential_acked",
+        HolderState::Failed => "abandoned",
+    };
+    credential_exchange_record(thread_id, connection_id, "holder", state)
+}
+
+fn credential_exchange_record(
+    thread_id: &str,
+    connection_id: &str,
+    role: &str,
+    state: &str,
+) -> Value {
+    json!({
+        "credential_exchange_id": thread_id,
+        "thread_id": thread_id,
+        "connection_id": connection_id,
+        "role": role,
+        "state": state,
+    })
+}
+
+pub(super) fn prover_record(thread_id: &str, connection_id: &str, state: ProverState) -> Value {
+    let state = match state {
+        ProverState::Initial => "initial",
+        ProverState::PresentationProposalSent => "proposal_sent",
+        ProverState::PresentationRequestReceived | ProverState::PresentationPrepared => {
+            "request_received"
+        }
+        ProverState::PresentationSent => "presentation_sent",
+        ProverState::Finished => "presentation_acked",
+        ProverState::PresentationPreparationFailed | ProverState::Failed => "abandoned",
+    };
+    presentation_exchange_record(thread_id, connection_id, "prover", state)
+}
+
+pub(super) fn verifier_record(thread_id: &str, connection_id: &str, state: VerifierState) -> Value {
+    let state = match state {
+        VerifierState::Initial | VerifierState::PresentationRequestSet => "initial",
+        VerifierState::PresentationProposalReceived => "proposal_received",
+        VerifierState::PresentationRequestSent => "request_sent",
+        VerifierState::Finished => "done",
+        VerifierState::Failed => "abandoned",
+    };
+    presentation_exchange_record(thread_id, connection_id, "verifier", state)
+}
+
+fn presentation_exchange_record(
+    thread_id: &str,
+    connection_id: &str,
+    role: &str,
+    state: &str,
+) -> Value {
+    json!({
+        "presentation_exchange_id": thread_id,
+        "thread_id": thread_id,
+        "connection_id": connection_id,
+        "role": role,
+        "state": state,
+    })
+}
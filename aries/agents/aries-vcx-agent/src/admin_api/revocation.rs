@@ -0,0 +1,72 @@
+use actix_web::{web, HttpResponse};
+use aries_vcx_wallet::wallet::base_wallet::BaseWallet;
+use serde::Deserialize;
+use serde_json::json;
+
+use super::AdminApi;
+use crate::error::AgentResult;
+
+#[derive(Deserialize)]
+struct Revoke {
+    rev_reg_id: String,
+    cred_rev_id: String,
+    #[serde(default)]
+    publish: bool,
+}
+
+#[derive(Deserialize)]
+struct PublishRevocations {
+    rev_reg_id: String,
+}
+
+async fn revoke<W: BaseWallet + 'static>(
+    api: web::Data<AdminApi<W>>,
+    body: web::Json<Revoke>,
+) -> AgentResult<HttpResponse> {
+    let rev_regs = api.agent.rev_regs();
+    rev_regs
+        .revoke_credential_locally(&body.rev_reg_id, &body.cred_rev_id)
+        .await?;
+    if body.publish {
+        rev_regs.publish_local_revocations(&body.rev_reg_id).await?;
+    }
+    Ok(HttpResponse::Ok().json(json!({})))
+}
+
+async fn publish_revocations<W: BaseWallet + 'static>(
+    api: web::Data<AdminApi<W>>,
+    body: web::Json<PublishRevocations>,
+) -> AgentResult<HttpResponse> {
+    api.agent
+        .rev_regs()
+        .publish_local_revocations(&body.rev_reg_id)
+        .await?;
+    Ok(HttpResponse::Ok().json(json!({})))
+}
+
+async fn get_revocation_info<W: BaseWallet + 'static>(
+    api: web::Data<AdminApi<W>>,
+    path: web::Path<String>,
+) -> AgentResult<HttpResponse> {
+    let issuer = api.agent.issuer();
+    Ok(HttpResponse::Ok().json(json!({
+        "cred_ex_id": path.as_str(),
+        "rev_reg_id": issuer.get_rev_reg_id(&path)?,
+        "cred_rev_id": issuer.get_rev_id(&path)?.to_string(),
+    })))
+}
+
+pub(super) fn config<W: BaseWallet + 'static>(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/revocation")
+            .route("/revoke", web::post().to(revoke::<W>))
+            .route(
+                "/publish-revocations",
+                web::post().to(publish_revocations::<W>),
+            )
+            .route(
+                "/credential-record/{cred_ex_id}",
+                web::get().to(get_revocation_info::<W>),
+            ),
+    );
+}
@@ -0,0 +1,100 @@
+use serde_json::Value;
+use url::Url;
+
+use super::records;
+use crate::events::AgentEvent;
+
+/// Posts records of protocols changing state to the configured controllers, at
+/// `{webhook_url}/topic/{topic}/` as ACA-Py does
+#[derive(Clone)]
+pub(super) struct Webhooks {
+    client: reqwest::Client,
+    urls: Vec<Url>,
+}
+
+impl Webhooks {
+    pub(super) fn new(urls: Vec<Url>) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            urls,
+        }
+    }
+
+    pub(super) async fn notify(&self, event: &AgentEvent) {
+        let Some((topic, payload)) = to_webhook(event) else {
+            return;
+        };
+        for url in &self.urls {
+            let target = format!("{}/topic/{}/", url.as_str().trim_end_matches('/'), topic);
+            let res = self
+                .client
+                .post(&target)
+                .json(&payload)
+                .send()
+                .await
+                .and_then(|res| res.error_for_status());
+            if let Err(err) = res {
+                warn!("Failed to post webhook to {}: {}", target, err);
+            }
+        }
+    }
+}
+
+fn to_webhook(event: &AgentEvent) -> Option<(&'static str, Value)> {
+    let webhook = match event {
+        AgentEvent::ConnectionStateChanged {
+            connection_id,
+            state,
+        } => (
+            "connections",
+            records::connection_record(connection_id, *state),
+        ),
+        AgentEvent::DidExchangeStateChanged { thread_id, state } => (
+            "connections",
+            records::did_exchange_record(thread_id, state),
+        ),
+        AgentEvent::DidExchangeRequestReceived { request, .. } => {
+            let thread_id = request.inner().decorators.thread.as_ref()?.thid.as_str();
+            (
+                "connections",
+                records::did_exchange_request_record(thread_id),
+            )
+        }
+        AgentEvent::HolderStateChanged {
+            thread_id,
+            connection_id,
+            state,
+        } => (
+            "issue_credential",
+            records::holder_record(thread_id, connection_id, *state),
+        ),
+        AgentEvent::IssuerStateChanged {
+            thread_id,
+            connection_id,
+            state,
+        } => (
+            "issue_credential",
+            records::issuer_record(thread_id, connection_id, *state),
+        ),
+        AgentEvent::ProverStateChanged {
+            thread_id,
+            connection_id,
+            state,
+        } => (
+            "present_proof",
+            records::prover_record(thread_id, connection_id, *state),
+        ),
+        AgentEvent::VerifierStateChanged {
+            thread_id,
+            connection_id,
+            state,
+        } => (
+            "present_proof",
+            records::verifier_record(thread_id, connection_id, *state),
+        ),
+        AgentEvent::ProblemReportReceived { problem_report } => {
+            ("problem_report", serde_json::to_value(problem_report).ok()?)
+        }
+    };
+    Some(webhook)
+}
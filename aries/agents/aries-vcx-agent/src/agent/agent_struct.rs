@@ -1,45 +1,57 @@
-use std::sync::Arc;
+use std::sync::{Arc, RwLock};
 
-use aries_vcx_anoncreds::anoncreds::anoncreds::Anoncreds;
-use aries_vcx_ledger::ledger::indy_vdr_ledger::{DefaultIndyLedgerRead, DefaultIndyLedgerWrite};
+use aries_vcx_anoncreds::anoncreds::{anoncreds::Anoncreds, base_anoncreds::BaseAnonCreds};
+use aries_vcx_ledger::ledger::{
+    base_ledger::{AnoncredsLedgerRead, AnoncredsLedgerWrite},
+    indy_vdr_ledger::{DefaultIndyLedgerRead, DefaultIndyLedgerWrite},
+};
 use aries_vcx_wallet::wallet::base_wallet::BaseWallet;
-
-use crate::handlers::{
-    connection::ServiceConnections, credential_definition::ServiceCredentialDefinitions,
-    did_exchange::DidcommHandlerDidExchange, holder::ServiceCredentialsHolder,
-    issuer::ServiceCredentialsIssuer, out_of_band::ServiceOutOfBand, prover::ServiceProver,
-    revocation_registry::ServiceRevocationRegistries, schema::ServiceSchemas,
-    verifier::ServiceVerifier,
+use tokio::sync::broadcast::Receiver;
+
+use crate::{
+    agent::auto_accept::AutoAcceptPolicies,
+    events::{AgentEvent, EventBus},
+    handlers::{
+        connection::ServiceConnections, credential_definition::ServiceCredentialDefinitions,
+        did_exchange::DidcommHandlerDidExchange, holder::ServiceCredentialsHolder,
+        issuer::ServiceCredentialsIssuer, out_of_band::ServiceOutOfBand, prover::ServiceProver,
+        revocation_registry::ServiceRevocationRegistries, schema::ServiceSchemas,
+        verifier::ServiceVerifier,
+    },
 };
 
-pub struct Agent<W> {
+/// Agent reading and writing AnonCreds objects through `LR` and `LW`, which default to an Indy
+/// ledger, see [`Agent::build`] to run with other ledgers.
+pub struct Agent<W, LR = DefaultIndyLedgerRead, LW = DefaultIndyLedgerWrite, A = Anoncreds> {
     pub(super) issuer_did: String,
-    pub(super) ledger_read: Arc<DefaultIndyLedgerRead>,
-    pub(super) ledger_write: Arc<DefaultIndyLedgerWrite>,
-    pub(super) anoncreds: Anoncreds,
+    pub(super) ledger_read: Arc<LR>,
+    pub(super) ledger_write: Arc<LW>,
+    pub(super) anoncreds: Arc<A>,
     pub(super) wallet: Arc<W>,
     pub(super) connections: Arc<ServiceConnections<W>>,
-    pub(super) schemas: Arc<ServiceSchemas<W>>,
-    pub(super) cred_defs: Arc<ServiceCredentialDefinitions<W>>,
-    pub(super) rev_regs: Arc<ServiceRevocationRegistries<W>>,
-    pub(super) holder: Arc<ServiceCredentialsHolder<W>>,
-    pub(super) issuer: Arc<ServiceCredentialsIssuer<W>>,
-    pub(super) verifier: Arc<ServiceVerifier<W>>,
-    pub(super) prover: Arc<ServiceProver<W>>,
+    pub(super) schemas: Arc<ServiceSchemas<W, LR, LW, A>>,
+    pub(super) cred_defs: Arc<ServiceCredentialDefinitions<W, LR, LW, A>>,
+    pub(super) rev_regs: Arc<ServiceRevocationRegistries<W, LR, LW, A>>,
+    pub(super) holder: Arc<ServiceCredentialsHolder<W, LR, A>>,
+    pub(super) issuer: Arc<ServiceCredentialsIssuer<W, A>>,
+    pub(super) verifier: Arc<ServiceVerifier<W, LR, A>>,
+    pub(super) prover: Arc<ServiceProver<W, LR, A>>,
     pub(super) out_of_band: Arc<ServiceOutOfBand<W>>,
     pub(super) did_exchange: Arc<DidcommHandlerDidExchange<W>>,
+    pub(super) events: EventBus,
+    pub(super) auto_accept: Arc<RwLock<AutoAcceptPolicies>>,
 }
 
 // Note: We do this manually, otherwise compiler is requesting us to implement Clone for generic
 // type W,       which is not in fact needed - W is wrapped in Arc. Underlying W has no reason to be
 // cloned.
-impl<W> Clone for Agent<W> {
+impl<W, LR, LW, A> Clone for Agent<W, LR, LW, A> {
     fn clone(&self) -> Self {
         Self {
             issuer_did: self.issuer_did.clone(),
             ledger_read: self.ledger_read.clone(),
             ledger_write: self.ledger_write.clone(),
-            anoncreds: self.anoncreds,
+            anoncreds: self.anoncreds.clone(),
             wallet: self.wallet.clone(),
             connections: self.connections.clone(),
             schemas: self.schemas.clone(),
@@ -51,20 +63,28 @@ impl<W> Clone for Agent<W> {
             prover: self.prover.clone(),
             out_of_band: self.out_of_band.clone(),
             did_exchange: self.did_exchange.clone(),
+            events: self.events.clone(),
+            auto_accept: self.auto_accept.clone(),
         }
     }
 }
 
-impl<T: BaseWallet> Agent<T> {
-    pub fn ledger_read(&self) -> &DefaultIndyLedgerRead {
+impl<T, LR, LW, A> Agent<T, LR, LW, A>
+where
+    T: BaseWallet,
+    LR: AnoncredsLedgerRead,
+    LW: AnoncredsLedgerWrite,
+    A: BaseAnonCreds,
+{
+    pub fn ledger_read(&self) -> &LR {
         &self.ledger_read
     }
 
-    pub fn ledger_write(&self) -> &DefaultIndyLedgerWrite {
+    pub fn ledger_write(&self) -> &LW {
         &self.ledger_write
     }
 
-    pub fn anoncreds(&self) -> &Anoncreds {
+    pub fn anoncreds(&self) -> &A {
         &self.anoncreds
     }
 
@@ -88,35 +108,40 @@ impl<T: BaseWallet> Agent<T> {
         self.did_exchange.clone()
     }
 
-    pub fn schemas(&self) -> Arc<ServiceSchemas<T>> {
+    pub fn schemas(&self) -> Arc<ServiceSchemas<T, LR, LW, A>> {
         self.schemas.clone()
     }
 
-    pub fn cred_defs(&self) -> Arc<ServiceCredentialDefinitions<T>> {
+    pub fn cred_defs(&self) -> Arc<ServiceCredentialDefinitions<T, LR, LW, A>> {
         self.cred_defs.clone()
     }
 
-    pub fn rev_regs(&self) -> Arc<ServiceRevocationRegistries<T>> {
+    pub fn rev_regs(&self) -> Arc<ServiceRevocationRegistries<T, LR, LW, A>> {
         self.rev_regs.clone()
     }
 
-    pub fn issuer(&self) -> Arc<ServiceCredentialsIssuer<T>> {
+    pub fn issuer(&self) -> Arc<ServiceCredentialsIssuer<T, A>> {
         self.issuer.clone()
     }
 
-    pub fn holder(&self) -> Arc<ServiceCredentialsHolder<T>> {
+    pub fn holder(&self) -> Arc<ServiceCredentialsHolder<T, LR, A>> {
         self.holder.clone()
     }
 
-    pub fn verifier(&self) -> Arc<ServiceVerifier<T>> {
+    pub fn verifier(&self) -> Arc<ServiceVerifier<T, LR, A>> {
         self.verifier.clone()
     }
 
-    pub fn prover(&self) -> Arc<ServiceProver<T>> {
+    pub fn prover(&self) -> Arc<ServiceProver<T, LR, A>> {
         self.prover.clone()
     }
 
     pub fn public_did(&self) -> &str {
         self.did_exchange.public_did()
     }
+
+    /// Subscribes to the events published from now on as protocols progress
+    pub fn subscribe(&self) -> Receiver<AgentEvent> {
+        self.events.subscribe()
+    }
 }
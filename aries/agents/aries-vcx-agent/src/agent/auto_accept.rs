@@ -0,0 +1,325 @@
+use std::collections::HashMap;
+
+use aries_vcx::{
+    handlers::util::AnyInvitation,
+    messages::{
+        decorators::attachment::{Attachment, AttachmentType},
+        msg_fields::protocols::{
+            cred_issuance::v1::request_credential::RequestCredentialV1,
+            did_exchange::v1_x::request::AnyRequest,
+        },
+        AriesMessage,
+    },
+    protocols::did_exchange::state_machine::requester::helpers::{
+        invitation_get_acceptable_did_exchange_version, invitation_get_first_did_service,
+    },
+};
+use aries_vcx_anoncreds::anoncreds::base_anoncreds::BaseAnonCreds;
+use aries_vcx_ledger::ledger::base_ledger::{AnoncredsLedgerRead, AnoncredsLedgerWrite};
+use aries_vcx_wallet::wallet::base_wallet::BaseWallet;
+use base64::{engine::general_purpose, Engine};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{agent::agent_struct::Agent, error::*};
+
+/// Protocol steps the agent takes on its own, rather than waiting for the application to trigger
+/// them. Every step is manual by default.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AutoAcceptPolicy {
+    /// Send a connection request as soon as a connection invitation is received, or a DID
+    /// exchange request for an out-of-band invitation
+    pub invitations: bool,
+    /// Send a connection or DID exchange response as soon as a request is received
+    pub connection_requests: bool,
+    /// Request offered credentials
+    pub credential_offers: bool,
+    /// Issue requested credentials, if the request is for the credential definition offered
+    pub credential_requests: bool,
+    /// Present, if exactly one credential in the wallet matches each requested attribute and
+    /// predicate
+    pub presentation_requests: bool,
+    /// Verify received presentations, instead of keeping them to be verified through
+    /// `ServiceVerifier::verify_received_presentation`
+    pub presentations: bool,
+}
+
+impl AutoAcceptPolicy {
+    /// Takes every step on its own
+    pub fn all() -> Self {
+        Self {
+            invitations: true,
+            connection_requests: true,
+            credential_offers: true,
+            credential_requests: true,
+            presentation_requests: true,
+            presentations: true,
+        }
+    }
+}
+
+/// The policy applying to all connections, unless overridden for a particular one
+#[derive(Clone, Debug, Default)]
+pub(super) struct AutoAcceptPolicies {
+    default: AutoAcceptPolicy,
+    by_connection: HashMap<String, AutoAcceptPolicy>,
+}
+
+impl AutoAcceptPolicies {
+    /// The policy of the first of the connections it is overridden for
+    fn get<'a>(&self, connection_ids: impl IntoIterator<Item = &'a str>) -> AutoAcceptPolicy {
+        connection_ids
+            .into_iter()
+            .find_map(|connection_id| self.by_connection.get(connection_id))
+            .copied()
+            .unwrap_or(self.default)
+    }
+}
+
+impl<W, LR, LW, A> Agent<W, LR, LW, A>
+where
+    W: BaseWallet,
+    LR: AnoncredsLedgerRead,
+    LW: AnoncredsLedgerWrite,
+    A: BaseAnonCreds,
+{
+    pub fn set_auto_accept_policy(&self, policy: AutoAcceptPolicy) -> AgentResult<()> {
+        self.auto_accept
+            .write()
+            .map_err(|_| AgentError::from_kind(AgentErrorKind::LockError))?
+            .default = policy;
+        Ok(())
+    }
+
+    /// Overrides the default policy for the connection, which for invitations created by this
+    /// agent is known as soon as the invitation is
+    pub fn set_connection_auto_accept_policy(
+        &self,
+        connection_id: &str,
+        policy: AutoAcceptPolicy,
+    ) -> AgentResult<()> {
+        self.auto_accept
+            .write()
+            .map_err(|_| AgentError::from_kind(AgentErrorKind::LockError))?
+            .by_connection
+            .insert(connection_id.to_string(), policy);
+        Ok(())
+    }
+
+    pub fn remove_connection_auto_accept_policy(&self, connection_id: &str) -> AgentResult<()> {
+        self.auto_accept
+            .write()
+            .map_err(|_| AgentError::from_kind(AgentErrorKind::LockError))?
+            .by_connection
+            .remove(connection_id);
+        Ok(())
+    }
+
+    /// The policy applying to the connection, or to invitations and DID exchange requests not
+    /// yet associated with one if `None`
+    pub fn auto_accept_policy(&self, connection_id: Option<&str>) -> AgentResult<AutoAcceptPolicy> {
+        Ok(self
+            .auto_accept
+            .read()
+            .map_err(|_| AgentError::from_kind(AgentErrorKind::LockError))?
+            .get(connection_id))
+    }
+
+    /// Receives a connection invitation, sending a request right away if invitations are
+    /// accepted automatically. Returns the id of the connection.
+    pub async fn receive_connection_invitation(
+        &self,
+        invitation: AnyInvitation,
+    ) -> AgentResult<String> {
+        let connection_id = self.connections.receive_invitation(invitation).await?;
+        if self.auto_accept_policy(None)?.invitations {
+            info!(
+                "Agent::receive_connection_invitation >> auto-accepting invitation of connection \
+                 {}",
+                connection_id
+            );
+            self.connections.send_request(&connection_id).await?;
+        }
+        Ok(connection_id)
+    }
+
+    /// Receives an out-of-band invitation, sending a DID exchange request right away if
+    /// invitations are accepted automatically. Returns the id of the invitation, along with the
+    /// thread id of the DID exchange if started.
+    pub async fn receive_oob_invitation(
+        &self,
+        invitation: AriesMessage,
+    ) -> AgentResult<(String, Option<String>)> {
+        let invitation_id = self.out_of_band.receive_invitation(invitation).await?;
+        if !self.auto_accept_policy(None)?.invitations {
+            return Ok((invitation_id, None));
+        }
+        info!(
+            "Agent::receive_oob_invitation >> auto-accepting invitation {}",
+            invitation_id
+        );
+        let invitation = self.out_of_band.get_invitation(&invitation_id)?;
+        let version = invitation_get_acceptable_did_exchange_version(&invitation)?;
+        let their_did = invitation_get_first_did_service(&invitation)?;
+        let (thread_id, ..) = self
+            .did_exchange
+            .handle_msg_invitation(their_did.to_string(), Some(invitation_id.clone()), version)
+            .await?;
+        Ok((invitation_id, Some(thread_id)))
+    }
+
+    pub(super) async fn auto_respond_connection_request(
+        &self,
+        connection_id: &str,
+    ) -> AgentResult<()> {
+        if !self
+            .auto_accept_policy(Some(connection_id))?
+            .connection_requests
+        {
+            return Ok(());
+        }
+        info!(
+            "Agent::auto_respond_connection_request >> sending response on connection {}",
+            connection_id
+        );
+        self.connections.send_response(connection_id).await
+    }
+
+    /// Returns the request back if it is not accepted automatically, for the application to
+    /// decide upon. The connection is identified by the thread of the exchange, the policy set
+    /// for the invitation answered applies unless overridden for the thread.
+    pub(super) async fn auto_respond_did_exchange_request(
+        &self,
+        request: AnyRequest,
+        recipient_verkey: String,
+    ) -> AgentResult<Option<(AnyRequest, String)>> {
+        let thread = request.inner().decorators.thread.as_ref();
+        let thread_id = thread.map(|thread| thread.thid.as_str());
+        let pthid = thread.and_then(|thread| thread.pthid.as_deref());
+        let policy = self
+            .auto_accept
+            .read()
+            .map_err(|_| AgentError::from_kind(AgentErrorKind::LockError))?
+            .get(thread_id.into_iter().chain(pthid));
+        if !policy.connection_requests {
+            return Ok(Some((request, recipient_verkey)));
+        }
+        let invitation = match pthid {
+            Some(pthid) => Some(self.out_of_band.get_invitation(pthid)?),
+            None => None,
+        };
+        let (thread_id, ..) = self
+            .did_exchange
+            .handle_msg_request(request, recipient_verkey, invitation)
+            .await?;
+        info!(
+            "Agent::auto_respond_did_exchange_request >> sending response on thread {}",
+            thread_id
+        );
+        self.did_exchange.send_response(thread_id).await?;
+        Ok(None)
+    }
+
+    pub(super) async fn auto_respond_credential_offer(&self, thread_id: &str) -> AgentResult<()> {
+        let connection_id = self.holder.get_connection_id(thread_id)?;
+        if !self
+            .auto_accept_policy(Some(&connection_id))?
+            .credential_offers
+        {
+            return Ok(());
+        }
+        info!(
+            "Agent::auto_respond_credential_offer >> requesting credential offered on thread {}",
+            thread_id
+        );
+        self.holder.send_credential_request(thread_id).await?;
+        Ok(())
+    }
+
+    pub(super) async fn auto_respond_credential_request(
+        &self,
+        thread_id: &str,
+        request: &RequestCredentialV1,
+    ) -> AgentResult<()> {
+        let connection_id = self.issuer.get_connection_id(thread_id)?;
+        if !self
+            .auto_accept_policy(Some(&connection_id))?
+            .credential_requests
+        {
+            return Ok(());
+        }
+        let offer = self.issuer.get_credential_offer(thread_id)?;
+        let offered = attachment_json(&offer.content.offers_attach)?;
+        let requested = attachment_json(&request.content.requests_attach)?;
+        if offered["cred_def_id"] != requested["cred_def_id"] {
+            info!(
+                "Agent::auto_respond_credential_request >> not issuing on thread {}, request for \
+                 {} does not match offer for {}",
+                thread_id, requested["cred_def_id"], offered["cred_def_id"]
+            );
+            return Ok(());
+        }
+        info!(
+            "Agent::auto_respond_credential_request >> issuing credential on thread {}",
+            thread_id
+        );
+        self.issuer.send_credential(thread_id).await
+    }
+
+    pub(super) async fn auto_respond_presentation_request(
+        &self,
+        thread_id: &str,
+    ) -> AgentResult<()> {
+        let connection_id = self.prover.get_connection_id(thread_id)?;
+        if !self
+            .auto_accept_policy(Some(&connection_id))?
+            .presentation_requests
+        {
+            return Ok(());
+        }
+        if !self.prover.has_unambiguous_credentials(thread_id).await? {
+            info!(
+                "Agent::auto_respond_presentation_request >> not presenting on thread {}, some \
+                 referent is matched by no or several credentials",
+                thread_id
+            );
+            return Ok(());
+        }
+        info!(
+            "Agent::auto_respond_presentation_request >> sending presentation on thread {}",
+            thread_id
+        );
+        self.prover.send_proof_prentation(thread_id, None).await
+    }
+
+    /// Whether the received presentation is to be verified right away
+    pub(super) fn auto_verify_presentation(&self, thread_id: &str) -> AgentResult<bool> {
+        let connection_id = self.verifier.get_connection_id(thread_id)?;
+        let verify = self.auto_accept_policy(Some(&connection_id))?.presentations;
+        info!(
+            "Agent::auto_verify_presentation >> presentation on thread {} {}",
+            thread_id,
+            if verify {
+                "verified right away"
+            } else {
+                "kept for verification"
+            }
+        );
+        Ok(verify)
+    }
+}
+
+fn attachment_json(attachments: &[Attachment]) -> AgentResult<Value> {
+    match attachments.first().map(|attach| &attach.data.content) {
+        Some(AttachmentType::Base64(encoded)) => {
+            let decoded = general_purpose::STANDARD.decode(encoded).map_err(|err| {
+                AgentError::from_msg(AgentErrorKind::SerializationError, &err.to_string())
+            })?;
+            Ok(serde_json::from_slice(&decoded)?)
+        }
+        _ => Err(AgentError::from_msg(
+            AgentErrorKind::SerializationError,
+            "Attachment is not base 64 encoded JSON",
+        )),
+    }
+}
@@ -0,0 +1,308 @@
+use aries_vcx::{
+    handlers::{issuance, proof_presentation},
+    messages::{
+        msg_fields::protocols::{
+            connection::{request::Request, Connection},
+            cred_issuance::{v1::CredentialIssuanceV1, CredentialIssuance},
+            did_exchange::{
+                v1_0::DidExchangeV1_0, v1_1::DidExchangeV1_1, v1_x::request::AnyRequest,
+                DidExchange,
+            },
+            notification::Notification,
+            present_proof::{v1::PresentProofV1, PresentProof},
+            trust_ping::TrustPing,
+        },
+        AriesMessage,
+    },
+    utils::encryption_envelope::EncryptionEnvelope,
+};
+use aries_vcx_anoncreds::anoncreds::base_anoncreds::BaseAnonCreds;
+use aries_vcx_ledger::ledger::base_ledger::{AnoncredsLedgerRead, AnoncredsLedgerWrite};
+use aries_vcx_wallet::wallet::base_wallet::BaseWallet;
+use public_key::Key;
+
+use crate::{agent::agent_struct::Agent, error::*, events::AgentEvent, ProtocolVersion};
+
+impl<W, LR, LW, A> Agent<W, LR, LW, A>
+where
+    W: BaseWallet,
+    LR: AnoncredsLedgerRead,
+    LW: AnoncredsLedgerWrite,
+    A: BaseAnonCreds,
+{
+    /// Unpacks an inbound DIDComm envelope and dispatches the message it carries, see
+    /// [`Agent::handle_message`]
+    pub async fn receive_message(&self, payload: &[u8]) -> AgentResult<()> {
+        let (message, sender_vk, recipient_vk) =
+            EncryptionEnvelope::unpack_aries_msg(self.wallet.as_ref(), payload, &None).await?;
+        self.handle_message(message, sender_vk, recipient_vk).await
+    }
+
+    /// Routes an unpacked message to the service handling its protocol. Messages starting a new
+    /// protocol are attributed to the connection the sender's key belongs to, the others are
+    /// matched with existing protocols by thread id. Issue Credential 2.0 and Present Proof 2.0
+    /// messages in the AnonCreds formats are handled by the 1.0 state machines.
+    ///
+    /// Steps following the message are taken right away as far as the
+    /// [`AutoAcceptPolicy`](crate::AutoAcceptPolicy) of the connection allows. DID exchange
+    /// requests not accepted automatically are published as
+    /// [`AgentEvent::DidExchangeRequestReceived`] for the application to decide upon.
+    pub async fn handle_message(
+        &self,
+        message: AriesMessage,
+        sender_vk: Option<Key>,
+        recipient_vk: Key,
+    ) -> AgentResult<()> {
+        info!("Agent::handle_message >> message: {}", message);
+        match message {
+            AriesMessage::Notification(Notification::Ack(ack)) => {
+                self.connections.process_ack(ack).await?;
+            }
+            AriesMessage::Notification(Notification::ProblemReport(problem_report)) => {
+                warn!("Received problem report: {:?}", problem_report);
+                self.events
+                    .publish(AgentEvent::ProblemReportReceived { problem_report });
+            }
+            AriesMessage::TrustPing(TrustPing::Ping(ping)) => {
+                let connection_id = self.connection_id(sender_vk.as_ref())?;
+                self.connections
+                    .process_trust_ping(ping, &connection_id)
+                    .await?;
+            }
+            AriesMessage::Connection(msg) => self.handle_connection_msg(msg).await?,
+            AriesMessage::DidRotate(msg) => {
+                let connection_id = self.connection_id(sender_vk.as_ref())?;
+                self.connections
+                    .process_did_rotate(&connection_id, msg)
+                    .await?;
+            }
+            AriesMessage::CredentialIssuance(CredentialIssuance::V1(msg)) => {
+                self.handle_issuance_msg(msg, ProtocolVersion::V1, sender_vk.as_ref())
+                    .await?
+            }
+            AriesMessage::CredentialIssuance(CredentialIssuance::V2(msg)) => {
+                let msg = issuance::v2_bridge::to_v1(msg)?;
+                self.handle_issuance_msg(msg, ProtocolVersion::V2, sender_vk.as_ref())
+                    .await?
+            }
+            AriesMessage::PresentProof(PresentProof::V1(msg)) => {
+                self.handle_presentation_msg(msg, ProtocolVersion::V1, sender_vk.as_ref())
+                    .await?
+            }
+            AriesMessage::PresentProof(PresentProof::V2(msg)) => {
+                let msg = proof_presentation::v2_bridge::to_v1(msg)?;
+                self.handle_presentation_msg(msg, ProtocolVersion::V2, sender_vk.as_ref())
+                    .await?
+            }
+            AriesMessage::DidExchange(msg) => {
+                self.handle_did_exchange_msg(msg, recipient_vk.base58())
+                    .await?
+            }
+            m => {
+                warn!("Received message of unsupported type: {}", m);
+            }
+        };
+        Ok(())
+    }
+
+    fn connection_id(&self, sender_vk: Option<&Key>) -> AgentResult<String> {
+        let sender_vk = sender_vk.ok_or_else(|| {
+            AgentError::from_msg(
+                AgentErrorKind::InvalidArguments,
+                "Unable to find the connection of an anoncrypted message",
+            )
+        })?;
+        self.connections.get_by_sender_vk(sender_vk.base58())
+    }
+
+    async fn handle_connection_msg(&self, msg: Connection) -> AgentResult<()> {
+        match msg {
+            Connection::Request(request) => {
+                let connection_id = self.connection_id_of_request(&request)?;
+                self.connections
+                    .accept_request(&connection_id, request)
+                    .await?;
+                self.auto_respond_connection_request(&connection_id).await?;
+            }
+            Connection::Response(response) => {
+                let thread_id = response.decorators.thread.thid.clone();
+                self.connections
+                    .accept_response(&thread_id, response)
+                    .await?;
+            }
+            m => {
+                warn!("Received unexpected connection protocol message: {:?}", m);
+            }
+        };
+        Ok(())
+    }
+
+    /// Requests answering a single-use invitation continue its thread, while those answering
+    /// other invitations reference it as the parent thread
+    fn connection_id_of_request(&self, request: &Request) -> AgentResult<String> {
+        let thread_id = request
+            .decorators
+            .thread
+            .as_ref()
+            .map_or(request.id.clone(), |thread| thread.thid.clone());
+        if self.connections.exists_by_id(&thread_id) {
+            return Ok(thread_id);
+        }
+        request
+            .decorators
+            .thread
+            .as_ref()
+            .and_then(|thread| thread.pthid.clone())
+            .ok_or_else(|| {
+                AgentError::from_msg(
+                    AgentErrorKind::InvalidState,
+                    "Connection request does not reference a known invitation",
+                )
+            })
+    }
+
+    async fn handle_issuance_msg(
+        &self,
+        msg: CredentialIssuanceV1,
+        version: ProtocolVersion,
+        sender_vk: Option<&Key>,
+    ) -> AgentResult<()> {
+        match msg {
+            CredentialIssuanceV1::OfferCredential(offer) => {
+                let connection_id = self.connection_id(sender_vk)?;
+                let thread_id = self
+                    .holder
+                    .create_from_offer(&connection_id, offer, version)
+                    .await?;
+                self.auto_respond_credential_offer(&thread_id).await?;
+            }
+            CredentialIssuanceV1::ProposeCredential(proposal) => {
+                let connection_id = self.connection_id(sender_vk)?;
+                self.issuer
+                    .accept_proposal(&connection_id, &proposal, version)
+                    .await?;
+            }
+            CredentialIssuanceV1::RequestCredential(request) => {
+                let thread_id = request
+                    .decorators
+                    .thread
+                    .as_ref()
+                    .map_or(request.id.clone(), |thread| thread.thid.clone());
+                self.issuer
+                    .process_credential_request(&thread_id, request.clone())
+                    .await?;
+                self.auto_respond_credential_request(&thread_id, &request)
+                    .await?;
+            }
+            CredentialIssuanceV1::IssueCredential(credential) => {
+                let thread_id = credential.decorators.thread.thid.clone();
+                self.holder
+                    .process_credential(&thread_id, credential)
+                    .await?;
+            }
+            CredentialIssuanceV1::Ack(ack) => {
+                let thread_id = ack.decorators.thread.thid.clone();
+                self.issuer.process_credential_ack(&thread_id, ack).await?;
+            }
+            m => {
+                warn!("Received unexpected issuance protocol message: {:?}", m);
+            }
+        };
+        Ok(())
+    }
+
+    async fn handle_presentation_msg(
+        &self,
+        msg: PresentProofV1,
+        version: ProtocolVersion,
+        sender_vk: Option<&Key>,
+    ) -> AgentResult<()> {
+        match msg {
+            PresentProofV1::RequestPresentation(request) => {
+                let connection_id = self.connection_id(sender_vk)?;
+                let thread_id = self
+                    .prover
+                    .create_from_request(&connection_id, request, version)
+                    .await?;
+                self.auto_respond_presentation_request(&thread_id).await?;
+            }
+            PresentProofV1::Presentation(presentation) => {
+                let thread_id = presentation.decorators.thread.thid.clone();
+                if self.auto_verify_presentation(&thread_id)? {
+                    self.verifier
+                        .verify_presentation(&thread_id, presentation)
+                        .await?;
+                } else {
+                    self.verifier
+                        .receive_presentation(&thread_id, presentation)
+                        .await?;
+                }
+            }
+            PresentProofV1::Ack(ack) => {
+                let thread_id = ack.decorators.thread.thid.clone();
+                self.prover
+                    .process_presentation_ack(&thread_id, ack)
+                    .await?;
+            }
+            m => {
+                warn!("Received unexpected presentation protocol message: {:?}", m);
+            }
+        };
+        Ok(())
+    }
+
+    async fn handle_did_exchange_msg(
+        &self,
+        msg: DidExchange,
+        recipient_verkey: String,
+    ) -> AgentResult<()> {
+        match msg {
+            DidExchange::V1_0(DidExchangeV1_0::Request(request)) => {
+                self.handle_did_exchange_request(AnyRequest::V1_0(request), recipient_verkey)
+                    .await?;
+            }
+            DidExchange::V1_1(DidExchangeV1_1::Request(request)) => {
+                self.handle_did_exchange_request(AnyRequest::V1_1(request), recipient_verkey)
+                    .await?;
+            }
+            DidExchange::V1_0(DidExchangeV1_0::Response(response)) => {
+                self.did_exchange
+                    .handle_msg_response(response.into())
+                    .await?;
+            }
+            DidExchange::V1_1(DidExchangeV1_1::Response(response)) => {
+                self.did_exchange
+                    .handle_msg_response(response.into())
+                    .await?;
+            }
+            DidExchange::V1_0(DidExchangeV1_0::Complete(complete))
+            | DidExchange::V1_1(DidExchangeV1_1::Complete(complete)) => {
+                self.did_exchange.handle_msg_complete(complete).await?;
+            }
+            DidExchange::V1_0(DidExchangeV1_0::ProblemReport(problem_report))
+            | DidExchange::V1_1(DidExchangeV1_1::ProblemReport(problem_report)) => {
+                self.did_exchange
+                    .receive_problem_report(problem_report)
+                    .await?;
+            }
+        };
+        Ok(())
+    }
+
+    async fn handle_did_exchange_request(
+        &self,
+        request: AnyRequest,
+        recipient_verkey: String,
+    ) -> AgentResult<()> {
+        if let Some((request, recipient_verkey)) = self
+            .auto_respond_did_exchange_request(request, recipient_verkey)
+            .await?
+        {
+            self.events.publish(AgentEvent::DidExchangeRequestReceived {
+                request,
+                recipient_verkey,
+            });
+        }
+        Ok(())
+    }
+}
@@ -14,11 +14,16 @@ use aries_vcx_anoncreds::{
     anoncreds::{anoncreds::Anoncreds, base_anoncreds::BaseAnonCreds},
     errors::error::VcxAnoncredsError,
 };
-use aries_vcx_ledger::ledger::indy_vdr_ledger::{
-    build_ledger_components, DefaultIndyLedgerRead, VcxPoolConfig,
+use aries_vcx_ledger::ledger::{
+    base_ledger::{AnoncredsLedgerRead, AnoncredsLedgerWrite, IndyLedgerRead},
+    indy_vdr_ledger::{build_ledger_components, DefaultIndyLedgerRead, VcxPoolConfig},
 };
 use aries_vcx_wallet::wallet::{
-    askar::{askar_wallet_config::AskarWalletConfig, key_method::KeyMethod, AskarWallet},
+    askar::{
+        askar_wallet_config::AskarWalletConfig,
+        key_method::{ArgonLevel, AskarKdfMethod, KeyMethod},
+        AskarWallet,
+    },
     base_wallet::{issuer_config::IssuerConfig, BaseWallet, ManageWallet},
 };
 use did_peer::resolver::PeerDidResolver;
@@ -31,7 +36,8 @@ use uuid::Uuid;
 
 use crate::{
     agent::agent_struct::Agent,
-    error::AgentResult,
+    error::{AgentError, AgentErrorKind, AgentResult},
+    events::EventBus,
     handlers::{
         connection::ServiceConnections, credential_definition::ServiceCredentialDefinitions,
         did_exchange::DidcommHandlerDidExchange, holder::ServiceCredentialsHolder,
@@ -62,20 +68,56 @@ pub async fn build_askar_wallet(
     );
     let wallet = config_wallet.create_wallet().await.unwrap();
     let config_issuer = wallet.configure_issuer(&issuer_seed).await.unwrap();
+    create_link_secret(&wallet).await.unwrap();
+
+    (wallet, config_issuer)
+}
 
-    let anoncreds = Anoncreds;
+/// Opens the wallet kept as profile `wallet_name` of the store at `db_url`, creating it on first
+/// use, so that the agent state survives restarts. `wallet_kdf` is one of `RAW`, `ARGON2I_INT`
+/// or `ARGON2I_MOD`.
+pub async fn open_askar_wallet(
+    db_url: &str,
+    wallet_config: &WalletInitConfig,
+) -> AgentResult<AskarWallet> {
+    let key_method = match wallet_config.wallet_kdf.as_str() {
+        "RAW" => KeyMethod::RawKey,
+        "ARGON2I_INT" => KeyMethod::DeriveKey {
+            inner: AskarKdfMethod::Argon2i {
+                inner: ArgonLevel::Interactive,
+            },
+        },
+        "ARGON2I_MOD" => KeyMethod::DeriveKey {
+            inner: AskarKdfMethod::Argon2i {
+                inner: ArgonLevel::Moderate,
+            },
+        },
+        kdf => {
+            return Err(AgentError::from_msg(
+                AgentErrorKind::InvalidArguments,
+                &format!("Unsupported wallet key derivation method {kdf}"),
+            ))
+        }
+    };
+    let config_wallet = AskarWalletConfig::new(
+        db_url,
+        key_method,
+        &wallet_config.wallet_key,
+        &wallet_config.wallet_name,
+    );
+    let wallet = AskarWallet::create_or_open_profile(&config_wallet).await?;
+    create_link_secret(&wallet).await?;
+    Ok(wallet)
+}
 
-    if let Err(err) = anoncreds
-        .prover_create_link_secret(&wallet, &DEFAULT_LINK_SECRET_ALIAS.to_string())
+async fn create_link_secret(wallet: &impl BaseWallet) -> AgentResult<()> {
+    match Anoncreds
+        .prover_create_link_secret(wallet, &DEFAULT_LINK_SECRET_ALIAS.to_string())
         .await
     {
-        match err {
-            VcxAnoncredsError::DuplicationMasterSecret(_) => {} // ignore
-            _ => panic!("{}", err),
-        };
+        Ok(()) | Err(VcxAnoncredsError::DuplicationMasterSecret(_)) => Ok(()),
+        Err(err) => Err(err.into()),
     }
-
-    (wallet, config_issuer)
 }
 
 impl<W: BaseWallet> Agent<W> {
@@ -111,6 +153,8 @@ impl<W: BaseWallet> Agent<W> {
         Ok(public_did)
     }
 
+    /// Initializes an agent backed by the Indy ledger described by the genesis file, resolving
+    /// `did:sov` and `did:peer` DIDs
     pub async fn initialize(
         genesis_path: String,
         wallet: Arc<W>,
@@ -124,7 +168,6 @@ impl<W: BaseWallet> Agent<W> {
             genesis_file_path: genesis_path,
         };
 
-        let anoncreds = Anoncreds;
         let (ledger_read, ledger_write) = build_ledger_components(vcx_pool_config.clone()).unwrap();
 
         let ledger_read = Arc::new(ledger_read);
@@ -139,62 +182,119 @@ impl<W: BaseWallet> Agent<W> {
                 .register_resolver("sov".into(), did_sov_resolver),
         );
 
-        let connections = Arc::new(ServiceConnections::new(
+        Agent::build(
+            wallet,
             ledger_read.clone(),
+            ledger_write,
+            Arc::new(Anoncreds),
+            Some(ledger_read),
+            did_resolver_registry,
+            service_endpoint,
+            issuer_did,
+        )
+        .await
+    }
+}
+
+impl<W, LR, LW, A> Agent<W, LR, LW, A>
+where
+    W: BaseWallet,
+    LR: AnoncredsLedgerRead,
+    LW: AnoncredsLedgerWrite,
+    A: BaseAnonCreds,
+{
+    /// Builds an agent on top of arbitrary AnonCreds ledgers and DID resolvers, such as a
+    /// `MultiLedgerAnoncredsRead` over cheqd. Agents which never publish can pass a
+    /// `ReadOnlyLedgerWrite` as the writer. The Indy ledger is only used by the connection
+    /// protocol to resolve public DIDs of invitations; without it, only invitations carrying
+    /// their keys and endpoint can be accepted.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn build(
+        wallet: Arc<W>,
+        ledger_read: Arc<LR>,
+        ledger_write: Arc<LW>,
+        anoncreds: Arc<A>,
+        indy_ledger_read: Option<Arc<dyn IndyLedgerRead>>,
+        did_resolver_registry: Arc<ResolverRegistry>,
+        service_endpoint: Url,
+        issuer_did: Did,
+    ) -> AgentResult<Self> {
+        let events = EventBus::default();
+
+        let connections = Arc::new(ServiceConnections::new(
+            indy_ledger_read,
             wallet.clone(),
+            did_resolver_registry.clone(),
             service_endpoint.clone(),
+            events.clone(),
         ));
         let did_exchange = Arc::new(DidcommHandlerDidExchange::new(
             wallet.clone(),
             did_resolver_registry,
             service_endpoint.clone(),
             issuer_did.to_string(),
+            events.clone(),
         ));
         let out_of_band = Arc::new(ServiceOutOfBand::new(wallet.clone(), service_endpoint));
         let schemas = Arc::new(ServiceSchemas::new(
             ledger_read.clone(),
             ledger_write.clone(),
-            anoncreds,
+            anoncreds.clone(),
             wallet.clone(),
             issuer_did.to_string(),
         ));
         let cred_defs = Arc::new(ServiceCredentialDefinitions::new(
             ledger_read.clone(),
             ledger_write.clone(),
-            anoncreds,
+            anoncreds.clone(),
             wallet.clone(),
         ));
         let rev_regs = Arc::new(ServiceRevocationRegistries::new(
             ledger_write.clone(),
             ledger_read.clone(),
-            anoncreds,
+            anoncreds.clone(),
             wallet.clone(),
             issuer_did.to_string(),
         ));
         let issuer = Arc::new(ServiceCredentialsIssuer::new(
-            anoncreds,
+            anoncreds.clone(),
             wallet.clone(),
             connections.clone(),
+            events.clone(),
         ));
         let holder = Arc::new(ServiceCredentialsHolder::new(
             ledger_read.clone(),
-            anoncreds,
+            anoncreds.clone(),
             wallet.clone(),
             connections.clone(),
+            events.clone(),
         ));
         let verifier = Arc::new(ServiceVerifier::new(
             ledger_read.clone(),
-            anoncreds,
+            anoncreds.clone(),
             wallet.clone(),
             connections.clone(),
+            events.clone(),
         ));
         let prover = Arc::new(ServiceProver::new(
             ledger_read.clone(),
-            anoncreds,
+            anoncreds.clone(),
             wallet.clone(),
             connections.clone(),
+            events.clone(),
         ));
 
+        connections.load().await?;
+        did_exchange.load().await?;
+        out_of_band.load().await?;
+        schemas.load().await?;
+        cred_defs.load().await?;
+        rev_regs.load().await?;
+        issuer.load().await?;
+        holder.load().await?;
+        verifier.load().await?;
+        prover.load().await?;
+
         Ok(Self {
             ledger_read,
             ledger_write,
@@ -211,6 +311,8 @@ impl<W: BaseWallet> Agent<W> {
             verifier,
             prover,
             issuer_did: issuer_did.to_string(),
+            events,
+            auto_accept: Default::default(),
         })
     }
 }
@@ -1,5 +1,8 @@
 mod agent_struct;
+mod auto_accept;
+mod dispatcher;
 mod init;
 
 pub use agent_struct::Agent;
-pub use init::{build_askar_wallet, WalletInitConfig};
+pub use auto_accept::AutoAcceptPolicy;
+pub use init::{build_askar_wallet, open_askar_wallet, WalletInitConfig};
@@ -0,0 +1,191 @@
+use std::{
+    io::{Error, ErrorKind},
+    net::IpAddr,
+    sync::Arc,
+};
+
+use actix_web::{middleware, web, App, HttpServer};
+use aries_vcx_agent::{
+    admin_api::{self, AdminApi},
+    aries_vcx::{
+        aries_vcx_wallet::{
+            errors::error::VcxWalletError,
+            wallet::{
+                askar::AskarWallet,
+                base_wallet::{
+                    issuer_config::IssuerConfig, record::Record, record_category::RecordCategory,
+                    record_wallet::RecordWallet, BaseWallet,
+                },
+            },
+        },
+        did_parser_nom::Did,
+    },
+    open_askar_wallet, Agent, AgentResult, AutoAcceptPolicy, WalletInitConfig,
+};
+use clap::Parser;
+use log::info;
+use url::Url;
+
+/// Wallet record keeping the DID set up on the ledger, reused on restarts
+const ISSUER_DID_RECORD: &str = "admin_api:issuer_did";
+
+/// Runs an agent controlled through the admin REST API
+#[derive(Parser)]
+struct Opts {
+    /// Address to bind to, binding to other than loopback addresses requires an API key
+    #[clap(long, env = "ADMIN_API_HOST", default_value = "127.0.0.1")]
+    host: IpAddr,
+    #[clap(short, long, env = "ADMIN_API_PORT", default_value = "8031")]
+    port: u16,
+    /// Key admin requests must carry in the `x-api-key` header
+    #[clap(long, env = "ADMIN_API_KEY")]
+    api_key: Option<String>,
+    /// Endpoint other agents send messages to, `/didcomm` of this server unless proxied
+    #[clap(short, long, env = "ADMIN_API_ENDPOINT")]
+    endpoint: Url,
+    #[clap(short, long, env = "GENESIS_FILE")]
+    genesis_file: String,
+    /// Askar store holding the wallet, such as `sqlite://wallet.db` or a `postgres://` URL
+    #[clap(
+        long,
+        env = "WALLET_DB_URL",
+        default_value = "sqlite://admin_api_wallet.db"
+    )]
+    wallet_db_url: String,
+    /// Profile of the store used as wallet
+    #[clap(long, env = "WALLET_NAME", default_value = "admin_api")]
+    wallet_name: String,
+    #[clap(long, env = "WALLET_KEY", hide_env_values = true)]
+    wallet_key: String,
+    /// Derivation of the store key from the wallet key: RAW, ARGON2I_INT or ARGON2I_MOD
+    #[clap(long, env = "WALLET_KDF", default_value = "ARGON2I_MOD")]
+    wallet_kdf: String,
+    /// Seed of the DID used to write to the ledger
+    #[clap(long, env = "ISSUER_SEED")]
+    issuer_seed: String,
+    /// The seed belongs to a trustee, which registers a new issuer DID rather than issuing itself
+    #[clap(long)]
+    trustee: bool,
+    /// Controller URL receiving state changes, may be repeated
+    #[clap(long = "webhook-url", env = "WEBHOOK_URL", value_delimiter = ',')]
+    webhook_urls: Vec<Url>,
+    /// Send requests for received invitations
+    #[clap(long)]
+    auto_accept_invites: bool,
+    /// Send responses to received connection and DID exchange requests
+    #[clap(long)]
+    auto_accept_requests: bool,
+    /// Request offered credentials
+    #[clap(long)]
+    auto_respond_credential_offer: bool,
+    /// Issue requested credentials
+    #[clap(long)]
+    auto_respond_credential_request: bool,
+    /// Present when a single credential matches each requested attribute and predicate
+    #[clap(long)]
+    auto_respond_presentation_request: bool,
+    /// Verify presentations as soon as received
+    #[clap(long)]
+    auto_verify_presentation: bool,
+}
+
+#[actix_web::main]
+async fn main() -> std::io::Result<()> {
+    env_logger::init_from_env(
+        env_logger::Env::default().filter_or(env_logger::DEFAULT_FILTER_ENV, "info"),
+    );
+    let opts = Opts::parse();
+
+    if !opts.host.is_loopback() && opts.api_key.is_none() {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            format!(
+                "Refusing to expose the admin API on {} without an API key",
+                opts.host
+            ),
+        ));
+    }
+
+    let wallet_config = WalletInitConfig {
+        wallet_name: opts.wallet_name.clone(),
+        wallet_key: opts.wallet_key.clone(),
+        wallet_kdf: opts.wallet_kdf.clone(),
+    };
+    let wallet = open_askar_wallet(&opts.wallet_db_url, &wallet_config)
+        .await
+        .expect("Failed to open the wallet");
+    let wallet = Arc::new(wallet);
+    let issuer_did = setup_issuer(&wallet, &opts)
+        .await
+        .expect("Failed to set up the issuer DID on the ledger");
+    let agent = Agent::initialize(opts.genesis_file, wallet, opts.endpoint, issuer_did)
+        .await
+        .expect("Failed to initialize the agent");
+    agent
+        .set_auto_accept_policy(AutoAcceptPolicy {
+            invitations: opts.auto_accept_invites,
+            connection_requests: opts.auto_accept_requests,
+            credential_offers: opts.auto_respond_credential_offer,
+            credential_requests: opts.auto_respond_credential_request,
+            presentation_requests: opts.auto_respond_presentation_request,
+            presentations: opts.auto_verify_presentation,
+        })
+        .expect("Failed to set the auto-accept policy");
+
+    let mut admin_api = AdminApi::new(agent, opts.webhook_urls);
+    if let Some(api_key) = opts.api_key {
+        admin_api = admin_api.with_api_key(api_key);
+    }
+    let admin_api = web::Data::new(admin_api);
+    info!("Starting admin API on {}:{}", opts.host, opts.port);
+    HttpServer::new(move || {
+        App::new()
+            .wrap(middleware::Logger::default())
+            .app_data(admin_api.clone())
+            .configure(admin_api::config::<AskarWallet>)
+    })
+    .bind((opts.host, opts.port))?
+    .run()
+    .await
+}
+
+/// Writes the endpoint of the issuer DID to the ledger, creating the DID from the seed on first
+/// start only, as the wallet already holds it afterwards
+async fn setup_issuer(wallet: &Arc<AskarWallet>, opts: &Opts) -> AgentResult<Did> {
+    match wallet
+        .get_record(RecordCategory::AgentState, ISSUER_DID_RECORD)
+        .await
+    {
+        Ok(record) => {
+            let issuer_did: Did = record.value().parse()?;
+            Agent::setup_ledger(
+                opts.genesis_file.clone(),
+                wallet.clone(),
+                opts.endpoint.clone(),
+                issuer_did,
+                false,
+            )
+            .await
+        }
+        Err(VcxWalletError::RecordNotFound(_)) => {
+            let IssuerConfig { institution_did } =
+                wallet.configure_issuer(&opts.issuer_seed).await?;
+            let issuer_did = Agent::setup_ledger(
+                opts.genesis_file.clone(),
+                wallet.clone(),
+                opts.endpoint.clone(),
+                institution_did.parse()?,
+                opts.trustee,
+            )
+            .await?;
+            let record = Record::builder()
+                .category(RecordCategory::AgentState)
+                .name(ISSUER_DID_RECORD.to_owned())
+                .value(issuer_did.to_string())
+                .build();
+            wallet.add_record(record).await?;
+            Ok(issuer_did)
+        }
+        Err(err) => Err(err.into()),
+    }
+}
@@ -5,7 +5,9 @@ use aries_vcx::{
     errors::error::{AriesVcxError, AriesVcxErrorKind},
     protocols::did_exchange::state_machine::generic::GenericDidExchange,
 };
+use aries_vcx_anoncreds::errors::error::VcxAnoncredsError;
 use aries_vcx_ledger::errors::error::VcxLedgerError;
+use aries_vcx_wallet::errors::error::VcxWalletError;
 use did_resolver_sov::did_resolver::did_doc::schema::utils::error::DidDocumentLookupError;
 
 use crate::error::*;
@@ -38,6 +40,25 @@ impl From<VcxLedgerError> for AgentError {
     }
 }
 
+impl From<VcxAnoncredsError> for AgentError {
+    fn from(err: VcxAnoncredsError) -> Self {
+        let kind = AgentErrorKind::GenericAriesVcxError;
+        let message = format!("VcxAnoncredsError; err: {:?}", err.to_string());
+        AgentError { message, kind }
+    }
+}
+
+impl From<VcxWalletError> for AgentError {
+    fn from(err: VcxWalletError) -> Self {
+        let kind = match err {
+            VcxWalletError::RecordNotFound(_) => AgentErrorKind::NotFound,
+            _ => AgentErrorKind::GenericAriesVcxError,
+        };
+        let message = format!("VcxWalletError; err: {:?}", err.to_string());
+        AgentError { message, kind }
+    }
+}
+
 impl From<DidDocumentBuilderError> for AgentError {
     fn from(err: DidDocumentBuilderError) -> Self {
         let kind = AgentErrorKind::GenericAriesVcxError;
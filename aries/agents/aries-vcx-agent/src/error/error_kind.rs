@@ -20,4 +20,6 @@ pub enum AgentErrorKind {
     PostMessageFailed,
     #[error("Invalid state")]
     InvalidState,
+    #[error("Missing or invalid API key")]
+    Unauthorized,
 }
@@ -0,0 +1,120 @@
+use aries_vcx::{
+    messages::msg_fields::protocols::{
+        did_exchange::v1_x::request::AnyRequest,
+        notification::problem_report::NotificationProblemReport,
+    },
+    protocols::{
+        connection::ThinState as ConnectionState,
+        did_exchange::state_machine::generic::ThinState as DidExchangeState,
+        issuance::{holder::state_machine::HolderState, issuer::state_machine::IssuerState},
+        proof_presentation::{
+            prover::state_machine::ProverState, verifier::state_machine::VerifierState,
+        },
+    },
+};
+use tokio::sync::broadcast::{self, Receiver, Sender};
+
+const EVENT_BUS_CAPACITY: usize = 256;
+
+/// Events published by the agent as protocols progress
+#[derive(Clone, Debug)]
+pub enum AgentEvent {
+    ConnectionStateChanged {
+        connection_id: String,
+        state: ConnectionState,
+    },
+    DidExchangeStateChanged {
+        thread_id: String,
+        state: DidExchangeState,
+    },
+    /// A DID exchange request was received and awaits a decision by the application, see
+    /// `DidcommHandlerDidExchange::handle_msg_request`
+    DidExchangeRequestReceived {
+        request: AnyRequest,
+        recipient_verkey: String,
+    },
+    HolderStateChanged {
+        thread_id: String,
+        connection_id: String,
+        state: HolderState,
+    },
+    IssuerStateChanged {
+        thread_id: String,
+        connection_id: String,
+        state: IssuerState,
+    },
+    ProverStateChanged {
+        thread_id: String,
+        connection_id: String,
+        state: ProverState,
+    },
+    VerifierStateChanged {
+        thread_id: String,
+        connection_id: String,
+        state: VerifierState,
+    },
+    ProblemReportReceived {
+        problem_report: NotificationProblemReport,
+    },
+}
+
+/// Broadcasts [`AgentEvent`]s to every subscriber. Events published while there are no
+/// subscribers are dropped, and subscribers lagging more than the channel capacity behind miss
+/// the oldest events.
+#[derive(Clone)]
+pub struct EventBus {
+    sender: Sender<AgentEvent>,
+}
+
+impl Default for EventBus {
+    fn default() -> Self {
+        Self {
+            sender: broadcast::channel(EVENT_BUS_CAPACITY).0,
+        }
+    }
+}
+
+impl EventBus {
+    pub fn subscribe(&self) -> Receiver<AgentEvent> {
+        self.sender.subscribe()
+    }
+
+    pub(crate) fn publish(&self, event: AgentEvent) {
+        trace!("EventBus::publish >> {:?}", event);
+        // fails only if there are no subscribers, in which case there is nobody to notify
+        let _ = self.sender.send(event);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aries_vcx::protocols::connection::State;
+
+    use super::*;
+
+    fn connection_event(connection_id: &str) -> AgentEvent {
+        AgentEvent::ConnectionStateChanged {
+            connection_id: connection_id.to_owned(),
+            state: ConnectionState::Inviter(State::Invited),
+        }
+    }
+
+    #[test]
+    fn test_events_reach_every_subscriber() {
+        let events = EventBus::default();
+        events.publish(connection_event("unobserved"));
+        let mut first = events.subscribe();
+        let mut second = events.subscribe();
+        events.publish(connection_event("observed"));
+
+        for subscriber in [&mut first, &mut second] {
+            match subscriber.try_recv().unwrap() {
+                AgentEvent::ConnectionStateChanged { connection_id, .. } => {
+                    assert_eq!(connection_id, "observed")
+                }
+                event => panic!("Unexpected event {event:?}"),
+            }
+            assert!(subscriber.try_recv().is_err());
+        }
+    }
+}
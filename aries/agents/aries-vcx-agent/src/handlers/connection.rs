@@ -1,10 +1,12 @@
 use std::sync::{Arc, Mutex};
 
 use aries_vcx::{
+    did_parser_nom::Did,
     handlers::util::AnyInvitation,
     messages::{
         msg_fields::protocols::{
             connection::{request::Request, response::Response},
+            did_rotate::DidRotate,
             notification::ack::Ack,
             trust_ping::ping::Ping,
         },
@@ -17,38 +19,65 @@ use aries_vcx::{
         },
         trustping::build_ping_response,
     },
+    transport::Transport,
+};
+use aries_vcx_ledger::{
+    errors::error::{VcxLedgerError, VcxLedgerResult},
+    ledger::base_ledger::IndyLedgerRead,
 };
-use aries_vcx_ledger::ledger::indy_vdr_ledger::DefaultIndyLedgerRead;
 use aries_vcx_wallet::wallet::base_wallet::BaseWallet;
+use async_trait::async_trait;
+use did_resolver_registry::ResolverRegistry;
 use url::Url;
 
 use crate::{
     error::*,
+    events::{AgentEvent, EventBus},
     http::VcxHttpClient,
-    storage::{agent_storage_inmem::AgentStorageInMem, AgentStorage},
+    storage::{agent_storage_wallet::AgentStorageWallet, AgentStorage},
 };
 
 pub struct ServiceConnections<T> {
-    ledger_read: Arc<DefaultIndyLedgerRead>,
+    indy_ledger_read: Option<Arc<dyn IndyLedgerRead>>,
     wallet: Arc<T>,
+    resolver_registry: Arc<ResolverRegistry>,
     service_endpoint: Url,
-    connections: Arc<AgentStorageInMem<GenericConnection>>,
+    connections: Arc<AgentStorageWallet<GenericConnection, T>>,
 }
 
 impl<T: BaseWallet> ServiceConnections<T> {
+    /// Without an Indy ledger, only invitations carrying the inviter's keys and endpoint can be
+    /// accepted, not those referencing a public DID
     pub fn new(
-        ledger_read: Arc<DefaultIndyLedgerRead>,
+        indy_ledger_read: Option<Arc<dyn IndyLedgerRead>>,
         wallet: Arc<T>,
+        resolver_registry: Arc<ResolverRegistry>,
         service_endpoint: Url,
+        events: EventBus,
     ) -> Self {
         Self {
+            connections: Arc::new(
+                AgentStorageWallet::new("connections", wallet.clone()).with_events(
+                    events,
+                    |connection_id: &str, connection: &GenericConnection| {
+                        AgentEvent::ConnectionStateChanged {
+                            connection_id: connection_id.to_string(),
+                            state: connection.state(),
+                        }
+                    },
+                ),
+            ),
             service_endpoint,
-            connections: Arc::new(AgentStorageInMem::new("connections")),
-            ledger_read,
+            indy_ledger_read,
             wallet,
+            resolver_registry,
         }
     }
 
+    pub(crate) async fn load(&self) -> AgentResult<()> {
+        self.connections.load().await
+    }
+
     pub async fn send_message(
         &self,
         connection_id: &str,
@@ -66,6 +95,28 @@ impl<T: BaseWallet> ServiceConnections<T> {
         Ok(())
     }
 
+    /// Delivers a message already packed for the other party of the connection, such as one
+    /// forwarded to it by this agent as its mediator. Hung up connections are refused.
+    pub async fn send_packed_message(
+        &self,
+        connection_id: &str,
+        message: Vec<u8>,
+    ) -> AgentResult<()> {
+        let connection = self.get_by_id(connection_id)?;
+        if connection
+            .did_rotation()
+            .is_some_and(|did_rotation| did_rotation.is_hung_up())
+        {
+            return Err(AgentError::from_msg(
+                AgentErrorKind::InvalidState,
+                &format!("Connection {connection_id} has been hung up"),
+            ));
+        }
+        let endpoint = connection.service_endpoint()?;
+        VcxHttpClient.send_message(message, &endpoint).await?;
+        Ok(())
+    }
+
     pub async fn create_invitation(
         &self,
         pw_info: Option<PairwiseInfo>,
@@ -76,7 +127,7 @@ impl<T: BaseWallet> ServiceConnections<T> {
         let invite = inviter.get_invitation().clone();
         let thread_id = inviter.thread_id().to_owned();
 
-        self.connections.insert(&thread_id, inviter.into())?;
+        self.connections.insert(&thread_id, inviter.into()).await?;
 
         Ok(invite)
     }
@@ -84,12 +135,15 @@ impl<T: BaseWallet> ServiceConnections<T> {
     pub async fn receive_invitation(&self, invite: AnyInvitation) -> AgentResult<String> {
         let pairwise_info = PairwiseInfo::create(self.wallet.as_ref()).await?;
         let invitee = Connection::new_invitee("".to_owned(), pairwise_info)
-            .accept_invitation(self.ledger_read.as_ref(), invite)
+            .accept_invitation(
+                &OptionalIndyLedgerRead(self.indy_ledger_read.as_deref()),
+                invite,
+            )
             .await?;
 
         let thread_id = invitee.thread_id().to_owned();
 
-        self.connections.insert(&thread_id, invitee.into())
+        self.connections.insert(&thread_id, invitee.into()).await
     }
 
     pub async fn send_request(&self, thread_id: &str) -> AgentResult<()> {
@@ -98,10 +152,14 @@ impl<T: BaseWallet> ServiceConnections<T> {
             .prepare_request(self.service_endpoint.clone(), vec![])
             .await?;
         let request = invitee.get_request().clone();
+        // Stored before sending, as an inviter responding right away answers before the request
+        // is delivered
+        self.connections
+            .insert(thread_id, invitee.clone().into())
+            .await?;
         invitee
             .send_message(self.wallet.as_ref(), &request.into(), &VcxHttpClient)
             .await?;
-        self.connections.insert(thread_id, invitee.into())?;
         Ok(())
     }
 
@@ -131,7 +189,7 @@ impl<T: BaseWallet> ServiceConnections<T> {
             )
             .await?;
 
-        self.connections.insert(thread_id, inviter.into())?;
+        self.connections.insert(thread_id, inviter.into()).await?;
 
         Ok(())
     }
@@ -143,7 +201,7 @@ impl<T: BaseWallet> ServiceConnections<T> {
             .send_message(self.wallet.as_ref(), &response.into(), &VcxHttpClient)
             .await?;
 
-        self.connections.insert(thread_id, inviter.into())?;
+        self.connections.insert(thread_id, inviter.into()).await?;
 
         Ok(())
     }
@@ -154,7 +212,7 @@ impl<T: BaseWallet> ServiceConnections<T> {
             .handle_response(self.wallet.as_ref(), response)
             .await?;
 
-        self.connections.insert(thread_id, invitee.into())?;
+        self.connections.insert(thread_id, invitee.into()).await?;
 
         Ok(())
     }
@@ -169,7 +227,7 @@ impl<T: BaseWallet> ServiceConnections<T> {
             )
             .await?;
 
-        self.connections.insert(thread_id, invitee.into())?;
+        self.connections.insert(thread_id, invitee.into()).await?;
 
         Ok(())
     }
@@ -179,7 +237,7 @@ impl<T: BaseWallet> ServiceConnections<T> {
         let inviter: Connection<_, _> = self.connections.get(&thread_id)?.try_into()?;
         let inviter = inviter.acknowledge_connection(&ack.into())?;
 
-        self.connections.insert(&thread_id, inviter.into())?;
+        self.connections.insert(&thread_id, inviter.into()).await?;
 
         Ok(())
     }
@@ -215,8 +273,53 @@ impl<T: BaseWallet> ServiceConnections<T> {
         }
 
         // update state
-        self.connections.insert(connection_id, inviter.into())?;
+        self.connections
+            .insert(connection_id, inviter.into())
+            .await?;
+
+        Ok(())
+    }
+
+    /// Rotates our side of a completed connection to a new did:peer:4. The rotate message is sent
+    /// from the current DID, which stays in use until the counterparty acknowledges the rotation.
+    pub async fn rotate_did(&self, connection_id: &str) -> AgentResult<()> {
+        let mut connection = self.connections.get(connection_id)?;
+        let rotate = connection
+            .rotate_did(self.wallet.as_ref(), self.service_endpoint.clone(), vec![])
+            .await?;
+        connection
+            .send_message(self.wallet.as_ref(), &rotate.into(), &VcxHttpClient)
+            .await?;
+        self.connections.insert(connection_id, connection).await?;
+        Ok(())
+    }
 
+    /// Processes a DID Rotate message. The connection is updated before answering, so that a
+    /// rotation of the counterparty is acknowledged at the DID it rotated to.
+    pub async fn process_did_rotate(&self, connection_id: &str, msg: DidRotate) -> AgentResult<()> {
+        let mut connection = self.connections.get(connection_id)?;
+        let answer = connection
+            .handle_did_rotate(&self.resolver_registry, msg)
+            .await?;
+        self.connections
+            .insert(connection_id, connection.clone())
+            .await?;
+        if let Some(answer) = answer {
+            connection
+                .send_message(self.wallet.as_ref(), &answer, &VcxHttpClient)
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Ends a completed connection, notifying the counterparty
+    pub async fn hangup(&self, connection_id: &str) -> AgentResult<()> {
+        let mut connection = self.connections.get(connection_id)?;
+        let hangup = connection.hangup()?;
+        connection
+            .send_message(self.wallet.as_ref(), &hangup.into(), &VcxHttpClient)
+            .await?;
+        self.connections.insert(connection_id, connection).await?;
         Ok(())
     }
 
@@ -257,3 +360,37 @@ impl<T: BaseWallet> ServiceConnections<T> {
         self.connections.contains_key(thread_id)
     }
 }
+
+/// Resolves public DIDs on the Indy ledger if there is one, failing otherwise
+#[derive(Debug)]
+struct OptionalIndyLedgerRead<'a>(Option<&'a dyn IndyLedgerRead>);
+
+impl OptionalIndyLedgerRead<'_> {
+    fn ledger(&self) -> VcxLedgerResult<&dyn IndyLedgerRead> {
+        self.0
+            .ok_or_else(|| VcxLedgerError::InvalidState("No Indy ledger configured".to_string()))
+    }
+}
+
+#[async_trait]
+impl IndyLedgerRead for OptionalIndyLedgerRead<'_> {
+    async fn get_attr(&self, target_did: &Did, attr_name: &str) -> VcxLedgerResult<String> {
+        self.ledger()?.get_attr(target_did, attr_name).await
+    }
+
+    async fn get_nym(&self, did: &Did) -> VcxLedgerResult<String> {
+        self.ledger()?.get_nym(did).await
+    }
+
+    async fn get_txn_author_agreement(&self) -> VcxLedgerResult<Option<String>> {
+        self.ledger()?.get_txn_author_agreement().await
+    }
+
+    async fn get_ledger_txn(
+        &self,
+        seq_no: i32,
+        submitter_did: Option<&Did>,
+    ) -> VcxLedgerResult<String> {
+        self.ledger()?.get_ledger_txn(seq_no, submitter_did).await
+    }
+}
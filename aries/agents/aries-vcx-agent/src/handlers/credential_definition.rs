@@ -2,32 +2,38 @@ use std::sync::{Arc, Mutex};
 
 use anoncreds_types::data_types::identifiers::schema_id::SchemaId;
 use aries_vcx::{common::primitives::credential_definition::CredentialDef, did_parser_nom::Did};
-use aries_vcx_anoncreds::anoncreds::anoncreds::Anoncreds;
-use aries_vcx_ledger::ledger::indy_vdr_ledger::{DefaultIndyLedgerRead, DefaultIndyLedgerWrite};
+use aries_vcx_anoncreds::anoncreds::base_anoncreds::BaseAnonCreds;
+use aries_vcx_ledger::ledger::base_ledger::{AnoncredsLedgerRead, AnoncredsLedgerWrite};
 use aries_vcx_wallet::wallet::base_wallet::BaseWallet;
 
 use crate::{
     error::*,
-    storage::{agent_storage_inmem::AgentStorageInMem, AgentStorage},
+    storage::{agent_storage_wallet::AgentStorageWallet, AgentStorage},
 };
 
-pub struct ServiceCredentialDefinitions<T> {
-    ledger_read: Arc<DefaultIndyLedgerRead>,
-    ledger_write: Arc<DefaultIndyLedgerWrite>,
-    anoncreds: Anoncreds,
+pub struct ServiceCredentialDefinitions<T, LR, LW, A> {
+    ledger_read: Arc<LR>,
+    ledger_write: Arc<LW>,
+    anoncreds: Arc<A>,
     wallet: Arc<T>,
-    cred_defs: AgentStorageInMem<CredentialDef>,
+    cred_defs: AgentStorageWallet<CredentialDef, T>,
 }
 
-impl<T: BaseWallet> ServiceCredentialDefinitions<T> {
+impl<T, LR, LW, A> ServiceCredentialDefinitions<T, LR, LW, A>
+where
+    T: BaseWallet,
+    LR: AnoncredsLedgerRead,
+    LW: AnoncredsLedgerWrite,
+    A: BaseAnonCreds,
+{
     pub fn new(
-        ledger_read: Arc<DefaultIndyLedgerRead>,
-        ledger_write: Arc<DefaultIndyLedgerWrite>,
-        anoncreds: Anoncreds,
+        ledger_read: Arc<LR>,
+        ledger_write: Arc<LW>,
+        anoncreds: Arc<A>,
         wallet: Arc<T>,
     ) -> Self {
         Self {
-            cred_defs: AgentStorageInMem::new("cred-defs"),
+            cred_defs: AgentStorageWallet::new("cred-defs", wallet.clone()),
             ledger_read,
             ledger_write,
             anoncreds,
@@ -35,6 +41,10 @@ impl<T: BaseWallet> ServiceCredentialDefinitions<T> {
         }
     }
 
+    pub(crate) async fn load(&self) -> AgentResult<()> {
+        self.cred_defs.load().await
+    }
+
     pub async fn create_cred_def(
         &self,
         issuer_did: Did,
@@ -44,7 +54,7 @@ impl<T: BaseWallet> ServiceCredentialDefinitions<T> {
         let cd = CredentialDef::create(
             self.wallet.as_ref(),
             self.ledger_read.as_ref(),
-            &self.anoncreds,
+            self.anoncreds.as_ref(),
             "".to_string(),
             issuer_did,
             schema_id,
@@ -52,7 +62,9 @@ impl<T: BaseWallet> ServiceCredentialDefinitions<T> {
             true,
         )
         .await?;
-        self.cred_defs.insert(&cd.get_cred_def_id().to_string(), cd)
+        self.cred_defs
+            .insert(&cd.get_cred_def_id().to_string(), cd)
+            .await
     }
 
     pub async fn publish_cred_def(&self, thread_id: &str) -> AgentResult<()> {
@@ -64,7 +76,7 @@ impl<T: BaseWallet> ServiceCredentialDefinitions<T> {
                 self.ledger_write.as_ref(),
             )
             .await?;
-        self.cred_defs.insert(thread_id, cred_def)?;
+        self.cred_defs.insert(thread_id, cred_def).await?;
         Ok(())
     }
 
@@ -31,8 +31,9 @@ use public_key::{Key, KeyType};
 use url::Url;
 
 use crate::{
+    events::{AgentEvent, EventBus},
     http::VcxHttpClient,
-    storage::{agent_storage_inmem::AgentStorageInMem, AgentStorage},
+    storage::{agent_storage_wallet::AgentStorageWallet, AgentStorage},
     AgentError, AgentErrorKind, AgentResult,
 };
 
@@ -41,7 +42,7 @@ pub struct DidcommHandlerDidExchange<T> {
     wallet: Arc<T>,
     resolver_registry: Arc<ResolverRegistry>,
     service_endpoint: Url,
-    did_exchange: Arc<AgentStorageInMem<(GenericDidExchange, Option<AriesMessage>)>>,
+    did_exchange: Arc<AgentStorageWallet<(GenericDidExchange, Option<AriesMessage>), T>>,
     public_did: String,
 }
 
@@ -51,16 +52,31 @@ impl<T: BaseWallet> DidcommHandlerDidExchange<T> {
         resolver_registry: Arc<ResolverRegistry>,
         service_endpoint: Url,
         public_did: String,
+        events: EventBus,
     ) -> Self {
         Self {
+            did_exchange: Arc::new(
+                AgentStorageWallet::new("did-exchange", wallet.clone()).with_events(
+                    events,
+                    |thread_id: &str, record: &(GenericDidExchange, Option<AriesMessage>)| {
+                        AgentEvent::DidExchangeStateChanged {
+                            thread_id: thread_id.to_string(),
+                            state: record.0.get_state(),
+                        }
+                    },
+                ),
+            ),
             wallet,
             service_endpoint,
             resolver_registry,
-            did_exchange: Arc::new(AgentStorageInMem::new("did-exchange")),
             public_did,
         }
     }
 
+    pub(crate) async fn load(&self) -> AgentResult<()> {
+        self.did_exchange.load().await
+    }
+
     pub async fn handle_msg_invitation(
         &self,
         their_did: String,
@@ -114,7 +130,9 @@ impl<T: BaseWallet> DidcommHandlerDidExchange<T> {
         //       but if AATH can not be changed and both thid and pthid are used to track instance
         //       of protocol then we need to update storage to enable identification by
         //       multiple IDs (both thid, pthid (or arbitrary other))
-        self.did_exchange.insert(&thid, (requester.clone(), None))?;
+        self.did_exchange
+            .insert(&thid, (requester.clone(), None))
+            .await?;
         VcxHttpClient
             .send_message(encryption_envelope.0, service.service_endpoint())
             .await?;
@@ -175,7 +193,8 @@ impl<T: BaseWallet> DidcommHandlerDidExchange<T> {
         )
         .await?;
         self.did_exchange
-            .insert(&thid, (responder.clone(), Some(response.into())))?;
+            .insert(&thid, (responder.clone(), Some(response.into())))
+            .await?;
 
         let our_did = responder.our_did_document().id().to_string();
         let their_did = responder.their_did_doc().id().to_string();
@@ -244,25 +263,34 @@ impl<T: BaseWallet> DidcommHandlerDidExchange<T> {
             service.id(),
         )
         .await?;
-        self.did_exchange.insert(&thid, (requester.clone(), None))?;
+        self.did_exchange
+            .insert(&thid, (requester.clone(), None))
+            .await?;
         VcxHttpClient
             .send_message(encryption_envelope.0, service.service_endpoint())
             .await?;
         Ok(thid)
     }
 
-    pub fn handle_msg_complete(&self, complete: Complete) -> AgentResult<String> {
+    pub async fn handle_msg_complete(&self, complete: Complete) -> AgentResult<String> {
         let thread_id = complete.decorators.thread.thid.clone();
         let (requester, _) = self.did_exchange.get(&thread_id)?;
         let requester = requester.handle_complete(complete)?;
-        self.did_exchange.insert(&thread_id, (requester, None))
+        self.did_exchange
+            .insert(&thread_id, (requester, None))
+            .await
     }
 
-    pub fn receive_problem_report(&self, problem_report: ProblemReport) -> AgentResult<String> {
+    pub async fn receive_problem_report(
+        &self,
+        problem_report: ProblemReport,
+    ) -> AgentResult<String> {
         let thread_id = problem_report.decorators.thread.thid.clone();
         let (requester, _) = self.did_exchange.get(&thread_id)?;
         let requester = requester.handle_problem_report(problem_report)?;
-        self.did_exchange.insert(&thread_id, (requester, None))
+        self.did_exchange
+            .insert(&thread_id, (requester, None))
+            .await
     }
 
     pub fn exists_by_id(&self, thread_id: &str) -> bool {
@@ -1,6 +1,7 @@
 use std::sync::Arc;
 
 use aries_vcx::{
+    common::credentials::store::{CredentialFilter, CredentialStore, StoredCredential},
     did_parser_nom::Did,
     handlers::issuance::holder::Holder,
     messages::{
@@ -12,56 +13,78 @@ use aries_vcx::{
     },
     protocols::{issuance::holder::state_machine::HolderState, SendClosure},
 };
-use aries_vcx_anoncreds::anoncreds::anoncreds::Anoncreds;
-use aries_vcx_ledger::ledger::indy_vdr_ledger::DefaultIndyLedgerRead;
+use aries_vcx_anoncreds::anoncreds::base_anoncreds::BaseAnonCreds;
+use aries_vcx_ledger::ledger::base_ledger::AnoncredsLedgerRead;
 use aries_vcx_wallet::wallet::base_wallet::BaseWallet;
+use serde::{Deserialize, Serialize};
 
 use crate::{
     error::*,
-    handlers::connection::ServiceConnections,
+    events::{AgentEvent, EventBus},
+    handlers::{connection::ServiceConnections, ProtocolVersion},
     http::VcxHttpClient,
-    storage::{agent_storage_inmem::AgentStorageInMem, AgentStorage},
+    storage::{agent_storage_wallet::AgentStorageWallet, AgentStorage},
 };
 
-#[derive(Clone)]
+#[derive(Clone, Serialize, Deserialize)]
 struct HolderWrapper {
     holder: Holder,
     connection_id: String,
+    #[serde(default)]
+    version: ProtocolVersion,
 }
 
 impl HolderWrapper {
-    pub fn new(holder: Holder, connection_id: &str) -> Self {
+    pub fn new(holder: Holder, connection_id: &str, version: ProtocolVersion) -> Self {
         Self {
             holder,
             connection_id: connection_id.to_string(),
+            version,
         }
     }
 }
 
-pub struct ServiceCredentialsHolder<T> {
-    ledger_read: Arc<DefaultIndyLedgerRead>,
-    anoncreds: Anoncreds,
+pub struct ServiceCredentialsHolder<T, LR, A> {
+    ledger_read: Arc<LR>,
+    anoncreds: Arc<A>,
     wallet: Arc<T>,
-    creds_holder: AgentStorageInMem<HolderWrapper>,
+    creds_holder: AgentStorageWallet<HolderWrapper, T>,
     service_connections: Arc<ServiceConnections<T>>,
 }
 
-impl<T: BaseWallet> ServiceCredentialsHolder<T> {
+impl<T, LR, A> ServiceCredentialsHolder<T, LR, A>
+where
+    T: BaseWallet,
+    LR: AnoncredsLedgerRead,
+    A: BaseAnonCreds,
+{
     pub fn new(
-        ledger_read: Arc<DefaultIndyLedgerRead>,
-        anoncreds: Anoncreds,
+        ledger_read: Arc<LR>,
+        anoncreds: Arc<A>,
         wallet: Arc<T>,
         service_connections: Arc<ServiceConnections<T>>,
+        events: EventBus,
     ) -> Self {
         Self {
+            creds_holder: AgentStorageWallet::new("creds-holder", wallet.clone()).with_events(
+                events,
+                |thread_id: &str, wrapper: &HolderWrapper| AgentEvent::HolderStateChanged {
+                    thread_id: thread_id.to_string(),
+                    connection_id: wrapper.connection_id.clone(),
+                    state: wrapper.holder.get_state(),
+                },
+            ),
             service_connections,
-            creds_holder: AgentStorageInMem::new("creds-holder"),
             ledger_read,
             anoncreds,
             wallet,
         }
     }
 
+    pub(crate) async fn load(&self) -> AgentResult<()> {
+        self.creds_holder.load().await
+    }
+
     fn get_holder(&self, thread_id: &str) -> AgentResult<Holder> {
         let HolderWrapper { holder, .. } = self.creds_holder.get(thread_id)?;
         Ok(holder)
@@ -76,58 +99,70 @@ impl<T: BaseWallet> ServiceCredentialsHolder<T> {
         &self,
         connection_id: &str,
         propose_credential: ProposeCredentialV1,
+        version: ProtocolVersion,
     ) -> AgentResult<String> {
         let holder = Holder::create_with_proposal("foobar", propose_credential)?;
 
-        let aries_msg: AriesMessage = holder.get_proposal()?.into();
+        let aries_msg = version.outbound(holder.get_proposal()?.into())?;
         self.service_connections
             .send_message(connection_id, &aries_msg)
             .await?;
 
-        self.creds_holder.insert(
-            &holder.get_thread_id()?,
-            HolderWrapper::new(holder, connection_id),
-        )
+        self.creds_holder
+            .insert(
+                &holder.get_thread_id()?,
+                HolderWrapper::new(holder, connection_id, version),
+            )
+            .await
     }
 
-    pub fn create_from_offer(
+    pub async fn create_from_offer(
         &self,
         connection_id: &str,
         offer: OfferCredentialV1,
+        version: ProtocolVersion,
     ) -> AgentResult<String> {
         self.service_connections.get_by_id(connection_id)?;
         let holder = Holder::create_from_offer("foobar", offer)?;
-        self.creds_holder.insert(
-            &holder.get_thread_id()?,
-            HolderWrapper::new(holder, connection_id),
-        )
+        self.creds_holder
+            .insert(
+                &holder.get_thread_id()?,
+                HolderWrapper::new(holder, connection_id, version),
+            )
+            .await
     }
 
     pub async fn send_credential_request(&self, thread_id: &str) -> AgentResult<String> {
-        let connection_id = self.get_connection_id(thread_id)?;
+        let HolderWrapper {
+            mut holder,
+            connection_id,
+            version,
+        } = self.creds_holder.get(thread_id)?;
         let connection = self.service_connections.get_by_id(&connection_id)?;
         // todo: technically doesn't need to be DID at all, and definitely need not to be pairwise
         // DID
         let pw_did_as_entropy = connection.pairwise_info().pw_did.to_string();
 
-        let mut holder = self.get_holder(thread_id)?;
         let message = holder
             .prepare_credential_request(
                 self.wallet.as_ref(),
                 self.ledger_read.as_ref(),
-                &self.anoncreds,
+                self.anoncreds.as_ref(),
                 Did::parse(pw_did_as_entropy)?,
             )
             .await?;
 
+        let thread_id = self
+            .creds_holder
+            .insert(
+                &holder.get_thread_id()?,
+                HolderWrapper::new(holder, &connection_id, version),
+            )
+            .await?;
         self.service_connections
-            .send_message(&connection_id, &message)
+            .send_message(&connection_id, &version.outbound(message)?)
             .await?;
-
-        self.creds_holder.insert(
-            &holder.get_thread_id()?,
-            HolderWrapper::new(holder, &connection_id),
-        )
+        Ok(thread_id)
     }
 
     pub async fn process_credential(
@@ -135,15 +170,18 @@ impl<T: BaseWallet> ServiceCredentialsHolder<T> {
         thread_id: &str,
         msg_issue_credential: IssueCredentialV1,
     ) -> AgentResult<String> {
-        let mut holder = self.get_holder(thread_id)?;
-        let connection_id = self.get_connection_id(thread_id)?;
+        let HolderWrapper {
+            mut holder,
+            connection_id,
+            version,
+        } = self.creds_holder.get(thread_id)?;
         let connection = self.service_connections.get_by_id(&connection_id)?;
 
         holder
             .process_credential(
                 self.wallet.as_ref(),
                 self.ledger_read.as_ref(),
-                &self.anoncreds,
+                self.anoncreds.as_ref(),
                 msg_issue_credential.clone(),
             )
             .await?;
@@ -157,13 +195,15 @@ impl<T: BaseWallet> ServiceCredentialsHolder<T> {
                             .await
                     })
                 });
-                send_closure(msg_response).await?;
+                send_closure(version.outbound(msg_response)?).await?;
             }
         }
-        self.creds_holder.insert(
-            &holder.get_thread_id()?,
-            HolderWrapper::new(holder, &connection_id),
-        )
+        self.creds_holder
+            .insert(
+                &holder.get_thread_id()?,
+                HolderWrapper::new(holder, &connection_id, version),
+            )
+            .await
     }
 
     pub fn get_state(&self, thread_id: &str) -> AgentResult<HolderState> {
@@ -198,4 +238,57 @@ impl<T: BaseWallet> ServiceCredentialsHolder<T> {
     pub fn exists_by_id(&self, thread_id: &str) -> bool {
         self.creds_holder.contains_key(thread_id)
     }
+
+    fn credential_store(&self) -> CredentialStore<'_, T, A> {
+        CredentialStore::new(self.wallet.as_ref(), self.anoncreds.as_ref())
+    }
+
+    pub async fn get_credential(&self, cred_id: &str) -> AgentResult<StoredCredential> {
+        Ok(self.credential_store().get(&cred_id.to_string()).await?)
+    }
+
+    pub async fn list_credentials(&self) -> AgentResult<Vec<StoredCredential>> {
+        Ok(self.credential_store().list().await?)
+    }
+
+    pub async fn search_credentials(
+        &self,
+        filter: &CredentialFilter,
+    ) -> AgentResult<Vec<StoredCredential>> {
+        Ok(self.credential_store().search(filter).await?)
+    }
+
+    pub async fn delete_credential(&self, cred_id: &str) -> AgentResult<()> {
+        Ok(self.credential_store().delete(&cred_id.to_string()).await?)
+    }
+
+    pub async fn set_credential_label(
+        &self,
+        cred_id: &str,
+        label: Option<&str>,
+    ) -> AgentResult<()> {
+        Ok(self
+            .credential_store()
+            .set_label(&cred_id.to_string(), label)
+            .await?)
+    }
+
+    pub async fn set_credential_tag(
+        &self,
+        cred_id: &str,
+        name: &str,
+        value: Option<&str>,
+    ) -> AgentResult<()> {
+        Ok(self
+            .credential_store()
+            .set_tag(&cred_id.to_string(), name, value)
+            .await?)
+    }
+
+    pub async fn refresh_credential_revocation_status(&self, cred_id: &str) -> AgentResult<bool> {
+        Ok(self
+            .credential_store()
+            .refresh_revocation_status(self.ledger_read.as_ref(), &cred_id.to_string())
+            .await?)
+    }
 }
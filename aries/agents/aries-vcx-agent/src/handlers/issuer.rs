@@ -4,59 +4,76 @@ use aries_vcx::{
     handlers::{issuance::issuer::Issuer, util::OfferInfo},
     messages::{
         msg_fields::protocols::cred_issuance::v1::{
-            ack::AckCredentialV1, propose_credential::ProposeCredentialV1,
-            request_credential::RequestCredentialV1,
+            ack::AckCredentialV1, offer_credential::OfferCredentialV1,
+            propose_credential::ProposeCredentialV1, request_credential::RequestCredentialV1,
         },
         AriesMessage,
     },
     protocols::{issuance::issuer::state_machine::IssuerState, SendClosure},
 };
-use aries_vcx_anoncreds::anoncreds::anoncreds::Anoncreds;
+use aries_vcx_anoncreds::anoncreds::base_anoncreds::BaseAnonCreds;
 use aries_vcx_wallet::wallet::base_wallet::BaseWallet;
+use serde::{Deserialize, Serialize};
 
 use crate::{
     error::*,
-    handlers::connection::ServiceConnections,
+    events::{AgentEvent, EventBus},
+    handlers::{connection::ServiceConnections, ProtocolVersion},
     http::VcxHttpClient,
-    storage::{agent_storage_inmem::AgentStorageInMem, AgentStorage},
+    storage::{agent_storage_wallet::AgentStorageWallet, AgentStorage},
 };
 
-#[derive(Clone)]
+#[derive(Clone, Serialize, Deserialize)]
 struct IssuerWrapper {
     issuer: Issuer,
     connection_id: String,
+    #[serde(default)]
+    version: ProtocolVersion,
 }
 
 impl IssuerWrapper {
-    pub fn new(issuer: Issuer, connection_id: &str) -> Self {
+    pub fn new(issuer: Issuer, connection_id: &str, version: ProtocolVersion) -> Self {
         Self {
             issuer,
             connection_id: connection_id.to_string(),
+            version,
         }
     }
 }
 
-pub struct ServiceCredentialsIssuer<T> {
-    anoncreds: Anoncreds,
+pub struct ServiceCredentialsIssuer<T, A> {
+    anoncreds: Arc<A>,
     wallet: Arc<T>,
-    creds_issuer: AgentStorageInMem<IssuerWrapper>,
+    creds_issuer: AgentStorageWallet<IssuerWrapper, T>,
     service_connections: Arc<ServiceConnections<T>>,
 }
 
-impl<T: BaseWallet> ServiceCredentialsIssuer<T> {
+impl<T: BaseWallet, A: BaseAnonCreds> ServiceCredentialsIssuer<T, A> {
     pub fn new(
-        anoncreds: Anoncreds,
+        anoncreds: Arc<A>,
         wallet: Arc<T>,
         service_connections: Arc<ServiceConnections<T>>,
+        events: EventBus,
     ) -> Self {
         Self {
+            creds_issuer: AgentStorageWallet::new("creds-issuer", wallet.clone()).with_events(
+                events,
+                |thread_id: &str, wrapper: &IssuerWrapper| AgentEvent::IssuerStateChanged {
+                    thread_id: thread_id.to_string(),
+                    connection_id: wrapper.connection_id.clone(),
+                    state: wrapper.issuer.get_state(),
+                },
+            ),
             service_connections,
-            creds_issuer: AgentStorageInMem::new("creds-issuer"),
             anoncreds,
             wallet,
         }
     }
 
+    pub(crate) async fn load(&self) -> AgentResult<()> {
+        self.creds_issuer.load().await
+    }
+
     fn get_issuer(&self, thread_id: &str) -> AgentResult<Issuer> {
         let IssuerWrapper { issuer, .. } = self.creds_issuer.get(thread_id)?;
         Ok(issuer)
@@ -71,11 +88,16 @@ impl<T: BaseWallet> ServiceCredentialsIssuer<T> {
         &self,
         connection_id: &str,
         proposal: &ProposeCredentialV1,
+        version: ProtocolVersion,
     ) -> AgentResult<String> {
         let issuer = Issuer::create_from_proposal("", proposal)?;
         let thread_id = issuer.get_thread_id()?;
         self.creds_issuer
-            .insert(&thread_id, IssuerWrapper::new(issuer, connection_id))?;
+            .insert(
+                &thread_id,
+                IssuerWrapper::new(issuer, connection_id, version),
+            )
+            .await?;
         info!(
             "Created new IssuerCredential with resource id: {}",
             thread_id
@@ -83,21 +105,38 @@ impl<T: BaseWallet> ServiceCredentialsIssuer<T> {
         Ok(thread_id)
     }
 
+    /// Sends an offer on the thread if given, or starts a new thread in the given version
+    /// otherwise. Offers on an existing thread keep the version the thread was started in.
     pub async fn send_credential_offer(
         &self,
         thread_id: Option<&str>,
         connection_id: Option<&str>,
         offer_info: OfferInfo,
+        version: ProtocolVersion,
     ) -> AgentResult<String> {
-        let (mut issuer, connection_id) = match (thread_id, connection_id) {
-            (Some(id), Some(connection_id)) => (self.get_issuer(id)?, connection_id.to_string()),
-            (Some(id), None) => (self.get_issuer(id)?, self.get_connection_id(id)?),
-            (None, Some(connection_id)) => (Issuer::create("")?, connection_id.to_string()),
+        let (mut issuer, connection_id, version) = match (thread_id, connection_id) {
+            (Some(id), connection_id) => {
+                let IssuerWrapper {
+                    issuer,
+                    connection_id: thread_connection_id,
+                    version,
+                } = self.creds_issuer.get(id)?;
+                let connection_id = connection_id.map_or(thread_connection_id, str::to_string);
+                (issuer, connection_id, version)
+            }
+            (None, Some(connection_id)) => {
+                (Issuer::create("")?, connection_id.to_string(), version)
+            }
             (None, None) => return Err(AgentError::from_kind(AgentErrorKind::InvalidArguments)),
         };
         let connection = self.service_connections.get_by_id(&connection_id)?;
         issuer
-            .build_credential_offer_msg(self.wallet.as_ref(), &self.anoncreds, offer_info, None)
+            .build_credential_offer_msg(
+                self.wallet.as_ref(),
+                self.anoncreds.as_ref(),
+                offer_info,
+                None,
+            )
             .await?;
 
         let send_closure: SendClosure = Box::new(|msg: AriesMessage| {
@@ -109,14 +148,18 @@ impl<T: BaseWallet> ServiceCredentialsIssuer<T> {
         });
 
         let credential_offer = issuer.get_credential_offer_msg()?;
-        send_closure(credential_offer).await?;
-        self.creds_issuer.insert(
-            &issuer.get_thread_id()?,
-            IssuerWrapper::new(issuer, &connection_id),
-        )
+        let thread_id = self
+            .creds_issuer
+            .insert(
+                &issuer.get_thread_id()?,
+                IssuerWrapper::new(issuer, &connection_id, version),
+            )
+            .await?;
+        send_closure(version.outbound(credential_offer)?).await?;
+        Ok(thread_id)
     }
 
-    pub fn process_credential_request(
+    pub async fn process_credential_request(
         &self,
         thread_id: &str,
         request: RequestCredentialV1,
@@ -124,25 +167,35 @@ impl<T: BaseWallet> ServiceCredentialsIssuer<T> {
         let IssuerWrapper {
             mut issuer,
             connection_id,
+            version,
         } = self.creds_issuer.get(thread_id)?;
         issuer.process_credential_request(request)?;
-        self.creds_issuer.insert(
-            &issuer.get_thread_id()?,
-            IssuerWrapper::new(issuer, &connection_id),
-        )?;
+        self.creds_issuer
+            .insert(
+                &issuer.get_thread_id()?,
+                IssuerWrapper::new(issuer, &connection_id, version),
+            )
+            .await?;
         Ok(())
     }
 
-    pub fn process_credential_ack(&self, thread_id: &str, ack: AckCredentialV1) -> AgentResult<()> {
+    pub async fn process_credential_ack(
+        &self,
+        thread_id: &str,
+        ack: AckCredentialV1,
+    ) -> AgentResult<()> {
         let IssuerWrapper {
             mut issuer,
             connection_id,
+            version,
         } = self.creds_issuer.get(thread_id)?;
         issuer.process_credential_ack(ack)?;
-        self.creds_issuer.insert(
-            &issuer.get_thread_id()?,
-            IssuerWrapper::new(issuer, &connection_id),
-        )?;
+        self.creds_issuer
+            .insert(
+                &issuer.get_thread_id()?,
+                IssuerWrapper::new(issuer, &connection_id, version),
+            )
+            .await?;
         Ok(())
     }
 
@@ -150,6 +203,7 @@ impl<T: BaseWallet> ServiceCredentialsIssuer<T> {
         let IssuerWrapper {
             mut issuer,
             connection_id,
+            version,
         } = self.creds_issuer.get(thread_id)?;
         let connection = self.service_connections.get_by_id(&connection_id)?;
 
@@ -162,22 +216,20 @@ impl<T: BaseWallet> ServiceCredentialsIssuer<T> {
         });
 
         issuer
-            .build_credential(self.wallet.as_ref(), &self.anoncreds)
+            .build_credential(self.wallet.as_ref(), self.anoncreds.as_ref())
             .await?;
-        match issuer.get_state() {
-            IssuerState::Failed => {
-                let problem_report = issuer.get_problem_report()?;
-                send_closure(problem_report.into()).await?;
-            }
-            _ => {
-                let msg_issue_credential = issuer.get_msg_issue_credential()?;
-                send_closure(msg_issue_credential.into()).await?;
-            }
-        }
-        self.creds_issuer.insert(
-            &issuer.get_thread_id()?,
-            IssuerWrapper::new(issuer, &connection_id),
-        )?;
+        let message: AriesMessage = match issuer.get_state() {
+            IssuerState::Failed => issuer.get_problem_report()?.into(),
+            _ => issuer.get_msg_issue_credential()?.into(),
+        };
+        // Stored before sending, as the holder acknowledges the credential right away
+        self.creds_issuer
+            .insert(
+                &issuer.get_thread_id()?,
+                IssuerWrapper::new(issuer, &connection_id, version),
+            )
+            .await?;
+        send_closure(version.outbound(message)?).await?;
         Ok(())
     }
 
@@ -200,6 +252,11 @@ impl<T: BaseWallet> ServiceCredentialsIssuer<T> {
         issuer.get_proposal().map_err(|err| err.into())
     }
 
+    pub fn get_credential_offer(&self, thread_id: &str) -> AgentResult<OfferCredentialV1> {
+        let issuer = self.get_issuer(thread_id)?;
+        issuer.get_credential_offer().map_err(|err| err.into())
+    }
+
     pub fn exists_by_id(&self, thread_id: &str) -> bool {
         self.creds_issuer.contains_key(thread_id)
     }
@@ -1,3 +1,14 @@
+use aries_vcx::{
+    handlers::{issuance, proof_presentation},
+    messages::{
+        msg_fields::protocols::{cred_issuance::CredentialIssuance, present_proof::PresentProof},
+        AriesMessage,
+    },
+};
+use serde::{Deserialize, Serialize};
+
+use crate::error::AgentResult;
+
 pub(crate) mod connection;
 pub(crate) mod credential_definition;
 pub(crate) mod did_exchange;
@@ -8,3 +19,28 @@ pub(crate) mod prover;
 pub(crate) mod revocation_registry;
 pub(crate) mod schema;
 pub(crate) mod verifier;
+
+/// Version of the issue-credential or present-proof protocol run on a thread. Both versions are
+/// run by the 1.0 state machines, whose messages are converted to and from the 2.0 AnonCreds
+/// formats on the wire.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ProtocolVersion {
+    #[default]
+    V1,
+    V2,
+}
+
+impl ProtocolVersion {
+    /// Converts a message built by a 1.0 state machine to be sent in this version
+    pub(crate) fn outbound(self, message: AriesMessage) -> AgentResult<AriesMessage> {
+        Ok(match (self, message) {
+            (Self::V2, AriesMessage::CredentialIssuance(CredentialIssuance::V1(msg))) => {
+                CredentialIssuance::V2(issuance::v2_bridge::to_v2(msg)?).into()
+            }
+            (Self::V2, AriesMessage::PresentProof(PresentProof::V1(msg))) => {
+                PresentProof::V2(proof_presentation::v2_bridge::to_v2(msg)?).into()
+            }
+            (_, message) => message,
+        })
+    }
+}
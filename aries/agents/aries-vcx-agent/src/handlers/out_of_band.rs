@@ -18,25 +18,29 @@ use aries_vcx_wallet::wallet::base_wallet::BaseWallet;
 use url::Url;
 
 use crate::{
-    storage::{agent_storage_inmem::AgentStorageInMem, AgentStorage},
+    storage::{agent_storage_wallet::AgentStorageWallet, AgentStorage},
     AgentResult,
 };
 
 pub struct ServiceOutOfBand<T> {
     wallet: Arc<T>,
     service_endpoint: Url,
-    out_of_band: Arc<AgentStorageInMem<GenericOutOfBand>>,
+    out_of_band: Arc<AgentStorageWallet<GenericOutOfBand, T>>,
 }
 
 impl<T: BaseWallet> ServiceOutOfBand<T> {
     pub fn new(wallet: Arc<T>, service_endpoint: Url) -> Self {
         Self {
+            out_of_band: Arc::new(AgentStorageWallet::new("out-of-band", wallet.clone())),
             wallet,
             service_endpoint,
-            out_of_band: Arc::new(AgentStorageInMem::new("out-of-band")),
         }
     }
 
+    pub(crate) async fn load(&self) -> AgentResult<()> {
+        self.out_of_band.load().await
+    }
+
     pub async fn create_invitation(&self) -> AgentResult<AriesMessage> {
         let (peer_did, _our_verkey) =
             create_peer_did_4(self.wallet.as_ref(), self.service_endpoint.clone(), vec![]).await?;
@@ -47,19 +51,22 @@ impl<T: BaseWallet> ServiceOutOfBand<T> {
                 DidExchangeTypeV1::new_v1_1(),
             )))?;
 
-        self.out_of_band.insert(
-            &sender.get_id(),
-            GenericOutOfBand::Sender(sender.to_owned()),
-        )?;
+        self.out_of_band
+            .insert(
+                &sender.get_id(),
+                GenericOutOfBand::Sender(sender.to_owned()),
+            )
+            .await?;
 
         Ok(sender.invitation_to_aries_message())
     }
 
-    pub fn receive_invitation(&self, invitation: AriesMessage) -> AgentResult<String> {
+    pub async fn receive_invitation(&self, invitation: AriesMessage) -> AgentResult<String> {
         let receiver = OutOfBandReceiver::create_from_a2a_msg(&invitation)?;
 
         self.out_of_band
             .insert(&receiver.get_id(), GenericOutOfBand::Receiver(receiver))
+            .await
     }
 
     pub fn get_invitation(&self, invitation_id: &str) -> AgentResult<OobInvitation> {
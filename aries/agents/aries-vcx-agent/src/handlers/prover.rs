@@ -11,57 +11,79 @@ use aries_vcx::{
     },
     protocols::{proof_presentation::prover::state_machine::ProverState, SendClosure},
 };
-use aries_vcx_anoncreds::anoncreds::anoncreds::Anoncreds;
-use aries_vcx_ledger::ledger::indy_vdr_ledger::DefaultIndyLedgerRead;
+use aries_vcx_anoncreds::anoncreds::base_anoncreds::BaseAnonCreds;
+use aries_vcx_ledger::ledger::base_ledger::AnoncredsLedgerRead;
 use aries_vcx_wallet::wallet::base_wallet::BaseWallet;
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
-use super::connection::ServiceConnections;
+use super::{connection::ServiceConnections, ProtocolVersion};
 use crate::{
     error::*,
+    events::{AgentEvent, EventBus},
     http::VcxHttpClient,
-    storage::{agent_storage_inmem::AgentStorageInMem, AgentStorage},
+    storage::{agent_storage_wallet::AgentStorageWallet, AgentStorage},
 };
 
-#[derive(Clone)]
+#[derive(Clone, Serialize, Deserialize)]
 struct ProverWrapper {
     prover: Prover,
     connection_id: String,
+    #[serde(default)]
+    version: ProtocolVersion,
 }
 
 impl ProverWrapper {
-    pub fn new(prover: Prover, connection_id: &str) -> Self {
+    pub fn new(prover: Prover, connection_id: &str, version: ProtocolVersion) -> Self {
         Self {
             prover,
             connection_id: connection_id.to_string(),
+            version,
         }
     }
 }
 
-pub struct ServiceProver<T> {
-    ledger_read: Arc<DefaultIndyLedgerRead>,
-    anoncreds: Anoncreds,
+pub struct ServiceProver<T, LR, A> {
+    ledger_read: Arc<LR>,
+    anoncreds: Arc<A>,
     wallet: Arc<T>,
-    provers: AgentStorageInMem<ProverWrapper>,
+    provers: AgentStorageWallet<ProverWrapper, T>,
     service_connections: Arc<ServiceConnections<T>>,
 }
 
-impl<T: BaseWallet> ServiceProver<T> {
+impl<T, LR, A> ServiceProver<T, LR, A>
+where
+    T: BaseWallet,
+    LR: AnoncredsLedgerRead,
+    A: BaseAnonCreds,
+{
     pub fn new(
-        ledger_read: Arc<DefaultIndyLedgerRead>,
-        anoncreds: Anoncreds,
+        ledger_read: Arc<LR>,
+        anoncreds: Arc<A>,
         wallet: Arc<T>,
         service_connections: Arc<ServiceConnections<T>>,
+        events: EventBus,
     ) -> Self {
         Self {
+            provers: AgentStorageWallet::new("provers", wallet.clone()).with_events(
+                events,
+                |thread_id: &str, wrapper: &ProverWrapper| AgentEvent::ProverStateChanged {
+                    thread_id: thread_id.to_string(),
+                    connection_id: wrapper.connection_id.clone(),
+                    state: wrapper.prover.get_state(),
+                },
+            ),
             service_connections,
-            provers: AgentStorageInMem::new("provers"),
             ledger_read,
             anoncreds,
             wallet,
         }
     }
 
+    pub(crate) async fn load(&self) -> AgentResult<()> {
+        self.provers.load().await
+    }
+
     pub fn get_prover(&self, thread_id: &str) -> AgentResult<Prover> {
         let ProverWrapper { prover, .. } = self.provers.get(thread_id)?;
         Ok(prover)
@@ -78,7 +100,7 @@ impl<T: BaseWallet> ServiceProver<T> {
         tails_dir: Option<&str>,
     ) -> AgentResult<SelectedCredentials> {
         let credentials = prover
-            .retrieve_credentials(self.wallet.as_ref(), &self.anoncreds)
+            .retrieve_credentials(self.wallet.as_ref(), self.anoncreds.as_ref())
             .await?;
 
         let mut res_credentials = SelectedCredentials::default();
@@ -94,23 +116,47 @@ impl<T: BaseWallet> ServiceProver<T> {
         Ok(res_credentials)
     }
 
-    pub fn create_from_request(
+    /// Whether exactly one credential in the wallet matches each requested attribute and
+    /// predicate, so that the credentials to present need no choosing
+    pub async fn has_unambiguous_credentials(&self, thread_id: &str) -> AgentResult<bool> {
+        let prover = self.get_prover(thread_id)?;
+        let request: Value = serde_json::from_str(&prover.presentation_request_data()?)?;
+        let credentials = prover
+            .retrieve_credentials(self.wallet.as_ref(), self.anoncreds.as_ref())
+            .await?;
+        let mut referents = ["requested_attributes", "requested_predicates"]
+            .iter()
+            .filter_map(|field| request[field].as_object())
+            .flat_map(|requested| requested.keys());
+        Ok(referents.all(|referent| {
+            credentials
+                .credentials_by_referent
+                .get(referent)
+                .is_some_and(|matching| matching.len() == 1)
+        }))
+    }
+
+    pub async fn create_from_request(
         &self,
         connection_id: &str,
         request: RequestPresentationV1,
+        version: ProtocolVersion,
     ) -> AgentResult<String> {
         self.service_connections.get_by_id(connection_id)?;
         let prover = Prover::create_from_request("", request)?;
-        self.provers.insert(
-            &prover.get_thread_id()?,
-            ProverWrapper::new(prover, connection_id),
-        )
+        self.provers
+            .insert(
+                &prover.get_thread_id()?,
+                ProverWrapper::new(prover, connection_id, version),
+            )
+            .await
     }
 
     pub async fn send_proof_proposal(
         &self,
         connection_id: &str,
         proposal: PresentationProposalData,
+        version: ProtocolVersion,
     ) -> AgentResult<String> {
         let connection = self.service_connections.get_by_id(connection_id)?;
         let mut prover = Prover::create("")?;
@@ -126,11 +172,13 @@ impl<T: BaseWallet> ServiceProver<T> {
         });
 
         let proposal = prover.build_presentation_proposal(proposal).await?;
-        send_closure(proposal.into()).await?;
-        self.provers.insert(
-            &prover.get_thread_id()?,
-            ProverWrapper::new(prover, connection_id),
-        )
+        send_closure(version.outbound(proposal.into())?).await?;
+        self.provers
+            .insert(
+                &prover.get_thread_id()?,
+                ProverWrapper::new(prover, connection_id, version),
+            )
+            .await
     }
 
     pub fn is_secondary_proof_requested(&self, thread_id: &str) -> AgentResult<bool> {
@@ -148,6 +196,7 @@ impl<T: BaseWallet> ServiceProver<T> {
         let ProverWrapper {
             mut prover,
             connection_id,
+            version,
         } = self.provers.get(thread_id)?;
         let connection = self.service_connections.get_by_id(&connection_id)?;
         let credentials = self
@@ -157,7 +206,7 @@ impl<T: BaseWallet> ServiceProver<T> {
             .generate_presentation(
                 self.wallet.as_ref(),
                 self.ledger_read.as_ref(),
-                &self.anoncreds,
+                self.anoncreds.as_ref(),
                 credentials,
                 HashMap::new(),
             )
@@ -174,15 +223,17 @@ impl<T: BaseWallet> ServiceProver<T> {
         });
 
         let message = prover.mark_presentation_sent()?;
-        send_closure(message).await?;
-        self.provers.insert(
-            &prover.get_thread_id()?,
-            ProverWrapper::new(prover, &connection_id),
-        )?;
+        self.provers
+            .insert(
+                &prover.get_thread_id()?,
+                ProverWrapper::new(prover, &connection_id, version),
+            )
+            .await?;
+        send_closure(version.outbound(message)?).await?;
         Ok(())
     }
 
-    pub fn process_presentation_ack(
+    pub async fn process_presentation_ack(
         &self,
         thread_id: &str,
         ack: AckPresentationV1,
@@ -190,12 +241,15 @@ impl<T: BaseWallet> ServiceProver<T> {
         let ProverWrapper {
             mut prover,
             connection_id,
+            version,
         } = self.provers.get(thread_id)?;
         prover.process_presentation_ack(ack)?;
-        self.provers.insert(
-            &prover.get_thread_id()?,
-            ProverWrapper::new(prover, &connection_id),
-        )
+        self.provers
+            .insert(
+                &prover.get_thread_id()?,
+                ProverWrapper::new(prover, &connection_id, version),
+            )
+            .await
     }
 
     pub fn get_state(&self, thread_id: &str) -> AgentResult<ProverState> {
@@ -5,35 +5,41 @@ use std::{
 
 use anoncreds_types::data_types::identifiers::cred_def_id::CredentialDefinitionId;
 use aries_vcx::{common::primitives::revocation_registry::RevocationRegistry, did_parser_nom::Did};
-use aries_vcx_anoncreds::anoncreds::anoncreds::Anoncreds;
-use aries_vcx_ledger::ledger::indy_vdr_ledger::{DefaultIndyLedgerRead, DefaultIndyLedgerWrite};
+use aries_vcx_anoncreds::anoncreds::base_anoncreds::BaseAnonCreds;
+use aries_vcx_ledger::ledger::base_ledger::{AnoncredsLedgerRead, AnoncredsLedgerWrite};
 use aries_vcx_wallet::wallet::base_wallet::BaseWallet;
 
 use crate::{
     error::*,
-    storage::{agent_storage_inmem::AgentStorageInMem, AgentStorage},
+    storage::{agent_storage_wallet::AgentStorageWallet, AgentStorage},
 };
 
-pub struct ServiceRevocationRegistries<T> {
-    ledger_write: Arc<DefaultIndyLedgerWrite>,
-    ledger_read: Arc<DefaultIndyLedgerRead>,
-    anoncreds: Anoncreds,
+pub struct ServiceRevocationRegistries<T, LR, LW, A> {
+    ledger_write: Arc<LW>,
+    ledger_read: Arc<LR>,
+    anoncreds: Arc<A>,
     wallet: Arc<T>,
     issuer_did: Did,
-    rev_regs: AgentStorageInMem<RevocationRegistry>,
+    rev_regs: AgentStorageWallet<RevocationRegistry, T>,
 }
 
-impl<T: BaseWallet> ServiceRevocationRegistries<T> {
+impl<T, LR, LW, A> ServiceRevocationRegistries<T, LR, LW, A>
+where
+    T: BaseWallet,
+    LR: AnoncredsLedgerRead,
+    LW: AnoncredsLedgerWrite,
+    A: BaseAnonCreds,
+{
     pub fn new(
-        ledger_write: Arc<DefaultIndyLedgerWrite>,
-        ledger_read: Arc<DefaultIndyLedgerRead>,
-        anoncreds: Anoncreds,
+        ledger_write: Arc<LW>,
+        ledger_read: Arc<LR>,
+        anoncreds: Arc<A>,
         wallet: Arc<T>,
         issuer_did: String,
     ) -> Self {
         Self {
+            rev_regs: AgentStorageWallet::new("rev-regs", wallet.clone()),
             issuer_did: Did::parse(issuer_did).unwrap(), // TODO
-            rev_regs: AgentStorageInMem::new("rev-regs"),
             ledger_write,
             ledger_read,
             anoncreds,
@@ -41,6 +47,10 @@ impl<T: BaseWallet> ServiceRevocationRegistries<T> {
         }
     }
 
+    pub(crate) async fn load(&self) -> AgentResult<()> {
+        self.rev_regs.load().await
+    }
+
     fn get_tails_hash(&self, thread_id: &str) -> AgentResult<String> {
         let rev_reg = self.rev_regs.get(thread_id)?;
         Ok(rev_reg.get_rev_reg_def().value.tails_hash)
@@ -58,7 +68,7 @@ impl<T: BaseWallet> ServiceRevocationRegistries<T> {
     ) -> AgentResult<String> {
         let rev_reg = RevocationRegistry::create(
             self.wallet.as_ref(),
-            &self.anoncreds,
+            self.anoncreds.as_ref(),
             &self.issuer_did,
             cred_def_id,
             "/tmp",
@@ -66,7 +76,9 @@ impl<T: BaseWallet> ServiceRevocationRegistries<T> {
             1,
         )
         .await?;
-        self.rev_regs.insert(&rev_reg.get_rev_reg_id(), rev_reg)
+        self.rev_regs
+            .insert(&rev_reg.get_rev_reg_id(), rev_reg)
+            .await
     }
 
     pub fn tails_file_path(&self, thread_id: &str) -> AgentResult<String> {
@@ -91,7 +103,7 @@ impl<T: BaseWallet> ServiceRevocationRegistries<T> {
                 tails_url,
             )
             .await?;
-        self.rev_regs.insert(thread_id, rev_reg)?;
+        self.rev_regs.insert(thread_id, rev_reg).await?;
         Ok(())
     }
 
@@ -100,7 +112,7 @@ impl<T: BaseWallet> ServiceRevocationRegistries<T> {
         rev_reg
             .revoke_credential_local(
                 self.wallet.as_ref(),
-                &self.anoncreds,
+                self.anoncreds.as_ref(),
                 self.ledger_read.as_ref(),
                 cred_rev_id.parse()?,
             )
@@ -113,7 +125,7 @@ impl<T: BaseWallet> ServiceRevocationRegistries<T> {
         rev_reg
             .publish_local_revocations(
                 self.wallet.as_ref(),
-                &self.anoncreds,
+                self.anoncreds.as_ref(),
                 self.ledger_write.as_ref(),
                 &self.issuer_did,
             )
@@ -1,38 +1,41 @@
 use std::sync::{Arc, Mutex};
 
 use aries_vcx::{common::primitives::credential_schema::Schema, did_parser_nom::Did};
-use aries_vcx_anoncreds::anoncreds::anoncreds::Anoncreds;
-use aries_vcx_ledger::ledger::{
-    base_ledger::AnoncredsLedgerRead,
-    indy_vdr_ledger::{DefaultIndyLedgerRead, DefaultIndyLedgerWrite},
-};
+use aries_vcx_anoncreds::anoncreds::base_anoncreds::BaseAnonCreds;
+use aries_vcx_ledger::ledger::base_ledger::{AnoncredsLedgerRead, AnoncredsLedgerWrite};
 use aries_vcx_wallet::wallet::base_wallet::BaseWallet;
 
 use crate::{
     error::*,
-    storage::{agent_storage_inmem::AgentStorageInMem, AgentStorage},
+    storage::{agent_storage_wallet::AgentStorageWallet, AgentStorage},
 };
 
-pub struct ServiceSchemas<T> {
-    ledger_read: Arc<DefaultIndyLedgerRead>,
-    ledger_write: Arc<DefaultIndyLedgerWrite>,
-    anoncreds: Anoncreds,
+pub struct ServiceSchemas<T, LR, LW, A> {
+    ledger_read: Arc<LR>,
+    ledger_write: Arc<LW>,
+    anoncreds: Arc<A>,
     wallet: Arc<T>,
     issuer_did: Did,
-    schemas: AgentStorageInMem<Schema>,
+    schemas: AgentStorageWallet<Schema, T>,
 }
 
-impl<T: BaseWallet> ServiceSchemas<T> {
+impl<T, LR, LW, A> ServiceSchemas<T, LR, LW, A>
+where
+    T: BaseWallet,
+    LR: AnoncredsLedgerRead,
+    LW: AnoncredsLedgerWrite,
+    A: BaseAnonCreds,
+{
     pub fn new(
-        ledger_read: Arc<DefaultIndyLedgerRead>,
-        ledger_write: Arc<DefaultIndyLedgerWrite>,
-        anoncreds: Anoncreds,
+        ledger_read: Arc<LR>,
+        ledger_write: Arc<LW>,
+        anoncreds: Arc<A>,
         wallet: Arc<T>,
         issuer_did: String,
     ) -> Self {
         Self {
+            schemas: AgentStorageWallet::new("schemas", wallet.clone()),
             issuer_did: Did::parse(issuer_did).unwrap(), // TODO
-            schemas: AgentStorageInMem::new("schemas"),
             ledger_read,
             ledger_write,
             anoncreds,
@@ -40,6 +43,10 @@ impl<T: BaseWallet> ServiceSchemas<T> {
         }
     }
 
+    pub(crate) async fn load(&self) -> AgentResult<()> {
+        self.schemas.load().await
+    }
+
     pub async fn create_schema(
         &self,
         name: &str,
@@ -47,7 +54,7 @@ impl<T: BaseWallet> ServiceSchemas<T> {
         attributes: Vec<String>,
     ) -> AgentResult<String> {
         let schema = Schema::create(
-            &self.anoncreds,
+            self.anoncreds.as_ref(),
             "",
             &self.issuer_did,
             name,
@@ -57,6 +64,7 @@ impl<T: BaseWallet> ServiceSchemas<T> {
         .await?;
         self.schemas
             .insert(&schema.get_schema_id().to_string(), schema)
+            .await
     }
 
     pub async fn publish_schema(&self, thread_id: &str) -> AgentResult<()> {
@@ -64,7 +72,7 @@ impl<T: BaseWallet> ServiceSchemas<T> {
         let schema = schema
             .publish(self.wallet.as_ref(), self.ledger_write.as_ref())
             .await?;
-        self.schemas.insert(thread_id, schema)?;
+        self.schemas.insert(thread_id, schema).await?;
         Ok(())
     }
 
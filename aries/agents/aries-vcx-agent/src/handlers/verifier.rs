@@ -16,61 +16,93 @@ use aries_vcx::{
         SendClosure,
     },
 };
-use aries_vcx_anoncreds::anoncreds::anoncreds::Anoncreds;
-use aries_vcx_ledger::ledger::indy_vdr_ledger::DefaultIndyLedgerRead;
+use aries_vcx_anoncreds::anoncreds::base_anoncreds::BaseAnonCreds;
+use aries_vcx_ledger::ledger::base_ledger::AnoncredsLedgerRead;
 use aries_vcx_wallet::wallet::base_wallet::BaseWallet;
+use serde::{Deserialize, Serialize};
 
-use super::connection::ServiceConnections;
+use super::{connection::ServiceConnections, ProtocolVersion};
 use crate::{
     error::*,
+    events::{AgentEvent, EventBus},
     http::VcxHttpClient,
-    storage::{agent_storage_inmem::AgentStorageInMem, AgentStorage},
+    storage::{agent_storage_wallet::AgentStorageWallet, AgentStorage},
 };
 
-#[derive(Clone)]
+#[derive(Clone, Serialize, Deserialize)]
 struct VerifierWrapper {
     verifier: Verifier,
     connection_id: String,
+    #[serde(default)]
+    version: ProtocolVersion,
+    /// Received presentation awaiting verification
+    #[serde(default)]
+    presentation: Option<PresentationV1>,
 }
 
 impl VerifierWrapper {
-    pub fn new(verifier: Verifier, connection_id: &str) -> Self {
+    pub fn new(verifier: Verifier, connection_id: &str, version: ProtocolVersion) -> Self {
         Self {
             verifier,
             connection_id: connection_id.to_string(),
+            version,
+            presentation: None,
         }
     }
 }
 
-pub struct ServiceVerifier<T> {
-    ledger_read: Arc<DefaultIndyLedgerRead>,
-    anoncreds: Anoncreds,
+pub struct ServiceVerifier<T, LR, A> {
+    ledger_read: Arc<LR>,
+    anoncreds: Arc<A>,
     wallet: Arc<T>,
-    verifiers: AgentStorageInMem<VerifierWrapper>,
+    verifiers: AgentStorageWallet<VerifierWrapper, T>,
     service_connections: Arc<ServiceConnections<T>>,
 }
 
-impl<T: BaseWallet> ServiceVerifier<T> {
+impl<T, LR, A> ServiceVerifier<T, LR, A>
+where
+    T: BaseWallet,
+    LR: AnoncredsLedgerRead,
+    A: BaseAnonCreds,
+{
     pub fn new(
-        ledger_read: Arc<DefaultIndyLedgerRead>,
-        anoncreds: Anoncreds,
+        ledger_read: Arc<LR>,
+        anoncreds: Arc<A>,
         wallet: Arc<T>,
         service_connections: Arc<ServiceConnections<T>>,
+        events: EventBus,
     ) -> Self {
         Self {
+            verifiers: AgentStorageWallet::new("verifiers", wallet.clone()).with_events(
+                events,
+                |thread_id: &str, wrapper: &VerifierWrapper| AgentEvent::VerifierStateChanged {
+                    thread_id: thread_id.to_string(),
+                    connection_id: wrapper.connection_id.clone(),
+                    state: wrapper.verifier.get_state(),
+                },
+            ),
             service_connections,
-            verifiers: AgentStorageInMem::new("verifiers"),
             ledger_read,
             anoncreds,
             wallet,
         }
     }
 
+    pub(crate) async fn load(&self) -> AgentResult<()> {
+        self.verifiers.load().await
+    }
+
+    pub fn get_connection_id(&self, thread_id: &str) -> AgentResult<String> {
+        let VerifierWrapper { connection_id, .. } = self.verifiers.get(thread_id)?;
+        Ok(connection_id)
+    }
+
     pub async fn send_proof_request(
         &self,
         connection_id: &str,
         request: PresentationRequest,
         proposal: Option<ProposePresentationV1>,
+        version: ProtocolVersion,
     ) -> AgentResult<String> {
         let connection = self.service_connections.get_by_id(connection_id)?;
         let mut verifier = if let Some(proposal) = proposal {
@@ -88,11 +120,15 @@ impl<T: BaseWallet> ServiceVerifier<T> {
         });
 
         let message = verifier.mark_presentation_request_sent()?;
-        send_closure(message.into()).await?;
-        self.verifiers.insert(
-            &verifier.get_thread_id()?,
-            VerifierWrapper::new(verifier, connection_id),
-        )
+        let thread_id = self
+            .verifiers
+            .insert(
+                &verifier.get_thread_id()?,
+                VerifierWrapper::new(verifier, connection_id, version),
+            )
+            .await?;
+        send_closure(version.outbound(message.into())?).await?;
+        Ok(thread_id)
     }
 
     pub fn get_presentation_status(
@@ -111,6 +147,8 @@ impl<T: BaseWallet> ServiceVerifier<T> {
         let VerifierWrapper {
             mut verifier,
             connection_id,
+            version,
+            ..
         } = self.verifiers.get(thread_id)?;
         let connection = self.service_connections.get_by_id(&connection_id)?;
 
@@ -123,14 +161,50 @@ impl<T: BaseWallet> ServiceVerifier<T> {
         });
 
         let message = verifier
-            .verify_presentation(self.ledger_read.as_ref(), &self.anoncreds, presentation)
+            .verify_presentation(
+                self.ledger_read.as_ref(),
+                self.anoncreds.as_ref(),
+                presentation,
+            )
             .await?;
-        send_closure(message).await?;
+        send_closure(version.outbound(message)?).await?;
         self.verifiers
-            .insert(thread_id, VerifierWrapper::new(verifier, &connection_id))?;
+            .insert(
+                thread_id,
+                VerifierWrapper::new(verifier, &connection_id, version),
+            )
+            .await?;
         Ok(())
     }
 
+    /// Keeps the presentation to be verified later, see [`Self::verify_received_presentation`]
+    pub async fn receive_presentation(
+        &self,
+        thread_id: &str,
+        presentation: PresentationV1,
+    ) -> AgentResult<()> {
+        let mut wrapper = self.verifiers.get(thread_id)?;
+        wrapper.presentation = Some(presentation);
+        self.verifiers.insert(thread_id, wrapper).await?;
+        Ok(())
+    }
+
+    pub fn has_received_presentation(&self, thread_id: &str) -> AgentResult<bool> {
+        let VerifierWrapper { presentation, .. } = self.verifiers.get(thread_id)?;
+        Ok(presentation.is_some())
+    }
+
+    pub async fn verify_received_presentation(&self, thread_id: &str) -> AgentResult<()> {
+        let VerifierWrapper { presentation, .. } = self.verifiers.get(thread_id)?;
+        let presentation = presentation.ok_or_else(|| {
+            AgentError::from_msg(
+                AgentErrorKind::InvalidState,
+                &format!("No presentation received on thread {thread_id}"),
+            )
+        })?;
+        self.verify_presentation(thread_id, presentation).await
+    }
+
     pub fn get_state(&self, thread_id: &str) -> AgentResult<VerifierState> {
         let VerifierWrapper { verifier, .. } = self.verifiers.get(thread_id)?;
         Ok(verifier.get_state())
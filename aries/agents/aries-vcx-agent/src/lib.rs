@@ -6,11 +6,16 @@ extern crate log;
 pub extern crate aries_vcx;
 extern crate uuid;
 
+#[cfg(feature = "admin_api")]
+pub mod admin_api;
 mod agent;
 mod error;
+mod events;
 mod handlers;
 mod http;
 mod storage;
 
 pub use agent::*;
 pub use error::*;
+pub use events::*;
+pub use handlers::ProtocolVersion;
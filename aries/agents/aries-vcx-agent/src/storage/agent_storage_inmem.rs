@@ -4,6 +4,8 @@ use std::{
     sync::{Mutex, RwLock, RwLockReadGuard, RwLockWriteGuard},
 };
 
+use async_trait::async_trait;
+
 use super::AgentStorage;
 use crate::error::*;
 
@@ -57,9 +59,10 @@ where
     }
 }
 
+#[async_trait]
 impl<T> AgentStorage<T> for AgentStorageInMem<T>
 where
-    T: Clone,
+    T: Clone + Send,
 {
     type Value = Mutex<T>;
 
@@ -83,7 +86,7 @@ where
         }
     }
 
-    fn insert(&self, id: &str, obj: T) -> AgentResult<String> {
+    async fn insert(&self, id: &str, obj: T) -> AgentResult<String> {
         info!("Inserting object {} into in-memory store {}", id, self.name);
         let mut store = self.lock_store_write()?;
 
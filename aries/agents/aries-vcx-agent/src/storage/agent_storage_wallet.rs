@@ -0,0 +1,240 @@
+use std::sync::{Arc, Mutex};
+
+use aries_vcx_wallet::{
+    errors::error::VcxWalletError,
+    wallet::{
+        base_wallet::{record::Record, record_category::RecordCategory, BaseWallet},
+        record_tags::{RecordTag, RecordTags},
+    },
+};
+use async_trait::async_trait;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::Value;
+
+use super::{agent_storage_inmem::AgentStorageInMem, AgentStorage};
+use crate::{
+    error::*,
+    events::{AgentEvent, EventBus},
+};
+
+/// Version of the record layout written by this build. Records written by a newer version are
+/// refused on load rather than being misinterpreted.
+const RECORD_VERSION: u32 = 1;
+
+const STORE_TAG: &str = "store";
+
+/// Maps an object being inserted to the event announcing its new state
+pub type EventMapper<T> = fn(&str, &T) -> AgentEvent;
+
+#[derive(Serialize, Deserialize)]
+struct VersionedRecord<T> {
+    version: u32,
+    data: T,
+}
+
+/// Write-through storage, keeping objects in memory and persisting every insert as a wallet
+/// record, so that the protocol state survives agent restarts.
+pub struct AgentStorageWallet<T, W>
+where
+    T: Clone,
+{
+    cache: AgentStorageInMem<T>,
+    wallet: Arc<W>,
+    events: Option<(EventBus, EventMapper<T>)>,
+}
+
+impl<T, W> AgentStorageWallet<T, W>
+where
+    T: Clone + Serialize + DeserializeOwned + Send,
+    W: BaseWallet,
+{
+    pub fn new(name: &str, wallet: Arc<W>) -> Self {
+        Self {
+            cache: AgentStorageInMem::new(name),
+            wallet,
+            events: None,
+        }
+    }
+
+    /// Publishes an event on every insert, as produced by `mapper` from the inserted object
+    pub fn with_events(mut self, events: EventBus, mapper: EventMapper<T>) -> Self {
+        self.events = Some((events, mapper));
+        self
+    }
+
+    fn record_name(&self, id: &str) -> String {
+        format!("{}:{}", self.cache.name, id)
+    }
+
+    /// Loads all objects of this store previously persisted in the wallet
+    pub async fn load(&self) -> AgentResult<()> {
+        let query = serde_json::json!({ STORE_TAG: self.cache.name }).to_string();
+        let records = self
+            .wallet
+            .search_record(RecordCategory::AgentState, Some(query))
+            .await?;
+        info!(
+            "Loading {} objects into store {} from wallet",
+            records.len(),
+            self.cache.name
+        );
+        let prefix = format!("{}:", self.cache.name);
+        for record in records {
+            let Some(id) = record.name().strip_prefix(&prefix) else {
+                continue;
+            };
+            let versioned: VersionedRecord<Value> = serde_json::from_str(record.value())?;
+            if versioned.version > RECORD_VERSION {
+                return Err(AgentError::from_msg(
+                    AgentErrorKind::SerializationError,
+                    &format!(
+                        "Object {} in store {} has record version {}, only versions up to {} \
+                         are supported",
+                        id, self.cache.name, versioned.version, RECORD_VERSION
+                    ),
+                ));
+            }
+            let obj: T = serde_json::from_value(versioned.data)?;
+            self.cache.insert(id, obj).await?;
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<T, W> AgentStorage<T> for AgentStorageWallet<T, W>
+where
+    T: Clone + Serialize + DeserializeOwned + Send + Sync,
+    W: BaseWallet,
+{
+    type Value = Mutex<T>;
+
+    fn get(&self, id: &str) -> AgentResult<T> {
+        self.cache.get(id)
+    }
+
+    async fn insert(&self, id: &str, obj: T) -> AgentResult<String> {
+        let value = serde_json::to_string(&VersionedRecord {
+            version: RECORD_VERSION,
+            data: &obj,
+        })?;
+        let name = self.record_name(id);
+        let record = Record::builder()
+            .category(RecordCategory::AgentState)
+            .name(name.clone())
+            .value(value.clone())
+            .tags(RecordTags::new(vec![RecordTag::new(
+                STORE_TAG,
+                &self.cache.name,
+            )]))
+            .build();
+        match self.wallet.add_record(record).await {
+            Err(VcxWalletError::DuplicateRecord(_)) => {
+                self.wallet
+                    .update_record_value(RecordCategory::AgentState, &name, &value)
+                    .await?
+            }
+            res => res?,
+        };
+        let event = self
+            .events
+            .as_ref()
+            .map(|(events, mapper)| (events, mapper(id, &obj)));
+        let id = self.cache.insert(id, obj).await?;
+        // published only once the new state is observable through `get`
+        if let Some((events, event)) = event {
+            events.publish(event);
+        }
+        Ok(id)
+    }
+
+    fn contains_key(&self, id: &str) -> bool {
+        self.cache.contains_key(id)
+    }
+
+    fn find_by<F>(&self, closure: F) -> AgentResult<Vec<String>>
+    where
+        F: FnMut((&String, &Self::Value)) -> Option<String>,
+    {
+        self.cache.find_by(closure)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aries_vcx_wallet::wallet::{
+        askar::{askar_wallet_config::AskarWalletConfig, key_method::KeyMethod, AskarWallet},
+        base_wallet::ManageWallet,
+    };
+    use uuid::Uuid;
+
+    use super::*;
+
+    async fn build_wallet() -> Arc<AskarWallet> {
+        let config_wallet = AskarWalletConfig::new(
+            "sqlite://:memory:",
+            KeyMethod::Unprotected,
+            "",
+            &Uuid::new_v4().to_string(),
+        );
+        Arc::new(config_wallet.create_wallet().await.unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_load_restores_inserted_objects() {
+        let wallet = build_wallet().await;
+        let storage = AgentStorageWallet::<String, _>::new("connections", wallet.clone());
+        storage.insert("foo", "first".to_owned()).await.unwrap();
+        storage.insert("foo", "second".to_owned()).await.unwrap();
+        storage.insert("bar", "third".to_owned()).await.unwrap();
+
+        let restored = AgentStorageWallet::<String, _>::new("connections", wallet);
+        assert!(!restored.contains_key("foo"));
+        restored.load().await.unwrap();
+        assert_eq!(restored.get("foo").unwrap(), "second");
+        assert_eq!(restored.get("bar").unwrap(), "third");
+    }
+
+    #[tokio::test]
+    async fn test_load_refuses_newer_record_version() {
+        let wallet = build_wallet().await;
+        let value = serde_json::to_string(&VersionedRecord {
+            version: RECORD_VERSION + 1,
+            data: "from the future",
+        })
+        .unwrap();
+        let record = Record::builder()
+            .category(RecordCategory::AgentState)
+            .name("connections:foo".to_owned())
+            .value(value)
+            .tags(RecordTags::new(vec![RecordTag::new(
+                STORE_TAG,
+                "connections",
+            )]))
+            .build();
+        wallet.add_record(record).await.unwrap();
+
+        let storage = AgentStorageWallet::<String, _>::new("connections", wallet);
+        let err = storage.load().await.unwrap_err();
+        assert!(matches!(err.kind, AgentErrorKind::SerializationError));
+        assert!(!storage.contains_key("foo"));
+    }
+
+    #[tokio::test]
+    async fn test_stores_sharing_wallet_are_isolated() {
+        let wallet = build_wallet().await;
+        let connections = AgentStorageWallet::<String, _>::new("connections", wallet.clone());
+        let holders = AgentStorageWallet::<String, _>::new("holders", wallet.clone());
+        connections
+            .insert("foo", "connection".to_owned())
+            .await
+            .unwrap();
+        holders.insert("foo", "holder".to_owned()).await.unwrap();
+        holders.insert("bar", "holder".to_owned()).await.unwrap();
+
+        let connections = AgentStorageWallet::<String, _>::new("connections", wallet);
+        connections.load().await.unwrap();
+        assert_eq!(connections.get("foo").unwrap(), "connection");
+        assert!(!connections.contains_key("bar"));
+    }
+}
@@ -0,0 +1,197 @@
+use std::sync::Arc;
+
+use actix_web::{
+    http::StatusCode,
+    test::{self, TestRequest},
+    web, App,
+};
+use aries_vcx_agent::{
+    admin_api::{self, AdminApi, API_KEY_HEADER},
+    aries_vcx::aries_vcx_wallet::wallet::askar::AskarWallet,
+    build_askar_wallet, Agent, WalletInitConfig,
+};
+use aries_vcx_ledger::ledger::indy::pool::test_utils::{
+    create_testpool_genesis_txn_file, get_temp_file_path,
+};
+use serde_json::Value;
+use uuid::Uuid;
+
+const ISSUER_SEED: &str = "000000000000000000000000Trustee1";
+
+const API_KEY: &str = "admin-api-test-key";
+
+// The ledger is never contacted by the endpoints exercised here, the genesis file of a local pool
+// only lets the agent be built.
+async fn build_admin_api(api_key: Option<&str>) -> web::Data<AdminApi<AskarWallet>> {
+    let genesis_path = get_temp_file_path(&format!("admin_api_{}.txn", Uuid::new_v4()))
+        .to_str()
+        .unwrap()
+        .to_owned();
+    create_testpool_genesis_txn_file(&genesis_path);
+    let wallet_config = WalletInitConfig {
+        wallet_name: Uuid::new_v4().to_string(),
+        wallet_key: String::new(),
+        wallet_kdf: "RAW".to_owned(),
+    };
+    let (wallet, issuer_config) = build_askar_wallet(wallet_config, ISSUER_SEED.to_owned()).await;
+    let agent = Agent::initialize(
+        genesis_path,
+        Arc::new(wallet),
+        "http://127.0.0.1:8031/didcomm".parse().unwrap(),
+        issuer_config.institution_did.parse().unwrap(),
+    )
+    .await
+    .unwrap();
+    let admin_api = AdminApi::new(agent, vec![]);
+    web::Data::new(match api_key {
+        Some(api_key) => admin_api.with_api_key(api_key.to_owned()),
+        None => admin_api,
+    })
+}
+
+#[actix_web::test]
+async fn test_connection_invitation() {
+    let inviter = test::init_service(
+        App::new()
+            .app_data(build_admin_api(None).await)
+            .configure(admin_api::config::<AskarWallet>),
+    )
+    .await;
+    let invitee = test::init_service(
+        App::new()
+            .app_data(build_admin_api(None).await)
+            .configure(admin_api::config::<AskarWallet>),
+    )
+    .await;
+
+    let req = TestRequest::post()
+        .uri("/connections/create-invitation")
+        .to_request();
+    let created: Value = test::call_and_read_body_json(&inviter, req).await;
+    let connection_id = created["connection_id"].as_str().unwrap();
+    assert!(created["invitation"]["recipientKeys"].is_array());
+
+    let req = TestRequest::get()
+        .uri(&format!("/connections/{connection_id}"))
+        .to_request();
+    let record: Value = test::call_and_read_body_json(&inviter, req).await;
+    assert_eq!(record["connection_id"], connection_id);
+    assert_eq!(record["their_role"], "invitee");
+    assert_eq!(record["state"], "invitation");
+
+    let req = TestRequest::post()
+        .uri("/connections/receive-invitation")
+        .set_json(&created["invitation"])
+        .to_request();
+    let record: Value = test::call_and_read_body_json(&invitee, req).await;
+    assert_eq!(record["connection_id"], connection_id);
+    assert_eq!(record["their_role"], "inviter");
+    assert_eq!(record["state"], "invitation");
+}
+
+#[actix_web::test]
+async fn test_out_of_band_invitation() {
+    let sender = test::init_service(
+        App::new()
+            .app_data(build_admin_api(None).await)
+            .configure(admin_api::config::<AskarWallet>),
+    )
+    .await;
+    let receiver = test::init_service(
+        App::new()
+            .app_data(build_admin_api(None).await)
+            .configure(admin_api::config::<AskarWallet>),
+    )
+    .await;
+
+    let req = TestRequest::post()
+        .uri("/out-of-band/create-invitation")
+        .to_request();
+    let created: Value = test::call_and_read_body_json(&sender, req).await;
+    assert_eq!(created["state"], "initial");
+    let invitation_id = created["invi_msg_id"].as_str().unwrap();
+    assert_eq!(created["invitation"]["@id"], invitation_id);
+
+    let req = TestRequest::post()
+        .uri("/out-of-band/receive-invitation")
+        .set_json(&created["invitation"])
+        .to_request();
+    let received: Value = test::call_and_read_body_json(&receiver, req).await;
+    assert_eq!(received["invi_msg_id"], invitation_id);
+    assert_eq!(received["state"], "invitation-received");
+    assert!(received["connection_id"].is_null());
+}
+
+#[actix_web::test]
+async fn test_error_mapping() {
+    let app = test::init_service(
+        App::new()
+            .app_data(build_admin_api(None).await)
+            .configure(admin_api::config::<AskarWallet>),
+    )
+    .await;
+
+    let req = TestRequest::get().uri("/connections/unknown").to_request();
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status(), StatusCode::NOT_FOUND);
+    let body: Value = test::read_body_json(res).await;
+    assert!(body["reason"].is_string());
+
+    let req = TestRequest::post()
+        .uri("/didexchange/unknown/accept-request")
+        .to_request();
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status(), StatusCode::NOT_FOUND);
+
+    let req = TestRequest::post()
+        .uri("/didexchange/unknown/reject")
+        .to_request();
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status(), StatusCode::NOT_FOUND);
+
+    let req = TestRequest::post()
+        .uri("/connections/receive-invitation")
+        .set_payload("not an invitation")
+        .insert_header(("content-type", "application/json"))
+        .to_request();
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+}
+
+#[actix_web::test]
+async fn test_api_key_required() {
+    let app = test::init_service(
+        App::new()
+            .app_data(build_admin_api(Some(API_KEY)).await)
+            .configure(admin_api::config::<AskarWallet>),
+    )
+    .await;
+
+    let req = TestRequest::post()
+        .uri("/connections/create-invitation")
+        .to_request();
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+
+    let req = TestRequest::post()
+        .uri("/connections/create-invitation")
+        .insert_header((API_KEY_HEADER, "wrong-key"))
+        .to_request();
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+
+    let req = TestRequest::post()
+        .uri("/connections/create-invitation")
+        .insert_header((API_KEY_HEADER, API_KEY))
+        .to_request();
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status(), StatusCode::OK);
+
+    // Other agents deliver messages without the key
+    let req = TestRequest::post()
+        .uri("/didcomm")
+        .set_payload("not a message")
+        .to_request();
+    let res = test::call_service(&app, req).await;
+    assert_ne!(res.status(), StatusCode::UNAUTHORIZED);
+}
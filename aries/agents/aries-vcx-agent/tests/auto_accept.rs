@@ -0,0 +1,561 @@
+use std::{
+    collections::HashMap,
+    net::TcpListener,
+    sync::{Arc, Mutex},
+};
+
+use actix_web::{web, App, HttpResponse, HttpServer};
+use anoncreds_types::data_types::{
+    identifiers::{
+        cred_def_id::CredentialDefinitionId, rev_reg_def_id::RevocationRegistryDefinitionId,
+        schema_id::SchemaId,
+    },
+    ledger::{
+        cred_def::CredentialDefinition, rev_reg::RevocationRegistry,
+        rev_reg_def::RevocationRegistryDefinition, rev_reg_delta::RevocationRegistryDelta,
+        rev_status_list::RevocationStatusList, schema::Schema,
+    },
+    messages::pres_request::{AttributeInfo, PresentationRequestPayload},
+};
+use aries_vcx::{
+    did_parser_nom::Did,
+    handlers::util::OfferInfo,
+    messages::{msg_fields::protocols::out_of_band::OutOfBand, AriesMessage},
+    protocols::{
+        connection::{State, ThinState},
+        did_exchange::state_machine::generic::ThinState as DidExchangeState,
+        issuance::{holder::state_machine::HolderState, issuer::state_machine::IssuerState},
+        proof_presentation::{
+            prover::state_machine::ProverState,
+            verifier::{
+                state_machine::VerifierState, verification_status::PresentationVerificationStatus,
+            },
+        },
+    },
+};
+use aries_vcx_agent::{
+    build_askar_wallet, Agent, AgentEvent, AutoAcceptPolicy, ProtocolVersion, WalletInitConfig,
+};
+use aries_vcx_anoncreds::anoncreds::{anoncreds::Anoncreds, base_anoncreds::BaseAnonCreds};
+use aries_vcx_ledger::{
+    errors::error::{VcxLedgerError, VcxLedgerResult},
+    ledger::base_ledger::{AnoncredsLedgerRead, AnoncredsLedgerWrite},
+};
+use aries_vcx_wallet::wallet::{askar::AskarWallet, base_wallet::BaseWallet};
+use async_trait::async_trait;
+use did_peer::resolver::PeerDidResolver;
+use did_resolver_registry::ResolverRegistry;
+use serde_json::json;
+use url::Url;
+use uuid::Uuid;
+
+const ISSUER_SEED: &str = "000000000000000000000000Trustee1";
+
+type TestAgent = Agent<AskarWallet, InMemoryLedger, InMemoryLedger, Anoncreds>;
+
+/// Ledger shared by the agents of a test, holding schemas and credential definitions as JSON
+#[derive(Debug, Default)]
+struct InMemoryLedger {
+    objects: Mutex<HashMap<String, String>>,
+}
+
+impl InMemoryLedger {
+    fn get<T: serde::de::DeserializeOwned>(&self, id: &str) -> VcxLedgerResult<T> {
+        let objects = self.objects.lock().unwrap();
+        let object = objects.get(id).ok_or(VcxLedgerError::LedgerItemNotFound)?;
+        Ok(serde_json::from_str(object)?)
+    }
+
+    fn put<T: serde::Serialize>(&self, id: &str, object: &T) -> VcxLedgerResult<()> {
+        let object = serde_json::to_string(object)?;
+        self.objects.lock().unwrap().insert(id.to_owned(), object);
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl AnoncredsLedgerRead for InMemoryLedger {
+    type RevocationRegistryDefinitionAdditionalMetadata = ();
+
+    async fn get_schema(
+        &self,
+        schema_id: &SchemaId,
+        _submitter_did: Option<&Did>,
+    ) -> VcxLedgerResult<Schema> {
+        self.get(&schema_id.to_string())
+    }
+
+    async fn get_cred_def(
+        &self,
+        cred_def_id: &CredentialDefinitionId,
+        _submitter_did: Option<&Did>,
+    ) -> VcxLedgerResult<CredentialDefinition> {
+        self.get(&cred_def_id.to_string())
+    }
+
+    async fn get_rev_reg_def_json(
+        &self,
+        _rev_reg_id: &RevocationRegistryDefinitionId,
+    ) -> VcxLedgerResult<(RevocationRegistryDefinition, ())> {
+        Err(VcxLedgerError::LedgerItemNotFound)
+    }
+
+    async fn get_rev_reg_delta_json(
+        &self,
+        _rev_reg_id: &RevocationRegistryDefinitionId,
+        _from: Option<u64>,
+        _to: Option<u64>,
+    ) -> VcxLedgerResult<(RevocationRegistryDelta, u64)> {
+        Err(VcxLedgerError::LedgerItemNotFound)
+    }
+
+    async fn get_rev_status_list(
+        &self,
+        _rev_reg_id: &RevocationRegistryDefinitionId,
+        _timestamp: u64,
+        _rev_reg_def_meta: Option<&()>,
+    ) -> VcxLedgerResult<(RevocationStatusList, u64)> {
+        Err(VcxLedgerError::LedgerItemNotFound)
+    }
+
+    async fn get_rev_reg(
+        &self,
+        _rev_reg_id: &RevocationRegistryDefinitionId,
+        _timestamp: u64,
+    ) -> VcxLedgerResult<(RevocationRegistry, u64)> {
+        Err(VcxLedgerError::LedgerItemNotFound)
+    }
+}
+
+#[async_trait]
+impl AnoncredsLedgerWrite for InMemoryLedger {
+    async fn publish_schema(
+        &self,
+        _wallet: &impl BaseWallet,
+        schema_json: Schema,
+        _submitter_did: &Did,
+        _endorser_did: Option<&Did>,
+    ) -> VcxLedgerResult<()> {
+        self.put(&schema_json.id.to_string(), &schema_json)
+    }
+
+    async fn publish_cred_def(
+        &self,
+        _wallet: &impl BaseWallet,
+        cred_def_json: CredentialDefinition,
+        _submitter_did: &Did,
+    ) -> VcxLedgerResult<()> {
+        self.put(&cred_def_json.id.to_string(), &cred_def_json)
+    }
+
+    async fn publish_rev_reg_def(
+        &self,
+        _wallet: &impl BaseWallet,
+        _rev_reg_def: RevocationRegistryDefinition,
+        _submitter_did: &Did,
+    ) -> VcxLedgerResult<()> {
+        Err(VcxLedgerError::UnimplementedFeature(
+            "Revocation is not supported by the test ledger".to_owned(),
+        ))
+    }
+
+    async fn publish_rev_reg_delta(
+        &self,
+        _wallet: &impl BaseWallet,
+        _rev_reg_id: &RevocationRegistryDefinitionId,
+        _rev_reg_entry_json: RevocationRegistryDelta,
+        _submitter_did: &Did,
+    ) -> VcxLedgerResult<()> {
+        Err(VcxLedgerError::UnimplementedFeature(
+            "Revocation is not supported by the test ledger".to_owned(),
+        ))
+    }
+}
+
+async fn receive_message(agent: web::Data<TestAgent>, payload: web::Bytes) -> HttpResponse {
+    match agent.receive_message(&payload).await {
+        Ok(()) => HttpResponse::Ok().finish(),
+        Err(err) => HttpResponse::InternalServerError().body(err.to_string()),
+    }
+}
+
+/// Builds an agent receiving messages on a local port, handling each message before answering
+/// its delivery, so that the steps taken automatically are over once a call returns
+async fn start_agent(ledger: &Arc<InMemoryLedger>) -> TestAgent {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let endpoint: Url = format!("http://{}/didcomm", listener.local_addr().unwrap())
+        .parse()
+        .unwrap();
+    let wallet_config = WalletInitConfig {
+        wallet_name: Uuid::new_v4().to_string(),
+        wallet_key: String::new(),
+        wallet_kdf: "RAW".to_owned(),
+    };
+    let (wallet, issuer_config) = build_askar_wallet(wallet_config, ISSUER_SEED.to_owned()).await;
+    let resolver_registry =
+        ResolverRegistry::new().register_resolver("peer".into(), PeerDidResolver::new());
+    let agent = Agent::build(
+        Arc::new(wallet),
+        ledger.clone(),
+        ledger.clone(),
+        Arc::new(Anoncreds),
+        None,
+        Arc::new(resolver_registry),
+        endpoint,
+        issuer_config.institution_did.parse().unwrap(),
+    )
+    .await
+    .unwrap();
+
+    let server_agent = web::Data::new(agent.clone());
+    let server = HttpServer::new(move || {
+        App::new()
+            .app_data(server_agent.clone())
+            .route("/didcomm", web::post().to(receive_message))
+    })
+    .workers(1)
+    .listen(listener)
+    .unwrap()
+    .run();
+    actix_web::rt::spawn(server);
+    agent
+}
+
+/// Connects the agents through the connection protocol, returning the id of the connection on
+/// each side
+async fn connect(inviter: &TestAgent, invitee: &TestAgent) -> (String, String) {
+    inviter
+        .set_auto_accept_policy(AutoAcceptPolicy {
+            connection_requests: true,
+            ..Default::default()
+        })
+        .unwrap();
+    invitee
+        .set_auto_accept_policy(AutoAcceptPolicy {
+            invitations: true,
+            ..Default::default()
+        })
+        .unwrap();
+    let invitation = inviter.connections().create_invitation(None).await.unwrap();
+    let inviter_connection_id = invitation.id().to_owned();
+    let invitee_connection_id = invitee
+        .receive_connection_invitation(invitation)
+        .await
+        .unwrap();
+    assert!(matches!(
+        invitee
+            .connections()
+            .get_state(&invitee_connection_id)
+            .unwrap(),
+        ThinState::Invitee(State::Completed)
+    ));
+    inviter
+        .set_auto_accept_policy(AutoAcceptPolicy::default())
+        .unwrap();
+    invitee
+        .set_auto_accept_policy(AutoAcceptPolicy::default())
+        .unwrap();
+    (inviter_connection_id, invitee_connection_id)
+}
+
+async fn create_cred_def(issuer: &TestAgent) -> String {
+    let schema_id = issuer
+        .schemas()
+        .create_schema("test-schema", "1.0", vec!["name".to_owned()])
+        .await
+        .unwrap();
+    issuer.schemas().publish_schema(&schema_id).await.unwrap();
+    let cred_def_id = issuer
+        .cred_defs()
+        .create_cred_def(
+            Did::parse(issuer.issuer_did()).unwrap(),
+            SchemaId::new(&schema_id).unwrap(),
+            "tag".to_owned(),
+        )
+        .await
+        .unwrap();
+    issuer
+        .cred_defs()
+        .publish_cred_def(&cred_def_id)
+        .await
+        .unwrap();
+    cred_def_id
+}
+
+async fn send_offer(issuer: &TestAgent, connection_id: &str, cred_def_id: &str) -> String {
+    let offer_info = OfferInfo {
+        credential_json: json!({ "name": "alice" }).to_string(),
+        cred_def_id: CredentialDefinitionId::new(cred_def_id).unwrap(),
+        rev_reg_id: None,
+        tails_file: None,
+    };
+    issuer
+        .issuer()
+        .send_credential_offer(None, Some(connection_id), offer_info, ProtocolVersion::V1)
+        .await
+        .unwrap()
+}
+
+async fn send_proof_request(verifier: &TestAgent, connection_id: &str) -> String {
+    let requested_attributes = HashMap::from([(
+        "name".to_owned(),
+        AttributeInfo {
+            name: Some("name".to_owned()),
+            ..AttributeInfo::default()
+        },
+    )]);
+    let request = PresentationRequestPayload::builder()
+        .name("test-request".to_owned())
+        .requested_attributes(requested_attributes)
+        .nonce(verifier.anoncreds().generate_nonce().await.unwrap())
+        .build();
+    verifier
+        .verifier()
+        .send_proof_request(connection_id, request.into_v1(), None, ProtocolVersion::V1)
+        .await
+        .unwrap()
+}
+
+#[actix_web::test]
+async fn test_connection_invitation_and_request() {
+    let ledger = Arc::new(InMemoryLedger::default());
+    let inviter = start_agent(&ledger).await;
+    let invitee = start_agent(&ledger).await;
+
+    // Nothing happens until the invitation is accepted
+    let invitation = inviter.connections().create_invitation(None).await.unwrap();
+    let connection_id = invitee
+        .receive_connection_invitation(invitation)
+        .await
+        .unwrap();
+    assert!(matches!(
+        invitee.connections().get_state(&connection_id).unwrap(),
+        ThinState::Invitee(State::Invited)
+    ));
+
+    // The request waits for the inviter
+    invitee
+        .set_auto_accept_policy(AutoAcceptPolicy {
+            invitations: true,
+            ..Default::default()
+        })
+        .unwrap();
+    let invitation = inviter.connections().create_invitation(None).await.unwrap();
+    let connection_id = invitee
+        .receive_connection_invitation(invitation)
+        .await
+        .unwrap();
+    assert!(matches!(
+        inviter.connections().get_state(&connection_id).unwrap(),
+        ThinState::Inviter(State::Requested)
+    ));
+    assert!(matches!(
+        invitee.connections().get_state(&connection_id).unwrap(),
+        ThinState::Invitee(State::Requested)
+    ));
+
+    // The inviter responds on its own
+    inviter
+        .set_auto_accept_policy(AutoAcceptPolicy {
+            connection_requests: true,
+            ..Default::default()
+        })
+        .unwrap();
+    let invitation = inviter.connections().create_invitation(None).await.unwrap();
+    let connection_id = invitee
+        .receive_connection_invitation(invitation)
+        .await
+        .unwrap();
+    assert!(matches!(
+        invitee.connections().get_state(&connection_id).unwrap(),
+        ThinState::Invitee(State::Completed)
+    ));
+}
+
+#[actix_web::test]
+async fn test_did_exchange_request() {
+    let ledger = Arc::new(InMemoryLedger::default());
+    let responder = start_agent(&ledger).await;
+    let requester = start_agent(&ledger).await;
+    requester
+        .set_auto_accept_policy(AutoAcceptPolicy {
+            invitations: true,
+            ..Default::default()
+        })
+        .unwrap();
+
+    // Requests not accepted automatically are left to the application
+    let mut events = responder.subscribe();
+    let invitation = responder.out_of_band().create_invitation().await.unwrap();
+    let (_, thread_id) = requester.receive_oob_invitation(invitation).await.unwrap();
+    let thread_id = thread_id.unwrap();
+    assert!(matches!(
+        requester.did_exchange().get_state(&thread_id).unwrap(),
+        DidExchangeState::RequestSent
+    ));
+    let mut request_received = false;
+    while let Ok(event) = events.try_recv() {
+        request_received |= matches!(event, AgentEvent::DidExchangeRequestReceived { .. });
+    }
+    assert!(request_received);
+
+    // The policy set for an invitation applies to the requests answering it
+    let invitation = responder.out_of_band().create_invitation().await.unwrap();
+    let AriesMessage::OutOfBand(OutOfBand::Invitation(oob_invitation)) = &invitation else {
+        panic!("Unexpected invitation: {invitation}");
+    };
+    responder
+        .set_connection_auto_accept_policy(
+            &oob_invitation.id,
+            AutoAcceptPolicy {
+                connection_requests: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    let (_, thread_id) = requester.receive_oob_invitation(invitation).await.unwrap();
+    let thread_id = thread_id.unwrap();
+    assert!(matches!(
+        requester.did_exchange().get_state(&thread_id).unwrap(),
+        DidExchangeState::Completed
+    ));
+    assert!(matches!(
+        responder.did_exchange().get_state(&thread_id).unwrap(),
+        DidExchangeState::Completed
+    ));
+}
+
+#[actix_web::test]
+async fn test_credential_offer_and_request() {
+    let ledger = Arc::new(InMemoryLedger::default());
+    let issuer = start_agent(&ledger).await;
+    let holder = start_agent(&ledger).await;
+    let (issuer_connection_id, holder_connection_id) = connect(&issuer, &holder).await;
+    let cred_def_id = create_cred_def(&issuer).await;
+
+    // The offer waits for the holder
+    let thread_id = send_offer(&issuer, &issuer_connection_id, &cred_def_id).await;
+    assert_eq!(
+        holder.holder().get_state(&thread_id).unwrap(),
+        HolderState::OfferReceived
+    );
+
+    // The request waits for the issuer
+    holder
+        .set_connection_auto_accept_policy(
+            &holder_connection_id,
+            AutoAcceptPolicy {
+                credential_offers: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    let thread_id = send_offer(&issuer, &issuer_connection_id, &cred_def_id).await;
+    assert_eq!(
+        holder.holder().get_state(&thread_id).unwrap(),
+        HolderState::RequestSet
+    );
+    assert_eq!(
+        issuer.issuer().get_state(&thread_id).unwrap(),
+        IssuerState::RequestReceived
+    );
+
+    // The issuer issues on its own
+    issuer
+        .set_connection_auto_accept_policy(
+            &issuer_connection_id,
+            AutoAcceptPolicy {
+                credential_requests: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    let thread_id = send_offer(&issuer, &issuer_connection_id, &cred_def_id).await;
+    assert_eq!(
+        holder.holder().get_state(&thread_id).unwrap(),
+        HolderState::Finished
+    );
+    assert_eq!(holder.holder().list_credentials().await.unwrap().len(), 1);
+}
+
+#[actix_web::test]
+async fn test_presentation_request_and_presentation() {
+    let ledger = Arc::new(InMemoryLedger::default());
+    let issuer = start_agent(&ledger).await;
+    let holder = start_agent(&ledger).await;
+    let (issuer_connection_id, holder_connection_id) = connect(&issuer, &holder).await;
+    let cred_def_id = create_cred_def(&issuer).await;
+    issuer
+        .set_connection_auto_accept_policy(
+            &issuer_connection_id,
+            AutoAcceptPolicy {
+                credential_requests: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    holder
+        .set_connection_auto_accept_policy(
+            &holder_connection_id,
+            AutoAcceptPolicy {
+                credential_offers: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    send_offer(&issuer, &issuer_connection_id, &cred_def_id).await;
+
+    // The request waits for the prover
+    let thread_id = send_proof_request(&issuer, &issuer_connection_id).await;
+    assert_eq!(
+        holder.prover().get_state(&thread_id).unwrap(),
+        ProverState::PresentationRequestReceived
+    );
+
+    // The presentation is kept for the verifier to verify
+    holder
+        .set_connection_auto_accept_policy(
+            &holder_connection_id,
+            AutoAcceptPolicy {
+                presentation_requests: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    let thread_id = send_proof_request(&issuer, &issuer_connection_id).await;
+    assert_eq!(
+        holder.prover().get_state(&thread_id).unwrap(),
+        ProverState::PresentationSent
+    );
+    assert!(issuer
+        .verifier()
+        .has_received_presentation(&thread_id)
+        .unwrap());
+    assert_eq!(
+        issuer.verifier().get_state(&thread_id).unwrap(),
+        VerifierState::PresentationRequestSent
+    );
+
+    // The verifier verifies on its own
+    issuer
+        .set_connection_auto_accept_policy(
+            &issuer_connection_id,
+            AutoAcceptPolicy {
+                presentations: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    let thread_id = send_proof_request(&issuer, &issuer_connection_id).await;
+    assert_eq!(
+        issuer.verifier().get_state(&thread_id).unwrap(),
+        VerifierState::Finished
+    );
+    assert_eq!(
+        issuer
+            .verifier()
+            .get_presentation_status(&thread_id)
+            .unwrap(),
+        PresentationVerificationStatus::Valid
+    );
+}
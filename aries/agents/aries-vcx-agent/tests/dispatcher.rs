@@ -0,0 +1,176 @@
+use std::sync::Arc;
+
+use aries_vcx_agent::{
+    aries_vcx::{
+        aries_vcx_anoncreds::anoncreds::anoncreds::Anoncreds,
+        handlers::util::AnyInvitation,
+        messages::{
+            decorators::thread::Thread,
+            msg_fields::protocols::{
+                connection::{
+                    request::{Request, RequestContent, RequestDecorators},
+                    ConnectionData,
+                },
+                notification::problem_report::{
+                    NotificationProblemReport, NotificationProblemReportContent,
+                },
+                report_problem::{Description, ProblemReportContent},
+                trust_ping::ping::{Ping, PingContent},
+            },
+            AriesMessage,
+        },
+        protocols::connection::{pairwise_info::PairwiseInfo, State, ThinState},
+    },
+    build_askar_wallet, Agent, AgentErrorKind, AgentEvent, WalletInitConfig,
+};
+use aries_vcx_ledger::ledger::{cheqd::CheqdAnoncredsLedgerRead, read_only::ReadOnlyLedgerWrite};
+use aries_vcx_wallet::wallet::askar::AskarWallet;
+use did_cheqd::resolution::resolver::{DidCheqdResolver, DidCheqdResolverConfiguration};
+use did_peer::resolver::PeerDidResolver;
+use did_resolver_registry::ResolverRegistry;
+use diddoc_legacy::aries::diddoc::AriesDidDoc;
+use public_key::{Key, KeyType};
+use uuid::Uuid;
+
+const ISSUER_SEED: &str = "000000000000000000000000Trustee1";
+const UNKNOWN_VK: &str = "7Z9ZajGKvb6BMsZ9TBEqxMHktxGdts3FvAbKSJT5XgzK";
+
+type TestAgent = Agent<AskarWallet, CheqdAnoncredsLedgerRead, ReadOnlyLedgerWrite, Anoncreds>;
+
+// Nothing is sent out, every step past the received message is manual
+async fn build_agent() -> TestAgent {
+    let wallet_config = WalletInitConfig {
+        wallet_name: Uuid::new_v4().to_string(),
+        wallet_key: String::new(),
+        wallet_kdf: "RAW".to_owned(),
+    };
+    let (wallet, issuer_config) = build_askar_wallet(wallet_config, ISSUER_SEED.to_owned()).await;
+
+    let cheqd_resolver = Arc::new(DidCheqdResolver::new(
+        DidCheqdResolverConfiguration::default(),
+    ));
+    let did_resolver_registry =
+        Arc::new(ResolverRegistry::new().register_resolver("peer".into(), PeerDidResolver::new()));
+    Agent::build(
+        Arc::new(wallet),
+        Arc::new(CheqdAnoncredsLedgerRead::new(cheqd_resolver)),
+        Arc::new(ReadOnlyLedgerWrite),
+        Arc::new(Anoncreds),
+        None,
+        did_resolver_registry,
+        "http://127.0.0.1:8040/didcomm".parse().unwrap(),
+        issuer_config.institution_did.parse().unwrap(),
+    )
+    .await
+    .unwrap()
+}
+
+fn build_ping() -> AriesMessage {
+    let ping: Ping = Ping::builder()
+        .id(Uuid::new_v4().to_string())
+        .content(PingContent::builder().response_requested(true).build())
+        .build();
+    ping.into()
+}
+
+#[tokio::test]
+async fn test_message_from_unknown_sender_is_refused() {
+    let agent = build_agent().await;
+    let recipient_vk = Key::from_base58(UNKNOWN_VK, KeyType::Ed25519).unwrap();
+    let sender_vk = Key::from_base58(UNKNOWN_VK, KeyType::Ed25519).unwrap();
+
+    let err = agent
+        .handle_message(build_ping(), Some(sender_vk), recipient_vk.clone())
+        .await
+        .unwrap_err();
+    assert!(matches!(err.kind, AgentErrorKind::InvalidState));
+
+    let err = agent
+        .handle_message(build_ping(), None, recipient_vk)
+        .await
+        .unwrap_err();
+    assert!(matches!(err.kind, AgentErrorKind::InvalidArguments));
+}
+
+#[tokio::test]
+async fn test_connection_request_is_routed_to_its_invitation() {
+    let agent = build_agent().await;
+    let mut events = agent.subscribe();
+    let invitation = agent.connections().create_invitation(None).await.unwrap();
+    let AnyInvitation::Con(invitation) = invitation else {
+        panic!("Expected a connection invitation");
+    };
+    let connection_id = invitation.id.clone();
+
+    let invitee = PairwiseInfo::create(agent.wallet().as_ref()).await.unwrap();
+    let mut did_doc = AriesDidDoc::default();
+    did_doc.set_id(invitee.pw_did.clone());
+    did_doc.set_recipient_keys(vec![invitee.pw_vk.clone()]);
+    did_doc.set_routing_keys(Vec::new());
+    did_doc.set_service_endpoint("http://127.0.0.1:8041/didcomm".parse().unwrap());
+    let content = RequestContent::builder()
+        .label("invitee".to_owned())
+        .connection(ConnectionData::new(invitee.pw_did.clone(), did_doc))
+        .build();
+    let decorators = RequestDecorators::builder()
+        .thread(Thread::builder().thid(connection_id.clone()).build())
+        .build();
+    let request: Request = Request::builder()
+        .id(Uuid::new_v4().to_string())
+        .content(content)
+        .decorators(decorators)
+        .build();
+
+    let sender_vk = Key::from_base58(&invitee.pw_vk, KeyType::Ed25519).unwrap();
+    let recipient_vk = Key::from_base58(UNKNOWN_VK, KeyType::Ed25519).unwrap();
+    agent
+        .handle_message(request.into(), Some(sender_vk), recipient_vk)
+        .await
+        .unwrap();
+
+    assert!(matches!(
+        agent.connections().get_state(&connection_id).unwrap(),
+        ThinState::Inviter(State::Requested)
+    ));
+    let mut last_state = None;
+    while let Ok(event) = events.try_recv() {
+        if let AgentEvent::ConnectionStateChanged {
+            connection_id: id,
+            state,
+        } = event
+        {
+            assert_eq!(id, connection_id);
+            last_state = Some(state);
+        }
+    }
+    assert!(matches!(
+        last_state,
+        Some(ThinState::Inviter(State::Requested))
+    ));
+}
+
+#[tokio::test]
+async fn test_problem_report_is_published() {
+    let agent = build_agent().await;
+    let mut events = agent.subscribe();
+    let content = ProblemReportContent::builder()
+        .description(Description::builder().code("test".to_owned()).build())
+        .build();
+    let problem_report: NotificationProblemReport = NotificationProblemReport::builder()
+        .id(Uuid::new_v4().to_string())
+        .content(NotificationProblemReportContent::from(content))
+        .build();
+    let recipient_vk = Key::from_base58(UNKNOWN_VK, KeyType::Ed25519).unwrap();
+
+    agent
+        .handle_message(problem_report.clone().into(), None, recipient_vk)
+        .await
+        .unwrap();
+
+    match events.try_recv().unwrap() {
+        AgentEvent::ProblemReportReceived {
+            problem_report: received,
+        } => assert_eq!(received, problem_report),
+        event => panic!("Unexpected event {event:?}"),
+    }
+}
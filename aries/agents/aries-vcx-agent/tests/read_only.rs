@@ -0,0 +1,60 @@
+use std::sync::Arc;
+
+use aries_vcx_agent::{
+    aries_vcx::{aries_vcx_anoncreds::anoncreds::anoncreds::Anoncreds, messages::AriesMessage},
+    build_askar_wallet, Agent, WalletInitConfig,
+};
+use aries_vcx_ledger::ledger::{cheqd::CheqdAnoncredsLedgerRead, read_only::ReadOnlyLedgerWrite};
+use did_cheqd::resolution::resolver::{DidCheqdResolver, DidCheqdResolverConfiguration};
+use did_peer::resolver::PeerDidResolver;
+use did_resolver_registry::ResolverRegistry;
+use uuid::Uuid;
+
+const ISSUER_SEED: &str = "000000000000000000000000Trustee1";
+
+// Neither the cheqd network nor an Indy pool is contacted, the resolver connects lazily
+#[tokio::test]
+async fn test_build_cheqd_only_agent() {
+    let wallet_config = WalletInitConfig {
+        wallet_name: Uuid::new_v4().to_string(),
+        wallet_key: String::new(),
+        wallet_kdf: "RAW".to_owned(),
+    };
+    let (wallet, issuer_config) = build_askar_wallet(wallet_config, ISSUER_SEED.to_owned()).await;
+
+    let cheqd_resolver = Arc::new(DidCheqdResolver::new(
+        DidCheqdResolverConfiguration::default(),
+    ));
+    let did_resolver_registry = Arc::new(
+        ResolverRegistry::new()
+            .register_resolver("peer".into(), PeerDidResolver::new())
+            .register_resolver("cheqd".into(), cheqd_resolver.clone()),
+    );
+    let agent = Agent::build(
+        Arc::new(wallet),
+        Arc::new(CheqdAnoncredsLedgerRead::new(cheqd_resolver)),
+        Arc::new(ReadOnlyLedgerWrite),
+        Arc::new(Anoncreds),
+        None,
+        did_resolver_registry,
+        "http://127.0.0.1:8040/didcomm".parse().unwrap(),
+        issuer_config.institution_did.parse().unwrap(),
+    )
+    .await
+    .unwrap();
+
+    let invitation = agent.out_of_band().create_invitation().await.unwrap();
+    assert!(matches!(invitation, AriesMessage::OutOfBand(_)));
+
+    let schema_id = agent
+        .schemas()
+        .create_schema("test_schema", "1.0", vec!["name".to_owned()])
+        .await
+        .unwrap();
+    let err = agent
+        .schemas()
+        .publish_schema(&schema_id)
+        .await
+        .unwrap_err();
+    assert!(err.message.contains("read-only"));
+}
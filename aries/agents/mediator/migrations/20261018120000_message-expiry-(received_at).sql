@@ -0,0 +1,6 @@
+-- Track when each message was received so that undelivered messages
+-- can be purged after the configured TTL (MESSAGE_TTL_SECS)
+
+ALTER TABLE messages
+    ADD COLUMN received_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    ADD INDEX(received_at);
@@ -1,4 +1,7 @@
-use std::{marker::PhantomData, sync::Arc};
+use std::{
+    marker::PhantomData,
+    sync::{Arc, RwLock},
+};
 
 use aries_vcx::{
     handlers::out_of_band::sender::OutOfBandSender,
@@ -21,10 +24,13 @@ use messages::{
     },
     AriesMessage,
 };
+use public_key::{Key, KeyType};
 use serde_json::json;
 use uuid::Uuid;
 
 use crate::{
+    mediation::limits::MediatorLimits,
+    metrics::MediatorMetrics,
     persistence::{get_persistence, AccountDetails, MediatorPersistence},
     utils::{prelude::*, structs::VerKey},
 };
@@ -37,7 +43,10 @@ pub mod utils;
 pub struct Agent<T: BaseWallet, P: MediatorPersistence> {
     wallet: Arc<T>,
     persistence: Arc<P>,
-    service: Option<AriesService>,
+    // Shared so that the service (and thus OOB invitation) can be rotated while serving requests
+    service: Arc<RwLock<Option<AriesService>>>,
+    limits: MediatorLimits,
+    metrics: Arc<MediatorMetrics>,
 }
 
 pub type ArcAgent<T, P> = Arc<Agent<T, P>>;
@@ -50,15 +59,21 @@ impl<T: BaseWallet> AgentBuilder<T> {
     pub async fn new_from_wallet_config(
         config: impl ManageWallet,
     ) -> Result<Agent<impl BaseWallet, sqlx::MySqlPool>, VcxWalletError> {
-        let wallet = Arc::new(config.create_wallet().await?);
+        let wallet = config.create_wallet().await?;
 
         info!("Connecting to persistence layer");
-        let persistence = Arc::new(get_persistence().await);
-        Ok(Agent {
-            wallet,
-            persistence,
-            service: None,
-        })
+        let persistence = get_persistence().await;
+        Ok(AgentBuilder::new_from_wallet(wallet, persistence))
+    }
+    /// Creates agent from already opened wallet and persistence, such as those of one tenant
+    pub fn new_from_wallet<P: MediatorPersistence>(wallet: T, persistence: P) -> Agent<T, P> {
+        Agent {
+            wallet: Arc::new(wallet),
+            persistence: Arc::new(persistence),
+            service: Arc::new(RwLock::new(None)),
+            limits: MediatorLimits::default(),
+            metrics: Arc::new(MediatorMetrics::default()),
+        }
     }
     pub async fn new_demo_agent() -> Result<Agent<impl BaseWallet, sqlx::MySqlPool>, VcxWalletError>
     {
@@ -80,11 +95,20 @@ impl<T: BaseWallet, P: MediatorPersistence> Agent<T, P> {
     pub fn get_persistence_ref(&self) -> Arc<impl MediatorPersistence> {
         self.persistence.clone()
     }
-    pub fn get_service_ref(&self) -> Option<&AriesService> {
-        self.service.as_ref()
+    pub fn get_service(&self) -> Option<AriesService> {
+        self.service.read().unwrap().clone()
+    }
+    pub fn get_limits_ref(&self) -> &MediatorLimits {
+        &self.limits
+    }
+    pub fn get_metrics_ref(&self) -> Arc<MediatorMetrics> {
+        self.metrics.clone()
+    }
+    pub fn set_limits(&mut self, limits: MediatorLimits) {
+        self.limits = limits;
     }
     pub async fn reset_service(
-        &mut self,
+        &self,
         routing_keys: Vec<String>,
         service_endpoint: url::Url,
     ) -> Result<(), VcxWalletError> {
@@ -97,7 +121,7 @@ impl<T: BaseWallet, P: MediatorPersistence> Agent<T, P> {
             routing_keys,
             service_endpoint,
         };
-        self.service = Some(service);
+        *self.service.write().unwrap() = Some(service);
         Ok(())
     }
 
@@ -108,10 +132,20 @@ impl<T: BaseWallet, P: MediatorPersistence> Agent<T, P> {
     ) -> Result<(), VcxWalletError> {
         self.reset_service(routing_keys, service_endpoint).await
     }
+    /// Replaces service recipient key with a freshly generated one, keeping endpoint and routing
+    /// keys. Invitations created before the rotation can no longer be used to connect.
+    pub async fn rotate_service_key(&self) -> Result<(), String> {
+        let service = self
+            .get_service()
+            .ok_or("No service to rotate".to_owned())?;
+        self.reset_service(service.routing_keys, service.service_endpoint)
+            .await
+            .map_err(string_from_std_error)
+    }
     pub fn get_oob_invite(&self) -> Result<OOBInvitation, String> {
-        if let Some(service) = &self.service {
+        if let Some(service) = self.get_service() {
             let invitation = OutOfBandSender::create()
-                .append_service(&OobService::AriesService(service.clone()))
+                .append_service(&OobService::AriesService(service))
                 .oob;
             Ok(invitation)
         } else {
@@ -144,6 +178,27 @@ impl<T: BaseWallet, P: MediatorPersistence> Agent<T, P> {
         .map_err(string_from_std_error)
     }
 
+    /// Packs message for party known only by its verkey, such as forwarder of a message
+    pub async fn pack_didcomm_for_verkey(
+        &self,
+        message: &[u8],
+        our_vk: &VerKey,
+        their_vk: &VerKey,
+    ) -> Result<EncryptionEnvelope, String> {
+        let our_key = Key::from_base58(our_vk, KeyType::Ed25519).map_err(string_from_std_error)?;
+        let their_key =
+            Key::from_base58(their_vk, KeyType::Ed25519).map_err(string_from_std_error)?;
+        EncryptionEnvelope::create_from_keys(
+            self.wallet.as_ref(),
+            message,
+            Some(our_key),
+            their_key,
+            vec![],
+        )
+        .await
+        .map_err(string_from_std_error)
+    }
+
     pub async fn auth_and_get_details(
         &self,
         sender_verkey: &Option<VerKey>,
@@ -177,14 +232,10 @@ impl<T: BaseWallet, P: MediatorPersistence> Agent<T, P> {
             .create_and_store_my_did(None, None)
             .await
             .map_err(|e| e.to_string())?;
-        let old_vk = self
-            .service
-            .as_ref()
-            .unwrap()
-            .recipient_keys
-            .first()
-            .unwrap()
-            .to_owned();
+        let service = self
+            .get_service()
+            .ok_or("Mediator has no service defined")?;
+        let old_vk = service.recipient_keys.first().unwrap().to_owned();
 
         let response: Response = utils::build_response_content(
             self.wallet.as_ref(),
@@ -192,8 +243,8 @@ impl<T: BaseWallet, P: MediatorPersistence> Agent<T, P> {
             old_vk.clone(),
             did_data.did().into(),
             did_data.verkey().base58(),
-            self.service.as_ref().unwrap().service_endpoint.clone(),
-            self.service.as_ref().unwrap().routing_keys.clone(),
+            service.service_endpoint,
+            service.routing_keys,
         )
         .await
         .map_err(|e| e.to_string())?;
@@ -260,7 +311,7 @@ mod test {
             oob_invitation_to_legacy_did_doc(&mock_ledger, &agent.get_oob_invite().unwrap())
                 .await
                 .unwrap();
-        let our_service = agent.service.as_ref().unwrap();
+        let our_service = agent.get_service().unwrap();
         let our_vk = our_service.recipient_keys.first().unwrap();
         let EncryptionEnvelope(packed) = agent
             .pack_didcomm(&message_bytes, our_vk, &their_diddoc)
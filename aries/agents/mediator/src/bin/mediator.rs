@@ -1,8 +1,15 @@
-use aries_vcx_wallet::wallet::askar::{
-    askar_wallet_config::AskarWalletConfig, key_method::KeyMethod, AskarWallet,
+use aries_vcx_wallet::wallet::{
+    askar::{askar_wallet_config::AskarWalletConfig, key_method::KeyMethod, AskarWallet},
+    base_wallet::BaseWallet,
 };
 use log::info;
-use mediator::aries_agent::AgentBuilder;
+use mediator::{
+    aries_agent::{Agent, AgentBuilder},
+    http_routes::{build_multi_tenant_router, build_router, TenantRouter},
+    mediation::limits::{spawn_expired_message_gc, MediatorLimits},
+    persistence::MediatorPersistence,
+    tenancy::{build_tenant_agent, load_tenant_configs},
+};
 use uuid::Uuid;
 
 #[tokio::main]
@@ -26,17 +33,46 @@ async fn main() {
             serde_json::from_str(&json).unwrap()
         });
     info!("Wallet Config: {:?}", wallet_config);
-    let mut agent = AgentBuilder::<AskarWallet>::new_from_wallet_config(wallet_config)
-        .await
-        .unwrap();
-    agent
-        .init_service(
-            vec![],
-            format!("http://{endpoint_root}/didcomm").parse().unwrap(),
-        )
-        .await
-        .unwrap();
-    let app_router = mediator::http_routes::build_router(agent).await;
+    let limits = MediatorLimits::from_env();
+    info!("Mediator limits: {:?}", limits);
+    let admin_api_token = std::env::var("ADMIN_API_TOKEN").ok();
+    if admin_api_token.is_none() {
+        info!("ADMIN_API_TOKEN not set, admin API is disabled unless a tenant sets its own token");
+    }
+    let app_router = if let Ok(tenants_config) = std::env::var("TENANTS_CONFIG") {
+        info!(
+            "Running multi-tenant mediator, tenants read from {}",
+            tenants_config
+        );
+        let tenants = load_tenant_configs(&tenants_config).unwrap();
+        let mut tenant_routers = vec![];
+        for tenant in tenants {
+            let mut agent = build_tenant_agent(&tenant, &wallet_config, &endpoint_root)
+                .await
+                .unwrap();
+            setup_limits(&mut agent, limits);
+            let tenant_admin_api_token = tenant.admin_api_token(admin_api_token.as_ref());
+            tenant_routers.push(TenantRouter {
+                id: tenant.id,
+                host: tenant.host,
+                router: build_router(agent, tenant_admin_api_token).await,
+            });
+        }
+        build_multi_tenant_router(tenant_routers)
+    } else {
+        let mut agent = AgentBuilder::<AskarWallet>::new_from_wallet_config(wallet_config)
+            .await
+            .unwrap();
+        agent
+            .init_service(
+                vec![],
+                format!("http://{endpoint_root}/didcomm").parse().unwrap(),
+            )
+            .await
+            .unwrap();
+        setup_limits(&mut agent, limits);
+        build_router(agent, admin_api_token).await
+    };
     info!("Starting server");
     let listener = tokio::net::TcpListener::bind(&endpoint_root).await.unwrap();
     axum::serve(listener, app_router.into_make_service())
@@ -44,6 +80,21 @@ async fn main() {
         .unwrap();
 }
 
+fn setup_limits<T: BaseWallet, P: MediatorPersistence>(
+    agent: &mut Agent<T, P>,
+    limits: MediatorLimits,
+) {
+    if let Some(message_ttl) = limits.message_ttl {
+        spawn_expired_message_gc(
+            agent.get_persistence_ref(),
+            agent.get_metrics_ref(),
+            message_ttl,
+            limits.gc_interval,
+        );
+    }
+    agent.set_limits(limits);
+}
+
 fn setup_logging() {
     let env = env_logger::Env::default().default_filter_or("info");
     env_logger::init_from_env(env);
@@ -1,15 +1,24 @@
 use aries_vcx_wallet::wallet::base_wallet::BaseWallet;
-use messages::msg_fields::protocols::{notification::ack::Ack, routing::Forward};
+use messages::msg_fields::protocols::{
+    notification::ack::{Ack, AckStatus},
+    report_problem::ProblemReport,
+    routing::Forward,
+};
 
 use super::{utils::prelude::*, ArcAgent};
-use crate::mediation::forward::handle_forward;
+use crate::{mediation::forward::handle_forward, metrics::DROP_REASON_STORAGE_ERROR};
 
 pub async fn handle_routing_forward(
     agent: ArcAgent<impl BaseWallet, impl MediatorPersistence>,
     forward: Forward,
-) -> Result<Ack, String> {
+) -> Result<Ack, ProblemReport> {
     info!("{:?}", forward);
-    let ack = handle_forward(agent.get_persistence_ref(), forward).await;
-
-    Ok(ack)
+    let result = handle_forward(agent.get_persistence_ref(), forward, agent.get_limits_ref()).await;
+    let metrics = agent.get_metrics_ref();
+    match &result {
+        Ok(ack) if ack.content.status == AckStatus::Ok => metrics.record_forwarded(),
+        Ok(_) => metrics.record_dropped(DROP_REASON_STORAGE_ERROR, 1),
+        Err(problem_report) => metrics.record_dropped(&problem_report.content.description.code, 1),
+    }
+    result
 }
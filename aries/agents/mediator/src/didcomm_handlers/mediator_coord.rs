@@ -13,7 +13,7 @@ pub async fn handle_mediation_coord(
 ) -> Result<CoordinateMediation, String> {
     if let CoordinateMediation::MediateRequest(_mediate_request) = coord_msg {
         let service = agent
-            .get_service_ref()
+            .get_service()
             .ok_or("Mediation agent must have service defined.")?;
         let mut routing_keys = Vec::new();
         routing_keys.extend_from_slice(&service.routing_keys);
@@ -43,8 +43,23 @@ pub async fn handle_aries<T: BaseWallet, P: MediatorPersistence>(
         } else if let GeneralAriesMessage::AriesVCXSupported(AriesMessage::Routing(forward)) =
             aries_message
         {
-            handle_routing_forward(agent.clone(), forward).await?;
-            return Ok(Json(json!({})));
+            let Err(problem_report) = handle_routing_forward(agent.clone(), forward).await else {
+                return Ok(Json(json!({})));
+            };
+            let aries_response = AriesMessage::ReportProblem(problem_report);
+            let Some(forwarder_verkey) = &unpacked.sender_verkey else {
+                // Anonymous forwarder can only be answered in plaintext
+                return Ok(Json(json!(aries_response)));
+            };
+            let aries_response_bytes =
+                serde_json::to_vec(&aries_response).map_err(string_from_std_error)?;
+            agent
+                .pack_didcomm_for_verkey(
+                    &aries_response_bytes,
+                    &unpacked.recipient_verkey,
+                    forwarder_verkey,
+                )
+                .await?
         } else {
             // Authenticated flow: Auth known VerKey then process account related messages
             let account_details = agent.auth_and_get_details(&unpacked.sender_verkey).await?;
@@ -14,5 +14,10 @@ pub async fn handle_pickup_protocol(
         auth_pubkey,
     )
     .await;
+    if let Pickup::Delivery(delivery) = &pickup_response {
+        agent
+            .get_metrics_ref()
+            .record_delivered(delivery.content.attach.len() as u64);
+    }
     Ok(pickup_response)
 }
@@ -0,0 +1,185 @@
+use std::sync::Arc;
+
+use aries_vcx_wallet::wallet::base_wallet::BaseWallet;
+use axum::{
+    extract::{Path, Request, State},
+    http::{
+        header::{AUTHORIZATION, CONTENT_TYPE},
+        StatusCode,
+    },
+    middleware::{self, Next},
+    response::{IntoResponse, Response},
+    routing::{get, post},
+    Json, Router,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use subtle::ConstantTimeEq;
+
+use crate::{
+    aries_agent::ArcAgent,
+    persistence::{
+        errors::{DeleteAccountError, GetAccountDetailsError},
+        MediatorPersistence,
+    },
+    utils::structs::VerKey,
+};
+
+type AdminError = (StatusCode, String);
+
+fn internal_error(err: impl std::fmt::Display) -> AdminError {
+    (StatusCode::INTERNAL_SERVER_ERROR, err.to_string())
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AccountSummary {
+    pub account_name: String,
+    pub auth_pubkey: VerKey,
+    pub pending_message_count: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RecipientKeySummary {
+    pub recipient_key: VerKey,
+    pub pending_message_count: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AccountOverview {
+    pub account_name: String,
+    pub auth_pubkey: VerKey,
+    pub our_signing_key: VerKey,
+    pub pending_message_count: u32,
+    pub recipient_keys: Vec<RecipientKeySummary>,
+}
+
+/// Rejects requests not carrying `Authorization: Bearer <admin api token>` header. The token is
+/// compared in constant time, so that response timing does not leak how much of it matched.
+async fn require_admin_token(
+    State(admin_api_token): State<Arc<String>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let authorized = request
+        .headers()
+        .get(AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .is_some_and(|token| bool::from(token.as_bytes().ct_eq(admin_api_token.as_bytes())));
+    if !authorized {
+        return (StatusCode::UNAUTHORIZED, "Invalid or missing admin token").into_response();
+    }
+    next.run(request).await
+}
+
+pub async fn list_accounts<T: BaseWallet, P: MediatorPersistence>(
+    State(agent): State<ArcAgent<T, P>>,
+) -> Result<Json<Vec<AccountSummary>>, AdminError> {
+    let persistence = agent.get_persistence_ref();
+    let accounts = persistence.list_accounts().await.map_err(internal_error)?;
+    let mut summaries = Vec::with_capacity(accounts.len());
+    for (account_name, auth_pubkey) in accounts {
+        let pending_message_count = persistence
+            .retrieve_pending_message_count(&auth_pubkey, None)
+            .await
+            .map_err(internal_error)?;
+        summaries.push(AccountSummary {
+            account_name,
+            auth_pubkey,
+            pending_message_count,
+        });
+    }
+    Ok(Json(summaries))
+}
+
+pub async fn get_account<T: BaseWallet, P: MediatorPersistence>(
+    State(agent): State<ArcAgent<T, P>>,
+    Path(auth_pubkey): Path<VerKey>,
+) -> Result<Json<AccountOverview>, AdminError> {
+    let persistence = agent.get_persistence_ref();
+    let account_details = persistence
+        .get_account_details(&auth_pubkey)
+        .await
+        .map_err(|err| match err {
+            GetAccountDetailsError::AccountNotFound(_) => (StatusCode::NOT_FOUND, err.to_string()),
+            _ => internal_error(err),
+        })?;
+    let mut recipient_keys = vec![];
+    for recipient_key in persistence
+        .list_recipient_keys(&auth_pubkey)
+        .await
+        .map_err(internal_error)?
+    {
+        let pending_message_count = persistence
+            .retrieve_pending_message_count(&auth_pubkey, Some(&recipient_key))
+            .await
+            .map_err(internal_error)?;
+        recipient_keys.push(RecipientKeySummary {
+            recipient_key,
+            pending_message_count,
+        });
+    }
+    let pending_message_count = persistence
+        .retrieve_pending_message_count(&auth_pubkey, None)
+        .await
+        .map_err(internal_error)?;
+    Ok(Json(AccountOverview {
+        account_name: account_details.account_name,
+        auth_pubkey: account_details.auth_pubkey,
+        our_signing_key: account_details.our_signing_key,
+        pending_message_count,
+        recipient_keys,
+    }))
+}
+
+pub async fn delete_account<T: BaseWallet, P: MediatorPersistence>(
+    State(agent): State<ArcAgent<T, P>>,
+    Path(auth_pubkey): Path<VerKey>,
+) -> Result<StatusCode, AdminError> {
+    agent
+        .get_persistence_ref()
+        .delete_account(&auth_pubkey)
+        .await
+        .map_err(|err| match err {
+            DeleteAccountError::AccountNotFound(_) => (StatusCode::NOT_FOUND, err.to_string()),
+            _ => internal_error(err),
+        })?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Rotates mediator's service key and returns the new OOB invitation
+pub async fn rotate_invitation<T: BaseWallet, P: MediatorPersistence>(
+    State(agent): State<ArcAgent<T, P>>,
+) -> Result<Json<Value>, AdminError> {
+    agent.rotate_service_key().await.map_err(internal_error)?;
+    let oob = agent.get_oob_invite().map_err(internal_error)?;
+    Ok(Json(serde_json::to_value(oob).map_err(internal_error)?))
+}
+
+pub async fn metrics<T: BaseWallet, P: MediatorPersistence>(
+    State(agent): State<ArcAgent<T, P>>,
+) -> Response {
+    (
+        [(CONTENT_TYPE, "text/plain; version=0.0.4")],
+        agent.get_metrics_ref().render_prometheus(),
+    )
+        .into_response()
+}
+
+/// Admin routes, all of which require the admin api token as bearer token
+pub fn build_admin_router<T: BaseWallet + 'static, P: MediatorPersistence>(
+    admin_api_token: String,
+) -> Router<ArcAgent<T, P>> {
+    Router::new()
+        .route("/accounts", get(list_accounts::<T, P>))
+        .route(
+            "/accounts/{auth_pubkey}",
+            get(get_account::<T, P>).delete(delete_account::<T, P>),
+        )
+        .route("/invitation/rotate", post(rotate_invitation::<T, P>))
+        .route("/metrics", get(metrics::<T, P>))
+        .route_layer(middleware::from_fn_with_state(
+            Arc::new(admin_api_token),
+            require_admin_token,
+        ))
+}
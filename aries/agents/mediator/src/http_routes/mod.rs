@@ -1,16 +1,22 @@
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc};
 
 use aries_vcx_wallet::wallet::base_wallet::BaseWallet;
 use axum::{
     body::Bytes,
-    extract::State,
-    http::header::{HeaderMap, ACCEPT},
+    extract::{Request, State},
+    http::{
+        header::{HeaderMap, ACCEPT, HOST},
+        StatusCode,
+    },
     response::{Html, IntoResponse, Response},
     routing::get,
     Json, Router,
 };
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use tower::ServiceExt;
+
+pub mod admin;
 
 use crate::{
     aries_agent::{Agent, ArcAgent},
@@ -58,13 +64,64 @@ pub async fn readme(headers: HeaderMap) -> Response {
     }
 }
 
-pub async fn build_router(
-    agent: Agent<impl BaseWallet + 'static, impl MediatorPersistence>,
+/// Builds mediator router. Admin API is served under `/admin` only if admin api token is given.
+pub async fn build_router<T: BaseWallet + 'static, P: MediatorPersistence>(
+    agent: Agent<T, P>,
+    admin_api_token: Option<String>,
 ) -> Router {
-    Router::default()
+    let mut router = Router::default()
         .route("/", get(readme))
         .route("/invitation", get(oob_invite_json))
-        .route("/didcomm", get(handle_didcomm).post(handle_didcomm))
+        .route("/didcomm", get(handle_didcomm).post(handle_didcomm));
+    if let Some(admin_api_token) = admin_api_token {
+        router = router.nest("/admin", admin::build_admin_router(admin_api_token));
+    }
+    router
         .layer(tower_http::catch_panic::CatchPanicLayer::new())
         .with_state(Arc::new(agent))
 }
+
+/// Routes of one tenant of a multi-tenant mediator
+pub struct TenantRouter {
+    pub id: String,
+    pub host: Option<String>,
+    pub router: Router,
+}
+
+/// Serves each tenant under `/tenants/{id}`, and additionally at the root path for requests
+/// whose `Host` header matches the tenant's host.
+pub fn build_multi_tenant_router(tenants: Vec<TenantRouter>) -> Router {
+    let mut router = Router::default().route("/", get(readme));
+    let mut routers_by_host = HashMap::new();
+    for tenant in tenants {
+        if let Some(host) = tenant.host {
+            routers_by_host.insert(host, tenant.router.clone());
+        }
+        router = router.nest(&format!("/tenants/{}", tenant.id), tenant.router);
+    }
+    let routers_by_host = Arc::new(routers_by_host);
+    router.fallback(move |request: Request| dispatch_by_host(routers_by_host.clone(), request))
+}
+
+async fn dispatch_by_host(
+    routers_by_host: Arc<HashMap<String, Router>>,
+    request: Request,
+) -> Response {
+    let host = request
+        .headers()
+        .get(HOST)
+        .and_then(|host| host.to_str().ok())
+        .unwrap_or_default();
+    // configured host may or may not include the port
+    let router = routers_by_host.get(host).or_else(|| {
+        host.rsplit_once(':')
+            .and_then(|(hostname, _port)| routers_by_host.get(hostname))
+    });
+    match router {
+        Some(router) => match router.clone().oneshot(request).await {
+            Ok(response) => response,
+            Err(infallible) => match infallible {},
+        },
+        None => StatusCode::NOT_FOUND.into_response(),
+    }
+}
@@ -2,5 +2,7 @@ pub mod aries_agent;
 pub mod didcomm_handlers;
 pub mod http_routes;
 pub mod mediation;
+pub mod metrics;
 pub mod persistence;
+pub mod tenancy;
 pub mod utils;
@@ -8,23 +8,51 @@ use messages::{
     decorators::thread::Thread,
     msg_fields::protocols::{
         notification::ack::{Ack, AckContent, AckDecorators, AckStatus},
+        report_problem::{
+            Description, Impact, ProblemReport, ProblemReportContent, ProblemReportDecorators,
+            WhoRetries,
+        },
         routing::Forward,
     },
 };
 use uuid::Uuid;
 
-use crate::persistence::MediatorPersistence;
+use super::limits::MediatorLimits;
+use crate::persistence::{errors::PersistForwardMessageError, MediatorPersistence};
 
-pub async fn handle_forward<T>(storage: Arc<T>, forward_msg: Forward) -> Ack
+pub const PROBLEM_CODE_MESSAGE_TOO_LARGE: &str = "message-too-large";
+pub const PROBLEM_CODE_QUOTA_EXCEEDED: &str = "quota-exceeded";
+
+/// Persists forwarded message for its recipient.
+/// If the message is rejected due to configured limits, returns problem report addressed to
+/// the forwarder instead of an ack.
+pub async fn handle_forward<T>(
+    storage: Arc<T>,
+    forward_msg: Forward,
+    limits: &MediatorLimits,
+) -> Result<Ack, ProblemReport>
 where
     T: MediatorPersistence,
 {
     info!("Persisting forward message");
     debug!("{forward_msg:#?}");
-    let _ack_status = match storage
+    let message_data = serde_json::to_string(&forward_msg.content.msg).unwrap();
+    if !limits.message_size_allowed(message_data.len()) {
+        info!(
+            "Rejecting forward of {} bytes, exceeds maximum message size",
+            message_data.len()
+        );
+        return Err(build_problem_report(
+            &forward_msg.id,
+            PROBLEM_CODE_MESSAGE_TOO_LARGE,
+            "Forwarded message exceeds maximum message size accepted by mediator",
+        ));
+    }
+    let ack_status = match storage
         .persist_forward_message(
             &forward_msg.content.to,
-            &serde_json::to_string(&forward_msg.content.msg).unwrap(),
+            &message_data,
+            &limits.account_quota,
         )
         .await
     {
@@ -32,18 +60,43 @@ where
             info!("Persisted forward");
             AckStatus::Ok
         }
+        Err(PersistForwardMessageError::QuotaExceeded(e)) => {
+            info!("Rejecting forward: {}", e);
+            return Err(build_problem_report(
+                &forward_msg.id,
+                PROBLEM_CODE_QUOTA_EXCEEDED,
+                "Recipient has too many undelivered messages queued at mediator",
+            ));
+        }
         Err(e) => {
             info!("Error when persisting forward: {}", e);
             AckStatus::Pending
         }
     };
-    let ack_content = AckContent::builder().status(AckStatus::Ok).build();
+    let ack_content = AckContent::builder().status(ack_status).build();
     let ack_deco = AckDecorators::builder()
         .thread(Thread::builder().thid(forward_msg.id).build())
         .build();
-    Ack::builder()
+    Ok(Ack::builder()
         .content(ack_content)
         .decorators(ack_deco)
         .id(Uuid::new_v4().to_string())
+        .build())
+}
+
+fn build_problem_report(thid: &str, code: &str, explanation: &str) -> ProblemReport {
+    let content = ProblemReportContent::builder()
+        .description(Description::builder().code(code.to_owned()).build())
+        .who_retries(WhoRetries::You)
+        .impact(Impact::MessageContent)
+        .fix_hint(explanation.to_owned())
+        .build();
+    let decorators = ProblemReportDecorators::builder()
+        .thread(Thread::builder().thid(thid.to_owned()).build())
+        .build();
+    ProblemReport::builder()
+        .id(Uuid::new_v4().to_string())
+        .content(content)
+        .decorators(decorators)
         .build()
 }
@@ -0,0 +1,119 @@
+use std::{sync::Arc, time::Duration};
+
+use log::{error, info};
+use tokio::task::JoinHandle;
+
+use crate::{
+    metrics::{MediatorMetrics, DROP_REASON_EXPIRED},
+    persistence::{AccountQuota, MediatorPersistence},
+};
+
+const DEFAULT_GC_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Operator configurable limits on messages the mediator queues for its clients.
+/// `None` means no limit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MediatorLimits {
+    /// Limits on pending messages per account
+    pub account_quota: AccountQuota,
+    /// Maximum size in bytes of a single forwarded message
+    pub max_message_size: Option<u64>,
+    /// Pending messages older than this are purged by the garbage collector
+    pub message_ttl: Option<Duration>,
+    /// How often the garbage collector looks for expired messages
+    pub gc_interval: Duration,
+}
+
+impl Default for MediatorLimits {
+    fn default() -> Self {
+        Self {
+            account_quota: AccountQuota::default(),
+            max_message_size: None,
+            message_ttl: None,
+            gc_interval: DEFAULT_GC_INTERVAL,
+        }
+    }
+}
+
+impl MediatorLimits {
+    /// Reads limits from environment variables
+    /// `MAX_MESSAGES_PER_ACCOUNT`, `MAX_BYTES_PER_ACCOUNT`, `MAX_MESSAGE_SIZE`,
+    /// `MESSAGE_TTL_SECS` and `MESSAGE_GC_INTERVAL_SECS`.
+    /// Unset variables leave the respective limit disabled.
+    pub fn from_env() -> Self {
+        Self {
+            account_quota: AccountQuota {
+                max_message_count: read_env_var("MAX_MESSAGES_PER_ACCOUNT"),
+                max_total_bytes: read_env_var("MAX_BYTES_PER_ACCOUNT"),
+            },
+            max_message_size: read_env_var("MAX_MESSAGE_SIZE"),
+            message_ttl: read_env_var("MESSAGE_TTL_SECS").map(Duration::from_secs),
+            gc_interval: read_env_var("MESSAGE_GC_INTERVAL_SECS")
+                .map_or(DEFAULT_GC_INTERVAL, Duration::from_secs),
+        }
+    }
+
+    pub fn message_size_allowed(&self, message_size: usize) -> bool {
+        self.max_message_size.map_or(true, |max_size| {
+            u64::try_from(message_size).is_ok_and(|size| size <= max_size)
+        })
+    }
+}
+
+fn read_env_var<T: std::str::FromStr>(name: &str) -> Option<T> {
+    let value = std::env::var(name).ok()?;
+    match value.parse() {
+        Ok(parsed) => Some(parsed),
+        Err(_) => {
+            error!(
+                "Ignoring invalid value {:?} of environment variable {}",
+                value, name
+            );
+            None
+        }
+    }
+}
+
+/// Spawns background task periodically deleting messages which were not picked up within ttl
+pub fn spawn_expired_message_gc(
+    storage: Arc<impl MediatorPersistence>,
+    metrics: Arc<MediatorMetrics>,
+    ttl: Duration,
+    interval: Duration,
+) -> JoinHandle<()> {
+    info!(
+        "Starting garbage collection of messages older than {:?}, every {:?}",
+        ttl, interval
+    );
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        loop {
+            ticker.tick().await;
+            match storage.purge_expired_messages(ttl).await {
+                Ok(purged_count) => metrics.record_dropped(DROP_REASON_EXPIRED, purged_count),
+                Err(err) => error!("Failed to purge expired messages: {}", err),
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_message_size_unlimited_by_default() {
+        let limits = MediatorLimits::default();
+        assert!(limits.message_size_allowed(usize::MAX));
+    }
+
+    #[test]
+    fn test_message_size_limit() {
+        let limits = MediatorLimits {
+            max_message_size: Some(10),
+            ..Default::default()
+        };
+        assert!(limits.message_size_allowed(10));
+        assert!(!limits.message_size_allowed(11));
+    }
+}
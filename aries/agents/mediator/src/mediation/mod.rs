@@ -1,3 +1,4 @@
 pub mod coordination;
 pub mod forward;
+pub mod limits;
 pub mod pickup;
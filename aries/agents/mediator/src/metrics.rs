@@ -0,0 +1,108 @@
+use std::{
+    collections::BTreeMap,
+    fmt::Write,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Mutex,
+    },
+};
+
+/// Message counters of the mediator, exposed in Prometheus text format by the admin API
+#[derive(Debug, Default)]
+pub struct MediatorMetrics {
+    forwarded: AtomicU64,
+    delivered: AtomicU64,
+    // keyed by reason
+    dropped: Mutex<BTreeMap<String, u64>>,
+}
+
+pub const DROP_REASON_STORAGE_ERROR: &str = "storage-error";
+pub const DROP_REASON_EXPIRED: &str = "expired";
+
+impl MediatorMetrics {
+    pub fn record_forwarded(&self) {
+        self.forwarded.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_delivered(&self, count: u64) {
+        self.delivered.fetch_add(count, Ordering::Relaxed);
+    }
+
+    pub fn record_dropped(&self, reason: &str, count: u64) {
+        let mut dropped = self.dropped.lock().unwrap();
+        *dropped.entry(reason.to_owned()).or_default() += count;
+    }
+
+    pub fn forwarded(&self) -> u64 {
+        self.forwarded.load(Ordering::Relaxed)
+    }
+
+    pub fn delivered(&self) -> u64 {
+        self.delivered.load(Ordering::Relaxed)
+    }
+
+    pub fn dropped(&self, reason: &str) -> u64 {
+        self.dropped
+            .lock()
+            .unwrap()
+            .get(reason)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Renders counters in Prometheus text exposition format
+    pub fn render_prometheus(&self) -> String {
+        let mut out = String::new();
+        // Writing into String can't fail
+        let _ = writeln!(
+            out,
+            "# HELP mediator_messages_forwarded_total Forwarded messages queued for pickup."
+        );
+        let _ = writeln!(out, "# TYPE mediator_messages_forwarded_total counter");
+        let _ = writeln!(
+            out,
+            "mediator_messages_forwarded_total {}",
+            self.forwarded()
+        );
+        let _ = writeln!(
+            out,
+            "# HELP mediator_messages_delivered_total Queued messages delivered to recipients."
+        );
+        let _ = writeln!(out, "# TYPE mediator_messages_delivered_total counter");
+        let _ = writeln!(
+            out,
+            "mediator_messages_delivered_total {}",
+            self.delivered()
+        );
+        let _ = writeln!(
+            out,
+            "# HELP mediator_messages_dropped_total Messages rejected or discarded by mediator."
+        );
+        let _ = writeln!(out, "# TYPE mediator_messages_dropped_total counter");
+        for (reason, count) in self.dropped.lock().unwrap().iter() {
+            let _ = writeln!(
+                out,
+                "mediator_messages_dropped_total{{reason=\"{reason}\"}} {count}"
+            );
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_prometheus() {
+        let metrics = MediatorMetrics::default();
+        metrics.record_forwarded();
+        metrics.record_forwarded();
+        metrics.record_delivered(2);
+        metrics.record_dropped(DROP_REASON_EXPIRED, 3);
+        let rendered = metrics.render_prometheus();
+        assert!(rendered.contains("mediator_messages_forwarded_total 2\n"));
+        assert!(rendered.contains("mediator_messages_delivered_total 2\n"));
+        assert!(rendered.contains("mediator_messages_dropped_total{reason=\"expired\"} 3\n"));
+    }
+}
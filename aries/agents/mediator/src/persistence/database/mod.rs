@@ -2,4 +2,4 @@
 // SPDX-License-Identifier: Apache-2.0
 
 mod mysql;
-pub use mysql::get_db_pool;
+pub use mysql::{connect_db_pool, get_db_pool};
@@ -1,6 +1,8 @@
 // Copyright 2023 Naian G.
 // SPDX-License-Identifier: Apache-2.0
 
+use std::time::Duration;
+
 use anyhow::anyhow;
 use async_trait::async_trait;
 use diddoc_legacy::aries::diddoc::AriesDidDoc;
@@ -16,11 +18,12 @@ use crate::{
     persistence::{
         errors::{
             AccountNotFound, AddRecipientError, CreateAccountError, DecodeError,
-            GetAccountDetailsError, GetAccountIdError, ListAccountsError, ListRecipientKeysError,
-            PersistForwardMessageError, RemoveRecipientError, RetrievePendingMessageCountError,
+            DeleteAccountError, GetAccountDetailsError, GetAccountIdError, ListAccountsError,
+            ListRecipientKeysError, PersistForwardMessageError, PurgeExpiredMessagesError,
+            QuotaExceeded, RemoveRecipientError, RetrievePendingMessageCountError,
             RetrievePendingMessagesError, StorageBackendError,
         },
-        AccountDetails,
+        AccountDetails, AccountQuota,
     },
     utils::structs::VerKey,
 };
@@ -30,8 +33,12 @@ pub async fn get_db_pool() -> MySqlPool {
     let database_url = std::env::var("MYSQL_URL")
         .expect("Required environment variable MYSQL_URL on command line or in .env!");
 
+    connect_db_pool(&database_url).await
+}
+
+pub async fn connect_db_pool(database_url: &str) -> MySqlPool {
     MySqlPoolOptions::new()
-        .connect(&database_url)
+        .connect(database_url)
         .await
         .expect("Failed to connect to database!")
 }
@@ -149,6 +156,23 @@ impl MediatorPersistence for sqlx::MySqlPool {
         Ok(account_details)
     }
 
+    async fn delete_account(&self, auth_pubkey: &str) -> Result<(), DeleteAccountError> {
+        info!(
+            "Deleting account with auth_pubkey {:#?} from database",
+            auth_pubkey
+        );
+        // Recipients and messages are removed by ON DELETE CASCADE
+        let delete_result = sqlx::query("DELETE FROM accounts WHERE auth_pubkey = ?;")
+            .bind(auth_pubkey)
+            .execute(self)
+            .await
+            .map_err(|e| StorageBackendError { source: e.into() })?;
+        if delete_result.rows_affected() == 0 {
+            return Err(AccountNotFound(format!("auth_pubkey={}", auth_pubkey.to_owned())).into());
+        }
+        Ok(())
+    }
+
     // async fn vaporize_account(&self, auth_pubkey: String) {
     //     let account: Vec<u8> = self.get_account(auth_pubkey).await?;
     //     let mut recipient_rows = sqlx::query(
@@ -179,12 +203,17 @@ impl MediatorPersistence for sqlx::MySqlPool {
         &self,
         recipient_key: &str,
         message_data: &str,
+        quota: &AccountQuota,
     ) -> Result<(), PersistForwardMessageError> {
+        let mut tx = self
+            .begin()
+            .await
+            .map_err(|e| StorageBackendError { source: e.into() })?;
         // Fetch recipient with given recipient_key
         info!("Fetching recipient with recipient_key {:#?}", recipient_key);
         let recipient_row = sqlx::query("SELECT * FROM recipients WHERE recipient_key = ?")
             .bind(recipient_key)
-            .fetch_one(self)
+            .fetch_one(&mut *tx)
             .await;
         if let Err(err) = recipient_row {
             info!("Error while finding target recipient, {:#}", err);
@@ -197,6 +226,54 @@ impl MediatorPersistence for sqlx::MySqlPool {
             return Err(mapped_err);
         }
         let account_id: Vec<u8> = recipient_row.unwrap().get("account_id");
+        if !quota.is_unlimited() {
+            // Lock account row so that concurrent forwards to the same account
+            // are checked against the quota one at a time
+            sqlx::query("SELECT account_id FROM accounts WHERE account_id = ? FOR UPDATE")
+                .bind(&account_id)
+                .fetch_one(&mut *tx)
+                .await
+                .map_err(|e| StorageBackendError { source: e.into() })?;
+            let usage_row = sqlx::query(
+                "SELECT COUNT(*) AS message_count,
+                CAST(COALESCE(SUM(OCTET_LENGTH(message_data)), 0) AS SIGNED) AS total_bytes
+                FROM messages WHERE account_id = ?",
+            )
+            .bind(&account_id)
+            .fetch_one(&mut *tx)
+            .await
+            .map_err(|e| StorageBackendError { source: e.into() })?;
+            let message_count: i64 = usage_row.try_get("message_count").map_err(|e| anyhow!(e))?;
+            let total_bytes: i64 = usage_row.try_get("total_bytes").map_err(|e| anyhow!(e))?;
+            if let Some(max_message_count) = quota.max_message_count {
+                if message_count >= i64::from(max_message_count) {
+                    info!(
+                        "Account {:x?} has {} pending messages, rejecting message",
+                        account_id, message_count
+                    );
+                    return Err(QuotaExceeded(format!(
+                        "recipient_key={recipient_key} has reached limit of {max_message_count} \
+                         pending messages"
+                    ))
+                    .into());
+                }
+            }
+            if let Some(max_total_bytes) = quota.max_total_bytes {
+                let message_size = u64::try_from(message_data.len()).map_err(|e| anyhow!(e))?;
+                let total_bytes = u64::try_from(total_bytes).map_err(|e| anyhow!(e))?;
+                if total_bytes.saturating_add(message_size) > max_total_bytes {
+                    info!(
+                        "Account {:x?} has {} bytes of pending messages, rejecting message",
+                        account_id, total_bytes
+                    );
+                    return Err(QuotaExceeded(format!(
+                        "recipient_key={recipient_key} has reached limit of {max_total_bytes} \
+                         bytes of pending messages"
+                    ))
+                    .into());
+                }
+            }
+        }
         // Save message for recipient
         info!("Persisting message for account {:x?}", account_id);
         let insert_result = sqlx::query(
@@ -205,7 +282,7 @@ impl MediatorPersistence for sqlx::MySqlPool {
         .bind(&account_id)
         .bind(recipient_key)
         .bind(message_data)
-        .execute(self)
+        .execute(&mut *tx)
         .await;
         if let Err(err) = insert_result {
             info!(
@@ -216,8 +293,27 @@ impl MediatorPersistence for sqlx::MySqlPool {
                 StorageBackendError { source: err.into() },
             ));
         }
+        tx.commit()
+            .await
+            .map_err(|e| StorageBackendError { source: e.into() })?;
         Ok(())
     }
+    async fn purge_expired_messages(
+        &self,
+        max_age: Duration,
+    ) -> Result<u64, PurgeExpiredMessagesError> {
+        let purge_result =
+            sqlx::query("DELETE FROM messages WHERE received_at < (NOW() - INTERVAL ? SECOND);")
+                .bind(max_age.as_secs())
+                .execute(self)
+                .await
+                .map_err(|e| StorageBackendError { source: e.into() })?;
+        let purged_count = purge_result.rows_affected();
+        if purged_count > 0 {
+            info!("Purged {} messages older than {:?}", purged_count, max_age);
+        }
+        Ok(purged_count)
+    }
     async fn retrieve_pending_message_count(
         &self,
         auth_pubkey: &str,
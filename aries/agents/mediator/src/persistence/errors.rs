@@ -4,6 +4,10 @@ use thiserror::Error;
 #[error("No account found matching given input: {0}")]
 pub struct AccountNotFound(pub String);
 
+#[derive(Error, Debug)]
+#[error("Quota exceeded: {0}")]
+pub struct QuotaExceeded(pub String);
+
 /// Error closely related to the storage backend
 #[derive(Error, Debug)]
 #[error(transparent)]
@@ -51,12 +55,14 @@ pub enum CreateAccountError {
 error_compose!(GetAccountIdError[StorageBackendError, AccountNotFound]);
 error_compose!(GetAccountDetailsError[StorageBackendError, AccountNotFound, DecodeError]);
 error_compose!(ListAccountsError[StorageBackendError, DecodeError]);
+error_compose!(DeleteAccountError[StorageBackendError, AccountNotFound]);
 
 error_compose!(AddRecipientError[StorageBackendError, AccountNotFound]);
 // Expected to fail similarly
 pub type RemoveRecipientError = AddRecipientError;
 error_compose!(ListRecipientKeysError[StorageBackendError, AccountNotFound]);
 
-error_compose!(PersistForwardMessageError[StorageBackendError, AccountNotFound]);
+error_compose!(PersistForwardMessageError[StorageBackendError, AccountNotFound, QuotaExceeded]);
 error_compose!(RetrievePendingMessageCountError[StorageBackendError, AccountNotFound]);
 error_compose!(RetrievePendingMessagesError[StorageBackendError, AccountNotFound]);
+error_compose!(PurgeExpiredMessagesError[StorageBackendError]);
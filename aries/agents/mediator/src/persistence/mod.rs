@@ -3,15 +3,18 @@
 
 pub mod database;
 pub mod errors;
+use std::time::Duration;
+
 use async_trait::async_trait;
 /// Database backend is used for default implementation of MediatorPersistence trait
-pub use database::get_db_pool as get_persistence;
+pub use database::{connect_db_pool as connect_persistence, get_db_pool as get_persistence};
 use diddoc_legacy::aries::diddoc::AriesDidDoc;
 
 use self::errors::{
-    AddRecipientError, CreateAccountError, GetAccountDetailsError, GetAccountIdError,
-    ListAccountsError, ListRecipientKeysError, PersistForwardMessageError, RemoveRecipientError,
-    RetrievePendingMessageCountError, RetrievePendingMessagesError,
+    AddRecipientError, CreateAccountError, DeleteAccountError, GetAccountDetailsError,
+    GetAccountIdError, ListAccountsError, ListRecipientKeysError, PersistForwardMessageError,
+    PurgeExpiredMessagesError, RemoveRecipientError, RetrievePendingMessageCountError,
+    RetrievePendingMessagesError,
 };
 use crate::utils::structs::VerKey;
 
@@ -24,7 +27,8 @@ pub trait MediatorPersistence: Send + Sync + 'static {
         did_doc: &str,
     ) -> Result<(), CreateAccountError>;
     async fn get_account_id(&self, auth_pubkey: &str) -> Result<Vec<u8>, GetAccountIdError>;
-    // async fn vaporize_account(&self, auth_pubkey: String);
+    /// Deletes account along with its recipient keys and pending messages
+    async fn delete_account(&self, auth_pubkey: &str) -> Result<(), DeleteAccountError>;
     async fn add_recipient(
         &self,
         auth_pubkey: &str,
@@ -39,10 +43,13 @@ pub trait MediatorPersistence: Send + Sync + 'static {
         &self,
         auth_pubkey: &str,
     ) -> Result<Vec<String>, ListRecipientKeysError>;
+    /// Stores message for the account owning recipient_key.
+    /// Fails with QuotaExceeded if storing it would take the account over the given quota.
     async fn persist_forward_message(
         &self,
         recipient_key: &str,
         message_data: &str,
+        quota: &AccountQuota,
     ) -> Result<(), PersistForwardMessageError>;
     async fn retrieve_pending_message_count(
         &self,
@@ -56,6 +63,12 @@ pub trait MediatorPersistence: Send + Sync + 'static {
         recipient_key: Option<&String>,
     ) -> Result<Vec<(String, Vec<u8>)>, RetrievePendingMessagesError>;
     // async fn mark_messages_received(&self, message_id: Vec<u32>);
+    /// Deletes pending messages received more than max_age ago.
+    /// Returns number of deleted messages
+    async fn purge_expired_messages(
+        &self,
+        max_age: Duration,
+    ) -> Result<u64, PurgeExpiredMessagesError>;
     /// Returns vector of (account_name, auth_pubkey)
     async fn list_accounts(&self) -> Result<Vec<(String, String)>, ListAccountsError>;
     /// Returns account details (sr.no, account_name, our_signing_key, did_doc)
@@ -76,3 +89,17 @@ pub struct AccountDetails {
     pub our_signing_key: VerKey,
     pub their_did_doc: AriesDidDoc,
 }
+
+/// Limits on pending (not yet picked up) messages stored per account.
+/// `None` means no limit.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AccountQuota {
+    pub max_message_count: Option<u32>,
+    pub max_total_bytes: Option<u64>,
+}
+
+impl AccountQuota {
+    pub fn is_unlimited(&self) -> bool {
+        self.max_message_count.is_none() && self.max_total_bytes.is_none()
+    }
+}
@@ -0,0 +1,143 @@
+use std::path::Path;
+
+use aries_vcx_wallet::wallet::askar::{askar_wallet_config::AskarWalletConfig, AskarWallet};
+use serde::Deserialize;
+use url::Url;
+
+use crate::{
+    aries_agent::{Agent, AgentBuilder},
+    persistence::connect_persistence,
+    utils::prelude::*,
+};
+
+/// Configuration of one tenant of a multi-tenant mediator.
+/// Each tenant has its own wallet (Askar profile), identity, invitation and database.
+#[derive(Debug, Clone, Deserialize)]
+pub struct TenantConfig {
+    /// Tenant routes are served under `/tenants/{id}`
+    pub id: String,
+    /// Requests carrying this `Host` header are served by the tenant at the root path as well
+    #[serde(default)]
+    pub host: Option<String>,
+    /// Endpoint advertised in tenant's invitation,
+    /// defaults to `http://{ENDPOINT_ROOT}/tenants/{id}/didcomm`
+    #[serde(default)]
+    pub endpoint: Option<Url>,
+    /// Tenant's own database, must have the mediator migrations applied
+    pub mysql_url: String,
+    /// Defaults to the mediator wallet config, with the tenant id as Askar profile
+    #[serde(default)]
+    pub wallet_config: Option<AskarWalletConfig>,
+    /// Token of the tenant's admin API, accepted for this tenant only
+    #[serde(default)]
+    pub admin_api_token: Option<String>,
+}
+
+impl TenantConfig {
+    pub fn endpoint(&self, endpoint_root: &str) -> Result<Url, String> {
+        match &self.endpoint {
+            Some(endpoint) => Ok(endpoint.clone()),
+            None => format!("http://{endpoint_root}/tenants/{}/didcomm", self.id)
+                .parse()
+                .map_err(string_from_std_error),
+        }
+    }
+
+    /// Tenants without a token of their own fall back to the mediator-wide `operator_token`,
+    /// which therefore grants access to the admin API of all such tenants
+    pub fn admin_api_token(&self, operator_token: Option<&String>) -> Option<String> {
+        self.admin_api_token.as_ref().or(operator_token).cloned()
+    }
+
+    pub fn wallet_config(&self, default_wallet_config: &AskarWalletConfig) -> AskarWalletConfig {
+        self.wallet_config.clone().unwrap_or_else(|| {
+            let mut wallet_config = default_wallet_config.clone();
+            wallet_config.profile.clone_from(&self.id);
+            wallet_config
+        })
+    }
+}
+
+/// Reads JSON array of tenant configs
+pub fn load_tenant_configs(path: impl AsRef<Path>) -> Result<Vec<TenantConfig>, String> {
+    let json = std::fs::read_to_string(path).map_err(string_from_std_error)?;
+    let tenants: Vec<TenantConfig> = serde_json::from_str(&json).map_err(string_from_std_error)?;
+    for (index, tenant) in tenants.iter().enumerate() {
+        if tenants[..index].iter().any(|other| other.id == tenant.id) {
+            return Err(format!("Duplicate tenant id {}", tenant.id));
+        }
+        if tenant.host.is_some()
+            && tenants[..index]
+                .iter()
+                .any(|other| other.host == tenant.host)
+        {
+            return Err(format!("Duplicate tenant host {:?}", tenant.host));
+        }
+    }
+    Ok(tenants)
+}
+
+/// Opens tenant's wallet and database and sets up its service
+pub async fn build_tenant_agent(
+    tenant: &TenantConfig,
+    default_wallet_config: &AskarWalletConfig,
+    endpoint_root: &str,
+) -> Result<Agent<AskarWallet, sqlx::MySqlPool>, String> {
+    info!("Setting up tenant {}", tenant.id);
+    let wallet = AskarWallet::create_or_open_profile(&tenant.wallet_config(default_wallet_config))
+        .await
+        .map_err(string_from_std_error)?;
+    let persistence = connect_persistence(&tenant.mysql_url).await;
+    let mut agent = AgentBuilder::new_from_wallet(wallet, persistence);
+    agent
+        .init_service(vec![], tenant.endpoint(endpoint_root)?)
+        .await
+        .map_err(string_from_std_error)?;
+    Ok(agent)
+}
+
+#[cfg(test)]
+mod tests {
+    use aries_vcx_wallet::wallet::askar::key_method::KeyMethod;
+
+    use super::*;
+
+    #[test]
+    fn test_tenant_config_defaults() {
+        let tenant: TenantConfig = serde_json::from_value(serde_json::json!({
+            "id": "acme",
+            "mysql_url": "mysql://localhost:3306/acme"
+        }))
+        .unwrap();
+        assert_eq!(
+            tenant.endpoint("127.0.0.1:8005").unwrap().as_str(),
+            "http://127.0.0.1:8005/tenants/acme/didcomm"
+        );
+        let default_wallet_config =
+            AskarWalletConfig::new("sqlite://wallet.db", KeyMethod::Unprotected, "", "default");
+        let wallet_config = tenant.wallet_config(&default_wallet_config);
+        assert_eq!(wallet_config.db_url(), "sqlite://wallet.db");
+        assert_eq!(wallet_config.profile(), "acme");
+        let operator_token = "operator".to_owned();
+        assert_eq!(
+            tenant.admin_api_token(Some(&operator_token)).as_deref(),
+            Some("operator")
+        );
+    }
+
+    #[test]
+    fn test_tenant_admin_api_token() {
+        let tenant: TenantConfig = serde_json::from_value(serde_json::json!({
+            "id": "acme",
+            "mysql_url": "mysql://localhost:3306/acme",
+            "admin_api_token": "acme-token"
+        }))
+        .unwrap();
+        let operator_token = "operator".to_owned();
+        assert_eq!(
+            tenant.admin_api_token(Some(&operator_token)).as_deref(),
+            Some("acme-token")
+        );
+        assert_eq!(tenant.admin_api_token(None).as_deref(), Some("acme-token"));
+    }
+}
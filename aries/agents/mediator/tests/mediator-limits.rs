@@ -0,0 +1,115 @@
+mod common;
+
+use std::time::Duration;
+
+use mediator::persistence::{
+    errors::PersistForwardMessageError, get_persistence, AccountQuota, MediatorPersistence,
+};
+use sqlx::MySqlPool;
+use uuid::Uuid;
+
+use crate::common::{prelude::*, test_setup::setup_env_logging};
+
+static LOGGING_INIT: std::sync::Once = std::sync::Once::new();
+
+/// Returns (auth_pubkey, recipient_key) of a fresh account with a single recipient key
+async fn create_account_with_recipient(storage: &MySqlPool) -> Result<(String, String)> {
+    let auth_pubkey = Uuid::new_v4().to_string();
+    let recipient_key = Uuid::new_v4().to_string();
+    storage
+        .create_account(&auth_pubkey, "our_signing_key", "{}")
+        .await?;
+    storage.add_recipient(&auth_pubkey, &recipient_key).await?;
+    Ok((auth_pubkey, recipient_key))
+}
+
+#[tokio::test]
+async fn test_message_count_quota() -> Result<()> {
+    LOGGING_INIT.call_once(setup_env_logging);
+    let storage = get_persistence().await;
+    let (auth_pubkey, recipient_key) = create_account_with_recipient(&storage).await?;
+    let quota = AccountQuota {
+        max_message_count: Some(2),
+        max_total_bytes: None,
+    };
+
+    for _ in 0..2 {
+        storage
+            .persist_forward_message(&recipient_key, "{}", &quota)
+            .await?;
+    }
+    let res = storage
+        .persist_forward_message(&recipient_key, "{}", &quota)
+        .await;
+    assert!(matches!(
+        res,
+        Err(PersistForwardMessageError::QuotaExceeded(_))
+    ));
+    assert_eq!(
+        storage
+            .retrieve_pending_message_count(&auth_pubkey, None)
+            .await?,
+        2
+    );
+
+    storage.delete_account(&auth_pubkey).await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_total_bytes_quota() -> Result<()> {
+    LOGGING_INIT.call_once(setup_env_logging);
+    let storage = get_persistence().await;
+    let (auth_pubkey, recipient_key) = create_account_with_recipient(&storage).await?;
+    let quota = AccountQuota {
+        max_message_count: None,
+        max_total_bytes: Some(10),
+    };
+
+    storage
+        .persist_forward_message(&recipient_key, "{\"a\":1}", &quota)
+        .await?;
+    let res = storage
+        .persist_forward_message(&recipient_key, "{\"b\":2}", &quota)
+        .await;
+    assert!(matches!(
+        res,
+        Err(PersistForwardMessageError::QuotaExceeded(_))
+    ));
+
+    storage.delete_account(&auth_pubkey).await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_purge_expired_messages() -> Result<()> {
+    LOGGING_INIT.call_once(setup_env_logging);
+    let storage = get_persistence().await;
+    let (auth_pubkey, recipient_key) = create_account_with_recipient(&storage).await?;
+    let quota = AccountQuota::default();
+    let ttl = Duration::from_secs(24 * 60 * 60);
+
+    storage
+        .persist_forward_message(&recipient_key, "{\"old\":true}", &quota)
+        .await?;
+    // Backdate the message rather than waiting, a ttl of a day leaves messages of concurrently
+    // running tests alone
+    sqlx::query("UPDATE messages SET received_at = NOW() - INTERVAL 2 DAY WHERE recipient_key = ?")
+        .bind(&recipient_key)
+        .execute(&storage)
+        .await?;
+    storage
+        .persist_forward_message(&recipient_key, "{\"old\":false}", &quota)
+        .await?;
+
+    let purged_count = storage.purge_expired_messages(ttl).await?;
+    assert!(purged_count >= 1);
+    let pending = storage
+        .retrieve_pending_messages(&auth_pubkey, 10, None)
+        .await?;
+    assert_eq!(pending.len(), 1);
+    assert_eq!(pending[0].1, b"{\"old\":false}");
+
+    storage.delete_account(&auth_pubkey).await?;
+    Ok(())
+}
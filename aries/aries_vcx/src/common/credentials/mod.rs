@@ -6,6 +6,7 @@ use time::OffsetDateTime;
 use crate::errors::error::{AriesVcxError, AriesVcxErrorKind, VcxResult};
 
 pub mod encoding;
+pub mod store;
 
 pub async fn get_cred_rev_id(
     wallet: &impl BaseWallet,
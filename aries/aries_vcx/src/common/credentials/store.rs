@@ -0,0 +1,266 @@
+use std::collections::HashMap;
+
+use anoncreds_types::data_types::messages::cred_selection::RetrievedCredentialInfo;
+use aries_vcx_anoncreds::anoncreds::base_anoncreds::{
+    BaseAnonCreds, CredentialId, CredentialMetadata, CREDENTIAL_METADATA_WQL_PREFIX,
+};
+use aries_vcx_ledger::ledger::base_ledger::AnoncredsLedgerRead;
+use aries_vcx_wallet::wallet::base_wallet::BaseWallet;
+use serde_json::{Map, Value};
+
+use crate::{
+    common::credentials::is_cred_revoked,
+    errors::error::{AriesVcxError, AriesVcxErrorKind, VcxResult},
+};
+
+// Names of the credential metadata kept by the store
+const LABEL: &str = "label";
+const REVOKED: &str = "revoked";
+const USER_TAG_PREFIX: &str = "tag::";
+
+/// Criteria credentials are searched by. Credentials match when they meet every criterion set.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CredentialFilter {
+    pub schema_id: Option<String>,
+    pub schema_issuer_did: Option<String>,
+    pub schema_name: Option<String>,
+    pub schema_version: Option<String>,
+    pub cred_def_id: Option<String>,
+    pub issuer_did: Option<String>,
+    /// Raw values of attributes, by attribute name
+    pub attributes: HashMap<String, String>,
+    pub label: Option<String>,
+    /// User-defined tags, see [`CredentialStore::set_tag`]
+    pub tags: HashMap<String, String>,
+}
+
+impl CredentialFilter {
+    /// WQL query matching the credential records meeting the filter
+    pub fn to_wql(&self) -> String {
+        let mut query = Map::new();
+        let fields = [
+            ("schema_id", &self.schema_id),
+            ("schema_issuer_did", &self.schema_issuer_did),
+            ("schema_name", &self.schema_name),
+            ("schema_version", &self.schema_version),
+            ("cred_def_id", &self.cred_def_id),
+            ("issuer_did", &self.issuer_did),
+        ];
+        for (tag, value) in fields {
+            if let Some(value) = value {
+                query.insert(tag.to_string(), Value::String(value.clone()));
+            }
+        }
+        if let Some(label) = &self.label {
+            query.insert(
+                format!("{CREDENTIAL_METADATA_WQL_PREFIX}{LABEL}"),
+                Value::String(label.clone()),
+            );
+        }
+        for (name, value) in &self.attributes {
+            // attribute tags are named after the attribute, ignoring case and spaces
+            let name = name.replace(' ', "").to_lowercase();
+            query.insert(format!("attr::{name}::value"), Value::String(value.clone()));
+        }
+        for (name, value) in &self.tags {
+            query.insert(
+                format!("{CREDENTIAL_METADATA_WQL_PREFIX}{USER_TAG_PREFIX}{name}"),
+                Value::String(value.clone()),
+            );
+        }
+        Value::Object(query).to_string()
+    }
+}
+
+/// Credential held in the wallet, along with the metadata kept for it
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct StoredCredential {
+    #[serde(flatten)]
+    pub info: RetrievedCredentialInfo,
+    pub label: Option<String>,
+    pub tags: HashMap<String, String>,
+    /// Revocation status found by the last [`CredentialStore::refresh_revocation_status`], if any
+    pub revoked: Option<bool>,
+}
+
+impl StoredCredential {
+    fn new(info: RetrievedCredentialInfo, metadata: &CredentialMetadata) -> Self {
+        let mut label = None;
+        let mut tags = HashMap::new();
+        let mut revoked = None;
+        for (name, value) in metadata {
+            match name.as_str() {
+                LABEL => label = Some(value.clone()),
+                REVOKED => revoked = Some(value == "true"),
+                name => {
+                    if let Some(name) = name.strip_prefix(USER_TAG_PREFIX) {
+                        tags.insert(name.to_string(), value.clone());
+                    }
+                }
+            }
+        }
+        Self {
+            info,
+            label,
+            tags,
+            revoked,
+        }
+    }
+}
+
+/// Façade over the credentials a holder keeps in its wallet. Credentials are stored by the
+/// [`Holder`](crate::handlers::issuance::holder::Holder) receiving them; the store lists, searches
+/// and deletes them and keeps labels, tags and revocation status for them.
+pub struct CredentialStore<'a, W, A> {
+    wallet: &'a W,
+    anoncreds: &'a A,
+}
+
+impl<'a, W: BaseWallet, A: BaseAnonCreds> CredentialStore<'a, W, A> {
+    pub fn new(wallet: &'a W, anoncreds: &'a A) -> Self {
+        Self { wallet, anoncreds }
+    }
+
+    pub async fn get(&self, cred_id: &CredentialId) -> VcxResult<StoredCredential> {
+        let info = self
+            .anoncreds
+            .prover_get_credential(self.wallet, cred_id)
+            .await?;
+        let metadata = self
+            .anoncreds
+            .prover_get_credential_metadata(self.wallet, cred_id)
+            .await?;
+        Ok(StoredCredential::new(info, &metadata))
+    }
+
+    pub async fn list(&self) -> VcxResult<Vec<StoredCredential>> {
+        self.search(&CredentialFilter::default()).await
+    }
+
+    pub async fn search(&self, filter: &CredentialFilter) -> VcxResult<Vec<StoredCredential>> {
+        Ok(self
+            .anoncreds
+            .prover_get_credentials_with_metadata(self.wallet, Some(&filter.to_wql()))
+            .await?
+            .into_iter()
+            .map(|(info, metadata)| StoredCredential::new(info, &metadata))
+            .collect())
+    }
+
+    pub async fn delete(&self, cred_id: &CredentialId) -> VcxResult<()> {
+        Ok(self
+            .anoncreds
+            .prover_delete_credential(self.wallet, cred_id)
+            .await?)
+    }
+
+    /// Sets the label of the credential, or removes it if `None`
+    pub async fn set_label(&self, cred_id: &CredentialId, label: Option<&str>) -> VcxResult<()> {
+        self.set_metadata(cred_id, LABEL, label).await
+    }
+
+    /// Sets a user-defined tag on the credential, or removes it if `value` is `None`
+    pub async fn set_tag(
+        &self,
+        cred_id: &CredentialId,
+        name: &str,
+        value: Option<&str>,
+    ) -> VcxResult<()> {
+        self.set_metadata(cred_id, &format!("{USER_TAG_PREFIX}{name}"), value)
+            .await
+    }
+
+    /// Checks on the ledger whether the credential has been revoked and keeps the result as its
+    /// revocation status. Fails for credentials which are not revocable.
+    pub async fn refresh_revocation_status(
+        &self,
+        ledger: &impl AnoncredsLedgerRead,
+        cred_id: &CredentialId,
+    ) -> VcxResult<bool> {
+        let info = self
+            .anoncreds
+            .prover_get_credential(self.wallet, cred_id)
+            .await?;
+        let (Some(rev_reg_id), Some(cred_rev_id)) = (info.rev_reg_id, info.cred_rev_id) else {
+            return Err(AriesVcxError::from_msg(
+                AriesVcxErrorKind::InvalidRevocationDetails,
+                format!("Credential {cred_id} is not revocable"),
+            ));
+        };
+        let revoked = is_cred_revoked(ledger, &rev_reg_id, cred_rev_id).await?;
+        self.set_metadata(cred_id, REVOKED, Some(&revoked.to_string()))
+            .await?;
+        Ok(revoked)
+    }
+
+    async fn set_metadata(
+        &self,
+        cred_id: &CredentialId,
+        name: &str,
+        value: Option<&str>,
+    ) -> VcxResult<()> {
+        Ok(self
+            .anoncreds
+            .prover_set_credential_metadata(self.wallet, cred_id, name, value)
+            .await?)
+    }
+}
+
+#[cfg(test)]
+pub mod unit_tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_filter_to_wql() {
+        let filter = CredentialFilter {
+            cred_def_id: Some("cred-def".to_string()),
+            attributes: HashMap::from([("First Name".to_string(), "Alice".to_string())]),
+            label: Some("diploma".to_string()),
+            tags: HashMap::from([("folder".to_string(), "education".to_string())]),
+            ..Default::default()
+        };
+        let wql: Value = serde_json::from_str(&filter.to_wql()).unwrap();
+        assert_eq!(
+            wql,
+            json!({
+                "cred_def_id": "cred-def",
+                "attr::firstname::value": "Alice",
+                "meta::label": "diploma",
+                "meta::tag::folder": "education",
+            })
+        );
+    }
+
+    #[test]
+    fn test_empty_filter_matches_all() {
+        assert_eq!(CredentialFilter::default().to_wql(), "{}");
+    }
+
+    #[test]
+    fn test_stored_credential_reads_metadata() {
+        let info: RetrievedCredentialInfo = serde_json::from_value(json!({
+            "referent": "cred-id",
+            "attrs": { "name": "Alice" },
+            "schema_id": "NcYxiDXkpYi6ov5FcYDi1e:2:gvt:1.0",
+            "cred_def_id": "NcYxiDXkpYi6ov5FcYDi1e:3:CL:NcYxiDXkpYi6ov5FcYDi1e:2:gvt:1.0:tag",
+            "rev_reg_id": null,
+            "cred_rev_id": null,
+        }))
+        .unwrap();
+        let metadata = HashMap::from([
+            (LABEL.to_string(), "id card".to_string()),
+            (REVOKED.to_string(), "false".to_string()),
+            ("tag::folder".to_string(), "identity".to_string()),
+        ]);
+        let stored = StoredCredential::new(info, &metadata);
+        assert_eq!(stored.label.as_deref(), Some("id card"));
+        assert_eq!(stored.revoked, Some(false));
+        assert_eq!(
+            stored.tags,
+            HashMap::from([("folder".to_string(), "identity".to_string())])
+        );
+    }
+}
@@ -1,2 +1,3 @@
 pub mod holder;
 pub mod issuer;
+pub mod v2_bridge;
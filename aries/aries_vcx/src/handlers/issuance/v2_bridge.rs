@@ -0,0 +1,629 @@
+//! Conversions letting the Issue Credential 1.0 state machines of [`super::holder::Holder`] and
+//! [`super::issuer::Issuer`] run an Issue Credential 2.0 exchange with the AnonCreds (`hlindy`)
+//! attachment formats. Outbound messages are converted with [`to_v2`] before sending, inbound
+//! ones with [`to_v1`] before processing; the thread, attachments and identifiers are preserved.
+
+use base64::{engine::general_purpose, Engine};
+use messages::{
+    decorators::attachment::{Attachment, AttachmentType},
+    msg_fields::protocols::{
+        common::attachment_format_specifier::AttachmentFormatSpecifier,
+        cred_issuance::{
+            v1::{
+                ack::{AckCredentialV1, AckCredentialV1Content},
+                issue_credential::{
+                    IssueCredentialV1, IssueCredentialV1Content, IssueCredentialV1Decorators,
+                },
+                offer_credential::{
+                    OfferCredentialV1, OfferCredentialV1Content, OfferCredentialV1Decorators,
+                },
+                problem_report::{CredIssuanceV1ProblemReport, CredIssuanceV1ProblemReportContent},
+                propose_credential::{
+                    ProposeCredentialV1, ProposeCredentialV1Content, ProposeCredentialV1Decorators,
+                },
+                request_credential::{
+                    RequestCredentialV1, RequestCredentialV1Content, RequestCredentialV1Decorators,
+                },
+                CredentialIssuanceV1, CredentialPreviewV1,
+            },
+            v2::{
+                ack::{AckCredentialV2, AckCredentialV2Content},
+                issue_credential::{
+                    IssueCredentialAttachmentFormatType, IssueCredentialV2,
+                    IssueCredentialV2Content, IssueCredentialV2Decorators,
+                },
+                offer_credential::{
+                    OfferCredentialAttachmentFormatType, OfferCredentialV2,
+                    OfferCredentialV2Content, OfferCredentialV2Decorators,
+                },
+                problem_report::{CredIssuanceProblemReportV2, CredIssuanceV2ProblemReportContent},
+                propose_credential::{
+                    ProposeCredentialAttachmentFormatType, ProposeCredentialV2,
+                    ProposeCredentialV2Content, ProposeCredentialV2Decorators,
+                },
+                request_credential::{
+                    RequestCredentialAttachmentFormatType, RequestCredentialV2,
+                    RequestCredentialV2Content, RequestCredentialV2Decorators,
+                },
+                CredentialIssuanceV2, CredentialPreviewV2,
+            },
+        },
+    },
+};
+use shared::maybe_known::MaybeKnown;
+use uuid::Uuid;
+
+use crate::{
+    errors::error::{AriesVcxError, AriesVcxErrorKind, VcxResult},
+    handlers::util::make_attach_from_str,
+};
+
+/// Fields of the `hlindy/cred-filter@v2.0` proposal attachment
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct IndyCredentialFilter {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    schema_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    cred_def_id: Option<String>,
+}
+
+pub fn to_v2(msg: CredentialIssuanceV1) -> VcxResult<CredentialIssuanceV2> {
+    Ok(match msg {
+        CredentialIssuanceV1::ProposeCredential(msg) => propose_to_v2(msg)?.into(),
+        CredentialIssuanceV1::OfferCredential(msg) => offer_to_v2(msg).into(),
+        CredentialIssuanceV1::RequestCredential(msg) => request_to_v2(msg).into(),
+        CredentialIssuanceV1::IssueCredential(msg) => credential_to_v2(msg).into(),
+        CredentialIssuanceV1::Ack(msg) => AckCredentialV2 {
+            id: msg.id,
+            content: AckCredentialV2Content {
+                inner: msg.content.inner,
+            },
+            decorators: msg.decorators,
+        }
+        .into(),
+        CredentialIssuanceV1::ProblemReport(msg) => CredIssuanceProblemReportV2 {
+            id: msg.id,
+            content: CredIssuanceV2ProblemReportContent {
+                inner: msg.content.inner,
+            },
+            decorators: msg.decorators,
+        }
+        .into(),
+    })
+}
+
+pub fn to_v1(msg: CredentialIssuanceV2) -> VcxResult<CredentialIssuanceV1> {
+    Ok(match msg {
+        CredentialIssuanceV2::ProposeCredential(msg) => propose_to_v1(msg)?.into(),
+        CredentialIssuanceV2::OfferCredential(msg) => offer_to_v1(msg)?.into(),
+        CredentialIssuanceV2::RequestCredential(msg) => request_to_v1(msg)?.into(),
+        CredentialIssuanceV2::IssueCredential(msg) => credential_to_v1(msg)?.into(),
+        CredentialIssuanceV2::Ack(msg) => AckCredentialV1 {
+            id: msg.id,
+            content: AckCredentialV1Content {
+                inner: msg.content.inner,
+            },
+            decorators: msg.decorators,
+        }
+        .into(),
+        CredentialIssuanceV2::ProblemReport(msg) => CredIssuanceV1ProblemReport {
+            id: msg.id,
+            content: CredIssuanceV1ProblemReportContent {
+                inner: msg.content.inner,
+            },
+            decorators: msg.decorators,
+        }
+        .into(),
+    })
+}
+
+fn propose_to_v2(msg: ProposeCredentialV1) -> VcxResult<ProposeCredentialV2> {
+    let filter = IndyCredentialFilter {
+        schema_id: Some(msg.content.schema_id),
+        cred_def_id: Some(msg.content.cred_def_id),
+    };
+    let (formats, filters_attach) = specify_format(
+        vec![make_attach_from_str!(
+            &serde_json::to_string(&filter)?,
+            Uuid::new_v4().to_string()
+        )],
+        ProposeCredentialAttachmentFormatType::HyperledgerIndyCredentialFilter2_0,
+    );
+    Ok(ProposeCredentialV2 {
+        id: msg.id,
+        content: ProposeCredentialV2Content {
+            goal_code: None,
+            comment: msg.content.comment,
+            credential_preview: Some(CredentialPreviewV2::new(
+                msg.content.credential_proposal.attributes,
+            )),
+            formats,
+            filters_attach,
+        },
+        decorators: ProposeCredentialV2Decorators {
+            thread: msg.decorators.thread,
+            timing: msg.decorators.timing,
+        },
+    })
+}
+
+fn propose_to_v1(msg: ProposeCredentialV2) -> VcxResult<ProposeCredentialV1> {
+    let filter = select_attachment(&msg.content.formats, msg.content.filters_attach, |format| {
+        matches!(
+            format,
+            ProposeCredentialAttachmentFormatType::HyperledgerIndyCredentialFilter2_0
+                | ProposeCredentialAttachmentFormatType::AnoncredCredentialFilter1_0
+        )
+    })?;
+    let filter: IndyCredentialFilter = serde_json::from_slice(&attachment_bytes(&filter)?)?;
+    let attributes = msg
+        .content
+        .credential_preview
+        .map(|preview| preview.attributes)
+        .unwrap_or_default();
+    Ok(ProposeCredentialV1 {
+        id: msg.id,
+        content: ProposeCredentialV1Content {
+            comment: msg.content.comment,
+            credential_proposal: CredentialPreviewV1::new(attributes),
+            schema_id: filter.schema_id.unwrap_or_default(),
+            cred_def_id: filter.cred_def_id.unwrap_or_default(),
+        },
+        decorators: ProposeCredentialV1Decorators {
+            thread: msg.decorators.thread,
+            timing: msg.decorators.timing,
+        },
+    })
+}
+
+fn offer_to_v2(msg: OfferCredentialV1) -> OfferCredentialV2 {
+    let (formats, offers_attach) = specify_format(
+        msg.content.offers_attach,
+        OfferCredentialAttachmentFormatType::HyperledgerIndyCredentialAbstract2_0,
+    );
+    OfferCredentialV2 {
+        id: msg.id,
+        content: OfferCredentialV2Content {
+            goal_code: None,
+            replacement_id: None,
+            comment: msg.content.comment,
+            credential_preview: CredentialPreviewV2::new(msg.content.credential_preview.attributes),
+            formats,
+            offers_attach,
+        },
+        decorators: OfferCredentialV2Decorators {
+            thread: msg.decorators.thread,
+            timing: msg.decorators.timing,
+        },
+    }
+}
+
+fn offer_to_v1(msg: OfferCredentialV2) -> VcxResult<OfferCredentialV1> {
+    let offer = select_attachment(&msg.content.formats, msg.content.offers_attach, |format| {
+        matches!(
+            format,
+            OfferCredentialAttachmentFormatType::HyperledgerIndyCredentialAbstract2_0
+                | OfferCredentialAttachmentFormatType::AnoncredsCredentialOffer1_0
+        )
+    })?;
+    Ok(OfferCredentialV1 {
+        id: msg.id,
+        content: OfferCredentialV1Content {
+            comment: msg.content.comment,
+            credential_preview: CredentialPreviewV1::new(msg.content.credential_preview.attributes),
+            offers_attach: vec![offer],
+        },
+        decorators: OfferCredentialV1Decorators {
+            thread: msg.decorators.thread,
+            timing: msg.decorators.timing,
+        },
+    })
+}
+
+fn request_to_v2(msg: RequestCredentialV1) -> RequestCredentialV2 {
+    let (formats, requests_attach) = specify_format(
+        msg.content.requests_attach,
+        RequestCredentialAttachmentFormatType::HyperledgerIndyCredentialRequest2_0,
+    );
+    RequestCredentialV2 {
+        id: msg.id,
+        content: RequestCredentialV2Content {
+            goal_code: None,
+            comment: msg.content.comment,
+            formats,
+            requests_attach,
+        },
+        decorators: RequestCredentialV2Decorators {
+            thread: msg.decorators.thread,
+            timing: msg.decorators.timing,
+        },
+    }
+}
+
+fn request_to_v1(msg: RequestCredentialV2) -> VcxResult<RequestCredentialV1> {
+    let request = select_attachment(
+        &msg.content.formats,
+        msg.content.requests_attach,
+        |format| {
+            matches!(
+                format,
+                RequestCredentialAttachmentFormatType::HyperledgerIndyCredentialRequest2_0
+                    | RequestCredentialAttachmentFormatType::AnoncredsCredentialRequest1_0
+            )
+        },
+    )?;
+    Ok(RequestCredentialV1 {
+        id: msg.id,
+        content: RequestCredentialV1Content {
+            comment: msg.content.comment,
+            requests_attach: vec![request],
+        },
+        decorators: RequestCredentialV1Decorators {
+            thread: msg.decorators.thread,
+            timing: msg.decorators.timing,
+        },
+    })
+}
+
+fn credential_to_v2(msg: IssueCredentialV1) -> IssueCredentialV2 {
+    let (formats, credentials_attach) = specify_format(
+        msg.content.credentials_attach,
+        IssueCredentialAttachmentFormatType::HyperledgerIndyCredential2_0,
+    );
+    IssueCredentialV2 {
+        id: msg.id,
+        content: IssueCredentialV2Content {
+            goal_code: None,
+            replacement_id: None,
+            comment: msg.content.comment,
+            formats,
+            credentials_attach,
+        },
+        decorators: IssueCredentialV2Decorators {
+            thread: msg.decorators.thread,
+            please_ack: msg.decorators.please_ack,
+            timing: msg.decorators.timing,
+        },
+    }
+}
+
+fn credential_to_v1(msg: IssueCredentialV2) -> VcxResult<IssueCredentialV1> {
+    let credential = select_attachment(
+        &msg.content.formats,
+        msg.content.credentials_attach,
+        |format| {
+            matches!(
+                format,
+                IssueCredentialAttachmentFormatType::HyperledgerIndyCredential2_0
+                    | IssueCredentialAttachmentFormatType::AnoncredsCredential1_0
+            )
+        },
+    )?;
+    Ok(IssueCredentialV1 {
+        id: msg.id,
+        content: IssueCredentialV1Content {
+            comment: msg.content.comment,
+            credentials_attach: vec![credential],
+        },
+        decorators: IssueCredentialV1Decorators {
+            thread: msg.decorators.thread,
+            please_ack: msg.decorators.please_ack,
+            timing: msg.decorators.timing,
+        },
+    })
+}
+
+/// Declares the format of each attachment, giving an id to those missing one
+pub(crate) fn specify_format<F: Clone>(
+    attachments: Vec<Attachment>,
+    format: F,
+) -> (Vec<AttachmentFormatSpecifier<F>>, Vec<Attachment>) {
+    attachments
+        .into_iter()
+        .map(|mut attachment| {
+            let attach_id = attachment
+                .id
+                .get_or_insert_with(|| Uuid::new_v4().to_string())
+                .clone();
+            let specifier = AttachmentFormatSpecifier::builder()
+                .attach_id(attach_id)
+                .format(MaybeKnown::Known(format.clone()))
+                .build();
+            (specifier, attachment)
+        })
+        .unzip()
+}
+
+/// Picks the attachment of the first supported format, base64 encoded as the v1 state machines
+/// expect
+pub(crate) fn select_attachment<F>(
+    formats: &[AttachmentFormatSpecifier<F>],
+    attachments: Vec<Attachment>,
+    is_supported: impl Fn(&F) -> bool,
+) -> VcxResult<Attachment> {
+    let attach_id = formats
+        .iter()
+        .find_map(|specifier| match &specifier.format {
+            MaybeKnown::Known(format) if is_supported(format) => Some(&specifier.attach_id),
+            _ => None,
+        })
+        .ok_or_else(|| {
+            AriesVcxError::from_msg(
+                AriesVcxErrorKind::InvalidMessageFormat,
+                "Message carries no attachment in a supported AnonCreds format",
+            )
+        })?;
+    let mut attachment = attachments
+        .into_iter()
+        .find(|attachment| attachment.id.as_ref() == Some(attach_id))
+        .ok_or_else(|| {
+            AriesVcxError::from_msg(
+                AriesVcxErrorKind::InvalidMessageFormat,
+                format!("Message is missing the attachment {attach_id} its formats refer to"),
+            )
+        })?;
+    if let AttachmentType::Json(value) = &attachment.data.content {
+        attachment.data.content =
+            AttachmentType::Base64(general_purpose::STANDARD.encode(value.to_string()));
+    }
+    Ok(attachment)
+}
+
+pub(crate) fn attachment_bytes(attachment: &Attachment) -> VcxResult<Vec<u8>> {
+    match &attachment.data.content {
+        AttachmentType::Base64(encoded) => {
+            general_purpose::STANDARD.decode(encoded).map_err(|err| {
+                AriesVcxError::from_msg(
+                    AriesVcxErrorKind::SerializationError,
+                    format!("Attachment is not valid base64: {err}"),
+                )
+            })
+        }
+        AttachmentType::Json(value) => Ok(serde_json::to_vec(value)?),
+        AttachmentType::Links(_) => Err(AriesVcxError::from_msg(
+            AriesVcxErrorKind::SerializationError,
+            "Attachment links are not supported",
+        )),
+    }
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use messages::{
+        decorators::thread::Thread,
+        msg_fields::protocols::{
+            cred_issuance::{
+                common::CredentialAttr,
+                v1::offer_credential::{
+                    OfferCredentialV1, OfferCredentialV1Content, OfferCredentialV1Decorators,
+                },
+            },
+            notification::ack::{AckContent, AckDecorators, AckStatus},
+            report_problem::{Description, ProblemReportContent, ProblemReportDecorators},
+        },
+    };
+
+    use super::*;
+
+    fn thread() -> Thread {
+        Thread::builder().thid("thread-id".to_owned()).build()
+    }
+
+    fn preview() -> CredentialPreviewV2 {
+        CredentialPreviewV2::new(vec![CredentialAttr::builder()
+            .name("name".to_owned())
+            .value("alice".to_owned())
+            .build()])
+    }
+
+    fn format<F>(attach_id: &str, format: F) -> Vec<AttachmentFormatSpecifier<F>> {
+        vec![AttachmentFormatSpecifier::builder()
+            .attach_id(attach_id.to_owned())
+            .format(MaybeKnown::Known(format))
+            .build()]
+    }
+
+    fn assert_v2_round_trip(msg: impl Into<CredentialIssuanceV2>) {
+        let msg = msg.into();
+        let round_tripped = to_v2(to_v1(msg.clone()).unwrap()).unwrap();
+        assert_eq!(round_tripped, msg);
+    }
+
+    fn offer_v1() -> OfferCredentialV1 {
+        OfferCredentialV1 {
+            id: "offer-id".to_owned(),
+            content: OfferCredentialV1Content {
+                comment: Some("comment".to_owned()),
+                credential_preview: CredentialPreviewV1::new(vec![CredentialAttr::builder()
+                    .name("name".to_owned())
+                    .value("alice".to_owned())
+                    .build()]),
+                offers_attach: vec![make_attach_from_str!(
+                    r#"{"cred_def_id":"cred-def-id"}"#,
+                    "libindy-cred-offer-0".to_owned()
+                )],
+            },
+            decorators: OfferCredentialV1Decorators {
+                thread: Some(Thread::builder().thid("thread-id".to_owned()).build()),
+                timing: None,
+            },
+        }
+    }
+
+    #[test]
+    fn test_offer_round_trip() {
+        let offer = offer_v1();
+
+        let CredentialIssuanceV2::OfferCredential(offer_v2) = to_v2(offer.clone().into()).unwrap()
+        else {
+            panic!("Expected an offer");
+        };
+        assert_eq!(offer_v2.content.formats.len(), 1);
+        assert_eq!(
+            offer_v2.content.formats[0].attach_id,
+            "libindy-cred-offer-0"
+        );
+
+        let offer_v1 = to_v1(offer_v2.into()).unwrap();
+        assert_eq!(offer_v1, CredentialIssuanceV1::OfferCredential(offer));
+    }
+
+    #[test]
+    fn test_offer_without_anoncreds_attachment_fails() {
+        let CredentialIssuanceV2::OfferCredential(mut offer_v2) = to_v2(offer_v1().into()).unwrap()
+        else {
+            panic!("Expected an offer");
+        };
+        offer_v2.content.formats[0].format =
+            MaybeKnown::Known(OfferCredentialAttachmentFormatType::AriesLdProofVcDetail1_0);
+
+        assert!(to_v1(offer_v2.into()).is_err());
+    }
+
+    #[test]
+    fn test_proposal_filter_round_trip() {
+        let proposal = ProposeCredentialV1 {
+            id: "proposal-id".to_owned(),
+            content: ProposeCredentialV1Content {
+                comment: None,
+                credential_proposal: CredentialPreviewV1::new(vec![]),
+                schema_id: "schema-id".to_owned(),
+                cred_def_id: "cred-def-id".to_owned(),
+            },
+            decorators: ProposeCredentialV1Decorators::default(),
+        };
+
+        let proposal_v1 = to_v1(to_v2(proposal.clone().into()).unwrap()).unwrap();
+        assert_eq!(
+            proposal_v1,
+            CredentialIssuanceV1::ProposeCredential(proposal)
+        );
+    }
+
+    #[test]
+    fn test_v2_proposal_round_trip() {
+        let proposal = ProposeCredentialV2 {
+            id: "proposal-id".to_owned(),
+            content: ProposeCredentialV2Content {
+                goal_code: None,
+                comment: Some("comment".to_owned()),
+                credential_preview: Some(preview()),
+                formats: format(
+                    "filter-0",
+                    ProposeCredentialAttachmentFormatType::HyperledgerIndyCredentialFilter2_0,
+                ),
+                filters_attach: vec![make_attach_from_str!(
+                    r#"{"schema_id":"schema-id","cred_def_id":"cred-def-id"}"#,
+                    "filter-0".to_owned()
+                )],
+            },
+            decorators: ProposeCredentialV2Decorators {
+                thread: Some(thread()),
+                timing: None,
+            },
+        };
+
+        let CredentialIssuanceV2::ProposeCredential(mut round_tripped) =
+            to_v2(to_v1(proposal.clone().into()).unwrap()).unwrap()
+        else {
+            panic!("Expected a proposal");
+        };
+        // the filter is attached anew, under an id of its own
+        assert_eq!(
+            round_tripped.content.filters_attach[0].id.as_ref(),
+            Some(&round_tripped.content.formats[0].attach_id)
+        );
+        round_tripped.content.formats[0].attach_id = "filter-0".to_owned();
+        round_tripped.content.filters_attach[0].id = Some("filter-0".to_owned());
+        assert_eq!(round_tripped, proposal);
+    }
+
+    #[test]
+    fn test_v2_offer_round_trip() {
+        assert_v2_round_trip(OfferCredentialV2 {
+            id: "offer-id".to_owned(),
+            content: OfferCredentialV2Content {
+                goal_code: None,
+                replacement_id: None,
+                comment: Some("comment".to_owned()),
+                credential_preview: preview(),
+                formats: format(
+                    "offer-0",
+                    OfferCredentialAttachmentFormatType::HyperledgerIndyCredentialAbstract2_0,
+                ),
+                offers_attach: vec![make_attach_from_str!(
+                    r#"{"cred_def_id":"cred-def-id"}"#,
+                    "offer-0".to_owned()
+                )],
+            },
+            decorators: OfferCredentialV2Decorators {
+                thread: Some(thread()),
+                timing: None,
+            },
+        });
+    }
+
+    #[test]
+    fn test_v2_request_round_trip() {
+        assert_v2_round_trip(RequestCredentialV2 {
+            id: "request-id".to_owned(),
+            content: RequestCredentialV2Content {
+                goal_code: None,
+                comment: None,
+                formats: format(
+                    "request-0",
+                    RequestCredentialAttachmentFormatType::HyperledgerIndyCredentialRequest2_0,
+                ),
+                requests_attach: vec![make_attach_from_str!(
+                    r#"{"cred_def_id":"cred-def-id"}"#,
+                    "request-0".to_owned()
+                )],
+            },
+            decorators: RequestCredentialV2Decorators {
+                thread: Some(thread()),
+                timing: None,
+            },
+        });
+    }
+
+    #[test]
+    fn test_v2_credential_round_trip() {
+        assert_v2_round_trip(IssueCredentialV2 {
+            id: "credential-id".to_owned(),
+            content: IssueCredentialV2Content {
+                goal_code: None,
+                replacement_id: None,
+                comment: None,
+                formats: format(
+                    "credential-0",
+                    IssueCredentialAttachmentFormatType::HyperledgerIndyCredential2_0,
+                ),
+                credentials_attach: vec![make_attach_from_str!(
+                    r#"{"cred_def_id":"cred-def-id"}"#,
+                    "credential-0".to_owned()
+                )],
+            },
+            decorators: IssueCredentialV2Decorators {
+                thread: thread(),
+                please_ack: None,
+                timing: None,
+            },
+        });
+    }
+
+    #[test]
+    fn test_v2_ack_and_problem_report_round_trip() {
+        assert_v2_round_trip(AckCredentialV2 {
+            id: "ack-id".to_owned(),
+            content: AckContent::builder().status(AckStatus::Ok).build(),
+            decorators: AckDecorators::builder().thread(thread()).build(),
+        });
+        assert_v2_round_trip(CredIssuanceProblemReportV2 {
+            id: "problem-report-id".to_owned(),
+            content: ProblemReportContent::builder()
+                .description(Description::builder().code("rejected".to_owned()).build())
+                .build(),
+            decorators: ProblemReportDecorators::builder().thread(thread()).build(),
+        });
+    }
+}
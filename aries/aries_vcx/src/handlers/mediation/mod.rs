@@ -0,0 +1,579 @@
+use std::future::Future;
+
+use aries_vcx_wallet::wallet::base_wallet::BaseWallet;
+use base64::Engine;
+use did_peer::peer_did::{numalgos::numalgo4::Numalgo4, PeerDid};
+use messages::{
+    decorators::attachment::AttachmentType,
+    msg_fields::protocols::{
+        coordinate_mediation::{
+            keylist_update::{KeylistUpdateItem, KeylistUpdateItemAction},
+            keylist_update_response::KeylistUpdateItemResult,
+            Keylist, KeylistUpdateResponse, MediateDeny, MediateGrant,
+        },
+        pickup::{Delivery, Pickup},
+    },
+    AriesMessage,
+};
+use public_key::Key;
+use url::Url;
+
+use super::util::matches_opt_thread_id;
+use crate::{
+    errors::error::{AriesVcxError, AriesVcxErrorKind, VcxResult},
+    protocols::{
+        did_exchange::state_machine::helpers::create_peer_did_4,
+        mediation::{
+            build_delivery_request, build_keylist_query, build_keylist_update,
+            build_live_delivery_change, build_mediate_request, build_messages_received,
+            build_status_request,
+        },
+        SendClosure,
+    },
+    utils::base64::URL_SAFE_LENIENT,
+};
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum MediationState {
+    Initial,
+    RequestSent {
+        thread_id: String,
+    },
+    Granted {
+        endpoint: String,
+        routing_keys: Vec<String>,
+    },
+    Denied,
+}
+
+/// Message picked up from the mediator, still packed for its recipient
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeliveredMessage {
+    pub id: String,
+    pub message: Vec<u8>,
+}
+
+/// Recipient side of Coordinate Mediation 1.0 and Pickup 2.0 protocols.
+///
+/// Tracks the mediation grant and the recipient keys registered with the mediator, so that the
+/// owner can advertise mediator's endpoint and routing keys in its DIDs and retrieve messages
+/// queued for it.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct MediationRecipient {
+    state: MediationState,
+    keylist: Vec<String>,
+    live_delivery: bool,
+}
+
+impl Default for MediationRecipient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MediationRecipient {
+    pub fn new() -> Self {
+        Self {
+            state: MediationState::Initial,
+            keylist: vec![],
+            live_delivery: false,
+        }
+    }
+
+    pub fn get_state(&self) -> &MediationState {
+        &self.state
+    }
+
+    pub fn is_granted(&self) -> bool {
+        matches!(self.state, MediationState::Granted { .. })
+    }
+
+    /// Recipient keys the mediator confirmed to route messages for
+    pub fn get_keylist(&self) -> &[String] {
+        &self.keylist
+    }
+
+    pub fn is_live_delivery(&self) -> bool {
+        self.live_delivery
+    }
+
+    pub fn get_endpoint(&self) -> VcxResult<Url> {
+        let MediationState::Granted { endpoint, .. } = &self.state else {
+            return Err(not_granted_error());
+        };
+        Url::parse(endpoint).map_err(|err| {
+            AriesVcxError::from_msg(
+                AriesVcxErrorKind::InvalidUrl,
+                format!("Mediator granted invalid endpoint {endpoint}: {err}"),
+            )
+        })
+    }
+
+    pub fn get_routing_keys(&self) -> VcxResult<&[String]> {
+        let MediationState::Granted { routing_keys, .. } = &self.state else {
+            return Err(not_granted_error());
+        };
+        Ok(routing_keys)
+    }
+
+    pub async fn send_mediate_request(&mut self, send_message: SendClosure<'_>) -> VcxResult<()> {
+        if self.is_granted() {
+            return Err(AriesVcxError::from_msg(
+                AriesVcxErrorKind::InvalidState,
+                "Mediation has already been granted",
+            ));
+        }
+        let request = build_mediate_request();
+        let thread_id = request.id.clone();
+        send_message(request.into()).await?;
+        self.state = MediationState::RequestSent { thread_id };
+        Ok(())
+    }
+
+    pub fn handle_mediate_grant(&mut self, grant: MediateGrant) -> VcxResult<()> {
+        let thread_id = self.get_request_thread_id()?;
+        if !matches_opt_thread_id!(grant, thread_id) {
+            return Err(thread_mismatch_error());
+        }
+        self.state = MediationState::Granted {
+            endpoint: grant.content.endpoint,
+            routing_keys: grant.content.routing_keys,
+        };
+        Ok(())
+    }
+
+    pub fn handle_mediate_deny(&mut self, deny: &MediateDeny) -> VcxResult<()> {
+        let thread_id = self.get_request_thread_id()?;
+        if !matches_opt_thread_id!(deny, thread_id) {
+            return Err(thread_mismatch_error());
+        }
+        self.state = MediationState::Denied;
+        Ok(())
+    }
+
+    /// Asks the mediator to add and remove recipient keys. The local keylist is updated once
+    /// the mediator confirms the changes, see [`Self::handle_keylist_update_response`].
+    pub async fn send_keylist_update(
+        &self,
+        updates: Vec<KeylistUpdateItem>,
+        send_message: SendClosure<'_>,
+    ) -> VcxResult<()> {
+        if !self.is_granted() {
+            return Err(not_granted_error());
+        }
+        send_message(build_keylist_update(updates).into()).await
+    }
+
+    pub fn handle_keylist_update_response(
+        &mut self,
+        response: &KeylistUpdateResponse,
+    ) -> VcxResult<()> {
+        if !self.is_granted() {
+            return Err(not_granted_error());
+        }
+        for item in &response.content.updated {
+            if !matches!(
+                item.result,
+                KeylistUpdateItemResult::Success | KeylistUpdateItemResult::NoChange
+            ) {
+                warn!(
+                    "Mediator failed to {:?} recipient key {}: {:?}",
+                    item.action, item.recipient_key, item.result
+                );
+                continue;
+            }
+            match item.action {
+                KeylistUpdateItemAction::Add => {
+                    if !self.keylist.contains(&item.recipient_key) {
+                        self.keylist.push(item.recipient_key.clone());
+                    }
+                }
+                KeylistUpdateItemAction::Remove => {
+                    self.keylist.retain(|key| key != &item.recipient_key);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub async fn send_keylist_query(&self, send_message: SendClosure<'_>) -> VcxResult<()> {
+        if !self.is_granted() {
+            return Err(not_granted_error());
+        }
+        send_message(build_keylist_query().into()).await
+    }
+
+    /// Replaces the local keylist with the one reported by the mediator
+    pub fn handle_keylist(&mut self, keylist: &Keylist) -> VcxResult<()> {
+        if !self.is_granted() {
+            return Err(not_granted_error());
+        }
+        self.keylist = keylist
+            .content
+            .keys
+            .iter()
+            .map(|item| item.recipient_key.clone())
+            .collect();
+        Ok(())
+    }
+
+    /// Creates did:peer:4 whose service routes through the mediator, and asks the mediator to
+    /// accept messages for its key.
+    pub async fn create_routed_peer_did_4(
+        &self,
+        wallet: &impl BaseWallet,
+        send_message: SendClosure<'_>,
+    ) -> VcxResult<(PeerDid<Numalgo4>, Key)> {
+        let (peer_did, key) = create_peer_did_4(
+            wallet,
+            self.get_endpoint()?,
+            self.get_routing_keys()?.to_vec(),
+        )
+        .await?;
+        let update = KeylistUpdateItem::builder()
+            .recipient_key(key.base58())
+            .action(KeylistUpdateItemAction::Add)
+            .build();
+        self.send_keylist_update(vec![update], send_message).await?;
+        Ok((peer_did, key))
+    }
+
+    pub async fn send_status_request(
+        &self,
+        recipient_key: Option<String>,
+        send_message: SendClosure<'_>,
+    ) -> VcxResult<()> {
+        send_message(build_status_request(recipient_key).into()).await
+    }
+
+    pub async fn send_delivery_request(
+        &self,
+        limit: u32,
+        recipient_key: Option<String>,
+        send_message: SendClosure<'_>,
+    ) -> VcxResult<()> {
+        send_message(build_delivery_request(limit, recipient_key).into()).await
+    }
+
+    pub async fn send_messages_received(
+        &self,
+        message_id_list: Vec<String>,
+        send_message: SendClosure<'_>,
+    ) -> VcxResult<()> {
+        send_message(build_messages_received(message_id_list).into()).await
+    }
+
+    /// Toggles live mode, in which the mediator pushes messages over the open connection as
+    /// they arrive instead of queueing them for polling
+    pub async fn send_live_delivery_change(
+        &mut self,
+        live_delivery: bool,
+        send_message: SendClosure<'_>,
+    ) -> VcxResult<()> {
+        send_message(build_live_delivery_change(live_delivery).into()).await?;
+        self.live_delivery = live_delivery;
+        Ok(())
+    }
+
+    /// Extracts messages attached to delivery. Once they're processed, the recipient should
+    /// acknowledge them using [`Self::send_messages_received`] so the mediator can drop them.
+    pub fn handle_delivery(delivery: &Delivery) -> VcxResult<Vec<DeliveredMessage>> {
+        delivery
+            .content
+            .attach
+            .iter()
+            .map(|attachment| {
+                let id = attachment.id.clone().ok_or_else(|| {
+                    AriesVcxError::from_msg(
+                        AriesVcxErrorKind::InvalidMessageFormat,
+                        "Delivered attachment is missing id",
+                    )
+                })?;
+                let message = match &attachment.data.content {
+                    AttachmentType::Base64(encoded) => {
+                        URL_SAFE_LENIENT.decode(encoded).map_err(|err| {
+                            AriesVcxError::from_msg(
+                                AriesVcxErrorKind::InvalidMessageFormat,
+                                format!("Delivered attachment {id} is not valid base64: {err}"),
+                            )
+                        })?
+                    }
+                    AttachmentType::Json(value) => serde_json::to_vec(value)?,
+                    AttachmentType::Links(_) => {
+                        return Err(AriesVcxError::from_msg(
+                            AriesVcxErrorKind::InvalidMessageFormat,
+                            format!("Delivered attachment {id} has unsupported links content"),
+                        ))
+                    }
+                };
+                Ok(DeliveredMessage { id, message })
+            })
+            .collect()
+    }
+
+    /// Polls the mediator until no more messages are queued, acknowledging each delivered
+    /// batch. `exchange` sends a message to the mediator and returns its response, which
+    /// requires a transport supporting return route, such as a plain HTTP request.
+    pub async fn pickup_messages<F, Fut>(
+        &self,
+        batch_size: u32,
+        recipient_key: Option<String>,
+        mut exchange: F,
+    ) -> VcxResult<Vec<DeliveredMessage>>
+    where
+        F: FnMut(AriesMessage) -> Fut,
+        Fut: Future<Output = VcxResult<AriesMessage>>,
+    {
+        let mut delivered = vec![];
+        loop {
+            let request = build_delivery_request(batch_size, recipient_key.clone());
+            let delivery = match exchange(request.into()).await? {
+                AriesMessage::Pickup(Pickup::Delivery(delivery)) => delivery,
+                // mediator responds with status when there's nothing to deliver
+                AriesMessage::Pickup(Pickup::Status(_)) => break,
+                other => {
+                    return Err(AriesVcxError::from_msg(
+                        AriesVcxErrorKind::InvalidMessageFormat,
+                        format!("Unexpected response to delivery request: {other:?}"),
+                    ))
+                }
+            };
+            let batch = Self::handle_delivery(&delivery)?;
+            if batch.is_empty() {
+                break;
+            }
+            let message_id_list = batch.iter().map(|msg| msg.id.clone()).collect();
+            exchange(build_messages_received(message_id_list).into()).await?;
+            delivered.extend(batch);
+        }
+        Ok(delivered)
+    }
+
+    fn get_request_thread_id(&self) -> VcxResult<&str> {
+        match &self.state {
+            MediationState::RequestSent { thread_id } => Ok(thread_id.as_str()),
+            _ => Err(AriesVcxError::from_msg(
+                AriesVcxErrorKind::InvalidState,
+                "Mediate request has not been sent",
+            )),
+        }
+    }
+}
+
+fn not_granted_error() -> AriesVcxError {
+    AriesVcxError::from_msg(
+        AriesVcxErrorKind::NotReady,
+        "Mediation has not been granted",
+    )
+}
+
+fn thread_mismatch_error() -> AriesVcxError {
+    AriesVcxError::from_msg(AriesVcxErrorKind::InvalidState, "Thread ID mismatch")
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use base64::Engine;
+    use messages::{
+        decorators::{
+            attachment::{Attachment, AttachmentData, AttachmentType},
+            thread::Thread,
+        },
+        msg_fields::protocols::{
+            coordinate_mediation::{
+                keylist::KeylistItem,
+                keylist_update::KeylistUpdateItemAction,
+                keylist_update_response::{KeylistUpdateItemResult, KeylistUpdateResponseItem},
+                Keylist, KeylistContent, KeylistUpdateResponse, KeylistUpdateResponseContent,
+                MediateGrant, MediateGrantContent, MediateGrantDecorators,
+            },
+            pickup::{Delivery, DeliveryContent, Status, StatusContent},
+        },
+        AriesMessage,
+    };
+    use test_utils::devsetup::SetupMocks;
+
+    use super::{MediationRecipient, MediationState};
+    use crate::{
+        errors::error::VcxResult, protocols::SendClosure, utils::base64::URL_SAFE_LENIENT,
+    };
+
+    pub fn _send_message() -> SendClosure<'static> {
+        Box::new(|_: AriesMessage| Box::pin(async { VcxResult::Ok(()) }))
+    }
+
+    fn _grant(thread_id: &str) -> MediateGrant {
+        MediateGrant::builder()
+            .id("grant".to_owned())
+            .content(
+                MediateGrantContent::builder()
+                    .endpoint("http://mediator.example.org/aries".to_owned())
+                    .routing_keys(vec!["routing-key".to_owned()])
+                    .build(),
+            )
+            .decorators(
+                MediateGrantDecorators::builder()
+                    .thread(Thread::builder().thid(thread_id.to_owned()).build())
+                    .build(),
+            )
+            .build()
+    }
+
+    async fn _granted_recipient() -> MediationRecipient {
+        let mut recipient = MediationRecipient::new();
+        recipient
+            .send_mediate_request(_send_message())
+            .await
+            .unwrap();
+        let thread_id = recipient.get_request_thread_id().unwrap().to_owned();
+        recipient.handle_mediate_grant(_grant(&thread_id)).unwrap();
+        recipient
+    }
+
+    fn _update_response(
+        recipient_key: &str,
+        action: KeylistUpdateItemAction,
+        result: KeylistUpdateItemResult,
+    ) -> KeylistUpdateResponse {
+        KeylistUpdateResponse::builder()
+            .id("update-response".to_owned())
+            .content(
+                KeylistUpdateResponseContent::builder()
+                    .updated(vec![KeylistUpdateResponseItem::builder()
+                        .recipient_key(recipient_key.to_owned())
+                        .action(action)
+                        .result(result)
+                        .build()])
+                    .build(),
+            )
+            .build()
+    }
+
+    #[tokio::test]
+    async fn test_mediation_granted() {
+        let _setup = SetupMocks::init();
+        let recipient = _granted_recipient().await;
+        assert_eq!(
+            recipient.get_state(),
+            &MediationState::Granted {
+                endpoint: "http://mediator.example.org/aries".to_owned(),
+                routing_keys: vec!["routing-key".to_owned()],
+            }
+        );
+        assert_eq!(
+            recipient.get_endpoint().unwrap().as_str(),
+            "http://mediator.example.org/aries"
+        );
+        assert_eq!(recipient.get_routing_keys().unwrap(), ["routing-key"]);
+    }
+
+    #[tokio::test]
+    async fn test_should_fail_on_thread_id_mismatch() {
+        let _setup = SetupMocks::init();
+        let mut recipient = MediationRecipient::new();
+        recipient
+            .send_mediate_request(_send_message())
+            .await
+            .unwrap();
+        recipient
+            .handle_mediate_grant(_grant("other-thread"))
+            .unwrap_err();
+        assert!(!recipient.is_granted());
+    }
+
+    #[tokio::test]
+    async fn test_should_fail_on_grant_without_request() {
+        let _setup = SetupMocks::init();
+        let mut recipient = MediationRecipient::new();
+        recipient
+            .handle_mediate_grant(_grant("thread"))
+            .unwrap_err();
+        recipient.get_endpoint().unwrap_err();
+    }
+
+    #[tokio::test]
+    async fn test_keylist_sync() {
+        let _setup = SetupMocks::init();
+        let mut recipient = _granted_recipient().await;
+        recipient
+            .handle_keylist_update_response(&_update_response(
+                "key1",
+                KeylistUpdateItemAction::Add,
+                KeylistUpdateItemResult::Success,
+            ))
+            .unwrap();
+        recipient
+            .handle_keylist_update_response(&_update_response(
+                "key2",
+                KeylistUpdateItemAction::Add,
+                KeylistUpdateItemResult::ServerError,
+            ))
+            .unwrap();
+        assert_eq!(recipient.get_keylist(), ["key1"]);
+
+        recipient
+            .handle_keylist_update_response(&_update_response(
+                "key1",
+                KeylistUpdateItemAction::Remove,
+                KeylistUpdateItemResult::Success,
+            ))
+            .unwrap();
+        assert!(recipient.get_keylist().is_empty());
+
+        let keylist = Keylist::builder()
+            .id("keylist".to_owned())
+            .content(
+                KeylistContent::builder()
+                    .keys(vec![KeylistItem::builder()
+                        .recipient_key("key3".to_owned())
+                        .build()])
+                    .build(),
+            )
+            .build();
+        recipient.handle_keylist(&keylist).unwrap();
+        assert_eq!(recipient.get_keylist(), ["key3"]);
+    }
+
+    #[tokio::test]
+    async fn test_pickup_messages() {
+        let _setup = SetupMocks::init();
+        let recipient = _granted_recipient().await;
+        let mut attachment = Attachment::builder()
+            .data(
+                AttachmentData::builder()
+                    .content(AttachmentType::Base64(URL_SAFE_LENIENT.encode(b"packed")))
+                    .build(),
+            )
+            .build();
+        attachment.id = Some("msg-1".to_owned());
+        let delivery: Delivery = Delivery::builder()
+            .id("delivery".to_owned())
+            .content(DeliveryContent::builder().attach(vec![attachment]).build())
+            .build();
+
+        let status: Status = Status::builder()
+            .id("status".to_owned())
+            .content(StatusContent::builder().message_count(0).build())
+            .build();
+
+        // delivery, then status after acknowledging it, then status as queue is empty
+        let mut responses = vec![
+            AriesMessage::from(delivery),
+            AriesMessage::from(status.clone()),
+            AriesMessage::from(status),
+        ];
+        let mut requests = vec![];
+        let delivered = recipient
+            .pickup_messages(10, None, |msg| {
+                requests.push(msg);
+                let response = responses.remove(0);
+                async move { Ok(response) }
+            })
+            .await
+            .unwrap();
+        assert_eq!(delivered.len(), 1);
+        assert_eq!(delivered[0].id, "msg-1");
+        assert_eq!(delivered[0].message, b"packed");
+        assert_eq!(requests.len(), 3);
+    }
+}
@@ -1,4 +1,5 @@
 pub mod issuance;
+pub mod mediation;
 pub mod out_of_band;
 pub mod proof_presentation;
 pub mod revocation_notification;
@@ -1,7 +1,7 @@
 pub mod receiver;
 pub mod sender;
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum GenericOutOfBand {
     Receiver(receiver::OutOfBandReceiver),
     Sender(sender::OutOfBandSender),
@@ -18,7 +18,7 @@ use crate::{
     errors::error::prelude::*, handlers::util::AttachmentId, utils::base64::URL_SAFE_LENIENT,
 };
 
-#[derive(Debug, PartialEq, Clone)]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
 pub struct OutOfBandReceiver {
     pub oob: Invitation,
 }
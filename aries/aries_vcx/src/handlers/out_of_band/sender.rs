@@ -23,7 +23,7 @@ use crate::{
     utils::base64::URL_SAFE_LENIENT,
 };
 
-#[derive(Debug, PartialEq, Clone)]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
 pub struct OutOfBandSender {
     pub oob: Invitation,
 }
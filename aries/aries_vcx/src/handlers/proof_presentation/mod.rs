@@ -1,2 +1,3 @@
 pub mod prover;
+pub mod v2_bridge;
 pub mod verifier;
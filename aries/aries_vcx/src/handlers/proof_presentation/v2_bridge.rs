@@ -0,0 +1,576 @@
+//! Conversions letting the Present Proof 1.0 state machines of [`super::prover::Prover`] and
+//! [`super::verifier::Verifier`] run a Present Proof 2.0 exchange with the AnonCreds (`hlindy`)
+//! attachment formats, the counterpart of [`crate::handlers::issuance::v2_bridge`].
+
+use std::collections::HashMap;
+
+use messages::msg_fields::protocols::{
+    common::attachment_format_specifier::OptionalIdAttachmentFormatSpecifier,
+    present_proof::{
+        v1::{
+            ack::{AckPresentationV1, AckPresentationV1Content},
+            present::{PresentationV1, PresentationV1Content, PresentationV1Decorators},
+            problem_report::{PresentProofV1ProblemReport, PresentProofV1ProblemReportContent},
+            propose::{
+                Predicate, PredicateOperator, PresentationAttr, PresentationPreview,
+                ProposePresentationV1, ProposePresentationV1Content,
+                ProposePresentationV1Decorators, Referent,
+            },
+            request::{
+                RequestPresentationV1, RequestPresentationV1Content,
+                RequestPresentationV1Decorators,
+            },
+            PresentProofV1,
+        },
+        v2::{
+            ack::{AckPresentationV2, AckPresentationV2Content},
+            present::{
+                PresentationAttachmentFormatType, PresentationV2, PresentationV2Content,
+                PresentationV2Decorators,
+            },
+            problem_report::{PresentProofV2ProblemReport, PresentProofV2ProblemReportContent},
+            propose::{
+                ProposePresentationAttachmentFormatType, ProposePresentationV2,
+                ProposePresentationV2Content, ProposePresentationV2Decorators,
+            },
+            request::{
+                PresentationRequestAttachmentFormatType, RequestPresentationV2,
+                RequestPresentationV2Content, RequestPresentationV2Decorators,
+            },
+            PresentProofV2,
+        },
+    },
+};
+use serde_json::Value;
+use shared::maybe_known::MaybeKnown;
+use uuid::Uuid;
+
+use crate::{
+    errors::error::{AriesVcxError, AriesVcxErrorKind, VcxResult},
+    handlers::{
+        issuance::v2_bridge::{attachment_bytes, select_attachment, specify_format},
+        util::make_attach_from_str,
+    },
+};
+
+pub fn to_v2(msg: PresentProofV1) -> VcxResult<PresentProofV2> {
+    Ok(match msg {
+        PresentProofV1::ProposePresentation(msg) => proposal_to_v2(msg).into(),
+        PresentProofV1::RequestPresentation(msg) => request_to_v2(msg).into(),
+        PresentProofV1::Presentation(msg) => presentation_to_v2(msg).into(),
+        PresentProofV1::Ack(msg) => AckPresentationV2 {
+            id: msg.id,
+            content: AckPresentationV2Content {
+                inner: msg.content.inner,
+            },
+            decorators: msg.decorators,
+        }
+        .into(),
+        PresentProofV1::ProblemReport(msg) => PresentProofV2ProblemReport {
+            id: msg.id,
+            content: PresentProofV2ProblemReportContent {
+                inner: msg.content.inner,
+            },
+            decorators: msg.decorators,
+        }
+        .into(),
+    })
+}
+
+pub fn to_v1(msg: PresentProofV2) -> VcxResult<PresentProofV1> {
+    Ok(match msg {
+        PresentProofV2::ProposePresentation(msg) => proposal_to_v1(msg)?.into(),
+        PresentProofV2::RequestPresentation(msg) => request_to_v1(msg)?.into(),
+        PresentProofV2::Presentation(msg) => presentation_to_v1(msg)?.into(),
+        PresentProofV2::Ack(msg) => AckPresentationV1 {
+            id: msg.id,
+            content: AckPresentationV1Content {
+                inner: msg.content.inner,
+            },
+            decorators: msg.decorators,
+        }
+        .into(),
+        PresentProofV2::ProblemReport(msg) => PresentProofV1ProblemReport {
+            id: msg.id,
+            content: PresentProofV1ProblemReportContent {
+                inner: msg.content.inner,
+            },
+            decorators: msg.decorators,
+        }
+        .into(),
+    })
+}
+
+/// Present Proof 2.0 proposals carry a proof request rather than a presentation preview; each
+/// previewed attribute and predicate becomes a requested one, restricted to its credential
+/// definition if the preview names one
+fn proposal_to_v2(msg: ProposePresentationV1) -> ProposePresentationV2 {
+    let preview = msg.content.presentation_proposal;
+    let restrictions = |cred_def_id: Option<&String>| {
+        cred_def_id.map_or(
+            json!([]),
+            |cred_def_id| json!([{ "cred_def_id": cred_def_id }]),
+        )
+    };
+    let requested_attributes: HashMap<String, Value> = preview
+        .attributes
+        .iter()
+        .enumerate()
+        .map(|(i, attr)| {
+            let referent = attr.referent.clone().unwrap_or(format!("attribute_{i}"));
+            let requested = json!({
+                "name": attr.name,
+                "restrictions": restrictions(attr.cred_def_id.as_ref()),
+            });
+            (referent, requested)
+        })
+        .collect();
+    let requested_predicates: HashMap<String, Value> = preview
+        .predicates
+        .iter()
+        .enumerate()
+        .map(|(i, predicate)| {
+            let referent = predicate
+                .referent
+                .as_ref()
+                .map_or(format!("predicate_{i}"), |referent| {
+                    referent.referent.clone()
+                });
+            let requested = json!({
+                "name": predicate.name,
+                "p_type": predicate.predicate,
+                "p_value": predicate.threshold,
+                "restrictions": restrictions(
+                    predicate.referent.as_ref().map(|referent| &referent.cred_def_id)
+                ),
+            });
+            (referent, requested)
+        })
+        .collect();
+    let proof_request = json!({
+        "name": "proposal",
+        "version": "1.0",
+        "requested_attributes": requested_attributes,
+        "requested_predicates": requested_predicates,
+    });
+    let attach_id = Uuid::new_v4().to_string();
+    let format = OptionalIdAttachmentFormatSpecifier::builder()
+        .attach_id(Some(attach_id.clone()))
+        .format(MaybeKnown::Known(
+            ProposePresentationAttachmentFormatType::HyperledgerIndyProofRequest2_0,
+        ))
+        .build();
+    ProposePresentationV2 {
+        id: msg.id,
+        content: ProposePresentationV2Content {
+            goal_code: None,
+            comment: msg.content.comment,
+            formats: vec![format],
+            proposals_attach: Some(vec![make_attach_from_str!(
+                &proof_request.to_string(),
+                attach_id
+            )]),
+        },
+        decorators: ProposePresentationV2Decorators {
+            thread: msg.decorators.thread,
+            timing: msg.decorators.timing,
+        },
+    }
+}
+
+fn proposal_to_v1(msg: ProposePresentationV2) -> VcxResult<ProposePresentationV1> {
+    let attach_id = msg
+        .content
+        .formats
+        .iter()
+        .find_map(|specifier| match &specifier.format {
+            MaybeKnown::Known(
+                ProposePresentationAttachmentFormatType::HyperledgerIndyProofRequest2_0
+                | ProposePresentationAttachmentFormatType::AnoncredsProofRequest1_0,
+            ) => specifier.attach_id.as_ref(),
+            _ => None,
+        })
+        .ok_or_else(|| {
+            AriesVcxError::from_msg(
+                AriesVcxErrorKind::InvalidMessageFormat,
+                "Proposal carries no proof request in a supported AnonCreds format",
+            )
+        })?;
+    let proof_request = msg
+        .content
+        .proposals_attach
+        .iter()
+        .flatten()
+        .find(|attachment| attachment.id.as_ref() == Some(attach_id))
+        .ok_or_else(|| {
+            AriesVcxError::from_msg(
+                AriesVcxErrorKind::InvalidMessageFormat,
+                format!("Proposal is missing the attachment {attach_id} its formats refer to"),
+            )
+        })?;
+    let proof_request: Value = serde_json::from_slice(&attachment_bytes(proof_request)?)?;
+
+    let cred_def_id = |requested: &Value| {
+        requested["restrictions"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .find_map(|restriction| restriction["cred_def_id"].as_str().map(ToOwned::to_owned))
+    };
+    let mut attributes = vec![];
+    for (referent, requested) in proof_request["requested_attributes"]
+        .as_object()
+        .into_iter()
+        .flatten()
+    {
+        let names = match (&requested["name"], &requested["names"]) {
+            (Value::String(name), _) => vec![name.to_owned()],
+            (_, Value::Array(names)) => names
+                .iter()
+                .filter_map(|name| name.as_str().map(ToOwned::to_owned))
+                .collect(),
+            _ => vec![],
+        };
+        for name in names {
+            attributes.push(PresentationAttr {
+                name,
+                cred_def_id: cred_def_id(requested),
+                mime_type: None,
+                value: None,
+                referent: Some(referent.to_owned()),
+            });
+        }
+    }
+    let mut predicates = vec![];
+    for (referent, requested) in proof_request["requested_predicates"]
+        .as_object()
+        .into_iter()
+        .flatten()
+    {
+        let predicate: PredicateOperator = serde_json::from_value(requested["p_type"].clone())?;
+        predicates.push(Predicate {
+            name: requested["name"].as_str().unwrap_or_default().to_owned(),
+            predicate,
+            threshold: requested["p_value"].as_i64().unwrap_or_default(),
+            referent: cred_def_id(requested)
+                .map(|cred_def_id| Referent::new(cred_def_id, referent.to_owned())),
+        });
+    }
+    Ok(ProposePresentationV1 {
+        id: msg.id,
+        content: ProposePresentationV1Content {
+            comment: msg.content.comment,
+            presentation_proposal: PresentationPreview::new(attributes, predicates),
+        },
+        decorators: ProposePresentationV1Decorators {
+            thread: msg.decorators.thread,
+            timing: msg.decorators.timing,
+        },
+    })
+}
+
+fn request_to_v2(msg: RequestPresentationV1) -> RequestPresentationV2 {
+    let (formats, request_presentations_attach) = specify_format(
+        msg.content.request_presentations_attach,
+        PresentationRequestAttachmentFormatType::HyperledgerIndyProofRequest2_0,
+    );
+    RequestPresentationV2 {
+        id: msg.id,
+        content: RequestPresentationV2Content {
+            goal_code: None,
+            comment: msg.content.comment,
+            // the verifier acknowledges every presentation it verifies
+            will_confirm: Some(true),
+            formats,
+            request_presentations_attach,
+        },
+        decorators: RequestPresentationV2Decorators {
+            thread: msg.decorators.thread,
+            timing: msg.decorators.timing,
+        },
+    }
+}
+
+fn request_to_v1(msg: RequestPresentationV2) -> VcxResult<RequestPresentationV1> {
+    let request = select_attachment(
+        &msg.content.formats,
+        msg.content.request_presentations_attach,
+        |format| {
+            matches!(
+                format,
+                PresentationRequestAttachmentFormatType::HyperledgerIndyProofRequest2_0
+                    | PresentationRequestAttachmentFormatType::AnoncredsProofRequest1_0
+            )
+        },
+    )?;
+    Ok(RequestPresentationV1 {
+        id: msg.id,
+        content: RequestPresentationV1Content {
+            comment: msg.content.comment,
+            request_presentations_attach: vec![request],
+        },
+        decorators: RequestPresentationV1Decorators {
+            thread: msg.decorators.thread,
+            timing: msg.decorators.timing,
+        },
+    })
+}
+
+fn presentation_to_v2(msg: PresentationV1) -> PresentationV2 {
+    let (formats, presentations_attach) = specify_format(
+        msg.content.presentations_attach,
+        PresentationAttachmentFormatType::HyperledgerIndyProof2_0,
+    );
+    PresentationV2 {
+        id: msg.id,
+        content: PresentationV2Content {
+            comment: msg.content.comment,
+            goal_code: None,
+            formats,
+            presentations_attach,
+        },
+        decorators: PresentationV2Decorators {
+            thread: msg.decorators.thread,
+            please_ack: msg.decorators.please_ack,
+            timing: msg.decorators.timing,
+        },
+    }
+}
+
+fn presentation_to_v1(msg: PresentationV2) -> VcxResult<PresentationV1> {
+    let presentation = select_attachment(
+        &msg.content.formats,
+        msg.content.presentations_attach,
+        |format| {
+            matches!(
+                format,
+                PresentationAttachmentFormatType::HyperledgerIndyProof2_0
+                    | PresentationAttachmentFormatType::AnoncredsProof1_0
+            )
+        },
+    )?;
+    Ok(PresentationV1 {
+        id: msg.id,
+        content: PresentationV1Content {
+            comment: msg.content.comment,
+            presentations_attach: vec![presentation],
+        },
+        decorators: PresentationV1Decorators {
+            thread: msg.decorators.thread,
+            please_ack: msg.decorators.please_ack,
+            timing: msg.decorators.timing,
+        },
+    })
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use messages::{
+        decorators::thread::Thread,
+        msg_fields::protocols::{
+            common::attachment_format_specifier::AttachmentFormatSpecifier,
+            notification::ack::{AckContent, AckDecorators, AckStatus},
+            report_problem::{Description, ProblemReportContent, ProblemReportDecorators},
+        },
+    };
+
+    use super::*;
+
+    fn thread() -> Thread {
+        Thread::builder().thid("thread-id".to_owned()).build()
+    }
+
+    fn format<F>(attach_id: &str, format: F) -> Vec<AttachmentFormatSpecifier<F>> {
+        vec![AttachmentFormatSpecifier::builder()
+            .attach_id(attach_id.to_owned())
+            .format(MaybeKnown::Known(format))
+            .build()]
+    }
+
+    fn assert_v2_round_trip(msg: impl Into<PresentProofV2>) {
+        let msg = msg.into();
+        let round_tripped = to_v2(to_v1(msg.clone()).unwrap()).unwrap();
+        assert_eq!(round_tripped, msg);
+    }
+
+    #[test]
+    fn test_request_round_trip() {
+        let request = RequestPresentationV1 {
+            id: "request-id".to_owned(),
+            content: RequestPresentationV1Content {
+                comment: None,
+                request_presentations_attach: vec![make_attach_from_str!(
+                    r#"{"name":"proof","requested_attributes":{}}"#,
+                    "libindy-request-presentation-0".to_owned()
+                )],
+            },
+            decorators: RequestPresentationV1Decorators {
+                thread: Some(Thread::builder().thid("thread-id".to_owned()).build()),
+                timing: None,
+            },
+        };
+
+        let request_v1 = to_v1(to_v2(request.clone().into()).unwrap()).unwrap();
+        assert_eq!(request_v1, PresentProofV1::RequestPresentation(request));
+    }
+
+    #[test]
+    fn test_proposal_round_trip() {
+        let proposal = ProposePresentationV1 {
+            id: "proposal-id".to_owned(),
+            content: ProposePresentationV1Content {
+                comment: Some("comment".to_owned()),
+                presentation_proposal: PresentationPreview::new(
+                    vec![PresentationAttr {
+                        name: "name".to_owned(),
+                        cred_def_id: Some("cred-def-id".to_owned()),
+                        mime_type: None,
+                        value: None,
+                        referent: Some("attribute_0".to_owned()),
+                    }],
+                    vec![Predicate {
+                        name: "age".to_owned(),
+                        predicate: PredicateOperator::GreaterOrEqual,
+                        threshold: 18,
+                        referent: Some(Referent::new(
+                            "cred-def-id".to_owned(),
+                            "predicate_0".to_owned(),
+                        )),
+                    }],
+                ),
+            },
+            decorators: ProposePresentationV1Decorators::default(),
+        };
+
+        let proposal_v1 = to_v1(to_v2(proposal.clone().into()).unwrap()).unwrap();
+        assert_eq!(proposal_v1, PresentProofV1::ProposePresentation(proposal));
+    }
+
+    #[test]
+    fn test_v2_proposal_round_trip() {
+        let proof_request = json!({
+            "name": "proposal",
+            "version": "1.0",
+            "requested_attributes": {
+                "attribute_0": {
+                    "name": "name",
+                    "restrictions": [{ "cred_def_id": "cred-def-id" }],
+                },
+            },
+            "requested_predicates": {
+                "predicate_0": {
+                    "name": "age",
+                    "p_type": ">=",
+                    "p_value": 18,
+                    "restrictions": [{ "cred_def_id": "cred-def-id" }],
+                },
+            },
+        });
+        let proposal = ProposePresentationV2 {
+            id: "proposal-id".to_owned(),
+            content: ProposePresentationV2Content {
+                goal_code: None,
+                comment: Some("comment".to_owned()),
+                formats: vec![OptionalIdAttachmentFormatSpecifier::builder()
+                    .attach_id(Some("proposal-0".to_owned()))
+                    .format(MaybeKnown::Known(
+                        ProposePresentationAttachmentFormatType::HyperledgerIndyProofRequest2_0,
+                    ))
+                    .build()],
+                proposals_attach: Some(vec![make_attach_from_str!(
+                    &proof_request.to_string(),
+                    "proposal-0".to_owned()
+                )]),
+            },
+            decorators: ProposePresentationV2Decorators {
+                thread: Some(thread()),
+                timing: None,
+            },
+        };
+
+        let PresentProofV2::ProposePresentation(mut round_tripped) =
+            to_v2(to_v1(proposal.clone().into()).unwrap()).unwrap()
+        else {
+            panic!("Expected a proposal");
+        };
+        // the proof request is rebuilt from the preview, and attached under an id of its own
+        let attachments = round_tripped.content.proposals_attach.take().unwrap();
+        assert_eq!(
+            attachments[0].id,
+            round_tripped.content.formats[0].attach_id
+        );
+        let round_tripped_request: Value =
+            serde_json::from_slice(&attachment_bytes(&attachments[0]).unwrap()).unwrap();
+        assert_eq!(round_tripped_request, proof_request);
+        assert_eq!(
+            round_tripped.content.formats[0].format,
+            proposal.content.formats[0].format
+        );
+        assert_eq!(round_tripped.content.comment, proposal.content.comment);
+        assert_eq!(round_tripped.decorators, proposal.decorators);
+    }
+
+    #[test]
+    fn test_v2_request_round_trip() {
+        assert_v2_round_trip(RequestPresentationV2 {
+            id: "request-id".to_owned(),
+            content: RequestPresentationV2Content {
+                goal_code: None,
+                comment: Some("comment".to_owned()),
+                will_confirm: Some(true),
+                formats: format(
+                    "request-0",
+                    PresentationRequestAttachmentFormatType::HyperledgerIndyProofRequest2_0,
+                ),
+                request_presentations_attach: vec![make_attach_from_str!(
+                    r#"{"name":"proof","requested_attributes":{}}"#,
+                    "request-0".to_owned()
+                )],
+            },
+            decorators: RequestPresentationV2Decorators {
+                thread: Some(thread()),
+                timing: None,
+            },
+        });
+    }
+
+    #[test]
+    fn test_v2_presentation_round_trip() {
+        assert_v2_round_trip(PresentationV2 {
+            id: "presentation-id".to_owned(),
+            content: PresentationV2Content {
+                comment: None,
+                goal_code: None,
+                formats: format(
+                    "presentation-0",
+                    PresentationAttachmentFormatType::HyperledgerIndyProof2_0,
+                ),
+                presentations_attach: vec![make_attach_from_str!(
+                    r#"{"proof":{},"requested_proof":{}}"#,
+                    "presentation-0".to_owned()
+                )],
+            },
+            decorators: PresentationV2Decorators {
+                thread: thread(),
+                please_ack: None,
+                timing: None,
+            },
+        });
+    }
+
+    #[test]
+    fn test_v2_ack_and_problem_report_round_trip() {
+        assert_v2_round_trip(AckPresentationV2 {
+            id: "ack-id".to_owned(),
+            content: AckContent::builder().status(AckStatus::Ok).build(),
+            decorators: AckDecorators::builder().thread(thread()).build(),
+        });
+        assert_v2_round_trip(PresentProofV2ProblemReport {
+            id: "problem-report-id".to_owned(),
+            content: ProblemReportContent::builder()
+                .description(Description::builder().code("rejected".to_owned()).build())
+                .build(),
+            decorators: ProblemReportDecorators::builder().thread(thread()).build(),
+        });
+    }
+}
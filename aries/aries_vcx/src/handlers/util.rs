@@ -5,6 +5,7 @@ use messages::{
         coordinate_mediation::CoordinateMediation,
         cred_issuance::{v1::CredentialIssuanceV1, v2::CredentialIssuanceV2, CredentialIssuance},
         did_exchange::{v1_0::DidExchangeV1_0, v1_1::DidExchangeV1_1, DidExchange},
+        did_rotate::DidRotate,
         discover_features::DiscoverFeatures,
         notification::Notification,
         out_of_band::{invitation::Invitation as OobInvitation, OutOfBand},
@@ -261,6 +262,12 @@ pub fn verify_thread_id(thread_id: &str, message: &AriesMessage) -> VcxResult<()
         AriesMessage::DidExchange(DidExchange::V1_1(DidExchangeV1_1::Response(msg))) => {
             matches_thread_id!(msg, thread_id)
         }
+        AriesMessage::DidRotate(DidRotate::Rotate(msg)) => matches_opt_thread_id!(msg, thread_id),
+        AriesMessage::DidRotate(DidRotate::Ack(msg)) => matches_thread_id!(msg, thread_id),
+        AriesMessage::DidRotate(DidRotate::ProblemReport(msg)) => {
+            matches_opt_thread_id!(msg, thread_id)
+        }
+        AriesMessage::DidRotate(DidRotate::Hangup(msg)) => matches_opt_thread_id!(msg, thread_id),
     };
 
     if !is_match {
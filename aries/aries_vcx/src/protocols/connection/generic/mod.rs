@@ -1,9 +1,16 @@
 mod conversions;
 mod thin_state;
 
+use std::sync::Arc;
+
 use aries_vcx_wallet::wallet::base_wallet::BaseWallet;
+use did_resolver_registry::ResolverRegistry;
 use diddoc_legacy::aries::diddoc::AriesDidDoc;
-use messages::AriesMessage;
+use messages::{
+    msg_fields::protocols::did_rotate::{hangup::Hangup, rotate::Rotate, DidRotate},
+    AriesMessage,
+};
+use url::Url;
 
 pub use self::thin_state::{State, ThinState};
 use super::trait_bounds::BootstrapDidDoc;
@@ -20,8 +27,9 @@ use crate::{
             invited::Invited as InviterInvited, requested::Requested as InviterRequested,
         },
         pairwise_info::PairwiseInfo,
-        trait_bounds::{TheirDidDoc, ThreadId},
+        trait_bounds::{CompletedState, TheirDidDoc, ThreadId},
     },
+    protocols::did_rotate::DidRotation,
     transport::Transport,
     utils::encryption_envelope::EncryptionEnvelope,
 };
@@ -142,8 +150,57 @@ impl GenericConnection {
         }
     }
 
+    pub fn did_rotation(&self) -> Option<&DidRotation> {
+        match &self.state {
+            GenericState::Invitee(InviteeState::Completed(s)) => s.did_rotation(),
+            GenericState::Inviter(InviterState::Completed(s)) => s.did_rotation(),
+            _ => None,
+        }
+    }
+
+    fn did_rotation_mut(&mut self) -> VcxResult<&mut DidRotation> {
+        match &mut self.state {
+            GenericState::Invitee(InviteeState::Completed(s)) => Ok(s.did_rotation_mut()),
+            GenericState::Inviter(InviterState::Completed(s)) => Ok(s.did_rotation_mut()),
+            _ => Err(AriesVcxError::from_msg(
+                AriesVcxErrorKind::NotReady,
+                "Only completed connections can rotate DIDs",
+            )),
+        }
+    }
+
+    /// See [`super::Connection::rotate_did`]
+    pub async fn rotate_did(
+        &mut self,
+        wallet: &impl BaseWallet,
+        service_endpoint: Url,
+        routing_keys: Vec<String>,
+    ) -> VcxResult<Rotate> {
+        self.did_rotation_mut()?
+            .rotate(wallet, service_endpoint, routing_keys)
+            .await
+    }
+
+    /// See [`super::Connection::handle_did_rotate`]
+    pub async fn handle_did_rotate(
+        &mut self,
+        resolver_registry: &Arc<ResolverRegistry>,
+        message: DidRotate,
+    ) -> VcxResult<Option<AriesMessage>> {
+        let thread_id = self.thread_id().unwrap_or_default().to_owned();
+        self.did_rotation_mut()?
+            .handle_message(resolver_registry, message, &thread_id)
+            .await
+    }
+
+    /// See [`super::Connection::hangup`]
+    pub fn hangup(&mut self) -> VcxResult<Hangup> {
+        self.did_rotation_mut()?.hangup()
+    }
+
     pub fn remote_did(&self) -> Option<&str> {
-        self.their_did_doc().map(|d| d.id.as_str())
+        self.their_did_doc()
+            .map(|did_doc| super::remote_did(did_doc, self.did_rotation()))
     }
 
     pub fn remote_vk(&self) -> VcxResult<String> {
@@ -151,15 +208,7 @@ impl GenericConnection {
             AriesVcxErrorKind::NotReady,
             "No DidDoc present",
         ))?;
-
-        did_doc
-            .recipient_keys()?
-            .first()
-            .map(ToOwned::to_owned)
-            .ok_or(AriesVcxError::from_msg(
-                AriesVcxErrorKind::NotReady,
-                "Can't resolve recipient key from the counterparty diddoc.",
-            ))
+        super::remote_vk(did_doc, self.did_rotation())
     }
 
     pub fn invitation(&self) -> Option<&AnyInvitation> {
@@ -175,20 +224,29 @@ impl GenericConnection {
         wallet: &impl BaseWallet,
         message: &AriesMessage,
     ) -> VcxResult<EncryptionEnvelope> {
-        let sender_verkey = &self.pairwise_info().pw_vk;
         let did_doc = self.their_did_doc().ok_or(AriesVcxError::from_msg(
             AriesVcxErrorKind::NotReady,
             "No DidDoc present",
         ))?;
-        EncryptionEnvelope::create_from_legacy(
+        super::encrypt_message(
             wallet,
-            json!(message).to_string().as_bytes(),
-            Some(sender_verkey),
+            message,
+            &self.pairwise_info().pw_vk,
             did_doc,
+            self.did_rotation(),
         )
         .await
     }
 
+    /// Endpoint messages to the counterparty are sent to, following its DID rotation if any
+    pub fn service_endpoint(&self) -> VcxResult<Url> {
+        let did_doc = self.their_did_doc().ok_or(AriesVcxError::from_msg(
+            AriesVcxErrorKind::NotReady,
+            "No DidDoc present",
+        ))?;
+        super::service_endpoint(did_doc, self.did_rotation())
+    }
+
     pub async fn send_message<T>(
         &self,
         wallet: &impl BaseWallet,
@@ -198,15 +256,8 @@ impl GenericConnection {
     where
         T: Transport,
     {
-        let did_doc = self.their_did_doc().ok_or(AriesVcxError::from_msg(
-            AriesVcxErrorKind::NotReady,
-            "No DidDoc present",
-        ))?;
-
         let msg = self.encrypt_message(wallet, message).await?.0;
-        let service_endpoint = did_doc.get_endpoint().ok_or_else(|| {
-            AriesVcxError::from_msg(AriesVcxErrorKind::InvalidUrl, "No URL in DID Doc")
-        })?;
+        let service_endpoint = self.service_endpoint()?;
         transport.send_message(msg, &service_endpoint).await
     }
 }
@@ -226,6 +277,7 @@ mod connection_serde_tests {
                 response::{Response, ResponseContent, ResponseDecorators},
                 ConnectionData,
             },
+            did_rotate::ack::DidRotateAck,
             notification::ack::{Ack, AckContent, AckDecorators, AckStatus},
         },
     };
@@ -528,6 +580,56 @@ mod connection_serde_tests {
         };
     }
 
+    fn make_rotation_ack(thread_id: &str) -> DidRotateAck {
+        let decorators = AckDecorators::builder()
+            .thread(Thread::builder().thid(thread_id.to_owned()).build())
+            .build();
+
+        DidRotateAck::builder()
+            .id(Uuid::new_v4().to_string())
+            .content(AckContent::builder().status(AckStatus::Ok).build())
+            .decorators(decorators)
+            .build()
+    }
+
+    // A first rotation gets acknowledged, a second one is left pending
+    macro_rules! generate_rotation_test {
+        ($name:ident, $func:ident) => {
+            #[tokio::test]
+            async fn $name() {
+                let wallet = MockWallet;
+                let resolver_registry = Arc::new(ResolverRegistry::new());
+                let service_endpoint: Url = SERVICE_ENDPOINT.parse().unwrap();
+                let mut con = $func().await;
+
+                let rotate = con
+                    .rotate_did(&wallet, service_endpoint.clone(), vec![])
+                    .await
+                    .unwrap();
+                let ack = make_rotation_ack(&rotate.id);
+                con.handle_did_rotate(&resolver_registry, DidRotate::Ack(ack))
+                    .await
+                    .unwrap();
+                let rotate = con
+                    .rotate_did(&wallet, service_endpoint, vec![])
+                    .await
+                    .unwrap();
+
+                let did_rotation = GenericConnection::from(con.clone())
+                    .did_rotation()
+                    .cloned()
+                    .unwrap();
+                assert!(did_rotation.our_did_doc().is_some());
+                assert_eq!(did_rotation.pending_thread_id(), Some(rotate.id.as_str()));
+
+                let gen_con: GenericConnection =
+                    serde_json::from_str(&serde_json::to_string(&con).unwrap()).unwrap();
+                assert_eq!(gen_con.did_rotation(), Some(&did_rotation));
+                serde_test(con);
+            }
+        };
+    }
+
     generate_test!(invitee_connection_initial, make_invitee_initial);
     generate_test!(invitee_connection_invited, make_invitee_invited);
     generate_test!(invitee_connection_requested, make_invitee_requested);
@@ -537,4 +639,7 @@ mod connection_serde_tests {
     generate_test!(inviter_connection_invited, make_inviter_invited);
     generate_test!(inviter_connection_requested, make_inviter_requested);
     generate_test!(inviter_connection_complete, make_inviter_completed);
+
+    generate_rotation_test!(invitee_connection_complete_rotated, make_invitee_completed);
+    generate_rotation_test!(inviter_connection_complete_rotated, make_inviter_completed);
 }
@@ -3,8 +3,9 @@ use std::clone::Clone;
 use diddoc_legacy::aries::diddoc::AriesDidDoc;
 use messages::msg_fields::protocols::discover_features::{disclose::Disclose, ProtocolDescriptor};
 
-use crate::protocols::connection::trait_bounds::{
-    BootstrapDidDoc, CompletedState, TheirDidDoc, ThreadId,
+use crate::protocols::{
+    connection::trait_bounds::{BootstrapDidDoc, CompletedState, TheirDidDoc, ThreadId},
+    did_rotate::DidRotation,
 };
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -13,6 +14,8 @@ pub struct Completed {
     pub(crate) bootstrap_did_doc: AriesDidDoc,
     pub(crate) thread_id: String,
     pub(crate) protocols: Option<Vec<ProtocolDescriptor>>,
+    #[serde(default, skip_serializing_if = "DidRotation::is_initial")]
+    pub(crate) did_rotation: DidRotation,
 }
 
 impl Completed {
@@ -27,6 +30,7 @@ impl Completed {
             bootstrap_did_doc,
             thread_id,
             protocols,
+            did_rotation: DidRotation::default(),
         }
     }
 }
@@ -35,6 +39,10 @@ impl TheirDidDoc for Completed {
     fn their_did_doc(&self) -> &AriesDidDoc {
         &self.did_doc
     }
+
+    fn did_rotation(&self) -> Option<&DidRotation> {
+        Some(&self.did_rotation)
+    }
 }
 
 impl BootstrapDidDoc for Completed {
@@ -57,4 +65,8 @@ impl CompletedState for Completed {
     fn handle_disclose(&mut self, disclose: Disclose) {
         self.protocols = Some(disclose.content.protocols)
     }
+
+    fn did_rotation_mut(&mut self) -> &mut DidRotation {
+        &mut self.did_rotation
+    }
 }
@@ -3,13 +3,18 @@ use std::clone::Clone;
 use diddoc_legacy::aries::diddoc::AriesDidDoc;
 use messages::msg_fields::protocols::discover_features::{disclose::Disclose, ProtocolDescriptor};
 
-use crate::protocols::connection::trait_bounds::{CompletedState, TheirDidDoc, ThreadId};
+use crate::protocols::{
+    connection::trait_bounds::{CompletedState, TheirDidDoc, ThreadId},
+    did_rotate::DidRotation,
+};
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct Completed {
     pub(crate) did_doc: AriesDidDoc,
     pub(crate) thread_id: String,
     pub(crate) protocols: Option<Vec<ProtocolDescriptor>>,
+    #[serde(default, skip_serializing_if = "DidRotation::is_initial")]
+    pub(crate) did_rotation: DidRotation,
 }
 
 impl Completed {
@@ -22,6 +27,7 @@ impl Completed {
             did_doc,
             thread_id,
             protocols,
+            did_rotation: DidRotation::default(),
         }
     }
 }
@@ -30,6 +36,10 @@ impl TheirDidDoc for Completed {
     fn their_did_doc(&self) -> &AriesDidDoc {
         &self.did_doc
     }
+
+    fn did_rotation(&self) -> Option<&DidRotation> {
+        Some(&self.did_rotation)
+    }
 }
 
 impl ThreadId for Completed {
@@ -46,4 +56,8 @@ impl CompletedState for Completed {
     fn handle_disclose(&mut self, disclose: Disclose) {
         self.protocols = Some(disclose.content.protocols)
     }
+
+    fn did_rotation_mut(&mut self) -> &mut DidRotation {
+        &mut self.did_rotation
+    }
 }
@@ -6,14 +6,19 @@ pub mod pairwise_info;
 mod serializable;
 mod trait_bounds;
 
+use std::sync::Arc;
+
 use aries_vcx_wallet::wallet::base_wallet::BaseWallet;
+use did_resolver_registry::ResolverRegistry;
 use diddoc_legacy::aries::diddoc::AriesDidDoc;
 use messages::{
-    msg_fields::protocols::discover_features::{
-        disclose::Disclose, query::QueryContent, ProtocolDescriptor,
+    msg_fields::protocols::{
+        did_rotate::{hangup::Hangup, rotate::Rotate, DidRotate},
+        discover_features::{disclose::Disclose, query::QueryContent, ProtocolDescriptor},
     },
     AriesMessage,
 };
+use url::Url;
 
 pub use self::generic::{GenericConnection, State, ThinState};
 use self::{
@@ -23,6 +28,7 @@ use self::{
 };
 use crate::{
     errors::error::{AriesVcxError, AriesVcxErrorKind, VcxResult},
+    protocols::did_rotate::DidRotation,
     transport::Transport,
     utils::encryption_envelope::EncryptionEnvelope,
 };
@@ -99,29 +105,22 @@ where
         wallet: &impl BaseWallet,
         message: &AriesMessage,
     ) -> VcxResult<EncryptionEnvelope> {
-        let sender_verkey = &self.pairwise_info().pw_vk;
-        EncryptionEnvelope::create_from_legacy(
+        encrypt_message(
             wallet,
-            json!(message).to_string().as_bytes(),
-            Some(sender_verkey),
+            message,
+            &self.pairwise_info.pw_vk,
             self.their_did_doc(),
+            self.state.did_rotation(),
         )
         .await
     }
 
     pub fn remote_did(&self) -> &str {
-        &self.their_did_doc().id
+        remote_did(self.their_did_doc(), self.state.did_rotation())
     }
 
     pub fn remote_vk(&self) -> VcxResult<String> {
-        self.their_did_doc()
-            .recipient_keys()?
-            .first()
-            .map(ToOwned::to_owned)
-            .ok_or(AriesVcxError::from_msg(
-                AriesVcxErrorKind::NotReady,
-                "Can't resolve recipient key from the counterparty diddoc.",
-            ))
+        remote_vk(self.their_did_doc(), self.state.did_rotation())
     }
 
     pub async fn send_message<T>(
@@ -134,16 +133,14 @@ where
         T: Transport,
     {
         let msg = self.encrypt_message(wallet, message).await?.0;
-        let service_endpoint = self.their_did_doc().get_endpoint().ok_or_else(|| {
-            AriesVcxError::from_msg(AriesVcxErrorKind::InvalidUrl, "No URL in DID Doc")
-        })?;
+        let service_endpoint = service_endpoint(self.their_did_doc(), self.state.did_rotation())?;
         transport.send_message(msg, &service_endpoint).await
     }
 }
 
 impl<I, S> Connection<I, S>
 where
-    S: CompletedState,
+    S: CompletedState + ThreadId,
 {
     pub fn remote_protocols(&self) -> Option<&[ProtocolDescriptor]> {
         self.state.remote_protocols()
@@ -152,4 +149,110 @@ where
     pub fn handle_disclose(&mut self, disclose: Disclose) {
         self.state.handle_disclose(disclose)
     }
+
+    /// Starts rotating our side of the connection to a new did:peer:4. The returned message must
+    /// be sent before the rotation is acknowledged, while the current DID is still in use.
+    pub async fn rotate_did(
+        &mut self,
+        wallet: &impl BaseWallet,
+        service_endpoint: Url,
+        routing_keys: Vec<String>,
+    ) -> VcxResult<Rotate> {
+        self.state
+            .did_rotation_mut()
+            .rotate(wallet, service_endpoint, routing_keys)
+            .await
+    }
+
+    /// Processes a [DID Rotate](DidRotate) message, returning the answer to send, if any. Answers
+    /// are meant to be sent once the connection has been updated, so that an acknowledged rotation
+    /// is answered at the DID rotated to.
+    pub async fn handle_did_rotate(
+        &mut self,
+        resolver_registry: &Arc<ResolverRegistry>,
+        message: DidRotate,
+    ) -> VcxResult<Option<AriesMessage>> {
+        let thread_id = self.state.thread_id().to_owned();
+        self.state
+            .did_rotation_mut()
+            .handle_message(resolver_registry, message, &thread_id)
+            .await
+    }
+
+    /// Ends the relationship, returning the hangup message to notify the counterparty with
+    pub fn hangup(&mut self) -> VcxResult<Hangup> {
+        self.state.did_rotation_mut().hangup()
+    }
+}
+
+/// Packs a message to the counterparty, following the DID rotations of either party if any.
+/// Shared with the mediated connection state machines.
+pub(crate) async fn encrypt_message(
+    wallet: &impl BaseWallet,
+    message: &AriesMessage,
+    sender_verkey: &str,
+    their_did_doc: &AriesDidDoc,
+    did_rotation: Option<&DidRotation>,
+) -> VcxResult<EncryptionEnvelope> {
+    let data = json!(message).to_string();
+    match did_rotation {
+        Some(did_rotation) => {
+            did_rotation.check_can_send(message)?;
+            did_rotation
+                .encrypt_message(wallet, data.as_bytes(), sender_verkey, their_did_doc)
+                .await
+        }
+        None => {
+            EncryptionEnvelope::create_from_legacy(
+                wallet,
+                data.as_bytes(),
+                Some(sender_verkey),
+                their_did_doc,
+            )
+            .await
+        }
+    }
+}
+
+pub(crate) fn service_endpoint(
+    their_did_doc: &AriesDidDoc,
+    did_rotation: Option<&DidRotation>,
+) -> VcxResult<Url> {
+    match did_rotation {
+        Some(did_rotation) => did_rotation.service_endpoint(their_did_doc),
+        None => their_did_doc.get_endpoint().ok_or_else(|| {
+            AriesVcxError::from_msg(AriesVcxErrorKind::InvalidUrl, "No URL in DID Doc")
+        }),
+    }
+}
+
+pub(crate) fn remote_did<'a>(
+    their_did_doc: &'a AriesDidDoc,
+    did_rotation: Option<&'a DidRotation>,
+) -> &'a str {
+    match did_rotation.and_then(DidRotation::their_did_doc) {
+        Some(did_doc) => did_doc.id().did(),
+        None => &their_did_doc.id,
+    }
+}
+
+pub(crate) fn remote_vk(
+    their_did_doc: &AriesDidDoc,
+    did_rotation: Option<&DidRotation>,
+) -> VcxResult<String> {
+    if let Some(key) = did_rotation
+        .map(DidRotation::their_verkey)
+        .transpose()?
+        .flatten()
+    {
+        return Ok(key.base58());
+    }
+    their_did_doc
+        .recipient_keys()?
+        .first()
+        .map(ToOwned::to_owned)
+        .ok_or(AriesVcxError::from_msg(
+            AriesVcxErrorKind::NotReady,
+            "Can't resolve recipient key from the counterparty diddoc.",
+        ))
 }
@@ -1,10 +1,18 @@
 use diddoc_legacy::aries::diddoc::AriesDidDoc;
 use messages::msg_fields::protocols::discover_features::{disclose::Disclose, ProtocolDescriptor};
 
+use crate::protocols::did_rotate::DidRotation;
+
 /// Trait implemented for [`super::Connection`] states that store an [`AriesDidDoc`].
 pub trait TheirDidDoc {
     /// Returns the [`AriesDidDoc`] currently being used by a [`super::Connection`].
     fn their_did_doc(&self) -> &AriesDidDoc;
+
+    /// Returns the [`DidRotation`] of the connection, which may have replaced the
+    /// [`AriesDidDoc`] of either party. Only completed connections can rotate.
+    fn did_rotation(&self) -> Option<&DidRotation> {
+        None
+    }
 }
 
 pub trait BootstrapDidDoc: TheirDidDoc {
@@ -26,6 +34,8 @@ pub trait CompletedState {
     fn remote_protocols(&self) -> Option<&[ProtocolDescriptor]>;
 
     fn handle_disclose(&mut self, disclose: Disclose);
+
+    fn did_rotation_mut(&mut self) -> &mut DidRotation;
 }
 
 /// Marker trait used for implementing
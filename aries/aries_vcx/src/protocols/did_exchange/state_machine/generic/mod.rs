@@ -32,20 +32,20 @@ use crate::{
 mod conversions;
 mod thin_state;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum GenericDidExchange {
     Requester(RequesterState),
     Responder(ResponderState),
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum RequesterState {
     RequestSent(DidExchangeRequester<RequestSent>),
     Completed(DidExchangeRequester<Completed>),
     Abandoned(DidExchangeRequester<Abandoned>),
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum ResponderState {
     ResponseSent(DidExchangeResponder<ResponseSent>),
     Completed(DidExchangeResponder<Completed>),
@@ -0,0 +1,539 @@
+//! [DID Rotate 1.0](https://github.com/hyperledger/aries-rfcs/blob/main/features/0794-did-rotate/README.md)
+//! for connections established with the connection protocol. Either party can rotate from its
+//! pairwise DID and legacy DID document to a did:peer:4 resolving to a [`DidDocument`], without
+//! re-establishing the connection.
+//!
+//! The [`DidRotation`] of a connection is kept in its completed state, so it is persisted along
+//! with the connection. This holds for both the connection and the mediated connection state
+//! machines. Once hung up, a connection only sends further hangups.
+
+use std::{collections::HashMap, sync::Arc};
+
+use aries_vcx_wallet::wallet::base_wallet::BaseWallet;
+use chrono::Utc;
+use did_doc::schema::{did_doc::DidDocument, service::typed::ServiceType};
+use did_parser_nom::Did;
+use did_resolver_registry::ResolverRegistry;
+use diddoc_legacy::aries::diddoc::AriesDidDoc;
+use messages::{
+    decorators::{thread::Thread, timing::Timing},
+    msg_fields::protocols::{
+        did_rotate::{
+            ack::DidRotateAck,
+            hangup::{Hangup, HangupContent, HangupDecorators},
+            problem_report::DidRotateProblemReport,
+            rotate::{Rotate, RotateContent, RotateDecorators},
+            DidRotate,
+        },
+        notification::ack::{AckContent, AckDecorators, AckStatus},
+        report_problem::{Description, ProblemReportContent, ProblemReportDecorators},
+    },
+    AriesMessage,
+};
+use public_key::{Key, KeyType};
+use url::Url;
+use uuid::Uuid;
+
+use crate::{
+    errors::error::{AriesVcxError, AriesVcxErrorKind, VcxResult},
+    protocols::did_exchange::state_machine::helpers::create_peer_did_4,
+    utils::{
+        didcomm_utils::{
+            get_ed25519_recipient_keys, get_ed25519_routing_keys, resolve_ed25519_key_agreement,
+        },
+        encryption_envelope::EncryptionEnvelope,
+    },
+};
+
+/// Problem code sent back when the DID rotated to cannot be resolved
+pub const PROBLEM_CODE_UNRESOLVABLE: &str = "e.did.unresolvable";
+/// Problem code sent back when the DID document rotated to offers no DIDComm v1 service
+pub const PROBLEM_CODE_DOC_UNSUPPORTED: &str = "e.did.doc-unsupported";
+
+/// Where and for which keys messages to a [`DidDocument`] are packed
+#[derive(Clone, Debug)]
+struct Destination {
+    service_endpoint: Url,
+    recipient_key: Key,
+    routing_keys: Vec<Key>,
+}
+
+impl Destination {
+    fn from_did_doc(did_doc: &DidDocument) -> VcxResult<Self> {
+        let service = did_doc.get_service_of_type(&ServiceType::DIDCommV1)?;
+        let recipient_key = match get_ed25519_recipient_keys(did_doc, service.id())?
+            .into_iter()
+            .next()
+        {
+            Some(key) => key,
+            None => resolve_ed25519_key_agreement(did_doc)?,
+        };
+        Ok(Self {
+            service_endpoint: service.service_endpoint().clone(),
+            recipient_key,
+            routing_keys: get_ed25519_routing_keys(did_doc, service.id())?,
+        })
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+struct PendingRotation {
+    thread_id: String,
+    did_doc: DidDocument,
+}
+
+/// DID rotation state of a connection. Until a party rotates, the connection keeps using the
+/// pairwise DID and DID document it was established with.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct DidRotation {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    our_did_doc: Option<DidDocument>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pending: Option<PendingRotation>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    their_did_doc: Option<DidDocument>,
+    #[serde(default)]
+    hung_up: bool,
+}
+
+impl DidRotation {
+    /// Document of the DID we rotated to, once the counterparty acknowledged it
+    pub fn our_did_doc(&self) -> Option<&DidDocument> {
+        self.our_did_doc.as_ref()
+    }
+
+    /// Document of the DID the counterparty rotated to
+    pub fn their_did_doc(&self) -> Option<&DidDocument> {
+        self.their_did_doc.as_ref()
+    }
+
+    /// Thread of our rotation awaiting an acknowledgement, if any
+    pub fn pending_thread_id(&self) -> Option<&str> {
+        self.pending
+            .as_ref()
+            .map(|pending| pending.thread_id.as_str())
+    }
+
+    pub fn is_hung_up(&self) -> bool {
+        self.hung_up
+    }
+
+    pub(crate) fn is_initial(&self) -> bool {
+        self == &Self::default()
+    }
+
+    pub(crate) fn our_verkey(&self) -> VcxResult<Option<Key>> {
+        self.our_did_doc
+            .as_ref()
+            .map(resolve_ed25519_key_agreement)
+            .transpose()
+    }
+
+    pub(crate) fn their_verkey(&self) -> VcxResult<Option<Key>> {
+        self.their_did_doc
+            .as_ref()
+            .map(resolve_ed25519_key_agreement)
+            .transpose()
+    }
+
+    fn their_destination(&self) -> VcxResult<Option<Destination>> {
+        self.their_did_doc
+            .as_ref()
+            .map(Destination::from_did_doc)
+            .transpose()
+    }
+
+    /// Packs a message from our current key to theirs, falling back to the pairwise verkey and
+    /// legacy DID document the connection was established with for parties which did not rotate
+    pub(crate) async fn encrypt_message(
+        &self,
+        wallet: &impl BaseWallet,
+        data: &[u8],
+        pw_vk: &str,
+        their_legacy_did_doc: &AriesDidDoc,
+    ) -> VcxResult<EncryptionEnvelope> {
+        let sender_vk = match self.our_verkey()? {
+            Some(key) => key,
+            None => Key::from_base58(pw_vk, KeyType::Ed25519)?,
+        };
+        match self.their_destination()? {
+            Some(destination) => {
+                EncryptionEnvelope::create_from_keys(
+                    wallet,
+                    data,
+                    Some(sender_vk),
+                    destination.recipient_key,
+                    destination.routing_keys,
+                )
+                .await
+            }
+            None => {
+                EncryptionEnvelope::create_from_legacy(
+                    wallet,
+                    data,
+                    Some(&sender_vk.base58()),
+                    their_legacy_did_doc,
+                )
+                .await
+            }
+        }
+    }
+
+    pub(crate) fn service_endpoint(&self, their_legacy_did_doc: &AriesDidDoc) -> VcxResult<Url> {
+        match self.their_destination()? {
+            Some(destination) => Ok(destination.service_endpoint),
+            None => their_legacy_did_doc.get_endpoint().ok_or_else(|| {
+                AriesVcxError::from_msg(AriesVcxErrorKind::InvalidUrl, "No URL in DID Doc")
+            }),
+        }
+    }
+
+    fn check_not_hung_up(&self) -> VcxResult<()> {
+        if self.hung_up {
+            return Err(AriesVcxError::from_msg(
+                AriesVcxErrorKind::NotReady,
+                "The connection has been hung up",
+            ));
+        }
+        Ok(())
+    }
+
+    /// Refuses to send anything but a hangup over a hung up connection
+    pub(crate) fn check_can_send(&self, message: &AriesMessage) -> VcxResult<()> {
+        match message {
+            AriesMessage::DidRotate(DidRotate::Hangup(_)) => Ok(()),
+            _ => self.check_not_hung_up(),
+        }
+    }
+
+    /// Creates a did:peer:4 to rotate to, along with the rotate message announcing it. The
+    /// message must still be sent from the current DID, which stays in use until the rotation is
+    /// acknowledged.
+    pub async fn rotate(
+        &mut self,
+        wallet: &impl BaseWallet,
+        service_endpoint: Url,
+        routing_keys: Vec<String>,
+    ) -> VcxResult<Rotate> {
+        self.check_not_hung_up()?;
+        let (peer_did, _) = create_peer_did_4(wallet, service_endpoint, routing_keys).await?;
+        let did_doc = peer_did.resolve_did_doc()?;
+
+        let content = RotateContent::builder()
+            .to_did(peer_did.did().to_string())
+            .build();
+        let decorators = RotateDecorators::builder()
+            .timing(Timing::builder().out_time(Utc::now()).build())
+            .build();
+        let rotate = Rotate::builder()
+            .id(Uuid::new_v4().to_string())
+            .content(content)
+            .decorators(decorators)
+            .build();
+
+        self.pending = Some(PendingRotation {
+            thread_id: rotate.id.clone(),
+            did_doc,
+        });
+        Ok(rotate)
+    }
+
+    /// Switches to the DID the counterparty rotated to. Returns the answer to send: an ack, which
+    /// goes to the new DID, or a problem report if the new DID cannot be used, in which case the
+    /// current one stays in use.
+    pub async fn handle_rotate(
+        &mut self,
+        resolver_registry: &Arc<ResolverRegistry>,
+        rotate: Rotate,
+    ) -> VcxResult<AriesMessage> {
+        self.check_not_hung_up()?;
+        let thread_id = rotate
+            .decorators
+            .thread
+            .map_or(rotate.id, |thread| thread.thid);
+        let thread = Thread::builder().thid(thread_id).build();
+        let to_did = rotate.content.to_did;
+
+        match resolve_rotated_did(resolver_registry, &to_did).await {
+            Ok(did_doc) => {
+                self.their_did_doc = Some(did_doc);
+                let decorators = AckDecorators::builder()
+                    .thread(thread)
+                    .timing(Timing::builder().out_time(Utc::now()).build())
+                    .build();
+                let ack = DidRotateAck::builder()
+                    .id(Uuid::new_v4().to_string())
+                    .content(AckContent::builder().status(AckStatus::Ok).build())
+                    .decorators(decorators)
+                    .build();
+                Ok(ack.into())
+            }
+            Err(code) => {
+                warn!("Cannot rotate to DID {to_did}: {code}");
+                let content = ProblemReportContent::builder()
+                    .description(Description::builder().code(code.to_owned()).build())
+                    .problem_items(vec![HashMap::from([("did".to_owned(), to_did)])])
+                    .build();
+                let decorators = ProblemReportDecorators::builder()
+                    .thread(thread)
+                    .timing(Timing::builder().out_time(Utc::now()).build())
+                    .build();
+                let problem_report = DidRotateProblemReport::builder()
+                    .id(Uuid::new_v4().to_string())
+                    .content(content)
+                    .decorators(decorators)
+                    .build();
+                Ok(problem_report.into())
+            }
+        }
+    }
+
+    /// Completes our rotation, the DID rotated to is used from now on
+    pub fn handle_ack(&mut self, ack: &DidRotateAck) -> VcxResult<()> {
+        match self.pending.take() {
+            Some(pending) if pending.thread_id == ack.decorators.thread.thid => {
+                self.our_did_doc = Some(pending.did_doc);
+                Ok(())
+            }
+            pending => {
+                self.pending = pending;
+                Err(AriesVcxError::from_msg(
+                    AriesVcxErrorKind::InvalidState,
+                    format!(
+                        "No rotation pending on thread {}",
+                        ack.decorators.thread.thid
+                    ),
+                ))
+            }
+        }
+    }
+
+    /// Abandons our rotation rejected by the counterparty, the current DID stays in use
+    pub fn handle_problem_report(
+        &mut self,
+        problem_report: &DidRotateProblemReport,
+    ) -> VcxResult<()> {
+        let thread_id = problem_report
+            .decorators
+            .thread
+            .as_ref()
+            .map(|thread| thread.thid.as_str());
+        if thread_id.is_none() || self.pending_thread_id() != thread_id {
+            return Err(AriesVcxError::from_msg(
+                AriesVcxErrorKind::InvalidState,
+                format!("No rotation pending on thread {thread_id:?}"),
+            ));
+        }
+        warn!(
+            "DID rotation rejected by the counterparty: {}",
+            problem_report.content.inner.description.code
+        );
+        self.pending = None;
+        Ok(())
+    }
+
+    /// Processes any [`DidRotate`] message received on the connection with the given thread id,
+    /// returning the answer to send, if any
+    pub async fn handle_message(
+        &mut self,
+        resolver_registry: &Arc<ResolverRegistry>,
+        message: DidRotate,
+        connection_thread_id: &str,
+    ) -> VcxResult<Option<AriesMessage>> {
+        match message {
+            DidRotate::Rotate(rotate) => self
+                .handle_rotate(resolver_registry, rotate)
+                .await
+                .map(Some),
+            DidRotate::Ack(ack) => self.handle_ack(&ack).map(|_| None),
+            DidRotate::ProblemReport(problem_report) => {
+                self.handle_problem_report(&problem_report).map(|_| None)
+            }
+            DidRotate::Hangup(hangup) => self
+                .handle_hangup(&hangup, connection_thread_id)
+                .map(|_| None),
+        }
+    }
+
+    /// Ends the relationship, returning the hangup message to notify the counterparty with
+    pub fn hangup(&mut self) -> VcxResult<Hangup> {
+        self.check_not_hung_up()?;
+        self.hung_up = true;
+        let decorators = HangupDecorators::builder()
+            .timing(Timing::builder().out_time(Utc::now()).build())
+            .build();
+        Ok(Hangup::builder()
+            .id(Uuid::new_v4().to_string())
+            .content(HangupContent::default())
+            .decorators(decorators)
+            .build())
+    }
+
+    /// Ends the relationship at the request of the counterparty. A hangup carrying a thread
+    /// must belong to the connection or to our pending rotation.
+    pub fn handle_hangup(&mut self, hangup: &Hangup, connection_thread_id: &str) -> VcxResult<()> {
+        if let Some(thread) = &hangup.decorators.thread {
+            if thread.thid != connection_thread_id
+                && self.pending_thread_id() != Some(thread.thid.as_str())
+            {
+                return Err(AriesVcxError::from_msg(
+                    AriesVcxErrorKind::InvalidState,
+                    format!("Hangup on unrelated thread {}", thread.thid),
+                ));
+            }
+        }
+        self.pending = None;
+        self.hung_up = true;
+        Ok(())
+    }
+}
+
+/// Resolves the DID rotated to, failing with the problem code to report back
+async fn resolve_rotated_did(
+    resolver_registry: &Arc<ResolverRegistry>,
+    did: &str,
+) -> Result<DidDocument, &'static str> {
+    let did: Did = did.parse().map_err(|_| PROBLEM_CODE_UNRESOLVABLE)?;
+    let did_doc = resolver_registry
+        .resolve(&did, &Default::default())
+        .await
+        .map_err(|_| PROBLEM_CODE_UNRESOLVABLE)?
+        .did_document;
+    Destination::from_did_doc(&did_doc).map_err(|_| PROBLEM_CODE_DOC_UNSUPPORTED)?;
+    Ok(did_doc)
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use super::*;
+
+    fn pending_rotation(thread_id: &str) -> DidRotation {
+        DidRotation {
+            pending: Some(PendingRotation {
+                thread_id: thread_id.to_owned(),
+                did_doc: DidDocument::default(),
+            }),
+            ..Default::default()
+        }
+    }
+
+    fn ack(thread_id: &str) -> DidRotateAck {
+        DidRotateAck::builder()
+            .id("ack".to_owned())
+            .content(AckContent::builder().status(AckStatus::Ok).build())
+            .decorators(
+                AckDecorators::builder()
+                    .thread(Thread::builder().thid(thread_id.to_owned()).build())
+                    .build(),
+            )
+            .build()
+    }
+
+    fn problem_report(thread_id: &str) -> DidRotateProblemReport {
+        DidRotateProblemReport::builder()
+            .id("problem".to_owned())
+            .content(
+                ProblemReportContent::builder()
+                    .description(
+                        Description::builder()
+                            .code(PROBLEM_CODE_UNRESOLVABLE.to_owned())
+                            .build(),
+                    )
+                    .build(),
+            )
+            .decorators(
+                ProblemReportDecorators::builder()
+                    .thread(Thread::builder().thid(thread_id.to_owned()).build())
+                    .build(),
+            )
+            .build()
+    }
+
+    #[test]
+    fn test_ack_completes_rotation() {
+        let mut rotation = pending_rotation("rotate");
+        rotation.handle_ack(&ack("rotate")).unwrap();
+        assert_eq!(rotation.pending_thread_id(), None);
+        assert_eq!(rotation.our_did_doc(), Some(&DidDocument::default()));
+    }
+
+    #[test]
+    fn test_ack_on_other_thread_is_rejected() {
+        let mut rotation = pending_rotation("rotate");
+        assert!(rotation.handle_ack(&ack("other")).is_err());
+        assert_eq!(rotation.pending_thread_id(), Some("rotate"));
+        assert_eq!(rotation.our_did_doc(), None);
+    }
+
+    #[test]
+    fn test_problem_report_abandons_rotation() {
+        let mut rotation = pending_rotation("rotate");
+        rotation
+            .handle_problem_report(&problem_report("rotate"))
+            .unwrap();
+        assert_eq!(rotation, DidRotation::default());
+    }
+
+    #[test]
+    fn test_hung_up_connection_cannot_rotate() {
+        let mut rotation = DidRotation::default();
+        rotation.hangup().unwrap();
+        assert!(rotation.is_hung_up());
+        assert!(rotation.hangup().is_err());
+    }
+
+    fn hangup(thread_id: Option<&str>) -> Hangup {
+        let decorators = match thread_id {
+            Some(thread_id) => HangupDecorators::builder()
+                .thread(Thread::builder().thid(thread_id.to_owned()).build())
+                .build(),
+            None => HangupDecorators::default(),
+        };
+        Hangup::builder()
+            .id("hangup".to_owned())
+            .content(HangupContent::default())
+            .decorators(decorators)
+            .build()
+    }
+
+    #[test]
+    fn test_hangup_on_connection_or_rotation_thread_is_accepted() {
+        for thread_id in [None, Some("connection"), Some("rotate")] {
+            let mut rotation = pending_rotation("rotate");
+            rotation
+                .handle_hangup(&hangup(thread_id), "connection")
+                .unwrap();
+            assert!(rotation.is_hung_up());
+            assert_eq!(rotation.pending_thread_id(), None);
+        }
+    }
+
+    #[test]
+    fn test_hangup_on_other_thread_is_rejected() {
+        let mut rotation = pending_rotation("rotate");
+        assert!(rotation
+            .handle_hangup(&hangup(Some("other")), "connection")
+            .is_err());
+        assert!(!rotation.is_hung_up());
+    }
+
+    #[test]
+    fn test_hung_up_connection_only_sends_hangups() {
+        let mut rotation = DidRotation::default();
+        let message: AriesMessage = ack("rotate").into();
+        rotation.check_can_send(&message).unwrap();
+        let hangup = rotation.hangup().unwrap();
+        assert!(rotation.check_can_send(&message).is_err());
+        rotation.check_can_send(&hangup.into()).unwrap();
+    }
+
+    #[test]
+    fn test_default_rotation_serializes_empty() {
+        let rotation: DidRotation = serde_json::from_str("{}").unwrap();
+        assert_eq!(rotation, DidRotation::default());
+        assert_eq!(
+            serde_json::to_value(&rotation).unwrap(),
+            json!({ "hung_up": false })
+        );
+    }
+}
@@ -56,7 +56,7 @@ pub enum IssuerFullState {
     Finished(FinishedState),
 }
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum IssuerState {
     Initial,
     OfferSet,
@@ -5,8 +5,9 @@ use messages::msg_fields::protocols::{
     connection::response::Response, discover_features::ProtocolDescriptor,
 };
 
-use crate::protocols::mediated_connection::invitee::states::{
-    requested::RequestedState, responded::RespondedState,
+use crate::protocols::{
+    did_rotate::DidRotation,
+    mediated_connection::invitee::states::{requested::RequestedState, responded::RespondedState},
 };
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -14,6 +15,8 @@ pub struct CompletedState {
     pub did_doc: AriesDidDoc,
     pub bootstrap_did_doc: AriesDidDoc,
     pub protocols: Option<Vec<ProtocolDescriptor>>,
+    #[serde(default, skip_serializing_if = "DidRotation::is_initial")]
+    pub did_rotation: DidRotation,
 }
 
 impl From<(CompletedState, Vec<ProtocolDescriptor>)> for CompletedState {
@@ -23,6 +26,7 @@ impl From<(CompletedState, Vec<ProtocolDescriptor>)> for CompletedState {
             bootstrap_did_doc: state.bootstrap_did_doc,
             did_doc: state.did_doc,
             protocols: Some(protocols),
+            did_rotation: state.did_rotation,
         }
     }
 }
@@ -36,6 +40,7 @@ impl From<(RequestedState, AriesDidDoc, Response)> for CompletedState {
             bootstrap_did_doc: state.did_doc,
             did_doc,
             protocols: None,
+            did_rotation: DidRotation::default(),
         }
     }
 }
@@ -47,6 +52,7 @@ impl From<RespondedState> for CompletedState {
             bootstrap_did_doc: state.did_doc,
             did_doc: state.resp_con_data.did_doc,
             protocols: None,
+            did_rotation: DidRotation::default(),
         }
     }
 }
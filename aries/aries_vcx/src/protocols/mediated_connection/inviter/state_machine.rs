@@ -1,7 +1,8 @@
-use std::{clone::Clone, collections::HashMap};
+use std::{clone::Clone, collections::HashMap, sync::Arc};
 
 use aries_vcx_wallet::wallet::base_wallet::BaseWallet;
 use chrono::Utc;
+use did_resolver_registry::ResolverRegistry;
 use diddoc_legacy::aries::diddoc::AriesDidDoc;
 use messages::{
     decorators::{thread::Thread, timing::Timing},
@@ -13,6 +14,7 @@ use messages::{
             response::{Response, ResponseContent, ResponseDecorators},
             Connection, ConnectionData,
         },
+        did_rotate::{hangup::Hangup, rotate::Rotate, DidRotate},
         discover_features::{disclose::Disclose, query::QueryContent, ProtocolDescriptor},
         trust_ping::TrustPing,
     },
@@ -26,6 +28,8 @@ use crate::{
     errors::error::prelude::*,
     handlers::util::{verify_thread_id, AnyInvitation},
     protocols::{
+        connection::{encrypt_message, remote_did, remote_vk, service_endpoint},
+        did_rotate::DidRotation,
         mediated_connection::{
             inviter::states::{
                 completed::CompletedState, initial::InitialState, invited::InvitedState,
@@ -35,6 +39,7 @@ use crate::{
         },
         SendClosureConnection,
     },
+    utils::encryption_envelope::EncryptionEnvelope,
 };
 
 #[derive(Clone, Serialize, Deserialize)]
@@ -174,7 +179,7 @@ impl SmMediatedConnectionInviter {
 
     pub fn remote_did(&self) -> VcxResult<String> {
         self.their_did_doc()
-            .map(|did_doc: AriesDidDoc| did_doc.id)
+            .map(|did_doc: AriesDidDoc| remote_did(&did_doc, self.did_rotation()).to_owned())
             .ok_or(AriesVcxError::from_msg(
                 AriesVcxErrorKind::NotReady,
                 "Remote Connection DID is not set",
@@ -186,14 +191,85 @@ impl SmMediatedConnectionInviter {
             AriesVcxErrorKind::NotReady,
             "Counterparty diddoc is not available.",
         ))?;
-        did_did
-            .recipient_keys()?
-            .first()
-            .ok_or(AriesVcxError::from_msg(
+        remote_vk(&did_did, self.did_rotation())
+    }
+
+    /// DID rotation state of the connection, once completed
+    pub fn did_rotation(&self) -> Option<&DidRotation> {
+        match self.state {
+            MediatedInviterFullState::Completed(ref state) => Some(&state.did_rotation),
+            _ => None,
+        }
+    }
+
+    fn did_rotation_mut(&mut self) -> VcxResult<&mut DidRotation> {
+        match self.state {
+            MediatedInviterFullState::Completed(ref mut state) => Ok(&mut state.did_rotation),
+            _ => Err(AriesVcxError::from_msg(
                 AriesVcxErrorKind::NotReady,
-                "Can't resolve recipient key from the counterparty diddoc.",
-            ))
-            .map(|s| s.to_string())
+                "Only completed connections can rotate DIDs",
+            )),
+        }
+    }
+
+    /// Packs a message to the counterparty, following the DID rotations of either party. Messages
+    /// of a completed connection should be packed with this rather than with the pairwise keys
+    /// given to a [`SendClosureConnection`].
+    pub async fn encrypt_message(
+        &self,
+        wallet: &impl BaseWallet,
+        message: &AriesMessage,
+    ) -> VcxResult<EncryptionEnvelope> {
+        let did_doc = self.their_did_doc().ok_or(AriesVcxError::from_msg(
+            AriesVcxErrorKind::NotReady,
+            "Counterparty diddoc is not available.",
+        ))?;
+        encrypt_message(
+            wallet,
+            message,
+            &self.pairwise_info.pw_vk,
+            &did_doc,
+            self.did_rotation(),
+        )
+        .await
+    }
+
+    /// Endpoint messages to the counterparty are sent to, following its DID rotation if any
+    pub fn service_endpoint(&self) -> VcxResult<Url> {
+        let did_doc = self.their_did_doc().ok_or(AriesVcxError::from_msg(
+            AriesVcxErrorKind::NotReady,
+            "Counterparty diddoc is not available.",
+        ))?;
+        service_endpoint(&did_doc, self.did_rotation())
+    }
+
+    /// See [`crate::protocols::connection::Connection::rotate_did`]
+    pub async fn rotate_did(
+        &mut self,
+        wallet: &impl BaseWallet,
+        service_endpoint: Url,
+        routing_keys: Vec<String>,
+    ) -> VcxResult<Rotate> {
+        self.did_rotation_mut()?
+            .rotate(wallet, service_endpoint, routing_keys)
+            .await
+    }
+
+    /// See [`crate::protocols::connection::Connection::handle_did_rotate`]
+    pub async fn handle_did_rotate(
+        &mut self,
+        resolver_registry: &Arc<ResolverRegistry>,
+        message: DidRotate,
+    ) -> VcxResult<Option<AriesMessage>> {
+        let thread_id = self.get_thread_id();
+        self.did_rotation_mut()?
+            .handle_message(resolver_registry, message, &thread_id)
+            .await
+    }
+
+    /// See [`crate::protocols::connection::Connection::hangup`]
+    pub fn hangup(&mut self) -> VcxResult<Hangup> {
+        self.did_rotation_mut()?.hangup()
     }
 
     pub fn can_progress_state(&self, message: &AriesMessage) -> bool {
@@ -3,11 +3,15 @@ use std::clone::Clone;
 use diddoc_legacy::aries::diddoc::AriesDidDoc;
 use messages::msg_fields::protocols::discover_features::ProtocolDescriptor;
 
+use crate::protocols::did_rotate::DidRotation;
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct CompletedState {
     pub did_doc: AriesDidDoc,
     pub protocols: Option<Vec<ProtocolDescriptor>>,
     pub thread_id: Option<String>,
+    #[serde(default, skip_serializing_if = "DidRotation::is_initial")]
+    pub did_rotation: DidRotation,
 }
 
 impl From<(CompletedState, Vec<ProtocolDescriptor>)> for CompletedState {
@@ -17,6 +21,7 @@ impl From<(CompletedState, Vec<ProtocolDescriptor>)> for CompletedState {
             did_doc: state.did_doc,
             thread_id: state.thread_id,
             protocols: Some(protocols),
+            did_rotation: state.did_rotation,
         }
     }
 }
@@ -3,8 +3,9 @@ use messages::msg_fields::protocols::connection::{
     problem_report::ProblemReport, response::Response,
 };
 
-use crate::protocols::mediated_connection::inviter::states::{
-    completed::CompletedState, initial::InitialState,
+use crate::protocols::{
+    did_rotate::DidRotation,
+    mediated_connection::inviter::states::{completed::CompletedState, initial::InitialState},
 };
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -31,6 +32,7 @@ impl From<RespondedState> for CompletedState {
             did_doc: state.did_doc,
             thread_id: Some(state.signed_response.decorators.thread.thid),
             protocols: None,
+            did_rotation: DidRotation::default(),
         }
     }
 }
@@ -0,0 +1,110 @@
+use messages::{
+    decorators::transport::{ReturnRoute, Transport},
+    msg_fields::protocols::{
+        coordinate_mediation::{
+            keylist_update::KeylistUpdateItem, KeylistQuery, KeylistQueryContent, KeylistUpdate,
+            KeylistUpdateContent, MediateRequest, MediateRequestContent,
+        },
+        pickup::{
+            DeliveryRequest, DeliveryRequestContent, DeliveryRequestDecorators, LiveDeliveryChange,
+            LiveDeliveryChangeContent, LiveDeliveryChangeDecorators, MessagesReceived,
+            MessagesReceivedContent, MessagesReceivedDecorators, StatusRequest,
+            StatusRequestContent, StatusRequestDecorators,
+        },
+    },
+};
+use uuid::Uuid;
+
+// Pickup messages ask the mediator to answer over the same connection, so that recipients
+// without an endpoint of their own (e.g. mobile agents) can receive the responses
+fn return_route_all() -> Transport {
+    Transport::builder().return_route(ReturnRoute::All).build()
+}
+
+pub fn build_mediate_request() -> MediateRequest {
+    MediateRequest::builder()
+        .id(Uuid::new_v4().to_string())
+        .content(MediateRequestContent::default())
+        .build()
+}
+
+pub fn build_keylist_update(updates: Vec<KeylistUpdateItem>) -> KeylistUpdate {
+    KeylistUpdate::builder()
+        .id(Uuid::new_v4().to_string())
+        .content(KeylistUpdateContent::builder().updates(updates).build())
+        .build()
+}
+
+pub fn build_keylist_query() -> KeylistQuery {
+    KeylistQuery::builder()
+        .id(Uuid::new_v4().to_string())
+        .content(KeylistQueryContent::default())
+        .build()
+}
+
+pub fn build_status_request(recipient_key: Option<String>) -> StatusRequest {
+    let content = match recipient_key {
+        None => StatusRequestContent::builder().build(),
+        Some(recipient_key) => StatusRequestContent::builder()
+            .recipient_key(recipient_key)
+            .build(),
+    };
+    StatusRequest::builder()
+        .id(Uuid::new_v4().to_string())
+        .content(content)
+        .decorators(
+            StatusRequestDecorators::builder()
+                .transport(return_route_all())
+                .build(),
+        )
+        .build()
+}
+
+pub fn build_delivery_request(limit: u32, recipient_key: Option<String>) -> DeliveryRequest {
+    let content = DeliveryRequestContent::builder().limit(limit);
+    let content = match recipient_key {
+        None => content.build(),
+        Some(recipient_key) => content.recipient_key(recipient_key).build(),
+    };
+    DeliveryRequest::builder()
+        .id(Uuid::new_v4().to_string())
+        .content(content)
+        .decorators(
+            DeliveryRequestDecorators::builder()
+                .transport(return_route_all())
+                .build(),
+        )
+        .build()
+}
+
+pub fn build_messages_received(message_id_list: Vec<String>) -> MessagesReceived {
+    MessagesReceived::builder()
+        .id(Uuid::new_v4().to_string())
+        .content(
+            MessagesReceivedContent::builder()
+                .message_id_list(message_id_list)
+                .build(),
+        )
+        .decorators(
+            MessagesReceivedDecorators::builder()
+                .transport(return_route_all())
+                .build(),
+        )
+        .build()
+}
+
+pub fn build_live_delivery_change(live_delivery: bool) -> LiveDeliveryChange {
+    LiveDeliveryChange::builder()
+        .id(Uuid::new_v4().to_string())
+        .content(
+            LiveDeliveryChangeContent::builder()
+                .live_delivery(live_delivery)
+                .build(),
+        )
+        .decorators(
+            LiveDeliveryChangeDecorators::builder()
+                .transport(return_route_all())
+                .build(),
+        )
+        .build()
+}
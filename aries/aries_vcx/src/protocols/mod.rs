@@ -7,8 +7,10 @@ use crate::errors::error::VcxResult;
 pub mod common;
 pub mod connection;
 pub mod did_exchange;
+pub mod did_rotate;
 pub mod issuance;
 pub mod mediated_connection;
+pub mod mediation;
 pub mod oob;
 pub mod proof_presentation;
 pub mod revocation_notification;
@@ -48,7 +48,7 @@ pub struct VerifierSM {
     state: VerifierFullState,
 }
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum VerifierState {
     Initial,
     PresentationProposalReceived,
@@ -1,9 +1,16 @@
-use std::error::Error;
+use std::{error::Error, sync::Arc};
 
 use aries_vcx::{
     common::ledger::transactions::write_endpoint_legacy,
-    protocols::{connection::GenericConnection, mediated_connection::pairwise_info::PairwiseInfo},
-    utils::encryption_envelope::EncryptionEnvelope,
+    handlers::util::AnyInvitation,
+    protocols::{
+        connection::{Connection, GenericConnection},
+        did_rotate::DidRotation,
+        mediated_connection::pairwise_info::PairwiseInfo,
+    },
+    utils::{
+        didcomm_utils::resolve_ed25519_key_agreement, encryption_envelope::EncryptionEnvelope,
+    },
 };
 use aries_vcx_anoncreds::anoncreds::base_anoncreds::BaseAnonCreds;
 use aries_vcx_ledger::ledger::base_ledger::{
@@ -11,15 +18,20 @@ use aries_vcx_ledger::ledger::base_ledger::{
 };
 use aries_vcx_wallet::wallet::base_wallet::BaseWallet;
 use chrono::Utc;
+use did_peer::resolver::PeerDidResolver;
+use did_resolver_registry::ResolverRegistry;
 use diddoc_legacy::aries::service::AriesService;
 use messages::{
     decorators::timing::Timing,
-    msg_fields::protocols::basic_message::{
-        BasicMessage, BasicMessageContent, BasicMessageDecorators,
+    msg_fields::protocols::{
+        basic_message::{BasicMessage, BasicMessageContent, BasicMessageDecorators},
+        connection::invitation::{Invitation, InvitationContent},
+        did_rotate::DidRotate,
     },
     AriesMessage,
 };
-use test_utils::devsetup::*;
+use test_utils::{devsetup::*, mockdata::mock_ledger::MockLedger};
+use url::Url;
 use utils::test_agent::TestAgent;
 use uuid::Uuid;
 
@@ -202,3 +214,118 @@ async fn test_agency_pool_establish_connection_via_out_of_band() -> Result<(), B
     }
     Ok(())
 }
+
+// Connects two wallets over a pairwise invitation, without touching the ledger
+async fn establish_pairwise_connection(
+    alice_wallet: &impl BaseWallet,
+    faber_wallet: &impl BaseWallet,
+) -> (GenericConnection, GenericConnection) {
+    let inviter_pairwise_info = PairwiseInfo::create(faber_wallet).await.unwrap();
+    let content = InvitationContent::builder_pairwise()
+        .label("".to_string())
+        .recipient_keys(vec![inviter_pairwise_info.pw_vk.clone()])
+        .service_endpoint("http://dummy.org".parse().unwrap())
+        .build();
+    let invitation = AnyInvitation::Con(
+        Invitation::builder()
+            .id(Uuid::new_v4().to_string())
+            .content(content)
+            .build(),
+    );
+
+    let invitee_pairwise_info = PairwiseInfo::create(alice_wallet).await.unwrap();
+    let invitee = Connection::new_invitee("".to_owned(), invitee_pairwise_info)
+        .accept_invitation(&MockLedger, invitation.clone())
+        .await
+        .unwrap()
+        .prepare_request("http://dummy.org".parse().unwrap(), vec![])
+        .await
+        .unwrap();
+    let request = invitee.get_request().clone();
+
+    let inviter = Connection::new_inviter("".to_owned(), inviter_pairwise_info)
+        .into_invited(invitation.id())
+        .handle_request(
+            faber_wallet,
+            request,
+            "http://dummy.org".parse().unwrap(),
+            vec![],
+        )
+        .await
+        .unwrap();
+    let response = inviter.get_connection_response_msg();
+
+    let invitee = invitee
+        .handle_response(alice_wallet, response)
+        .await
+        .unwrap();
+    let inviter = inviter
+        .acknowledge_connection(&invitee.get_ack().into())
+        .unwrap();
+
+    (invitee.into(), inviter.into())
+}
+
+#[tokio::test]
+#[ignore]
+async fn test_did_rotation_switches_keys_and_endpoint() -> Result<(), Box<dyn Error>> {
+    let (_, alice_wallet) = dev_build_featured_wallet("000000000000000000000000000Alice").await;
+    let (_, faber_wallet) = dev_build_featured_wallet("000000000000000000000000000Faber").await;
+    let (mut alice, mut faber) = establish_pairwise_connection(&alice_wallet, &faber_wallet).await;
+    let resolver_registry = Arc::new(
+        ResolverRegistry::new()
+            .register_resolver::<PeerDidResolver>("peer".into(), PeerDidResolver::new()),
+    );
+    let alice_vk = alice.pairwise_info().pw_vk.clone();
+    let old_faber_vk = faber.pairwise_info().pw_vk.clone();
+    assert_eq!(alice.remote_vk()?, old_faber_vk);
+
+    let new_service_endpoint: Url = "http://rotated.org/".parse()?;
+    let rotate = faber
+        .rotate_did(&faber_wallet, new_service_endpoint.clone(), vec![])
+        .await?;
+
+    // The rotate message itself still comes from the current DID
+    let envelope = faber
+        .encrypt_message(&faber_wallet, &rotate.clone().into())
+        .await?;
+    let unpacked = alice_wallet.unpack_message(&envelope.0).await?;
+    assert_eq!(unpacked.sender_verkey, Some(old_faber_vk.clone()));
+
+    let answer = alice
+        .handle_did_rotate(&resolver_registry, DidRotate::Rotate(rotate))
+        .await?;
+    let Some(AriesMessage::DidRotate(DidRotate::Ack(ack))) = answer else {
+        panic!("Expected a DID rotate ack, got {answer:?}");
+    };
+    let new_faber_did_doc = alice
+        .did_rotation()
+        .and_then(DidRotation::their_did_doc)
+        .cloned()
+        .unwrap();
+    let new_faber_vk = resolve_ed25519_key_agreement(&new_faber_did_doc)?.base58();
+    assert_ne!(new_faber_vk, old_faber_vk);
+    assert_eq!(alice.remote_did(), Some(new_faber_did_doc.id().did()));
+    assert_eq!(alice.remote_vk()?, new_faber_vk);
+    assert_eq!(alice.service_endpoint()?, new_service_endpoint);
+
+    // The ack already goes to the DID rotated to
+    let envelope = alice
+        .encrypt_message(&alice_wallet, &ack.clone().into())
+        .await?;
+    let unpacked = faber_wallet.unpack_message(&envelope.0).await?;
+    assert_eq!(unpacked.recipient_verkey, new_faber_vk);
+    assert_eq!(unpacked.sender_verkey, Some(alice_vk.clone()));
+
+    let answer = faber
+        .handle_did_rotate(&resolver_registry, DidRotate::Ack(ack))
+        .await?;
+    assert!(answer.is_none());
+
+    let message = build_basic_message("Hello from the rotated DID".to_owned()).into();
+    let envelope = faber.encrypt_message(&faber_wallet, &message).await?;
+    let unpacked = alice_wallet.unpack_message(&envelope.0).await?;
+    assert_eq!(unpacked.sender_verkey, Some(new_faber_vk));
+    assert_eq!(unpacked.recipient_verkey, alice_vk);
+    Ok(())
+}
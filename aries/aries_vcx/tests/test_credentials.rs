@@ -1,6 +1,9 @@
 use std::error::Error;
 
-use aries_vcx::common::credentials::{get_cred_rev_id, is_cred_revoked};
+use aries_vcx::common::credentials::{
+    get_cred_rev_id, is_cred_revoked,
+    store::{CredentialFilter, CredentialStore},
+};
 use aries_vcx_anoncreds::anoncreds::base_anoncreds::BaseAnonCreds;
 use aries_vcx_ledger::ledger::base_ledger::AnoncredsLedgerRead;
 use test_utils::{constants::DEFAULT_SCHEMA_ATTRS, devsetup::build_setup_profile};
@@ -141,3 +144,59 @@ async fn test_pool_is_cred_revoked() -> Result<(), Box<dyn Error>> {
     assert!(is_cred_revoked(&setup.ledger_read, &rev_reg.rev_reg_id, cred_rev_id).await?);
     Ok(())
 }
+
+#[tokio::test]
+#[ignore]
+async fn test_pool_credential_store_label_search_delete() -> Result<(), Box<dyn Error>> {
+    let setup = build_setup_profile().await;
+    let schema = create_and_write_test_schema(
+        &setup.wallet,
+        &setup.anoncreds,
+        &setup.ledger_write,
+        &setup.institution_did,
+        DEFAULT_SCHEMA_ATTRS,
+    )
+    .await;
+    let cred_def = create_and_write_test_cred_def(
+        &setup.wallet,
+        &setup.anoncreds,
+        &setup.ledger_read,
+        &setup.ledger_write,
+        &setup.institution_did,
+        &schema.schema_id,
+        false,
+    )
+    .await;
+    let cred_id = create_and_write_credential(
+        &setup.wallet,
+        &setup.wallet,
+        &setup.anoncreds,
+        &setup.anoncreds,
+        &setup.institution_did,
+        &schema,
+        &cred_def,
+        None,
+    )
+    .await;
+    let store = CredentialStore::new(&setup.wallet, &setup.anoncreds);
+    let label = uuid::Uuid::new_v4().to_string();
+    let by_label = CredentialFilter {
+        label: Some(label.clone()),
+        ..Default::default()
+    };
+
+    assert!(store.search(&by_label).await?.is_empty());
+
+    store.set_label(&cred_id, Some(&label)).await?;
+    let found = store.search(&by_label).await?;
+    assert_eq!(found.len(), 1);
+    assert_eq!(found[0].info.referent, cred_id);
+    assert_eq!(found[0].info.schema_id, schema.schema_id);
+    assert_eq!(found[0].label.as_deref(), Some(label.as_str()));
+    assert_eq!(found[0], store.get(&cred_id).await?);
+
+    store.delete(&cred_id).await?;
+    assert!(store.search(&by_label).await?.is_empty());
+    assert!(store.get(&cred_id).await.is_err());
+    Ok(())
+}
@@ -76,8 +76,9 @@ use time::OffsetDateTime;
 use uuid::Uuid;
 
 use super::base_anoncreds::{
-    BaseAnonCreds, CredentialDefinitionsMap, CredentialId, LinkSecretId, RevocationRegistriesMap,
-    RevocationRegistryDefinitionsMap, RevocationStatesMap, SchemasMap,
+    BaseAnonCreds, CredentialDefinitionsMap, CredentialId, CredentialMetadata, LinkSecretId,
+    RevocationRegistriesMap, RevocationRegistryDefinitionsMap, RevocationStatesMap, SchemasMap,
+    CREDENTIAL_METADATA_WQL_PREFIX,
 };
 use crate::{
     anoncreds::anoncreds::type_conversion::Convert,
@@ -830,6 +831,59 @@ impl BaseAnonCreds for Anoncreds {
             .collect()
     }
 
+    async fn prover_get_credentials_with_metadata(
+        &self,
+        wallet: &impl BaseWallet,
+        filter_json: Option<&str>,
+    ) -> VcxAnoncredsResult<Vec<(RetrievedCredentialInfo, CredentialMetadata)>> {
+        let creds_wql = filter_json.map_or("{}", |x| x);
+        wallet
+            .search_record(RecordCategory::Cred, Some(creds_wql.into()))
+            .await?
+            .into_iter()
+            .map(|record| {
+                let cred: Credential = serde_json::from_str(record.value())?;
+                Ok((
+                    _make_cred_info(record.name(), &cred)?,
+                    _make_cred_metadata(record.tags()),
+                ))
+            })
+            .collect()
+    }
+
+    async fn prover_get_credential_metadata(
+        &self,
+        wallet: &impl BaseWallet,
+        cred_id: &CredentialId,
+    ) -> VcxAnoncredsResult<CredentialMetadata> {
+        let record = wallet.get_record(RecordCategory::Cred, cred_id).await?;
+        Ok(_make_cred_metadata(record.tags()))
+    }
+
+    async fn prover_set_credential_metadata(
+        &self,
+        wallet: &impl BaseWallet,
+        cred_id: &CredentialId,
+        name: &str,
+        value: Option<&str>,
+    ) -> VcxAnoncredsResult<()> {
+        // metadata is kept in tags of the credential record, next to the tags the credential is
+        // stored with, so that it can be searched by alongside them
+        let tag_name = format!("{CREDENTIAL_METADATA_WQL_PREFIX}{name}");
+        let mut tags = wallet
+            .get_record(RecordCategory::Cred, cred_id)
+            .await?
+            .tags()
+            .clone();
+        tags.remove(RecordTag::new(&tag_name, ""));
+        if let Some(value) = value {
+            tags.add(RecordTag::new(&tag_name, value));
+        }
+        Ok(wallet
+            .update_record_tags(RecordCategory::Cred, cred_id, tags)
+            .await?)
+    }
+
     async fn prover_get_credentials_for_proof_req(
         &self,
         wallet: &impl BaseWallet,
@@ -1289,6 +1343,17 @@ fn _make_cred_info(
     })
 }
 
+fn _make_cred_metadata(tags: &RecordTags) -> CredentialMetadata {
+    tags.clone()
+        .into_iter()
+        .filter_map(|tag| {
+            tag.key()
+                .strip_prefix(CREDENTIAL_METADATA_WQL_PREFIX)
+                .map(|name| (name.to_string(), tag.value().to_string()))
+        })
+        .collect()
+}
+
 fn _format_attribute_as_value_tag_name(attribute_name: &str) -> String {
     format!("attr::{attribute_name}::value")
 }
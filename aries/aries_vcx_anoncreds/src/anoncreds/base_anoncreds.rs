@@ -32,10 +32,14 @@ use did_parser_nom::Did;
 use crate::errors::error::VcxAnoncredsResult;
 
 pub type CredentialId = String;
+/// Metadata kept by the holder along with a credential, by name
+pub type CredentialMetadata = HashMap<String, String>;
 pub type LinkSecretId = String;
 pub type SchemasMap = HashMap<SchemaId, Schema>;
 pub type CredentialDefinitionsMap = HashMap<CredentialDefinitionId, CredentialDefinition>;
 pub type RevocationStatesMap = HashMap<String, HashMap<u64, CredentialRevocationState>>;
+/// Prefix under which credential metadata can be filtered by in WQL, e.g. `{"meta::label": "id"}`
+pub const CREDENTIAL_METADATA_WQL_PREFIX: &str = "meta::";
 pub type RevocationRegistryDefinitionsMap =
     HashMap<RevocationRegistryDefinitionId, RevocationRegistryDefinition>;
 pub type RevocationRegistriesMap =
@@ -120,6 +124,29 @@ pub trait BaseAnonCreds: std::fmt::Debug + Send + Sync {
         filter_json: Option<&str>,
     ) -> VcxAnoncredsResult<Vec<RetrievedCredentialInfo>>;
 
+    /// Credentials matching `filter_json` like [`BaseAnonCreds::prover_get_credentials`], along
+    /// with their metadata, which can be filtered by under [`CREDENTIAL_METADATA_WQL_PREFIX`]
+    async fn prover_get_credentials_with_metadata(
+        &self,
+        wallet: &impl BaseWallet,
+        filter_json: Option<&str>,
+    ) -> VcxAnoncredsResult<Vec<(RetrievedCredentialInfo, CredentialMetadata)>>;
+
+    async fn prover_get_credential_metadata(
+        &self,
+        wallet: &impl BaseWallet,
+        cred_id: &CredentialId,
+    ) -> VcxAnoncredsResult<CredentialMetadata>;
+
+    /// Sets metadata `name` of the credential to `value`, or removes it if `None`
+    async fn prover_set_credential_metadata(
+        &self,
+        wallet: &impl BaseWallet,
+        cred_id: &CredentialId,
+        name: &str,
+        value: Option<&str>,
+    ) -> VcxAnoncredsResult<()>;
+
     async fn prover_get_credentials_for_proof_req(
         &self,
         wallet: &impl BaseWallet,
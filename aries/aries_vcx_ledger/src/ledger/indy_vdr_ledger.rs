@@ -20,7 +20,7 @@ use anoncreds_types::{
 };
 use aries_vcx_wallet::wallet::base_wallet::BaseWallet;
 use async_trait::async_trait;
-use did_parser_nom::Did;
+use did_parser_nom::{Did, DidUrl};
 pub use indy_ledger_response_parser::GetTxnAuthorAgreementData;
 use indy_ledger_response_parser::{
     ResponseParser, RevocationRegistryDeltaInfo, RevocationRegistryInfo,
@@ -75,7 +75,7 @@ use crate::{
     ledger::{
         base_ledger::{TaaConfigurator, TxnAuthrAgrmtOptions},
         common::verify_transaction_can_be_endorsed,
-        type_conversion::Convert,
+        type_conversion::{to_legacy_object_id, Convert},
     },
 };
 
@@ -113,6 +113,7 @@ where
     response_parser: ResponseParser,
     response_cacher: V,
     protocol_version: ProtocolVersion,
+    did_indy_namespace: Option<String>,
 }
 
 pub struct IndyVdrLedgerWrite<T>
@@ -135,9 +136,17 @@ where
             response_parser: config.response_parser,
             response_cacher: config.response_cacher,
             protocol_version: config.protocol_version,
+            did_indy_namespace: None,
         }
     }
 
+    /// Sets the did:indy namespace of the ledger (e.g. `sovrin:staging`), so that AnonCreds
+    /// objects with did:indy identifiers of that namespace are resolved by this reader
+    pub fn with_did_indy_namespace(mut self, namespace: impl Into<String>) -> Self {
+        self.did_indy_namespace = Some(namespace.into());
+        self
+    }
+
     pub fn request_builder(&self) -> VcxLedgerResult<RequestBuilder> {
         Ok(RequestBuilder::new(self.protocol_version))
     }
@@ -516,9 +525,10 @@ where
         _submitter_did: Option<&Did>,
     ) -> VcxLedgerResult<Schema> {
         debug!("get_schema >> schema_id: {schema_id}");
+        let legacy_schema_id = SchemaId::new_unchecked(to_legacy_object_id(&schema_id.0)?);
         let request = self
             .request_builder()?
-            .build_get_schema_request(None, &schema_id.convert(())?)?;
+            .build_get_schema_request(None, &legacy_schema_id.convert(())?)?;
         let response = self.submit_request(None, request).await?;
         debug!("get_schema << response: {response}");
         let schema = self
@@ -534,7 +544,7 @@ where
     ) -> VcxLedgerResult<CredentialDefinition> {
         debug!("get_cred_def >> cred_def_id: {cred_def_id}");
         let identifier = submitter_did.map(|did| did.convert(())).transpose()?;
-        let id = IndyVdrCredentialDefinitionId::from_str(&cred_def_id.to_string())?;
+        let id = IndyVdrCredentialDefinitionId::from_str(&to_legacy_object_id(&cred_def_id.0)?)?;
         let request = self
             .request_builder()?
             .build_get_cred_def_request(identifier.as_ref(), &id)?;
@@ -571,7 +581,7 @@ where
         RevocationRegistryDefinitionAdditionalMetadata,
     )> {
         debug!("get_rev_reg_def_json >> rev_reg_id: {rev_reg_id}");
-        let id = RevocationRegistryId::from_str(&rev_reg_id.to_string())?;
+        let id = RevocationRegistryId::from_str(&to_legacy_object_id(&rev_reg_id.0)?)?;
         let request = self
             .request_builder()?
             .build_get_revoc_reg_def_request(None, &id)?;
@@ -599,7 +609,8 @@ where
         to: Option<u64>,
     ) -> VcxLedgerResult<(RevocationRegistryDelta, u64)> {
         debug!("get_rev_reg_delta_json >> rev_reg_id: {rev_reg_id}, from: {from:?}, to: {to:?}");
-        let revoc_reg_def_id = RevocationRegistryId::from_str(&rev_reg_id.to_string())?;
+        let revoc_reg_def_id =
+            RevocationRegistryId::from_str(&to_legacy_object_id(&rev_reg_id.0)?)?;
 
         let from = from.map(|x| x as i64);
         let current_time = OffsetDateTime::now_utc().unix_timestamp();
@@ -662,7 +673,8 @@ where
         timestamp: u64,
     ) -> VcxLedgerResult<(RevocationRegistry, u64)> {
         debug!("get_rev_reg >> rev_reg_id: {rev_reg_id}, timestamp: {timestamp}");
-        let revoc_reg_def_id = RevocationRegistryId::from_str(&rev_reg_id.to_string())?;
+        let revoc_reg_def_id =
+            RevocationRegistryId::from_str(&to_legacy_object_id(&rev_reg_id.0)?)?;
 
         let request = self.request_builder()?.build_get_revoc_reg_request(
             None,
@@ -690,7 +702,7 @@ impl<T: RequestSubmitter, V: ResponseCacher> AnoncredsLedgerSupport for IndyVdrL
             // unqualified
             return true;
         }
-        did_method_is_supported(&id.0)
+        self.did_method_is_supported(&id.0)
     }
 
     fn supports_credential_definition(&self, id: &CredentialDefinitionId) -> bool {
@@ -698,7 +710,7 @@ impl<T: RequestSubmitter, V: ResponseCacher> AnoncredsLedgerSupport for IndyVdrL
             // unqualified
             return true;
         }
-        did_method_is_supported(&id.0)
+        self.did_method_is_supported(&id.0)
     }
 
     fn supports_revocation_registry(&self, id: &RevocationRegistryDefinitionId) -> bool {
@@ -706,17 +718,26 @@ impl<T: RequestSubmitter, V: ResponseCacher> AnoncredsLedgerSupport for IndyVdrL
             // unqualified
             return true;
         }
-        did_method_is_supported(&id.0)
+        self.did_method_is_supported(&id.0)
     }
 }
 
-fn did_method_is_supported(id: &str) -> bool {
-    let is_sov = id.starts_with("did:sov:");
-    let is_unqualified = !id.starts_with("did");
-
-    // FUTURE - indy & namespace
+impl<T: RequestSubmitter, V: ResponseCacher> IndyVdrLedgerRead<T, V> {
+    fn did_method_is_supported(&self, id: &str) -> bool {
+        let is_sov = id.starts_with("did:sov:");
+        let is_unqualified = !id.starts_with("did");
+        is_sov || is_unqualified || self.is_did_indy_namespace_supported(id)
+    }
 
-    is_sov || is_unqualified
+    fn is_did_indy_namespace_supported(&self, id: &str) -> bool {
+        let Some(namespace) = &self.did_indy_namespace else {
+            return false;
+        };
+        id.starts_with("did:indy:")
+            && to_legacy_object_id(id).is_ok()
+            && DidUrl::parse(id.to_string())
+                .is_ok_and(|did_url| did_url.namespace() == Some(namespace.as_str()))
+    }
 }
 
 #[async_trait]
@@ -966,4 +987,76 @@ mod unit_tests {
             .unwrap()
         ));
     }
+
+    #[test]
+    fn test_anoncreds_did_indy_support() {
+        let schema_id = SchemaId::new(
+            "did:indy:sovrin:staging:7BPMqYgYLQni258J8JPS8K/anoncreds/v0/SCHEMA/degree%20schema/\
+             46.58.87",
+        )
+        .unwrap();
+        let cred_def_id = CredentialDefinitionId::new(
+            "did:indy:sovrin:staging:7BPMqYgYLQni258J8JPS8K/anoncreds/v0/CLAIM_DEF/70/\
+             faber.agent.degree_schema",
+        )
+        .unwrap();
+        let rev_reg_id = RevocationRegistryDefinitionId::new(
+            "did:indy:sovrin:staging:7BPMqYgYLQni258J8JPS8K/anoncreds/v0/REV_REG_DEF/70/\
+             faber.agent.degree_schema/61d5a381-30be-4120-9307-b150b49c203c",
+        )
+        .unwrap();
+
+        // namespace not configured
+        let reader = dummy_indy_vdr_reader();
+        assert!(!reader.supports_schema(&schema_id));
+        assert!(!reader.supports_credential_definition(&cred_def_id));
+        assert!(!reader.supports_revocation_registry(&rev_reg_id));
+
+        // other namespace
+        let reader = dummy_indy_vdr_reader().with_did_indy_namespace("sovrin");
+        assert!(!reader.supports_schema(&schema_id));
+
+        let reader = dummy_indy_vdr_reader().with_did_indy_namespace("sovrin:staging");
+        assert!(reader.supports_schema(&schema_id));
+        assert!(reader.supports_credential_definition(&cred_def_id));
+        assert!(reader.supports_revocation_registry(&rev_reg_id));
+        // not an anoncreds object
+        assert!(!reader.supports_schema(
+            &SchemaId::new("did:indy:sovrin:staging:7BPMqYgYLQni258J8JPS8K/resources/1").unwrap()
+        ));
+    }
+
+    #[test]
+    fn test_did_indy_object_id_to_legacy() {
+        assert_eq!(
+            to_legacy_object_id(
+                "did:indy:sovrin:7BPMqYgYLQni258J8JPS8K/anoncreds/v0/SCHEMA/degree%20schema/\
+                 46.58.87"
+            )
+            .unwrap(),
+            "7BPMqYgYLQni258J8JPS8K:2:degree schema:46.58.87"
+        );
+        assert_eq!(
+            to_legacy_object_id(
+                "did:indy:sovrin:7BPMqYgYLQni258J8JPS8K/anoncreds/v0/CLAIM_DEF/70/\
+                 faber.agent.degree_schema"
+            )
+            .unwrap(),
+            "7BPMqYgYLQni258J8JPS8K:3:CL:70:faber.agent.degree_schema"
+        );
+        assert_eq!(
+            to_legacy_object_id(
+                "did:indy:sovrin:7BPMqYgYLQni258J8JPS8K/anoncreds/v0/REV_REG_ENTRY/70/\
+                 faber.agent.degree_schema/61d5a381-30be-4120-9307-b150b49c203c"
+            )
+            .unwrap(),
+            "7BPMqYgYLQni258J8JPS8K:4:7BPMqYgYLQni258J8JPS8K:3:CL:70:faber.agent.degree_schema:\
+             CL_ACCUM:61d5a381-30be-4120-9307-b150b49c203c"
+        );
+        // legacy identifiers are kept as they are
+        assert_eq!(
+            to_legacy_object_id("7BPMqYgYLQni258J8JPS8K:2:degree schema:46.58.87").unwrap(),
+            "7BPMqYgYLQni258J8JPS8K:2:degree schema:46.58.87"
+        );
+    }
 }
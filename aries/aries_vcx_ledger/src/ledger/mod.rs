@@ -9,6 +9,7 @@ pub mod cheqd;
 pub mod indy;
 pub mod indy_vdr_ledger;
 pub mod multi_ledger;
+pub mod read_only;
 mod type_conversion;
 
 pub mod request_submitter;
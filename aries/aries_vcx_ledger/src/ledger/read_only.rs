@@ -0,0 +1,66 @@
+//! Contains a no-op [AnoncredsLedgerWrite], for holders and verifiers which never publish
+//! anything, e.g. agents reading AnonCreds objects from cheqd only.
+use anoncreds_types::data_types::{
+    identifiers::rev_reg_def_id::RevocationRegistryDefinitionId,
+    ledger::{
+        cred_def::CredentialDefinition, rev_reg_def::RevocationRegistryDefinition,
+        rev_reg_delta::RevocationRegistryDelta, schema::Schema,
+    },
+};
+use aries_vcx_wallet::wallet::base_wallet::BaseWallet;
+use async_trait::async_trait;
+use did_parser_nom::Did;
+
+use super::base_ledger::AnoncredsLedgerWrite;
+use crate::errors::error::{VcxLedgerError, VcxLedgerResult};
+
+/// [AnoncredsLedgerWrite] refusing every write with [VcxLedgerError::UnimplementedFeature].
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ReadOnlyLedgerWrite;
+
+fn read_only<T>(object: &str) -> VcxLedgerResult<T> {
+    Err(VcxLedgerError::UnimplementedFeature(format!(
+        "Unable to publish {object}, the ledger is read-only"
+    )))
+}
+
+#[async_trait]
+impl AnoncredsLedgerWrite for ReadOnlyLedgerWrite {
+    async fn publish_schema(
+        &self,
+        _wallet: &impl BaseWallet,
+        _schema_json: Schema,
+        _submitter_did: &Did,
+        _endorser_did: Option<&Did>,
+    ) -> VcxLedgerResult<()> {
+        read_only("schema")
+    }
+
+    async fn publish_cred_def(
+        &self,
+        _wallet: &impl BaseWallet,
+        _cred_def_json: CredentialDefinition,
+        _submitter_did: &Did,
+    ) -> VcxLedgerResult<()> {
+        read_only("credential definition")
+    }
+
+    async fn publish_rev_reg_def(
+        &self,
+        _wallet: &impl BaseWallet,
+        _rev_reg_def: RevocationRegistryDefinition,
+        _submitter_did: &Did,
+    ) -> VcxLedgerResult<()> {
+        read_only("revocation registry definition")
+    }
+
+    async fn publish_rev_reg_delta(
+        &self,
+        _wallet: &impl BaseWallet,
+        _rev_reg_id: &RevocationRegistryDefinitionId,
+        _rev_reg_entry_json: RevocationRegistryDelta,
+        _submitter_did: &Did,
+    ) -> VcxLedgerResult<()> {
+        read_only("revocation registry delta")
+    }
+}
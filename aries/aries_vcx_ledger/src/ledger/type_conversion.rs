@@ -20,7 +20,7 @@ use anoncreds_types::data_types::{
         schema::Schema as OurSchema,
     },
 };
-use did_parser_nom::Did;
+use did_parser_nom::{Did, DidUrl, IndyAnoncredsObject, ParseError};
 use indy_vdr::{
     ledger::{
         identifiers::{
@@ -127,6 +127,36 @@ impl Convert for &OurSchemaId {
     }
 }
 
+/// Legacy Indy ledger identifier of an AnonCreds object. did:indy object identifiers, such as
+/// `did:indy:sovrin:<nym>/anoncreds/v0/SCHEMA/<name>/<version>`, are mapped to their legacy form
+/// (`<nym>:2:<name>:<version>`), any other identifier is returned as is.
+pub(crate) fn to_legacy_object_id(id: &str) -> Result<String, ParseError> {
+    if !id.starts_with("did:indy:") {
+        return Ok(id.to_string());
+    }
+    let did_url = DidUrl::parse(id.to_string())?;
+    let nym = did_url
+        .id()
+        .ok_or(ParseError::InvalidInput("Missing DID in object identifier"))?;
+    let legacy_id = match IndyAnoncredsObject::try_from(&did_url)? {
+        IndyAnoncredsObject::Schema { name, version } => format!("{nym}:2:{name}:{version}"),
+        IndyAnoncredsObject::CredentialDefinition { schema_seq_no, tag } => {
+            format!("{nym}:3:CL:{schema_seq_no}:{tag}")
+        }
+        IndyAnoncredsObject::RevocationRegistryDefinition {
+            schema_seq_no,
+            cred_def_tag,
+            tag,
+        }
+        | IndyAnoncredsObject::RevocationRegistryEntry {
+            schema_seq_no,
+            cred_def_tag,
+            tag,
+        } => format!("{nym}:4:{nym}:3:CL:{schema_seq_no}:{cred_def_tag}:CL_ACCUM:{tag}"),
+    };
+    Ok(legacy_id)
+}
+
 impl Convert for &Did {
     type Args = ();
     type Target = DidValue;
@@ -40,6 +40,7 @@ pub fn public_key_type_to_askar_key_alg(value: &KeyType) -> VcxWalletResult<KeyA
         KeyType::Bls12381g2 => KeyAlg::Bls12_381(BlsCurves::G2),
         KeyType::P256 => KeyAlg::EcCurve(EcCurves::Secp256r1),
         KeyType::P384 => KeyAlg::EcCurve(EcCurves::Secp384r1),
+        KeyType::Secp256k1 => KeyAlg::EcCurve(EcCurves::Secp256k1),
         _ => {
             return Err(VcxWalletError::Unimplemented(format!(
                 "Unsupported key type: {value:?}"
@@ -95,6 +95,46 @@ impl AskarWallet {
         })
     }
 
+    /// Opens wallet kept as a profile of a shared store, provisioning the store and creating
+    /// the profile if they don't exist yet. Lets several wallets, such as tenants of a
+    /// multi-tenant agent, share one database. In-memory stores are always provisioned anew.
+    pub async fn create_or_open_profile(
+        wallet_config: &AskarWalletConfig,
+    ) -> VcxWalletResult<Self> {
+        if wallet_config.db_url().contains(":memory:") {
+            return Self::create(wallet_config, false).await;
+        }
+        let backend = match Store::open(
+            wallet_config.db_url(),
+            Some((*wallet_config.key_method()).into()),
+            wallet_config.pass_key().into(),
+            None,
+        )
+        .await
+        {
+            Ok(backend) => backend,
+            Err(err) => {
+                return match err.kind() {
+                    aries_askar::ErrorKind::NotFound => Self::create(wallet_config, false).await,
+                    _ => Err(err.into()),
+                }
+            }
+        };
+        if let Err(err) = backend
+            .create_profile(Some(wallet_config.profile().to_owned()))
+            .await
+        {
+            match err.kind() {
+                aries_askar::ErrorKind::Duplicate => {}
+                _ => return Err(err.into()),
+            }
+        }
+        Ok(Self {
+            backend,
+            profile: wallet_config.profile().into(),
+        })
+    }
+
     async fn fetch(
         &self,
         session: &mut Session,
@@ -252,4 +292,48 @@ pub mod tests {
 
         config.create_wallet().await.unwrap()
     }
+
+    #[tokio::test]
+    async fn test_create_or_open_profile() {
+        use uuid::Uuid;
+
+        use crate::wallet::base_wallet::{
+            record::Record, record_category::RecordCategory, record_wallet::RecordWallet,
+        };
+
+        let db_path = std::env::temp_dir().join(format!("{}.db", Uuid::new_v4()));
+        let db_url = format!("sqlite://{}", db_path.display());
+        let config =
+            |profile: &str| AskarWalletConfig::new(&db_url, KeyMethod::Unprotected, "", profile);
+        let record = Record::builder()
+            .category(RecordCategory::Cred)
+            .name("cred".into())
+            .value("value".into())
+            .build();
+
+        // provisions the store along with the first profile
+        let acme = AskarWallet::create_or_open_profile(&config("acme"))
+            .await
+            .unwrap();
+        acme.add_record(record).await.unwrap();
+        // adds a profile to the existing store
+        let globex = AskarWallet::create_or_open_profile(&config("globex"))
+            .await
+            .unwrap();
+        assert!(globex
+            .get_record(RecordCategory::Cred, "cred")
+            .await
+            .is_err());
+        drop((acme, globex));
+
+        // reopens the existing profile
+        let acme = AskarWallet::create_or_open_profile(&config("acme"))
+            .await
+            .unwrap();
+        let record = acme.get_record(RecordCategory::Cred, "cred").await.unwrap();
+        assert_eq!(record.value(), "value");
+
+        drop(acme);
+        let _ = std::fs::remove_file(db_path);
+    }
 }
@@ -20,6 +20,7 @@ const REV_REG_DEF_PRIV: &str = "VCX_REV_REG_DEF_PRIV";
 const DID: &str = "Indy::Did";
 const TMP_DID: &str = "Indy::TemporaryDid";
 const KEY: &str = "Indy::Key";
+const AGENT_STATE: &str = "VCX_AGENT_STATE";
 
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
 pub enum RecordCategory {
@@ -39,6 +40,7 @@ pub enum RecordCategory {
     Did,
     TmpDid,
     Key,
+    AgentState,
 }
 
 impl FromStr for RecordCategory {
@@ -61,6 +63,7 @@ impl FromStr for RecordCategory {
             DID => Ok(RecordCategory::Did),
             TMP_DID => Ok(RecordCategory::TmpDid),
             KEY => Ok(RecordCategory::Key),
+            AGENT_STATE => Ok(RecordCategory::AgentState),
             _ => Err(Self::Err::UnknownRecordCategory(s.into())),
         }
     }
@@ -84,6 +87,7 @@ impl Display for RecordCategory {
             RecordCategory::Did => DID,
             RecordCategory::TmpDid => TMP_DID,
             RecordCategory::Key => KEY,
+            RecordCategory::AgentState => AGENT_STATE,
         };
 
         write!(f, "{}", value)
@@ -35,8 +35,9 @@ use crate::{
     msg_fields::{
         protocols::{
             basic_message::BasicMessage, connection::Connection,
-            coordinate_mediation::CoordinateMediation, discover_features::DiscoverFeatures,
-            notification::Notification, out_of_band::OutOfBand, present_proof::v1::PresentProofV1,
+            coordinate_mediation::CoordinateMediation, did_rotate::DidRotate,
+            discover_features::DiscoverFeatures, notification::Notification,
+            out_of_band::OutOfBand, present_proof::v1::PresentProofV1,
             report_problem::ProblemReport, revocation::Revocation, routing::Forward,
             trust_ping::TrustPing,
         },
@@ -76,6 +77,7 @@ pub enum AriesMessage {
     Pickup(Pickup),
     CoordinateMediation(CoordinateMediation),
     DidExchange(DidExchange),
+    DidRotate(DidRotate),
 }
 
 impl DelayedSerde for AriesMessage {
@@ -200,6 +202,9 @@ impl DelayedSerde for AriesMessage {
                 DidExchangeV1_1::delayed_deserialize((msg_type, kind_str), deserializer)
                     .map(|x| AriesMessage::from(DidExchange::V1_1(x)))
             }
+            Protocol::DidRotateType(msg_type) => {
+                DidRotate::delayed_deserialize((msg_type, kind_str), deserializer).map(From::from)
+            }
         }
     }
 
@@ -225,6 +230,7 @@ impl DelayedSerde for AriesMessage {
             Self::CoordinateMediation(v) => v.delayed_serialize(serializer),
             Self::DidExchange(DidExchange::V1_0(v)) => v.delayed_serialize(serializer),
             Self::DidExchange(DidExchange::V1_1(v)) => v.delayed_serialize(serializer),
+            Self::DidRotate(v) => v.delayed_serialize(serializer),
         }
     }
 }
@@ -0,0 +1,45 @@
+use serde::{Deserialize, Serialize};
+use typed_builder::TypedBuilder;
+
+use crate::{
+    decorators::{thread::Thread, timing::Timing},
+    msg_parts::MsgParts,
+};
+
+/// Sent by the rotating party instead of [`Rotate`](super::rotate::Rotate) to end the relationship.
+pub type Hangup = MsgParts<HangupContent, HangupDecorators>;
+
+#[derive(Clone, Debug, Deserialize, Serialize, Default, PartialEq, TypedBuilder)]
+pub struct HangupContent {}
+
+#[derive(Clone, Debug, Deserialize, Serialize, Default, PartialEq, TypedBuilder)]
+pub struct HangupDecorators {
+    #[builder(default, setter(strip_option))]
+    #[serde(rename = "~thread")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thread: Option<Thread>,
+    #[builder(default, setter(strip_option))]
+    #[serde(rename = "~timing")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timing: Option<Timing>,
+}
+
+#[cfg(test)]
+#[allow(clippy::field_reassign_with_default)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::{misc::test_utils, msg_types::protocols::did_rotate::DidRotateTypeV1_0};
+
+    #[test]
+    fn test_minimal_hangup() {
+        let content = HangupContent::default();
+
+        let decorators = HangupDecorators::default();
+
+        let expected = json!({});
+
+        test_utils::test_msg(content, decorators, DidRotateTypeV1_0::Hangup, expected);
+    }
+}
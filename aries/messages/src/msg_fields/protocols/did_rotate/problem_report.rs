@@ -0,0 +1,107 @@
+use serde::{Deserialize, Serialize};
+use typed_builder::TypedBuilder;
+
+use crate::{
+    msg_fields::protocols::report_problem::{
+        ProblemReport, ProblemReportContent, ProblemReportDecorators,
+    },
+    msg_parts::MsgParts,
+};
+
+/// Sent by the observing party when it cannot rotate to the announced DID.
+pub type DidRotateProblemReport = MsgParts<DidRotateProblemReportContent, ProblemReportDecorators>;
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, TypedBuilder)]
+#[serde(transparent)]
+pub struct DidRotateProblemReportContent {
+    pub inner: ProblemReportContent,
+}
+
+impl From<ProblemReportContent> for DidRotateProblemReportContent {
+    fn from(value: ProblemReportContent) -> Self {
+        Self { inner: value }
+    }
+}
+
+impl From<DidRotateProblemReport> for ProblemReport {
+    fn from(value: DidRotateProblemReport) -> Self {
+        Self::builder()
+            .id(value.id)
+            .content(value.content.inner)
+            .decorators(value.decorators)
+            .build()
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::field_reassign_with_default)]
+mod tests {
+    use std::collections::HashMap;
+
+    use serde_json::json;
+
+    use super::*;
+    use crate::{
+        decorators::thread::tests::make_extended_thread,
+        misc::test_utils,
+        msg_fields::protocols::report_problem::{Description, Impact},
+        msg_types::protocols::did_rotate::DidRotateTypeV1_0,
+    };
+
+    #[test]
+    fn test_minimal_problem_report() {
+        let description = Description::builder()
+            .code("e.did.unresolvable".to_owned())
+            .build();
+
+        let content: DidRotateProblemReportContent = ProblemReportContent::builder()
+            .description(description)
+            .build();
+        let decorators = ProblemReportDecorators::default();
+
+        let expected = json!({
+            "description": content.inner.description
+        });
+
+        test_utils::test_msg(
+            content,
+            decorators,
+            DidRotateTypeV1_0::ProblemReport,
+            expected,
+        );
+    }
+
+    #[test]
+    fn test_extended_problem_report() {
+        let description = Description::builder()
+            .code("e.did.unresolvable".to_owned())
+            .build();
+
+        let content: DidRotateProblemReportContent = ProblemReportContent::builder()
+            .description(description)
+            .impact(Impact::Thread)
+            .problem_items(vec![HashMap::from([(
+                "did".to_owned(),
+                "did:peer:4zQmtest".to_owned(),
+            )])])
+            .build();
+
+        let decorators = ProblemReportDecorators::builder()
+            .thread(make_extended_thread())
+            .build();
+
+        let expected = json!({
+            "description": content.inner.description,
+            "impact": content.inner.impact,
+            "problem_items": content.inner.problem_items,
+            "~thread": decorators.thread
+        });
+
+        test_utils::test_msg(
+            content,
+            decorators,
+            DidRotateTypeV1_0::ProblemReport,
+            expected,
+        );
+    }
+}
@@ -0,0 +1,75 @@
+use serde::{Deserialize, Serialize};
+use typed_builder::TypedBuilder;
+
+use crate::{
+    decorators::{thread::Thread, timing::Timing},
+    msg_parts::MsgParts,
+};
+
+/// Sent by the rotating party, from its current DID, to announce the DID it rotates to.
+pub type Rotate = MsgParts<RotateContent, RotateDecorators>;
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, TypedBuilder)]
+pub struct RotateContent {
+    pub to_did: String,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, Default, PartialEq, TypedBuilder)]
+pub struct RotateDecorators {
+    #[builder(default, setter(strip_option))]
+    #[serde(rename = "~thread")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thread: Option<Thread>,
+    #[builder(default, setter(strip_option))]
+    #[serde(rename = "~timing")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timing: Option<Timing>,
+}
+
+#[cfg(test)]
+#[allow(clippy::field_reassign_with_default)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::{
+        decorators::{thread::tests::make_extended_thread, timing::tests::make_extended_timing},
+        misc::test_utils,
+        msg_types::protocols::did_rotate::DidRotateTypeV1_0,
+    };
+
+    #[test]
+    fn test_minimal_rotate() {
+        let content = RotateContent::builder()
+            .to_did("did:peer:4zQmtest".to_owned())
+            .build();
+
+        let decorators = RotateDecorators::default();
+
+        let expected = json!({
+            "to_did": content.to_did,
+        });
+
+        test_utils::test_msg(content, decorators, DidRotateTypeV1_0::Rotate, expected);
+    }
+
+    #[test]
+    fn test_extended_rotate() {
+        let content = RotateContent::builder()
+            .to_did("did:peer:4zQmtest".to_owned())
+            .build();
+
+        let decorators = RotateDecorators::builder()
+            .thread(make_extended_thread())
+            .timing(make_extended_timing())
+            .build();
+
+        let expected = json!({
+            "to_did": content.to_did,
+            "~thread": decorators.thread,
+            "~timing": decorators.timing
+        });
+
+        test_utils::test_msg(content, decorators, DidRotateTypeV1_0::Rotate, expected);
+    }
+}
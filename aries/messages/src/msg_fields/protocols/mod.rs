@@ -4,6 +4,7 @@ pub mod connection;
 pub mod coordinate_mediation;
 pub mod cred_issuance;
 pub mod did_exchange;
+pub mod did_rotate;
 pub mod discover_features;
 pub mod notification;
 pub mod out_of_band;
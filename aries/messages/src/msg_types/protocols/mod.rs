@@ -7,10 +7,11 @@ use shared::misc::utils::CowStr;
 use self::{
     basic_message::BasicMessageType, connection::ConnectionType,
     coordinate_mediation::CoordinateMediationType, cred_issuance::CredentialIssuanceType,
-    did_exchange::DidExchangeType, discover_features::DiscoverFeaturesType,
-    notification::NotificationType, out_of_band::OutOfBandType, pickup::PickupType,
-    present_proof::PresentProofType, report_problem::ReportProblemType, revocation::RevocationType,
-    routing::RoutingType, signature::SignatureType, trust_ping::TrustPingType,
+    did_exchange::DidExchangeType, did_rotate::DidRotateType,
+    discover_features::DiscoverFeaturesType, notification::NotificationType,
+    out_of_band::OutOfBandType, pickup::PickupType, present_proof::PresentProofType,
+    report_problem::ReportProblemType, revocation::RevocationType, routing::RoutingType,
+    signature::SignatureType, trust_ping::TrustPingType,
 };
 use crate::{
     error::{MsgTypeError, MsgTypeResult},
@@ -22,6 +23,7 @@ pub mod connection;
 pub mod coordinate_mediation;
 pub mod cred_issuance;
 pub mod did_exchange;
+pub mod did_rotate;
 pub mod discover_features;
 pub mod notification;
 pub mod out_of_band;
@@ -65,6 +67,7 @@ pub enum Protocol {
     PickupType(PickupType),
     CoordinateMediationType(CoordinateMediationType),
     DidExchangeType(DidExchangeType),
+    DidRotateType(DidRotateType),
 }
 
 /// Utility macro to avoid harder to read and error prone calling
@@ -103,6 +106,7 @@ impl Protocol {
         match_protocol!(PickupType, protocol, major, minor);
         match_protocol!(CoordinateMediationType, protocol, major, minor);
         match_protocol!(DidExchangeType, protocol, major, minor);
+        match_protocol!(DidRotateType, protocol, major, minor);
 
         Err(MsgTypeError::unknown_protocol(protocol.to_owned()))
     }
@@ -125,6 +129,7 @@ impl Protocol {
             Self::PickupType(v) => v.as_protocol_parts(),
             Self::CoordinateMediationType(v) => v.as_protocol_parts(),
             Self::DidExchangeType(v) => v.as_protocol_parts(),
+            Self::DidRotateType(v) => v.as_protocol_parts(),
         }
     }
 
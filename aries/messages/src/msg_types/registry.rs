@@ -12,6 +12,7 @@ use crate::msg_types::{
         coordinate_mediation::CoordinateMediationTypeV1,
         cred_issuance::{CredentialIssuanceTypeV1, CredentialIssuanceTypeV2},
         did_exchange::DidExchangeTypeV1,
+        did_rotate::DidRotateTypeV1,
         discover_features::DiscoverFeaturesTypeV1,
         notification::NotificationTypeV1,
         out_of_band::OutOfBandTypeV1,
@@ -98,6 +99,7 @@ lazy_static! {
         map_insert(&mut m, extract_parts!(CoordinateMediationTypeV1::new_v1_0()));
         map_insert(&mut m, extract_parts!(DidExchangeTypeV1::new_v1_0()));
         map_insert(&mut m, extract_parts!(DidExchangeTypeV1::new_v1_1()));
+        map_insert(&mut m, extract_parts!(DidRotateTypeV1::new_v1_0()));
         m
     };
 }
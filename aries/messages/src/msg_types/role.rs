@@ -19,4 +19,8 @@ pub enum Role {
     Notifier,
     Mediator,
     Recipient,
+    #[serde(rename = "rotating_party")]
+    RotatingParty,
+    #[serde(rename = "observing_party")]
+    ObservingParty,
 }
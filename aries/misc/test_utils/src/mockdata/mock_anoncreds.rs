@@ -27,7 +27,7 @@ use anoncreds_types::data_types::{
 };
 use aries_vcx_anoncreds::{
     anoncreds::base_anoncreds::{
-        BaseAnonCreds, CredentialDefinitionsMap, CredentialId, LinkSecretId,
+        BaseAnonCreds, CredentialDefinitionsMap, CredentialId, CredentialMetadata, LinkSecretId,
         RevocationRegistriesMap, RevocationRegistryDefinitionsMap, RevocationStatesMap, SchemasMap,
     },
     errors::error::{VcxAnoncredsError, VcxAnoncredsResult},
@@ -150,6 +150,41 @@ impl BaseAnonCreds for MockAnoncreds {
         ))
     }
 
+    async fn prover_get_credentials_with_metadata(
+        &self,
+        _wallet: &impl BaseWallet,
+        _filter_json: Option<&str>,
+    ) -> VcxAnoncredsResult<Vec<(RetrievedCredentialInfo, CredentialMetadata)>> {
+        // not needed yet
+        Err(VcxAnoncredsError::UnimplementedFeature(
+            "unimplemented mock method: prover_get_credentials_with_metadata".into(),
+        ))
+    }
+
+    async fn prover_get_credential_metadata(
+        &self,
+        _wallet: &impl BaseWallet,
+        _cred_id: &CredentialId,
+    ) -> VcxAnoncredsResult<CredentialMetadata> {
+        // not needed yet
+        Err(VcxAnoncredsError::UnimplementedFeature(
+            "unimplemented mock method: prover_get_credential_metadata".into(),
+        ))
+    }
+
+    async fn prover_set_credential_metadata(
+        &self,
+        _wallet: &impl BaseWallet,
+        _cred_id: &CredentialId,
+        _name: &str,
+        _value: Option<&str>,
+    ) -> VcxAnoncredsResult<()> {
+        // not needed yet
+        Err(VcxAnoncredsError::UnimplementedFeature(
+            "unimplemented mock method: prover_set_credential_metadata".into(),
+        ))
+    }
+
     async fn prover_get_credentials_for_proof_req(
         &self,
         _wallet: &impl BaseWallet,
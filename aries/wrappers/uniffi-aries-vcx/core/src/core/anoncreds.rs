@@ -15,3 +15,31 @@ pub fn get_credentials(profile_holder: Arc<ProfileHolder>) -> VcxUniFFIResult<St
         Ok(serde_json::to_string(&credentials)?)
     })
 }
+
+pub fn get_credential(
+    profile_holder: Arc<ProfileHolder>,
+    cred_id: String,
+) -> VcxUniFFIResult<String> {
+    block_on(async {
+        let credential = profile_holder
+            .inner
+            .anoncreds()
+            .prover_get_credential(profile_holder.inner.wallet(), &cred_id)
+            .await?;
+        Ok(serde_json::to_string(&credential)?)
+    })
+}
+
+pub fn delete_credential(
+    profile_holder: Arc<ProfileHolder>,
+    cred_id: String,
+) -> VcxUniFFIResult<()> {
+    block_on(async {
+        profile_holder
+            .inner
+            .anoncreds()
+            .prover_delete_credential(profile_holder.inner.wallet(), &cred_id)
+            .await?;
+        Ok(())
+    })
+}
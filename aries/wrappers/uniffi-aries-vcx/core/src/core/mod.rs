@@ -2,4 +2,5 @@ pub mod anoncreds;
 pub mod http_client;
 pub mod logging;
 pub mod profile;
+pub mod transport;
 pub mod unpack_message;
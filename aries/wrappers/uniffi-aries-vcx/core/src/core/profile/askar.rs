@@ -6,6 +6,7 @@ use aries_vcx::{
         askar::{askar_wallet_config::AskarWalletConfig, AskarWallet},
         base_wallet::ManageWallet,
     },
+    did_peer::resolver::PeerDidResolver,
 };
 use aries_vcx_anoncreds::anoncreds::anoncreds::Anoncreds;
 use aries_vcx_ledger::ledger::{
@@ -13,22 +14,53 @@ use aries_vcx_ledger::ledger::{
     request_submitter::vdr_ledger::{IndyVdrLedgerPool, IndyVdrSubmitter},
     response_cacher::in_memory::{InMemoryResponseCacher, InMemoryResponseCacherConfig},
 };
+use did_resolver_registry::ResolverRegistry;
+use did_resolver_sov::resolution::DidSovResolver;
 use indy_vdr::config::PoolConfig;
 
 use crate::{
-    core::logging::enable_logging, errors::error::VcxUniFFIResult, runtime::block_on, ProfileHolder,
+    core::{
+        logging::enable_logging,
+        transport::{ForeignTransport, Transport},
+    },
+    errors::error::VcxUniFFIResult,
+    runtime::block_on,
+    ProfileHolder,
 };
 
 #[derive(Debug)]
 pub struct UniffiProfile {
     pub wallet: AskarWallet,
     pub anoncreds: Anoncreds,
-    pub ledger_read: IndyVdrLedgerRead<IndyVdrSubmitter, InMemoryResponseCacher>,
+    pub ledger_read: Arc<IndyVdrLedgerRead<IndyVdrSubmitter, InMemoryResponseCacher>>,
+    pub resolver_registry: Arc<ResolverRegistry>,
+    pub transport: Option<ForeignTransport>,
 }
 
 pub fn new_indy_profile(
     wallet_config: AskarWalletConfig,
     genesis_file_path: String,
+) -> VcxUniFFIResult<Arc<ProfileHolder>> {
+    build_indy_profile(wallet_config, genesis_file_path, None)
+}
+
+/// Profile sending messages through the transport, rather than posting them over HTTP
+pub fn new_indy_profile_with_transport(
+    wallet_config: AskarWalletConfig,
+    genesis_file_path: String,
+    transport: Arc<dyn Transport>,
+) -> VcxUniFFIResult<Arc<ProfileHolder>> {
+    build_indy_profile(
+        wallet_config,
+        genesis_file_path,
+        Some(ForeignTransport::new(transport)),
+    )
+}
+
+fn build_indy_profile(
+    wallet_config: AskarWalletConfig,
+    genesis_file_path: String,
+    transport: Option<ForeignTransport>,
 ) -> VcxUniFFIResult<Arc<ProfileHolder>> {
     // Enable android logging
     enable_logging();
@@ -50,11 +82,22 @@ pub fn new_indy_profile(
             .build();
         let ledger_pool = IndyVdrLedgerPool::new(genesis_file_path, indy_vdr_config, vec![])?;
         let request_submitter = IndyVdrSubmitter::new(ledger_pool);
-        let ledger_read = indyvdr_build_ledger_read(request_submitter, cache_config)?;
+        let ledger_read = Arc::new(indyvdr_build_ledger_read(request_submitter, cache_config)?);
+        let did_sov_resolver: DidSovResolver<
+            Arc<IndyVdrLedgerRead<_, _>>,
+            IndyVdrLedgerRead<_, _>,
+        > = DidSovResolver::new(ledger_read.clone());
+        let resolver_registry = Arc::new(
+            ResolverRegistry::new()
+                .register_resolver("peer".into(), PeerDidResolver::new())
+                .register_resolver("sov".into(), did_sov_resolver),
+        );
         let profile = UniffiProfile {
             anoncreds: Anoncreds,
             wallet,
             ledger_read,
+            resolver_registry,
+            transport,
         };
 
         Ok(Arc::new(ProfileHolder { inner: profile }))
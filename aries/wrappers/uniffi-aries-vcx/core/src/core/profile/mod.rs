@@ -7,16 +7,23 @@ use aries_vcx_ledger::ledger::{
 };
 
 pub mod askar;
-use aries_vcx::aries_vcx_wallet::wallet::askar::AskarWallet;
+use std::sync::Arc;
+
+use aries_vcx::{aries_vcx_wallet::wallet::askar::AskarWallet, transport::Transport};
 pub use askar as profile;
+use did_resolver_registry::ResolverRegistry;
 
-use crate::profile::UniffiProfile;
+use crate::{core::http_client::HttpClient, profile::UniffiProfile};
 
 impl UniffiProfile {
     pub fn ledger_read(&self) -> &IndyVdrLedgerRead<IndyVdrSubmitter, InMemoryResponseCacher> {
         &self.ledger_read
     }
 
+    pub fn resolver_registry(&self) -> &Arc<ResolverRegistry> {
+        &self.resolver_registry
+    }
+
     pub fn anoncreds(&self) -> &Anoncreds {
         &self.anoncreds
     }
@@ -25,6 +32,14 @@ impl UniffiProfile {
         &self.wallet
     }
 
+    /// The transport supplied by the app, or HTTP if none was
+    pub fn transport(&self) -> &dyn Transport {
+        match &self.transport {
+            Some(transport) => transport,
+            None => &HttpClient,
+        }
+    }
+
     pub fn update_taa_configuration(&self, _taa_options: TxnAuthrAgrmtOptions) -> VcxResult<()> {
         Err(AriesVcxError::from_msg(
             AriesVcxErrorKind::ActionNotSupported,
@@ -0,0 +1,45 @@
+use std::sync::Arc;
+
+use aries_vcx::{
+    errors::error::{AriesVcxError, AriesVcxErrorKind, VcxResult},
+    transport::Transport as VcxTransport,
+};
+use async_trait::async_trait;
+use url::Url;
+
+use crate::errors::error::VcxUniFFIResult;
+
+/// Sends packed messages on behalf of the wrapper, implemented by the app over its own networking
+/// stack, or by forwarding through a mediator
+#[async_trait]
+pub trait Transport: Send + Sync {
+    async fn send_message(&self, msg: Vec<u8>, service_endpoint: String) -> VcxUniFFIResult<()>;
+}
+
+/// Wraps a foreign [Transport] into an [aries_vcx::transport::Transport]
+#[derive(Clone)]
+pub struct ForeignTransport(Arc<dyn Transport>);
+
+impl ForeignTransport {
+    pub fn new(transport: Arc<dyn Transport>) -> Self {
+        Self(transport)
+    }
+}
+
+impl std::fmt::Debug for ForeignTransport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("ForeignTransport")
+    }
+}
+
+#[async_trait]
+impl VcxTransport for ForeignTransport {
+    async fn send_message(&self, msg: Vec<u8>, service_endpoint: &Url) -> VcxResult<()> {
+        self.0
+            .send_message(msg, service_endpoint.to_string())
+            .await
+            .map_err(|err| {
+                AriesVcxError::from_msg(AriesVcxErrorKind::PostMessageFailed, err.to_string())
+            })
+    }
+}
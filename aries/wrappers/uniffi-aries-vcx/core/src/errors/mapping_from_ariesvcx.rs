@@ -1,4 +1,8 @@
-use aries_vcx::{aries_vcx_wallet::errors::error::VcxWalletError, errors::error::AriesVcxError};
+use aries_vcx::{
+    aries_vcx_wallet::errors::error::VcxWalletError,
+    did_doc::schema::utils::error::DidDocumentLookupError, errors::error::AriesVcxError,
+    protocols::did_exchange::state_machine::generic::GenericDidExchange,
+};
 use aries_vcx_anoncreds::errors::error::VcxAnoncredsError;
 use aries_vcx_ledger::errors::error::VcxLedgerError;
 
@@ -39,3 +43,17 @@ impl From<VcxAnoncredsError> for VcxUniFFIError {
         }
     }
 }
+
+impl From<(GenericDidExchange, AriesVcxError)> for VcxUniFFIError {
+    fn from((_, e): (GenericDidExchange, AriesVcxError)) -> Self {
+        e.into()
+    }
+}
+
+impl From<DidDocumentLookupError> for VcxUniFFIError {
+    fn from(e: DidDocumentLookupError) -> Self {
+        VcxUniFFIError::AriesVcxError {
+            error_msg: format!("DidDocumentLookupError: {e}"),
+        }
+    }
+}
@@ -33,3 +33,19 @@ impl From<did_parser_nom::ParseError> for VcxUniFFIError {
         }
     }
 }
+
+impl From<public_key::PublicKeyError> for VcxUniFFIError {
+    fn from(e: public_key::PublicKeyError) -> Self {
+        VcxUniFFIError::StringParseError {
+            error_msg: e.to_string(),
+        }
+    }
+}
+
+impl From<uniffi::UnexpectedUniFFICallbackError> for VcxUniFFIError {
+    fn from(e: uniffi::UnexpectedUniFFICallbackError) -> Self {
+        VcxUniFFIError::InternalError {
+            error_msg: e.reason,
+        }
+    }
+}
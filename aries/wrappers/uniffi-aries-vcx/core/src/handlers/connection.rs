@@ -1,4 +1,4 @@
-use std::sync::{Arc, Mutex};
+use std::sync::Arc;
 
 use aries_vcx::{
     errors::error::{AriesVcxError, AriesVcxErrorKind},
@@ -7,13 +7,10 @@ use aries_vcx::{
         GenericConnection as VcxGenericConnection, ThinState,
     },
 };
+use tokio::sync::Mutex;
 use url::Url;
 
-use crate::{
-    core::{http_client::HttpClient, profile::ProfileHolder},
-    errors::error::VcxUniFFIResult,
-    runtime::block_on,
-};
+use crate::{core::profile::ProfileHolder, errors::error::VcxUniFFIResult, runtime::block_on};
 
 /// Wraps [ThinState], as uniffi cannot process enums with un-named fields
 pub struct ConnectionState {
@@ -94,12 +91,12 @@ pub fn create_invitee(profile: Arc<ProfileHolder>) -> VcxUniFFIResult<Arc<Connec
 
 impl Connection {
     pub fn get_state(&self) -> VcxUniFFIResult<ConnectionState> {
-        let handler = self.handler.lock()?;
+        let handler = self.handler.blocking_lock();
         Ok(ConnectionState::from(handler.state()))
     }
 
     pub fn pairwise_info(&self) -> VcxUniFFIResult<PairwiseInfo> {
-        let handler = self.handler.lock()?;
+        let handler = self.handler.blocking_lock();
         Ok(handler.pairwise_info().clone())
     }
 
@@ -112,18 +109,7 @@ impl Connection {
         profile: Arc<ProfileHolder>,
         invitation: String,
     ) -> VcxUniFFIResult<()> {
-        let mut handler = self.handler.lock()?;
-        let invitation = serde_json::from_str(&invitation)?;
-
-        let connection = VcxConnection::try_from(handler.clone())?;
-
-        block_on(async {
-            let new_conn = connection
-                .accept_invitation(profile.inner.ledger_read(), invitation)
-                .await?;
-            *handler = VcxGenericConnection::from(new_conn);
-            Ok(())
-        })
+        block_on(self.accept_invitation_async(profile, invitation))
     }
 
     // NOTE : using string here out of laziness. We could have type this,
@@ -137,23 +123,7 @@ impl Connection {
         service_endpoint: String,
         routing_keys: Vec<String>,
     ) -> VcxUniFFIResult<()> {
-        let mut handler = self.handler.lock()?;
-        let request = serde_json::from_str(&request)?;
-
-        let connection = VcxConnection::try_from(handler.clone())?;
-        let url = Url::parse(&service_endpoint).map_err(|err| {
-            AriesVcxError::from_msg(AriesVcxErrorKind::InvalidUrl, err.to_string())
-        })?;
-
-        block_on(async {
-            let new_conn = connection
-                .handle_request(profile.inner.wallet(), request, url, routing_keys)
-                .await?;
-
-            *handler = VcxGenericConnection::from(new_conn);
-
-            Ok(())
-        })
+        block_on(self.handle_request_async(profile, request, service_endpoint, routing_keys))
     }
 
     // NOTE : using string here out of laziness. We could have type this,
@@ -165,92 +135,158 @@ impl Connection {
         profile: Arc<ProfileHolder>,
         response: String,
     ) -> VcxUniFFIResult<()> {
-        let mut handler = self.handler.lock()?;
-        let response = serde_json::from_str(&response)?;
+        block_on(self.handle_response_async(profile, response))
+    }
 
-        let connection = VcxConnection::try_from(handler.clone())?;
+    pub fn send_request(
+        &self,
+        profile: Arc<ProfileHolder>,
+        service_endpoint: String,
+        routing_keys: Vec<String>,
+    ) -> VcxUniFFIResult<()> {
+        block_on(self.send_request_async(profile, service_endpoint, routing_keys))
+    }
+
+    pub fn send_response(&self, profile: Arc<ProfileHolder>) -> VcxUniFFIResult<()> {
+        block_on(self.send_response_async(profile))
+    }
 
-        block_on(async {
-            let new_conn = connection
-                .handle_response(profile.inner.wallet(), response)
-                .await?;
-            *handler = VcxGenericConnection::from(new_conn);
+    pub fn send_ack(&self, profile: Arc<ProfileHolder>) -> VcxUniFFIResult<()> {
+        block_on(self.send_ack_async(profile))
+    }
 
-            Ok(())
-        })
+    pub fn send_message(
+        &self,
+        profile: Arc<ProfileHolder>,
+        message: String,
+    ) -> VcxUniFFIResult<()> {
+        block_on(self.send_message_async(profile, message))
     }
+}
 
-    pub fn send_request(
+// The guard is held for the whole operation, so that concurrent calls on the same connection run
+// one after the other instead of overwriting each other's state
+#[uniffi::export(async_runtime = "tokio")]
+impl Connection {
+    pub async fn accept_invitation_async(
+        &self,
+        profile: Arc<ProfileHolder>,
+        invitation: String,
+    ) -> VcxUniFFIResult<()> {
+        let invitation = serde_json::from_str(&invitation)?;
+        let mut handler = self.handler.lock().await;
+        let connection = VcxConnection::try_from(handler.clone())?;
+
+        let new_conn = connection
+            .accept_invitation(profile.inner.ledger_read(), invitation)
+            .await?;
+        *handler = VcxGenericConnection::from(new_conn);
+        Ok(())
+    }
+
+    pub async fn handle_request_async(
         &self,
         profile: Arc<ProfileHolder>,
+        request: String,
         service_endpoint: String,
         routing_keys: Vec<String>,
     ) -> VcxUniFFIResult<()> {
-        let mut handler = self.handler.lock()?;
-
+        let request = serde_json::from_str(&request)?;
+        let mut handler = self.handler.lock().await;
         let connection = VcxConnection::try_from(handler.clone())?;
         let url = Url::parse(&service_endpoint).map_err(|err| {
             AriesVcxError::from_msg(AriesVcxErrorKind::InvalidUrl, err.to_string())
         })?;
 
-        block_on(async {
-            let connection = connection.prepare_request(url, routing_keys).await?;
-            let request = connection.get_request().clone();
-            connection
-                .send_message(profile.inner.wallet(), &request.into(), &HttpClient)
-                .await?;
-            *handler = VcxGenericConnection::from(connection);
-            Ok(())
-        })
+        let new_conn = connection
+            .handle_request(profile.inner.wallet(), request, url, routing_keys)
+            .await?;
+        *handler = VcxGenericConnection::from(new_conn);
+        Ok(())
     }
 
-    pub fn send_response(&self, profile: Arc<ProfileHolder>) -> VcxUniFFIResult<()> {
-        let mut handler = self.handler.lock()?;
-
+    pub async fn handle_response_async(
+        &self,
+        profile: Arc<ProfileHolder>,
+        response: String,
+    ) -> VcxUniFFIResult<()> {
+        let response = serde_json::from_str(&response)?;
+        let mut handler = self.handler.lock().await;
         let connection = VcxConnection::try_from(handler.clone())?;
 
-        block_on(async {
-            let response = connection.get_connection_response_msg();
-            connection
-                .send_message(profile.inner.wallet(), &response.into(), &HttpClient)
-                .await?;
+        let new_conn = connection
+            .handle_response(profile.inner.wallet(), response)
+            .await?;
+        *handler = VcxGenericConnection::from(new_conn);
+        Ok(())
+    }
 
-            *handler = VcxGenericConnection::from(connection);
+    pub async fn send_request_async(
+        &self,
+        profile: Arc<ProfileHolder>,
+        service_endpoint: String,
+        routing_keys: Vec<String>,
+    ) -> VcxUniFFIResult<()> {
+        let mut handler = self.handler.lock().await;
+        let connection = VcxConnection::try_from(handler.clone())?;
+        let url = Url::parse(&service_endpoint).map_err(|err| {
+            AriesVcxError::from_msg(AriesVcxErrorKind::InvalidUrl, err.to_string())
+        })?;
 
-            Ok(())
-        })
+        let connection = connection.prepare_request(url, routing_keys).await?;
+        let request = connection.get_request().clone();
+        connection
+            .send_message(
+                profile.inner.wallet(),
+                &request.into(),
+                &profile.inner.transport(),
+            )
+            .await?;
+        *handler = VcxGenericConnection::from(connection);
+        Ok(())
     }
 
-    pub fn send_ack(&self, profile: Arc<ProfileHolder>) -> VcxUniFFIResult<()> {
-        let handler = self.handler.lock()?;
+    pub async fn send_response_async(&self, profile: Arc<ProfileHolder>) -> VcxUniFFIResult<()> {
+        let mut handler = self.handler.lock().await;
+        let connection = VcxConnection::try_from(handler.clone())?;
 
+        let response = connection.get_connection_response_msg();
+        connection
+            .send_message(
+                profile.inner.wallet(),
+                &response.into(),
+                &profile.inner.transport(),
+            )
+            .await?;
+        *handler = VcxGenericConnection::from(connection);
+        Ok(())
+    }
+
+    pub async fn send_ack_async(&self, profile: Arc<ProfileHolder>) -> VcxUniFFIResult<()> {
+        let handler = self.handler.lock().await;
         let connection = VcxConnection::try_from(handler.clone())?;
 
-        block_on(async {
-            connection
-                .send_message(
-                    profile.inner.wallet(),
-                    &connection.get_ack().into(),
-                    &HttpClient,
-                )
-                .await?;
-            Ok(())
-        })
+        connection
+            .send_message(
+                profile.inner.wallet(),
+                &connection.get_ack().into(),
+                &profile.inner.transport(),
+            )
+            .await?;
+        Ok(())
     }
 
-    pub fn send_message(
+    pub async fn send_message_async(
         &self,
         profile: Arc<ProfileHolder>,
         message: String,
     ) -> VcxUniFFIResult<()> {
         let message = serde_json::from_str(&message)?;
-        let handler = self.handler.lock()?.clone();
-
-        block_on(async {
-            handler
-                .send_message(profile.inner.wallet(), &message, &HttpClient)
-                .await?;
-            Ok(())
-        })
+        let handler = self.handler.lock().await;
+
+        handler
+            .send_message(profile.inner.wallet(), &message, &profile.inner.transport())
+            .await?;
+        Ok(())
     }
 }
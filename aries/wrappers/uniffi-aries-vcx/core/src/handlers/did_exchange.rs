@@ -0,0 +1,335 @@
+use std::sync::Arc;
+
+use aries_vcx::{
+    did_doc::schema::service::typed::ServiceType,
+    errors::error::{AriesVcxError, AriesVcxErrorKind},
+    handlers::out_of_band::receiver::OutOfBandReceiver as VcxOutOfBandReceiver,
+    messages::{
+        msg_fields::protocols::did_exchange::{
+            v1_0::DidExchangeV1_0,
+            v1_1::DidExchangeV1_1,
+            v1_x::{request::AnyRequest, response::AnyResponse},
+            DidExchange as DidExchangeMsg,
+        },
+        AriesMessage,
+    },
+    protocols::did_exchange::{
+        resolve_enc_key_from_did_doc, resolve_enc_key_from_invitation,
+        state_machine::{
+            generic::{GenericDidExchange, ThinState},
+            helpers::create_peer_did_4,
+            requester::helpers::{
+                invitation_get_acceptable_did_exchange_version, invitation_get_first_did_service,
+            },
+        },
+    },
+    transport::Transport,
+    utils::encryption_envelope::EncryptionEnvelope,
+};
+use public_key::{Key, KeyType};
+use tokio::sync::Mutex;
+use url::Url;
+
+use crate::{core::profile::ProfileHolder, errors::error::VcxUniFFIResult, runtime::block_on};
+
+pub struct DidExchange {
+    handler: Mutex<GenericDidExchange>,
+    thread_id: String,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum DidExchangeState {
+    RequestSent,
+    ResponseSent,
+    Completed,
+    Abandoned,
+}
+
+impl From<ThinState> for DidExchangeState {
+    fn from(x: ThinState) -> Self {
+        match x {
+            ThinState::RequestSent => DidExchangeState::RequestSent,
+            ThinState::ResponseSent => DidExchangeState::ResponseSent,
+            ThinState::Completed => DidExchangeState::Completed,
+            ThinState::Abandoned => DidExchangeState::Abandoned,
+        }
+    }
+}
+
+// seperate function since uniffi can't handle constructors with results
+/// Accepts an out-of-band invitation by sending a DID exchange request from a new did:peer:4
+/// reachable through the service endpoint
+pub fn create_did_exchange_requester(
+    profile: Arc<ProfileHolder>,
+    invitation: String,
+    service_endpoint: String,
+    routing_keys: Vec<String>,
+    label: String,
+) -> VcxUniFFIResult<Arc<DidExchange>> {
+    block_on(create_did_exchange_requester_async(
+        profile,
+        invitation,
+        service_endpoint,
+        routing_keys,
+        label,
+    ))
+}
+
+#[uniffi::export(async_runtime = "tokio")]
+pub async fn create_did_exchange_requester_async(
+    profile: Arc<ProfileHolder>,
+    invitation: String,
+    service_endpoint: String,
+    routing_keys: Vec<String>,
+    label: String,
+) -> VcxUniFFIResult<Arc<DidExchange>> {
+    let invitation = VcxOutOfBandReceiver::create_from_json_encoded_oob(&invitation)?.oob;
+    let url = Url::parse(&service_endpoint)
+        .map_err(|err| AriesVcxError::from_msg(AriesVcxErrorKind::InvalidUrl, err.to_string()))?;
+
+    let version = invitation_get_acceptable_did_exchange_version(&invitation)?;
+    let their_did = invitation_get_first_did_service(&invitation)?;
+    let (our_peer_did, _our_verkey) =
+        create_peer_did_4(profile.inner.wallet(), url, routing_keys).await?;
+
+    let (requester, request) = GenericDidExchange::construct_request(
+        profile.inner.resolver_registry(),
+        Some(invitation.id.clone()),
+        &their_did,
+        &our_peer_did,
+        label,
+        version,
+    )
+    .await?;
+    let thread_id = request_thread_id(&request);
+    send_pairwise(&profile, &requester, &request.into()).await?;
+
+    Ok(Arc::new(DidExchange {
+        handler: Mutex::new(requester),
+        thread_id,
+    }))
+}
+
+// seperate function since uniffi can't handle constructors with results
+/// Answers a DID exchange request with a response from a new did:peer:4 reachable through the
+/// service endpoint. The request is expected to be signed by the key it was sent to, or by the
+/// key of the invitation it answers if given.
+pub fn create_did_exchange_responder(
+    profile: Arc<ProfileHolder>,
+    request: String,
+    invitation: Option<String>,
+    recipient_verkey: String,
+    service_endpoint: String,
+    routing_keys: Vec<String>,
+) -> VcxUniFFIResult<Arc<DidExchange>> {
+    block_on(create_did_exchange_responder_async(
+        profile,
+        request,
+        invitation,
+        recipient_verkey,
+        service_endpoint,
+        routing_keys,
+    ))
+}
+
+#[uniffi::export(async_runtime = "tokio")]
+pub async fn create_did_exchange_responder_async(
+    profile: Arc<ProfileHolder>,
+    request: String,
+    invitation: Option<String>,
+    recipient_verkey: String,
+    service_endpoint: String,
+    routing_keys: Vec<String>,
+) -> VcxUniFFIResult<Arc<DidExchange>> {
+    let request = match serde_json::from_str(&request)? {
+        AriesMessage::DidExchange(DidExchangeMsg::V1_0(DidExchangeV1_0::Request(request))) => {
+            AnyRequest::V1_0(request)
+        }
+        AriesMessage::DidExchange(DidExchangeMsg::V1_1(DidExchangeV1_1::Request(request))) => {
+            AnyRequest::V1_1(request)
+        }
+        _ => {
+            return Err(AriesVcxError::from_msg(
+                AriesVcxErrorKind::InvalidMessageFormat,
+                "Expected a DID exchange request",
+            )
+            .into())
+        }
+    };
+    let invitation = invitation
+        .map(|invitation| VcxOutOfBandReceiver::create_from_json_encoded_oob(&invitation))
+        .transpose()?
+        .map(|receiver| receiver.oob);
+    let url = Url::parse(&service_endpoint)
+        .map_err(|err| AriesVcxError::from_msg(AriesVcxErrorKind::InvalidUrl, err.to_string()))?;
+
+    let invitation_key = match invitation {
+        Some(invitation) => {
+            resolve_enc_key_from_invitation(&invitation, profile.inner.resolver_registry()).await?
+        }
+        None => Key::from_base58(&recipient_verkey, KeyType::Ed25519)?,
+    };
+    let (our_peer_did, _our_verkey) =
+        create_peer_did_4(profile.inner.wallet(), url, routing_keys).await?;
+    let thread_id = request_thread_id(&request);
+
+    let (responder, response) = GenericDidExchange::handle_request(
+        profile.inner.wallet(),
+        profile.inner.resolver_registry(),
+        request,
+        &our_peer_did,
+        invitation_key,
+    )
+    .await?;
+    send_pairwise(&profile, &responder, &response.into()).await?;
+
+    Ok(Arc::new(DidExchange {
+        handler: Mutex::new(responder),
+        thread_id,
+    }))
+}
+
+impl DidExchange {
+    pub fn get_state(&self) -> VcxUniFFIResult<DidExchangeState> {
+        let handler = self.handler.blocking_lock();
+
+        Ok(DidExchangeState::from(handler.get_state()))
+    }
+
+    pub fn get_thread_id(&self) -> String {
+        self.thread_id.clone()
+    }
+
+    pub fn our_did(&self) -> VcxUniFFIResult<String> {
+        let handler = self.handler.blocking_lock();
+
+        Ok(handler.our_did_document().id().to_string())
+    }
+
+    pub fn their_did(&self) -> VcxUniFFIResult<String> {
+        let handler = self.handler.blocking_lock();
+
+        Ok(handler.their_did_doc().id().to_string())
+    }
+
+    /// Processes the response, complete or problem report received on the exchange. A response
+    /// completes the exchange as requester, sending the complete message to the responder.
+    pub fn process_aries_msg(
+        &self,
+        profile: Arc<ProfileHolder>,
+        message: String,
+    ) -> VcxUniFFIResult<()> {
+        block_on(self.process_aries_msg_async(profile, message))
+    }
+
+    /// Sends a message to the other party, encrypted for the DIDComm service of their DID
+    /// document
+    pub fn send_message(
+        &self,
+        profile: Arc<ProfileHolder>,
+        message: String,
+    ) -> VcxUniFFIResult<()> {
+        block_on(self.send_message_async(profile, message))
+    }
+}
+
+// The guard is held for the whole operation, so that concurrent calls on the same exchange run
+// one after the other instead of overwriting each other's state
+#[uniffi::export(async_runtime = "tokio")]
+impl DidExchange {
+    pub async fn process_aries_msg_async(
+        &self,
+        profile: Arc<ProfileHolder>,
+        message: String,
+    ) -> VcxUniFFIResult<()> {
+        let message = serde_json::from_str(&message)?;
+        let mut handler = self.handler.lock().await;
+        let did_exchange = handler.clone();
+
+        let did_exchange = match message {
+            AriesMessage::DidExchange(DidExchangeMsg::V1_0(DidExchangeV1_0::Response(
+                response,
+            ))) => handle_response(&profile, did_exchange, response.into()).await?,
+            AriesMessage::DidExchange(DidExchangeMsg::V1_1(DidExchangeV1_1::Response(
+                response,
+            ))) => handle_response(&profile, did_exchange, response.into()).await?,
+            AriesMessage::DidExchange(DidExchangeMsg::V1_0(DidExchangeV1_0::Complete(
+                complete,
+            )))
+            | AriesMessage::DidExchange(DidExchangeMsg::V1_1(DidExchangeV1_1::Complete(
+                complete,
+            ))) => did_exchange.handle_complete(complete)?,
+            AriesMessage::DidExchange(DidExchangeMsg::V1_0(DidExchangeV1_0::ProblemReport(
+                problem_report,
+            )))
+            | AriesMessage::DidExchange(DidExchangeMsg::V1_1(DidExchangeV1_1::ProblemReport(
+                problem_report,
+            ))) => did_exchange.handle_problem_report(problem_report)?,
+            _ => did_exchange,
+        };
+        *handler = did_exchange;
+        Ok(())
+    }
+
+    pub async fn send_message_async(
+        &self,
+        profile: Arc<ProfileHolder>,
+        message: String,
+    ) -> VcxUniFFIResult<()> {
+        let message = serde_json::from_str(&message)?;
+        let handler = self.handler.lock().await;
+
+        send_pairwise(&profile, &handler, &message).await
+    }
+}
+
+// A request starting its own thread carries no ~thread decorator, its @id is the thread id
+fn request_thread_id(request: &AnyRequest) -> String {
+    let request = request.inner();
+    request
+        .decorators
+        .thread
+        .as_ref()
+        .map_or(request.id.clone(), |thread| thread.thid.clone())
+}
+
+async fn handle_response(
+    profile: &ProfileHolder,
+    requester: GenericDidExchange,
+    response: AnyResponse,
+) -> VcxUniFFIResult<GenericDidExchange> {
+    let inviter_key = resolve_enc_key_from_did_doc(requester.their_did_doc())?;
+    let (requester, complete) = requester
+        .handle_response(
+            profile.inner.wallet(),
+            &inviter_key,
+            response,
+            profile.inner.resolver_registry(),
+        )
+        .await?;
+    send_pairwise(profile, &requester, &complete.into()).await?;
+    Ok(requester)
+}
+
+async fn send_pairwise(
+    profile: &ProfileHolder,
+    did_exchange: &GenericDidExchange,
+    message: &AriesMessage,
+) -> VcxUniFFIResult<()> {
+    let their_did_doc = did_exchange.their_did_doc();
+    let service = their_did_doc.get_service_of_type(&ServiceType::DIDCommV1)?;
+    let envelope = EncryptionEnvelope::create(
+        profile.inner.wallet(),
+        serde_json::to_string(message)?.as_bytes(),
+        did_exchange.our_did_document(),
+        their_did_doc,
+        service.id(),
+    )
+    .await?;
+    profile
+        .inner
+        .transport()
+        .send_message(envelope.0, service.service_endpoint())
+        .await?;
+    Ok(())
+}
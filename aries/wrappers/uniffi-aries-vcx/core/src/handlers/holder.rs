@@ -1,9 +1,10 @@
-use std::sync::{Arc, Mutex};
+use std::sync::Arc;
 
 use aries_vcx::{
     handlers::issuance::holder::Holder as VcxHolder, messages::AriesMessage,
     protocols::issuance::holder::state_machine::HolderState as VcxHolderState,
 };
+use tokio::sync::Mutex;
 
 use crate::{core::profile::ProfileHolder, errors::error::VcxUniFFIResult, runtime::block_on};
 pub struct Holder {
@@ -65,7 +66,7 @@ pub fn create_with_proposal(
 
 impl Holder {
     pub fn set_proposal(&self, credential_proposal: String) -> VcxUniFFIResult<()> {
-        let mut handler = self.handler.lock()?;
+        let mut handler = self.handler.blocking_lock();
 
         handler.set_proposal(serde_json::from_str(&credential_proposal)?)?;
         Ok(())
@@ -76,26 +77,11 @@ impl Holder {
         profile: Arc<ProfileHolder>,
         my_pw_did: String,
     ) -> VcxUniFFIResult<()> {
-        let mut handler = self.handler.lock()?;
-        let mut holder = handler.clone();
-
-        block_on(async {
-            holder
-                .prepare_credential_request(
-                    profile.inner.wallet(),
-                    profile.inner.ledger_read(),
-                    profile.inner.anoncreds(),
-                    my_pw_did.parse()?,
-                )
-                .await?;
-            *handler = holder;
-
-            Ok(())
-        })
+        block_on(self.prepare_credential_request_async(profile, my_pw_did))
     }
 
     pub fn get_msg_credential_request(&self) -> VcxUniFFIResult<String> {
-        let handler = self.handler.lock()?;
+        let handler = self.handler.blocking_lock();
 
         Ok(serde_json::to_string(&AriesMessage::from(
             handler.clone().get_msg_credential_request()?,
@@ -103,7 +89,7 @@ impl Holder {
     }
 
     pub fn decline_offer(&self, comment: Option<String>) -> VcxUniFFIResult<String> {
-        let mut handler = self.handler.lock()?;
+        let mut handler = self.handler.blocking_lock();
         let mut holder = handler.clone();
         let problem_report_result = &holder.decline_offer(comment.as_deref())?;
         *handler = holder;
@@ -116,104 +102,89 @@ impl Holder {
         profile: Arc<ProfileHolder>,
         credential: String,
     ) -> VcxUniFFIResult<()> {
-        let credential = serde_json::from_str(&credential)?;
-        let mut handler = self.handler.lock()?;
-        let mut holder = handler.clone();
-
-        block_on(async {
-            holder
-                .process_credential(
-                    profile.inner.wallet(),
-                    profile.inner.ledger_read(),
-                    profile.inner.anoncreds(),
-                    credential,
-                )
-                .await?;
-            *handler = holder;
-            Ok(())
-        })
+        block_on(self.process_credential_async(profile, credential))
     }
 
     pub fn is_terminal_state(&self) -> VcxUniFFIResult<bool> {
-        let handler = self.handler.lock()?;
+        let handler = self.handler.blocking_lock();
 
         Ok(handler.is_terminal_state())
     }
 
     pub fn get_state(&self) -> VcxUniFFIResult<HolderState> {
-        let handler = self.handler.lock()?;
+        let handler = self.handler.blocking_lock();
 
         Ok(HolderState::from(handler.get_state()))
     }
 
     pub fn get_source_id(&self) -> VcxUniFFIResult<String> {
-        let handler = self.handler.lock()?;
+        let handler = self.handler.blocking_lock();
 
         Ok(handler.get_source_id())
     }
 
     pub fn get_credential(&self) -> VcxUniFFIResult<String> {
-        let handler = self.handler.lock()?;
+        let handler = self.handler.blocking_lock();
         let credential = handler.get_credential()?;
         Ok(credential.0)
     }
 
     pub fn get_attributes(&self) -> VcxUniFFIResult<String> {
-        let handler = self.handler.lock()?;
+        let handler = self.handler.blocking_lock();
 
         Ok(handler.get_attributes()?)
     }
 
     pub fn get_attachment(&self) -> VcxUniFFIResult<String> {
-        let handler = self.handler.lock()?;
+        let handler = self.handler.blocking_lock();
 
         Ok(handler.get_attachment()?)
     }
 
     pub fn get_offer(&self) -> VcxUniFFIResult<String> {
-        let handler = self.handler.lock()?;
+        let handler = self.handler.blocking_lock();
 
         Ok(serde_json::to_string(&(handler.get_offer()?))?)
     }
 
     pub fn get_tails_location(&self) -> VcxUniFFIResult<String> {
-        let handler = self.handler.lock()?;
+        let handler = self.handler.blocking_lock();
 
         Ok(handler.get_tails_location()?)
     }
 
     pub fn get_tails_hash(&self) -> VcxUniFFIResult<String> {
-        let handler = self.handler.lock()?;
+        let handler = self.handler.blocking_lock();
 
         Ok(handler.get_tails_hash()?)
     }
 
     pub fn get_rev_reg_id(&self) -> VcxUniFFIResult<String> {
-        let handler = self.handler.lock()?;
+        let handler = self.handler.blocking_lock();
 
         Ok(handler.get_rev_reg_id()?)
     }
 
     pub fn get_cred_id(&self) -> VcxUniFFIResult<String> {
-        let handler = self.handler.lock()?;
+        let handler = self.handler.blocking_lock();
 
         Ok(handler.get_cred_id()?)
     }
 
     pub fn get_thread_id(&self) -> VcxUniFFIResult<String> {
-        let handler = self.handler.lock()?;
+        let handler = self.handler.blocking_lock();
 
         Ok(handler.get_thread_id()?)
     }
 
     pub fn is_revokable(&self, profile: Arc<ProfileHolder>) -> VcxUniFFIResult<bool> {
-        let handler = self.handler.lock()?.clone();
+        let handler = self.handler.blocking_lock().clone();
 
         block_on(async { Ok(handler.is_revokable(profile.inner.ledger_read()).await?) })
     }
 
     pub fn is_revoked(&self, profile: Arc<ProfileHolder>) -> VcxUniFFIResult<bool> {
-        let handler = self.handler.lock()?.clone();
+        let handler = self.handler.blocking_lock().clone();
 
         block_on(async {
             Ok(handler
@@ -227,7 +198,7 @@ impl Holder {
     }
 
     pub fn get_cred_rev_id(&self, profile: Arc<ProfileHolder>) -> VcxUniFFIResult<u32> {
-        let handler = self.handler.lock()?.clone();
+        let handler = self.handler.blocking_lock().clone();
 
         block_on(async {
             Ok(handler
@@ -237,14 +208,60 @@ impl Holder {
     }
 
     pub fn get_problem_report(&self) -> VcxUniFFIResult<String> {
-        let handler = self.handler.lock()?;
+        let handler = self.handler.blocking_lock();
 
         Ok(serde_json::to_string(&handler.get_problem_report()?)?)
     }
 
     pub fn get_final_message(&self) -> VcxUniFFIResult<Option<String>> {
-        let handler = self.handler.lock()?;
+        let handler = self.handler.blocking_lock();
 
         Ok(Some(serde_json::to_string(&handler.get_final_message()?)?))
     }
 }
+
+// The guard is held for the whole operation, so that concurrent calls on the same holder run
+// one after the other instead of overwriting each other's state
+#[uniffi::export(async_runtime = "tokio")]
+impl Holder {
+    pub async fn prepare_credential_request_async(
+        &self,
+        profile: Arc<ProfileHolder>,
+        my_pw_did: String,
+    ) -> VcxUniFFIResult<()> {
+        let mut handler = self.handler.lock().await;
+        let mut holder = handler.clone();
+
+        holder
+            .prepare_credential_request(
+                profile.inner.wallet(),
+                profile.inner.ledger_read(),
+                profile.inner.anoncreds(),
+                my_pw_did.parse()?,
+            )
+            .await?;
+        *handler = holder;
+        Ok(())
+    }
+
+    pub async fn process_credential_async(
+        &self,
+        profile: Arc<ProfileHolder>,
+        credential: String,
+    ) -> VcxUniFFIResult<()> {
+        let credential = serde_json::from_str(&credential)?;
+        let mut handler = self.handler.lock().await;
+        let mut holder = handler.clone();
+
+        holder
+            .process_credential(
+                profile.inner.wallet(),
+                profile.inner.ledger_read(),
+                profile.inner.anoncreds(),
+                credential,
+            )
+            .await?;
+        *handler = holder;
+        Ok(())
+    }
+}
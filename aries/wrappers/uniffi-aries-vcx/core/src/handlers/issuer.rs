@@ -0,0 +1,176 @@
+use std::sync::Arc;
+
+use aries_vcx::{
+    handlers::issuance::issuer::Issuer as VcxIssuer, messages::AriesMessage,
+    protocols::issuance::issuer::state_machine::IssuerState as VcxIssuerState,
+};
+use tokio::sync::Mutex;
+
+use crate::{core::profile::ProfileHolder, errors::error::VcxUniFFIResult, runtime::block_on};
+
+pub struct Issuer {
+    handler: Mutex<VcxIssuer>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum IssuerState {
+    Initial,
+    OfferSet,
+    ProposalReceived,
+    RequestReceived,
+    CredentialSet,
+    Finished,
+    Failed,
+}
+
+impl From<VcxIssuerState> for IssuerState {
+    fn from(x: VcxIssuerState) -> Self {
+        match x {
+            VcxIssuerState::Initial => IssuerState::Initial,
+            VcxIssuerState::OfferSet => IssuerState::OfferSet,
+            VcxIssuerState::ProposalReceived => IssuerState::ProposalReceived,
+            VcxIssuerState::RequestReceived => IssuerState::RequestReceived,
+            VcxIssuerState::CredentialSet => IssuerState::CredentialSet,
+            VcxIssuerState::Finished => IssuerState::Finished,
+            VcxIssuerState::Failed => IssuerState::Failed,
+        }
+    }
+}
+
+pub fn create_issuer(source_id: String) -> VcxUniFFIResult<Arc<Issuer>> {
+    let handler = Mutex::new(VcxIssuer::create(&source_id)?);
+    Ok(Arc::new(Issuer { handler }))
+}
+
+pub fn create_issuer_from_proposal(
+    source_id: String,
+    credential_proposal: String,
+) -> VcxUniFFIResult<Arc<Issuer>> {
+    let credential_proposal = serde_json::from_str(&credential_proposal)?;
+    let handler = Mutex::new(VcxIssuer::create_from_proposal(
+        &source_id,
+        &credential_proposal,
+    )?);
+    Ok(Arc::new(Issuer { handler }))
+}
+
+impl Issuer {
+    pub fn get_state(&self) -> VcxUniFFIResult<IssuerState> {
+        let handler = self.handler.blocking_lock();
+
+        Ok(IssuerState::from(handler.get_state()))
+    }
+
+    pub fn get_source_id(&self) -> VcxUniFFIResult<String> {
+        let handler = self.handler.blocking_lock();
+
+        Ok(handler.get_source_id()?)
+    }
+
+    pub fn get_thread_id(&self) -> VcxUniFFIResult<String> {
+        let handler = self.handler.blocking_lock();
+
+        Ok(handler.get_thread_id()?)
+    }
+
+    pub fn is_terminal_state(&self) -> VcxUniFFIResult<bool> {
+        let handler = self.handler.blocking_lock();
+
+        Ok(handler.is_terminal_state())
+    }
+
+    // NOTE : offer_info is the JSON of an OfferInfo, holding the credential values and the id of
+    // the credential definition to issue from
+    pub fn build_credential_offer_msg(
+        &self,
+        profile: Arc<ProfileHolder>,
+        offer_info: String,
+        comment: Option<String>,
+    ) -> VcxUniFFIResult<()> {
+        block_on(self.build_credential_offer_msg_async(profile, offer_info, comment))
+    }
+
+    /// Returns the credential offer for the app to send to the holder
+    pub fn get_credential_offer_msg(&self) -> VcxUniFFIResult<String> {
+        let handler = self.handler.blocking_lock();
+
+        Ok(serde_json::to_string(&handler.get_credential_offer_msg()?)?)
+    }
+
+    pub fn process_aries_msg(&self, message: String) -> VcxUniFFIResult<()> {
+        block_on(self.process_aries_msg_async(message))
+    }
+
+    pub fn build_credential(&self, profile: Arc<ProfileHolder>) -> VcxUniFFIResult<()> {
+        block_on(self.build_credential_async(profile))
+    }
+
+    /// Returns the credential for the app to send to the holder
+    pub fn get_msg_issue_credential(&self) -> VcxUniFFIResult<String> {
+        let mut handler = self.handler.blocking_lock();
+
+        Ok(serde_json::to_string(&AriesMessage::from(
+            handler.get_msg_issue_credential()?,
+        ))?)
+    }
+
+    pub fn get_rev_reg_id(&self) -> VcxUniFFIResult<String> {
+        let handler = self.handler.blocking_lock();
+
+        Ok(handler.get_rev_reg_id()?)
+    }
+
+    pub fn get_problem_report(&self) -> VcxUniFFIResult<String> {
+        let handler = self.handler.blocking_lock();
+
+        Ok(serde_json::to_string(&handler.get_problem_report()?)?)
+    }
+}
+
+// The guard is held for the whole operation, so that concurrent calls on the same issuer run one
+// after the other instead of overwriting each other's state
+#[uniffi::export(async_runtime = "tokio")]
+impl Issuer {
+    pub async fn build_credential_offer_msg_async(
+        &self,
+        profile: Arc<ProfileHolder>,
+        offer_info: String,
+        comment: Option<String>,
+    ) -> VcxUniFFIResult<()> {
+        let offer_info = serde_json::from_str(&offer_info)?;
+        let mut handler = self.handler.lock().await;
+        let mut issuer = handler.clone();
+
+        issuer
+            .build_credential_offer_msg(
+                profile.inner.wallet(),
+                profile.inner.anoncreds(),
+                offer_info,
+                comment,
+            )
+            .await?;
+        *handler = issuer;
+        Ok(())
+    }
+
+    pub async fn process_aries_msg_async(&self, message: String) -> VcxUniFFIResult<()> {
+        let message = serde_json::from_str(&message)?;
+        let mut handler = self.handler.lock().await;
+        let mut issuer = handler.clone();
+
+        issuer.process_aries_msg(message).await?;
+        *handler = issuer;
+        Ok(())
+    }
+
+    pub async fn build_credential_async(&self, profile: Arc<ProfileHolder>) -> VcxUniFFIResult<()> {
+        let mut handler = self.handler.lock().await;
+        let mut issuer = handler.clone();
+
+        issuer
+            .build_credential(profile.inner.wallet(), profile.inner.anoncreds())
+            .await?;
+        *handler = issuer;
+        Ok(())
+    }
+}
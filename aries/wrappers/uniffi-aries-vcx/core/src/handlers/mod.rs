@@ -1,2 +1,7 @@
 pub mod connection;
+pub mod did_exchange;
 pub mod holder;
+pub mod issuer;
+pub mod out_of_band;
+pub mod prover;
+pub mod verifier;
@@ -0,0 +1,100 @@
+use std::sync::Arc;
+
+use aries_vcx::{
+    errors::error::{AriesVcxError, AriesVcxErrorKind},
+    handlers::out_of_band::{
+        receiver::OutOfBandReceiver as VcxOutOfBandReceiver,
+        sender::OutOfBandSender as VcxOutOfBandSender,
+    },
+    messages::{
+        msg_fields::protocols::out_of_band::invitation::OobService,
+        msg_types::{
+            protocols::did_exchange::{DidExchangeType, DidExchangeTypeV1},
+            Protocol,
+        },
+    },
+    protocols::did_exchange::state_machine::helpers::create_peer_did_4,
+};
+use url::Url;
+
+use crate::{core::profile::ProfileHolder, errors::error::VcxUniFFIResult, runtime::block_on};
+
+pub struct OutOfBandSender {
+    handler: VcxOutOfBandSender,
+}
+
+pub struct OutOfBandReceiver {
+    handler: VcxOutOfBandReceiver,
+}
+
+// seperate function since uniffi can't handle constructors with results
+/// Invitation to a DID exchange with a new did:peer:4 reachable through the service endpoint
+pub fn create_oob_sender(
+    profile: Arc<ProfileHolder>,
+    service_endpoint: String,
+    routing_keys: Vec<String>,
+    label: Option<String>,
+) -> VcxUniFFIResult<Arc<OutOfBandSender>> {
+    let url = Url::parse(&service_endpoint)
+        .map_err(|err| AriesVcxError::from_msg(AriesVcxErrorKind::InvalidUrl, err.to_string()))?;
+
+    block_on(async {
+        let (peer_did, _our_verkey) =
+            create_peer_did_4(profile.inner.wallet(), url, routing_keys).await?;
+        let mut sender = VcxOutOfBandSender::create()
+            .append_service(&OobService::Did(peer_did.to_string()))
+            .append_handshake_protocol(Protocol::DidExchangeType(DidExchangeType::V1(
+                DidExchangeTypeV1::new_v1_1(),
+            )))?;
+        if let Some(label) = label {
+            sender = sender.set_label(&label);
+        }
+        Ok(Arc::new(OutOfBandSender { handler: sender }))
+    })
+}
+
+// seperate function since uniffi can't handle constructors with results
+pub fn create_oob_receiver(invitation: String) -> VcxUniFFIResult<Arc<OutOfBandReceiver>> {
+    let handler = VcxOutOfBandReceiver::create_from_json_encoded_oob(&invitation)?;
+    Ok(Arc::new(OutOfBandReceiver { handler }))
+}
+
+// seperate function since uniffi can't handle constructors with results
+pub fn create_oob_receiver_from_url(url: String) -> VcxUniFFIResult<Arc<OutOfBandReceiver>> {
+    let handler = VcxOutOfBandReceiver::create_from_url_encoded_oob(&url)?;
+    Ok(Arc::new(OutOfBandReceiver { handler }))
+}
+
+impl OutOfBandSender {
+    pub fn get_id(&self) -> String {
+        self.handler.get_id()
+    }
+
+    pub fn invitation_to_json_string(&self) -> String {
+        self.handler.invitation_to_json_string()
+    }
+
+    pub fn invitation_to_url(&self, domain_path: String) -> VcxUniFFIResult<String> {
+        Ok(self.handler.invitation_to_url(&domain_path)?.to_string())
+    }
+}
+
+impl OutOfBandReceiver {
+    pub fn get_id(&self) -> String {
+        self.handler.get_id()
+    }
+
+    pub fn invitation_to_json_string(&self) -> String {
+        self.handler.invitation_to_json_string()
+    }
+
+    /// The message attached to the invitation, such as a credential offer or presentation
+    /// request, if any
+    pub fn extract_a2a_message(&self) -> VcxUniFFIResult<Option<String>> {
+        Ok(self
+            .handler
+            .extract_a2a_message()?
+            .map(|message| serde_json::to_string(&message))
+            .transpose()?)
+    }
+}
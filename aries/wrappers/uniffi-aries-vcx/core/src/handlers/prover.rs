@@ -0,0 +1,178 @@
+use std::{collections::HashMap, sync::Arc};
+
+use aries_vcx::{
+    handlers::proof_presentation::prover::Prover as VcxProver, messages::AriesMessage,
+    protocols::proof_presentation::prover::state_machine::ProverState as VcxProverState,
+};
+use tokio::sync::Mutex;
+
+use crate::{core::profile::ProfileHolder, errors::error::VcxUniFFIResult, runtime::block_on};
+
+pub struct Prover {
+    handler: Mutex<VcxProver>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ProverState {
+    Initial,
+    PresentationProposalSent,
+    PresentationRequestReceived,
+    PresentationPrepared,
+    PresentationPreparationFailed,
+    PresentationSent,
+    Finished,
+    Failed,
+}
+
+impl From<VcxProverState> for ProverState {
+    fn from(x: VcxProverState) -> Self {
+        match x {
+            VcxProverState::Initial => ProverState::Initial,
+            VcxProverState::PresentationProposalSent => ProverState::PresentationProposalSent,
+            VcxProverState::PresentationRequestReceived => ProverState::PresentationRequestReceived,
+            VcxProverState::PresentationPrepared => ProverState::PresentationPrepared,
+            VcxProverState::PresentationPreparationFailed => {
+                ProverState::PresentationPreparationFailed
+            }
+            VcxProverState::PresentationSent => ProverState::PresentationSent,
+            VcxProverState::Finished => ProverState::Finished,
+            VcxProverState::Failed => ProverState::Failed,
+        }
+    }
+}
+
+pub fn create_prover_from_request(
+    source_id: String,
+    presentation_request: String,
+) -> VcxUniFFIResult<Arc<Prover>> {
+    let presentation_request = serde_json::from_str(&presentation_request)?;
+    let handler = Mutex::new(VcxProver::create_from_request(
+        &source_id,
+        presentation_request,
+    )?);
+    Ok(Arc::new(Prover { handler }))
+}
+
+impl Prover {
+    pub fn get_state(&self) -> VcxUniFFIResult<ProverState> {
+        let handler = self.handler.blocking_lock();
+
+        Ok(ProverState::from(handler.get_state()))
+    }
+
+    pub fn get_thread_id(&self) -> VcxUniFFIResult<String> {
+        let handler = self.handler.blocking_lock();
+
+        Ok(handler.get_thread_id()?)
+    }
+
+    pub fn get_presentation_request(&self) -> VcxUniFFIResult<String> {
+        let handler = self.handler.blocking_lock();
+
+        Ok(handler.presentation_request_data()?)
+    }
+
+    /// Credentials in the wallet matching each referent of the presentation request
+    pub fn retrieve_credentials(&self, profile: Arc<ProfileHolder>) -> VcxUniFFIResult<String> {
+        block_on(self.retrieve_credentials_async(profile))
+    }
+
+    // NOTE : selected_credentials is the JSON of a SelectedCredentials, built from the output of
+    // retrieve_credentials by picking a credential for each referent
+    pub fn generate_presentation(
+        &self,
+        profile: Arc<ProfileHolder>,
+        selected_credentials: String,
+        self_attested_attributes: HashMap<String, String>,
+    ) -> VcxUniFFIResult<()> {
+        block_on(self.generate_presentation_async(
+            profile,
+            selected_credentials,
+            self_attested_attributes,
+        ))
+    }
+
+    pub fn get_presentation_msg(&self) -> VcxUniFFIResult<String> {
+        let handler = self.handler.blocking_lock();
+
+        Ok(serde_json::to_string(&AriesMessage::from(
+            handler.get_presentation_msg()?,
+        ))?)
+    }
+
+    /// Returns the message to send to the verifier, the presentation or a problem report if it
+    /// could not be generated
+    pub fn mark_presentation_sent(&self) -> VcxUniFFIResult<String> {
+        let mut handler = self.handler.blocking_lock();
+        let mut prover = handler.clone();
+        let message = prover.mark_presentation_sent()?;
+        *handler = prover;
+
+        Ok(serde_json::to_string(&message)?)
+    }
+
+    pub fn process_aries_msg(&self, message: String) -> VcxUniFFIResult<()> {
+        let message = serde_json::from_str(&message)?;
+        let mut handler = self.handler.blocking_lock();
+        let mut prover = handler.clone();
+
+        block_on(async {
+            prover.process_aries_msg(message).await?;
+            *handler = prover;
+            Ok(())
+        })
+    }
+
+    pub fn decline_presentation_request(&self, reason: String) -> VcxUniFFIResult<String> {
+        let mut handler = self.handler.blocking_lock();
+        let mut prover = handler.clone();
+
+        block_on(async {
+            let problem_report = prover
+                .decline_presentation_request(Some(reason), None)
+                .await?;
+            *handler = prover;
+            Ok(serde_json::to_string(&problem_report)?)
+        })
+    }
+}
+
+// The guard is held for the whole operation, so that concurrent calls on the same prover run
+// one after the other instead of overwriting each other's state
+#[uniffi::export(async_runtime = "tokio")]
+impl Prover {
+    pub async fn retrieve_credentials_async(
+        &self,
+        profile: Arc<ProfileHolder>,
+    ) -> VcxUniFFIResult<String> {
+        let prover = self.handler.lock().await;
+
+        let credentials = prover
+            .retrieve_credentials(profile.inner.wallet(), profile.inner.anoncreds())
+            .await?;
+        Ok(serde_json::to_string(&credentials)?)
+    }
+
+    pub async fn generate_presentation_async(
+        &self,
+        profile: Arc<ProfileHolder>,
+        selected_credentials: String,
+        self_attested_attributes: HashMap<String, String>,
+    ) -> VcxUniFFIResult<()> {
+        let selected_credentials = serde_json::from_str(&selected_credentials)?;
+        let mut handler = self.handler.lock().await;
+        let mut prover = handler.clone();
+
+        prover
+            .generate_presentation(
+                profile.inner.wallet(),
+                profile.inner.ledger_read(),
+                profile.inner.anoncreds(),
+                selected_credentials,
+                self_attested_attributes,
+            )
+            .await?;
+        *handler = prover;
+        Ok(())
+    }
+}
@@ -0,0 +1,229 @@
+use std::sync::Arc;
+
+use aries_vcx::{
+    handlers::proof_presentation::verifier::Verifier as VcxVerifier,
+    messages::AriesMessage,
+    protocols::proof_presentation::verifier::{
+        state_machine::VerifierState as VcxVerifierState,
+        verification_status::PresentationVerificationStatus as VcxPresentationVerificationStatus,
+    },
+};
+use tokio::sync::Mutex;
+
+use crate::{core::profile::ProfileHolder, errors::error::VcxUniFFIResult, runtime::block_on};
+
+pub struct Verifier {
+    handler: Mutex<VcxVerifier>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum VerifierState {
+    Initial,
+    PresentationProposalReceived,
+    PresentationRequestSet,
+    PresentationRequestSent,
+    Finished,
+    Failed,
+}
+
+impl From<VcxVerifierState> for VerifierState {
+    fn from(x: VcxVerifierState) -> Self {
+        match x {
+            VcxVerifierState::Initial => VerifierState::Initial,
+            VcxVerifierState::PresentationProposalReceived => {
+                VerifierState::PresentationProposalReceived
+            }
+            VcxVerifierState::PresentationRequestSet => VerifierState::PresentationRequestSet,
+            VcxVerifierState::PresentationRequestSent => VerifierState::PresentationRequestSent,
+            VcxVerifierState::Finished => VerifierState::Finished,
+            VcxVerifierState::Failed => VerifierState::Failed,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum PresentationVerificationStatus {
+    Valid,
+    Invalid,
+    Unavailable,
+}
+
+impl From<VcxPresentationVerificationStatus> for PresentationVerificationStatus {
+    fn from(x: VcxPresentationVerificationStatus) -> Self {
+        match x {
+            VcxPresentationVerificationStatus::Valid => PresentationVerificationStatus::Valid,
+            VcxPresentationVerificationStatus::Invalid => PresentationVerificationStatus::Invalid,
+            VcxPresentationVerificationStatus::Unavailable => {
+                PresentationVerificationStatus::Unavailable
+            }
+        }
+    }
+}
+
+pub fn create_verifier_from_request(
+    source_id: String,
+    presentation_request: String,
+) -> VcxUniFFIResult<Arc<Verifier>> {
+    let presentation_request = serde_json::from_str(&presentation_request)?;
+    let handler = Mutex::new(VcxVerifier::create_from_request(
+        source_id,
+        &presentation_request,
+    )?);
+    Ok(Arc::new(Verifier { handler }))
+}
+
+pub fn create_verifier_from_proposal(
+    source_id: String,
+    presentation_proposal: String,
+) -> VcxUniFFIResult<Arc<Verifier>> {
+    let presentation_proposal = serde_json::from_str(&presentation_proposal)?;
+    let handler = Mutex::new(VcxVerifier::create_from_proposal(
+        &source_id,
+        &presentation_proposal,
+    )?);
+    Ok(Arc::new(Verifier { handler }))
+}
+
+impl Verifier {
+    pub fn get_state(&self) -> VcxUniFFIResult<VerifierState> {
+        let handler = self.handler.blocking_lock();
+
+        Ok(VerifierState::from(handler.get_state()))
+    }
+
+    pub fn get_thread_id(&self) -> VcxUniFFIResult<String> {
+        let handler = self.handler.blocking_lock();
+
+        Ok(handler.get_thread_id()?)
+    }
+
+    // NOTE : presentation_request is the JSON of a PresentationRequest, set on a verifier created
+    // from a proposal to answer it
+    pub fn set_presentation_request(
+        &self,
+        presentation_request: String,
+        comment: Option<String>,
+    ) -> VcxUniFFIResult<()> {
+        let mut handler = self.handler.blocking_lock();
+
+        handler.set_presentation_request(serde_json::from_str(&presentation_request)?, comment)?;
+        Ok(())
+    }
+
+    /// Returns the presentation request for the app to send to the prover
+    pub fn mark_presentation_request_sent(&self) -> VcxUniFFIResult<String> {
+        let mut handler = self.handler.blocking_lock();
+        let mut verifier = handler.clone();
+        let request = verifier.mark_presentation_request_sent()?;
+        *handler = verifier;
+
+        Ok(serde_json::to_string(&AriesMessage::from(request))?)
+    }
+
+    pub fn get_presentation_request_msg(&self) -> VcxUniFFIResult<String> {
+        let handler = self.handler.blocking_lock();
+
+        Ok(serde_json::to_string(&AriesMessage::from(
+            handler.get_presentation_request_msg()?,
+        ))?)
+    }
+
+    /// Returns the ack, or the problem report if the presentation is invalid, for the app to send
+    /// to the prover
+    pub fn verify_presentation(
+        &self,
+        profile: Arc<ProfileHolder>,
+        presentation: String,
+    ) -> VcxUniFFIResult<String> {
+        block_on(self.verify_presentation_async(profile, presentation))
+    }
+
+    pub fn get_verification_status(&self) -> VcxUniFFIResult<PresentationVerificationStatus> {
+        let handler = self.handler.blocking_lock();
+
+        Ok(PresentationVerificationStatus::from(
+            handler.get_verification_status(),
+        ))
+    }
+
+    pub fn get_presentation_attachment(&self) -> VcxUniFFIResult<String> {
+        let handler = self.handler.blocking_lock();
+
+        Ok(handler.get_presentation_attachment()?)
+    }
+
+    pub fn get_presentation_msg(&self) -> VcxUniFFIResult<String> {
+        let handler = self.handler.blocking_lock();
+
+        Ok(serde_json::to_string(&AriesMessage::from(
+            handler.get_presentation_msg()?,
+        ))?)
+    }
+
+    /// Returns the message to send back to the prover, if any
+    pub fn process_aries_msg(
+        &self,
+        profile: Arc<ProfileHolder>,
+        message: String,
+    ) -> VcxUniFFIResult<Option<String>> {
+        block_on(self.process_aries_msg_async(profile, message))
+    }
+
+    pub fn decline_presentation_proposal(&self, reason: String) -> VcxUniFFIResult<String> {
+        let mut handler = self.handler.blocking_lock();
+        let mut verifier = handler.clone();
+
+        block_on(async {
+            let problem_report = verifier.decline_presentation_proposal(&reason).await?;
+            *handler = verifier;
+            Ok(serde_json::to_string(&AriesMessage::from(problem_report))?)
+        })
+    }
+}
+
+// The guard is held for the whole operation, so that concurrent calls on the same verifier run
+// one after the other instead of overwriting each other's state
+#[uniffi::export(async_runtime = "tokio")]
+impl Verifier {
+    pub async fn verify_presentation_async(
+        &self,
+        profile: Arc<ProfileHolder>,
+        presentation: String,
+    ) -> VcxUniFFIResult<String> {
+        let presentation = serde_json::from_str(&presentation)?;
+        let mut handler = self.handler.lock().await;
+        let mut verifier = handler.clone();
+
+        let message = verifier
+            .verify_presentation(
+                profile.inner.ledger_read(),
+                profile.inner.anoncreds(),
+                presentation,
+            )
+            .await?;
+        *handler = verifier;
+        Ok(serde_json::to_string(&message)?)
+    }
+
+    pub async fn process_aries_msg_async(
+        &self,
+        profile: Arc<ProfileHolder>,
+        message: String,
+    ) -> VcxUniFFIResult<Option<String>> {
+        let message = serde_json::from_str(&message)?;
+        let mut handler = self.handler.lock().await;
+        let mut verifier = handler.clone();
+
+        let reply = verifier
+            .process_aries_msg(
+                profile.inner.ledger_read(),
+                profile.inner.anoncreds(),
+                message,
+            )
+            .await?;
+        *handler = verifier;
+        Ok(reply
+            .map(|reply| serde_json::to_string(&reply))
+            .transpose()?)
+    }
+}
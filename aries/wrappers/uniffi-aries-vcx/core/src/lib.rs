@@ -12,10 +12,12 @@ use aries_vcx::{
     },
     protocols::connection::pairwise_info::PairwiseInfo,
 };
-use handlers::{connection::*, holder::*};
+use handlers::{
+    connection::*, did_exchange::*, holder::*, issuer::*, out_of_band::*, prover::*, verifier::*,
+};
 
 use crate::{
-    core::{anoncreds::*, profile::*, unpack_message::*},
+    core::{anoncreds::*, profile::*, transport::*, unpack_message::*},
     errors::error::*,
-    profile::new_indy_profile,
+    profile::{new_indy_profile, new_indy_profile_with_transport},
 };
@@ -0,0 +1,97 @@
+import kotlinx.coroutines.runBlocking
+import org.hyperledger.ariesvcx.*
+
+// Keeps the messages sent instead of posting them, so both parties can run in this script
+class CapturingTransport : Transport {
+    val messages = mutableListOf<String>()
+
+    override suspend fun sendMessage(msg: List<UByte>, serviceEndpoint: String) {
+        messages.add(msg.map { it.toByte() }.toByteArray().decodeToString())
+    }
+}
+
+// Fails every send, to check errors raised by the app come back through the library
+class FailingTransport : Transport {
+    override suspend fun sendMessage(msg: List<UByte>, serviceEndpoint: String) {
+        throw RuntimeException("offline")
+    }
+}
+
+val walletConfig = AskarWalletConfig(
+    "sqlite://:memory:",
+    KeyMethod.DeriveKey(AskarKdfMethod.Argon2i(ArgonLevel.INTERACTIVE)),
+    "test-pass-key",
+    "default",
+)
+val transport = CapturingTransport()
+val profile = newIndyProfileWithTransport(walletConfig, System.getenv("GENESIS_FILE"), transport)
+
+// credentials
+assert(getCredentials(profile) == "[]")
+
+// out-of-band
+val sender = createOobSender(profile, "http://localhost:8080", listOf(), "alice")
+val receiver = createOobReceiver(sender.invitationToJsonString())
+assert(receiver.getId() == sender.getId())
+assert(receiver.extractA2aMessage() == null)
+val receiverFromUrl = createOobReceiverFromUrl(sender.invitationToUrl("http://localhost:8080"))
+assert(receiverFromUrl.getId() == sender.getId())
+
+// issuance
+val issuer = createIssuer("issuer")
+assert(issuer.getState() == IssuerState.INITIAL)
+assert(!issuer.isTerminalState())
+
+// present proof
+val presentationRequest = """
+    {
+        "name": "proof",
+        "version": "1.0",
+        "nonce": "1234567890",
+        "requested_attributes": { "attr_name": { "name": "name" } },
+        "requested_predicates": {}
+    }
+"""
+val verifier = createVerifierFromRequest("verifier", presentationRequest)
+assert(verifier.getState() == VerifierState.PRESENTATION_REQUEST_SET)
+val requestMessage = verifier.markPresentationRequestSent()
+assert(verifier.getState() == VerifierState.PRESENTATION_REQUEST_SENT)
+assert(verifier.getVerificationStatus() == PresentationVerificationStatus.UNAVAILABLE)
+
+val prover = createProverFromRequest("prover", requestMessage)
+assert(prover.getState() == ProverState.PRESENTATION_REQUEST_RECEIVED)
+assert(prover.getThreadId() == verifier.getThreadId())
+prover.retrieveCredentials(profile)
+
+prover.declinePresentationRequest("no credentials")
+assert(prover.getState() == ProverState.FAILED)
+
+// did exchange, both parties sharing the profile
+val invitation = sender.invitationToJsonString()
+val requester = createDidExchangeRequester(profile, invitation, "http://localhost:8080", listOf(), "bob")
+assert(requester.getState() == DidExchangeState.REQUEST_SENT)
+val request = unpackMessage(profile, transport.messages.removeAt(0))
+
+val responder = createDidExchangeResponder(
+    profile, request.message, invitation, request.recipientVerkey, "http://localhost:8080", listOf()
+)
+assert(responder.getThreadId() == requester.getThreadId())
+val response = unpackMessage(profile, transport.messages.removeAt(0))
+
+runBlocking { requester.processAriesMsgAsync(profile, response.message) }
+assert(requester.getState() == DidExchangeState.COMPLETED)
+val complete = unpackMessage(profile, transport.messages.removeAt(0))
+
+responder.processAriesMsg(profile, complete.message)
+assert(responder.getState() == DidExchangeState.COMPLETED)
+assert(requester.theirDid() == responder.ourDid())
+assert(responder.theirDid() == requester.ourDid())
+
+// a failure of the transport fails the call that sent the message
+val offlineProfile = newIndyProfileWithTransport(walletConfig, System.getenv("GENESIS_FILE"), FailingTransport())
+val failure = runCatching {
+    runBlocking {
+        createDidExchangeRequesterAsync(offlineProfile, invitation, "http://localhost:8080", listOf(), "carol")
+    }
+}.exceptionOrNull()
+assert(failure is VcxUniFfiException.AriesVcxException)
@@ -0,0 +1,123 @@
+import Foundation
+import vcx
+
+// Keeps the messages sent instead of posting them, so both parties can run in this script
+class CapturingTransport: Transport {
+    var messages: [String] = []
+
+    func sendMessage(msg: [UInt8], serviceEndpoint: String) async throws {
+        messages.append(String(decoding: msg, as: UTF8.self))
+    }
+}
+
+// Fails every send, to check errors raised by the app come back through the library
+class FailingTransport: Transport {
+    struct Offline: Error {}
+
+    func sendMessage(msg: [UInt8], serviceEndpoint: String) async throws {
+        throw Offline()
+    }
+}
+
+let walletConfig = AskarWalletConfig(
+    dbUrl: "sqlite://:memory:",
+    keyMethod: .deriveKey(inner: .argon2i(inner: .interactive)),
+    passKey: "test-pass-key",
+    profile: "default"
+)
+let transport = CapturingTransport()
+let profile = try! newIndyProfileWithTransport(
+    walletConfig: walletConfig,
+    genesisFilePath: ProcessInfo.processInfo.environment["GENESIS_FILE"]!,
+    transport: transport
+)
+
+// credentials
+assert(try! getCredentials(profile: profile) == "[]")
+
+// out-of-band
+let sender = try! createOobSender(
+    profile: profile, serviceEndpoint: "http://localhost:8080", routingKeys: [], label: "alice"
+)
+let receiver = try! createOobReceiver(invitation: sender.invitationToJsonString())
+assert(receiver.getId() == sender.getId())
+assert(try! receiver.extractA2aMessage() == nil)
+let receiverFromUrl = try! createOobReceiverFromUrl(
+    url: try! sender.invitationToUrl(domainPath: "http://localhost:8080")
+)
+assert(receiverFromUrl.getId() == sender.getId())
+
+// issuance
+let issuer = try! createIssuer(sourceId: "issuer")
+assert(try! issuer.getState() == .initial)
+assert(try! !issuer.isTerminalState())
+
+// present proof
+let presentationRequest = """
+    {
+        "name": "proof",
+        "version": "1.0",
+        "nonce": "1234567890",
+        "requested_attributes": { "attr_name": { "name": "name" } },
+        "requested_predicates": {}
+    }
+    """
+let verifier = try! createVerifierFromRequest(
+    sourceId: "verifier", presentationRequest: presentationRequest
+)
+assert(try! verifier.getState() == .presentationRequestSet)
+let requestMessage = try! verifier.markPresentationRequestSent()
+assert(try! verifier.getState() == .presentationRequestSent)
+assert(try! verifier.getVerificationStatus() == .unavailable)
+
+let prover = try! createProverFromRequest(sourceId: "prover", presentationRequest: requestMessage)
+assert(try! prover.getState() == .presentationRequestReceived)
+assert(try! prover.getThreadId() == verifier.getThreadId())
+_ = try! prover.retrieveCredentials(profile: profile)
+_ = try! await prover.retrieveCredentialsAsync(profile: profile)
+
+_ = try! prover.declinePresentationRequest(reason: "no credentials")
+assert(try! prover.getState() == .failed)
+
+// did exchange, both parties sharing the profile
+let invitation = sender.invitationToJsonString()
+let requester = try! createDidExchangeRequester(
+    profile: profile, invitation: invitation, serviceEndpoint: "http://localhost:8080",
+    routingKeys: [], label: "bob"
+)
+assert(try! requester.getState() == .requestSent)
+let request = try! unpackMessage(profile: profile, packedMsg: transport.messages.removeFirst())
+
+let responder = try! createDidExchangeResponder(
+    profile: profile, request: request.message, invitation: invitation,
+    recipientVerkey: request.recipientVerkey, serviceEndpoint: "http://localhost:8080",
+    routingKeys: []
+)
+assert(responder.getThreadId() == requester.getThreadId())
+let response = try! unpackMessage(profile: profile, packedMsg: transport.messages.removeFirst())
+
+try! requester.processAriesMsg(profile: profile, message: response.message)
+assert(try! requester.getState() == .completed)
+let complete = try! unpackMessage(profile: profile, packedMsg: transport.messages.removeFirst())
+
+try! await responder.processAriesMsgAsync(profile: profile, message: complete.message)
+assert(try! responder.getState() == .completed)
+assert(try! requester.theirDid() == responder.ourDid())
+assert(try! responder.theirDid() == requester.ourDid())
+
+// a failure of the transport fails the call that sent the message
+let offlineProfile = try! newIndyProfileWithTransport(
+    walletConfig: walletConfig,
+    genesisFilePath: ProcessInfo.processInfo.environment["GENESIS_FILE"]!,
+    transport: FailingTransport()
+)
+do {
+    _ = try await createDidExchangeRequesterAsync(
+        profile: offlineProfile, invitation: invitation, serviceEndpoint: "http://localhost:8080",
+        routingKeys: [], label: "carol"
+    )
+    assertionFailure("the transport failure should be raised")
+} catch VcxUniFFIError.AriesVcxError {
+} catch {
+    assertionFailure("unexpected error \(error)")
+}
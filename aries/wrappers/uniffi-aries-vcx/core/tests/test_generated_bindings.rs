@@ -0,0 +1,7 @@
+// Runs the Kotlin and Swift scripts against bindings generated from vcx.udl. The scripts open an
+// in-memory wallet and need the path of a genesis file in the GENESIS_FILE environment variable,
+// but do not otherwise reach the ledger.
+uniffi::build_foreign_language_testcases!(
+    "tests/bindings/test_vcx.kts",
+    "tests/bindings/test_vcx.swift"
+);
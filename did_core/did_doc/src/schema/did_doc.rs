@@ -8,9 +8,10 @@ use serde_json::Value;
 use super::{
     types::uri::Uri,
     utils::OneOrList,
+    validation::{self, DidDocumentValidationError},
     verification_method::{VerificationMethod, VerificationMethodKind},
 };
-use crate::{error::DidDocumentBuilderError, schema::service::Service};
+use crate::schema::service::Service;
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default, Display)]
 #[serde(default)]
@@ -214,8 +215,9 @@ impl DidDocument {
         all_vms.find(|vm| vm.id().fragment() == reference.fragment())
     }
 
-    pub fn validate(&self) -> Result<(), DidDocumentBuilderError> {
-        Ok(())
+    /// Checks conformance to DID core spec, listing every problem found
+    pub fn validate(&self) -> Result<(), DidDocumentValidationError> {
+        validation::validate(self)
     }
 
     pub fn set_also_known_as(&mut self, uris: Vec<Uri>) {
@@ -2,6 +2,7 @@ pub mod did_doc;
 pub mod service;
 pub mod types;
 pub mod utils;
+pub mod validation;
 pub mod verification_method;
 
 /// Module of commonly used DID-related JSON-LD contexts
@@ -0,0 +1,454 @@
+use std::{collections::HashSet, fmt};
+
+use did_parser_nom::{Did, DidUrl};
+use public_key::{Key, KeyType};
+use thiserror::Error;
+
+use crate::schema::{
+    did_doc::DidDocument,
+    service::Service,
+    utils::OneOrList,
+    verification_method::{
+        PublicKeyField, VerificationMethod, VerificationMethodKind, VerificationMethodType,
+    },
+};
+
+/// Single spec conformance violation found in a DID document
+#[derive(Clone, Debug, PartialEq)]
+pub enum ValidationProblem {
+    DuplicateVerificationMethodId(String),
+    DuplicateServiceId(String),
+    /// Relationship (`authentication`, `keyAgreement`, ...) refers to a verification method
+    /// which is not defined in the document
+    UnresolvedReference {
+        relationship: &'static str,
+        reference: String,
+    },
+    InvalidPublicKey {
+        verification_method: String,
+        reason: String,
+    },
+    InvalidController {
+        controller: String,
+    },
+    InvalidServiceEndpoint {
+        service: String,
+        reason: String,
+    },
+}
+
+impl fmt::Display for ValidationProblem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ValidationProblem::DuplicateVerificationMethodId(id) => {
+                write!(f, "duplicate verification method id {id}")
+            }
+            ValidationProblem::DuplicateServiceId(id) => write!(f, "duplicate service id {id}"),
+            ValidationProblem::UnresolvedReference {
+                relationship,
+                reference,
+            } => write!(
+                f,
+                "{relationship} refers to undefined verification method {reference}"
+            ),
+            ValidationProblem::InvalidPublicKey {
+                verification_method,
+                reason,
+            } => write!(
+                f,
+                "invalid public key of verification method {verification_method}: {reason}"
+            ),
+            ValidationProblem::InvalidController { controller } => {
+                write!(f, "invalid controller DID {controller}")
+            }
+            ValidationProblem::InvalidServiceEndpoint { service, reason } => {
+                write!(f, "invalid endpoint of service {service}: {reason}")
+            }
+        }
+    }
+}
+
+/// Lists every problem found by [`DidDocument::validate`]
+#[derive(Debug, Error, Clone, PartialEq)]
+pub struct DidDocumentValidationError {
+    did: String,
+    problems: Vec<ValidationProblem>,
+}
+
+impl DidDocumentValidationError {
+    pub fn problems(&self) -> &[ValidationProblem] {
+        &self.problems
+    }
+}
+
+impl fmt::Display for DidDocumentValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Invalid DID document {}: ", self.did)?;
+        for (index, problem) in self.problems.iter().enumerate() {
+            if index > 0 {
+                write!(f, "; ")?;
+            }
+            write!(f, "{problem}")?;
+        }
+        Ok(())
+    }
+}
+
+pub(crate) fn validate(did_doc: &DidDocument) -> Result<(), DidDocumentValidationError> {
+    let mut problems = vec![];
+
+    let controllers = match did_doc.controller() {
+        Some(OneOrList::One(controller)) => std::slice::from_ref(controller),
+        Some(OneOrList::List(controllers)) => controllers.as_slice(),
+        None => &[],
+    };
+    for controller in controllers {
+        validate_controller(controller, &mut problems);
+    }
+
+    let relationships: [(&'static str, &[VerificationMethodKind]); 5] = [
+        ("authentication", did_doc.authentication()),
+        ("assertionMethod", did_doc.assertion_method()),
+        ("keyAgreement", did_doc.key_agreement()),
+        ("capabilityInvocation", did_doc.capability_invocation()),
+        ("capabilityDelegation", did_doc.capability_delegation()),
+    ];
+    let embedded_vms = relationships
+        .iter()
+        .flat_map(|(_, vms)| vms.iter())
+        .filter_map(|vm| vm.resolved());
+
+    let mut vm_ids = HashSet::new();
+    for vm in did_doc.verification_method().iter().chain(embedded_vms) {
+        if !vm_ids.insert(local_id(did_doc.id(), vm.id())) {
+            problems.push(ValidationProblem::DuplicateVerificationMethodId(
+                vm.id().to_string(),
+            ));
+        }
+        validate_controller(vm.controller(), &mut problems);
+        if let Err(reason) = validate_public_key(vm) {
+            problems.push(ValidationProblem::InvalidPublicKey {
+                verification_method: vm.id().to_string(),
+                reason,
+            });
+        }
+    }
+
+    for (relationship, vms) in relationships {
+        for reference in vms.iter().filter_map(|vm| vm.resolvable()) {
+            let is_local = reference
+                .did()
+                .map_or(true, |did| did == did_doc.id().did());
+            if is_local && !vm_ids.contains(&local_id(did_doc.id(), reference)) {
+                problems.push(ValidationProblem::UnresolvedReference {
+                    relationship,
+                    reference: reference.to_string(),
+                });
+            }
+        }
+    }
+
+    let mut service_ids = HashSet::new();
+    for service in did_doc.service() {
+        let id = service.id().to_string();
+        if !service_ids.insert(id.clone()) {
+            problems.push(ValidationProblem::DuplicateServiceId(id.clone()));
+        }
+        if let Err(reason) = validate_service_endpoint(service) {
+            problems.push(ValidationProblem::InvalidServiceEndpoint {
+                service: id,
+                reason,
+            });
+        }
+    }
+
+    if problems.is_empty() {
+        Ok(())
+    } else {
+        Err(DidDocumentValidationError {
+            did: did_doc.id().to_string(),
+            problems,
+        })
+    }
+}
+
+/// Verification method ids within the document are compared by fragment, so that relative and
+/// absolute DID URLs referring to the same method match
+fn local_id(did: &Did, id: &DidUrl) -> String {
+    match (id.did(), id.fragment()) {
+        (None, Some(fragment)) => fragment.to_string(),
+        (Some(id_did), Some(fragment)) if id_did == did.did() => fragment.to_string(),
+        _ => id.to_string(),
+    }
+}
+
+fn validate_controller(controller: &Did, problems: &mut Vec<ValidationProblem>) {
+    if controller.method().is_none() || controller.id().is_empty() {
+        let problem = ValidationProblem::InvalidController {
+            controller: controller.to_string(),
+        };
+        if !problems.contains(&problem) {
+            problems.push(problem);
+        }
+    }
+}
+
+fn validate_public_key(vm: &VerificationMethod) -> Result<(), String> {
+    let public_key = vm.public_key_field();
+    let vm_type = vm.verification_method_type();
+    match vm_type {
+        VerificationMethodType::Multikey => match public_key {
+            PublicKeyField::Multibase {
+                public_key_multibase,
+            } => Key::from_fingerprint(public_key_multibase)
+                .map(|_| ())
+                .map_err(|err| err.to_string()),
+            _ => Err(format!("{vm_type} requires publicKeyMultibase")),
+        },
+        VerificationMethodType::JsonWebKey2020 => match public_key {
+            PublicKeyField::Jwk { .. } => Ok(()),
+            // multibase keys are self-describing, so the key type is known without JWK, e.g. the
+            // P-256 keys of did:peer documents
+            PublicKeyField::Multibase {
+                public_key_multibase,
+            } => Key::from_fingerprint(public_key_multibase)
+                .map(|_| ())
+                .map_err(|err| err.to_string()),
+            _ => Err(format!(
+                "{vm_type} requires publicKeyJwk or publicKeyMultibase"
+            )),
+        },
+        VerificationMethodType::PgpVerificationKey2021 => match public_key {
+            PublicKeyField::Pgp { .. } => Ok(()),
+            _ => Err(format!("{vm_type} requires publicKeyPgp")),
+        },
+        VerificationMethodType::EcdsaSecp256k1RecoveryMethod2020 => match public_key {
+            PublicKeyField::Pgp { .. } | PublicKeyField::Pem { .. } => {
+                Err(format!("{vm_type} doesn't support this public key format"))
+            }
+            _ => Ok(()),
+        },
+        VerificationMethodType::EcdsaSecp256k1VerificationKey2019 => match public_key {
+            PublicKeyField::Pgp { .. }
+            | PublicKeyField::Pem { .. }
+            | PublicKeyField::BlockchainAccountId { .. } => {
+                Err(format!("{vm_type} doesn't support this public key format"))
+            }
+            _ => Ok(()),
+        },
+        VerificationMethodType::Ed25519VerificationKey2018
+        | VerificationMethodType::Ed25519VerificationKey2020
+        | VerificationMethodType::X25519KeyAgreementKey2019
+        | VerificationMethodType::X25519KeyAgreementKey2020
+        | VerificationMethodType::Bls12381G1Key2020
+        | VerificationMethodType::Bls12381G2Key2020 => {
+            let key_type = KeyType::try_from(*vm_type).map_err(|err| err.to_string())?;
+            validate_raw_key(public_key, key_type)
+        }
+    }
+}
+
+/// Checks key of verification method type implying single key type
+fn validate_raw_key(public_key: &PublicKeyField, key_type: KeyType) -> Result<(), String> {
+    let expected_length = match key_type {
+        KeyType::Ed25519 | KeyType::X25519 => 32,
+        KeyType::Bls12381g1 => 48,
+        KeyType::Bls12381g2 => 96,
+        _ => return Ok(()),
+    };
+    let key = match public_key {
+        PublicKeyField::Jwk { .. }
+        | PublicKeyField::Pgp { .. }
+        | PublicKeyField::BlockchainAccountId { .. } => {
+            return Err(format!("{key_type} key can't be expressed in this format"));
+        }
+        // multibase keys are usually multicodec prefixed, though raw keys are seen in the wild
+        PublicKeyField::Multibase {
+            public_key_multibase,
+        } => match multibase::decode(public_key_multibase) {
+            Ok((_, bytes)) if bytes.len() == expected_length => bytes,
+            _ => {
+                let key =
+                    Key::from_fingerprint(public_key_multibase).map_err(|err| err.to_string())?;
+                if *key.key_type() != key_type {
+                    return Err(format!("expected {key_type} key, got {}", key.key_type()));
+                }
+                key.key().to_vec()
+            }
+        },
+        _ => public_key.key_decoded().map_err(|err| err.to_string())?,
+    };
+    if key.len() != expected_length {
+        return Err(format!(
+            "{key_type} key must be {expected_length} bytes long, got {}",
+            key.len()
+        ));
+    }
+    Ok(())
+}
+
+fn validate_service_endpoint(service: &Service) -> Result<(), String> {
+    if service.service_types().is_empty() {
+        return Err("service has no type".to_string());
+    }
+    let endpoint = service.service_endpoint();
+    match endpoint.scheme() {
+        "http" | "https" | "ws" | "wss" if endpoint.host().is_none() => {
+            Err(format!("{endpoint} has no host"))
+        }
+        "did" => DidUrl::parse(endpoint.to_string())
+            .map(|_| ())
+            .map_err(|err| format!("{endpoint} is not a valid DID URL: {err}")),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use serde_json::json;
+
+    use super::*;
+
+    fn did_doc_from(value: serde_json::Value) -> DidDocument {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn test_valid_did_doc() {
+        let did_doc = did_doc_from(json!({
+            "id": "did:example:123",
+            "controller": "did:example:456",
+            "verificationMethod": [
+                {
+                    "id": "#key-1",
+                    "type": "Ed25519VerificationKey2018",
+                    "controller": "did:example:123",
+                    "publicKeyBase58": "8HH5gYEeNc3z7PYXmd54d4x6qAfCNrqQqEB3nS7Zfu7K"
+                },
+                {
+                    "id": "did:example:123#key-2",
+                    "type": "Multikey",
+                    "controller": "did:example:123",
+                    "publicKeyMultibase": "z6LSbysY2xFMRpGMhb7tFTLMpeuPRaqaWM1yECx2AtzE3KCc"
+                }
+            ],
+            "authentication": ["did:example:123#key-1", "did:other:789#key-1"],
+            "keyAgreement": [
+                "#key-2",
+                {
+                    "id": "#key-3",
+                    "type": "X25519KeyAgreementKey2020",
+                    "controller": "did:example:123",
+                    "publicKeyMultibase": "z6LSbysY2xFMRpGMhb7tFTLMpeuPRaqaWM1yECx2AtzE3KCc"
+                }
+            ],
+            "service": [
+                {
+                    "id": "did:example:123#service-0",
+                    "type": "DIDCommMessaging",
+                    "serviceEndpoint": "https://example.com/endpoint"
+                }
+            ]
+        }));
+        assert_eq!(validate(&did_doc), Ok(()));
+    }
+
+    #[test]
+    fn test_invalid_did_doc_lists_all_problems() {
+        let did_doc = did_doc_from(json!({
+            "id": "did:example:123",
+            "verificationMethod": [
+                {
+                    "id": "#key-1",
+                    "type": "Ed25519VerificationKey2020",
+                    "controller": "did:example:123",
+                    "publicKeyBase58": "8HH5gYEe"
+                },
+                {
+                    "id": "did:example:123#key-1",
+                    "type": "X25519KeyAgreementKey2020",
+                    "controller": "did:example:123",
+                    "publicKeyMultibase": "z6MkpTHR8VNsBxYAAWHut2Geadd9jSwuBV8xRoAnwWsdvktH"
+                },
+                {
+                    "id": "#key-2",
+                    "type": "JsonWebKey2020",
+                    "controller": "did:example:123",
+                    "publicKeyBase58": "8HH5gYEeNc3z7PYXmd54d4x6qAfCNrqQqEB3nS7Zfu7K"
+                }
+            ],
+            "authentication": ["#key-1", "#key-3"],
+            "service": [
+                {
+                    "id": "did:example:123#service-0",
+                    "type": "DIDCommMessaging",
+                    "serviceEndpoint": "https://example.com/endpoint"
+                },
+                {
+                    "id": "did:example:123#service-0",
+                    "type": "DIDCommMessaging",
+                    "serviceEndpoint": "did:example:mediator"
+                }
+            ]
+        }));
+        let error = validate(&did_doc).unwrap_err();
+        assert_eq!(
+            error.problems(),
+            &[
+                ValidationProblem::InvalidPublicKey {
+                    verification_method: "#key-1".to_string(),
+                    reason: "Ed25519 key must be 32 bytes long, got 6".to_string()
+                },
+                ValidationProblem::DuplicateVerificationMethodId(
+                    "did:example:123#key-1".to_string()
+                ),
+                ValidationProblem::InvalidPublicKey {
+                    verification_method: "did:example:123#key-1".to_string(),
+                    reason: "expected X25519 key, got Ed25519".to_string()
+                },
+                ValidationProblem::InvalidPublicKey {
+                    verification_method: "#key-2".to_string(),
+                    reason: "JsonWebKey2020 requires publicKeyJwk or publicKeyMultibase"
+                        .to_string()
+                },
+                ValidationProblem::UnresolvedReference {
+                    relationship: "authentication",
+                    reference: "#key-3".to_string()
+                },
+                ValidationProblem::DuplicateServiceId("did:example:123#service-0".to_string()),
+            ]
+        );
+        assert!(error.to_string().contains("#key-3"));
+    }
+
+    #[test]
+    fn test_invalid_controller() {
+        let mut did_doc = DidDocument::new("did:example:123".parse().unwrap());
+        did_doc.set_controller(OneOrList::One(Did::default()));
+        let error = validate(&did_doc).unwrap_err();
+        assert_eq!(
+            error.problems(),
+            &[ValidationProblem::InvalidController {
+                controller: "".to_string()
+            }]
+        );
+    }
+
+    #[test]
+    fn test_invalid_service_endpoint() {
+        let mut did_doc = DidDocument::new("did:example:123".parse().unwrap());
+        did_doc.add_service(Service::new(
+            "did:example:123#service-0".parse().unwrap(),
+            "did:bad".parse().unwrap(),
+            OneOrList::List(vec![]),
+            HashMap::new(),
+        ));
+        let error = validate(&did_doc).unwrap_err();
+        assert!(matches!(
+            error.problems(),
+            [ValidationProblem::InvalidServiceEndpoint { .. }]
+        ));
+    }
+}
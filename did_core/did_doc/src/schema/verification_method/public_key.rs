@@ -22,6 +22,10 @@ pub enum PublicKeyField {
     Pem { public_key_pem: String },
     #[serde(rename_all = "camelCase")]
     Pgp { public_key_pgp: String },
+    /// CAIP-10 account id, used by verification methods which identify the key by the account it
+    /// controls rather than by the key itself
+    #[serde(rename_all = "camelCase")]
+    BlockchainAccountId { blockchain_account_id: String },
 }
 
 impl PublicKeyField {
@@ -49,6 +53,11 @@ impl PublicKeyField {
             PublicKeyField::Pgp { public_key_pgp: _ } => Err(KeyDecodingError::new(
                 "PGP public key decoding not supported",
             )),
+            PublicKeyField::BlockchainAccountId {
+                blockchain_account_id: _,
+            } => Err(KeyDecodingError::new(
+                "Blockchain account id does not contain public key",
+            )),
         }
     }
 
@@ -143,4 +152,21 @@ mod tests {
             .is::<pem::PemError>());
         assert!(err.to_string().contains("Failed to decode PEM"));
     }
+
+    #[test]
+    fn test_blockchain_account_id_serde() {
+        let value = serde_json::json!({
+            "blockchainAccountId": "eip155:1:0xb9c5714089478a327f09197987f16f9e5d936e8a"
+        });
+        let public_key_field: PublicKeyField = serde_json::from_value(value.clone()).unwrap();
+        assert_eq!(
+            public_key_field,
+            PublicKeyField::BlockchainAccountId {
+                blockchain_account_id: "eip155:1:0xb9c5714089478a327f09197987f16f9e5d936e8a"
+                    .to_string()
+            }
+        );
+        assert_eq!(serde_json::to_value(&public_key_field).unwrap(), value);
+        assert!(public_key_field.key_decoded().is_err());
+    }
 }
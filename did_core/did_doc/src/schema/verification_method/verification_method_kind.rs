@@ -21,4 +21,12 @@ impl VerificationMethodKind {
             VerificationMethodKind::Resolvable(_) => None,
         }
     }
+
+    /// Convenience function to try get the DID URL reference (if it is that variant)
+    pub fn resolvable(&self) -> Option<&DidUrl> {
+        match &self {
+            VerificationMethodKind::Resolved(_) => None,
+            VerificationMethodKind::Resolvable(x) => Some(x),
+        }
+    }
 }
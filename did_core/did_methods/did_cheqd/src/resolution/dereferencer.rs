@@ -0,0 +1,121 @@
+use std::io::Cursor;
+
+use async_trait::async_trait;
+use did_resolver::{
+    did_parser_nom::{Did, DidUrl},
+    error::GenericError,
+    shared_types::{did_document_metadata::DidDocumentMetadata, did_resource::DidResource},
+    traits::{
+        dereferenceable::{
+            dereferencing_metadata::DidDereferencingMetadata,
+            dereferencing_options::DidDereferencingOptions,
+            dereferencing_output::DidDereferencingOutput,
+            document_dereferencing::{
+                dereference_did_document, VERSION_ID_QUERY, VERSION_TIME_QUERY,
+            },
+            DidDereferenceable,
+        },
+        resolvable::DidResolvable,
+    },
+};
+
+use super::{options::DidCheqdResolutionOptions, resolver::DidCheqdResolver};
+use crate::error::DidCheqdError;
+
+const RESOURCE_QUERIES: [&str; 2] = ["resourceName", "resourceType"];
+
+#[async_trait]
+impl DidDereferenceable for DidCheqdResolver {
+    type Output = Cursor<Vec<u8>>;
+
+    /// Dereferences DID-Linked Resource URLs (see [DidCheqdResolver::resolve_resource]) to the
+    /// resource content, and any other DID URL within the (optionally versioned) DID document.
+    async fn dereference(
+        &self,
+        did_url: &DidUrl,
+        options: &DidDereferencingOptions,
+    ) -> Result<DidDereferencingOutput<Self::Output>, GenericError> {
+        let queries = did_url.queries();
+        if did_url.path().is_some() || RESOURCE_QUERIES.iter().any(|q| queries.contains_key(*q)) {
+            let resource = self.resolve_resource(did_url).await?;
+            return Ok(resource_to_output(resource));
+        }
+
+        if queries.contains_key(VERSION_TIME_QUERY) {
+            return Err(Box::new(DidCheqdError::InvalidDidUrl(format!(
+                "Dereferencing DID document by {VERSION_TIME_QUERY} is not supported: {did_url}"
+            ))));
+        }
+        let did = Did::try_from(did_url)?;
+        let resolution_options = DidCheqdResolutionOptions {
+            version_id: queries.get(VERSION_ID_QUERY).cloned(),
+        };
+        let resolution_output = self.resolve(&did, &resolution_options).await?;
+        Ok(dereference_did_document(
+            &resolution_output,
+            did_url,
+            options,
+        )?)
+    }
+}
+
+fn resource_to_output(resource: DidResource) -> DidDereferencingOutput<Cursor<Vec<u8>>> {
+    let metadata = resource.metadata;
+    let mut content_metadata = DidDocumentMetadata::builder()
+        .created(metadata.created)
+        .version_id(metadata.resource_id);
+    if let Some(updated) = metadata.updated {
+        content_metadata = content_metadata.updated(updated);
+    }
+    if let Some(next_version_id) = metadata.next_version_id {
+        content_metadata = content_metadata.next_version_id(next_version_id);
+    }
+
+    DidDereferencingOutput::builder(Cursor::new(resource.content))
+        .dereferencing_metadata(
+            DidDereferencingMetadata::builder()
+                .content_type(metadata.media_type)
+                .build(),
+        )
+        .content_metadata(content_metadata.build())
+        .build()
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_dereference_version_time_not_supported() {
+        let url = "did:cheqd:mainnet:Ps1ysXP2Ae6GBfxNhNQNKN?versionTime=2022-08-21T08:40:00Z"
+            .parse()
+            .unwrap();
+        let resolver = DidCheqdResolver::new(Default::default());
+        let e = resolver
+            .dereference(&url, &Default::default())
+            .await
+            .err()
+            .unwrap();
+        assert!(matches!(
+            e.downcast_ref::<DidCheqdError>(),
+            Some(DidCheqdError::InvalidDidUrl(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_dereference_resource_fails_if_wrong_path() {
+        let url = "did:cheqd:mainnet:zF7rhDBfUt9d1gJPjx7s1J/other/123"
+            .parse()
+            .unwrap();
+        let resolver = DidCheqdResolver::new(Default::default());
+        let e = resolver
+            .dereference(&url, &Default::default())
+            .await
+            .err()
+            .unwrap();
+        assert!(matches!(
+            e.downcast_ref::<DidCheqdError>(),
+            Some(DidCheqdError::InvalidDidUrl(_))
+        ));
+    }
+}
@@ -1,2 +1,4 @@
+pub mod dereferencer;
+pub mod options;
 pub mod resolver;
 pub mod transformer;
@@ -0,0 +1,8 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DidCheqdResolutionOptions {
+    /// Version of the DID document to resolve, the latest version if not set
+    pub version_id: Option<String>,
+}
@@ -22,11 +22,14 @@ use hyper_util::{
 use tokio::sync::Mutex;
 use tonic::{transport::Uri, Status};
 
-use super::transformer::CheqdResourceMetadataWithUri;
+use super::{options::DidCheqdResolutionOptions, transformer::CheqdResourceMetadataWithUri};
 use crate::{
     error::{DidCheqdError, DidCheqdResult},
     proto::cheqd::{
-        did::v2::{query_client::QueryClient as DidQueryClient, QueryDidDocRequest},
+        did::v2::{
+            query_client::QueryClient as DidQueryClient, DidDocWithMetadata, QueryDidDocRequest,
+            QueryDidDocVersionRequest,
+        },
         resource::v2::{
             query_client::QueryClient as ResourceQueryClient, Metadata as CheqdResourceMetadata,
             QueryCollectionResourcesRequest, QueryResourceRequest,
@@ -101,14 +104,19 @@ pub struct DidCheqdResolver {
 
 #[async_trait]
 impl DidResolvable for DidCheqdResolver {
-    type DidResolutionOptions = ();
+    type DidResolutionOptions = DidCheqdResolutionOptions;
 
     async fn resolve(
         &self,
         did: &Did,
-        _: &Self::DidResolutionOptions,
+        options: &Self::DidResolutionOptions,
     ) -> Result<DidResolutionOutput, GenericError> {
-        Ok(self.resolve_did(did).await?)
+        let resolution_output = match &options.version_id {
+            Some(version_id) => self.resolve_did_version(did, version_id).await?,
+            None => self.resolve_did(did).await?,
+        };
+        resolution_output.did_document.validate()?;
+        Ok(resolution_output)
     }
 }
 
@@ -172,23 +180,30 @@ impl DidCheqdResolver {
         let request = tonic::Request::new(QueryDidDocRequest { id: did });
         let response = client.did.did_doc(request).await?;
 
-        let query_response = response.into_inner();
-        let query_doc_res = query_response.value.ok_or(DidCheqdError::InvalidResponse(
-            "DIDDoc query did not return a value".into(),
-        ))?;
-        let query_doc = query_doc_res.did_doc.ok_or(DidCheqdError::InvalidResponse(
-            "DIDDoc query did not return a DIDDoc".into(),
-        ))?;
-
-        let mut output_builder = DidResolutionOutput::builder(DidDocument::try_from(query_doc)?);
-
-        if let Some(query_metadata) = query_doc_res.metadata {
-            // FUTURE - append linked resources to metadata
-            output_builder = output_builder
-                .did_document_metadata(DidDocumentMetadata::try_from(query_metadata)?);
+        did_doc_with_metadata_to_output(response.into_inner().value)
+    }
+
+    /// Resolve the given version of a cheqd DID document.
+    pub async fn resolve_did_version(
+        &self,
+        did: &Did,
+        version_id: &str,
+    ) -> DidCheqdResult<DidResolutionOutput> {
+        let method = did.method();
+        if method != Some("cheqd") {
+            return Err(DidCheqdError::MethodNotSupported(format!("{method:?}")));
         }
 
-        Ok(output_builder.build())
+        let network = did.namespace().unwrap_or(MAINNET_NAMESPACE);
+        let mut client = self.client_for_network(network).await?;
+
+        let request = tonic::Request::new(QueryDidDocVersionRequest {
+            id: did.did().to_owned(),
+            version: version_id.to_owned(),
+        });
+        let response = client.did.did_doc_version(request).await?;
+
+        did_doc_with_metadata_to_output(response.into_inner().value)
     }
 
     /// Resolve a cheqd DID resource & associated metadata from the given [DidUrl].
@@ -357,6 +372,28 @@ fn native_tls_hyper_client() -> DidCheqdResult<HyperClient> {
         .build(connector))
 }
 
+/// Converts the DIDDoc of a DIDDoc (version) query response to a resolution output
+fn did_doc_with_metadata_to_output(
+    value: Option<DidDocWithMetadata>,
+) -> DidCheqdResult<DidResolutionOutput> {
+    let query_doc_res = value.ok_or(DidCheqdError::InvalidResponse(
+        "DIDDoc query did not return a value".into(),
+    ))?;
+    let query_doc = query_doc_res.did_doc.ok_or(DidCheqdError::InvalidResponse(
+        "DIDDoc query did not return a DIDDoc".into(),
+    ))?;
+
+    let mut output_builder = DidResolutionOutput::builder(DidDocument::try_from(query_doc)?);
+
+    if let Some(query_metadata) = query_doc_res.metadata {
+        // FUTURE - append linked resources to metadata
+        output_builder =
+            output_builder.did_document_metadata(DidDocumentMetadata::try_from(query_metadata)?);
+    }
+
+    Ok(output_builder.build())
+}
+
 /// Filter for resources which have a matching name and type
 fn filter_resources_by_name_and_type<'a>(
     resources: impl Iterator<Item = &'a CheqdResourceMetadata> + 'a,
@@ -50,7 +50,7 @@ async fn test_resolve_known_mainnet_did_vector() {
     });
 
     let resolver = DidCheqdResolver::new(DidCheqdResolverConfiguration::default());
-    let output = resolver.resolve(&did, &()).await.unwrap();
+    let output = resolver.resolve(&did, &Default::default()).await.unwrap();
     let doc = output.did_document;
     assert_eq!(serde_json::to_value(doc.clone()).unwrap(), expected_doc);
     assert_eq!(doc, serde_json::from_value(expected_doc).unwrap());
@@ -85,7 +85,7 @@ async fn test_resolve_known_testnet_did_vector() {
     });
 
     let resolver = DidCheqdResolver::new(DidCheqdResolverConfiguration::default());
-    let output = resolver.resolve(&did, &()).await.unwrap();
+    let output = resolver.resolve(&did, &Default::default()).await.unwrap();
     let doc = output.did_document;
     assert_eq!(serde_json::to_value(doc.clone()).unwrap(), expected_doc);
     assert_eq!(doc, serde_json::from_value(expected_doc).unwrap());
@@ -104,7 +104,8 @@ impl TryFrom<Key> for DidJwk {
             | KeyType::Bls12381g2
             | KeyType::P256
             | KeyType::P384
-            | KeyType::P521 => {
+            | KeyType::P521
+            | KeyType::Secp256k1 => {
                 jwk.extra.insert(String::from(USE), json!(USE_SIG));
             }
             KeyType::X25519 => {
@@ -65,6 +65,7 @@ impl DidResolvable for DidJwkResolver {
                 did_doc.add_key_agreement_ref(vm_id.clone());
             }
         };
+        did_doc.validate()?;
 
         Ok(DidResolutionOutput::builder(did_doc).build())
     }
@@ -23,6 +23,10 @@ pub enum DidPeerError {
     InvalidService(String),
     #[error("Unsupported numalgo: {0}")]
     UnsupportedNumalgo(NumalgoKind),
+    #[error("Resolution of numalgo {0} requires a peer DID store")]
+    StoreRequired(NumalgoKind),
+    #[error("Peer DID not found in store: {0}")]
+    NotFoundInStore(String),
     #[error("Invalid numalgo character: {0}")]
     InvalidNumalgoCharacter(char),
     #[error("Unsupported purpose character: {0}")]
@@ -7,13 +7,18 @@ use super::PeerDid;
 use crate::{
     error::DidPeerError,
     peer_did::{
-        numalgos::{kind::NumalgoKind, numalgo2::Numalgo2, numalgo3::Numalgo3, numalgo4::Numalgo4},
+        numalgos::{
+            kind::NumalgoKind, numalgo0::Numalgo0, numalgo1::Numalgo1, numalgo2::Numalgo2,
+            numalgo3::Numalgo3, numalgo4::Numalgo4,
+        },
         parse::parse_numalgo,
     },
 };
 
 #[derive(Clone, Debug, PartialEq)]
 pub enum AnyPeerDid {
+    Numalgo0(PeerDid<Numalgo0>),
+    Numalgo1(PeerDid<Numalgo1>),
     Numalgo2(PeerDid<Numalgo2>),
     Numalgo3(PeerDid<Numalgo3>),
     Numalgo4(PeerDid<Numalgo4>),
@@ -32,6 +37,14 @@ impl AnyPeerDid {
         let numalgo = parse_numalgo(&did)?;
         log::info!("AnyPeerDid >> parsed numalgo {}", numalgo.to_char());
         let parsed = match numalgo {
+            NumalgoKind::InceptionKeyWithoutDoc(numalgo0) => AnyPeerDid::Numalgo0(PeerDid {
+                did,
+                numalgo: numalgo0,
+            }),
+            NumalgoKind::GenesisDoc(numalgo1) => AnyPeerDid::Numalgo1(PeerDid {
+                did,
+                numalgo: numalgo1,
+            }),
             NumalgoKind::MultipleInceptionKeys(numalgo2) => AnyPeerDid::Numalgo2(PeerDid {
                 did,
                 numalgo: numalgo2,
@@ -44,13 +57,14 @@ impl AnyPeerDid {
                 did,
                 numalgo: numalgo4,
             }),
-            o => unimplemented!("Parsing numalgo {} is not supported", o.to_char()),
         };
         Ok(parsed)
     }
 
     pub fn numalgo(&self) -> NumalgoKind {
         match self {
+            AnyPeerDid::Numalgo0(peer_did) => NumalgoKind::InceptionKeyWithoutDoc(peer_did.numalgo),
+            AnyPeerDid::Numalgo1(peer_did) => NumalgoKind::GenesisDoc(peer_did.numalgo),
             AnyPeerDid::Numalgo2(peer_did) => NumalgoKind::MultipleInceptionKeys(peer_did.numalgo),
             AnyPeerDid::Numalgo3(peer_did) => NumalgoKind::DidShortening(peer_did.numalgo),
             AnyPeerDid::Numalgo4(peer_did) => NumalgoKind::DidPeer4(peer_did.numalgo),
@@ -64,6 +78,8 @@ impl Serialize for AnyPeerDid {
         S: Serializer,
     {
         match &self {
+            AnyPeerDid::Numalgo0(peer_did) => serializer.serialize_str(peer_did.did().did()),
+            AnyPeerDid::Numalgo1(peer_did) => serializer.serialize_str(peer_did.did().did()),
             AnyPeerDid::Numalgo2(peer_did) => serializer.serialize_str(peer_did.did().did()),
             AnyPeerDid::Numalgo3(peer_did) => serializer.serialize_str(peer_did.did().did()),
             AnyPeerDid::Numalgo4(peer_did) => serializer.serialize_str(peer_did.did().did()),
@@ -94,6 +110,9 @@ mod tests {
     const VALID_PEER_DID_NUMALGO3: &str =
         "did:peer:3zQmS19jtYDvGtKVrJhQnRFpBQAx3pJ9omx2HpNrcXFuRCz9";
 
+    const VALID_PEER_DID_NUMALGO0: &str =
+        "did:peer:0z6MkpTHR8VNsBxYAAWHut2Geadd9jSwuBV8xRoAnwWsdvktH";
+
     fn generic_peer_did_numalgo2() -> AnyPeerDid {
         AnyPeerDid::Numalgo2(PeerDid {
             did: VALID_PEER_DID_NUMALGO2.parse().unwrap(),
@@ -127,6 +146,19 @@ mod tests {
     mod deserialize {
         use super::*;
 
+        #[test]
+        fn numalgo0() {
+            let deserialized: AnyPeerDid =
+                serde_json::from_str(&format!("\"{}\"", VALID_PEER_DID_NUMALGO0)).unwrap();
+            assert_eq!(
+                deserialized,
+                AnyPeerDid::Numalgo0(PeerDid {
+                    did: VALID_PEER_DID_NUMALGO0.parse().unwrap(),
+                    numalgo: Numalgo0,
+                })
+            );
+        }
+
         #[test]
         fn numalgo2() {
             let deserialized: AnyPeerDid =
@@ -1,4 +1,14 @@
-use crate::peer_did::numalgos::Numalgo;
+use did_doc::schema::did_doc::DidDocument;
+use public_key::{Key, KeyType};
+
+use crate::{
+    error::DidPeerError,
+    peer_did::{
+        numalgos::{numalgo2::verification_method::get_verification_methods_by_key, Numalgo},
+        PeerDid,
+    },
+    resolver::options::PublicKeyEncoding,
+};
 
 #[derive(Clone, Copy, Default, Debug, PartialEq)]
 pub struct Numalgo0;
@@ -6,3 +16,126 @@ pub struct Numalgo0;
 impl Numalgo for Numalgo0 {
     const NUMALGO_CHAR: char = '0';
 }
+
+impl PeerDid<Numalgo0> {
+    /// Implementation of did:peer:0 creation spec:
+    /// https://identity.foundation/peer-did-method-spec/#method-0-inception-key-without-doc
+    pub fn from_key(key: &Key) -> Result<Self, DidPeerError> {
+        PeerDid::<Numalgo0>::parse(format!("did:peer:0{}", key.fingerprint()))
+    }
+
+    /// The inception key encoded in the DID
+    pub fn key(&self) -> Result<Key, DidPeerError> {
+        Ok(Key::from_fingerprint(&self.did().id()[1..])?)
+    }
+
+    /// Resolves DID document from the inception key, like did:key: an X25519 key is used for
+    /// key agreement, other keys for signing, with an X25519 key agreement key derived from
+    /// Ed25519 keys
+    pub(crate) fn to_did_doc(
+        &self,
+        public_key_encoding: PublicKeyEncoding,
+    ) -> Result<DidDocument, DidPeerError> {
+        let key = self.key()?;
+        let mut did_doc = DidDocument::new(self.did().clone());
+        let mut vm_index: usize = 1;
+        let vms =
+            get_verification_methods_by_key(&key, self.did(), public_key_encoding, &mut vm_index)?;
+        for vm in vms.into_iter() {
+            let vm_reference = vm.id().to_owned();
+            did_doc.add_verification_method(vm);
+            if *key.key_type() == KeyType::X25519 {
+                did_doc.add_key_agreement_ref(vm_reference);
+            } else {
+                did_doc.add_authentication_ref(vm_reference.clone());
+                did_doc.add_assertion_method_ref(vm_reference.clone());
+                did_doc.add_capability_invocation_ref(vm_reference.clone());
+                did_doc.add_capability_delegation_ref(vm_reference);
+            }
+        }
+        if *key.key_type() == KeyType::Ed25519 {
+            let key_agreement_key = key.ed25519_to_x25519()?;
+            let vms = get_verification_methods_by_key(
+                &key_agreement_key,
+                self.did(),
+                public_key_encoding,
+                &mut vm_index,
+            )?;
+            for vm in vms.into_iter() {
+                did_doc.add_key_agreement_ref(vm.id().to_owned());
+                did_doc.add_verification_method(vm);
+            }
+        }
+        Ok(did_doc)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use did_doc::schema::verification_method::PublicKeyField;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    // https://identity.foundation/peer-did-method-spec/#method-0-inception-key-without-doc
+    const VALID_PEER_DID_NUMALGO0: &str =
+        "did:peer:0z6MkpTHR8VNsBxYAAWHut2Geadd9jSwuBV8xRoAnwWsdvktH";
+    const DERIVED_X25519_FINGERPRINT: &str = "z6LSbysY2xFMRpGMhb7tFTLMpeuPRaqaWM1yECx2AtzE3KCc";
+
+    #[test]
+    fn test_from_key() {
+        let key =
+            Key::from_fingerprint("z6MkpTHR8VNsBxYAAWHut2Geadd9jSwuBV8xRoAnwWsdvktH").unwrap();
+        let peer_did = PeerDid::<Numalgo0>::from_key(&key).unwrap();
+        assert_eq!(peer_did.to_string(), VALID_PEER_DID_NUMALGO0);
+        assert_eq!(peer_did.key().unwrap(), key);
+    }
+
+    #[test]
+    fn test_resolve_ed25519() {
+        let peer_did = PeerDid::<Numalgo0>::parse(VALID_PEER_DID_NUMALGO0).unwrap();
+        let did_doc = peer_did.to_did_doc(PublicKeyEncoding::Multibase).unwrap();
+
+        assert_eq!(did_doc.id(), peer_did.did());
+        assert_eq!(did_doc.verification_method().len(), 2);
+        let vm = did_doc.verification_method_by_id("key-1").unwrap();
+        assert_eq!(
+            vm.public_key_field(),
+            &PublicKeyField::Multibase {
+                public_key_multibase: "z6MkpTHR8VNsBxYAAWHut2Geadd9jSwuBV8xRoAnwWsdvktH"
+                    .to_string()
+            }
+        );
+        assert_eq!(did_doc.authentication().len(), 1);
+        assert_eq!(did_doc.assertion_method().len(), 1);
+        let vm = did_doc.verification_method_by_id("key-2").unwrap();
+        assert_eq!(
+            vm.public_key_field(),
+            &PublicKeyField::Multibase {
+                public_key_multibase: DERIVED_X25519_FINGERPRINT.to_string()
+            }
+        );
+        assert_eq!(did_doc.key_agreement().len(), 1);
+    }
+
+    #[test]
+    fn test_resolve_x25519() {
+        let peer_did = PeerDid::<Numalgo0>::parse(
+            "did:peer:0z6LSbysY2xFMRpGMhb7tFTLMpeuPRaqaWM1yECx2AtzE3KCc",
+        )
+        .unwrap();
+        let did_doc = peer_did.to_did_doc(PublicKeyEncoding::Base58).unwrap();
+
+        assert_eq!(did_doc.key_agreement().len(), 1);
+        assert!(did_doc.authentication().is_empty());
+    }
+
+    #[test]
+    fn test_resolve_invalid_key() {
+        let peer_did = PeerDid::<Numalgo0>::parse("did:peer:0z6Mkp").unwrap();
+        assert!(matches!(
+            peer_did.to_did_doc(PublicKeyEncoding::Multibase),
+            Err(DidPeerError::PublicKeyError(_))
+        ));
+    }
+}
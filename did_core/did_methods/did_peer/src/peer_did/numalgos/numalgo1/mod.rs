@@ -1,4 +1,11 @@
-use crate::peer_did::numalgos::Numalgo;
+use did_doc::schema::did_doc::DidDocument;
+use sha2::{Digest, Sha256};
+
+use crate::{
+    error::DidPeerError,
+    helpers::MULTIHASH_SHA2_256,
+    peer_did::{numalgos::Numalgo, FromDidDoc, PeerDid},
+};
 
 #[derive(Clone, Copy, Default, Debug, PartialEq)]
 pub struct Numalgo1;
@@ -6,3 +13,115 @@ pub struct Numalgo1;
 impl Numalgo for Numalgo1 {
     const NUMALGO_CHAR: char = '1';
 }
+
+impl FromDidDoc for Numalgo1 {
+    /// Serializes the document without `id` as its genesis version. The genesis version has to be
+    /// kept (see [`PeerDid::<Numalgo1>::genesis_doc`]) to resolve the DID later.
+    fn from_did_doc(did_document: DidDocument) -> Result<PeerDid<Numalgo1>, DidPeerError> {
+        PeerDid::<Numalgo1>::from_genesis_doc(&PeerDid::<Numalgo1>::genesis_doc(did_document)?)
+    }
+}
+
+impl PeerDid<Numalgo1> {
+    /// Stored form of the genesis version of the document: JSON without `id`
+    pub fn genesis_doc(did_document: DidDocument) -> Result<Vec<u8>, DidPeerError> {
+        let mut value = serde_json::to_value(did_document)?;
+        if let Some(object) = value.as_object_mut() {
+            object.remove("id");
+        }
+        Ok(serde_json::to_vec(&value)?)
+    }
+
+    /// Implementation of did:peer:1 creation spec, `genesis_doc` being the stored variant:
+    /// https://identity.foundation/peer-did-method-spec/#method-1-genesis-doc
+    pub fn from_genesis_doc(genesis_doc: &[u8]) -> Result<Self, DidPeerError> {
+        PeerDid::<Numalgo1>::parse(format!("did:peer:1{}", hash_genesis_doc(genesis_doc)))
+    }
+
+    /// Resolves DID document from the stored genesis version, which must hash to this DID
+    pub fn resolve_did_doc(&self, genesis_doc: &[u8]) -> Result<DidDocument, DidPeerError> {
+        let hash = hash_genesis_doc(genesis_doc);
+        if self.did().id()[1..] != hash {
+            return Err(DidPeerError::DidValidationError(format!(
+                "Genesis document hash {} doesn't match peer did: {}",
+                hash,
+                self.did()
+            )));
+        }
+        let mut did_doc: DidDocument = serde_json::from_slice(genesis_doc)?;
+        did_doc.set_id(self.did().clone());
+        Ok(did_doc)
+    }
+}
+
+fn hash_genesis_doc(genesis_doc: &[u8]) -> String {
+    let digest = {
+        let mut hasher = Sha256::new();
+        hasher.update(genesis_doc);
+        hasher.finalize()
+    };
+    multibase::encode(
+        multibase::Base::Base58Btc,
+        [MULTIHASH_SHA2_256.as_slice(), &digest].concat(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use did_doc::schema::verification_method::{
+        PublicKeyField, VerificationMethod, VerificationMethodType,
+    };
+    use did_parser_nom::{Did, DidUrl};
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn genesis_did_doc() -> DidDocument {
+        let mut did_doc = DidDocument::default();
+        let vm = VerificationMethod::builder()
+            .id(DidUrl::from_fragment("key-1".to_string()).unwrap())
+            .controller(
+                Did::parse("did:key:z6MkpTHR8VNsBxYAAWHut2Geadd9jSwuBV8xRoAnwWsdvktH".to_string())
+                    .unwrap(),
+            )
+            .verification_method_type(VerificationMethodType::Ed25519VerificationKey2020)
+            .public_key(PublicKeyField::Multibase {
+                public_key_multibase: "z6MkpTHR8VNsBxYAAWHut2Geadd9jSwuBV8xRoAnwWsdvktH"
+                    .to_string(),
+            })
+            .build();
+        did_doc.add_verification_method(vm);
+        did_doc.add_authentication_ref(DidUrl::from_fragment("key-1".to_string()).unwrap());
+        did_doc
+    }
+
+    #[test]
+    fn test_create_and_resolve_numalgo1() {
+        let genesis_doc = PeerDid::<Numalgo1>::genesis_doc(genesis_did_doc()).unwrap();
+        let peer_did = PeerDid::<Numalgo1>::from_genesis_doc(&genesis_doc).unwrap();
+        assert!(peer_did.to_string().starts_with("did:peer:1zQm"));
+        assert_eq!(
+            peer_did,
+            PeerDid::<Numalgo1>::from_did_doc(genesis_did_doc()).unwrap()
+        );
+
+        let did_doc = peer_did.resolve_did_doc(&genesis_doc).unwrap();
+        assert_eq!(did_doc.id(), peer_did.did());
+        assert_eq!(
+            did_doc.verification_method(),
+            genesis_did_doc().verification_method()
+        );
+        assert_eq!(did_doc.authentication(), genesis_did_doc().authentication());
+    }
+
+    #[test]
+    fn test_resolve_numalgo1_tampered_genesis_doc() {
+        let genesis_doc = PeerDid::<Numalgo1>::genesis_doc(genesis_did_doc()).unwrap();
+        let peer_did = PeerDid::<Numalgo1>::from_genesis_doc(&genesis_doc).unwrap();
+        let tampered = PeerDid::<Numalgo1>::genesis_doc(DidDocument::default()).unwrap();
+        assert!(matches!(
+            peer_did.resolve_did_doc(&tampered),
+            Err(DidPeerError::DidValidationError(_))
+        ));
+    }
+}
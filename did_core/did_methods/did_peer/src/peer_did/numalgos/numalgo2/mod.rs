@@ -16,7 +16,7 @@ mod encoding;
 mod helpers;
 mod purpose;
 mod service_abbreviation;
-mod verification_method;
+pub(crate) mod verification_method;
 
 impl FromDidDoc for Numalgo2 {
     fn from_did_doc(did_document: DidDocument) -> Result<PeerDid<Numalgo2>, DidPeerError> {
@@ -20,6 +20,7 @@ pub fn get_verification_methods_by_key(
         KeyType::P256 => VerificationMethodType::JsonWebKey2020,
         KeyType::P384 => VerificationMethodType::JsonWebKey2020,
         KeyType::P521 => VerificationMethodType::JsonWebKey2020,
+        KeyType::Secp256k1 => VerificationMethodType::EcdsaSecp256k1VerificationKey2019,
         KeyType::Bls12381g1g2 => {
             return Ok(build_verification_methods_from_bls_multikey(
                 &Key::new(key.key()[..48].to_vec(), KeyType::Bls12381g1)?,
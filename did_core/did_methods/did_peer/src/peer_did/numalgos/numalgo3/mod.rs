@@ -6,6 +6,7 @@ use crate::{
         numalgos::{numalgo2::Numalgo2, Numalgo},
         FromDidDoc, PeerDid,
     },
+    resolver::options::PublicKeyEncoding,
 };
 
 #[derive(Clone, Copy, Default, Debug, PartialEq)]
@@ -21,13 +22,68 @@ impl FromDidDoc for Numalgo3 {
     }
 }
 
+impl PeerDid<Numalgo3> {
+    /// Resolves DID document of the numalgo 2 DID this DID is the short form of, with this DID as
+    /// `id` and the numalgo 2 DID as `alsoKnownAs`
+    pub(crate) fn resolve_did_doc(
+        &self,
+        numalgo2: &PeerDid<Numalgo2>,
+        public_key_encoding: PublicKeyEncoding,
+    ) -> Result<DidDocument, DidPeerError> {
+        if &numalgo2.to_numalgo3()? != self {
+            return Err(DidPeerError::DidValidationError(format!(
+                "Peer did {} is not the short form of {}",
+                self.did(),
+                numalgo2.did()
+            )));
+        }
+        let mut did_doc = numalgo2.to_did_doc_builder(public_key_encoding)?;
+        did_doc.set_id(self.did().clone());
+        did_doc.add_also_known_as(numalgo2.to_string().parse()?);
+        Ok(did_doc)
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::peer_did::{
-        numalgos::{numalgo2::Numalgo2, numalgo3::Numalgo3},
-        PeerDid,
+    use pretty_assertions::assert_eq;
+
+    use crate::{
+        error::DidPeerError,
+        peer_did::{
+            numalgos::{numalgo2::Numalgo2, numalgo3::Numalgo3},
+            PeerDid,
+        },
+        resolver::options::PublicKeyEncoding,
     };
 
+    const FULL_DID2: &str = "did:peer:2.Ez6LSbysY2xFMRpGMhb7tFTLMpeuPRaqaWM1yECx2AtzE3KCc.Vz6MkqRYqQiSgvZQdnBytw86Qbs2ZWUkGv22od935YF4s8M7V.Vz6MkgoLTnTypo3tDRwCkZXSccTPHRLhF4ZnjhueYAFpEX6vg.SeyJ0IjoiZG0iLCJzIjoiaHR0cHM6Ly9leGFtcGxlLmNvbS9lbmRwb2ludCIsInIiOlsiZGlkOmV4YW1wbGU6c29tZW1lZGlhdG9yI3NvbWVrZXkiXSwiYSI6WyJkaWRjb21tL3YyIiwiZGlkY29tbS9haXAyO2Vudj1yZmM1ODciXX0";
+
+    #[test]
+    fn test_resolve_numalgo3() {
+        let peer_did_2 = PeerDid::<Numalgo2>::parse(FULL_DID2).unwrap();
+        let peer_did_3 = peer_did_2.to_numalgo3().unwrap();
+        let did_doc = peer_did_3
+            .resolve_did_doc(&peer_did_2, PublicKeyEncoding::Multibase)
+            .unwrap();
+        assert_eq!(did_doc.id(), peer_did_3.did());
+        assert_eq!(did_doc.also_known_as()[0].to_string(), FULL_DID2);
+        assert_eq!(did_doc.verification_method().len(), 3);
+    }
+
+    #[test]
+    fn test_resolve_numalgo3_mismatched_numalgo2() {
+        let peer_did_2 = PeerDid::<Numalgo2>::parse(FULL_DID2).unwrap();
+        let peer_did_3 = PeerDid::<Numalgo3>::parse(
+            "did:peer:3zQmfUHk2UkVZwkMwXLRCJrTmEkxpNdfare68fLo3YUwWryp".to_string(),
+        )
+        .unwrap();
+        assert!(matches!(
+            peer_did_3.resolve_did_doc(&peer_did_2, PublicKeyEncoding::Multibase),
+            Err(DidPeerError::DidValidationError(_))
+        ));
+    }
+
     #[test]
     fn test_generate_numalgo3() {
         // from spec: https://identity.foundation/peer-did-method-spec/#method-3-did-shortening-with-sha-256-hash
@@ -1,3 +1,5 @@
+use std::sync::Arc;
+
 use async_trait::async_trait;
 use did_doc::schema::did_doc::DidDocument;
 use did_parser_nom::Did;
@@ -11,17 +13,33 @@ use did_resolver::{
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    error::DidPeerError, peer_did::generic::AnyPeerDid, resolver::options::PublicKeyEncoding,
+    error::DidPeerError,
+    peer_did::generic::AnyPeerDid,
+    resolver::{options::PublicKeyEncoding, store::PeerDidStore},
 };
 
 pub mod options;
+pub mod store;
 
 #[derive(Default)]
-pub struct PeerDidResolver;
+pub struct PeerDidResolver {
+    store: Option<Arc<dyn PeerDidStore>>,
+}
 
 impl PeerDidResolver {
     pub fn new() -> Self {
-        Self
+        Self { store: None }
+    }
+
+    /// Resolver able to resolve numalgo 1 and 3 DIDs found in the store
+    pub fn with_store(store: Arc<dyn PeerDidStore>) -> Self {
+        Self { store: Some(store) }
+    }
+
+    fn store(&self, peer_did: &AnyPeerDid) -> Result<&dyn PeerDidStore, DidPeerError> {
+        self.store
+            .as_deref()
+            .ok_or(DidPeerError::StoreRequired(peer_did.numalgo()))
     }
 }
 
@@ -39,16 +57,33 @@ impl DidResolvable for PeerDidResolver {
         options: &Self::DidResolutionOptions,
     ) -> Result<DidResolutionOutput, GenericError> {
         let peer_did = AnyPeerDid::parse(did.to_owned())?;
-        let did_doc = match peer_did {
-            AnyPeerDid::Numalgo2(peer_did) => {
-                let encoding = options.encoding.unwrap_or(PublicKeyEncoding::Multibase);
-                let mut did_doc: DidDocument = peer_did.to_did_doc_builder(encoding)?;
-                did_doc.add_also_known_as(peer_did.to_numalgo3()?.to_string().parse()?);
+        let encoding = options.encoding.unwrap_or(PublicKeyEncoding::Multibase);
+        let did_doc = match &peer_did {
+            AnyPeerDid::Numalgo0(numalgo0) => numalgo0.to_did_doc(encoding)?,
+            AnyPeerDid::Numalgo1(numalgo1) => {
+                let genesis_doc = self
+                    .store(&peer_did)?
+                    .get_genesis_doc(numalgo1)
+                    .await?
+                    .ok_or_else(|| DidPeerError::NotFoundInStore(numalgo1.to_string()))?;
+                numalgo1.resolve_did_doc(&genesis_doc)?
+            }
+            AnyPeerDid::Numalgo2(numalgo2) => {
+                let mut did_doc: DidDocument = numalgo2.to_did_doc_builder(encoding)?;
+                did_doc.add_also_known_as(numalgo2.to_numalgo3()?.to_string().parse()?);
                 did_doc
             }
-            AnyPeerDid::Numalgo4(peer_did) => peer_did.resolve_did_doc()?,
-            n => return Err(Box::new(DidPeerError::UnsupportedNumalgo(n.numalgo()))),
+            AnyPeerDid::Numalgo3(numalgo3) => {
+                let numalgo2 = self
+                    .store(&peer_did)?
+                    .get_numalgo2(numalgo3)
+                    .await?
+                    .ok_or_else(|| DidPeerError::NotFoundInStore(numalgo3.to_string()))?;
+                numalgo3.resolve_did_doc(&numalgo2, encoding)?
+            }
+            AnyPeerDid::Numalgo4(numalgo4) => numalgo4.resolve_did_doc()?,
         };
+        did_doc.validate()?;
         let resolution_metadata = DidResolutionMetadata::builder()
             .content_type("application/did+json".to_string())
             .build();
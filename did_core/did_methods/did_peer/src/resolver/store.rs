@@ -0,0 +1,91 @@
+use std::{collections::HashMap, sync::RwLock};
+
+use async_trait::async_trait;
+use did_resolver::error::GenericError;
+
+use crate::{
+    error::DidPeerError,
+    peer_did::{
+        numalgos::{numalgo1::Numalgo1, numalgo2::Numalgo2, numalgo3::Numalgo3},
+        PeerDid,
+    },
+};
+
+/// Storage of previously seen peer DIDs, needed to resolve numalgo 1 and 3 DIDs which
+/// can't be resolved from the DID alone
+#[async_trait]
+pub trait PeerDidStore: Send + Sync {
+    /// Numalgo 2 DID whose short form is the given numalgo 3 DID
+    async fn get_numalgo2(
+        &self,
+        did: &PeerDid<Numalgo3>,
+    ) -> Result<Option<PeerDid<Numalgo2>>, GenericError>;
+
+    /// Stored form of the genesis version of the DID document of the given numalgo 1 DID
+    async fn get_genesis_doc(
+        &self,
+        did: &PeerDid<Numalgo1>,
+    ) -> Result<Option<Vec<u8>>, GenericError>;
+}
+
+#[derive(Default)]
+pub struct InMemoryPeerDidStore {
+    numalgo2_by_numalgo3: RwLock<HashMap<String, PeerDid<Numalgo2>>>,
+    genesis_docs: RwLock<HashMap<String, Vec<u8>>>,
+}
+
+impl InMemoryPeerDidStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Remembers numalgo 2 DID, returns its numalgo 3 short form under which it can be resolved
+    pub fn add_numalgo2(
+        &self,
+        peer_did: PeerDid<Numalgo2>,
+    ) -> Result<PeerDid<Numalgo3>, DidPeerError> {
+        let numalgo3 = peer_did.to_numalgo3()?;
+        self.numalgo2_by_numalgo3
+            .write()
+            .map_err(|err| DidPeerError::GeneralError(err.to_string()))?
+            .insert(numalgo3.to_string(), peer_did);
+        Ok(numalgo3)
+    }
+
+    /// Remembers genesis document in its stored form, returns numalgo 1 DID derived from it
+    pub fn add_genesis_doc(&self, genesis_doc: Vec<u8>) -> Result<PeerDid<Numalgo1>, DidPeerError> {
+        let peer_did = PeerDid::<Numalgo1>::from_genesis_doc(&genesis_doc)?;
+        self.genesis_docs
+            .write()
+            .map_err(|err| DidPeerError::GeneralError(err.to_string()))?
+            .insert(peer_did.to_string(), genesis_doc);
+        Ok(peer_did)
+    }
+}
+
+#[async_trait]
+impl PeerDidStore for InMemoryPeerDidStore {
+    async fn get_numalgo2(
+        &self,
+        did: &PeerDid<Numalgo3>,
+    ) -> Result<Option<PeerDid<Numalgo2>>, GenericError> {
+        Ok(self
+            .numalgo2_by_numalgo3
+            .read()
+            .map_err(|err| DidPeerError::GeneralError(err.to_string()))?
+            .get(&did.to_string())
+            .cloned())
+    }
+
+    async fn get_genesis_doc(
+        &self,
+        did: &PeerDid<Numalgo1>,
+    ) -> Result<Option<Vec<u8>>, GenericError> {
+        Ok(self
+            .genesis_docs
+            .read()
+            .map_err(|err| DidPeerError::GeneralError(err.to_string()))?
+            .get(&did.to_string())
+            .cloned())
+    }
+}
@@ -1,8 +1,13 @@
 mod fixtures;
 
+use std::sync::Arc;
+
 use did_peer::{
     error::DidPeerError,
-    resolver::{options::PublicKeyEncoding, PeerDidResolutionOptions, PeerDidResolver},
+    resolver::{
+        options::PublicKeyEncoding, store::InMemoryPeerDidStore, PeerDidResolutionOptions,
+        PeerDidResolver,
+    },
 };
 use did_resolver::traits::resolvable::DidResolvable;
 use tokio::test;
@@ -11,7 +16,7 @@ async fn resolve_error(peer_did: &str) -> DidPeerError {
     let options = PeerDidResolutionOptions {
         encoding: Some(PublicKeyEncoding::Multibase),
     };
-    *PeerDidResolver
+    *PeerDidResolver::new()
         .resolve(&peer_did.parse().unwrap(), &options)
         .await
         .unwrap_err()
@@ -104,3 +109,24 @@ async fn test_resolve_numalgo_2_invalid_7() {
         DidPeerError::PublicKeyError(_)
     ));
 }
+
+#[test]
+async fn test_resolve_numalgo_3_without_store() {
+    let peer_did = "did:peer:3zQmdysQimott3jS93beGPVX8sTRSRFJWt1FsihPcSy9kZfB";
+    assert!(matches!(
+        resolve_error(peer_did).await,
+        DidPeerError::StoreRequired(_)
+    ));
+}
+
+#[test]
+async fn test_resolve_numalgo_3_not_in_store() {
+    let peer_did = "did:peer:3zQmdysQimott3jS93beGPVX8sTRSRFJWt1FsihPcSy9kZfB";
+    let error = PeerDidResolver::with_store(Arc::new(InMemoryPeerDidStore::new()))
+        .resolve(&peer_did.parse().unwrap(), &Default::default())
+        .await
+        .unwrap_err()
+        .downcast::<DidPeerError>()
+        .unwrap();
+    assert!(matches!(*error, DidPeerError::NotFoundInStore(_)));
+}
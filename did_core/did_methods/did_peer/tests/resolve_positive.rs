@@ -1,20 +1,31 @@
 mod fixtures;
 
+use std::sync::Arc;
+
 use did_doc::schema::did_doc::DidDocument;
-use did_peer::resolver::{options::PublicKeyEncoding, PeerDidResolutionOptions, PeerDidResolver};
+use did_peer::{
+    peer_did::{numalgos::numalgo2::Numalgo2, PeerDid},
+    resolver::{
+        options::PublicKeyEncoding, store::InMemoryPeerDidStore, PeerDidResolutionOptions,
+        PeerDidResolver,
+    },
+};
 use did_resolver::traits::resolvable::DidResolvable;
 use pretty_assertions::assert_eq;
 use tokio::test;
 
 use crate::fixtures::{
     basic::{DID_DOC_BASIC, PEER_DID_NUMALGO_2_BASIC},
+    multiple_services::DID_DOC_MULTIPLE_SERVICES,
     no_routing_keys::{DID_DOC_NO_ROUTING_KEYS, PEER_DID_NUMALGO_2_NO_ROUTING_KEYS},
-    no_services::{DID_DOC_NO_SERVICES, PEER_DID_NUMALGO_2_NO_SERVICES},
+    no_services::{
+        DID_DOC_NO_SERVICES, PEER_DID_NUMALGO_2_NO_SERVICES, PEER_DID_NUMALGO_3_NO_SERVICES,
+    },
 };
 
 async fn resolve_positive_test(did_doc: &str, peer_did: &str, options: PeerDidResolutionOptions) {
     let did_document_expected = serde_json::from_str::<DidDocument>(did_doc).unwrap();
-    let resolution = PeerDidResolver
+    let resolution = PeerDidResolver::new()
         .resolve(&peer_did.parse().unwrap(), &options)
         .await
         .unwrap();
@@ -49,3 +60,117 @@ async fn test_resolve_numalgo2_no_services() {
     };
     resolve_positive_test(DID_DOC_NO_SERVICES, PEER_DID_NUMALGO_2_NO_SERVICES, options).await;
 }
+
+#[test]
+async fn test_resolve_numalgo0() {
+    let peer_did = "did:peer:0z6MkpTHR8VNsBxYAAWHut2Geadd9jSwuBV8xRoAnwWsdvktH";
+    let did_document_expected = serde_json::json!({
+        "id": peer_did,
+        "verificationMethod": [
+            {
+                "id": "#key-1",
+                "controller": peer_did,
+                "type": "Ed25519VerificationKey2020",
+                "publicKeyMultibase": "z6MkpTHR8VNsBxYAAWHut2Geadd9jSwuBV8xRoAnwWsdvktH"
+            },
+            {
+                "id": "#key-2",
+                "controller": peer_did,
+                "type": "X25519KeyAgreementKey2020",
+                "publicKeyMultibase": "z6LSbysY2xFMRpGMhb7tFTLMpeuPRaqaWM1yECx2AtzE3KCc"
+            }
+        ],
+        "authentication": ["#key-1"],
+        "assertionMethod": ["#key-1"],
+        "capabilityInvocation": ["#key-1"],
+        "capabilityDelegation": ["#key-1"],
+        "keyAgreement": ["#key-2"]
+    });
+    let resolution = PeerDidResolver::new()
+        .resolve(&peer_did.parse().unwrap(), &Default::default())
+        .await
+        .unwrap();
+    assert_eq!(
+        resolution.did_document,
+        serde_json::from_value::<DidDocument>(did_document_expected).unwrap()
+    );
+}
+
+#[test]
+async fn test_resolve_numalgo1_from_store() {
+    let store = Arc::new(InMemoryPeerDidStore::new());
+    let genesis_doc = serde_json::to_vec(&serde_json::json!({
+        "verificationMethod": [
+            {
+                "id": "#key-1",
+                "controller": "did:key:z6MkpTHR8VNsBxYAAWHut2Geadd9jSwuBV8xRoAnwWsdvktH",
+                "type": "Ed25519VerificationKey2020",
+                "publicKeyMultibase": "z6MkpTHR8VNsBxYAAWHut2Geadd9jSwuBV8xRoAnwWsdvktH"
+            }
+        ],
+        "authentication": ["#key-1"]
+    }))
+    .unwrap();
+    let peer_did = store.add_genesis_doc(genesis_doc).unwrap();
+
+    let resolution = PeerDidResolver::with_store(store)
+        .resolve(peer_did.did(), &Default::default())
+        .await
+        .unwrap();
+    assert_eq!(resolution.did_document.id(), peer_did.did());
+    assert_eq!(resolution.did_document.verification_method().len(), 1);
+    assert_eq!(resolution.did_document.authentication().len(), 1);
+}
+
+#[test]
+async fn test_resolve_numalgo3_from_store() {
+    let store = Arc::new(InMemoryPeerDidStore::new());
+    let numalgo2 = PeerDid::<Numalgo2>::parse(PEER_DID_NUMALGO_2_NO_SERVICES).unwrap();
+    let numalgo3 = store.add_numalgo2(numalgo2).unwrap();
+    assert_eq!(numalgo3.to_string(), PEER_DID_NUMALGO_3_NO_SERVICES);
+
+    let mut did_document_expected =
+        serde_json::from_str::<DidDocument>(DID_DOC_NO_SERVICES).unwrap();
+    did_document_expected.set_id(numalgo3.did().clone());
+    did_document_expected.set_also_known_as(vec![PEER_DID_NUMALGO_2_NO_SERVICES.parse().unwrap()]);
+
+    let options = PeerDidResolutionOptions {
+        encoding: Some(PublicKeyEncoding::Multibase),
+    };
+    let resolution = PeerDidResolver::with_store(store)
+        .resolve(numalgo3.did(), &options)
+        .await
+        .unwrap();
+    assert_eq!(resolution.did_document, did_document_expected);
+}
+
+// https://github.com/hyperledger/aries-cloudagent-python did:peer:4 test vector
+const PEER_DID_NUMALGO_4_ACAPY: &str = "did:peer:4zQmcQCH8nWEBBA6BpSEDxHyhPwHdi5CVGcvsZcjhb618zbA:z5CTtVoAxKjH1V1sKizLy5kLvV6AbmACYfcGmfVUDGn4A7BpnVQEESXEYYUG7W479kDHaqLnk7NJuu4w7ftTd9REipB2CQgW9fjzPvmsXyyHzot9o1tgYHNnqFDXgCXwFYJfjkzz3m6mex1WMN4XHWWNM4NB7exDA2maVGis7gJnVAiNrBExaihyeKJ4nBXrB3ArQ1TyuZ39F9qTeCSrBntTTa85wtUtHz5M1oE7Sj1CZeAEQzDnAMToP9idSrSXUo5z8q9Un325d8MtQgxyKGW2a9VYyW189C722GKQbGQSU3dRSwCanVHJwCh9q2G2eNVPeuydAHXmouCUCq3cVHeUkatv73DSoBV17LEJgq8dAYfvSAutG7LFyvrRW5wNjcQMT7WdFHRCqhtzz18zu6fSTQWM4PQPLMVEaKbs51EeYGiGurhu1ChQMjXqnpcRcpCP7RAEgyWSjMER6e3gdCVsBhQSoqGk1UN8NfVah8pxGg2i5Gd1754Ys6aBEhTashFa47Ke7oPoZ6LZiRMETYhUr1cQY65TQhMzyrR6RzLudeRVgcRdKiTTmP2fFi5H8nCHPSGb4wncUxgn3N5CbFaUC";
+
+// Documents were resolved before the resolver validated them, none of them may be rejected now
+#[test]
+async fn test_resolved_documents_validate() {
+    for did_doc in [
+        DID_DOC_BASIC,
+        DID_DOC_MULTIPLE_SERVICES,
+        DID_DOC_NO_ROUTING_KEYS,
+        DID_DOC_NO_SERVICES,
+    ] {
+        let did_doc = serde_json::from_str::<DidDocument>(did_doc).unwrap();
+        did_doc.validate().unwrap();
+    }
+
+    for peer_did in [
+        "did:peer:0z6MkpTHR8VNsBxYAAWHut2Geadd9jSwuBV8xRoAnwWsdvktH",
+        PEER_DID_NUMALGO_2_BASIC,
+        PEER_DID_NUMALGO_2_NO_ROUTING_KEYS,
+        PEER_DID_NUMALGO_2_NO_SERVICES,
+        PEER_DID_NUMALGO_4_ACAPY,
+    ] {
+        let resolution = PeerDidResolver::new()
+            .resolve(&peer_did.parse().unwrap(), &Default::default())
+            .await
+            .unwrap_or_else(|err| panic!("{peer_did} was rejected: {err}"));
+        resolution.did_document.validate().unwrap();
+    }
+}
@@ -0,0 +1,17 @@
+use did_resolver::did_doc::schema::validation::DidDocumentValidationError;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[non_exhaustive]
+pub enum DidEthrResolverError {
+    #[error("DID method not supported: {0}")]
+    MethodNotSupported(String),
+    #[error("Network not supported: {0}")]
+    NetworkNotSupported(String),
+    #[error("Invalid identifier: {0}")]
+    InvalidIdentifier(String),
+    #[error("Invalid DID document: {0}")]
+    InvalidDidDocument(#[from] DidDocumentValidationError),
+    #[error("DID parser error: {0}")]
+    DidParserError(#[from] did_resolver::did_parser_nom::ParseError),
+}
@@ -0,0 +1,2 @@
+mod network;
+pub mod resolver;
@@ -0,0 +1,50 @@
+use crate::error::DidEthrResolverError;
+
+const MAINNET: u64 = 1;
+
+// https://github.com/decentralized-identity/ethr-did-resolver/blob/master/src/config/deployments.ts
+const NAMED_NETWORKS: [(&str, u64); 6] = [
+    ("mainnet", MAINNET),
+    ("goerli", 5),
+    ("sepolia", 11155111),
+    ("rsk", 30),
+    ("polygon", 137),
+    ("linea", 59144),
+];
+
+/// Returns chain id of the network named by the DID, which is either a known network name or
+/// a hex encoded chain id. DIDs without network refer to the mainnet.
+pub(super) fn chain_id(network: Option<&str>) -> Result<u64, DidEthrResolverError> {
+    let Some(network) = network else {
+        return Ok(MAINNET);
+    };
+    if let Some(hex_chain_id) = network.strip_prefix("0x") {
+        return u64::from_str_radix(hex_chain_id, 16)
+            .map_err(|_| DidEthrResolverError::NetworkNotSupported(network.to_string()));
+    }
+    NAMED_NETWORKS
+        .iter()
+        .find(|(name, _)| *name == network)
+        .map(|(_, chain_id)| *chain_id)
+        .ok_or_else(|| DidEthrResolverError::NetworkNotSupported(network.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chain_id() {
+        assert_eq!(chain_id(None).unwrap(), 1);
+        assert_eq!(chain_id(Some("mainnet")).unwrap(), 1);
+        assert_eq!(chain_id(Some("sepolia")).unwrap(), 11155111);
+        assert_eq!(chain_id(Some("0x5")).unwrap(), 5);
+        assert_eq!(chain_id(Some("0xaa36a7")).unwrap(), 11155111);
+    }
+
+    #[test]
+    fn test_chain_id_unknown_network() {
+        assert!(chain_id(Some("foo")).is_err());
+        assert!(chain_id(Some("0xzz")).is_err());
+    }
+}
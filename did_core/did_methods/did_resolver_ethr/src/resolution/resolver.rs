@@ -0,0 +1,290 @@
+use async_trait::async_trait;
+use did_resolver::{
+    did_doc::schema::{
+        contexts,
+        did_doc::DidDocument,
+        verification_method::{PublicKeyField, VerificationMethod, VerificationMethodType},
+    },
+    did_parser_nom::{Did, DidUrl},
+    error::GenericError,
+    traits::resolvable::{
+        resolution_metadata::DidResolutionMetadata, resolution_output::DidResolutionOutput,
+        DidResolvable,
+    },
+};
+use k256::{elliptic_curve::sec1::ToEncodedPoint, PublicKey};
+use serde_json::json;
+use sha3::{Digest, Keccak256};
+
+use super::network::chain_id;
+use crate::error::DidEthrResolverError;
+
+const ADDRESS_LENGTH: usize = 20;
+const COMPRESSED_PUBLIC_KEY_LENGTH: usize = 33;
+
+/// Resolves did:ethr DIDs to the document they have before any change is made in the registry
+/// contract, which is derived from the identifier alone, see
+/// https://github.com/decentralized-identity/ethr-did-resolver/blob/master/doc/did-method-spec.md#read-resolve
+///
+/// Owner changes, delegates and attributes published on chain are not taken into account.
+#[derive(Default)]
+pub struct DidEthrResolver;
+
+impl DidEthrResolver {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl DidResolvable for DidEthrResolver {
+    type DidResolutionOptions = ();
+
+    async fn resolve(
+        &self,
+        did: &Did,
+        _options: &Self::DidResolutionOptions,
+    ) -> Result<DidResolutionOutput, GenericError> {
+        let did_doc = resolve_did_doc(did)?;
+        did_doc.validate()?;
+        let resolution_metadata = DidResolutionMetadata::builder()
+            .content_type("application/did+json".to_string())
+            .build();
+        Ok(DidResolutionOutput::builder(did_doc)
+            .did_resolution_metadata(resolution_metadata)
+            .build())
+    }
+}
+
+fn resolve_did_doc(did: &Did) -> Result<DidDocument, DidEthrResolverError> {
+    if did.method() != Some("ethr") {
+        return Err(DidEthrResolverError::MethodNotSupported(format!(
+            "{:?}",
+            did.method()
+        )));
+    }
+    let chain_id = chain_id(did.namespace())?;
+    let identifier = did
+        .id()
+        .strip_prefix("0x")
+        .and_then(|id| hex::decode(id).ok())
+        .ok_or_else(|| DidEthrResolverError::InvalidIdentifier(did.id().to_string()))?;
+    let (address, public_key) = match identifier.len() {
+        ADDRESS_LENGTH => (identifier, None),
+        COMPRESSED_PUBLIC_KEY_LENGTH => (public_key_to_address(&identifier)?, Some(identifier)),
+        len => {
+            return Err(DidEthrResolverError::InvalidIdentifier(format!(
+                "expected address or compressed public key, got {len} bytes"
+            )))
+        }
+    };
+
+    let mut did_doc = DidDocument::new(did.to_owned());
+    let mut context = vec![contexts::W3C_DID_V1];
+
+    let controller_id = DidUrl::parse(format!("{did}#controller"))?;
+    did_doc.add_verification_method(
+        VerificationMethod::builder()
+            .id(controller_id.clone())
+            .controller(did.to_owned())
+            .verification_method_type(VerificationMethodType::EcdsaSecp256k1RecoveryMethod2020)
+            .public_key(PublicKeyField::BlockchainAccountId {
+                blockchain_account_id: format!(
+                    "eip155:{chain_id}:{}",
+                    to_checksum_address(&address)
+                ),
+            })
+            .build(),
+    );
+    did_doc.add_authentication_ref(controller_id.clone());
+    did_doc.add_assertion_method_ref(controller_id);
+    context.push(contexts::W3C_SUITE_SECP259K1_RECOVERY_2020);
+
+    if let Some(public_key) = public_key {
+        let controller_key_id = DidUrl::parse(format!("{did}#controllerKey"))?;
+        did_doc.add_verification_method(
+            VerificationMethod::builder()
+                .id(controller_key_id.clone())
+                .controller(did.to_owned())
+                .verification_method_type(VerificationMethodType::EcdsaSecp256k1VerificationKey2019)
+                .public_key(PublicKeyField::Hex {
+                    public_key_hex: hex::encode(public_key),
+                })
+                .build(),
+        );
+        did_doc.add_authentication_ref(controller_key_id.clone());
+        did_doc.add_assertion_method_ref(controller_key_id);
+        context.push(contexts::W3C_SUITE_SECP256K1_2019);
+    }
+
+    did_doc.set_extra_field(String::from("@context"), json!(context));
+
+    Ok(did_doc)
+}
+
+/// Ethereum address is the last 20 bytes of the Keccak-256 hash of the uncompressed public key
+fn public_key_to_address(public_key: &[u8]) -> Result<Vec<u8>, DidEthrResolverError> {
+    let public_key = PublicKey::from_sec1_bytes(public_key).map_err(|_| {
+        DidEthrResolverError::InvalidIdentifier(
+            "identifier is not a valid secp256k1 public key".to_string(),
+        )
+    })?;
+    let uncompressed = public_key.to_encoded_point(false);
+    let hash = Keccak256::digest(&uncompressed.as_bytes()[1..]);
+    Ok(hash[hash.len() - ADDRESS_LENGTH..].to_vec())
+}
+
+/// Mixed-case checksum address encoding, see https://eips.ethereum.org/EIPS/eip-55
+fn to_checksum_address(address: &[u8]) -> String {
+    let address = hex::encode(address);
+    let hash = Keccak256::digest(address.as_bytes());
+    let checksummed: String = address
+        .chars()
+        .enumerate()
+        .map(|(i, c)| {
+            let nibble = (hash[i / 2] >> (if i % 2 == 0 { 4 } else { 0 })) & 0x0f;
+            if nibble >= 8 {
+                c.to_ascii_uppercase()
+            } else {
+                c
+            }
+        })
+        .collect();
+    format!("0x{checksummed}")
+}
+
+#[cfg(test)]
+mod tests {
+    use did_resolver::did_doc::schema::verification_method::VerificationMethodKind;
+
+    use super::*;
+
+    // address and public key of the secp256k1 private key 0x01
+    const ADDRESS_DID: &str = "did:ethr:0x7e5f4552091a69125d5dfcb7b8c2659029395bdf";
+    const PUBLIC_KEY_DID: &str =
+        "did:ethr:0x0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798";
+    const CHECKSUM_ADDRESS: &str = "0x7E5F4552091A69125d5DfCb7b8C2659029395Bdf";
+
+    async fn resolve(did: &str) -> DidDocument {
+        DidEthrResolver::new()
+            .resolve(&Did::parse(did.to_string()).unwrap(), &())
+            .await
+            .unwrap()
+            .did_document
+    }
+
+    #[test]
+    fn test_checksum_address() {
+        let address = hex::decode("7e5f4552091a69125d5dfcb7b8c2659029395bdf").unwrap();
+        assert_eq!(to_checksum_address(&address), CHECKSUM_ADDRESS);
+    }
+
+    #[test]
+    fn test_public_key_to_address() {
+        let public_key =
+            hex::decode("0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798")
+                .unwrap();
+        assert_eq!(
+            to_checksum_address(&public_key_to_address(&public_key).unwrap()),
+            CHECKSUM_ADDRESS
+        );
+    }
+
+    #[tokio::test]
+    async fn test_resolve_address() {
+        let did_doc = resolve(ADDRESS_DID).await;
+
+        assert_eq!(did_doc.id().to_string(), ADDRESS_DID);
+        assert_eq!(did_doc.verification_method().len(), 1);
+        let vm = &did_doc.verification_method()[0];
+        assert_eq!(vm.id().to_string(), format!("{ADDRESS_DID}#controller"));
+        assert_eq!(
+            vm.verification_method_type(),
+            &VerificationMethodType::EcdsaSecp256k1RecoveryMethod2020
+        );
+        assert_eq!(
+            vm.public_key_field(),
+            &PublicKeyField::BlockchainAccountId {
+                blockchain_account_id: format!("eip155:1:{CHECKSUM_ADDRESS}")
+            }
+        );
+        let vm_ref = VerificationMethodKind::Resolvable(vm.id().clone());
+        assert_eq!(did_doc.authentication(), [vm_ref.clone()]);
+        assert_eq!(did_doc.assertion_method(), [vm_ref]);
+        assert_eq!(
+            did_doc.extra_field("@context").unwrap(),
+            &json!([
+                contexts::W3C_DID_V1,
+                contexts::W3C_SUITE_SECP259K1_RECOVERY_2020
+            ])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_resolve_public_key() {
+        let did_doc = resolve(PUBLIC_KEY_DID).await;
+
+        assert_eq!(did_doc.verification_method().len(), 2);
+        assert_eq!(
+            did_doc.verification_method()[0].public_key_field(),
+            &PublicKeyField::BlockchainAccountId {
+                blockchain_account_id: format!("eip155:1:{CHECKSUM_ADDRESS}")
+            }
+        );
+        let vm = &did_doc.verification_method()[1];
+        assert_eq!(
+            vm.id().to_string(),
+            format!("{PUBLIC_KEY_DID}#controllerKey")
+        );
+        assert_eq!(
+            vm.verification_method_type(),
+            &VerificationMethodType::EcdsaSecp256k1VerificationKey2019
+        );
+        assert_eq!(
+            vm.public_key_field(),
+            &PublicKeyField::Hex {
+                public_key_hex: PUBLIC_KEY_DID
+                    .strip_prefix("did:ethr:0x")
+                    .unwrap()
+                    .to_string()
+            }
+        );
+        let key = vm.public_key().unwrap();
+        assert_eq!(key.key_type(), &public_key::KeyType::Secp256k1);
+        assert_eq!(
+            did_doc.authentication(),
+            [
+                VerificationMethodKind::Resolvable(did_doc.verification_method()[0].id().clone()),
+                VerificationMethodKind::Resolvable(vm.id().clone()),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_resolve_network() {
+        let did_doc = resolve(&ADDRESS_DID.replace("did:ethr:", "did:ethr:sepolia:")).await;
+
+        assert_eq!(
+            did_doc.verification_method()[0].public_key_field(),
+            &PublicKeyField::BlockchainAccountId {
+                blockchain_account_id: format!("eip155:11155111:{CHECKSUM_ADDRESS}")
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_resolve_invalid_public_key_fails() {
+        let did = Did::parse(
+            "did:ethr:0x020000000000000000000000000000000000000000000000000000000000000000"
+                .to_string(),
+        )
+        .unwrap();
+        assert!(DidEthrResolver::new().resolve(&did, &()).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_resolve_unknown_network_fails() {
+        let did = Did::parse(ADDRESS_DID.replace("did:ethr:", "did:ethr:foo:")).unwrap();
+        assert!(DidEthrResolver::new().resolve(&did, &()).await.is_err());
+    }
+}
@@ -0,0 +1,27 @@
+use did_resolver::did_doc::schema::{
+    types::uri::UriWrapperError, validation::DidDocumentValidationError,
+};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[non_exhaustive]
+pub enum DidIonResolverError {
+    #[error("DID method not supported: {0}")]
+    MethodNotSupported(String),
+    #[error("Only long-form DIDs can be resolved without a network: {0}")]
+    NotLongForm(String),
+    #[error("Invalid long-form DID: {0}")]
+    InvalidLongForm(String),
+    #[error("Invalid DID document: {0}")]
+    InvalidDidDocument(#[from] DidDocumentValidationError),
+    #[error("Invalid encoding: {0}")]
+    Base64Error(#[from] base64::DecodeError),
+    #[error("JSON error: {0}")]
+    JsonError(#[from] serde_json::Error),
+    #[error("DID parser error: {0}")]
+    DidParserError(#[from] did_resolver::did_parser_nom::ParseError),
+    #[error("Invalid service id: {0}")]
+    UriError(#[from] UriWrapperError),
+    #[error("Invalid service endpoint: {0}")]
+    UrlError(#[from] url::ParseError),
+}
@@ -0,0 +1,213 @@
+//! https://identity.foundation/sidetree/spec/#long-form-did-uris
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use did_resolver::did_doc::schema::{
+    types::jsonwebkey::JsonWebKey, verification_method::VerificationMethodType,
+};
+use serde::Deserialize;
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+
+use crate::error::DidIonResolverError;
+
+// multihash code and digest length of sha2-256
+const MULTIHASH_SHA2_256_PREFIX: [u8; 2] = [0x12, 0x20];
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct InitialState {
+    // kept as raw JSON, so that hashes are computed over exactly what the creator committed to
+    suffix_data: Value,
+    delta: Value,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SuffixData {
+    delta_hash: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Delta {
+    patches: Vec<Patch>,
+}
+
+/// Document patch actions, see https://identity.foundation/sidetree/spec/#standard-patch-actions
+#[derive(Deserialize)]
+#[serde(tag = "action", rename_all = "kebab-case")]
+enum Patch {
+    Replace {
+        document: IonDocument,
+    },
+    #[serde(rename_all = "camelCase")]
+    AddPublicKeys {
+        public_keys: Vec<IonPublicKey>,
+    },
+    RemovePublicKeys {
+        ids: Vec<String>,
+    },
+    AddServices {
+        services: Vec<IonService>,
+    },
+    RemoveServices {
+        ids: Vec<String>,
+    },
+}
+
+/// Document state produced by the create operation embedded in a long-form DID
+#[derive(Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct IonDocument {
+    #[serde(default)]
+    pub public_keys: Vec<IonPublicKey>,
+    #[serde(default)]
+    pub services: Vec<IonService>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct IonPublicKey {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub key_type: VerificationMethodType,
+    pub public_key_jwk: JsonWebKey,
+    #[serde(default)]
+    pub purposes: Vec<IonPublicKeyPurpose>,
+}
+
+#[derive(Clone, Copy, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) enum IonPublicKeyPurpose {
+    Authentication,
+    AssertionMethod,
+    CapabilityInvocation,
+    CapabilityDelegation,
+    KeyAgreement,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct IonService {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub service_type: String,
+    pub service_endpoint: Value,
+}
+
+/// Decodes the initial state of a long-form DID, checking that it matches the DID suffix, and
+/// applies its patches
+pub(super) fn decode_long_form(
+    did_suffix: &str,
+    encoded_state: &str,
+) -> Result<IonDocument, DidIonResolverError> {
+    let state: InitialState = serde_json::from_slice(&URL_SAFE_NO_PAD.decode(encoded_state)?)?;
+
+    if multihash(&state.suffix_data)? != did_suffix {
+        return Err(DidIonResolverError::InvalidLongForm(
+            "DID suffix does not match the hash of the suffix data".to_string(),
+        ));
+    }
+    let suffix_data: SuffixData = serde_json::from_value(state.suffix_data)?;
+    if multihash(&state.delta)? != suffix_data.delta_hash {
+        return Err(DidIonResolverError::InvalidLongForm(
+            "delta does not match the delta hash".to_string(),
+        ));
+    }
+    let delta: Delta = serde_json::from_value(state.delta)?;
+
+    let mut document = IonDocument::default();
+    for patch in delta.patches {
+        apply_patch(&mut document, patch);
+    }
+    Ok(document)
+}
+
+fn apply_patch(document: &mut IonDocument, patch: Patch) {
+    match patch {
+        Patch::Replace {
+            document: replacement,
+        } => *document = replacement,
+        Patch::AddPublicKeys { public_keys } => {
+            for public_key in public_keys {
+                document.public_keys.retain(|key| key.id != public_key.id);
+                document.public_keys.push(public_key);
+            }
+        }
+        Patch::RemovePublicKeys { ids } => {
+            document.public_keys.retain(|key| !ids.contains(&key.id));
+        }
+        Patch::AddServices { services } => {
+            for service in services {
+                document.services.retain(|s| s.id != service.id);
+                document.services.push(service);
+            }
+        }
+        Patch::RemoveServices { ids } => {
+            document
+                .services
+                .retain(|service| !ids.contains(&service.id));
+        }
+    }
+}
+
+/// Base64url encoded sha2-256 multihash of the JCS canonicalized value
+fn multihash(value: &Value) -> Result<String, DidIonResolverError> {
+    let canonical = serde_jcs::to_vec(value)?;
+    let mut multihash = MULTIHASH_SHA2_256_PREFIX.to_vec();
+    multihash.extend_from_slice(&Sha256::digest(canonical));
+    Ok(URL_SAFE_NO_PAD.encode(multihash))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn public_key(id: &str) -> IonPublicKey {
+        serde_json::from_value(json!({
+            "id": id,
+            "type": "EcdsaSecp256k1VerificationKey2019",
+            "publicKeyJwk": { "kty": "EC", "crv": "secp256k1", "x": "x", "y": "y" }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_apply_patches() {
+        let mut document = IonDocument::default();
+        apply_patch(
+            &mut document,
+            Patch::AddPublicKeys {
+                public_keys: vec![public_key("key-1"), public_key("key-2")],
+            },
+        );
+        apply_patch(
+            &mut document,
+            Patch::RemovePublicKeys {
+                ids: vec!["key-1".to_string()],
+            },
+        );
+        let ids: Vec<&str> = document
+            .public_keys
+            .iter()
+            .map(|key| key.id.as_str())
+            .collect();
+        assert_eq!(ids, vec!["key-2"]);
+
+        apply_patch(
+            &mut document,
+            Patch::Replace {
+                document: IonDocument::default(),
+            },
+        );
+        assert!(document.public_keys.is_empty());
+    }
+
+    #[test]
+    fn test_unsupported_patch_fails() {
+        let patch = json!({ "action": "ietf-json-patch", "patches": [] });
+        assert!(serde_json::from_value::<Patch>(patch).is_err());
+    }
+}
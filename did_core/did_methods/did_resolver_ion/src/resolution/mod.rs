@@ -0,0 +1,2 @@
+mod long_form;
+pub mod resolver;
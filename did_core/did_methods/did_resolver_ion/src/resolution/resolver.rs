@@ -0,0 +1,239 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use did_resolver::{
+    did_doc::schema::{
+        contexts,
+        did_doc::DidDocument,
+        service::{typed::ServiceType, Service},
+        types::uri::Uri,
+        utils::OneOrList,
+        verification_method::{PublicKeyField, VerificationMethod},
+    },
+    did_parser_nom::{Did, DidUrl},
+    error::GenericError,
+    shared_types::did_document_metadata::DidDocumentMetadata,
+    traits::resolvable::{
+        resolution_metadata::DidResolutionMetadata, resolution_output::DidResolutionOutput,
+        DidResolvable,
+    },
+};
+use serde_json::{json, Value};
+use url::Url;
+
+use super::long_form::{decode_long_form, IonDocument, IonPublicKeyPurpose};
+use crate::error::DidIonResolverError;
+
+/// Resolves long-form did:ion DIDs from the initial state embedded in the identifier, see
+/// https://identity.foundation/sidetree/spec/#long-form-did-uris
+///
+/// Operations anchored after creation are not taken into account, and short-form DIDs can't be
+/// resolved, as both require access to an ION node.
+#[derive(Default)]
+pub struct DidIonResolver;
+
+impl DidIonResolver {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl DidResolvable for DidIonResolver {
+    type DidResolutionOptions = ();
+
+    async fn resolve(
+        &self,
+        did: &Did,
+        _options: &Self::DidResolutionOptions,
+    ) -> Result<DidResolutionOutput, GenericError> {
+        let (did_doc, short_form_did) = resolve_did_doc(did)?;
+        did_doc.validate()?;
+        let resolution_metadata = DidResolutionMetadata::builder()
+            .content_type("application/did+json".to_string())
+            .build();
+        let document_metadata = DidDocumentMetadata::builder()
+            .add_equivalent_id(short_form_did)
+            .build();
+        Ok(DidResolutionOutput::builder(did_doc)
+            .did_resolution_metadata(resolution_metadata)
+            .did_document_metadata(document_metadata)
+            .build())
+    }
+}
+
+/// Returns the document of the long-form DID along with the short-form DID it is equivalent to
+fn resolve_did_doc(did: &Did) -> Result<(DidDocument, Did), DidIonResolverError> {
+    if did.method() != Some("ion") {
+        return Err(DidIonResolverError::MethodNotSupported(format!(
+            "{:?}",
+            did.method()
+        )));
+    }
+    let Some((did_suffix, encoded_state)) = did.id().split_once(':') else {
+        return Err(DidIonResolverError::NotLongForm(did.to_string()));
+    };
+    let ion_document = decode_long_form(did_suffix, encoded_state)?;
+    let short_form_did =
+        Did::parse(did.did()[..did.did().len() - encoded_state.len() - 1].to_string())?;
+
+    Ok((to_did_document(did, ion_document)?, short_form_did))
+}
+
+fn to_did_document(
+    did: &Did,
+    ion_document: IonDocument,
+) -> Result<DidDocument, DidIonResolverError> {
+    let mut did_doc = DidDocument::new(did.to_owned());
+
+    for public_key in ion_document.public_keys {
+        let vm_id = DidUrl::parse(format!("{did}#{}", public_key.id))?;
+        did_doc.add_verification_method(
+            VerificationMethod::builder()
+                .id(vm_id.clone())
+                .controller(did.to_owned())
+                .verification_method_type(public_key.key_type)
+                .public_key(PublicKeyField::Jwk {
+                    public_key_jwk: public_key.public_key_jwk,
+                })
+                .build(),
+        );
+        for purpose in public_key.purposes {
+            let vm_id = vm_id.clone();
+            match purpose {
+                IonPublicKeyPurpose::Authentication => did_doc.add_authentication_ref(vm_id),
+                IonPublicKeyPurpose::AssertionMethod => did_doc.add_assertion_method_ref(vm_id),
+                IonPublicKeyPurpose::CapabilityInvocation => {
+                    did_doc.add_capability_invocation_ref(vm_id)
+                }
+                IonPublicKeyPurpose::CapabilityDelegation => {
+                    did_doc.add_capability_delegation_ref(vm_id)
+                }
+                IonPublicKeyPurpose::KeyAgreement => did_doc.add_key_agreement_ref(vm_id),
+            }
+        }
+    }
+
+    for service in ion_document.services {
+        let Value::String(service_endpoint) = service.service_endpoint else {
+            return Err(DidIonResolverError::InvalidLongForm(format!(
+                "service {} has non-URI endpoint, which is not supported",
+                service.id
+            )));
+        };
+        did_doc.add_service(Service::new(
+            Uri::new(&format!("{did}#{}", service.id))?,
+            Url::parse(&service_endpoint)?,
+            OneOrList::One(ServiceType::Other(service.service_type)),
+            HashMap::new(),
+        ));
+    }
+
+    let mut context = vec![contexts::W3C_DID_V1];
+    for vm in did_doc.verification_method() {
+        let vm_context = vm.verification_method_type().context_for_type();
+        if !context.contains(&vm_context) {
+            context.push(vm_context);
+        }
+    }
+    let context = json!(context);
+    did_doc.set_extra_field(String::from("@context"), context);
+
+    Ok(did_doc)
+}
+
+#[cfg(test)]
+mod tests {
+    use did_resolver::did_doc::schema::verification_method::{
+        VerificationMethodKind, VerificationMethodType,
+    };
+
+    use super::*;
+
+    // long-form DID with a secp256k1 key (private key 0x01) and a LinkedDomains service
+    const DID_SUFFIX: &str = "EiD7bUa1VaC4mOzyA7DScmB5DCp1kmUccb-EHGc88Br_cQ";
+    const LONG_FORM_STATE: &str = "eyJkZWx0YSI6eyJwYXRjaGVzIjpbeyJhY3Rpb24iOiJyZXBsYWNlIiwiZG9jdW1lbnQiOnsicHVibGljS2V5cyI6W3siaWQiOiJrZXktMSIsInB1YmxpY0tleUp3ayI6eyJjcnYiOiJzZWNwMjU2azEiLCJrdHkiOiJFQyIsIngiOiJlYjVtZnZuY3U2eFZvR0tWem9jTEJ3S2JfTnN0emlqWldmS0JXeGI0RjVnIiwieSI6IlNEcmFkeWFqeEdWZHBQdjhEaEVJcVAwWHRFaW1oVlFabkVmUWpfc1ExTGcifSwicHVycG9zZXMiOlsiYXV0aGVudGljYXRpb24iLCJhc3NlcnRpb25NZXRob2QiLCJrZXlBZ3JlZW1lbnQiXSwidHlwZSI6IkVjZHNhU2VjcDI1NmsxVmVyaWZpY2F0aW9uS2V5MjAxOSJ9XSwic2VydmljZXMiOlt7ImlkIjoibGlua2VkaW4iLCJzZXJ2aWNlRW5kcG9pbnQiOiJodHRwczovL2xpbmtlZGluLmNvbS8iLCJ0eXBlIjoiTGlua2VkRG9tYWlucyJ9XX19XSwidXBkYXRlQ29tbWl0bWVudCI6IkVpREtJa3dxTzY5SVBHM3BPbEhrZGI4Nm5ZdDBhTnhTSFp1MnItYmhFem5qZEEifSwic3VmZml4RGF0YSI6eyJkZWx0YUhhc2giOiJFaUF1VENadU9xeWRKR3dMYnFGMm8tSXJxT243eHY3UTllNVJPLTU3NTBMc3h3IiwicmVjb3ZlcnlDb21taXRtZW50IjoiRWlCZk9aZE10VTZPQnc4UGs4NzlRdFotMkotOUZiYmpTWnlvYUFfYnFENHpoQSJ9fQ";
+
+    async fn resolve(did: &str) -> Result<DidResolutionOutput, GenericError> {
+        DidIonResolver::new()
+            .resolve(&Did::parse(did.to_string()).unwrap(), &())
+            .await
+    }
+
+    #[tokio::test]
+    async fn test_resolve_long_form() {
+        let did = format!("did:ion:{DID_SUFFIX}:{LONG_FORM_STATE}");
+        let output = resolve(&did).await.unwrap();
+        let did_doc = output.did_document;
+
+        assert_eq!(did_doc.id().to_string(), did);
+        assert_eq!(did_doc.verification_method().len(), 1);
+        let vm = &did_doc.verification_method()[0];
+        assert_eq!(vm.id().to_string(), format!("{did}#key-1"));
+        assert_eq!(
+            vm.verification_method_type(),
+            &VerificationMethodType::EcdsaSecp256k1VerificationKey2019
+        );
+        let PublicKeyField::Jwk { public_key_jwk } = vm.public_key_field() else {
+            panic!("Expected JWK, got {:?}", vm.public_key_field());
+        };
+        assert_eq!(public_key_jwk.crv, "secp256k1");
+        assert_eq!(
+            public_key_jwk.x,
+            "eb5mfvncu6xVoGKVzocLBwKb_NstzijZWfKBWxb4F5g"
+        );
+        let vm_ref = VerificationMethodKind::Resolvable(vm.id().clone());
+        assert_eq!(did_doc.authentication(), [vm_ref.clone()]);
+        assert_eq!(did_doc.assertion_method(), [vm_ref.clone()]);
+        assert_eq!(did_doc.key_agreement(), [vm_ref]);
+        assert!(did_doc.capability_invocation().is_empty());
+
+        assert_eq!(did_doc.service().len(), 1);
+        let service = &did_doc.service()[0];
+        assert_eq!(service.id().to_string(), format!("{did}#linkedin"));
+        assert_eq!(
+            service.service_types(),
+            &[ServiceType::Other("LinkedDomains".to_string())]
+        );
+        assert_eq!(
+            did_doc.extra_field("@context").unwrap(),
+            &json!([contexts::W3C_DID_V1, contexts::W3C_SUITE_SECP256K1_2019])
+        );
+        assert_eq!(
+            output.did_document_metadata.equivalent_id(),
+            &[Did::parse(format!("did:ion:{DID_SUFFIX}")).unwrap()]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_resolve_test_network_long_form() {
+        let did = format!("did:ion:test:{DID_SUFFIX}:{LONG_FORM_STATE}");
+        let output = resolve(&did).await.unwrap();
+
+        assert_eq!(output.did_document.id().to_string(), did);
+        assert_eq!(
+            output.did_document_metadata.equivalent_id(),
+            &[Did::parse(format!("did:ion:test:{DID_SUFFIX}")).unwrap()]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_resolve_short_form_fails() {
+        let err = resolve(&format!("did:ion:{DID_SUFFIX}")).await.unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<DidIonResolverError>(),
+            Some(DidIonResolverError::NotLongForm(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_resolve_mismatched_suffix_fails() {
+        let did =
+            format!("did:ion:EiDyOQbbZAa3aiRzeCkV7LOx3SERjjH93EXoIM3UoN4oWg:{LONG_FORM_STATE}");
+        let err = resolve(&did).await.unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<DidIonResolverError>(),
+            Some(DidIonResolverError::InvalidLongForm(_))
+        ));
+    }
+}
@@ -0,0 +1,19 @@
+use did_resolver::did_doc::schema::types::jsonwebkey::JsonWebKeyError;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[non_exhaustive]
+pub enum DidKeyResolverError {
+    #[error("DID method not supported: {0}")]
+    MethodNotSupported(String),
+    #[error("Invalid key: {0}")]
+    InvalidKey(String),
+    #[error("did:key error: {0}")]
+    DidKeyError(#[from] did_key::error::DidKeyError),
+    #[error("Public key error: {0}")]
+    PublicKeyError(#[from] public_key::PublicKeyError),
+    #[error("DID parser error: {0}")]
+    DidParserError(#[from] did_resolver::did_parser_nom::ParseError),
+    #[error("JWK error: {0}")]
+    JsonWebKeyError(#[from] JsonWebKeyError),
+}
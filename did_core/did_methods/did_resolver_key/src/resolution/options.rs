@@ -0,0 +1,19 @@
+use serde::{Deserialize, Serialize};
+
+/// Representation of public keys in the resolved DID document
+#[derive(Clone, Copy, Debug, PartialEq, Default, Serialize, Deserialize)]
+pub enum PublicKeyFormat {
+    /// `Multikey` verification methods with `publicKeyMultibase`
+    #[default]
+    Multikey,
+    /// `JsonWebKey2020` verification methods with `publicKeyJwk`
+    JsonWebKey2020,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Default, Serialize, Deserialize)]
+pub struct DidKeyResolutionOptions {
+    /// Defaults to [`PublicKeyFormat::Multikey`]
+    pub public_key_format: Option<PublicKeyFormat>,
+    /// Whether X25519 key agreement key is derived from Ed25519 key, enabled by default
+    pub enable_encryption_key_derivation: Option<bool>,
+}
@@ -0,0 +1,336 @@
+use async_trait::async_trait;
+use did_key::DidKey;
+use did_resolver::{
+    did_doc::schema::{
+        contexts,
+        did_doc::DidDocument,
+        types::jsonwebkey::JsonWebKey,
+        verification_method::{PublicKeyField, VerificationMethod, VerificationMethodType},
+    },
+    did_parser_nom::{Did, DidUrl},
+    error::GenericError,
+    traits::resolvable::{
+        resolution_metadata::DidResolutionMetadata, resolution_output::DidResolutionOutput,
+        DidResolvable,
+    },
+};
+use public_key::{Key, KeyType};
+use serde_json::json;
+
+use super::options::{DidKeyResolutionOptions, PublicKeyFormat};
+use crate::error::DidKeyResolverError;
+
+const BLS12381G1_KEY_LENGTH: usize = 48;
+const BLS12381G2_KEY_LENGTH: usize = 96;
+
+/// Resolves did:key according to https://w3c-ccg.github.io/did-method-key/
+#[derive(Default)]
+pub struct DidKeyResolver;
+
+impl DidKeyResolver {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl DidResolvable for DidKeyResolver {
+    type DidResolutionOptions = DidKeyResolutionOptions;
+
+    async fn resolve(
+        &self,
+        did: &Did,
+        options: &Self::DidResolutionOptions,
+    ) -> Result<DidResolutionOutput, GenericError> {
+        let did_doc = resolve_did_doc(did, options)?;
+        did_doc.validate()?;
+        let resolution_metadata = DidResolutionMetadata::builder()
+            .content_type("application/did+json".to_string())
+            .build();
+        Ok(DidResolutionOutput::builder(did_doc)
+            .did_resolution_metadata(resolution_metadata)
+            .build())
+    }
+}
+
+fn resolve_did_doc(
+    did: &Did,
+    options: &DidKeyResolutionOptions,
+) -> Result<DidDocument, DidKeyResolverError> {
+    if did.method() != Some("key") {
+        return Err(DidKeyResolverError::MethodNotSupported(format!(
+            "{:?}",
+            did.method()
+        )));
+    }
+    let did_key = DidKey::parse(did.did().to_string())?;
+    let key = did_key.key();
+    let format = options.public_key_format.unwrap_or_default();
+
+    let mut did_doc = DidDocument::new(did.to_owned());
+    match key.key_type() {
+        KeyType::Ed25519 => {
+            let vm_id = add_verification_method(&mut did_doc, key, format)?;
+            add_signing_refs(&mut did_doc, &vm_id);
+            if options.enable_encryption_key_derivation.unwrap_or(true) {
+                let key_agreement_key = key.ed25519_to_x25519()?;
+                let vm_id = add_verification_method(&mut did_doc, &key_agreement_key, format)?;
+                did_doc.add_key_agreement_ref(vm_id);
+            }
+        }
+        KeyType::X25519 => {
+            let vm_id = add_verification_method(&mut did_doc, key, format)?;
+            did_doc.add_key_agreement_ref(vm_id);
+        }
+        KeyType::P256 | KeyType::P384 | KeyType::P521 | KeyType::Secp256k1 => {
+            let vm_id = add_verification_method(&mut did_doc, key, format)?;
+            add_signing_refs(&mut did_doc, &vm_id);
+            did_doc.add_key_agreement_ref(vm_id);
+        }
+        KeyType::Bls12381g1 | KeyType::Bls12381g2 => {
+            let vm_id = add_verification_method(&mut did_doc, key, format)?;
+            add_signing_refs(&mut did_doc, &vm_id);
+        }
+        KeyType::Bls12381g1g2 => {
+            // the concatenated key is expressed as separate G1 and G2 verification methods
+            if key.key().len() != BLS12381G1_KEY_LENGTH + BLS12381G2_KEY_LENGTH {
+                return Err(DidKeyResolverError::InvalidKey(format!(
+                    "BLS12-381 G1G2 key must be {} bytes long, got {}",
+                    BLS12381G1_KEY_LENGTH + BLS12381G2_KEY_LENGTH,
+                    key.key().len()
+                )));
+            }
+            let (g1, g2) = key.key().split_at(BLS12381G1_KEY_LENGTH);
+            for key in [
+                Key::new(g1.to_vec(), KeyType::Bls12381g1)?,
+                Key::new(g2.to_vec(), KeyType::Bls12381g2)?,
+            ] {
+                let vm_id = add_verification_method(&mut did_doc, &key, format)?;
+                add_signing_refs(&mut did_doc, &vm_id);
+            }
+        }
+    }
+
+    let mut context = vec![contexts::W3C_DID_V1];
+    for vm in did_doc.verification_method() {
+        let vm_context = vm.verification_method_type().context_for_type();
+        if !context.contains(&vm_context) {
+            context.push(vm_context);
+        }
+    }
+    let context = json!(context);
+    did_doc.set_extra_field(String::from("@context"), context);
+
+    Ok(did_doc)
+}
+
+/// Adds verification method identified by fingerprint of the key, returns its id
+fn add_verification_method(
+    did_doc: &mut DidDocument,
+    key: &Key,
+    format: PublicKeyFormat,
+) -> Result<DidUrl, DidKeyResolverError> {
+    let did = did_doc.id().to_owned();
+    let vm_id = DidUrl::parse(format!("{}#{}", did, key.fingerprint()))?;
+    let (verification_method_type, public_key) = match format {
+        PublicKeyFormat::Multikey => (
+            VerificationMethodType::Multikey,
+            PublicKeyField::Multibase {
+                public_key_multibase: key.fingerprint(),
+            },
+        ),
+        PublicKeyFormat::JsonWebKey2020 => (
+            VerificationMethodType::JsonWebKey2020,
+            PublicKeyField::Jwk {
+                public_key_jwk: JsonWebKey::new(&key.to_jwk()?)?,
+            },
+        ),
+    };
+    let vm = VerificationMethod::builder()
+        .id(vm_id.clone())
+        .controller(did)
+        .verification_method_type(verification_method_type)
+        .public_key(public_key)
+        .build();
+    did_doc.add_verification_method(vm);
+    Ok(vm_id)
+}
+
+fn add_signing_refs(did_doc: &mut DidDocument, vm_id: &DidUrl) {
+    did_doc.add_authentication_ref(vm_id.clone());
+    did_doc.add_assertion_method_ref(vm_id.clone());
+    did_doc.add_capability_invocation_ref(vm_id.clone());
+    did_doc.add_capability_delegation_ref(vm_id.clone());
+}
+
+#[cfg(test)]
+mod tests {
+    use did_resolver::did_doc::schema::verification_method::VerificationMethodKind;
+
+    use super::*;
+
+    // vectors from https://w3c-ccg.github.io/did-method-key/#test-vectors
+    const ED25519_DID: &str = "did:key:z6MkiTBz1ymuepAQ4HEHYSF1H8quG5GLVVQR3djdX3mDooWp";
+    const ED25519_DERIVED_X25519_FINGERPRINT: &str =
+        "z6LShs9GGnqk85isEBzzshkuVWrVKsRp24GnDuHk8QWkARMW";
+    const X25519_DID: &str = "did:key:z6LSeu9HkTHSfLLeUs2nnzUSNedgDUevfNQgQjQC23ZCit6F";
+    const P256_DID: &str = "did:key:zDnaerDaTF5BXEavCrfRZEk316dpbLsfPDZ3WJ5hRTPFU2169";
+    const BLS12381G1G2_DID: &str = "did:key:z5TcESXuYUE9aZWYwSdrUEGK1HNQFHyTt4aVpaCTVZcDXQmUheFwfNZmRksaAbBneNm5KyE52SdJeRCN1g6PJmF31GsHWwFiqUDujvasK3wTiDr3vvkYwEJHt7H5RGEKYEp1ErtQtcEBgsgY2DA9JZkHj1J9HZ8MRDTguAhoFtR4aTBQhgnkP4SwVbxDYMEZoF2TMYn3s";
+
+    async fn resolve(did: &str, options: &DidKeyResolutionOptions) -> DidDocument {
+        DidKeyResolver::new()
+            .resolve(&Did::parse(did.to_string()).unwrap(), options)
+            .await
+            .unwrap()
+            .did_document
+    }
+
+    #[tokio::test]
+    async fn test_resolve_ed25519_multikey() {
+        let did_doc = resolve(ED25519_DID, &Default::default()).await;
+        let fingerprint = ED25519_DID.strip_prefix("did:key:").unwrap();
+
+        assert_eq!(did_doc.id().to_string(), ED25519_DID);
+        assert_eq!(did_doc.verification_method().len(), 2);
+        let vm = &did_doc.verification_method()[0];
+        assert_eq!(vm.id().to_string(), format!("{ED25519_DID}#{fingerprint}"));
+        assert_eq!(vm.controller().to_string(), ED25519_DID);
+        assert_eq!(
+            vm.verification_method_type(),
+            &VerificationMethodType::Multikey
+        );
+        assert_eq!(
+            vm.public_key_field(),
+            &PublicKeyField::Multibase {
+                public_key_multibase: fingerprint.to_string()
+            }
+        );
+        let vm_ref = VerificationMethodKind::Resolvable(vm.id().clone());
+        assert_eq!(did_doc.authentication(), [vm_ref.clone()]);
+        assert_eq!(did_doc.assertion_method(), [vm_ref.clone()]);
+        assert_eq!(did_doc.capability_invocation(), [vm_ref.clone()]);
+        assert_eq!(did_doc.capability_delegation(), [vm_ref]);
+        let key_agreement_vm = &did_doc.verification_method()[1];
+        assert_eq!(
+            key_agreement_vm.id().fragment(),
+            Some(ED25519_DERIVED_X25519_FINGERPRINT)
+        );
+        assert_eq!(
+            key_agreement_vm.public_key_field(),
+            &PublicKeyField::Multibase {
+                public_key_multibase: ED25519_DERIVED_X25519_FINGERPRINT.to_string()
+            }
+        );
+        assert_eq!(
+            did_doc.key_agreement(),
+            [VerificationMethodKind::Resolvable(
+                key_agreement_vm.id().clone()
+            )]
+        );
+        assert_eq!(
+            did_doc.extra_field("@context").unwrap(),
+            &json!([contexts::W3C_DID_V1, contexts::W3C_MULTIKEY_V1])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_resolve_ed25519_without_key_derivation() {
+        let options = DidKeyResolutionOptions {
+            enable_encryption_key_derivation: Some(false),
+            ..Default::default()
+        };
+        let did_doc = resolve(ED25519_DID, &options).await;
+
+        assert_eq!(did_doc.verification_method().len(), 1);
+        assert!(did_doc.key_agreement().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_resolve_x25519() {
+        let did_doc = resolve(X25519_DID, &Default::default()).await;
+        let fingerprint = X25519_DID.strip_prefix("did:key:").unwrap();
+
+        assert_eq!(did_doc.verification_method().len(), 1);
+        let vm_id = did_doc.verification_method()[0].id();
+        assert_eq!(vm_id.fragment(), Some(fingerprint));
+        assert_eq!(
+            did_doc.key_agreement(),
+            [VerificationMethodKind::Resolvable(vm_id.clone())]
+        );
+        assert!(did_doc.authentication().is_empty());
+        assert!(did_doc.assertion_method().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_resolve_p256() {
+        let did_doc = resolve(P256_DID, &Default::default()).await;
+        let fingerprint = P256_DID.strip_prefix("did:key:").unwrap();
+
+        assert_eq!(did_doc.verification_method().len(), 1);
+        let vm_id = did_doc.verification_method()[0].id();
+        assert_eq!(vm_id.fragment(), Some(fingerprint));
+        let vm_ref = VerificationMethodKind::Resolvable(vm_id.clone());
+        assert_eq!(did_doc.authentication(), [vm_ref.clone()]);
+        assert_eq!(did_doc.key_agreement(), [vm_ref]);
+    }
+
+    #[tokio::test]
+    async fn test_resolve_bls12381g1g2() {
+        let did_doc = resolve(BLS12381G1G2_DID, &Default::default()).await;
+
+        assert_eq!(did_doc.verification_method().len(), 2);
+        let key_types: Vec<KeyType> = did_doc
+            .verification_method()
+            .iter()
+            .map(|vm| {
+                *Key::from_fingerprint(vm.id().fragment().unwrap())
+                    .unwrap()
+                    .key_type()
+            })
+            .collect();
+        assert_eq!(key_types, vec![KeyType::Bls12381g1, KeyType::Bls12381g2]);
+        assert_eq!(did_doc.authentication().len(), 2);
+        assert!(did_doc.key_agreement().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_resolve_ed25519_json_web_key() {
+        let options = DidKeyResolutionOptions {
+            public_key_format: Some(PublicKeyFormat::JsonWebKey2020),
+            ..Default::default()
+        };
+        let did_doc = resolve(ED25519_DID, &options).await;
+
+        let vm = &did_doc.verification_method()[0];
+        assert_eq!(
+            vm.verification_method_type(),
+            &VerificationMethodType::JsonWebKey2020
+        );
+        let PublicKeyField::Jwk { public_key_jwk } = vm.public_key_field() else {
+            panic!("Expected JWK, got {:?}", vm.public_key_field());
+        };
+        assert_eq!(public_key_jwk.kty, "OKP");
+        assert_eq!(public_key_jwk.crv, "Ed25519");
+        let PublicKeyField::Jwk { public_key_jwk } =
+            did_doc.verification_method()[1].public_key_field()
+        else {
+            panic!("Expected JWK");
+        };
+        assert_eq!(public_key_jwk.crv, "X25519");
+        assert_eq!(
+            did_doc.extra_field("@context").unwrap(),
+            &json!([contexts::W3C_DID_V1, contexts::W3C_SUITE_JWS_2020])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_resolve_other_method_fails() {
+        let did =
+            Did::parse("did:peer:z6MkiTBz1ymuepAQ4HEHYSF1H8quG5GLVVQR3djdX3mDooWp".to_string())
+                .unwrap();
+        assert!(DidKeyResolver::new()
+            .resolve(&did, &Default::default())
+            .await
+            .is_err());
+    }
+}
@@ -18,6 +18,8 @@ pub enum DidSovError {
     NotFound(String),
     #[error("DID method not supported: {0}")]
     MethodNotSupported(String),
+    #[error("No ledger configured for did:indy namespace: {0}")]
+    NamespaceNotSupported(String),
     #[error("Representation not supported: {0}")]
     RepresentationNotSupported(String),
     #[error("Internal error")]
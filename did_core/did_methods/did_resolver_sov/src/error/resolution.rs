@@ -8,7 +8,9 @@ impl From<&DidSovError> for DidResolutionError {
     fn from(err: &DidSovError) -> Self {
         match err {
             DidSovError::NotFound(_) => DidResolutionError::NotFound,
-            DidSovError::MethodNotSupported(_) => DidResolutionError::MethodNotSupported,
+            DidSovError::MethodNotSupported(_) | DidSovError::NamespaceNotSupported(_) => {
+                DidResolutionError::MethodNotSupported
+            }
             _ => DidResolutionError::InternalError,
         }
     }
@@ -0,0 +1,391 @@
+use std::{borrow::Borrow, collections::HashMap, marker::PhantomData};
+
+use async_trait::async_trait;
+use did_resolver::{
+    did_doc::schema::did_doc::DidDocument,
+    did_parser_nom::Did,
+    error::GenericError,
+    shared_types::did_document_metadata::DidDocumentMetadata,
+    traits::resolvable::{
+        resolution_metadata::DidResolutionMetadata, resolution_output::DidResolutionOutput,
+        DidResolvable,
+    },
+};
+use serde_json::{json, Map, Value};
+
+use super::utils::{
+    expand_abbreviated_verkey, get_data_from_response, get_txn_time_from_response,
+    is_valid_sovrin_did_id, unix_to_datetime,
+};
+use crate::{
+    error::{parsing::ParsingErrorSource, DidSovError},
+    reader::AttrReader,
+    service::{DidSovServiceType, EndpointDidSov},
+};
+
+const VERKEY_FRAGMENT: &str = "verkey";
+
+/// Resolves `did:indy:<namespace>:<nym>` DIDs against the ledger registered for the namespace,
+/// see https://hyperledger.github.io/indy-did-method/#did-resolution
+pub struct DidIndyResolver<T, A>
+where
+    T: Borrow<A> + Sync + Send,
+    A: AttrReader,
+{
+    ledgers: HashMap<String, T>,
+    _marker: PhantomData<A>,
+}
+
+impl<T, A> Default for DidIndyResolver<T, A>
+where
+    T: Borrow<A> + Sync + Send,
+    A: AttrReader,
+{
+    fn default() -> Self {
+        Self {
+            ledgers: HashMap::new(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T, A> DidIndyResolver<T, A>
+where
+    T: Borrow<A> + Sync + Send,
+    A: AttrReader,
+{
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers the ledger of a did:indy namespace, e.g. `sovrin` or `sovrin:staging`
+    pub fn register_namespace(mut self, namespace: impl Into<String>, ledger: T) -> Self {
+        self.ledgers.insert(namespace.into(), ledger);
+        self
+    }
+
+    async fn endpoint_services(&self, ledger: &A, did: &Did, nym: &Did) -> Vec<Value> {
+        let endpoint = match ledger.get_attr(nym, "endpoint").await {
+            Ok(response) => get_data_from_response(&response),
+            Err(err) => Err(err),
+        };
+        let endpoint = match endpoint.and_then(|data| {
+            serde_json::from_value::<EndpointDidSov>(data["endpoint"].clone()).map_err(Into::into)
+        }) {
+            Ok(endpoint) => endpoint,
+            Err(err) => {
+                log::debug!("No endpoint attribute found for {did}: {err}");
+                return vec![];
+            }
+        };
+        endpoint_to_services(did, endpoint)
+    }
+}
+
+#[async_trait]
+impl<T, A> DidResolvable for DidIndyResolver<T, A>
+where
+    T: Borrow<A> + Sync + Send,
+    A: AttrReader,
+{
+    type DidResolutionOptions = ();
+
+    async fn resolve(
+        &self,
+        did: &Did,
+        _options: &Self::DidResolutionOptions,
+    ) -> Result<DidResolutionOutput, GenericError> {
+        log::info!("DidIndyResolver::resolve >> Resolving did: {did}");
+        let method = did.method().unwrap_or_default();
+        if method != "indy" {
+            return Err(Box::new(DidSovError::MethodNotSupported(
+                method.to_string(),
+            )));
+        }
+        let namespace = did
+            .namespace()
+            .ok_or_else(|| DidSovError::InvalidDid(format!("Indy DID {did} has no namespace")))?;
+        if !is_valid_sovrin_did_id(did.id()) {
+            return Err(Box::new(DidSovError::InvalidDid(format!(
+                "Indy DID: {did} contains invalid DID ID."
+            ))));
+        }
+        let ledger: &A = self
+            .ledgers
+            .get(namespace)
+            .ok_or_else(|| DidSovError::NamespaceNotSupported(namespace.to_string()))?
+            .borrow();
+
+        // the ledger knows the DID by its unqualified nym only
+        let nym = Did::parse(did.id().to_string())?;
+        let nym_response = ledger.get_nym(&nym).await?;
+        let nym_data = get_data_from_response(&nym_response)?;
+
+        let mut did_document = base_did_document(did, &nym_data)?;
+        match nym_data.get("diddocContent") {
+            Some(Value::Null) | None => {
+                let services = self.endpoint_services(ledger, did, &nym).await;
+                if !services.is_empty() {
+                    did_document["service"] = Value::Array(services);
+                }
+            }
+            Some(Value::String(content)) => {
+                merge_diddoc_content(&mut did_document, serde_json::from_str(content)?)?
+            }
+            Some(content) => merge_diddoc_content(&mut did_document, content.clone())?,
+        }
+        let did_document: DidDocument = serde_json::from_value(did_document)?;
+        did_document.validate()?;
+
+        let mut metadata = DidDocumentMetadata::builder().deactivated(false);
+        if let Some(updated) = get_txn_time_from_response(&nym_response)
+            .ok()
+            .and_then(unix_to_datetime)
+        {
+            metadata = metadata.updated(updated);
+        }
+        Ok(DidResolutionOutput::builder(did_document)
+            .did_document_metadata(metadata.build())
+            .did_resolution_metadata(
+                DidResolutionMetadata::builder()
+                    .content_type("application/did+json".to_string())
+                    .build(),
+            )
+            .build())
+    }
+}
+
+/// DID document assembled from the NYM verkey alone
+fn base_did_document(did: &Did, nym_data: &Value) -> Result<Value, DidSovError> {
+    let verkey = nym_data["verkey"].as_str().ok_or_else(|| {
+        DidSovError::ParsingError(ParsingErrorSource::LedgerResponseParsingError(
+            "Failed to parse verkey from nym data".to_string(),
+        ))
+    })?;
+    let verkey = expand_abbreviated_verkey(did.id(), verkey)?;
+    let verkey_id = format!("{did}#{VERKEY_FRAGMENT}");
+    Ok(json!({
+        "@context": [
+            "https://www.w3.org/ns/did/v1",
+            "https://w3id.org/security/suites/ed25519-2018/v1"
+        ],
+        "id": did,
+        "verificationMethod": [{
+            "id": verkey_id,
+            "type": "Ed25519VerificationKey2018",
+            "controller": did,
+            "publicKeyBase58": verkey,
+        }],
+        "authentication": [verkey_id],
+    }))
+}
+
+/// Adds the `diddocContent` of the NYM to the base DID document. Entries of array properties
+/// (including `@context`) are appended, other properties must not be present in the base DID
+/// document.
+fn merge_diddoc_content(did_document: &mut Value, content: Value) -> Result<(), DidSovError> {
+    let Value::Object(content) = content else {
+        return Err(DidSovError::InvalidDid(
+            "diddocContent is not a JSON object".to_string(),
+        ));
+    };
+    let did_document = did_document
+        .as_object_mut()
+        .ok_or(DidSovError::InternalError)?;
+    for (key, value) in content {
+        match (did_document.get_mut(&key), value) {
+            (None, value) => {
+                did_document.insert(key, value);
+            }
+            (Some(Value::Array(existing)), Value::Array(values)) => {
+                for value in values {
+                    if !existing.contains(&value) {
+                        existing.push(value);
+                    }
+                }
+            }
+            (Some(Value::Array(existing)), value) if key == "@context" => {
+                if !existing.contains(&value) {
+                    existing.push(value);
+                }
+            }
+            (Some(_), _) => {
+                return Err(DidSovError::InvalidDid(format!(
+                    "diddocContent must not override {key}"
+                )));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Services of the legacy endpoint attribute, as assembled by
+/// https://hyperledger.github.io/indy-did-method/#diddoc-assembly
+fn endpoint_to_services(did: &Did, endpoint: EndpointDidSov) -> Vec<Value> {
+    let recipient_keys = json!([format!("{did}#{VERKEY_FRAGMENT}")]);
+    [
+        DidSovServiceType::Endpoint,
+        DidSovServiceType::DidCommunication,
+        DidSovServiceType::DIDComm,
+    ]
+    .into_iter()
+    .filter(|service_type| endpoint.types.contains(service_type))
+    .map(|service_type| {
+        let mut service = Map::new();
+        service.insert("type".to_string(), json!(service_type.to_string()));
+        service.insert("serviceEndpoint".to_string(), json!(endpoint.endpoint));
+        match service_type {
+            DidSovServiceType::DidCommunication => {
+                service.insert("id".to_string(), json!(format!("{did}#did-communication")));
+                service.insert("recipientKeys".to_string(), recipient_keys.clone());
+                service.insert("routingKeys".to_string(), json!(endpoint.routing_keys));
+                service.insert("priority".to_string(), json!(0));
+                service.insert("accept".to_string(), json!(["didcomm/aip2;env=rfc19"]));
+            }
+            DidSovServiceType::DIDComm => {
+                service.insert("id".to_string(), json!(format!("{did}#didcomm-1")));
+                service.insert("routingKeys".to_string(), json!(endpoint.routing_keys));
+                service.insert("accept".to_string(), json!(["didcomm/v2"]));
+            }
+            _ => {
+                service.insert("id".to_string(), json!(format!("{did}#endpoint")));
+            }
+        }
+        Value::Object(service)
+    })
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{future::Future, pin::Pin, sync::Arc};
+
+    use did_resolver::did_doc::schema::{
+        service::typed::ServiceType, verification_method::PublicKeyField,
+    };
+
+    use super::*;
+    use crate::reader::MockAttrReader;
+
+    const NYM: &str = "7Sqc3ne5NfUVxMTrHahxz3";
+    const VERKEY: &str = "4WkksEAXsewRbDYDz66aTdjtVF2LBxbqEMyF2WEjTBKk";
+
+    type MockResponse = Pin<Box<dyn Future<Output = Result<String, DidSovError>> + Send>>;
+
+    fn ready(response: String) -> MockResponse {
+        Box::pin(async move { Ok(response) })
+    }
+
+    fn nym_response(diddoc_content: Option<Value>) -> String {
+        let mut data = json!({ "dest": NYM, "verkey": VERKEY });
+        if let Some(content) = diddoc_content {
+            data["diddocContent"] = Value::String(content.to_string());
+        }
+        json!({ "result": { "data": data.to_string(), "txnTime": 1629272938 } }).to_string()
+    }
+
+    fn resolver(ledger: MockAttrReader) -> DidIndyResolver<Arc<MockAttrReader>, MockAttrReader> {
+        DidIndyResolver::new().register_namespace("sovrin:staging", Arc::new(ledger))
+    }
+
+    fn did() -> Did {
+        Did::parse(format!("did:indy:sovrin:staging:{NYM}")).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_resolve_nym_with_endpoint() {
+        let mut ledger = MockAttrReader::new();
+        ledger
+            .expect_get_nym()
+            .withf(|nym| nym.did() == NYM)
+            .returning(|_| ready(nym_response(None)));
+        ledger.expect_get_attr().returning(|_, _| {
+            ready(
+                json!({ "result": { "data": json!({
+                "endpoint": {
+                    "endpoint": "https://example.com/",
+                    "types": ["did-communication"],
+                    "routingKeys": ["routing-key"]
+                }
+            }).to_string() } })
+                .to_string(),
+            )
+        });
+
+        let output = resolver(ledger).resolve(&did(), &()).await.unwrap();
+        let did_document = output.did_document;
+        assert_eq!(did_document.id(), &did());
+        assert_eq!(
+            did_document.verification_method()[0].public_key_field(),
+            &PublicKeyField::Base58 {
+                public_key_base58: VERKEY.to_string()
+            }
+        );
+        let service = &did_document.service()[0];
+        assert_eq!(
+            service.id().to_string(),
+            format!("{}#did-communication", did())
+        );
+        assert!(service.service_types().contains(&ServiceType::DIDCommV1));
+        assert!(output.did_document_metadata.updated().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_resolve_nym_with_diddoc_content() {
+        let mut ledger = MockAttrReader::new();
+        ledger.expect_get_nym().returning(|_| {
+            ready(nym_response(Some(json!({
+                "@context": ["https://didcomm.org/messaging/contexts/v2"],
+                "service": [{
+                    "id": format!("did:indy:sovrin:staging:{NYM}#didcomm-1"),
+                    "type": "DIDComm",
+                    "serviceEndpoint": "https://example.com/",
+                    "accept": ["didcomm/v2"]
+                }]
+            }))))
+        });
+        ledger.expect_get_attr().never();
+
+        let did_document = resolver(ledger)
+            .resolve(&did(), &())
+            .await
+            .unwrap()
+            .did_document;
+        assert_eq!(did_document.service().len(), 1);
+        assert_eq!(did_document.verification_method().len(), 1);
+        assert_eq!(
+            did_document
+                .extra_field("@context")
+                .unwrap()
+                .as_array()
+                .unwrap()
+                .len(),
+            3
+        );
+    }
+
+    #[tokio::test]
+    async fn test_diddoc_content_must_not_override_id() {
+        let mut ledger = MockAttrReader::new();
+        ledger.expect_get_nym().returning(|_| {
+            ready(nym_response(Some(
+                json!({ "id": "did:indy:sovrin:7Sqc3ne5NfUVxMTrHahxz3" }),
+            )))
+        });
+
+        assert!(resolver(ledger).resolve(&did(), &()).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_unknown_namespace() {
+        let did = Did::parse(format!("did:indy:sovrin:{NYM}")).unwrap();
+        let err = resolver(MockAttrReader::new())
+            .resolve(&did, &())
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<DidSovError>(),
+            Some(DidSovError::NamespaceNotSupported(namespace)) if namespace == "sovrin"
+        ));
+    }
+}
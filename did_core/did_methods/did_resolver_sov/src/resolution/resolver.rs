@@ -73,9 +73,10 @@ where
             .get_attr(parsed_did, "endpoint")
             .await?;
         let verkey = self.get_verkey(parsed_did).await?;
-        ledger_response_to_ddo(parsed_did.did(), &ledger_response, verkey)
-            .await
-            .map_err(|err| err.into())
+        let resolution_output =
+            ledger_response_to_ddo(parsed_did.did(), &ledger_response, verkey).await?;
+        resolution_output.did_document.validate()?;
+        Ok(resolution_output)
     }
 }
 
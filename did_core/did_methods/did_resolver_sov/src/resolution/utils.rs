@@ -26,7 +26,7 @@ fn prepare_ids(did: &str) -> Result<(Uri, Did), DidSovError> {
     Ok((service_id, ddo_id))
 }
 
-fn get_data_from_response(resp: &str) -> Result<Value, DidSovError> {
+pub(super) fn get_data_from_response(resp: &str) -> Result<Value, DidSovError> {
     let resp: serde_json::Value = serde_json::from_str(resp)?;
     match &resp["result"]["data"] {
         Value::String(ref data) => serde_json::from_str(data).map_err(|err| err.into()),
@@ -39,7 +39,7 @@ fn get_data_from_response(resp: &str) -> Result<Value, DidSovError> {
     }
 }
 
-fn get_txn_time_from_response(resp: &str) -> Result<i64, DidSovError> {
+pub(super) fn get_txn_time_from_response(resp: &str) -> Result<i64, DidSovError> {
     let resp: serde_json::Value = serde_json::from_str(resp)?;
     let txn_time = resp["result"]["txnTime"]
         .as_i64()
@@ -49,11 +49,11 @@ fn get_txn_time_from_response(resp: &str) -> Result<i64, DidSovError> {
     Ok(txn_time)
 }
 
-fn unix_to_datetime(posix_timestamp: i64) -> Option<DateTime<Utc>> {
+pub(super) fn unix_to_datetime(posix_timestamp: i64) -> Option<DateTime<Utc>> {
     DateTime::from_timestamp(posix_timestamp, 0)
 }
 
-fn expand_abbreviated_verkey(nym: &str, verkey: &str) -> Result<String, DidSovError> {
+pub(super) fn expand_abbreviated_verkey(nym: &str, verkey: &str) -> Result<String, DidSovError> {
     if let Some(stripped_verkey) = verkey.strip_prefix('~') {
         let mut decoded_nym = bs58::decode(nym).into_vec().map_err(|e| {
             DidSovError::ParsingError(ParsingErrorSource::LedgerResponseParsingError(format!(
@@ -260,6 +260,32 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_resolved_ddo_validates() {
+        // ATTRIB and NYM data as written to the ledger by ACA-Py and aries-vcx agents
+        let did = "did:sov:7Sqc3ne5NfUVxMTrHahxz3";
+        let resp = r#"{
+            "result": {
+                "data": "{\"endpoint\":{\"endpoint\":\"https://agent.example.org:8020\",\"routingKeys\":[\"3LYuxJBJkngDbvJj4zjx13DBUdZ2P96eNybwd2n9L9AU\"],\"types\":[\"endpoint\",\"did-communication\",\"DIDComm\"]}}",
+                "txnTime": 1629272938
+            }
+        }"#;
+        let verkey = "~DczaFTexiEYv5abkEUZeZt".to_string();
+        let ddo = ledger_response_to_ddo(did, resp, verkey)
+            .await
+            .unwrap()
+            .did_document;
+        ddo.validate().unwrap();
+
+        let resp = r#"{ "result": { "data": null, "txnTime": null } }"#;
+        let verkey = "4WkksEAXsewRbDYDz66aTdjtVF2LBxbqEMyF2WEjTBKk".to_string();
+        let ddo = ledger_response_to_ddo(did, resp, verkey)
+            .await
+            .unwrap()
+            .did_document;
+        ddo.validate().unwrap();
+    }
+
     #[test]
     fn test_expand_abbreviated_verkey_with_abbreviation() {
         let nym = "7Sqc3ne5NfUVxMTrHahxz3";
@@ -0,0 +1,26 @@
+pub mod parsing;
+
+use hyper::StatusCode;
+use thiserror::Error;
+
+use self::parsing::ParsingErrorSource;
+
+#[derive(Error, Debug)]
+#[non_exhaustive]
+pub enum DidUniversalResolverError {
+    #[error("Parsing error: {0}")]
+    ParsingError(#[from] ParsingErrorSource),
+    #[error("Network error: {0}")]
+    NetworkError(#[from] hyper::Error),
+    #[error("Network error: {0}")]
+    NetworkClientError(#[from] hyper_util::client::legacy::Error),
+    #[error("Invalid request: {0}")]
+    RequestError(#[from] hyper::http::Error),
+    #[error("Universal resolver responded with {status}, error: {error:?}")]
+    NonSuccessResponse {
+        status: StatusCode,
+        error: Option<String>,
+    },
+    #[error(transparent)]
+    Other(#[from] Box<dyn std::error::Error + Send + Sync>),
+}
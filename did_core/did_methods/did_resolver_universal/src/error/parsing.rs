@@ -0,0 +1,23 @@
+use thiserror::Error;
+
+use super::DidUniversalResolverError;
+
+#[derive(Error, Debug)]
+pub enum ParsingErrorSource {
+    #[error("JSON parsing error: {0}")]
+    JsonError(#[from] serde_json::Error),
+    #[error("Invalid URI: {0}")]
+    UriError(#[from] hyper::http::uri::InvalidUri),
+}
+
+impl From<serde_json::Error> for DidUniversalResolverError {
+    fn from(error: serde_json::Error) -> Self {
+        DidUniversalResolverError::ParsingError(ParsingErrorSource::JsonError(error))
+    }
+}
+
+impl From<hyper::http::uri::InvalidUri> for DidUniversalResolverError {
+    fn from(error: hyper::http::uri::InvalidUri) -> Self {
+        DidUniversalResolverError::ParsingError(ParsingErrorSource::UriError(error))
+    }
+}
@@ -0,0 +1,189 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use did_resolver::{
+    did_doc::schema::did_doc::DidDocument,
+    did_parser_nom::Did,
+    error::GenericError,
+    traits::resolvable::{
+        resolution_error::DidResolutionError, resolution_metadata::DidResolutionMetadata,
+        resolution_output::DidResolutionOutput, DidResolvable,
+    },
+};
+use http_body_util::{combinators::BoxBody, BodyExt as _, Empty};
+use hyper::{body::Bytes, header::ACCEPT, Request, StatusCode, Uri};
+use hyper_tls::HttpsConnector;
+use hyper_util::{
+    client::legacy::{
+        connect::{Connect, HttpConnector},
+        Client,
+    },
+    rt::TokioExecutor,
+};
+use percent_encoding::{utf8_percent_encode, NON_ALPHANUMERIC};
+use serde_json::Value;
+
+use crate::error::DidUniversalResolverError;
+
+/// Media type of the DID resolution result, as opposed to the DID document alone
+pub const DID_RESOLUTION_RESULT_MEDIA_TYPE: &str =
+    "application/ld+json;profile=\"https://w3id.org/did-resolution\"";
+
+/// Path of the Universal Resolver (and its drivers) endpoint resolving DIDs
+pub const IDENTIFIERS_PATH: &str = "/1.0/identifiers/";
+
+/// Client of a DIF Universal Resolver (or a single Universal Resolver driver), resolving DIDs
+/// through its `/1.0/identifiers/{did}` endpoint. Resolution options are passed as query
+/// parameters.
+pub struct UniversalResolver<C>
+where
+    C: Connect + Send + Sync + Clone + 'static,
+{
+    client: Client<C, BoxBody<Bytes, GenericError>>,
+    endpoint: String,
+}
+
+impl UniversalResolver<HttpConnector> {
+    /// `endpoint` is the base URL of the Universal Resolver, e.g. `http://localhost:8080`
+    pub fn http(endpoint: Uri) -> UniversalResolver<HttpConnector> {
+        UniversalResolver {
+            client: Client::builder(TokioExecutor::new())
+                .build::<_, BoxBody<Bytes, GenericError>>(HttpConnector::new()),
+            endpoint: endpoint.to_string(),
+        }
+    }
+}
+
+impl UniversalResolver<HttpsConnector<HttpConnector>> {
+    /// `endpoint` is the base URL of the Universal Resolver, e.g. `https://dev.uniresolver.io`
+    pub fn https(endpoint: Uri) -> UniversalResolver<HttpsConnector<HttpConnector>> {
+        UniversalResolver {
+            client: Client::builder(TokioExecutor::new())
+                .build::<_, BoxBody<Bytes, GenericError>>(HttpsConnector::new()),
+            endpoint: endpoint.to_string(),
+        }
+    }
+}
+
+impl<C> UniversalResolver<C>
+where
+    C: Connect + Send + Sync + Clone + 'static,
+{
+    fn identifier_url(
+        &self,
+        did: &Did,
+        options: &HashMap<String, Value>,
+    ) -> Result<Uri, DidUniversalResolverError> {
+        let mut url = format!(
+            "{}{}{}",
+            self.endpoint.trim_end_matches('/'),
+            IDENTIFIERS_PATH,
+            did.did()
+        );
+        let mut options: Vec<_> = options.iter().collect();
+        options.sort_by_key(|(name, _)| name.as_str());
+        for (i, (name, value)) in options.into_iter().enumerate() {
+            let value = match value {
+                Value::String(value) => value.to_owned(),
+                value => value.to_string(),
+            };
+            url.push(if i == 0 { '?' } else { '&' });
+            url.push_str(&format!(
+                "{}={}",
+                utf8_percent_encode(name, NON_ALPHANUMERIC),
+                utf8_percent_encode(&value, NON_ALPHANUMERIC)
+            ));
+        }
+        Ok(url.parse()?)
+    }
+
+    async fn fetch_resolution_result(
+        &self,
+        url: Uri,
+    ) -> Result<(StatusCode, Bytes), DidUniversalResolverError> {
+        let request = Request::get(url)
+            .header(ACCEPT, DID_RESOLUTION_RESULT_MEDIA_TYPE)
+            .body(Empty::new().map_err(|never| match never {}).boxed())?;
+        let res = self.client.request(request).await?;
+        let status = res.status();
+        let body = res.into_body().collect().await?.to_bytes();
+        Ok((status, body))
+    }
+}
+
+#[async_trait]
+impl<C> DidResolvable for UniversalResolver<C>
+where
+    C: Connect + Send + Sync + Clone + 'static,
+{
+    type DidResolutionOptions = HashMap<String, Value>;
+
+    async fn resolve(
+        &self,
+        did: &Did,
+        options: &Self::DidResolutionOptions,
+    ) -> Result<DidResolutionOutput, GenericError> {
+        let url = self.identifier_url(did, options)?;
+        let (status, body) = self.fetch_resolution_result(url).await?;
+        let body: Value = serde_json::from_slice(&body).unwrap_or(Value::Null);
+
+        // deactivated DIDs are still resolved, but with 410 Gone status
+        let has_document = body.get("didDocument").is_some_and(|doc| !doc.is_null());
+        let resolved = status.is_success() || (status == StatusCode::GONE && has_document);
+        if !resolved {
+            let error = body
+                .pointer("/didResolutionMetadata/error")
+                .and_then(Value::as_str)
+                .map(str::to_owned);
+            // errors of the DID Resolution spec are passed on as such
+            if let Some(error) = error.as_ref().and_then(|error| {
+                serde_json::from_value::<DidResolutionError>(error.as_str().into()).ok()
+            }) {
+                return Err(Box::new(error));
+            }
+            return Err(Box::new(DidUniversalResolverError::NonSuccessResponse {
+                status,
+                error,
+            }));
+        }
+
+        let resolution_output = if has_document {
+            resolution_output_from_result(body)?
+        } else {
+            // plain DID document, returned by drivers ignoring the requested media type
+            let did_document: DidDocument =
+                serde_json::from_value(body).map_err(DidUniversalResolverError::from)?;
+            DidResolutionOutput::builder(did_document)
+                .did_resolution_metadata(
+                    DidResolutionMetadata::builder()
+                        .content_type("application/did+json".to_string())
+                        .build(),
+                )
+                .build()
+        };
+        resolution_output.did_document.validate()?;
+        Ok(resolution_output)
+    }
+}
+
+/// Maps a DID resolution result, whose metadata properties are camelCase on the wire
+fn resolution_output_from_result(
+    mut body: Value,
+) -> Result<DidResolutionOutput, DidUniversalResolverError> {
+    let did_document: DidDocument = serde_json::from_value(body["didDocument"].take())?;
+    let did_document_metadata = match body.get_mut("didDocumentMetadata") {
+        Some(metadata) if !metadata.is_null() => serde_json::from_value(metadata.take())?,
+        _ => Default::default(),
+    };
+    let mut did_resolution_metadata = DidResolutionMetadata::builder();
+    if let Some(content_type) = body
+        .pointer("/didResolutionMetadata/contentType")
+        .and_then(Value::as_str)
+    {
+        did_resolution_metadata = did_resolution_metadata.content_type(content_type.to_owned());
+    }
+    Ok(DidResolutionOutput::builder(did_document)
+        .did_resolution_metadata(did_resolution_metadata.build())
+        .did_document_metadata(did_document_metadata)
+        .build())
+}
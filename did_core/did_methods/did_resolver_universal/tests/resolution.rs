@@ -0,0 +1,163 @@
+use std::{convert::Infallible, net::SocketAddr};
+
+use did_resolver::{
+    did_parser_nom::Did,
+    traits::resolvable::{resolution_error::DidResolutionError, DidResolvable},
+};
+use did_resolver_universal::{
+    error::DidUniversalResolverError, resolution::resolver::UniversalResolver,
+};
+use http_body_util::{combinators::BoxBody, BodyExt, Full};
+use hyper::{
+    body::{Bytes, Incoming},
+    header::ACCEPT,
+    service::service_fn,
+    Request, Response,
+};
+use hyper_util::{
+    rt::{TokioExecutor, TokioIo},
+    server::conn::auto::Builder,
+};
+use serde_json::json;
+use tokio::net::TcpListener;
+
+const DID: &str = "did:example:123456789abcdefghi";
+
+fn resolution_result() -> serde_json::Value {
+    json!({
+        "didDocument": {
+            "id": DID,
+            "verificationMethod": [{
+                "id": format!("{DID}#keys-1"),
+                "type": "Ed25519VerificationKey2018",
+                "controller": DID,
+                "publicKeyBase58": "H3C2AVvLMv6gmMNam3uVAjZpfkcJCwDwnZn6z3wXmqPV"
+            }],
+            "authentication": [format!("{DID}#keys-1")]
+        },
+        "didResolutionMetadata": {
+            "contentType": "application/did+ld+json",
+            "pattern": "^(did:example:.+)$",
+            "driverUrl": "http://example-driver:8080/1.0/identifiers/"
+        },
+        "didDocumentMetadata": {
+            "versionId": "2"
+        }
+    })
+}
+
+async fn mock_server_handler(
+    req: Request<Incoming>,
+) -> Result<Response<BoxBody<Bytes, Infallible>>, Infallible> {
+    assert!(req
+        .headers()
+        .get(ACCEPT)
+        .unwrap()
+        .to_str()
+        .unwrap()
+        .contains("https://w3id.org/did-resolution"));
+    let path = req.uri().path();
+    let query = req.uri().query().unwrap_or_default();
+    let response = if path == format!("/1.0/identifiers/{DID}") {
+        let mut result = resolution_result();
+        if query == "versionId=1" {
+            result["didDocumentMetadata"]["versionId"] = json!("1");
+        }
+        Response::new(Full::new(Bytes::from(result.to_string())).boxed())
+    } else if path == "/1.0/identifiers/did:example:unsupported" {
+        Response::builder()
+            .status(501)
+            .body(
+                Full::new(Bytes::from(
+                    json!({
+                        "didDocument": null,
+                        "didResolutionMetadata": { "error": "methodNotSupported" },
+                        "didDocumentMetadata": {}
+                    })
+                    .to_string(),
+                ))
+                .boxed(),
+            )
+            .unwrap()
+    } else {
+        Response::builder()
+            .status(500)
+            .body(Full::new(Bytes::from("Internal Server Error")).boxed())
+            .unwrap()
+    };
+
+    Ok(response)
+}
+
+async fn create_mock_server() -> SocketAddr {
+    let tcp_listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = tcp_listener.local_addr().unwrap();
+
+    tokio::spawn(async move {
+        loop {
+            let (stream, _) = match tcp_listener.accept().await {
+                Ok(x) => x,
+                Err(e) => {
+                    eprintln!("failed to accept connection: {e}");
+                    continue;
+                }
+            };
+            tokio::spawn(async move {
+                if let Err(e) = Builder::new(TokioExecutor::new())
+                    .serve_connection(TokioIo::new(stream), service_fn(mock_server_handler))
+                    .await
+                {
+                    eprintln!("error serving connection: {e}");
+                }
+            });
+        }
+    });
+
+    addr
+}
+
+#[tokio::test]
+async fn test_universal_resolver() {
+    let addr = create_mock_server().await;
+    let resolver = UniversalResolver::http(format!("http://{addr}").parse().unwrap());
+
+    let did = Did::parse(DID.to_string()).unwrap();
+    let output = resolver.resolve(&did, &Default::default()).await.unwrap();
+    assert_eq!(output.did_document.id(), &did);
+    assert_eq!(output.did_document.verification_method().len(), 1);
+    assert_eq!(
+        output.did_resolution_metadata.content_type().unwrap(),
+        "application/did+ld+json"
+    );
+    assert_eq!(output.did_document_metadata.version_id().unwrap(), "2");
+
+    let options = [("versionId".to_string(), json!("1"))].into();
+    let output = resolver.resolve(&did, &options).await.unwrap();
+    assert_eq!(output.did_document_metadata.version_id().unwrap(), "1");
+}
+
+#[tokio::test]
+async fn test_universal_resolver_errors() {
+    let addr = create_mock_server().await;
+    let resolver = UniversalResolver::http(format!("http://{addr}/").parse().unwrap());
+
+    let did = Did::parse("did:example:unsupported".to_string()).unwrap();
+    let err = resolver
+        .resolve(&did, &Default::default())
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err.downcast_ref::<DidResolutionError>(),
+        Some(&DidResolutionError::MethodNotSupported)
+    );
+
+    let did = Did::parse("did:example:failing".to_string()).unwrap();
+    let err = resolver
+        .resolve(&did, &Default::default())
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err.downcast_ref::<DidUniversalResolverError>(),
+        Some(DidUniversalResolverError::NonSuccessResponse { error: None, .. })
+    ));
+}
@@ -0,0 +1,179 @@
+use did_resolver::{
+    did_doc::schema::{
+        contexts,
+        did_doc::DidDocument,
+        service::Service,
+        verification_method::{PublicKeyField, VerificationMethod, VerificationMethodType},
+    },
+    did_parser_nom::DidUrl,
+};
+use public_key::{Key, KeyType};
+use serde_json::json;
+
+use super::did_web::DidWeb;
+use crate::error::DidWebError;
+
+/// Verification relationships a key is referenced by in the DID document
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyPurpose {
+    Authentication,
+    AssertionMethod,
+    KeyAgreement,
+    CapabilityInvocation,
+    CapabilityDelegation,
+}
+
+impl KeyPurpose {
+    /// Purposes a key is used for by default: signing keys authenticate and assert,
+    /// X25519 keys are used for key agreement only
+    pub fn defaults_for(key_type: &KeyType) -> &'static [KeyPurpose] {
+        match key_type {
+            KeyType::X25519 => &[KeyPurpose::KeyAgreement],
+            _ => &[KeyPurpose::Authentication, KeyPurpose::AssertionMethod],
+        }
+    }
+}
+
+/// Builds the DID document of a did:web, with `Multikey` verification methods identified
+/// as `#key-{index}` in order of addition
+pub struct DidWebDocumentBuilder {
+    did_web: DidWeb,
+    did_doc: DidDocument,
+}
+
+impl DidWebDocumentBuilder {
+    pub fn new(did_web: DidWeb) -> Self {
+        let did_doc = DidDocument::new(did_web.did().to_owned());
+        Self { did_web, did_doc }
+    }
+
+    /// Adds the key with the default purposes of its type, see [`KeyPurpose::defaults_for`]
+    pub fn add_key(self, key: &Key) -> Result<Self, DidWebError> {
+        let purposes = KeyPurpose::defaults_for(key.key_type());
+        self.add_key_with_purposes(key, purposes)
+    }
+
+    pub fn add_key_with_purposes(
+        mut self,
+        key: &Key,
+        purposes: &[KeyPurpose],
+    ) -> Result<Self, DidWebError> {
+        let did = self.did_web.did();
+        let vm_id = DidUrl::parse(format!(
+            "{}#key-{}",
+            did,
+            self.did_doc.verification_method().len()
+        ))?;
+        let vm = VerificationMethod::builder()
+            .id(vm_id.clone())
+            .controller(did.to_owned())
+            .verification_method_type(VerificationMethodType::Multikey)
+            .public_key(PublicKeyField::Multibase {
+                public_key_multibase: key.fingerprint(),
+            })
+            .build();
+        self.did_doc.add_verification_method(vm);
+        for purpose in purposes {
+            match purpose {
+                KeyPurpose::Authentication => self.did_doc.add_authentication_ref(vm_id.clone()),
+                KeyPurpose::AssertionMethod => self.did_doc.add_assertion_method_ref(vm_id.clone()),
+                KeyPurpose::KeyAgreement => self.did_doc.add_key_agreement_ref(vm_id.clone()),
+                KeyPurpose::CapabilityInvocation => {
+                    self.did_doc.add_capability_invocation_ref(vm_id.clone())
+                }
+                KeyPurpose::CapabilityDelegation => {
+                    self.did_doc.add_capability_delegation_ref(vm_id.clone())
+                }
+            }
+        }
+        Ok(self)
+    }
+
+    /// Adds the service; its id should be a DID URL of this did:web, e.g. `{did}#agent`
+    pub fn add_service(mut self, service: Service) -> Self {
+        self.did_doc.add_service(service);
+        self
+    }
+
+    /// Builds the DID document, failing if it does not conform to DID Core
+    pub fn build(mut self) -> Result<DidDocument, DidWebError> {
+        let mut context = vec![contexts::W3C_DID_V1];
+        if !self.did_doc.verification_method().is_empty() {
+            context.push(contexts::W3C_MULTIKEY_V1);
+        }
+        self.did_doc
+            .set_extra_field(String::from("@context"), json!(context));
+        self.did_doc.validate()?;
+        Ok(self.did_doc)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use did_resolver::did_doc::schema::{
+        service::typed::ServiceType, types::uri::Uri, utils::OneOrList,
+    };
+
+    use super::*;
+
+    const ED25519_FINGERPRINT: &str = "z6MkiTBz1ymuepAQ4HEHYSF1H8quG5GLVVQR3djdX3mDooWp";
+    const X25519_FINGERPRINT: &str = "z6LSeu9HkTHSfLLeUs2nnzUSNedgDUevfNQgQjQC23ZCit6F";
+
+    #[test]
+    fn test_build_did_web_document() {
+        let did_web = DidWeb::new("example.com", None, &["issuer"]).unwrap();
+        let service = Service::new(
+            Uri::new("did:web:example.com:issuer#agent").unwrap(),
+            "https://example.com/agent".parse().unwrap(),
+            OneOrList::One(ServiceType::DIDCommV1),
+            HashMap::new(),
+        );
+        let did_doc = DidWebDocumentBuilder::new(did_web)
+            .add_key(&Key::from_fingerprint(ED25519_FINGERPRINT).unwrap())
+            .unwrap()
+            .add_key(&Key::from_fingerprint(X25519_FINGERPRINT).unwrap())
+            .unwrap()
+            .add_service(service)
+            .build()
+            .unwrap();
+
+        assert_eq!(did_doc.id().to_string(), "did:web:example.com:issuer");
+        assert_eq!(did_doc.verification_method().len(), 2);
+        let vm = did_doc.verification_method_by_id("key-0").unwrap();
+        assert_eq!(
+            vm.verification_method_type(),
+            &VerificationMethodType::Multikey
+        );
+        assert_eq!(
+            vm.public_key_field(),
+            &PublicKeyField::Multibase {
+                public_key_multibase: ED25519_FINGERPRINT.to_string()
+            }
+        );
+        assert_eq!(did_doc.authentication().len(), 1);
+        assert_eq!(did_doc.assertion_method().len(), 1);
+        assert!(did_doc.key_agreement_by_id("key-1").is_some());
+        assert_eq!(did_doc.service().len(), 1);
+        assert_eq!(
+            did_doc.extra_field("@context").unwrap(),
+            &json!([contexts::W3C_DID_V1, contexts::W3C_MULTIKEY_V1])
+        );
+    }
+
+    #[test]
+    fn test_build_did_web_document_with_purposes() {
+        let did_web = DidWeb::new("example.com", None, &[]).unwrap();
+        let did_doc = DidWebDocumentBuilder::new(did_web)
+            .add_key_with_purposes(
+                &Key::from_fingerprint(ED25519_FINGERPRINT).unwrap(),
+                &[KeyPurpose::CapabilityInvocation],
+            )
+            .unwrap()
+            .build()
+            .unwrap();
+        assert!(did_doc.authentication().is_empty());
+        assert!(did_doc.capability_invocation_by_id("key-0").is_some());
+    }
+}
@@ -0,0 +1,191 @@
+use did_resolver::did_parser_nom::Did;
+use hyper::{
+    http::uri::{self, Scheme},
+    Uri,
+};
+
+use crate::error::DidWebError;
+
+const WELL_KNOWN_PATH: &str = "/.well-known/did.json";
+const DID_DOCUMENT_FILE: &str = "did.json";
+const ENCODED_PORT_SEPARATOR: &str = "%3A";
+
+/// did:web identifier, mapped to the location of its DID document as defined by
+/// https://w3c-ccg.github.io/did-method-web/#read-resolve:
+/// * `did:web:example.com` is hosted at `https://example.com/.well-known/did.json`
+/// * `did:web:example.com%3A3000:user:alice` is hosted at
+///   `https://example.com:3000/user/alice/did.json`
+#[derive(Clone, Debug, PartialEq)]
+pub struct DidWeb {
+    did: Did,
+    authority: String,
+    path: Vec<String>,
+}
+
+impl DidWeb {
+    /// Creates the DID identifying the document hosted by `host` (with optional `port`),
+    /// at the path made of `path` segments. Without path segments, the document is hosted
+    /// under the `.well-known` path.
+    pub fn new(host: &str, port: Option<u16>, path: &[&str]) -> Result<Self, DidWebError> {
+        if host.is_empty() || host.contains([':', '/']) {
+            return Err(DidWebError::InvalidDid(format!(
+                "Invalid did:web host: {host}"
+            )));
+        }
+        if let Some(segment) = path
+            .iter()
+            .find(|segment| segment.is_empty() || segment.contains([':', '/']))
+        {
+            return Err(DidWebError::InvalidDid(format!(
+                "Invalid did:web path segment: {segment}"
+            )));
+        }
+        let authority = match port {
+            Some(port) => format!("{host}:{port}"),
+            None => host.to_string(),
+        };
+        let mut did = format!("did:web:{}", authority.replace(':', ENCODED_PORT_SEPARATOR));
+        for segment in path {
+            did.push(':');
+            did.push_str(segment);
+        }
+        Ok(Self {
+            did: Did::parse(did)?,
+            authority,
+            path: path.iter().map(|segment| segment.to_string()).collect(),
+        })
+    }
+
+    /// Creates the DID of the document hosted at `url`, e.g. `https://example.com/user/alice`.
+    /// The URL may point to the `did.json` file itself, or to the `.well-known` location.
+    pub fn from_url(url: &Uri) -> Result<Self, DidWebError> {
+        let host = url
+            .host()
+            .ok_or_else(|| DidWebError::InvalidDid(format!("URL without host: {url}")))?;
+        let path = url.path();
+        let path = if path == WELL_KNOWN_PATH {
+            ""
+        } else {
+            path.strip_suffix(DID_DOCUMENT_FILE).unwrap_or(path)
+        };
+        let path: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+        Self::new(host, url.port_u16(), &path)
+    }
+
+    pub fn parse(did: &Did) -> Result<Self, DidWebError> {
+        let method = did.method().ok_or_else(|| {
+            DidWebError::InvalidDid("Attempted to resolve unqualified did".to_string())
+        })?;
+        if method != "web" {
+            return Err(DidWebError::MethodNotSupported(method.to_string()));
+        }
+
+        let mut did_parts = did.id().split(':');
+        let authority = match did_parts.next() {
+            Some(authority) if !authority.is_empty() => {
+                authority.replace(ENCODED_PORT_SEPARATOR, ":")
+            }
+            _ => return Err(DidWebError::InvalidDid(did.id().to_string())),
+        };
+        Ok(Self {
+            did: did.to_owned(),
+            authority,
+            path: did_parts.map(str::to_string).collect(),
+        })
+    }
+
+    pub fn did(&self) -> &Did {
+        &self.did
+    }
+
+    /// Host and optional port serving the DID document
+    pub fn authority(&self) -> &str {
+        &self.authority
+    }
+
+    /// Path of the DID document on the host, e.g. `/.well-known/did.json`
+    pub fn document_path(&self) -> String {
+        if self.path.is_empty() {
+            WELL_KNOWN_PATH.to_string()
+        } else {
+            format!("/{}/{DID_DOCUMENT_FILE}", self.path.join("/"))
+        }
+    }
+
+    /// URL of the DID document, `https` per the spec, `http` for local development only
+    pub fn document_url(&self, scheme: Scheme) -> Result<Uri, DidWebError> {
+        Ok(uri::Builder::new()
+            .scheme(scheme)
+            .authority(self.authority.as_str())
+            .path_and_query(self.document_path())
+            .build()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_did_web_without_path() {
+        let did_web = DidWeb::new("w3c-ccg.github.io", None, &[]).unwrap();
+        assert_eq!(did_web.did().to_string(), "did:web:w3c-ccg.github.io");
+        assert_eq!(
+            did_web.document_url(Scheme::HTTPS).unwrap().to_string(),
+            "https://w3c-ccg.github.io/.well-known/did.json"
+        );
+    }
+
+    #[test]
+    fn test_did_web_with_path_and_port() {
+        let did_web = DidWeb::new("example.com", Some(3000), &["user", "alice"]).unwrap();
+        assert_eq!(
+            did_web.did().to_string(),
+            "did:web:example.com%3A3000:user:alice"
+        );
+        assert_eq!(did_web.authority(), "example.com:3000");
+        assert_eq!(did_web.document_path(), "/user/alice/did.json");
+        assert_eq!(
+            did_web.document_url(Scheme::HTTPS).unwrap().to_string(),
+            "https://example.com:3000/user/alice/did.json"
+        );
+    }
+
+    #[test]
+    fn test_did_web_from_url() {
+        for (url, did) in [
+            ("https://example.com", "did:web:example.com"),
+            (
+                "https://example.com/.well-known/did.json",
+                "did:web:example.com",
+            ),
+            (
+                "https://example.com/user/alice/",
+                "did:web:example.com:user:alice",
+            ),
+            (
+                "http://localhost:8080/user/alice/did.json",
+                "did:web:localhost%3A8080:user:alice",
+            ),
+        ] {
+            let did_web = DidWeb::from_url(&url.parse().unwrap()).unwrap();
+            assert_eq!(did_web.did().to_string(), did);
+        }
+    }
+
+    #[test]
+    fn test_did_web_parse_round_trip() {
+        let did_web = DidWeb::new("example.com", Some(3000), &["user", "alice"]).unwrap();
+        assert_eq!(DidWeb::parse(did_web.did()).unwrap(), did_web);
+    }
+
+    #[test]
+    fn test_did_web_invalid() {
+        assert!(DidWeb::new("", None, &[]).is_err());
+        assert!(DidWeb::new("example.com", None, &["user/alice"]).is_err());
+        assert!(matches!(
+            DidWeb::parse(&Did::parse("did:key:z6Mkabc".to_string()).unwrap()),
+            Err(DidWebError::MethodNotSupported(_))
+        ));
+    }
+}
@@ -0,0 +1,2 @@
+pub mod builder;
+pub mod did_web;
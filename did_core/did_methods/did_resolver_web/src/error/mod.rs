@@ -1,5 +1,6 @@
 pub mod parsing;
 
+use did_resolver::did_doc::schema::validation::DidDocumentValidationError;
 use hyper::StatusCode;
 use thiserror::Error;
 
@@ -14,6 +15,8 @@ pub enum DidWebError {
     RepresentationNotSupported(String),
     #[error("Invalid DID: {0}")]
     InvalidDid(String),
+    #[error("Invalid DID document: {0}")]
+    InvalidDidDocument(#[from] DidDocumentValidationError),
     #[error("Parsing error: {0}")]
     ParsingError(#[from] ParsingErrorSource),
     #[error("Network error: {0}")]
@@ -1,3 +1,4 @@
+use did_resolver::did_parser_nom;
 use thiserror::Error;
 
 use super::DidWebError;
@@ -8,6 +9,10 @@ pub enum ParsingErrorSource {
     JsonError(#[from] serde_json::Error),
     #[error("Invalid encoding: {0}")]
     Utf8Error(#[from] std::string::FromUtf8Error),
+    #[error("DID parsing error: {0}")]
+    DidParserError(#[from] did_parser_nom::ParseError),
+    #[error("Invalid URI: {0}")]
+    InvalidUri(#[from] hyper::http::Error),
 }
 
 impl From<serde_json::Error> for DidWebError {
@@ -21,3 +26,15 @@ impl From<std::string::FromUtf8Error> for DidWebError {
         DidWebError::ParsingError(ParsingErrorSource::Utf8Error(error))
     }
 }
+
+impl From<did_parser_nom::ParseError> for DidWebError {
+    fn from(error: did_parser_nom::ParseError) -> Self {
+        DidWebError::ParsingError(ParsingErrorSource::DidParserError(error))
+    }
+}
+
+impl From<hyper::http::Error> for DidWebError {
+    fn from(error: hyper::http::Error) -> Self {
+        DidWebError::ParsingError(ParsingErrorSource::InvalidUri(error))
+    }
+}
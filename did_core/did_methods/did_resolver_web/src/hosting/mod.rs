@@ -0,0 +1,84 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, RwLock},
+};
+
+use axum::{
+    extract::State,
+    http::{header, StatusCode, Uri},
+    response::{IntoResponse, Response},
+    routing::get,
+    Router,
+};
+use did_resolver::{did_doc::schema::did_doc::DidDocument, did_parser_nom::Did};
+
+use crate::{creation::did_web::DidWeb, error::DidWebError};
+
+const DID_JSON_CONTENT_TYPE: &str = "application/did+json";
+
+/// DID documents of did:webs hosted by this server, keyed by their did:web path
+#[derive(Clone, Default)]
+pub struct DidWebDocuments {
+    documents: Arc<RwLock<HashMap<String, DidDocument>>>,
+}
+
+impl DidWebDocuments {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Hosts the document at the path given by its did:web id, replacing the document
+    /// previously hosted there
+    pub fn insert(&self, did_document: DidDocument) -> Result<(), DidWebError> {
+        let did_web = DidWeb::parse(did_document.id())?;
+        did_document.validate()?;
+        self.documents
+            .write()
+            .expect("did:web documents lock poisoned")
+            .insert(did_web.document_path(), did_document);
+        Ok(())
+    }
+
+    pub fn remove(&self, did: &Did) -> Result<Option<DidDocument>, DidWebError> {
+        let did_web = DidWeb::parse(did)?;
+        Ok(self
+            .documents
+            .write()
+            .expect("did:web documents lock poisoned")
+            .remove(&did_web.document_path()))
+    }
+
+    pub fn get(&self, path: &str) -> Option<DidDocument> {
+        self.documents
+            .read()
+            .expect("did:web documents lock poisoned")
+            .get(path)
+            .cloned()
+    }
+
+    /// Router serving the hosted documents at `/.well-known/did.json` and `/{path}/did.json`,
+    /// to be merged into the application router or served on its own
+    pub fn router(&self) -> Router {
+        Router::new()
+            .route("/.well-known/did.json", get(serve_did_document))
+            .route("/{*path}", get(serve_did_document))
+            .with_state(self.clone())
+    }
+}
+
+/// Handler responding with the document hosted at the request path, usable as a fallback
+/// of an application router with [`DidWebDocuments`] state
+pub async fn serve_did_document(State(documents): State<DidWebDocuments>, uri: Uri) -> Response {
+    match documents.get(uri.path()) {
+        Some(did_document) => match serde_json::to_string(&did_document) {
+            Ok(body) => (
+                StatusCode::OK,
+                [(header::CONTENT_TYPE, DID_JSON_CONTENT_TYPE)],
+                body,
+            )
+                .into_response(),
+            Err(err) => (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()).into_response(),
+        },
+        None => StatusCode::NOT_FOUND.into_response(),
+    }
+}
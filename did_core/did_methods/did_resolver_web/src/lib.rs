@@ -1,2 +1,5 @@
+pub mod creation;
 pub mod error;
+#[cfg(feature = "hosting")]
+pub mod hosting;
 pub mod resolution;
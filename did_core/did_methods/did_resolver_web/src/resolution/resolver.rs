@@ -1,5 +1,6 @@
 use async_trait::async_trait;
 use did_resolver::{
+    did_doc::schema::did_doc::DidDocument,
     did_parser_nom::Did,
     error::GenericError,
     shared_types::did_document_metadata::DidDocumentMetadata,
@@ -9,11 +10,7 @@ use did_resolver::{
     },
 };
 use http_body_util::{combinators::BoxBody, BodyExt as _};
-use hyper::{
-    body::Bytes,
-    http::uri::{self, Scheme},
-    Uri,
-};
+use hyper::{body::Bytes, http::uri::Scheme, Uri};
 use hyper_tls::HttpsConnector;
 use hyper_util::{
     client::legacy::{
@@ -23,7 +20,7 @@ use hyper_util::{
     rt::TokioExecutor,
 };
 
-use crate::error::DidWebError;
+use crate::{creation::did_web::DidWeb, error::DidWebError};
 
 pub struct DidWebResolver<C>
 where
@@ -82,37 +79,10 @@ where
         did: &Did,
         _options: &Self::DidResolutionOptions,
     ) -> Result<DidResolutionOutput, GenericError> {
-        let method = did.method().ok_or_else(|| {
-            DidWebError::InvalidDid("Attempted to resolve unqualified did".to_string())
-        })?;
-        if method != "web" {
-            return Err(Box::new(DidWebError::MethodNotSupported(
-                method.to_string(),
-            )));
-        }
-
-        let did_parts: Vec<&str> = did.id().split(':').collect();
-
-        if did_parts.is_empty() {
-            return Err(Box::new(DidWebError::InvalidDid(did.id().to_string())));
-        }
-
-        let domain = did_parts[0].replace("%3A", ":");
-
-        let path_parts = &did_parts[1..];
-        let path_and_query = if path_parts.is_empty() {
-            "/.well-known/did.json".to_string()
-        } else {
-            let path = path_parts.join("/");
-            format!("/{}/did.json", path)
-        };
-        let url = uri::Builder::new()
-            .scheme(self.scheme.clone())
-            .authority(domain.as_str())
-            .path_and_query(path_and_query.as_str())
-            .build()?;
+        let url = DidWeb::parse(did)?.document_url(self.scheme.clone())?;
 
-        let did_document = serde_json::from_str(&self.fetch_did_document(url).await?)?;
+        let did_document: DidDocument = serde_json::from_str(&self.fetch_did_document(url).await?)?;
+        did_document.validate()?;
 
         let did_resolution_output = DidResolutionOutput::builder(did_document)
             .did_resolution_metadata(DidResolutionMetadata::default())
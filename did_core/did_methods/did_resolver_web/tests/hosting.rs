@@ -0,0 +1,91 @@
+#![cfg(feature = "hosting")]
+
+use std::collections::HashMap;
+
+use did_resolver::{
+    did_doc::schema::{
+        did_doc::DidDocument, service::typed::ServiceType, service::Service, types::uri::Uri,
+        utils::OneOrList,
+    },
+    did_parser_nom::Did,
+    traits::resolvable::DidResolvable,
+};
+use did_resolver_web::{
+    creation::{builder::DidWebDocumentBuilder, did_web::DidWeb},
+    hosting::DidWebDocuments,
+    resolution::resolver::DidWebResolver,
+};
+use public_key::Key;
+use tokio::net::TcpListener;
+
+const ED25519_FINGERPRINT: &str = "z6MkiTBz1ymuepAQ4HEHYSF1H8quG5GLVVQR3djdX3mDooWp";
+const X25519_FINGERPRINT: &str = "z6LSeu9HkTHSfLLeUs2nnzUSNedgDUevfNQgQjQC23ZCit6F";
+
+async fn serve(documents: &DidWebDocuments) -> u16 {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let router = documents.router();
+    tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+    port
+}
+
+fn build_did_document(did_web: DidWeb) -> DidDocument {
+    let service = Service::new(
+        Uri::new(&format!("{}#agent", did_web.did())).unwrap(),
+        "https://example.com/agent".parse().unwrap(),
+        OneOrList::One(ServiceType::DIDCommV1),
+        HashMap::new(),
+    );
+    DidWebDocumentBuilder::new(did_web)
+        .add_key(&Key::from_fingerprint(ED25519_FINGERPRINT).unwrap())
+        .unwrap()
+        .add_key(&Key::from_fingerprint(X25519_FINGERPRINT).unwrap())
+        .unwrap()
+        .add_service(service)
+        .build()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_hosted_did_web_round_trip() {
+    let documents = DidWebDocuments::new();
+    let port = serve(&documents).await;
+
+    for path in [vec![], vec!["user", "alice"]] {
+        let did_web = DidWeb::new("localhost", Some(port), &path).unwrap();
+        let did = did_web.did().to_owned();
+        let did_document = build_did_document(did_web);
+        documents.insert(did_document.clone()).unwrap();
+
+        let resolved = DidWebResolver::http()
+            .resolve(&did, &())
+            .await
+            .unwrap()
+            .did_document;
+        assert_eq!(resolved, did_document);
+        assert_eq!(resolved.authentication().len(), 1);
+        assert_eq!(resolved.key_agreement().len(), 1);
+        assert_eq!(resolved.service().len(), 1);
+    }
+}
+
+#[tokio::test]
+async fn test_removed_did_web_not_resolved() {
+    let documents = DidWebDocuments::new();
+    let port = serve(&documents).await;
+
+    let did_web = DidWeb::new("localhost", Some(port), &["issuer"]).unwrap();
+    let did = did_web.did().to_owned();
+    documents.insert(build_did_document(did_web)).unwrap();
+    assert!(documents.remove(&did).unwrap().is_some());
+
+    assert!(DidWebResolver::http().resolve(&did, &()).await.is_err());
+    let unknown = Did::parse(format!("did:web:localhost%3A{port}:unknown")).unwrap();
+    assert!(DidWebResolver::http().resolve(&unknown, &()).await.is_err());
+}
+
+#[test]
+fn test_insert_non_did_web_document_fails() {
+    let did_document = DidDocument::new(Did::parse("did:example:123".to_string()).unwrap());
+    assert!(DidWebDocuments::new().insert(did_document).is_err());
+}
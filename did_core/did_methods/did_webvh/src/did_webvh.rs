@@ -0,0 +1,136 @@
+use did_resolver::did_parser_nom::Did;
+use did_resolver_web::creation::did_web::DidWeb;
+use hyper::{http::uri::Scheme, Uri};
+
+use crate::error::{DidWebvhError, DidWebvhResult};
+
+/// Placeholder of the self-certifying identifier in the first log entry, before it is computed
+pub const SCID_PLACEHOLDER: &str = "{SCID}";
+
+const DID_DOCUMENT_FILE: &str = "did.json";
+const DID_LOG_FILE: &str = "did.jsonl";
+
+/// did:webvh identifier `did:webvh:{SCID}:{domain}[:{path}]`. The DID log is hosted at the
+/// location of the equivalent did:web document, in the `did.jsonl` file:
+/// * `did:webvh:{SCID}:example.com` at `https://example.com/.well-known/did.jsonl`
+/// * `did:webvh:{SCID}:example.com:user:alice` at `https://example.com/user/alice/did.jsonl`
+#[derive(Clone, Debug, PartialEq)]
+pub struct DidWebvh {
+    did: Did,
+    scid: String,
+    did_web: DidWeb,
+}
+
+impl DidWebvh {
+    pub fn new(scid: &str, did_web: DidWeb) -> DidWebvhResult<Self> {
+        let did = Did::parse(format!("did:webvh:{scid}:{}", did_web.did().id()))?;
+        Ok(Self {
+            did,
+            scid: scid.to_string(),
+            did_web,
+        })
+    }
+
+    pub fn parse(did: &Did) -> DidWebvhResult<Self> {
+        let method = did
+            .method()
+            .ok_or_else(|| DidWebvhError::InvalidDid("Unqualified DID".to_string()))?;
+        if method != "webvh" {
+            return Err(DidWebvhError::MethodNotSupported(method.to_string()));
+        }
+        let (scid, location) = did
+            .id()
+            .split_once(':')
+            .ok_or_else(|| DidWebvhError::InvalidDid(did.did().to_string()))?;
+        if scid.is_empty() {
+            return Err(DidWebvhError::InvalidDid(did.did().to_string()));
+        }
+        let did_web = DidWeb::parse(&Did::parse(format!("did:web:{location}"))?)?;
+        Ok(Self {
+            did: did.to_owned(),
+            scid: scid.to_string(),
+            did_web,
+        })
+    }
+
+    pub fn did(&self) -> &Did {
+        &self.did
+    }
+
+    /// Self-certifying identifier, the hash of the first log entry
+    pub fn scid(&self) -> &str {
+        &self.scid
+    }
+
+    /// did:web hosted at the same location, e.g. `did:web:example.com` for
+    /// `did:webvh:{SCID}:example.com`
+    pub fn did_web(&self) -> &DidWeb {
+        &self.did_web
+    }
+
+    /// Path of the DID log on the host, e.g. `/.well-known/did.jsonl`
+    pub fn log_path(&self) -> String {
+        let document_path = self.did_web.document_path();
+        let directory = document_path
+            .strip_suffix(DID_DOCUMENT_FILE)
+            .unwrap_or(&document_path);
+        format!("{directory}{DID_LOG_FILE}")
+    }
+
+    /// URL of the DID log, `https` per the spec, `http` for local development only
+    pub fn log_url(&self, scheme: Scheme) -> DidWebvhResult<Uri> {
+        Ok(Uri::builder()
+            .scheme(scheme)
+            .authority(self.did_web.authority())
+            .path_and_query(self.log_path())
+            .build()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCID: &str = "QmfGEUAcMpzo25kF2Rhn8L5FAXysfGnkzjwdKoNPi615XQ";
+
+    #[test]
+    fn test_did_webvh_parse() {
+        let did = Did::parse(format!("did:webvh:{SCID}:example.com%3A3000:user:alice")).unwrap();
+        let did_webvh = DidWebvh::parse(&did).unwrap();
+        assert_eq!(did_webvh.scid(), SCID);
+        assert_eq!(
+            did_webvh.did_web().did().to_string(),
+            "did:web:example.com%3A3000:user:alice"
+        );
+        assert_eq!(
+            did_webvh.log_url(Scheme::HTTPS).unwrap().to_string(),
+            "https://example.com:3000/user/alice/did.jsonl"
+        );
+    }
+
+    #[test]
+    fn test_did_webvh_well_known_log() {
+        let did_web = DidWeb::new("example.com", None, &[]).unwrap();
+        let did_webvh = DidWebvh::new(SCID, did_web).unwrap();
+        assert_eq!(
+            did_webvh.did().to_string(),
+            format!("did:webvh:{SCID}:example.com")
+        );
+        assert_eq!(did_webvh.log_path(), "/.well-known/did.jsonl");
+        assert_eq!(DidWebvh::parse(did_webvh.did()).unwrap(), did_webvh);
+    }
+
+    #[test]
+    fn test_did_webvh_parse_invalid() {
+        let did = Did::parse("did:web:example.com".to_string()).unwrap();
+        assert!(matches!(
+            DidWebvh::parse(&did),
+            Err(DidWebvhError::MethodNotSupported(_))
+        ));
+        let did = Did::parse(format!("did:webvh:{SCID}")).unwrap();
+        assert!(matches!(
+            DidWebvh::parse(&did),
+            Err(DidWebvhError::InvalidDid(_))
+        ));
+    }
+}
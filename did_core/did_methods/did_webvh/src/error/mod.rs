@@ -0,0 +1,50 @@
+use aries_vcx_wallet::errors::error::VcxWalletError;
+use did_resolver::did_doc::schema::validation::DidDocumentValidationError;
+use did_resolver_web::error::DidWebError;
+use hyper::StatusCode;
+use parsing::ParsingErrorSource;
+use public_key::PublicKeyError;
+use thiserror::Error;
+
+pub mod parsing;
+
+pub type DidWebvhResult<T> = Result<T, DidWebvhError>;
+
+#[derive(Error, Debug)]
+#[non_exhaustive]
+pub enum DidWebvhError {
+    #[error("DID method not supported: {0}")]
+    MethodNotSupported(String),
+    #[error("Invalid DID: {0}")]
+    InvalidDid(String),
+    #[error("Invalid DID log: {0}")]
+    InvalidLog(String),
+    #[error("Invalid log entry proof: {0}")]
+    InvalidProof(String),
+    #[error("Key not authorized to update the DID: {0}")]
+    UnauthorizedKey(String),
+    #[error("Unsupported DID log parameter: {0}")]
+    UnsupportedParameter(String),
+    #[error("DID is deactivated")]
+    Deactivated,
+    #[error("DID version not found: {0}")]
+    VersionNotFound(String),
+    #[error("Invalid DID document: {0}")]
+    InvalidDidDocument(#[from] DidDocumentValidationError),
+    #[error("Invalid key: {0}")]
+    InvalidKey(#[from] PublicKeyError),
+    #[error("Wallet error: {0}")]
+    WalletError(#[from] VcxWalletError),
+    #[error("did:web error: {0}")]
+    DidWebError(#[from] DidWebError),
+    #[error("Parsing error: {0}")]
+    ParsingError(#[from] ParsingErrorSource),
+    #[error("Network error: {0}")]
+    NetworkError(#[from] hyper::Error),
+    #[error("Network error: {0}")]
+    NetworkClientError(#[from] hyper_util::client::legacy::Error),
+    #[error("Non-success server response: {0}")]
+    NonSuccessResponse(StatusCode),
+    #[error(transparent)]
+    Other(#[from] Box<dyn std::error::Error + Send + Sync>),
+}
@@ -0,0 +1,56 @@
+use did_resolver::did_parser_nom;
+use thiserror::Error;
+
+use super::DidWebvhError;
+
+#[derive(Error, Debug)]
+pub enum ParsingErrorSource {
+    #[error("JSON parsing error: {0}")]
+    JsonError(#[from] serde_json::Error),
+    #[error("Invalid encoding: {0}")]
+    Utf8Error(#[from] std::string::FromUtf8Error),
+    #[error("DID parsing error: {0}")]
+    DidParserError(#[from] did_parser_nom::ParseError),
+    #[error("Invalid timestamp: {0}")]
+    TimestampError(#[from] chrono::ParseError),
+    #[error("Invalid base58 encoding: {0}")]
+    Base58Error(#[from] bs58::decode::Error),
+    #[error("Invalid URI: {0}")]
+    InvalidUri(#[from] hyper::http::Error),
+}
+
+impl From<serde_json::Error> for DidWebvhError {
+    fn from(error: serde_json::Error) -> Self {
+        DidWebvhError::ParsingError(ParsingErrorSource::JsonError(error))
+    }
+}
+
+impl From<std::string::FromUtf8Error> for DidWebvhError {
+    fn from(error: std::string::FromUtf8Error) -> Self {
+        DidWebvhError::ParsingError(ParsingErrorSource::Utf8Error(error))
+    }
+}
+
+impl From<did_parser_nom::ParseError> for DidWebvhError {
+    fn from(error: did_parser_nom::ParseError) -> Self {
+        DidWebvhError::ParsingError(ParsingErrorSource::DidParserError(error))
+    }
+}
+
+impl From<chrono::ParseError> for DidWebvhError {
+    fn from(error: chrono::ParseError) -> Self {
+        DidWebvhError::ParsingError(ParsingErrorSource::TimestampError(error))
+    }
+}
+
+impl From<bs58::decode::Error> for DidWebvhError {
+    fn from(error: bs58::decode::Error) -> Self {
+        DidWebvhError::ParsingError(ParsingErrorSource::Base58Error(error))
+    }
+}
+
+impl From<hyper::http::Error> for DidWebvhError {
+    fn from(error: hyper::http::Error) -> Self {
+        DidWebvhError::ParsingError(ParsingErrorSource::InvalidUri(error))
+    }
+}
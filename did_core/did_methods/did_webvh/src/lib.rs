@@ -0,0 +1,4 @@
+pub mod did_webvh;
+pub mod error;
+pub mod log;
+pub mod resolution;
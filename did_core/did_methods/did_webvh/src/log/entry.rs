@@ -0,0 +1,32 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::{parameters::Parameters, proof::DataIntegrityProof};
+use crate::error::{DidWebvhError, DidWebvhResult};
+
+/// Line of the DID log: version of the DID document with the parameters it changes, signed
+/// by an authorized update key
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LogEntry {
+    /// `{version number}-{entry hash}`
+    pub version_id: String,
+    pub version_time: String,
+    pub parameters: Parameters,
+    /// DID document of this version
+    pub state: Value,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub proof: Vec<DataIntegrityProof>,
+}
+
+impl LogEntry {
+    /// Splits the version id into the version number and the entry hash
+    pub fn version_number_and_hash(&self) -> DidWebvhResult<(u64, &str)> {
+        self.version_id
+            .split_once('-')
+            .and_then(|(number, hash)| Some((number.parse().ok()?, hash)))
+            .ok_or_else(|| {
+                DidWebvhError::InvalidLog(format!("Invalid version id: {}", self.version_id))
+            })
+    }
+}
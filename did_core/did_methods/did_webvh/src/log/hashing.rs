@@ -0,0 +1,57 @@
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+
+use crate::error::DidWebvhResult;
+
+/// Multihash prefix of SHA-256 digests: `sha2-256` code and digest length
+const SHA2_256_MULTIHASH_PREFIX: [u8; 2] = [0x12, 0x20];
+
+pub(crate) fn sha256(data: &[u8]) -> Vec<u8> {
+    Sha256::digest(data).to_vec()
+}
+
+fn multihash_sha256(data: &[u8]) -> Vec<u8> {
+    let mut multihash = SHA2_256_MULTIHASH_PREFIX.to_vec();
+    multihash.extend(sha256(data));
+    multihash
+}
+
+/// Base58btc encoded SHA-256 multihash of the JCS canonicalized value, as used for the SCID
+/// and entry hashes
+pub(crate) fn hash_json(value: &Value) -> DidWebvhResult<String> {
+    let canonical = serde_jcs::to_vec(value)?;
+    Ok(bs58::encode(multihash_sha256(&canonical)).into_string())
+}
+
+/// Hash of the update key (its multikey string) committed to in `nextKeyHashes` when
+/// pre-rotating keys
+pub fn next_key_hash(update_key: &str) -> String {
+    bs58::encode(multihash_sha256(update_key.as_bytes())).into_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_hash_json_is_canonical() {
+        let hash = hash_json(&json!({"b": 1, "a": [true, null]})).unwrap();
+        assert_eq!(
+            hash,
+            hash_json(&json!({"a": [true, null], "b": 1})).unwrap()
+        );
+        assert!(hash.starts_with("Qm"));
+    }
+
+    #[test]
+    fn test_next_key_hash() {
+        let hash = next_key_hash("z6MkiTBz1ymuepAQ4HEHYSF1H8quG5GLVVQR3djdX3mDooWp");
+        assert!(hash.starts_with("Qm"));
+        assert_ne!(
+            hash,
+            next_key_hash("z6LSeu9HkTHSfLLeUs2nnzUSNedgDUevfNQgQjQC23ZCit6F")
+        );
+    }
+}
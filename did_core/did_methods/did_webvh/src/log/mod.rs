@@ -0,0 +1,291 @@
+pub mod entry;
+pub(crate) mod hashing;
+pub mod operations;
+pub mod parameters;
+pub mod proof;
+
+use chrono::{DateTime, Utc};
+use did_resolver::did_doc::schema::did_doc::DidDocument;
+use serde_json::Value;
+
+use self::{entry::LogEntry, hashing::hash_json, parameters::Parameters};
+use crate::{
+    did_webvh::{DidWebvh, SCID_PLACEHOLDER},
+    error::{DidWebvhError, DidWebvhResult},
+};
+
+pub use hashing::next_key_hash;
+
+/// DID log (`did.jsonl`) of a did:webvh, one JSON log entry per line. Entries are kept as
+/// published, so their hashes and proofs are verified over the exact content.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DidWebvhLog {
+    entries: Vec<Value>,
+}
+
+/// Version of the DID document, as verified from the log
+#[derive(Clone, Debug, PartialEq)]
+pub struct DidWebvhVersion {
+    version_id: String,
+    version_number: u64,
+    version_time: DateTime<Utc>,
+    did_document: DidDocument,
+    parameters: Parameters,
+}
+
+impl DidWebvhVersion {
+    pub fn version_id(&self) -> &str {
+        &self.version_id
+    }
+
+    pub fn version_number(&self) -> u64 {
+        self.version_number
+    }
+
+    pub fn version_time(&self) -> DateTime<Utc> {
+        self.version_time
+    }
+
+    pub fn did_document(&self) -> &DidDocument {
+        &self.did_document
+    }
+
+    /// Parameters active after this version
+    pub fn parameters(&self) -> &Parameters {
+        &self.parameters
+    }
+}
+
+impl DidWebvhLog {
+    pub fn parse(jsonl: &str) -> DidWebvhResult<Self> {
+        let entries = jsonl
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(serde_json::from_str)
+            .collect::<Result<Vec<Value>, _>>()?;
+        Ok(Self { entries })
+    }
+
+    pub fn to_jsonl(&self) -> DidWebvhResult<String> {
+        let mut jsonl = String::new();
+        for entry in &self.entries {
+            jsonl.push_str(&serde_json::to_string(entry)?);
+            jsonl.push('\n');
+        }
+        Ok(jsonl)
+    }
+
+    pub fn entries(&self) -> DidWebvhResult<Vec<LogEntry>> {
+        self.entries
+            .iter()
+            .map(|entry| Ok(serde_json::from_value(entry.clone())?))
+            .collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Verifies the whole log as defined by https://identity.foundation/didwebvh/#read-resolve:
+    /// the SCID, the hash chain of the entries, their proofs by authorized update keys, the
+    /// pre-rotation commitments and the DID documents. Returns the versions in log order.
+    pub fn verify(&self) -> DidWebvhResult<Vec<DidWebvhVersion>> {
+        let mut versions: Vec<DidWebvhVersion> = Vec::with_capacity(self.entries.len());
+        let mut active = Parameters::default();
+        let mut previous_version_id = String::new();
+        let mut first_did = None;
+
+        for (index, raw_entry) in self.entries.iter().enumerate() {
+            let entry: LogEntry = serde_json::from_value(raw_entry.clone())?;
+            let (version_number, entry_hash) = entry.version_number_and_hash()?;
+            if version_number != index as u64 + 1 {
+                return Err(DidWebvhError::InvalidLog(format!(
+                    "Expected version number {}, got {version_number}",
+                    index + 1
+                )));
+            }
+            if active.is_deactivated() {
+                return Err(DidWebvhError::InvalidLog(format!(
+                    "Entry {} follows the deactivation of the DID",
+                    entry.version_id
+                )));
+            }
+            let unsigned_entry = without_proof(raw_entry);
+
+            if index == 0 {
+                previous_version_id = verify_first_entry(&entry, &unsigned_entry)?;
+            } else if entry
+                .parameters
+                .scid
+                .as_ref()
+                .is_some_and(|scid| Some(scid) != active.scid.as_ref())
+            {
+                return Err(DidWebvhError::InvalidLog("SCID changed".to_string()));
+            } else if entry.parameters.portable == Some(true) && !active.is_portable() {
+                return Err(DidWebvhError::InvalidLog(
+                    "Portability can only be enabled in the first entry".to_string(),
+                ));
+            }
+
+            let mut chained_entry = unsigned_entry.clone();
+            chained_entry["versionId"] = Value::String(previous_version_id);
+            if hash_json(&chained_entry)? != entry_hash {
+                return Err(DidWebvhError::InvalidLog(format!(
+                    "Invalid entry hash of version {}",
+                    entry.version_id
+                )));
+            }
+
+            let authorized_keys = authorized_update_keys(index, &active, &entry.parameters)?;
+            verify_proofs(&entry, &unsigned_entry, &authorized_keys)?;
+
+            active.apply(&entry.parameters);
+            if active.has_witnesses() {
+                return Err(DidWebvhError::UnsupportedParameter("witness".to_string()));
+            }
+
+            let version_time = DateTime::parse_from_rfc3339(&entry.version_time)?.to_utc();
+            if version_time > Utc::now() {
+                return Err(DidWebvhError::InvalidLog(format!(
+                    "Version time {} is in the future",
+                    entry.version_time
+                )));
+            }
+            if let Some(previous) = versions.last() {
+                if version_time < previous.version_time {
+                    return Err(DidWebvhError::InvalidLog(format!(
+                        "Version time {} is before the previous version",
+                        entry.version_time
+                    )));
+                }
+            }
+
+            let did_document: DidDocument = serde_json::from_value(entry.state.clone())?;
+            did_document.validate()?;
+            let did_webvh = DidWebvh::parse(did_document.id())?;
+            if Some(did_webvh.scid()) != active.scid.as_deref() {
+                return Err(DidWebvhError::InvalidLog(format!(
+                    "DID {} does not match the SCID of the log",
+                    did_document.id()
+                )));
+            }
+            match &first_did {
+                None => first_did = Some(did_document.id().to_owned()),
+                Some(first_did) if !active.is_portable() && first_did != did_document.id() => {
+                    return Err(DidWebvhError::InvalidLog(format!(
+                        "DID changed to {} without being portable",
+                        did_document.id()
+                    )));
+                }
+                Some(_) => {}
+            }
+
+            previous_version_id = entry.version_id.clone();
+            versions.push(DidWebvhVersion {
+                version_id: entry.version_id,
+                version_number,
+                version_time,
+                did_document,
+                parameters: active.clone(),
+            });
+        }
+
+        if versions.is_empty() {
+            return Err(DidWebvhError::InvalidLog("Empty DID log".to_string()));
+        }
+        Ok(versions)
+    }
+}
+
+fn without_proof(entry: &Value) -> Value {
+    let mut entry = entry.clone();
+    if let Some(entry) = entry.as_object_mut() {
+        entry.remove("proof");
+    }
+    entry
+}
+
+/// Verifies the method and SCID of the first entry, returns the SCID the first entry hash is
+/// chained to
+fn verify_first_entry(entry: &LogEntry, unsigned_entry: &Value) -> DidWebvhResult<String> {
+    match entry.parameters.method.as_deref() {
+        Some(parameters::METHOD_VERSION) => {}
+        method => {
+            return Err(DidWebvhError::UnsupportedParameter(format!(
+                "method {method:?}"
+            )))
+        }
+    }
+    let scid = entry
+        .parameters
+        .scid
+        .as_deref()
+        .ok_or_else(|| DidWebvhError::InvalidLog("Missing SCID".to_string()))?;
+
+    let mut preliminary_entry = unsigned_entry.clone();
+    preliminary_entry["versionId"] = Value::String(SCID_PLACEHOLDER.to_string());
+    let preliminary_entry: Value = serde_json::from_str(
+        &serde_json::to_string(&preliminary_entry)?.replace(scid, SCID_PLACEHOLDER),
+    )?;
+    if hash_json(&preliminary_entry)? != scid {
+        return Err(DidWebvhError::InvalidLog(format!("Invalid SCID {scid}")));
+    }
+    Ok(scid.to_string())
+}
+
+/// Update keys allowed to sign the entry: its own keys in the first entry or when pre-rotation
+/// is active (each committed to by the previous entry), the previously active keys otherwise
+fn authorized_update_keys(
+    index: usize,
+    active: &Parameters,
+    changes: &Parameters,
+) -> DidWebvhResult<Vec<String>> {
+    if index == 0 {
+        return Ok(changes.update_keys().to_vec());
+    }
+    if !active.is_pre_rotation_active() {
+        return Ok(active.update_keys().to_vec());
+    }
+    let update_keys = changes.update_keys.as_ref().ok_or_else(|| {
+        DidWebvhError::InvalidLog("Update keys must be rotated with pre-rotation".to_string())
+    })?;
+    if let Some(update_key) = update_keys
+        .iter()
+        .find(|key| !active.next_key_hashes().contains(&next_key_hash(key)))
+    {
+        return Err(DidWebvhError::UnauthorizedKey(format!(
+            "{update_key} was not committed to in nextKeyHashes"
+        )));
+    }
+    Ok(update_keys.clone())
+}
+
+fn verify_proofs(
+    entry: &LogEntry,
+    unsigned_entry: &Value,
+    authorized_keys: &[String],
+) -> DidWebvhResult<()> {
+    if entry.proof.is_empty() {
+        return Err(DidWebvhError::InvalidProof(format!(
+            "Version {} is not signed",
+            entry.version_id
+        )));
+    }
+    let mut authorized = false;
+    for proof in &entry.proof {
+        proof.verify(unsigned_entry)?;
+        let update_key = proof.update_key()?;
+        authorized |= authorized_keys.iter().any(|key| key == update_key);
+    }
+    if !authorized {
+        return Err(DidWebvhError::UnauthorizedKey(format!(
+            "Version {} is not signed by an authorized update key",
+            entry.version_id
+        )));
+    }
+    Ok(())
+}
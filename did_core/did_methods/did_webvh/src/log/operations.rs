@@ -0,0 +1,158 @@
+use aries_vcx_wallet::wallet::base_wallet::did_wallet::DidWallet;
+use chrono::{SecondsFormat, Utc};
+use did_resolver::{did_doc::schema::did_doc::DidDocument, did_parser_nom::Did};
+use did_resolver_web::creation::did_web::DidWeb;
+use public_key::Key;
+use serde_json::{json, Value};
+
+use super::{
+    hashing::hash_json,
+    parameters::{Parameters, METHOD_VERSION},
+    proof::DataIntegrityProof,
+    DidWebvhLog,
+};
+use crate::{
+    did_webvh::{DidWebvh, SCID_PLACEHOLDER},
+    error::{DidWebvhError, DidWebvhResult},
+};
+
+/// Stands in for the SCID while the DID document of the first entry is built, as the
+/// `{SCID}` placeholder is not a valid DID
+const PROVISIONAL_SCID: &str = "scid";
+
+impl DidWebvhLog {
+    /// Creates the DID log with its first entry, hosted at the location of `did_web`. The DID
+    /// document is built by `build_document` for the DID, whose SCID is computed afterwards.
+    /// `parameters` must list the update keys, including `signing_key` held by the wallet.
+    pub async fn create<W>(
+        wallet: &W,
+        signing_key: &Key,
+        did_web: DidWeb,
+        parameters: Parameters,
+        build_document: impl FnOnce(&Did) -> DidDocument,
+    ) -> DidWebvhResult<Self>
+    where
+        W: DidWallet + ?Sized + Send + Sync,
+    {
+        let provisional_did = DidWebvh::new(PROVISIONAL_SCID, did_web)?.did().to_string();
+        let placeholder_did = provisional_did.replacen(PROVISIONAL_SCID, SCID_PLACEHOLDER, 1);
+        let did_document = build_document(&Did::parse(provisional_did.clone())?);
+
+        let parameters = Parameters {
+            method: Some(METHOD_VERSION.to_string()),
+            scid: Some(SCID_PLACEHOLDER.to_string()),
+            ..parameters
+        };
+        let preliminary_entry = json!({
+            "versionId": SCID_PLACEHOLDER,
+            "versionTime": now(),
+            "parameters": parameters,
+            "state": did_document,
+        });
+        let preliminary_entry =
+            serde_json::to_string(&preliminary_entry)?.replace(&provisional_did, &placeholder_did);
+        let scid = hash_json(&serde_json::from_str(&preliminary_entry)?)?;
+        let entry: Value =
+            serde_json::from_str(&preliminary_entry.replace(SCID_PLACEHOLDER, &scid))?;
+
+        let mut log = Self::default();
+        log.append_entry(wallet, signing_key, entry, &scid, 1)
+            .await?;
+        Ok(log)
+    }
+
+    /// Appends a new version of the DID document, changing the given parameters only
+    pub async fn update<W>(
+        &mut self,
+        wallet: &W,
+        signing_key: &Key,
+        did_document: DidDocument,
+        parameters: Parameters,
+    ) -> DidWebvhResult<()>
+    where
+        W: DidWallet + ?Sized + Send + Sync,
+    {
+        let versions = self.verify()?;
+        let last = versions.last().ok_or_else(|| {
+            DidWebvhError::InvalidLog("Cannot update an empty DID log".to_string())
+        })?;
+        if last.parameters().is_deactivated() {
+            return Err(DidWebvhError::Deactivated);
+        }
+        let entry = json!({
+            "versionTime": now(),
+            "parameters": parameters,
+            "state": did_document,
+        });
+        let previous_version_id = last.version_id().to_string();
+        let version_number = last.version_number() + 1;
+        self.append_entry(
+            wallet,
+            signing_key,
+            entry,
+            &previous_version_id,
+            version_number,
+        )
+        .await
+    }
+
+    /// Deactivates the DID, removing its update keys so that no further versions can be added.
+    /// With pre-rotation active, `signing_key` must be the pre-committed next update key.
+    pub async fn deactivate<W>(&mut self, wallet: &W, signing_key: &Key) -> DidWebvhResult<()>
+    where
+        W: DidWallet + ?Sized + Send + Sync,
+    {
+        let versions = self.verify()?;
+        let last = versions.last().ok_or_else(|| {
+            DidWebvhError::InvalidLog("Cannot deactivate an empty DID log".to_string())
+        })?;
+        let parameters = if last.parameters().is_pre_rotation_active() {
+            Parameters {
+                update_keys: Some(vec![signing_key.fingerprint()]),
+                next_key_hashes: Some(vec![]),
+                deactivated: Some(true),
+                ..Default::default()
+            }
+        } else {
+            Parameters {
+                update_keys: Some(vec![]),
+                deactivated: Some(true),
+                ..Default::default()
+            }
+        };
+        let did_document = last.did_document().clone();
+        self.update(wallet, signing_key, did_document, parameters)
+            .await
+    }
+
+    /// Chains the entry to the previous version, signs it and appends it if the resulting log
+    /// verifies
+    async fn append_entry<W>(
+        &mut self,
+        wallet: &W,
+        signing_key: &Key,
+        mut entry: Value,
+        previous_version_id: &str,
+        version_number: u64,
+    ) -> DidWebvhResult<()>
+    where
+        W: DidWallet + ?Sized + Send + Sync,
+    {
+        entry["versionId"] = Value::String(previous_version_id.to_string());
+        let entry_hash = hash_json(&entry)?;
+        entry["versionId"] = Value::String(format!("{version_number}-{entry_hash}"));
+
+        let proof = DataIntegrityProof::sign(wallet, signing_key, &entry, &now()).await?;
+        entry["proof"] = json!([proof]);
+
+        let mut log = self.clone();
+        log.entries.push(entry);
+        log.verify()?;
+        *self = log;
+        Ok(())
+    }
+}
+
+fn now() -> String {
+    Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true)
+}
@@ -0,0 +1,121 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Version of the did:webvh specification the log is created with
+pub const METHOD_VERSION: &str = "did:webvh:1.0";
+
+/// DID log entry parameters. The first entry sets the initial parameters, later entries only
+/// contain the parameters they change.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Parameters {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub method: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scid: Option<String>,
+    /// Multikey encoded public keys authorized to sign the following log entries
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub update_keys: Option<Vec<String>>,
+    /// Hashes of the update keys of the next entry, making key pre-rotation active
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_key_hashes: Option<Vec<String>>,
+    /// Whether the DID may move to another domain, can only be set in the first entry
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub portable: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deactivated: Option<bool>,
+    /// Seconds the resolved DID may be cached for
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ttl: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub witness: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub watchers: Option<Vec<String>>,
+}
+
+impl Parameters {
+    /// Applies the parameters changed by a log entry to the currently active ones
+    pub fn apply(&mut self, changes: &Parameters) {
+        fn apply_field<T: Clone>(field: &mut Option<T>, change: &Option<T>) {
+            if change.is_some() {
+                field.clone_from(change);
+            }
+        }
+        apply_field(&mut self.method, &changes.method);
+        apply_field(&mut self.scid, &changes.scid);
+        apply_field(&mut self.update_keys, &changes.update_keys);
+        apply_field(&mut self.next_key_hashes, &changes.next_key_hashes);
+        apply_field(&mut self.portable, &changes.portable);
+        apply_field(&mut self.deactivated, &changes.deactivated);
+        apply_field(&mut self.ttl, &changes.ttl);
+        apply_field(&mut self.witness, &changes.witness);
+        apply_field(&mut self.watchers, &changes.watchers);
+    }
+
+    pub fn update_keys(&self) -> &[String] {
+        self.update_keys.as_deref().unwrap_or_default()
+    }
+
+    pub fn next_key_hashes(&self) -> &[String] {
+        self.next_key_hashes.as_deref().unwrap_or_default()
+    }
+
+    pub fn is_pre_rotation_active(&self) -> bool {
+        !self.next_key_hashes().is_empty()
+    }
+
+    pub fn is_portable(&self) -> bool {
+        self.portable.unwrap_or(false)
+    }
+
+    pub fn is_deactivated(&self) -> bool {
+        self.deactivated.unwrap_or(false)
+    }
+
+    /// Witnesses are configured by a non-empty witness object
+    pub(crate) fn has_witnesses(&self) -> bool {
+        match &self.witness {
+            None | Some(Value::Null) => false,
+            Some(Value::Object(witness)) => !witness.is_empty(),
+            Some(_) => true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_apply_changed_parameters() {
+        let mut parameters: Parameters = serde_json::from_value(json!({
+            "method": METHOD_VERSION,
+            "scid": "QmScid",
+            "updateKeys": ["z6MkA"],
+            "nextKeyHashes": ["QmNext"],
+        }))
+        .unwrap();
+        parameters.apply(&Parameters {
+            update_keys: Some(vec!["z6MkB".to_string()]),
+            next_key_hashes: Some(vec![]),
+            ..Default::default()
+        });
+        assert_eq!(parameters.scid.as_deref(), Some("QmScid"));
+        assert_eq!(parameters.update_keys(), ["z6MkB".to_string()]);
+        assert!(!parameters.is_pre_rotation_active());
+    }
+
+    #[test]
+    fn test_serialize_only_set_parameters() {
+        let parameters = Parameters {
+            deactivated: Some(true),
+            ..Default::default()
+        };
+        assert_eq!(
+            serde_json::to_value(parameters).unwrap(),
+            json!({"deactivated": true})
+        );
+    }
+}
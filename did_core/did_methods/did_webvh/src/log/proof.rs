@@ -0,0 +1,125 @@
+use aries_vcx_wallet::wallet::base_wallet::did_wallet::DidWallet;
+use ed25519_dalek::{Signature, VerifyingKey};
+use public_key::{Key, KeyType};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use super::hashing::sha256;
+use crate::error::{DidWebvhError, DidWebvhResult};
+
+pub const DATA_INTEGRITY_PROOF_TYPE: &str = "DataIntegrityProof";
+pub const EDDSA_JCS_2022_CRYPTOSUITE: &str = "eddsa-jcs-2022";
+pub const ASSERTION_METHOD_PURPOSE: &str = "assertionMethod";
+
+const MULTIBASE_BASE58BTC_PREFIX: char = 'z';
+
+/// Data Integrity proof of a log entry, using the `eddsa-jcs-2022` cryptosuite with the update
+/// key identified as `did:key:{key}#{key}`
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DataIntegrityProof {
+    #[serde(rename = "type")]
+    pub proof_type: String,
+    pub cryptosuite: String,
+    pub verification_method: String,
+    pub created: String,
+    pub proof_purpose: String,
+    pub proof_value: String,
+}
+
+impl DataIntegrityProof {
+    /// Signs the log entry (without proofs) with the update key held by the wallet
+    pub(crate) async fn sign<W>(
+        wallet: &W,
+        key: &Key,
+        entry: &Value,
+        created: &str,
+    ) -> DidWebvhResult<Self>
+    where
+        W: DidWallet + ?Sized + Send + Sync,
+    {
+        let update_key = key.fingerprint();
+        let proof_config = json!({
+            "type": DATA_INTEGRITY_PROOF_TYPE,
+            "cryptosuite": EDDSA_JCS_2022_CRYPTOSUITE,
+            "verificationMethod": format!("did:key:{update_key}#{update_key}"),
+            "created": created,
+            "proofPurpose": ASSERTION_METHOD_PURPOSE,
+        });
+        let signature = wallet
+            .sign(key, &signing_input(&proof_config, entry)?)
+            .await?;
+
+        let mut proof = proof_config;
+        proof["proofValue"] = Value::String(format!(
+            "{MULTIBASE_BASE58BTC_PREFIX}{}",
+            bs58::encode(signature).into_string()
+        ));
+        Ok(serde_json::from_value(proof)?)
+    }
+
+    /// Multikey encoded update key the entry is signed with
+    pub fn update_key(&self) -> DidWebvhResult<&str> {
+        self.verification_method
+            .strip_prefix("did:key:")
+            .and_then(|key_ref| key_ref.split_once('#'))
+            .filter(|(key, fragment)| key == fragment)
+            .map(|(key, _)| key)
+            .ok_or_else(|| {
+                DidWebvhError::InvalidProof(format!(
+                    "Unsupported verification method: {}",
+                    self.verification_method
+                ))
+            })
+    }
+
+    /// Verifies the signature over the log entry (without proofs)
+    pub(crate) fn verify(&self, entry: &Value) -> DidWebvhResult<()> {
+        if self.proof_type != DATA_INTEGRITY_PROOF_TYPE
+            || self.cryptosuite != EDDSA_JCS_2022_CRYPTOSUITE
+        {
+            return Err(DidWebvhError::InvalidProof(format!(
+                "Unsupported proof {} with cryptosuite {}",
+                self.proof_type, self.cryptosuite
+            )));
+        }
+        if self.proof_purpose != ASSERTION_METHOD_PURPOSE {
+            return Err(DidWebvhError::InvalidProof(format!(
+                "Unexpected proof purpose: {}",
+                self.proof_purpose
+            )));
+        }
+
+        let key = Key::from_fingerprint(self.update_key()?)?;
+        key.validate_key_type(KeyType::Ed25519)?;
+        let verifying_key = key
+            .key()
+            .try_into()
+            .ok()
+            .and_then(|bytes| VerifyingKey::from_bytes(bytes).ok())
+            .ok_or_else(|| DidWebvhError::InvalidProof("Invalid Ed25519 key".to_string()))?;
+        let signature = self
+            .proof_value
+            .strip_prefix(MULTIBASE_BASE58BTC_PREFIX)
+            .ok_or_else(|| {
+                DidWebvhError::InvalidProof("Proof value is not base58btc encoded".to_string())
+            })?;
+        let signature = Signature::from_slice(&bs58::decode(signature).into_vec()?)
+            .map_err(|err| DidWebvhError::InvalidProof(err.to_string()))?;
+
+        let mut proof_config = serde_json::to_value(self)?;
+        if let Some(proof_config) = proof_config.as_object_mut() {
+            proof_config.remove("proofValue");
+        }
+        verifying_key
+            .verify_strict(&signing_input(&proof_config, entry)?, &signature)
+            .map_err(|_| DidWebvhError::InvalidProof("Invalid signature".to_string()))
+    }
+}
+
+/// Hash of the canonical proof configuration followed by the hash of the canonical entry
+fn signing_input(proof_config: &Value, entry: &Value) -> DidWebvhResult<Vec<u8>> {
+    let mut input = sha256(&serde_jcs::to_vec(proof_config)?);
+    input.extend(sha256(&serde_jcs::to_vec(entry)?));
+    Ok(input)
+}
@@ -0,0 +1,12 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Selects the version of the DID document to resolve, the latest version if none is set
+#[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DidWebvhResolutionOptions {
+    pub version_id: Option<String>,
+    /// Resolves the version valid at the given time
+    pub version_time: Option<DateTime<Utc>>,
+    pub version_number: Option<u64>,
+}
@@ -0,0 +1,152 @@
+use async_trait::async_trait;
+use did_resolver::{
+    did_parser_nom::Did,
+    error::GenericError,
+    shared_types::did_document_metadata::DidDocumentMetadata,
+    traits::resolvable::{
+        resolution_metadata::DidResolutionMetadata, resolution_output::DidResolutionOutput,
+        DidResolvable,
+    },
+};
+use http_body_util::{combinators::BoxBody, BodyExt as _};
+use hyper::{body::Bytes, http::uri::Scheme, Uri};
+use hyper_tls::HttpsConnector;
+use hyper_util::{
+    client::legacy::{
+        connect::{Connect, HttpConnector},
+        Client,
+    },
+    rt::TokioExecutor,
+};
+
+use super::options::DidWebvhResolutionOptions;
+use crate::{
+    did_webvh::DidWebvh,
+    error::{DidWebvhError, DidWebvhResult},
+    log::{DidWebvhLog, DidWebvhVersion},
+};
+
+/// Resolves did:webvh by fetching and verifying its DID log, see
+/// https://identity.foundation/didwebvh/#read-resolve
+pub struct DidWebvhResolver<C>
+where
+    C: Connect + Send + Sync + Clone + 'static,
+{
+    client: Client<C, BoxBody<Bytes, GenericError>>,
+    scheme: Scheme,
+}
+
+impl DidWebvhResolver<HttpConnector> {
+    pub fn http() -> DidWebvhResolver<HttpConnector> {
+        DidWebvhResolver {
+            client: Client::builder(TokioExecutor::new())
+                .build::<_, BoxBody<Bytes, GenericError>>(HttpConnector::new()),
+            scheme: Scheme::HTTP,
+        }
+    }
+}
+
+impl DidWebvhResolver<HttpsConnector<HttpConnector>> {
+    pub fn https() -> DidWebvhResolver<HttpsConnector<HttpConnector>> {
+        DidWebvhResolver {
+            client: Client::builder(TokioExecutor::new())
+                .build::<_, BoxBody<Bytes, GenericError>>(HttpsConnector::new()),
+            scheme: Scheme::HTTPS,
+        }
+    }
+}
+
+impl<C> DidWebvhResolver<C>
+where
+    C: Connect + Send + Sync + Clone + 'static,
+{
+    async fn fetch_did_log(&self, url: Uri) -> DidWebvhResult<DidWebvhLog> {
+        let res = self.client.get(url).await?;
+
+        if !res.status().is_success() {
+            return Err(DidWebvhError::NonSuccessResponse(res.status()));
+        }
+
+        let body = res.into_body().collect().await?.to_bytes();
+        DidWebvhLog::parse(&String::from_utf8(body.to_vec())?)
+    }
+}
+
+#[async_trait]
+impl<C> DidResolvable for DidWebvhResolver<C>
+where
+    C: Connect + Send + Sync + Clone + 'static,
+{
+    type DidResolutionOptions = DidWebvhResolutionOptions;
+
+    async fn resolve(
+        &self,
+        did: &Did,
+        options: &Self::DidResolutionOptions,
+    ) -> Result<DidResolutionOutput, GenericError> {
+        let did_webvh = DidWebvh::parse(did)?;
+        let log = self
+            .fetch_did_log(did_webvh.log_url(self.scheme.clone())?)
+            .await?;
+        let versions = log.verify()?;
+        Ok(resolution_output(did, &versions, options)?)
+    }
+}
+
+fn resolution_output(
+    did: &Did,
+    versions: &[DidWebvhVersion],
+    options: &DidWebvhResolutionOptions,
+) -> DidWebvhResult<DidResolutionOutput> {
+    let index = select_version(versions, options)?;
+    let version = &versions[index];
+    let (first, last) = match (versions.first(), versions.last()) {
+        (Some(first), Some(last)) => (first, last),
+        _ => return Err(DidWebvhError::InvalidLog("Empty DID log".to_string())),
+    };
+    if version.did_document().id() != did && !version.parameters().is_portable() {
+        return Err(DidWebvhError::InvalidDid(format!(
+            "Log of {did} resolves to {}",
+            version.did_document().id()
+        )));
+    }
+
+    let mut metadata = DidDocumentMetadata::builder()
+        .created(first.version_time())
+        .updated(version.version_time())
+        .version_id(version.version_id().to_string())
+        .deactivated(last.parameters().is_deactivated());
+    if let Some(next) = versions.get(index + 1) {
+        metadata = metadata.next_version_id(next.version_id().to_string());
+    }
+    Ok(DidResolutionOutput::builder(version.did_document().clone())
+        .did_resolution_metadata(
+            DidResolutionMetadata::builder()
+                .content_type("application/did+json".to_string())
+                .build(),
+        )
+        .did_document_metadata(metadata.build())
+        .build())
+}
+
+fn select_version(
+    versions: &[DidWebvhVersion],
+    options: &DidWebvhResolutionOptions,
+) -> DidWebvhResult<usize> {
+    let index = if let Some(version_id) = &options.version_id {
+        versions
+            .iter()
+            .position(|version| version.version_id() == version_id)
+    } else if let Some(version_number) = options.version_number {
+        versions
+            .iter()
+            .position(|version| version.version_number() == version_number)
+    } else if let Some(version_time) = options.version_time {
+        versions
+            .iter()
+            .rposition(|version| version.version_time() <= version_time)
+    } else {
+        versions.len().checked_sub(1)
+    };
+    index.ok_or_else(|| DidWebvhError::VersionNotFound(format!("{options:?}")))
+}
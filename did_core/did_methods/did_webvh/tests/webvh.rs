@@ -0,0 +1,296 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, RwLock},
+};
+
+use aries_vcx_wallet::wallet::{
+    askar::{askar_wallet_config::AskarWalletConfig, key_method::KeyMethod, AskarWallet},
+    base_wallet::{did_wallet::DidWallet, ManageWallet},
+};
+use axum::{extract::State, routing::get, Router};
+use did_resolver::{
+    did_doc::schema::{
+        did_doc::DidDocument,
+        service::{typed::ServiceType, Service},
+        types::uri::Uri,
+        utils::OneOrList,
+        verification_method::{PublicKeyField, VerificationMethod, VerificationMethodType},
+    },
+    did_parser_nom::{Did, DidUrl},
+    traits::resolvable::DidResolvable,
+};
+use did_resolver_registry::ResolverRegistry;
+use did_resolver_web::creation::did_web::DidWeb;
+use did_webvh::{
+    error::DidWebvhError,
+    log::{next_key_hash, parameters::Parameters, DidWebvhLog},
+    resolution::resolver::DidWebvhResolver,
+};
+use public_key::Key;
+use serde_json::json;
+use tokio::net::TcpListener;
+use uuid::Uuid;
+
+async fn build_wallet() -> AskarWallet {
+    AskarWalletConfig::new(
+        "sqlite://:memory:",
+        KeyMethod::Unprotected,
+        "",
+        &Uuid::new_v4().to_string(),
+    )
+    .create_wallet()
+    .await
+    .unwrap()
+}
+
+/// Creates an Ed25519 update key held by the wallet
+async fn add_key(wallet: &AskarWallet) -> Key {
+    wallet
+        .create_and_store_my_did(None, None)
+        .await
+        .unwrap()
+        .verkey()
+        .to_owned()
+}
+
+type HostedLog = Arc<RwLock<String>>;
+
+/// Serves the DID log at `/{path}/did.jsonl`, returns the did:web location of the log
+async fn serve_log(path: &[&str]) -> (DidWeb, HostedLog) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let hosted_log = HostedLog::default();
+    let router = Router::new()
+        .route(
+            &format!("/{}/did.jsonl", path.join("/")),
+            get(|State(log): State<HostedLog>| async move { log.read().unwrap().clone() }),
+        )
+        .with_state(hosted_log.clone());
+    tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+    (
+        DidWeb::new("localhost", Some(port), path).unwrap(),
+        hosted_log,
+    )
+}
+
+fn publish(hosted_log: &HostedLog, log: &DidWebvhLog) {
+    *hosted_log.write().unwrap() = log.to_jsonl().unwrap();
+}
+
+fn build_document(did: &Did, signing_key: &Key) -> DidDocument {
+    let mut did_document = DidDocument::new(did.to_owned());
+    did_document.set_extra_field(
+        "@context".to_string(),
+        json!([
+            "https://www.w3.org/ns/did/v1",
+            "https://w3id.org/security/multikey/v1"
+        ]),
+    );
+    let vm_id = DidUrl::parse(format!("{did}#key-0")).unwrap();
+    did_document.add_verification_method(
+        VerificationMethod::builder()
+            .id(vm_id.clone())
+            .controller(did.to_owned())
+            .verification_method_type(VerificationMethodType::Multikey)
+            .public_key(PublicKeyField::Multibase {
+                public_key_multibase: signing_key.fingerprint(),
+            })
+            .build(),
+    );
+    did_document.add_authentication_ref(vm_id);
+    did_document
+}
+
+fn with_service(did_document: &DidDocument) -> DidDocument {
+    let mut did_document = did_document.clone();
+    did_document.add_service(Service::new(
+        Uri::new(&format!("{}#agent", did_document.id())).unwrap(),
+        "https://example.com/agent".parse().unwrap(),
+        OneOrList::One(ServiceType::DIDCommV1),
+        HashMap::new(),
+    ));
+    did_document
+}
+
+async fn create_log(
+    wallet: &AskarWallet,
+    did_web: DidWeb,
+    update_key: &Key,
+    parameters: Parameters,
+) -> DidWebvhLog {
+    DidWebvhLog::create(
+        wallet,
+        update_key,
+        did_web,
+        Parameters {
+            update_keys: Some(vec![update_key.fingerprint()]),
+            ..parameters
+        },
+        |did| build_document(did, update_key),
+    )
+    .await
+    .unwrap()
+}
+
+#[tokio::test]
+async fn test_create_update_and_resolve_through_registry() {
+    let wallet = build_wallet().await;
+    let update_key = add_key(&wallet).await;
+    let (did_web, hosted_log) = serve_log(&["issuer"]).await;
+
+    let mut log = create_log(&wallet, did_web, &update_key, Parameters::default()).await;
+    let versions = log.verify().unwrap();
+    let did = versions[0].did_document().id().to_owned();
+    assert!(did.to_string().starts_with("did:webvh:Qm"));
+    assert!(did.to_string().ends_with(":issuer"));
+
+    let updated = with_service(versions[0].did_document());
+    log.update(&wallet, &update_key, updated, Parameters::default())
+        .await
+        .unwrap();
+    publish(&hosted_log, &log);
+
+    let registry =
+        ResolverRegistry::new().register_resolver("webvh".into(), DidWebvhResolver::http());
+    let output = registry.resolve(&did, &HashMap::new()).await.unwrap();
+    assert_eq!(output.did_document.service().len(), 1);
+    let metadata = output.did_document_metadata;
+    assert!(metadata.version_id().unwrap().starts_with("2-"));
+    assert_eq!(metadata.deactivated(), Some(false));
+    assert!(metadata.next_version_id().is_none());
+
+    let options = HashMap::from([("versionNumber".to_string(), json!(1))]);
+    let output = registry.resolve(&did, &options).await.unwrap();
+    assert!(output.did_document.service().is_empty());
+    assert!(output
+        .did_document_metadata
+        .next_version_id()
+        .unwrap()
+        .starts_with("2-"));
+}
+
+#[tokio::test]
+async fn test_pre_rotation() {
+    let wallet = build_wallet().await;
+    let update_key = add_key(&wallet).await;
+    let next_key = add_key(&wallet).await;
+    let did_web = DidWeb::new("example.com", None, &[]).unwrap();
+
+    let mut log = create_log(
+        &wallet,
+        did_web,
+        &update_key,
+        Parameters {
+            next_key_hashes: Some(vec![next_key_hash(&next_key.fingerprint())]),
+            ..Default::default()
+        },
+    )
+    .await;
+    let did_document = log.verify().unwrap()[0].did_document().clone();
+
+    // the current key was not committed to as the next key
+    let err = log
+        .update(
+            &wallet,
+            &update_key,
+            did_document.clone(),
+            Parameters {
+                update_keys: Some(vec![update_key.fingerprint()]),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap_err();
+    assert!(matches!(err, DidWebvhError::UnauthorizedKey(_)));
+    assert_eq!(log.len(), 1);
+
+    log.update(
+        &wallet,
+        &next_key,
+        did_document,
+        Parameters {
+            update_keys: Some(vec![next_key.fingerprint()]),
+            next_key_hashes: Some(vec![]),
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+    let versions = log.verify().unwrap();
+    assert_eq!(versions.len(), 2);
+    assert!(!versions[1].parameters().is_pre_rotation_active());
+}
+
+#[tokio::test]
+async fn test_update_with_unauthorized_key_fails() {
+    let wallet = build_wallet().await;
+    let update_key = add_key(&wallet).await;
+    let other_key = add_key(&wallet).await;
+    let did_web = DidWeb::new("example.com", None, &[]).unwrap();
+
+    let mut log = create_log(&wallet, did_web, &update_key, Parameters::default()).await;
+    let did_document = log.verify().unwrap()[0].did_document().clone();
+    let err = log
+        .update(&wallet, &other_key, did_document, Parameters::default())
+        .await
+        .unwrap_err();
+    assert!(matches!(err, DidWebvhError::UnauthorizedKey(_)));
+}
+
+#[tokio::test]
+async fn test_deactivate() {
+    let wallet = build_wallet().await;
+    let update_key = add_key(&wallet).await;
+    let (did_web, hosted_log) = serve_log(&["user", "alice"]).await;
+
+    let mut log = create_log(&wallet, did_web, &update_key, Parameters::default()).await;
+    let did_document = log.verify().unwrap()[0].did_document().clone();
+    log.deactivate(&wallet, &update_key).await.unwrap();
+    publish(&hosted_log, &log);
+
+    let output = DidWebvhResolver::http()
+        .resolve(did_document.id(), &Default::default())
+        .await
+        .unwrap();
+    assert_eq!(output.did_document_metadata.deactivated(), Some(true));
+
+    let err = log
+        .update(&wallet, &update_key, did_document, Parameters::default())
+        .await
+        .unwrap_err();
+    assert!(matches!(err, DidWebvhError::Deactivated));
+}
+
+#[tokio::test]
+async fn test_tampered_log_fails_verification() {
+    let wallet = build_wallet().await;
+    let update_key = add_key(&wallet).await;
+    let did_web = DidWeb::new("example.com", None, &[]).unwrap();
+
+    let mut log = create_log(&wallet, did_web, &update_key, Parameters::default()).await;
+    let did_document = log.verify().unwrap()[0].did_document().clone();
+    log.update(
+        &wallet,
+        &update_key,
+        with_service(&did_document),
+        Parameters::default(),
+    )
+    .await
+    .unwrap();
+
+    let jsonl = log.to_jsonl().unwrap();
+    assert_eq!(DidWebvhLog::parse(&jsonl).unwrap(), log);
+
+    let tampered = jsonl.replace("https://example.com/agent", "https://attacker.com/agent");
+    assert!(matches!(
+        DidWebvhLog::parse(&tampered).unwrap().verify(),
+        Err(DidWebvhError::InvalidLog(_))
+    ));
+
+    let mut lines: Vec<&str> = jsonl.lines().collect();
+    lines.swap(0, 1);
+    assert!(DidWebvhLog::parse(&lines.join("\n"))
+        .unwrap()
+        .verify()
+        .is_err());
+}
@@ -0,0 +1,43 @@
+//! https://github.com/decentralized-identity/ethr-did-resolver/blob/master/doc/did-method-spec.md#method-specific-identifier
+
+use nom::{
+    branch::alt,
+    bytes::complete::{tag, take_while1},
+    character::complete::{char, one_of},
+    combinator::{cut, opt, recognize},
+    multi::count,
+    sequence::{delimited, pair, terminated, tuple},
+    IResult,
+};
+
+use super::{DidPart, HEX_DIGIT_CHARS};
+
+// network = chain name / hex chain id
+fn did_ethr_network(input: &str) -> IResult<&str, &str> {
+    take_while1(|c: char| c.is_ascii_alphanumeric() || c == '-')(input)
+}
+
+fn hex_encoded(len: usize) -> impl FnMut(&str) -> IResult<&str, &str> {
+    move |input| recognize(pair(tag("0x"), count(one_of(HEX_DIGIT_CHARS), len)))(input)
+}
+
+// ethereum-address = "0x" 40*HEXDIG
+// public-key-hex = "0x" 66*HEXDIG
+fn did_ethr_identifier(input: &str) -> IResult<&str, &str> {
+    alt((hex_encoded(66), hex_encoded(40)))(input)
+}
+
+// ethr-did = "did:ethr:" [network ":"] (public-key-hex / ethereum-address)
+pub(super) fn parse_did_ethr(input: &str) -> IResult<&str, DidPart> {
+    fn did_ethr_method(input: &str) -> IResult<&str, &str> {
+        delimited(char(':'), tag("ethr"), char(':'))(input)
+    }
+    let (input_left, (prefix, method, namespace, id)) = tuple((
+        tag("did"),
+        did_ethr_method,
+        opt(terminated(did_ethr_network, char(':'))),
+        cut(did_ethr_identifier),
+    ))(input)?;
+
+    Ok((input_left, (prefix, method, namespace, id)))
+}
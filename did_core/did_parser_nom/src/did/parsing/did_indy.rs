@@ -0,0 +1,43 @@
+//! https://hyperledger.github.io/indy-did-method/#indy-did-method-identifiers
+
+use nom::{
+    bytes::complete::{tag, take_while1},
+    character::complete::char,
+    combinator::{cut, recognize},
+    multi::many_m_n,
+    sequence::{delimited, terminated, tuple},
+    IResult,
+};
+
+use super::{did_sov::parse_unqualified_sovrin_did, DidPart};
+
+// namespace-identifier = 1*(%x61-7A / DIGIT / "_" / "-")
+fn did_indy_namespace_identifier(input: &str) -> IResult<&str, &str> {
+    take_while1(|c: char| c.is_ascii_lowercase() || c.is_ascii_digit() || "_-".contains(c))(input)
+}
+
+// namespace = namespace-identifier [":" namespace-identifier] ":"
+fn did_indy_namespace(input: &str) -> IResult<&str, &str> {
+    let (input_left, namespace) = recognize(many_m_n(
+        1,
+        2,
+        terminated(did_indy_namespace_identifier, char(':')),
+    ))(input)?;
+    Ok((input_left, &namespace[..namespace.len() - 1]))
+}
+
+// indy-did = "did:indy:" namespace nsid
+// nsid = 21*22(base58char)
+pub(super) fn parse_did_indy(input: &str) -> IResult<&str, DidPart> {
+    fn did_indy_method(input: &str) -> IResult<&str, &str> {
+        delimited(char(':'), tag("indy"), char(':'))(input)
+    }
+    let (input_left, (prefix, method, namespace, id)) = tuple((
+        tag("did"),
+        did_indy_method,
+        cut(did_indy_namespace),
+        cut(parse_unqualified_sovrin_did),
+    ))(input)?;
+
+    Ok((input_left, (prefix, method, Some(namespace), id)))
+}
@@ -0,0 +1,42 @@
+//! https://identity.foundation/sidetree/spec/#did-uri-composition
+
+use nom::{
+    bytes::complete::{tag, take_while1, take_while_m_n},
+    character::complete::char,
+    combinator::{cut, opt, recognize},
+    sequence::{delimited, pair, preceded, terminated, tuple},
+    IResult,
+};
+
+use super::DidPart;
+
+// Length of a base64url encoded sha2-256 multihash
+const DID_SUFFIX_LEN: usize = 46;
+
+fn is_base64url_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '-' || c == '_'
+}
+
+// did-suffix = 46(base64url-char)
+// long-form-suffix = did-suffix [":" 1*base64url-char]
+fn did_ion_suffix(input: &str) -> IResult<&str, &str> {
+    recognize(pair(
+        take_while_m_n(DID_SUFFIX_LEN, DID_SUFFIX_LEN, is_base64url_char),
+        opt(preceded(char(':'), take_while1(is_base64url_char))),
+    ))(input)
+}
+
+// ion-did = "did:ion:" ["test:"] long-form-suffix
+pub(super) fn parse_did_ion(input: &str) -> IResult<&str, DidPart> {
+    fn did_ion_method(input: &str) -> IResult<&str, &str> {
+        delimited(char(':'), tag("ion"), char(':'))(input)
+    }
+    let (input_left, (prefix, method, namespace, id)) = tuple((
+        tag("did"),
+        did_ion_method,
+        opt(terminated(tag("test"), char(':'))),
+        cut(did_ion_suffix),
+    ))(input)?;
+
+    Ok((input_left, (prefix, method, namespace, id)))
+}
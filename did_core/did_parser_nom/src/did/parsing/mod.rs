@@ -1,5 +1,8 @@
 mod did_cheqd;
 mod did_core;
+mod did_ethr;
+mod did_indy;
+mod did_ion;
 mod did_key;
 mod did_peer_4;
 mod did_sov;
@@ -14,6 +17,9 @@ use nom::{
 
 use self::{
     did_core::parse_qualified_did,
+    did_ethr::parse_did_ethr,
+    did_indy::parse_did_indy,
+    did_ion::parse_did_ion,
     did_key::parse_did_key,
     did_sov::{parse_qualified_sovrin_did, parse_unqualified_sovrin_did},
     did_web::parse_did_web,
@@ -70,6 +76,9 @@ pub fn parse_did_ranges(input: &str) -> IResult<&str, DidRanges> {
         map(parse_did_web, to_did_ranges),
         map(parse_did_key, to_did_ranges),
         map(parse_did_cheqd, to_did_ranges),
+        map(parse_did_indy, to_did_ranges),
+        map(parse_did_ethr, to_did_ranges),
+        map(parse_did_ion, to_did_ranges),
         map(parse_qualified_sovrin_did, to_did_ranges),
         map(parse_qualified_did, to_did_ranges),
         map(parse_unqualified_sovrin_did, to_id_range),
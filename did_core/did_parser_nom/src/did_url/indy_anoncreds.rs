@@ -0,0 +1,177 @@
+//! https://hyperledger.github.io/indy-did-method/#anoncreds-objects
+
+use std::fmt::Display;
+
+use nom::{
+    branch::alt,
+    bytes::complete::{tag, take_while1},
+    character::complete::{char, u32 as seq_no},
+    combinator::{all_consuming, map},
+    sequence::{preceded, tuple},
+    IResult,
+};
+use percent_encoding::{percent_decode_str, utf8_percent_encode, AsciiSet, CONTROLS};
+
+use crate::{DidUrl, ParseError};
+
+const ANONCREDS_PATH_PREFIX: &str = "/anoncreds/v0/";
+
+/// Characters escaped in path segments, leaving the unreserved and sub-delims characters of
+/// https://www.rfc-editor.org/rfc/rfc3986#section-3.3 as they are
+const PATH_SEGMENT: &AsciiSet = &CONTROLS
+    .add(b' ')
+    .add(b'"')
+    .add(b'#')
+    .add(b'%')
+    .add(b'/')
+    .add(b'<')
+    .add(b'>')
+    .add(b'?')
+    .add(b'[')
+    .add(b'\\')
+    .add(b']')
+    .add(b'^')
+    .add(b'`')
+    .add(b'{')
+    .add(b'|')
+    .add(b'}');
+
+/// AnonCreds object published by a did:indy DID, as referenced by the path of a DID URL such as
+/// `did:indy:sovrin:F72i3Y3Q4i466efjYJYCHM/anoncreds/v0/SCHEMA/npdb/4.3.4`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IndyAnoncredsObject {
+    Schema {
+        name: String,
+        version: String,
+    },
+    CredentialDefinition {
+        schema_seq_no: u32,
+        tag: String,
+    },
+    RevocationRegistryDefinition {
+        schema_seq_no: u32,
+        cred_def_tag: String,
+        tag: String,
+    },
+    RevocationRegistryEntry {
+        schema_seq_no: u32,
+        cred_def_tag: String,
+        tag: String,
+    },
+}
+
+impl IndyAnoncredsObject {
+    pub fn parse_path(path: &str) -> Result<Self, ParseError> {
+        let (_, object) =
+            all_consuming(preceded(tag(ANONCREDS_PATH_PREFIX), anoncreds_object))(path)?;
+        Ok(object)
+    }
+
+    /// Path of the object relative to the DID of its issuer
+    pub fn path(&self) -> String {
+        let segments = match self {
+            Self::Schema { name, version } => {
+                vec!["SCHEMA".to_string(), encode(name), encode(version)]
+            }
+            Self::CredentialDefinition { schema_seq_no, tag } => vec![
+                "CLAIM_DEF".to_string(),
+                schema_seq_no.to_string(),
+                encode(tag),
+            ],
+            Self::RevocationRegistryDefinition {
+                schema_seq_no,
+                cred_def_tag,
+                tag,
+            } => vec![
+                "REV_REG_DEF".to_string(),
+                schema_seq_no.to_string(),
+                encode(cred_def_tag),
+                encode(tag),
+            ],
+            Self::RevocationRegistryEntry {
+                schema_seq_no,
+                cred_def_tag,
+                tag,
+            } => vec![
+                "REV_REG_ENTRY".to_string(),
+                schema_seq_no.to_string(),
+                encode(cred_def_tag),
+                encode(tag),
+            ],
+        };
+        format!("{ANONCREDS_PATH_PREFIX}{}", segments.join("/"))
+    }
+}
+
+impl Display for IndyAnoncredsObject {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.path())
+    }
+}
+
+impl TryFrom<&DidUrl> for IndyAnoncredsObject {
+    type Error = ParseError;
+
+    fn try_from(did_url: &DidUrl) -> Result<Self, Self::Error> {
+        if did_url.method() != Some("indy") {
+            return Err(ParseError::InvalidInput("Not a did:indy DID URL"));
+        }
+        let path = did_url
+            .path()
+            .ok_or(ParseError::InvalidInput("DID URL has no path"))?;
+        Self::parse_path(path)
+    }
+}
+
+fn encode(segment: &str) -> String {
+    utf8_percent_encode(segment, PATH_SEGMENT).to_string()
+}
+
+// segment = 1*pchar, percent-decoded
+fn segment(input: &str) -> IResult<&str, String> {
+    map(take_while1(|c| c != '/'), |segment: &str| {
+        percent_decode_str(segment).decode_utf8_lossy().into_owned()
+    })(input)
+}
+
+fn anoncreds_object(input: &str) -> IResult<&str, IndyAnoncredsObject> {
+    alt((
+        map(
+            preceded(tag("SCHEMA/"), tuple((segment, char('/'), segment))),
+            |(name, _, version)| IndyAnoncredsObject::Schema { name, version },
+        ),
+        map(
+            preceded(tag("CLAIM_DEF/"), tuple((seq_no, char('/'), segment))),
+            |(schema_seq_no, _, tag)| IndyAnoncredsObject::CredentialDefinition {
+                schema_seq_no,
+                tag,
+            },
+        ),
+        map(
+            preceded(
+                tag("REV_REG_DEF/"),
+                tuple((seq_no, char('/'), segment, char('/'), segment)),
+            ),
+            |(schema_seq_no, _, cred_def_tag, _, tag)| {
+                IndyAnoncredsObject::RevocationRegistryDefinition {
+                    schema_seq_no,
+                    cred_def_tag,
+                    tag,
+                }
+            },
+        ),
+        map(
+            preceded(
+                tag("REV_REG_ENTRY/"),
+                tuple((seq_no, char('/'), segment, char('/'), segment)),
+            ),
+            |(schema_seq_no, _, cred_def_tag, _, tag)| {
+                IndyAnoncredsObject::RevocationRegistryEntry {
+                    schema_seq_no,
+                    cred_def_tag,
+                    tag,
+                }
+            },
+        ),
+    ))(input)
+}
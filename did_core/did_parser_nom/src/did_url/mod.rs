@@ -1,3 +1,4 @@
+pub(crate) mod indy_anoncreds;
 mod parsing;
 
 use std::{collections::HashMap, fmt::Display, str::FromStr};
@@ -7,5 +7,5 @@ use std::ops::Range;
 type DidRange = Range<usize>;
 
 pub use did::Did;
-pub use did_url::DidUrl;
+pub use did_url::{indy_anoncreds::IndyAnoncredsObject, DidUrl};
 pub use error::ParseError;
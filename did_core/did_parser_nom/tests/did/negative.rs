@@ -24,6 +24,14 @@ test_cases_negative! {
         "did:indy:s@vrin:7Tqg6BwSSWapxgUDm9KKgg"
     indy_multiple_namespaces_invalid_char_in_method_specific_id:
         "did:indy:sovrin:alpha:%0zqg6BwS.Wapxg-Dm9K_gg"
+    indy_no_namespace:
+        "did:indy:7Tqg6BwSSWapxgUDm9KKgg"
+    indy_uppercase_namespace:
+        "did:indy:Sovrin:7Tqg6BwSSWapxgUDm9KKgg"
+    indy_too_many_namespaces:
+        "did:indy:sovrin:staging:alpha:7Tqg6BwSSWapxgUDm9KKgg"
+    indy_invalid_len:
+        "did:indy:sovrin:7Tqg6BwSSWapxgUDm9K"
     sov_invalid_len:
         "did:sov:2wJPyULfLLnYTEFYzByf"
     sov_invalid_char:
@@ -58,4 +66,18 @@ test_cases_negative! {
         "did:cheqd:mainnet:de9786cd-ec53-458c-857c9342cf2-64f80"
     cheqd_non_alpha_uuid_style_char:
         "did:cheqd:mainnet:qe9786cd-ec53-458c-857c-9342cf264f80"
+    ethr_short_address:
+        "did:ethr:0xb9c5714089478a327f09197987f16f9e5d936e"
+    ethr_no_hex_prefix:
+        "did:ethr:b9c5714089478a327f09197987f16f9e5d936e8a"
+    ethr_non_hex_char:
+        "did:ethr:0xg9c5714089478a327f09197987f16f9e5d936e8a"
+    ethr_empty_network:
+        "did:ethr::0xb9c5714089478a327f09197987f16f9e5d936e8a"
+    ion_short_suffix:
+        "did:ion:EiDyOQbbZAa3aiRzeCkV7LOx3SERjjH93EXoIM3UoN4o"
+    ion_invalid_suffix_char:
+        "did:ion:EiDyOQbbZAa3aiRzeCkV7LOx3SERjjH93EXoIM3UoN4o.g"
+    ion_empty_long_form:
+        "did:ion:EiDyOQbbZAa3aiRzeCkV7LOx3SERjjH93EXoIM3UoN4oWg:"
 }
@@ -59,8 +59,13 @@ test_cases_positive! {
     test_did_indy:
         "did:indy:sovrin:7Tqg6BwSSWapxgUDm9KKgg",
         Some("indy"),
-        None,
-        "sovrin:7Tqg6BwSSWapxgUDm9KKgg"
+        Some("sovrin"),
+        "7Tqg6BwSSWapxgUDm9KKgg"
+    test_did_indy_sub_namespace:
+        "did:indy:sovrin:staging:6cgbu8ZPoWTnR5Rv5JcSMB",
+        Some("indy"),
+        Some("sovrin:staging"),
+        "6cgbu8ZPoWTnR5Rv5JcSMB"
     test_did_sov_namespaced:
         "did:sov:builder:VbPQNHsvoLZdaNU7fTBeFx",
         Some("sov"),
@@ -91,4 +96,39 @@ test_cases_positive! {
         Some("cheqd"),
         Some("testnet"),
         "TAwT8WVt3dz2DBAifwuSkn"
+    test_did_ethr_address:
+        "did:ethr:0xb9c5714089478a327f09197987f16f9e5d936e8a",
+        Some("ethr"),
+        None,
+        "0xb9c5714089478a327f09197987f16f9e5d936e8a"
+    test_did_ethr_public_key:
+        "did:ethr:0x03fdd57adec3d438ea237fe46b33ee1e016eda6b585c3e27ea66686c2ea5358479",
+        Some("ethr"),
+        None,
+        "0x03fdd57adec3d438ea237fe46b33ee1e016eda6b585c3e27ea66686c2ea5358479"
+    test_did_ethr_network:
+        "did:ethr:sepolia:0xb9c5714089478a327f09197987f16f9e5d936e8a",
+        Some("ethr"),
+        Some("sepolia"),
+        "0xb9c5714089478a327f09197987f16f9e5d936e8a"
+    test_did_ethr_chain_id:
+        "did:ethr:0x5:0xb9c5714089478a327f09197987f16f9e5d936e8a",
+        Some("ethr"),
+        Some("0x5"),
+        "0xb9c5714089478a327f09197987f16f9e5d936e8a"
+    test_did_ion_short_form:
+        "did:ion:EiDyOQbbZAa3aiRzeCkV7LOx3SERjjH93EXoIM3UoN4oWg",
+        Some("ion"),
+        None,
+        "EiDyOQbbZAa3aiRzeCkV7LOx3SERjjH93EXoIM3UoN4oWg"
+    test_did_ion_long_form:
+        "did:ion:EiDyOQbbZAa3aiRzeCkV7LOx3SERjjH93EXoIM3UoN4oWg:eyJkZWx0YSI6e319",
+        Some("ion"),
+        None,
+        "EiDyOQbbZAa3aiRzeCkV7LOx3SERjjH93EXoIM3UoN4oWg:eyJkZWx0YSI6e319"
+    test_did_ion_test_network:
+        "did:ion:test:EiDyOQbbZAa3aiRzeCkV7LOx3SERjjH93EXoIM3UoN4oWg",
+        Some("ion"),
+        Some("test"),
+        "EiDyOQbbZAa3aiRzeCkV7LOx3SERjjH93EXoIM3UoN4oWg"
 }
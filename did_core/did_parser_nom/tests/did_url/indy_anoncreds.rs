@@ -0,0 +1,74 @@
+use did_parser_nom::{DidUrl, IndyAnoncredsObject};
+
+fn parse_object(did_url: &str) -> IndyAnoncredsObject {
+    IndyAnoncredsObject::try_from(&DidUrl::parse(did_url.to_string()).unwrap()).unwrap()
+}
+
+#[test]
+fn test_indy_anoncreds_schema() {
+    let did_url =
+        "did:indy:sovrin:F72i3Y3Q4i466efjYJYCHM/anoncreds/v0/SCHEMA/degree%20schema/4.3.4";
+    let object = parse_object(did_url);
+    assert_eq!(
+        object,
+        IndyAnoncredsObject::Schema {
+            name: "degree schema".to_string(),
+            version: "4.3.4".to_string()
+        }
+    );
+    assert_eq!(object.path(), "/anoncreds/v0/SCHEMA/degree%20schema/4.3.4");
+}
+
+#[test]
+fn test_indy_anoncreds_credential_definition() {
+    let object = parse_object(
+        "did:indy:sovrin:staging:5nDyJVP1NrcPAttP3xwMB9/anoncreds/v0/CLAIM_DEF/56495/npdb",
+    );
+    assert_eq!(
+        object,
+        IndyAnoncredsObject::CredentialDefinition {
+            schema_seq_no: 56495,
+            tag: "npdb".to_string()
+        }
+    );
+}
+
+#[test]
+fn test_indy_anoncreds_revocation_registry() {
+    let path = "/anoncreds/v0/REV_REG_DEF/56495/npdb/TAG1";
+    let object = parse_object(&format!("did:indy:sovrin:5nDyJVP1NrcPAttP3xwMB9{path}"));
+    assert_eq!(
+        object,
+        IndyAnoncredsObject::RevocationRegistryDefinition {
+            schema_seq_no: 56495,
+            cred_def_tag: "npdb".to_string(),
+            tag: "TAG1".to_string()
+        }
+    );
+    assert_eq!(object.path(), path);
+
+    let object = IndyAnoncredsObject::parse_path("/anoncreds/v0/REV_REG_ENTRY/56495/npdb/TAG1");
+    assert!(matches!(
+        object,
+        Ok(IndyAnoncredsObject::RevocationRegistryEntry { .. })
+    ));
+}
+
+#[test]
+fn test_indy_anoncreds_invalid() {
+    for path in [
+        "/anoncreds/v0/SCHEMA/npdb",
+        "/anoncreds/v0/CLAIM_DEF/abc/npdb",
+        "/anoncreds/v0/REV_REG_DEF/56495/npdb",
+        "/anoncreds/v0/SCHEMA/npdb/4.3.4/extra",
+        "/anoncreds/v1/SCHEMA/npdb/4.3.4",
+        "/resources/40829caf-b415-4b1d-91a3-b56dfb6374f4",
+    ] {
+        assert!(IndyAnoncredsObject::parse_path(path).is_err(), "{path}");
+    }
+
+    let did_url =
+        DidUrl::parse("did:sov:5nDyJVP1NrcPAttP3xwMB9/anoncreds/v0/CLAIM_DEF/56495/npdb".into())
+            .unwrap();
+    assert!(IndyAnoncredsObject::try_from(&did_url).is_err());
+}
@@ -1,3 +1,4 @@
+mod indy_anoncreds;
 mod negative;
 mod positive;
 mod serde;
@@ -361,6 +361,33 @@ test_cases_positive! {
         Some("/anoncreds/v0/REV_REG_DEF/56495/npdb/TAG1"),
         None,
         HashMap::new()
+    test_case27_indy:
+        "did:indy:sovrin:5nDyJVP1NrcPAttP3xwMB9/anoncreds/v0/SCHEMA/degree%20schema/1.0",
+        Some("did:indy:sovrin:5nDyJVP1NrcPAttP3xwMB9"),
+        Some("indy"),
+        Some("sovrin"),
+        Some("5nDyJVP1NrcPAttP3xwMB9"),
+        Some("/anoncreds/v0/SCHEMA/degree%20schema/1.0"),
+        None,
+        HashMap::new()
+    test_case27_ethr:
+        "did:ethr:sepolia:0xb9c5714089478a327f09197987f16f9e5d936e8a#controller",
+        Some("did:ethr:sepolia:0xb9c5714089478a327f09197987f16f9e5d936e8a"),
+        Some("ethr"),
+        Some("sepolia"),
+        Some("0xb9c5714089478a327f09197987f16f9e5d936e8a"),
+        None,
+        Some("controller"),
+        HashMap::new()
+    test_case27_ion:
+        "did:ion:EiDyOQbbZAa3aiRzeCkV7LOx3SERjjH93EXoIM3UoN4oWg:eyJkZWx0YSI6e319#key-1",
+        Some("did:ion:EiDyOQbbZAa3aiRzeCkV7LOx3SERjjH93EXoIM3UoN4oWg:eyJkZWx0YSI6e319"),
+        Some("ion"),
+        None,
+        Some("EiDyOQbbZAa3aiRzeCkV7LOx3SERjjH93EXoIM3UoN4oWg:eyJkZWx0YSI6e319"),
+        None,
+        Some("key-1"),
+        HashMap::new()
     test_case28:
         "did:cheqd:testnet:d8ac0372-0d4b-413e-8ef5-8e8f07822b2c/resources/40829caf-b415-4b1d-91a3-b56dfb6374f4",
         Some("did:cheqd:testnet:d8ac0372-0d4b-413e-8ef5-8e8f07822b2c"),
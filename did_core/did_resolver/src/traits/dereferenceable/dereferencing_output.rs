@@ -32,6 +32,10 @@ where
     pub fn content_metadata(&self) -> &DidDocumentMetadata {
         &self.content_metadata
     }
+
+    pub fn into_content_stream(self) -> R {
+        self.content_stream
+    }
 }
 
 pub struct DidDDereferencingOutputBuilder<R: Read + Send + Sync> {
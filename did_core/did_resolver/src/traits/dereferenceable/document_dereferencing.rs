@@ -0,0 +1,210 @@
+use std::io::Cursor;
+
+use did_doc::schema::{did_doc::DidDocument, service::Service};
+use did_parser_nom::DidUrl;
+
+use super::{
+    dereferencing_error::DidDereferencingError, dereferencing_metadata::DidDereferencingMetadata,
+    dereferencing_options::DidDereferencingOptions, dereferencing_output::DidDereferencingOutput,
+};
+use crate::{
+    shared_types::media_type::MediaType, traits::resolvable::resolution_output::DidResolutionOutput,
+};
+
+pub const SERVICE_QUERY: &str = "service";
+pub const RELATIVE_REF_QUERY: &str = "relativeRef";
+pub const VERSION_ID_QUERY: &str = "versionId";
+pub const VERSION_TIME_QUERY: &str = "versionTime";
+
+const URI_LIST_CONTENT_TYPE: &str = "text/uri-list";
+
+/// Dereferences a DID URL against the already resolved DID document, following
+/// https://www.w3.org/TR/did-core/#did-url-dereferencing:
+/// * without fragment and `service` query, the content is the DID document itself
+/// * with `service` (and optionally `relativeRef`) query, the content is the selected service
+///   endpoint URL, with the `relativeRef` resolved against it and the fragment appended
+/// * with fragment only, the content is the verification method or service with that fragment
+///
+/// Paths are method specific, so are not dereferenced here.
+pub fn dereference_did_document(
+    resolution_output: &DidResolutionOutput,
+    did_url: &DidUrl,
+    options: &DidDereferencingOptions,
+) -> Result<DidDereferencingOutput<Cursor<Vec<u8>>>, DidDereferencingError> {
+    if did_url.path().is_some_and(|path| !path.is_empty()) {
+        return Err(DidDereferencingError::NotFound);
+    }
+    let did_document = &resolution_output.did_document;
+    let queries = did_url.queries();
+
+    let (content, content_type) = match queries.get(SERVICE_QUERY) {
+        Some(service_id) => {
+            let service = service_by_fragment(did_document, service_id)
+                .ok_or(DidDereferencingError::NotFound)?;
+            let mut endpoint = match queries.get(RELATIVE_REF_QUERY) {
+                Some(relative_ref) => service
+                    .service_endpoint()
+                    .join(relative_ref)
+                    .map_err(|_| DidDereferencingError::InvalidDidUrl)?,
+                None => service.service_endpoint().clone(),
+            };
+            if let Some(fragment) = did_url.fragment() {
+                endpoint.set_fragment(Some(fragment));
+            }
+            (
+                endpoint.to_string().into_bytes(),
+                URI_LIST_CONTENT_TYPE.to_string(),
+            )
+        }
+        None if queries.contains_key(RELATIVE_REF_QUERY) => {
+            return Err(DidDereferencingError::InvalidDidUrl);
+        }
+        None => {
+            let value = match did_url.fragment() {
+                Some(fragment) => fragment_value(did_document, did_url, fragment)?,
+                None => serde_json::to_value(did_document)
+                    .map_err(|_| DidDereferencingError::InvalidDid)?,
+            };
+            let content_type = options.accept().unwrap_or(&MediaType::DidJson).to_string();
+            (value.to_string().into_bytes(), content_type)
+        }
+    };
+
+    Ok(DidDereferencingOutput::builder(Cursor::new(content))
+        .dereferencing_metadata(
+            DidDereferencingMetadata::builder()
+                .content_type(content_type)
+                .build(),
+        )
+        .content_metadata(resolution_output.did_document_metadata.clone())
+        .build())
+}
+
+fn service_by_fragment<'a>(did_document: &'a DidDocument, fragment: &str) -> Option<&'a Service> {
+    let fragment = format!("#{fragment}");
+    did_document
+        .service()
+        .iter()
+        .find(|service| service.id().to_string().ends_with(&fragment))
+}
+
+fn fragment_value(
+    did_document: &DidDocument,
+    did_url: &DidUrl,
+    fragment: &str,
+) -> Result<serde_json::Value, DidDereferencingError> {
+    let value = match (
+        did_document.dereference_key(did_url),
+        service_by_fragment(did_document, fragment),
+    ) {
+        (Some(verification_method), None) => serde_json::to_value(verification_method),
+        (None, Some(service)) => serde_json::to_value(service),
+        (None, None) => return Err(DidDereferencingError::NotFound),
+        (Some(_), Some(_)) => return Err(DidDereferencingError::InvalidDidUrl),
+    };
+    value.map_err(|_| DidDereferencingError::InvalidDid)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+
+    use serde_json::{json, Value};
+
+    use super::*;
+
+    fn resolution_output() -> DidResolutionOutput {
+        let did_document: DidDocument = serde_json::from_value(json!({
+            "id": "did:example:123456789abcdefghi",
+            "verificationMethod": [{
+                "id": "did:example:123456789abcdefghi#keys-1",
+                "type": "Ed25519VerificationKey2018",
+                "controller": "did:example:123456789abcdefghi",
+                "publicKeyBase58": "H3C2AVvLMv6gmMNam3uVAjZpfkcJCwDwnZn6z3wXmqPV"
+            }],
+            "service": [{
+                "id": "did:example:123456789abcdefghi#files",
+                "type": "LinkedDomains",
+                "serviceEndpoint": "https://example.com/files/"
+            }]
+        }))
+        .unwrap();
+        DidResolutionOutput::builder(did_document).build()
+    }
+
+    fn dereference(did_url: &str) -> Result<(String, String), DidDereferencingError> {
+        let output = dereference_did_document(
+            &resolution_output(),
+            &DidUrl::parse(did_url.to_string()).unwrap(),
+            &DidDereferencingOptions::default(),
+        )?;
+        let content_type = output
+            .dereferencing_metadata()
+            .content_type()
+            .unwrap()
+            .to_owned();
+        let mut content = String::new();
+        output
+            .content_stream()
+            .clone()
+            .read_to_string(&mut content)
+            .unwrap();
+        Ok((content, content_type))
+    }
+
+    #[test]
+    fn test_dereference_document() {
+        let (content, content_type) = dereference("did:example:123456789abcdefghi").unwrap();
+        assert_eq!(content_type, "application/did+json");
+        let value: Value = serde_json::from_str(&content).unwrap();
+        assert_eq!(value["id"], "did:example:123456789abcdefghi");
+    }
+
+    #[test]
+    fn test_dereference_fragment() {
+        let (content, _) = dereference("did:example:123456789abcdefghi#keys-1").unwrap();
+        let value: Value = serde_json::from_str(&content).unwrap();
+        assert_eq!(value["type"], "Ed25519VerificationKey2018");
+
+        let (content, _) = dereference("did:example:123456789abcdefghi#files").unwrap();
+        let value: Value = serde_json::from_str(&content).unwrap();
+        assert_eq!(value["serviceEndpoint"], "https://example.com/files/");
+
+        assert_eq!(
+            dereference("did:example:123456789abcdefghi#unknown"),
+            Err(DidDereferencingError::NotFound)
+        );
+    }
+
+    #[test]
+    fn test_dereference_service_relative_ref() {
+        let (content, content_type) = dereference(
+            "did:example:123456789abcdefghi?service=files&relativeRef=%2Fresume.pdf#page-1",
+        )
+        .unwrap();
+        assert_eq!(content_type, "text/uri-list");
+        assert_eq!(content, "https://example.com/resume.pdf#page-1");
+
+        let (content, _) =
+            dereference("did:example:123456789abcdefghi?service=files&relativeRef=resume.pdf")
+                .unwrap();
+        assert_eq!(content, "https://example.com/files/resume.pdf");
+
+        assert_eq!(
+            dereference("did:example:123456789abcdefghi?service=agent"),
+            Err(DidDereferencingError::NotFound)
+        );
+        assert_eq!(
+            dereference("did:example:123456789abcdefghi?relativeRef=resume.pdf"),
+            Err(DidDereferencingError::InvalidDidUrl)
+        );
+    }
+
+    #[test]
+    fn test_dereference_path_not_found() {
+        assert_eq!(
+            dereference("did:example:123456789abcdefghi/some/path"),
+            Err(DidDereferencingError::NotFound)
+        );
+    }
+}
@@ -2,6 +2,7 @@ pub mod dereferencing_error;
 pub mod dereferencing_metadata;
 pub mod dereferencing_options;
 pub mod dereferencing_output;
+pub mod document_dereferencing;
 
 use std::io::Read;
 
@@ -2,6 +2,8 @@ pub mod resolution_error;
 pub mod resolution_metadata;
 pub mod resolution_output;
 
+use std::sync::Arc;
+
 use async_trait::async_trait;
 use did_parser_nom::Did;
 
@@ -18,3 +20,20 @@ pub trait DidResolvable {
         options: &Self::DidResolutionOptions,
     ) -> Result<DidResolutionOutput, GenericError>;
 }
+
+#[async_trait]
+impl<T> DidResolvable for Arc<T>
+where
+    T: DidResolvable + Send + Sync,
+    T::DidResolutionOptions: Send + Sync,
+{
+    type DidResolutionOptions = T::DidResolutionOptions;
+
+    async fn resolve(
+        &self,
+        did: &Did,
+        options: &Self::DidResolutionOptions,
+    ) -> Result<DidResolutionOutput, GenericError> {
+        self.as_ref().resolve(did, options).await
+    }
+}
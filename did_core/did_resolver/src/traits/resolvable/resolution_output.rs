@@ -8,7 +8,9 @@ use crate::shared_types::did_document_metadata::DidDocumentMetadata;
 #[serde(rename_all = "camelCase")]
 pub struct DidResolutionOutput {
     pub did_document: DidDocument,
+    #[serde(default)]
     pub did_resolution_metadata: DidResolutionMetadata,
+    #[serde(default)]
     pub did_document_metadata: DidDocumentMetadata,
 }
 
@@ -0,0 +1,290 @@
+use std::{collections::HashMap, io::Read, sync::Arc};
+
+use axum::{
+    extract::{Path, Query, State},
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+    routing::get,
+    Router,
+};
+use did_resolver::{
+    did_parser_nom::{Did, DidUrl},
+    error::GenericError,
+    shared_types::media_type::MediaType,
+    traits::{
+        dereferenceable::{
+            dereferencing_error::DidDereferencingError,
+            dereferencing_options::DidDereferencingOptions,
+            document_dereferencing::{
+                RELATIVE_REF_QUERY, SERVICE_QUERY, VERSION_ID_QUERY, VERSION_TIME_QUERY,
+            },
+        },
+        resolvable::{
+            resolution_error::DidResolutionError, resolution_output::DidResolutionOutput,
+        },
+    },
+};
+use did_resolver_registry::{error::DidResolverRegistryError, ResolverRegistry};
+use did_resolver_universal::resolution::resolver::{
+    DID_RESOLUTION_RESULT_MEDIA_TYPE, IDENTIFIERS_PATH,
+};
+use serde_json::{json, Map, Value};
+use url::form_urlencoded;
+
+/// DID URL parameters, taken as the query of the DID URL rather than as resolution options when
+/// passed as query parameters
+const DID_URL_PARAMETERS: [&str; 7] = [
+    SERVICE_QUERY,
+    RELATIVE_REF_QUERY,
+    VERSION_ID_QUERY,
+    VERSION_TIME_QUERY,
+    "hl",
+    "resourceName",
+    "resourceType",
+];
+
+/// Router of the Universal Resolver driver interface, resolving DIDs and dereferencing DID URLs
+/// at `/1.0/identifiers/{identifier}` with the given registry. DID URL parameters among the query
+/// parameters make the identifier a DID URL to dereference. The other query parameters are
+/// passed to the registry as resolution options, as far as the resolver of the method accepts
+/// them.
+pub fn router(registry: Arc<ResolverRegistry>) -> Router {
+    Router::new()
+        .route(
+            &format!("{IDENTIFIERS_PATH}{{*identifier}}"),
+            get(resolve_identifier),
+        )
+        .with_state(registry)
+}
+
+async fn resolve_identifier(
+    State(registry): State<Arc<ResolverRegistry>>,
+    Path(identifier): Path<String>,
+    Query(parameters): Query<Vec<(String, String)>>,
+    headers: HeaderMap,
+) -> Response {
+    log::info!("resolve_identifier >> identifier: {identifier}, parameters: {parameters:?}");
+    let accept = headers
+        .get(header::ACCEPT)
+        .and_then(|accept| accept.to_str().ok())
+        .unwrap_or_default();
+
+    let (did_url_parameters, options): (Vec<_>, Vec<_>) = parameters
+        .into_iter()
+        .partition(|(name, _)| DID_URL_PARAMETERS.contains(&name.as_str()));
+    let identifier = with_query(identifier, &did_url_parameters);
+    let options = options.into_iter().collect();
+
+    match Did::parse(identifier.clone()) {
+        Ok(did) => resolve_did(&registry, &did, options, accept).await,
+        Err(_) => match DidUrl::parse(identifier) {
+            Ok(did_url) => dereference_did_url(&registry, &did_url, accept).await,
+            Err(err) => error_response(Box::new(err)),
+        },
+    }
+}
+
+/// Appends the parameters, percent-encoded, to the query of the DID URL
+fn with_query(identifier: String, parameters: &[(String, String)]) -> String {
+    if parameters.is_empty() {
+        return identifier;
+    }
+    let query = form_urlencoded::Serializer::new(String::new())
+        .extend_pairs(parameters)
+        .finish();
+    let (did_url, fragment) = match identifier.split_once('#') {
+        Some((did_url, fragment)) => (did_url, Some(fragment)),
+        None => (identifier.as_str(), None),
+    };
+    let separator = if did_url.contains('?') { '&' } else { '?' };
+    match fragment {
+        Some(fragment) => format!("{did_url}{separator}{query}#{fragment}"),
+        None => format!("{did_url}{separator}{query}"),
+    }
+}
+
+async fn resolve_did(
+    registry: &ResolverRegistry,
+    did: &Did,
+    options: HashMap<String, String>,
+    accept: &str,
+) -> Response {
+    let options = options
+        .into_iter()
+        .map(|(name, value)| {
+            let value = match value.as_str() {
+                "true" => Value::Bool(true),
+                "false" => Value::Bool(false),
+                _ => Value::String(value),
+            };
+            (name, value)
+        })
+        .collect();
+    let options = registry.supported_options(did, &options);
+    let resolution_output = match registry.resolve(did, &options).await {
+        Ok(resolution_output) => resolution_output,
+        Err(err) => return error_response(err),
+    };
+
+    let status = if resolution_output.did_document_metadata.deactivated() == Some(true) {
+        StatusCode::GONE
+    } else {
+        StatusCode::OK
+    };
+    match document_media_type(accept) {
+        Some(media_type) => json_response(
+            status,
+            &media_type.to_string(),
+            json!(resolution_output.did_document),
+        ),
+        None => json_response(
+            status,
+            DID_RESOLUTION_RESULT_MEDIA_TYPE,
+            resolution_result(&resolution_output),
+        ),
+    }
+}
+
+/// DID resolution result, with the camelCase metadata properties of the DID Resolution spec
+fn resolution_result(resolution_output: &DidResolutionOutput) -> Value {
+    let metadata = &resolution_output.did_resolution_metadata;
+    let mut did_resolution_metadata = Map::new();
+    if let Some(content_type) = metadata.content_type() {
+        did_resolution_metadata.insert("contentType".to_string(), json!(content_type));
+    }
+    if let Some(error) = metadata.error() {
+        did_resolution_metadata.insert("error".to_string(), json!(error));
+    }
+    json!({
+        "didDocument": resolution_output.did_document,
+        "didResolutionMetadata": did_resolution_metadata,
+        "didDocumentMetadata": resolution_output.did_document_metadata,
+    })
+}
+
+async fn dereference_did_url(
+    registry: &ResolverRegistry,
+    did_url: &DidUrl,
+    accept: &str,
+) -> Response {
+    let mut options = DidDereferencingOptions::new();
+    if let Some(media_type) = document_media_type(accept) {
+        options = options.set_accept(media_type);
+    }
+    let output = match registry.dereference(did_url, &options).await {
+        Ok(output) => output,
+        Err(err) => return error_response(err),
+    };
+    let content_type = output
+        .dereferencing_metadata()
+        .content_type()
+        .cloned()
+        .unwrap_or("application/octet-stream".to_string());
+    let mut content = Vec::new();
+    if let Err(err) = output.into_content_stream().read_to_end(&mut content) {
+        return error_response(Box::new(err));
+    }
+    (
+        StatusCode::OK,
+        [(header::CONTENT_TYPE, content_type)],
+        content,
+    )
+        .into_response()
+}
+
+/// Media type of the DID document alone, if requested instead of the resolution result
+fn document_media_type(accept: &str) -> Option<MediaType> {
+    if accept.contains("application/did+ld+json") {
+        Some(MediaType::DidLdJson)
+    } else if accept.contains("application/did+json") {
+        Some(MediaType::DidJson)
+    } else {
+        None
+    }
+}
+
+fn error_response(err: GenericError) -> Response {
+    log::warn!("error_response >> {err}");
+    let error = if let Some(error) = err.downcast_ref::<DidResolutionError>() {
+        error.clone()
+    } else if let Some(error) = err.downcast_ref::<DidResolverRegistryError>() {
+        match error {
+            DidResolverRegistryError::UnsupportedMethod => DidResolutionError::MethodNotSupported,
+            DidResolverRegistryError::UnqualifiedDid => DidResolutionError::InvalidDid,
+            _ => DidResolutionError::InternalError,
+        }
+    } else if let Some(error) = err.downcast_ref::<DidDereferencingError>() {
+        match error {
+            DidDereferencingError::NotFound => DidResolutionError::NotFound,
+            _ => DidResolutionError::InvalidDid,
+        }
+    } else if err
+        .downcast_ref::<did_resolver::did_parser_nom::ParseError>()
+        .is_some()
+    {
+        DidResolutionError::InvalidDid
+    } else {
+        DidResolutionError::InternalError
+    };
+    let status = match error {
+        DidResolutionError::InvalidDid => StatusCode::BAD_REQUEST,
+        DidResolutionError::NotFound => StatusCode::NOT_FOUND,
+        DidResolutionError::RepresentationNotSupported => StatusCode::NOT_ACCEPTABLE,
+        DidResolutionError::MethodNotSupported => StatusCode::NOT_IMPLEMENTED,
+        _ => StatusCode::INTERNAL_SERVER_ERROR,
+    };
+    json_response(
+        status,
+        DID_RESOLUTION_RESULT_MEDIA_TYPE,
+        json!({
+            "didDocument": null,
+            "didResolutionMetadata": {
+                "error": error,
+                "errorMessage": err.to_string(),
+            },
+            "didDocumentMetadata": {},
+        }),
+    )
+}
+
+fn json_response(status: StatusCode, content_type: &str, body: Value) -> Response {
+    (
+        status,
+        [(header::CONTENT_TYPE, content_type.to_string())],
+        body.to_string(),
+    )
+        .into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DID_KEY: &str = "did:key:z6MkpTHR8VNsBxYAAWHut2Geadd9jSwuBV8xRoAnwWsdvktH";
+
+    #[test]
+    fn test_with_query_encodes_reserved_characters() {
+        let value = "a&b=c#d?e f";
+        let identifier = with_query(
+            DID_KEY.to_string(),
+            &[("service".to_string(), value.to_string())],
+        );
+        assert_eq!(identifier, format!("{DID_KEY}?service=a%26b%3Dc%23d%3Fe+f"));
+
+        let did_url = DidUrl::parse(identifier).unwrap();
+        assert_eq!(did_url.queries().get("service").unwrap(), value);
+        assert_eq!(did_url.fragment(), None);
+    }
+
+    #[test]
+    fn test_with_query_keeps_existing_query_and_fragment() {
+        let identifier = with_query(
+            format!("{DID_KEY}?versionId=1#key-1"),
+            &[("service".to_string(), "files".to_string())],
+        );
+        assert_eq!(
+            identifier,
+            format!("{DID_KEY}?versionId=1&service=files#key-1")
+        );
+    }
+}
@@ -0,0 +1,72 @@
+use std::{sync::Arc, time::Duration};
+
+use axum::http::Uri;
+use clap::Parser;
+use did_cheqd::resolution::resolver::DidCheqdResolver;
+use did_jwk::resolver::DidJwkResolver;
+use did_peer::resolver::PeerDidResolver;
+use did_resolver_key::resolution::resolver::DidKeyResolver;
+use did_resolver_registry::{
+    cache::{CachePolicy, ResolutionCache},
+    ResolverRegistry,
+};
+use did_resolver_universal::resolution::resolver::UniversalResolver;
+use did_resolver_web::resolution::resolver::DidWebResolver;
+use did_webvh::resolution::resolver::DidWebvhResolver;
+use tokio::net::TcpListener;
+
+#[derive(Parser)]
+struct Opts {
+    #[clap(long, default_value = "0.0.0.0")]
+    host: String,
+    #[clap(short, long, default_value = "8080")]
+    port: u16,
+    /// Caches resolution results for the given number of seconds
+    #[clap(long)]
+    cache_ttl: Option<u64>,
+    /// Universal Resolver resolving DIDs of the methods not resolved locally, e.g.
+    /// `https://dev.uniresolver.io`
+    #[clap(long, env = "UNIVERSAL_RESOLVER_URL")]
+    universal_resolver_url: Option<Uri>,
+}
+
+#[tokio::main]
+async fn main() {
+    env_logger::init();
+    let opts = Opts::parse();
+
+    let mut registry = ResolverRegistry::new()
+        .register_resolver("key".into(), DidKeyResolver::new())
+        .register_resolver("peer".into(), PeerDidResolver::new())
+        .register_resolver("jwk".into(), DidJwkResolver::new())
+        .register_resolver("web".into(), DidWebResolver::https())
+        .register_resolver("webvh".into(), DidWebvhResolver::https())
+        .register_dereferenceable_resolver(
+            "cheqd".into(),
+            DidCheqdResolver::new(Default::default()),
+        );
+    if let Some(endpoint) = opts.universal_resolver_url {
+        log::info!("Resolving other DID methods with the Universal Resolver at {endpoint}");
+        registry = if endpoint.scheme_str() == Some("https") {
+            registry.with_fallback_resolver(UniversalResolver::https(endpoint))
+        } else {
+            registry.with_fallback_resolver(UniversalResolver::http(endpoint))
+        };
+    }
+    if let Some(cache_ttl) = opts.cache_ttl {
+        registry = registry.with_cache(ResolutionCache::new(CachePolicy::new(
+            Duration::from_secs(cache_ttl),
+        )));
+    }
+
+    let listener = TcpListener::bind((opts.host.as_str(), opts.port))
+        .await
+        .expect("Failed to bind driver address");
+    log::info!(
+        "Universal Resolver driver listening on {}",
+        listener.local_addr().expect("Failed to get local address")
+    );
+    axum::serve(listener, did_resolver_driver::router(Arc::new(registry)))
+        .await
+        .expect("Driver server failed");
+}
@@ -0,0 +1,138 @@
+use std::{collections::HashMap, sync::Arc};
+
+use axum::{
+    body::{to_bytes, Body},
+    http::{header, Request, StatusCode},
+};
+use did_resolver::{
+    did_parser_nom::Did,
+    traits::resolvable::{resolution_error::DidResolutionError, DidResolvable},
+};
+use did_resolver_key::resolution::resolver::DidKeyResolver;
+use did_resolver_registry::ResolverRegistry;
+use did_resolver_universal::resolution::resolver::{
+    UniversalResolver, DID_RESOLUTION_RESULT_MEDIA_TYPE,
+};
+use serde_json::Value;
+use tokio::net::TcpListener;
+use tower::ServiceExt;
+
+const DID_KEY: &str = "did:key:z6MkpTHR8VNsBxYAAWHut2Geadd9jSwuBV8xRoAnwWsdvktH";
+
+fn registry() -> Arc<ResolverRegistry> {
+    Arc::new(ResolverRegistry::new().register_resolver("key".into(), DidKeyResolver::new()))
+}
+
+async fn spawn_driver() -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        axum::serve(listener, did_resolver_driver::router(registry()))
+            .await
+            .unwrap()
+    });
+    format!("http://{addr}")
+}
+
+async fn get(uri: &str, accept: &str) -> (StatusCode, String, Vec<u8>) {
+    let response = did_resolver_driver::router(registry())
+        .oneshot(
+            Request::get(uri)
+                .header(header::ACCEPT, accept)
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    let status = response.status();
+    let content_type = response.headers()[header::CONTENT_TYPE]
+        .to_str()
+        .unwrap()
+        .to_string();
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, content_type, body.to_vec())
+}
+
+#[tokio::test]
+async fn test_resolve_through_driver() {
+    let driver_url = spawn_driver().await;
+    let resolver = UniversalResolver::http(driver_url.parse().unwrap());
+
+    let did = Did::parse(DID_KEY.to_string()).unwrap();
+    let output = resolver.resolve(&did, &HashMap::new()).await.unwrap();
+    let expected = DidKeyResolver::new()
+        .resolve(&did, &Default::default())
+        .await
+        .unwrap();
+    assert_eq!(output.did_document, expected.did_document);
+
+    let did = Did::parse("did:unknown:123".to_string()).unwrap();
+    let err = resolver.resolve(&did, &HashMap::new()).await.unwrap_err();
+    assert_eq!(
+        err.downcast_ref::<DidResolutionError>(),
+        Some(&DidResolutionError::MethodNotSupported)
+    );
+}
+
+#[tokio::test]
+async fn test_resolve_document_representation() {
+    let (status, content_type, body) = get(
+        &format!("/1.0/identifiers/{DID_KEY}"),
+        "application/did+ld+json",
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(content_type, "application/did+ld+json");
+    let document: Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(document["id"], DID_KEY);
+}
+
+#[tokio::test]
+async fn test_resolve_invalid_did() {
+    let (status, _, body) = get("/1.0/identifiers/not-a-did", "*/*").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let result: Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(result["didResolutionMetadata"]["error"], "invalidDid");
+}
+
+#[tokio::test]
+async fn test_dereference_fragment() {
+    let fragment = DID_KEY.strip_prefix("did:key:").unwrap();
+    let (status, _, body) = get(&format!("/1.0/identifiers/{DID_KEY}%23{fragment}"), "*/*").await;
+    assert_eq!(status, StatusCode::OK);
+    let verification_method: Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(verification_method["id"], format!("{DID_KEY}#{fragment}"));
+}
+
+#[tokio::test]
+async fn test_resolve_drops_options_unsupported_by_method() {
+    let (status, content_type, body) = get(
+        &format!("/1.0/identifiers/{DID_KEY}?foo=bar&noCache=true"),
+        "*/*",
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(content_type, DID_RESOLUTION_RESULT_MEDIA_TYPE);
+    let result: Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(result["didDocument"]["id"], DID_KEY);
+}
+
+#[tokio::test]
+async fn test_dereference_did_url_query_parameters() {
+    let (status, _, body) = get(&format!("/1.0/identifiers/{DID_KEY}?versionId=1"), "*/*").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let result: Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(result["didResolutionMetadata"]["error"], "notFound");
+
+    let (status, _, body) = get(
+        &format!("/1.0/identifiers/{DID_KEY}?service=missing"),
+        "*/*",
+    )
+    .await;
+    assert_eq!(
+        status,
+        StatusCode::NOT_FOUND,
+        "{}",
+        String::from_utf8_lossy(&body)
+    );
+}
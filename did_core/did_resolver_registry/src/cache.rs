@@ -0,0 +1,280 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex, MutexGuard},
+    time::{Duration, Instant},
+};
+
+use chrono::Utc;
+use did_resolver::traits::resolvable::resolution_output::DidResolutionOutput;
+use tokio::sync::OnceCell;
+
+const DEFAULT_MAX_ENTRIES: usize = 1000;
+
+/// Outcome of a resolution shared between the resolution leader, concurrent callers waiting for
+/// the same DID and the cache. Failures are kept in their display form, as resolver errors are
+/// not cloneable.
+pub(crate) type SharedOutcome = Result<DidResolutionOutput, String>;
+
+/// How long results of resolution of DIDs of a given method are kept
+#[derive(Clone, Debug, PartialEq)]
+pub struct CachePolicy {
+    ttl: Duration,
+    negative_ttl: Option<Duration>,
+}
+
+impl CachePolicy {
+    /// Successful resolutions are cached for `ttl`, failures are not cached
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            negative_ttl: None,
+        }
+    }
+
+    /// Failed resolutions (including results carrying an error in their resolution metadata)
+    /// are cached for `negative_ttl`
+    pub fn with_negative_ttl(mut self, negative_ttl: Duration) -> Self {
+        self.negative_ttl = Some(negative_ttl);
+        self
+    }
+
+    pub fn ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    pub fn negative_ttl(&self) -> Option<Duration> {
+        self.negative_ttl
+    }
+
+    /// Time to live of a result. Successful results are never kept past the `nextUpdate` of the
+    /// resolved document.
+    fn ttl_for(&self, outcome: &SharedOutcome) -> Option<Duration> {
+        match outcome {
+            Ok(output) if output.did_resolution_metadata.error().is_some() => self.negative_ttl,
+            Ok(output) => match output.did_document_metadata.next_update() {
+                Some(next_update) => (next_update - Utc::now())
+                    .to_std()
+                    .ok()
+                    .map(|until_update| until_update.min(self.ttl)),
+                None => Some(self.ttl),
+            },
+            Err(_) => self.negative_ttl,
+        }
+        .filter(|ttl| !ttl.is_zero())
+    }
+}
+
+struct CacheEntry {
+    outcome: SharedOutcome,
+    expires_at: Instant,
+}
+
+/// Cache of resolution results with per-method policies, keyed by DID and resolution options
+pub struct ResolutionCache {
+    default_policy: Option<CachePolicy>,
+    method_policies: HashMap<String, Option<CachePolicy>>,
+    max_entries: usize,
+    entries: Mutex<HashMap<String, CacheEntry>>,
+    in_flight: Mutex<HashMap<String, Arc<OnceCell<SharedOutcome>>>>,
+}
+
+impl ResolutionCache {
+    /// Cache applying `default_policy` to every method without a policy of its own
+    pub fn new(default_policy: CachePolicy) -> Self {
+        Self {
+            default_policy: Some(default_policy),
+            method_policies: HashMap::new(),
+            max_entries: DEFAULT_MAX_ENTRIES,
+            entries: Mutex::new(HashMap::new()),
+            in_flight: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn with_method_policy(mut self, method: impl Into<String>, policy: CachePolicy) -> Self {
+        self.method_policies.insert(method.into(), Some(policy));
+        self
+    }
+
+    /// Results of resolution of DIDs of this method are never cached
+    pub fn without_caching_for(mut self, method: impl Into<String>) -> Self {
+        self.method_policies.insert(method.into(), None);
+        self
+    }
+
+    pub fn with_max_entries(mut self, max_entries: usize) -> Self {
+        self.max_entries = max_entries;
+        self
+    }
+
+    pub fn policy(&self, method: &str) -> Option<&CachePolicy> {
+        match self.method_policies.get(method) {
+            Some(policy) => policy.as_ref(),
+            None => self.default_policy.as_ref(),
+        }
+    }
+
+    pub(crate) fn get(&self, key: &str) -> Option<SharedOutcome> {
+        let mut entries = lock(&self.entries);
+        match entries.get(key) {
+            Some(entry) if entry.expires_at > Instant::now() => Some(entry.outcome.clone()),
+            Some(_) => {
+                entries.remove(key);
+                None
+            }
+            None => None,
+        }
+    }
+
+    pub(crate) fn insert(&self, method: &str, key: String, outcome: SharedOutcome) {
+        let Some(ttl) = self
+            .policy(method)
+            .and_then(|policy| policy.ttl_for(&outcome))
+        else {
+            return;
+        };
+        let Some(expires_at) = Instant::now().checked_add(ttl) else {
+            return;
+        };
+        let mut entries = lock(&self.entries);
+        if entries.len() >= self.max_entries && !entries.contains_key(&key) {
+            let now = Instant::now();
+            entries.retain(|_, entry| entry.expires_at > now);
+            if entries.len() >= self.max_entries {
+                let soonest_expiring = entries
+                    .iter()
+                    .min_by_key(|(_, entry)| entry.expires_at)
+                    .map(|(key, _)| key.clone());
+                if let Some(soonest_expiring) = soonest_expiring {
+                    entries.remove(&soonest_expiring);
+                }
+            }
+        }
+        if self.max_entries > 0 {
+            entries.insert(
+                key,
+                CacheEntry {
+                    outcome,
+                    expires_at,
+                },
+            );
+        }
+    }
+
+    /// Drops cached results of resolution of the DID, under any resolution options
+    pub fn invalidate(&self, did: &str) {
+        let prefix = format!("{did}?");
+        lock(&self.entries).retain(|key, _| !key.starts_with(&prefix));
+    }
+
+    pub fn clear(&self) {
+        lock(&self.entries).clear();
+    }
+
+    /// Cell through which concurrent resolutions under the same key share a single outcome
+    pub(crate) fn in_flight(&self, key: &str) -> Arc<OnceCell<SharedOutcome>> {
+        lock(&self.in_flight)
+            .entry(key.to_string())
+            .or_default()
+            .clone()
+    }
+
+    pub(crate) fn finish_in_flight(&self, key: &str, cell: &Arc<OnceCell<SharedOutcome>>) {
+        let mut in_flight = lock(&self.in_flight);
+        if in_flight
+            .get(key)
+            .is_some_and(|current| Arc::ptr_eq(current, cell))
+        {
+            in_flight.remove(key);
+        }
+    }
+}
+
+/// The maps are left consistent by every critical section, so a poisoned lock is still usable
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration as ChronoDuration;
+    use did_resolver::{
+        did_doc::schema::did_doc::DidDocument,
+        did_parser_nom::Did,
+        shared_types::did_document_metadata::DidDocumentMetadata,
+        traits::resolvable::{
+            resolution_error::DidResolutionError, resolution_metadata::DidResolutionMetadata,
+        },
+    };
+
+    use super::*;
+
+    fn output() -> DidResolutionOutput {
+        DidResolutionOutput::builder(DidDocument::new(
+            Did::parse("did:example:1234".to_string()).unwrap(),
+        ))
+        .build()
+    }
+
+    #[test]
+    fn test_ttl_capped_by_next_update() {
+        let policy = CachePolicy::new(Duration::from_secs(3600));
+        let mut output = output();
+        output.did_document_metadata = DidDocumentMetadata::builder()
+            .next_update(Utc::now() + ChronoDuration::seconds(60))
+            .build();
+        let ttl = policy.ttl_for(&Ok(output.clone())).unwrap();
+        assert!(ttl <= Duration::from_secs(60));
+
+        output.did_document_metadata = DidDocumentMetadata::builder()
+            .next_update(Utc::now() - ChronoDuration::seconds(60))
+            .build();
+        assert_eq!(policy.ttl_for(&Ok(output)), None);
+    }
+
+    #[test]
+    fn test_negative_ttl() {
+        let policy = CachePolicy::new(Duration::from_secs(3600));
+        assert_eq!(policy.ttl_for(&Err("not found".to_string())), None);
+
+        let policy = policy.with_negative_ttl(Duration::from_secs(10));
+        assert_eq!(
+            policy.ttl_for(&Err("not found".to_string())),
+            Some(Duration::from_secs(10))
+        );
+        let mut output = output();
+        output.did_resolution_metadata = DidResolutionMetadata::builder()
+            .error(DidResolutionError::NotFound)
+            .build();
+        assert_eq!(policy.ttl_for(&Ok(output)), Some(Duration::from_secs(10)));
+    }
+
+    #[test]
+    fn test_method_policy() {
+        let cache = ResolutionCache::new(CachePolicy::new(Duration::from_secs(60)))
+            .with_method_policy("web", CachePolicy::new(Duration::from_secs(5)))
+            .without_caching_for("peer");
+        assert_eq!(cache.policy("key").unwrap().ttl(), Duration::from_secs(60));
+        assert_eq!(cache.policy("web").unwrap().ttl(), Duration::from_secs(5));
+        assert!(cache.policy("peer").is_none());
+
+        cache.insert("peer", "did:peer:1?{}".to_string(), Ok(output()));
+        assert!(cache.get("did:peer:1?{}").is_none());
+        cache.insert("key", "did:key:1?{}".to_string(), Ok(output()));
+        assert!(cache.get("did:key:1?{}").is_some());
+        cache.invalidate("did:key:1");
+        assert!(cache.get("did:key:1?{}").is_none());
+    }
+
+    #[test]
+    fn test_max_entries() {
+        let cache =
+            ResolutionCache::new(CachePolicy::new(Duration::from_secs(60))).with_max_entries(2);
+        for i in 0..3 {
+            cache.insert("key", format!("did:key:{i}?{{}}"), Ok(output()));
+        }
+        assert_eq!(lock(&cache.entries).len(), 2);
+        assert!(cache.get("did:key:2?{}").is_some());
+    }
+}
@@ -0,0 +1,280 @@
+use std::{
+    collections::HashMap,
+    io::{Cursor, Read},
+    sync::Arc,
+};
+
+use async_trait::async_trait;
+use chrono::DateTime;
+use did_resolver::{
+    did_parser_nom::DidUrl,
+    error::GenericError,
+    traits::{
+        dereferenceable::{
+            dereferencing_error::DidDereferencingError,
+            dereferencing_options::DidDereferencingOptions,
+            dereferencing_output::DidDereferencingOutput,
+            document_dereferencing::{VERSION_ID_QUERY, VERSION_TIME_QUERY},
+            DidDereferenceable,
+        },
+        resolvable::resolution_output::DidResolutionOutput,
+    },
+};
+
+pub type GenericDereferencer = dyn DidDereferenceableAdaptorTrait + Send + Sync;
+
+pub struct DidDereferenceableAdaptor<T: DidDereferenceable> {
+    pub(crate) inner: Arc<T>,
+}
+
+#[async_trait]
+pub trait DidDereferenceableAdaptorTrait: Send + Sync {
+    async fn dereference(
+        &self,
+        did_url: &DidUrl,
+        options: &DidDereferencingOptions,
+    ) -> Result<DidDereferencingOutput<Cursor<Vec<u8>>>, GenericError>;
+}
+
+#[async_trait]
+impl<T: DidDereferenceable + Send + Sync> DidDereferenceableAdaptorTrait
+    for DidDereferenceableAdaptor<T>
+{
+    async fn dereference(
+        &self,
+        did_url: &DidUrl,
+        options: &DidDereferencingOptions,
+    ) -> Result<DidDereferencingOutput<Cursor<Vec<u8>>>, GenericError> {
+        let output = self.inner.dereference(did_url, options).await?;
+        let dereferencing_metadata = output.dereferencing_metadata().clone();
+        let content_metadata = output.content_metadata().clone();
+        let mut content = Vec::new();
+        output.into_content_stream().read_to_end(&mut content)?;
+
+        Ok(DidDereferencingOutput::builder(Cursor::new(content))
+            .dereferencing_metadata(dereferencing_metadata)
+            .content_metadata(content_metadata)
+            .build())
+    }
+}
+
+/// Version queries of the DID URL, passed to the method resolver as resolution options
+pub(crate) fn version_options(
+    queries: &HashMap<String, String>,
+) -> HashMap<String, serde_json::Value> {
+    [VERSION_ID_QUERY, VERSION_TIME_QUERY]
+        .into_iter()
+        .filter_map(|query| {
+            queries
+                .get(query)
+                .map(|value| (query.to_string(), serde_json::Value::String(value.clone())))
+        })
+        .collect()
+}
+
+/// Methods ignoring the version options resolve the current document, which only satisfies the
+/// version queries if its metadata says so
+pub(crate) fn check_version(
+    resolution_output: &DidResolutionOutput,
+    queries: &HashMap<String, String>,
+) -> Result<(), DidDereferencingError> {
+    let metadata = &resolution_output.did_document_metadata;
+    if let Some(version_id) = queries.get(VERSION_ID_QUERY) {
+        if metadata.version_id() != Some(version_id) {
+            return Err(DidDereferencingError::NotFound);
+        }
+    }
+    if let Some(version_time) = queries.get(VERSION_TIME_QUERY) {
+        let version_time = DateTime::parse_from_rfc3339(version_time)
+            .map_err(|_| DidDereferencingError::InvalidDidUrl)?
+            .to_utc();
+        let valid_since = metadata.updated().or(metadata.created());
+        if valid_since.is_some_and(|valid_since| valid_since > version_time) {
+            return Err(DidDereferencingError::NotFound);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use did_resolver::{
+        did_doc::schema::did_doc::DidDocument, did_parser_nom::Did,
+        shared_types::did_document_metadata::DidDocumentMetadata,
+        traits::resolvable::DidResolvable,
+    };
+    use serde::{Deserialize, Serialize};
+    use serde_json::{json, Value};
+
+    use super::*;
+    use crate::ResolverRegistry;
+
+    #[derive(Default, Serialize, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct VersionedResolutionOptions {
+        version_id: Option<String>,
+    }
+
+    struct VersionedResolver;
+
+    #[async_trait]
+    impl DidResolvable for VersionedResolver {
+        type DidResolutionOptions = VersionedResolutionOptions;
+
+        async fn resolve(
+            &self,
+            did: &Did,
+            options: &Self::DidResolutionOptions,
+        ) -> Result<DidResolutionOutput, GenericError> {
+            let did_document: DidDocument = serde_json::from_value(json!({
+                "id": did.did(),
+                "verificationMethod": [{
+                    "id": format!("{}#keys-1", did.did()),
+                    "type": "Ed25519VerificationKey2018",
+                    "controller": did.did(),
+                    "publicKeyBase58": "H3C2AVvLMv6gmMNam3uVAjZpfkcJCwDwnZn6z3wXmqPV"
+                }]
+            }))?;
+            let metadata = DidDocumentMetadata::builder()
+                .version_id(options.version_id.clone().unwrap_or("2".to_string()))
+                .updated(Utc::now())
+                .build();
+            Ok(DidResolutionOutput::builder(did_document)
+                .did_document_metadata(metadata)
+                .build())
+        }
+    }
+
+    /// Resolver of a method without versions, whose current document was last updated now
+    struct UnversionedResolver;
+
+    #[async_trait]
+    impl DidResolvable for UnversionedResolver {
+        type DidResolutionOptions = ();
+
+        async fn resolve(
+            &self,
+            did: &Did,
+            _options: &Self::DidResolutionOptions,
+        ) -> Result<DidResolutionOutput, GenericError> {
+            let metadata = DidDocumentMetadata::builder().updated(Utc::now()).build();
+            Ok(DidResolutionOutput::builder(DidDocument::new(did.clone()))
+                .did_document_metadata(metadata)
+                .build())
+        }
+    }
+
+    struct StaticDereferencer;
+
+    #[async_trait]
+    impl DidResolvable for StaticDereferencer {
+        type DidResolutionOptions = ();
+
+        async fn resolve(
+            &self,
+            did: &Did,
+            _options: &Self::DidResolutionOptions,
+        ) -> Result<DidResolutionOutput, GenericError> {
+            Ok(DidResolutionOutput::builder(DidDocument::new(did.clone())).build())
+        }
+    }
+
+    #[async_trait]
+    impl DidDereferenceable for StaticDereferencer {
+        type Output = Cursor<Vec<u8>>;
+
+        async fn dereference(
+            &self,
+            _did_url: &DidUrl,
+            _options: &DidDereferencingOptions,
+        ) -> Result<DidDereferencingOutput<Self::Output>, GenericError> {
+            Ok(DidDereferencingOutput::builder(Cursor::new(b"resource".to_vec())).build())
+        }
+    }
+
+    fn registry() -> ResolverRegistry {
+        ResolverRegistry::new()
+            .register_resolver("example".to_string(), VersionedResolver)
+            .register_resolver("plain".to_string(), UnversionedResolver)
+            .register_dereferenceable_resolver("static".to_string(), StaticDereferencer)
+    }
+
+    async fn dereference(
+        registry: &ResolverRegistry,
+        did_url: &str,
+    ) -> Result<(Vec<u8>, DidDocumentMetadata), GenericError> {
+        let output = registry
+            .dereference(
+                &DidUrl::parse(did_url.to_string()).unwrap(),
+                &DidDereferencingOptions::default(),
+            )
+            .await?;
+        let content_metadata = output.content_metadata().clone();
+        Ok((output.into_content_stream().into_inner(), content_metadata))
+    }
+
+    #[tokio::test]
+    async fn test_dereference_fragment() {
+        let (content, metadata) = dereference(&registry(), "did:example:123#keys-1")
+            .await
+            .unwrap();
+        let value: Value = serde_json::from_slice(&content).unwrap();
+        assert_eq!(value["id"], "did:example:123#keys-1");
+        assert_eq!(metadata.version_id().unwrap(), "2");
+    }
+
+    #[tokio::test]
+    async fn test_dereference_version_id() {
+        let (content, metadata) = dereference(&registry(), "did:example:123?versionId=1")
+            .await
+            .unwrap();
+        let value: Value = serde_json::from_slice(&content).unwrap();
+        assert_eq!(value["id"], "did:example:123");
+        assert_eq!(metadata.version_id().unwrap(), "1");
+    }
+
+    #[tokio::test]
+    async fn test_dereference_version_time_before_update() {
+        let err = dereference(
+            &registry(),
+            "did:example:123?versionTime=2020-01-01T00:00:00Z",
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<DidDereferencingError>(),
+            Some(&DidDereferencingError::NotFound)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_dereference_version_with_resolver_without_options() {
+        let (content, _) = dereference(&registry(), "did:plain:123").await.unwrap();
+        let value: Value = serde_json::from_slice(&content).unwrap();
+        assert_eq!(value["id"], "did:plain:123");
+
+        for did_url in [
+            "did:plain:123?versionId=1",
+            "did:plain:123?versionTime=2020-01-01T00:00:00Z",
+        ] {
+            let err = dereference(&registry(), did_url).await.unwrap_err();
+            assert_eq!(
+                err.downcast_ref::<DidDereferencingError>(),
+                Some(&DidDereferencingError::NotFound)
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_dereference_with_registered_dereferencer() {
+        let registry = registry();
+        let (content, _) = dereference(&registry, "did:static:123/resources/1")
+            .await
+            .unwrap();
+        assert_eq!(content, b"resource");
+
+        let did = Did::parse("did:static:123".to_string()).unwrap();
+        assert!(registry.resolve(&did, &HashMap::new()).await.is_ok());
+    }
+}
@@ -4,6 +4,7 @@ use std::error::Error;
 pub enum DidResolverRegistryError {
     UnsupportedMethod,
     UnqualifiedDid,
+    ResolutionFailed(String),
 }
 
 impl std::fmt::Display for DidResolverRegistryError {
@@ -13,6 +14,9 @@ impl std::fmt::Display for DidResolverRegistryError {
             DidResolverRegistryError::UnqualifiedDid => {
                 write!(f, "Attempted to resolve unqualified DID")
             }
+            DidResolverRegistryError::ResolutionFailed(reason) => {
+                write!(f, "DID resolution failed: {reason}")
+            }
         }
     }
 }
@@ -1,13 +1,29 @@
+pub mod cache;
+pub mod dereferencing;
 pub mod error;
 
-use std::collections::HashMap;
+use std::{
+    collections::{BTreeMap, HashMap},
+    io::Cursor,
+    sync::Arc,
+};
 
 use async_trait::async_trait;
+use cache::ResolutionCache;
+use dereferencing::{
+    check_version, version_options, DidDereferenceableAdaptor, GenericDereferencer,
+};
 use did_resolver::{
-    did_doc::schema::did_doc::DidDocument,
-    did_parser_nom::Did,
+    did_parser_nom::{Did, DidUrl},
     error::GenericError,
-    traits::resolvable::{resolution_output::DidResolutionOutput, DidResolvable},
+    traits::{
+        dereferenceable::{
+            dereferencing_options::DidDereferencingOptions,
+            dereferencing_output::DidDereferencingOutput,
+            document_dereferencing::dereference_did_document, DidDereferenceable,
+        },
+        resolvable::{resolution_output::DidResolutionOutput, DidResolvable},
+    },
 };
 use error::DidResolverRegistryError;
 use serde::{Deserialize, Serialize};
@@ -15,9 +31,15 @@ use serde_json::Value;
 
 pub type GenericResolver = dyn DidResolvableAdaptorTrait + Send + Sync;
 
+/// Resolution option bypassing cached results; the fresh result still gets cached
+pub const NO_CACHE_OPTION: &str = "noCache";
+
 #[derive(Default)]
 pub struct ResolverRegistry {
     resolvers: HashMap<String, Box<GenericResolver>>,
+    dereferencers: HashMap<String, Box<GenericDereferencer>>,
+    fallback_resolver: Option<Box<GenericResolver>>,
+    cache: Option<ResolutionCache>,
 }
 
 pub struct DidResolvableAdaptor<T: DidResolvable> {
@@ -31,6 +53,11 @@ pub trait DidResolvableAdaptorTrait: Send + Sync {
         did: &Did,
         options: HashMap<String, Value>,
     ) -> Result<DidResolutionOutput, GenericError>;
+
+    /// Whether the resolution options of the resolver can be deserialized from the options
+    fn accepts_options(&self, _options: &HashMap<String, Value>) -> bool {
+        true
+    }
 }
 
 #[async_trait]
@@ -49,21 +76,15 @@ where
             let json_map = options.into_iter().collect();
             serde_json::from_value(Value::Object(json_map))?
         };
-        let result_inner = self.inner.resolve(did, &options).await?;
-
-        let did_document_inner_hashmap = serde_json::to_value(result_inner.did_document)
-            .unwrap()
-            .as_object()
-            .unwrap()
-            .clone();
-
-        let did_document: DidDocument =
-            serde_json::from_value(Value::Object(did_document_inner_hashmap))?;
+        self.inner.resolve(did, &options).await
+    }
 
-        Ok(DidResolutionOutput::builder(did_document)
-            .did_resolution_metadata(result_inner.did_resolution_metadata)
-            .did_document_metadata(result_inner.did_document_metadata)
-            .build())
+    fn accepts_options(&self, options: &HashMap<String, Value>) -> bool {
+        options.is_empty()
+            || serde_json::from_value::<T::DidResolutionOptions>(Value::Object(
+                options.clone().into_iter().collect(),
+            ))
+            .is_ok()
     }
 }
 
@@ -83,11 +104,50 @@ impl ResolverRegistry {
         self
     }
 
+    /// Registers a resolver which also dereferences DID URLs of its method itself, instead of
+    /// the generic dereferencing of the resolved DID document
+    pub fn register_dereferenceable_resolver<T>(mut self, method: String, resolver: T) -> Self
+    where
+        T: DidDereferenceable + 'static + Send + Sync,
+        for<'de> <T as DidResolvable>::DidResolutionOptions:
+            Send + Sync + Serialize + Deserialize<'de>,
+    {
+        let resolver = Arc::new(resolver);
+        self.dereferencers.insert(
+            method.clone(),
+            Box::new(DidDereferenceableAdaptor {
+                inner: resolver.clone(),
+            }),
+        );
+        self.register_resolver(method, resolver)
+    }
+
     pub fn unregister_resolver(mut self, method: &str) -> Self {
         self.resolvers.remove(method);
+        self.dereferencers.remove(method);
+        self
+    }
+
+    /// Resolver of DIDs of methods without a registered resolver, e.g. a Universal Resolver client
+    pub fn with_fallback_resolver<T>(mut self, resolver: T) -> Self
+    where
+        T: DidResolvable + 'static + Send + Sync,
+        for<'de> <T as DidResolvable>::DidResolutionOptions:
+            Send + Sync + Serialize + Deserialize<'de>,
+    {
+        self.fallback_resolver = Some(Box::new(DidResolvableAdaptor { inner: resolver }));
         self
     }
 
+    pub fn with_cache(mut self, cache: ResolutionCache) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    pub fn cache(&self) -> Option<&ResolutionCache> {
+        self.cache.as_ref()
+    }
+
     pub async fn resolve(
         &self,
         did: &Did,
@@ -96,22 +156,148 @@ impl ResolverRegistry {
         let method = did
             .method()
             .ok_or(DidResolverRegistryError::UnsupportedMethod)?;
-        match self.resolvers.get(method) {
-            Some(resolver) => resolver.resolve(did, options.clone()).await,
-            None => Err(Box::new(DidResolverRegistryError::UnsupportedMethod)),
+        let Some(resolver) = self.resolver(method) else {
+            return Err(Box::new(DidResolverRegistryError::UnsupportedMethod));
+        };
+        let mut options = options.clone();
+        let no_cache = matches!(options.remove(NO_CACHE_OPTION), Some(Value::Bool(true)));
+        let Some(cache) = self
+            .cache
+            .as_ref()
+            .filter(|cache| cache.policy(method).is_some())
+        else {
+            return resolver.resolve(did, options).await;
+        };
+
+        let key = cache_key(did, &options);
+        if !no_cache {
+            if let Some(outcome) = cache.get(&key) {
+                return outcome.map_err(|reason| {
+                    Box::new(DidResolverRegistryError::ResolutionFailed(reason)) as GenericError
+                });
+            }
+        }
+
+        // Concurrent resolutions of the same DID wait for the first one; only the caller which
+        // actually ran the resolver gets its original result and error
+        let in_flight = cache.in_flight(&key);
+        let mut own_result = None;
+        let own_result_ref = &mut own_result;
+        let outcome = in_flight
+            .get_or_init(|| async move {
+                let result = resolver.resolve(did, options).await;
+                let outcome = match &result {
+                    Ok(output) => Ok(output.clone()),
+                    Err(err) => Err(err.to_string()),
+                };
+                *own_result_ref = Some(result);
+                outcome
+            })
+            .await
+            .clone();
+        match own_result {
+            Some(result) => {
+                cache.insert(method, key.clone(), outcome);
+                cache.finish_in_flight(&key, &in_flight);
+                result
+            }
+            None => outcome.map_err(|reason| {
+                Box::new(DidResolverRegistryError::ResolutionFailed(reason)) as GenericError
+            }),
+        }
+    }
+
+    /// Dereferences the DID URL with the dereferencer of its method if registered, otherwise
+    /// within the DID document resolved by the method resolver. `versionId` and `versionTime`
+    /// queries are passed to resolvers accepting them as resolution options; the resolved
+    /// document is checked against them either way.
+    pub async fn dereference(
+        &self,
+        did_url: &DidUrl,
+        options: &DidDereferencingOptions,
+    ) -> Result<DidDereferencingOutput<Cursor<Vec<u8>>>, GenericError> {
+        let method = did_url
+            .method()
+            .ok_or(DidResolverRegistryError::UnqualifiedDid)?;
+        if let Some(dereferencer) = self.dereferencers.get(method) {
+            return dereferencer.dereference(did_url, options).await;
+        }
+
+        let did = Did::try_from(did_url)?;
+        let queries = did_url.queries();
+        let resolution_options = self.supported_options(&did, &version_options(&queries));
+        let resolution_output = self.resolve(&did, &resolution_options).await?;
+        check_version(&resolution_output, &queries)?;
+        Ok(dereference_did_document(
+            &resolution_output,
+            did_url,
+            options,
+        )?)
+    }
+
+    /// The options accepted by the resolver of the method of the DID, along with the options of
+    /// the registry itself. The other options are dropped rather than failing resolution.
+    pub fn supported_options(
+        &self,
+        did: &Did,
+        options: &HashMap<String, Value>,
+    ) -> HashMap<String, Value> {
+        let resolver = did.method().and_then(|method| self.resolver(method));
+        options
+            .iter()
+            .filter(|(name, value)| {
+                name.as_str() == NO_CACHE_OPTION
+                    || resolver.is_some_and(|resolver| {
+                        resolver
+                            .accepts_options(&HashMap::from([(name.to_string(), (*value).clone())]))
+                    })
+            })
+            .map(|(name, value)| (name.clone(), value.clone()))
+            .collect()
+    }
+
+    fn resolver(&self, method: &str) -> Option<&GenericResolver> {
+        self.resolvers
+            .get(method)
+            .or(self.fallback_resolver.as_ref())
+            .map(Box::as_ref)
+    }
+
+    /// Drops cached results of resolution of the DID
+    pub fn invalidate(&self, did: &Did) {
+        if let Some(cache) = &self.cache {
+            cache.invalidate(did.did());
         }
     }
 }
 
+fn cache_key(did: &Did, options: &HashMap<String, Value>) -> String {
+    let options: BTreeMap<_, _> = options.iter().collect();
+    format!(
+        "{}?{}",
+        did.did(),
+        serde_json::to_string(&options).unwrap_or_default()
+    )
+}
+
 #[cfg(test)]
 mod tests {
-    use std::{error::Error, pin::Pin};
+    use std::{
+        error::Error,
+        pin::Pin,
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+        time::Duration,
+    };
 
     use async_trait::async_trait;
     use did_resolver::did_doc::schema::did_doc::DidDocument;
     use mockall::automock;
 
     use super::*;
+    use crate::cache::CachePolicy;
 
     #[allow(unused)] // false positive. used for automock
     struct DummyDidResolver;
@@ -235,6 +421,151 @@ mod tests {
         );
     }
 
+    struct CountingResolver {
+        calls: Arc<AtomicUsize>,
+        fail: bool,
+    }
+
+    #[async_trait]
+    impl DidResolvable for CountingResolver {
+        type DidResolutionOptions = ();
+
+        async fn resolve(
+            &self,
+            did: &Did,
+            _options: &Self::DidResolutionOptions,
+        ) -> Result<DidResolutionOutput, GenericError> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            for _ in 0..5 {
+                tokio::task::yield_now().await;
+            }
+            if self.fail {
+                return Err(Box::new(DummyResolverError));
+            }
+            Ok(DidResolutionOutput::builder(DidDocument::new(did.clone())).build())
+        }
+    }
+
+    fn counting_registry(
+        cache: ResolutionCache,
+        fail: bool,
+    ) -> (ResolverRegistry, Arc<AtomicUsize>) {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let resolver = CountingResolver {
+            calls: calls.clone(),
+            fail,
+        };
+        let registry = ResolverRegistry::new()
+            .register_resolver("example".to_string(), resolver)
+            .with_cache(cache);
+        (registry, calls)
+    }
+
+    #[tokio::test]
+    async fn test_resolve_cached() {
+        let did = Did::parse("did:example:1234".to_string()).unwrap();
+        let (registry, calls) = counting_registry(
+            ResolutionCache::new(CachePolicy::new(Duration::from_secs(60))),
+            false,
+        );
+
+        let first = registry.resolve(&did, &HashMap::new()).await.unwrap();
+        let second = registry.resolve(&did, &HashMap::new()).await.unwrap();
+        assert_eq!(first, second);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        let no_cache = HashMap::from([(NO_CACHE_OPTION.to_string(), Value::Bool(true))]);
+        registry.resolve(&did, &no_cache).await.unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+        registry.invalidate(&did);
+        registry.resolve(&did, &HashMap::new()).await.unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_resolve_not_cached_for_method() {
+        let did = Did::parse("did:example:1234".to_string()).unwrap();
+        let (registry, calls) = counting_registry(
+            ResolutionCache::new(CachePolicy::new(Duration::from_secs(60)))
+                .without_caching_for("example"),
+            false,
+        );
+
+        registry.resolve(&did, &HashMap::new()).await.unwrap();
+        registry.resolve(&did, &HashMap::new()).await.unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_resolve_negative_cached() {
+        let did = Did::parse("did:example:1234".to_string()).unwrap();
+        let (registry, calls) = counting_registry(
+            ResolutionCache::new(
+                CachePolicy::new(Duration::from_secs(60))
+                    .with_negative_ttl(Duration::from_secs(60)),
+            ),
+            true,
+        );
+
+        let first = registry.resolve(&did, &HashMap::new()).await.unwrap_err();
+        assert!(first.downcast_ref::<DummyResolverError>().is_some());
+        let second = registry.resolve(&did, &HashMap::new()).await.unwrap_err();
+        assert!(matches!(
+            second.downcast_ref::<DidResolverRegistryError>(),
+            Some(DidResolverRegistryError::ResolutionFailed(_))
+        ));
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_resolve_deduplicates_in_flight() {
+        let did = Did::parse("did:example:1234".to_string()).unwrap();
+        let (registry, calls) = counting_registry(
+            ResolutionCache::new(CachePolicy::new(Duration::from_secs(60))),
+            false,
+        );
+
+        let options = HashMap::new();
+        let (first, second) = tokio::join!(
+            registry.resolve(&did, &options),
+            registry.resolve(&did, &options)
+        );
+        assert_eq!(first.unwrap(), second.unwrap());
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_supported_options() {
+        let did = Did::parse("did:example:1234".to_string()).unwrap();
+        let (registry, _) = counting_registry(
+            ResolutionCache::new(CachePolicy::new(Duration::from_secs(60))),
+            false,
+        );
+        let options = HashMap::from([
+            (NO_CACHE_OPTION.to_string(), Value::Bool(true)),
+            ("versionId".to_string(), Value::String("1".to_string())),
+        ]);
+        assert_eq!(
+            registry.supported_options(&did, &options),
+            HashMap::from([(NO_CACHE_OPTION.to_string(), Value::Bool(true))])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_resolve_with_fallback_resolver() {
+        let did = Did::parse("did:unknown:1234".to_string()).unwrap();
+        let calls = Arc::new(AtomicUsize::new(0));
+        let registry = ResolverRegistry::new().with_fallback_resolver(CountingResolver {
+            calls: calls.clone(),
+            fail: false,
+        });
+
+        let output = registry.resolve(&did, &HashMap::new()).await.unwrap();
+        assert_eq!(output.did_document.id(), &did);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
     #[tokio::test]
     async fn test_resolve_after_registering_resolver() {
         let did = "did:example:1234";
@@ -25,6 +25,7 @@ impl Key {
             askar_crypto::alg::KeyAlg::X25519 => KeyType::X25519,
             askar_crypto::alg::KeyAlg::EcCurve(EcCurves::Secp256r1) => KeyType::P256,
             askar_crypto::alg::KeyAlg::EcCurve(EcCurves::Secp384r1) => KeyType::P384,
+            askar_crypto::alg::KeyAlg::EcCurve(EcCurves::Secp256k1) => KeyType::Secp256k1,
             _ => return Err(PublicKeyError::UnsupportedKeyType(askar_alg.to_string())),
         };
 
@@ -55,6 +56,7 @@ pub fn public_key_type_to_askar_key_alg(value: &KeyType) -> Result<KeyAlg, Publi
         KeyType::Bls12381g2 => KeyAlg::Bls12_381(BlsCurves::G2),
         KeyType::P256 => KeyAlg::EcCurve(EcCurves::Secp256r1),
         KeyType::P384 => KeyAlg::EcCurve(EcCurves::Secp384r1),
+        KeyType::Secp256k1 => KeyAlg::EcCurve(EcCurves::Secp256k1),
         other => {
             return Err(PublicKeyError::UnsupportedKeyType(format!(
                 "Unsupported key type: {other:?}"
@@ -1,5 +1,6 @@
 use std::fmt::Display;
 
+use curve25519_dalek::edwards::CompressedEdwardsY;
 use serde::{Deserialize, Serialize};
 
 use super::KeyType;
@@ -80,6 +81,25 @@ impl Key {
         })
     }
 
+    /// Converts an Ed25519 public key (Edwards point) to the X25519 public key (Montgomery point)
+    /// of the same key pair
+    pub fn ed25519_to_x25519(&self) -> Result<Self, PublicKeyError> {
+        self.validate_key_type(KeyType::Ed25519)?;
+        let bytes: [u8; 32] = self.key().try_into().map_err(|_| {
+            PublicKeyError::InvalidKey(format!(
+                "Ed25519 key must be 32 bytes long, got {}",
+                self.key().len()
+            ))
+        })?;
+        let edwards_point = CompressedEdwardsY(bytes).decompress().ok_or_else(|| {
+            PublicKeyError::InvalidKey("Ed25519 key is not a valid curve point".to_string())
+        })?;
+        Ok(Self {
+            key_type: KeyType::X25519,
+            key: edwards_point.to_montgomery().to_bytes().to_vec(),
+        })
+    }
+
     pub fn short_prefixless_fingerprint(&self) -> String {
         self.prefixless_fingerprint()
             .chars()
@@ -229,6 +249,27 @@ mod tests {
         fn strip_multicodec_prefix_if_present_test() {
             super::strip_multicodec_prefix_if_present_test(key_bytes(), &KeyType::Ed25519);
         }
+
+        #[test]
+        fn ed25519_to_x25519_test() {
+            // https://w3c-ccg.github.io/did-method-key/#test-vectors
+            let key =
+                Key::from_fingerprint("z6MkiTBz1ymuepAQ4HEHYSF1H8quG5GLVVQR3djdX3mDooWp").unwrap();
+            let x25519_key = key.ed25519_to_x25519().unwrap();
+            assert_eq!(x25519_key.key_type(), &KeyType::X25519);
+            assert_eq!(
+                x25519_key.fingerprint(),
+                "z6LShs9GGnqk85isEBzzshkuVWrVKsRp24GnDuHk8QWkARMW"
+            );
+        }
+
+        #[test]
+        fn ed25519_to_x25519_error_test() {
+            let x25519_key = Key::new(key_bytes(), KeyType::X25519).unwrap();
+            assert!(x25519_key.ed25519_to_x25519().is_err());
+            let short_key = Key::new(key_bytes()[..31].to_vec(), KeyType::Ed25519).unwrap();
+            assert!(short_key.ed25519_to_x25519().is_err());
+        }
     }
 
     mod x25519 {
@@ -280,4 +321,54 @@ mod tests {
             super::strip_multicodec_prefix_if_present_test(key_bytes(), &KeyType::X25519);
         }
     }
+
+    mod secp256k1 {
+        use super::*;
+
+        const TEST_KEY_BASE58: &str = "23o6Sau8NxxzXcgSc3PLcNxrzrZpbLeBn1izfv3jbKhuv";
+        const TEST_FINGERPRINT: &str = "zQ3shokFTS3brHcDQrn82RUDfCZESWL1ZdCEJwekUDPQiYBme";
+
+        fn key_bytes() -> Vec<u8> {
+            bs58::decode(TEST_KEY_BASE58).into_vec().unwrap()
+        }
+
+        fn encode_multibase(key_bytes: Vec<u8>) -> String {
+            multibase::encode(multibase::Base::Base58Btc, key_bytes)
+        }
+
+        #[test]
+        fn new_key_test() {
+            super::new_key_test(key_bytes(), KeyType::Secp256k1);
+        }
+
+        #[test]
+        fn prefixed_key_test() {
+            super::prefixed_key_test(
+                key_bytes(),
+                KeyType::Secp256k1,
+                TEST_FINGERPRINT.to_string(),
+                encode_multibase,
+            );
+        }
+
+        #[test]
+        fn fingerprint_test() {
+            super::fingerprint_test(key_bytes(), KeyType::Secp256k1, TEST_FINGERPRINT);
+        }
+
+        #[test]
+        fn base58_test() {
+            super::base58_test(key_bytes(), KeyType::Secp256k1, TEST_KEY_BASE58);
+        }
+
+        #[test]
+        fn from_fingerprint_test() {
+            super::from_fingerprint_test(key_bytes(), KeyType::Secp256k1, TEST_FINGERPRINT);
+        }
+
+        #[test]
+        fn strip_multicodec_prefix_if_present_test() {
+            super::strip_multicodec_prefix_if_present_test(key_bytes(), &KeyType::Secp256k1);
+        }
+    }
 }
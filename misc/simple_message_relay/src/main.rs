@@ -1,17 +1,78 @@
-use std::{
-    collections::{HashMap, VecDeque},
-    sync::Mutex,
-};
+mod storage;
 
 use actix_web::{
-    get, post,
+    get,
+    http::header::AUTHORIZATION,
+    post,
     web::{self, Bytes},
-    App, HttpResponse, HttpServer, Responder,
+    App, HttpRequest, HttpResponse, HttpServer, Responder,
 };
+use base64::{engine::general_purpose::STANDARD, Engine};
+use serde::{Deserialize, Serialize};
+use storage::{MessageStore, StorageError};
+use subtle::ConstantTimeEq;
+use uuid::Uuid;
+
+const DEFAULT_MAX_MESSAGE_SIZE: usize = 256 * 1024;
+const DEFAULT_BATCH_LIMIT: usize = 10;
+const MAX_BATCH_LIMIT: usize = 100;
 
 #[derive(Default)]
-struct UserMessages {
-    messages_by_user_id: Mutex<HashMap<String, VecDeque<Vec<u8>>>>,
+struct Relay {
+    store: MessageStore,
+    // when set, only registered users can pop messages
+    require_auth: bool,
+    // token of the operator, required to register users; registration is disabled without it
+    admin_token: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct PopQuery {
+    limit: Option<usize>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct RegisteredUser {
+    token: String,
+}
+
+fn storage_error_response(err: &StorageError) -> HttpResponse {
+    HttpResponse::InternalServerError().body(err.to_string())
+}
+
+/// Whether the request carries `Authorization: Bearer <token>` header with `token`, compared in
+/// constant time so that the token can't be guessed from response times
+fn has_bearer_token(request: &HttpRequest, token: &str) -> bool {
+    request
+        .headers()
+        .get(AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .is_some_and(|presented| bool::from(presented.as_bytes().ct_eq(token.as_bytes())))
+}
+
+/// Checks the request carries the token of the user.
+/// Users who were never registered are let through unless the relay requires authentication.
+async fn authorize(
+    request: &HttpRequest,
+    relay: &Relay,
+    user_id: &str,
+) -> Result<(), HttpResponse> {
+    let token = relay
+        .store
+        .get_user_token(user_id)
+        .await
+        .map_err(|err| storage_error_response(&err))?;
+    let Some(token) = token else {
+        if relay.require_auth {
+            return Err(HttpResponse::Unauthorized().body("User is not registered"));
+        }
+        return Ok(());
+    };
+    if !has_bearer_token(request, &token) {
+        return Err(HttpResponse::Unauthorized().body("Invalid or missing token"));
+    }
+    Ok(())
 }
 
 #[get("/")]
@@ -19,21 +80,74 @@ async fn hello() -> impl Responder {
     HttpResponse::Ok().body("Hello world!")
 }
 
+/// Registers the user on behalf of the operator, who hands the token over to the user. Agents
+/// can't register themselves, as they could claim user ids of others.
+#[post("/register_user/{user_id}")]
+async fn register_user(
+    request: HttpRequest,
+    path: web::Path<String>,
+    data: web::Data<Relay>,
+) -> impl Responder {
+    let Some(admin_token) = &data.admin_token else {
+        return HttpResponse::Forbidden().body("Registration is disabled");
+    };
+    if !has_bearer_token(&request, admin_token) {
+        return HttpResponse::Unauthorized().body("Invalid or missing admin token");
+    }
+    let user_id = path.into_inner();
+    let token = Uuid::new_v4().simple().to_string();
+    match data.store.register_user(&user_id, &token).await {
+        Ok(true) => HttpResponse::Ok().json(RegisteredUser { token }),
+        Ok(false) => HttpResponse::Conflict().body("User is already registered"),
+        Err(err) => storage_error_response(&err),
+    }
+}
+
 #[get("/pop_user_message/{user_id}")]
 async fn pop_user_message(
+    request: HttpRequest,
     path: web::Path<String>,
-    data: web::Data<UserMessages>,
+    data: web::Data<Relay>,
 ) -> impl Responder {
     let user_id = path.into_inner();
-    let mut messages_by_user_id = data.messages_by_user_id.lock().unwrap();
-    let messages_for_user = messages_by_user_id.get_mut(&user_id);
+    if let Err(response) = authorize(&request, &data, &user_id).await {
+        return response;
+    }
 
-    let message_body = messages_for_user.and_then(|msgs| msgs.pop_front());
+    match data.store.pop(&user_id, 1).await {
+        Ok(messages) => match messages.into_iter().next() {
+            Some(body) => HttpResponse::Ok().body(body),
+            None => HttpResponse::NoContent().into(),
+        },
+        Err(err) => storage_error_response(&err),
+    }
+}
 
-    if let Some(body) = message_body {
-        HttpResponse::Ok().body(body)
-    } else {
-        HttpResponse::NoContent().into()
+/// Pops up to `limit` oldest messages, returned as JSON array of base64 encoded messages
+#[get("/pop_user_messages/{user_id}")]
+async fn pop_user_messages(
+    request: HttpRequest,
+    path: web::Path<String>,
+    query: web::Query<PopQuery>,
+    data: web::Data<Relay>,
+) -> impl Responder {
+    let user_id = path.into_inner();
+    if let Err(response) = authorize(&request, &data, &user_id).await {
+        return response;
+    }
+
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_BATCH_LIMIT)
+        .min(MAX_BATCH_LIMIT);
+    match data.store.pop(&user_id, limit).await {
+        Ok(messages) => HttpResponse::Ok().json(
+            messages
+                .iter()
+                .map(|message| STANDARD.encode(message))
+                .collect::<Vec<_>>(),
+        ),
+        Err(err) => storage_error_response(&err),
     }
 }
 
@@ -41,23 +155,14 @@ async fn pop_user_message(
 async fn send_user_message(
     path: web::Path<String>,
     body: Bytes,
-    data: web::Data<UserMessages>,
+    data: web::Data<Relay>,
 ) -> impl Responder {
     let user_id = path.into_inner();
 
-    let body = body.to_vec();
-
-    let mut messages_by_user_id = data.messages_by_user_id.lock().unwrap();
-
-    let messages_for_user = messages_by_user_id.get_mut(&user_id);
-
-    if let Some(messages) = messages_for_user {
-        messages.push_back(body);
-    } else {
-        messages_by_user_id.insert(user_id, vec![body].into());
+    match data.store.push(&user_id, body.to_vec()).await {
+        Ok(()) => HttpResponse::Ok().finish(),
+        Err(err) => storage_error_response(&err),
     }
-
-    HttpResponse::Ok()
 }
 
 #[get("/status")]
@@ -65,14 +170,42 @@ async fn status() -> impl Responder {
     HttpResponse::Ok()
 }
 
+fn read_env_var<T: std::str::FromStr>(name: &str) -> std::io::Result<Option<T>> {
+    match std::env::var(name) {
+        Ok(value) => value.parse().map(Some).map_err(|_| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!("invalid value {value:?} of {name}"),
+            )
+        }),
+        Err(_) => Ok(None),
+    }
+}
+
 #[actix_web::main]
 async fn main() -> std::io::Result<()> {
-    let user_messages = web::Data::new(UserMessages::default());
+    let storage = read_env_var::<String>("RELAY_STORAGE")?.unwrap_or_else(|| "memory".to_owned());
+    let max_message_size =
+        read_env_var("RELAY_MAX_MESSAGE_SIZE")?.unwrap_or(DEFAULT_MAX_MESSAGE_SIZE);
+    let require_auth = read_env_var("RELAY_REQUIRE_AUTH")?.unwrap_or(false);
+    let admin_token = read_env_var("RELAY_ADMIN_TOKEN")?;
+
+    let store = MessageStore::open(&storage)
+        .await
+        .map_err(|err| std::io::Error::new(std::io::ErrorKind::Other, err))?;
+    let relay = web::Data::new(Relay {
+        store,
+        require_auth,
+        admin_token,
+    });
 
     HttpServer::new(move || {
         App::new()
-            .app_data(user_messages.clone())
+            .app_data(relay.clone())
+            .app_data(web::PayloadConfig::new(max_message_size))
+            .service(register_user)
             .service(pop_user_message)
+            .service(pop_user_messages)
             .service(send_user_message)
             .service(status)
     })
@@ -89,8 +222,14 @@ mod tests {
         test::{self, TestRequest},
         web, App,
     };
+    use base64::{engine::general_purpose::STANDARD, Engine};
 
-    use crate::{pop_user_message, send_user_message, UserMessages};
+    use crate::{
+        pop_user_message, pop_user_messages, register_user, send_user_message,
+        storage::MessageStore, RegisteredUser, Relay,
+    };
+
+    const ADMIN_TOKEN: &str = "relay-admin-token";
 
     fn pop_message_request(user_id: &str) -> TestRequest {
         test::TestRequest::get().uri(&format!("/pop_user_message/{user_id}"))
@@ -105,7 +244,7 @@ mod tests {
     #[actix_web::test]
     async fn test_standard_user_flow() {
         // assemble service
-        let user_messages = web::Data::new(UserMessages::default());
+        let user_messages = web::Data::new(Relay::default());
 
         let app = test::init_service(
             App::new()
@@ -149,7 +288,7 @@ mod tests {
     #[actix_web::test]
     async fn test_multi_message_multi_user_flow() {
         // assemble service
-        let user_messages = web::Data::new(UserMessages::default());
+        let user_messages = web::Data::new(Relay::default());
 
         let app = test::init_service(
             App::new()
@@ -198,4 +337,187 @@ mod tests {
             message4.as_bytes()
         );
     }
+
+    #[actix_web::test]
+    async fn test_registered_user_requires_token() {
+        let relay = web::Data::new(Relay {
+            admin_token: Some(ADMIN_TOKEN.to_owned()),
+            ..Default::default()
+        });
+        let app = test::init_service(
+            App::new()
+                .app_data(relay)
+                .service(register_user)
+                .service(send_user_message)
+                .service(pop_user_message),
+        )
+        .await;
+
+        let user_id = "user1";
+        let register_request = || {
+            test::TestRequest::post()
+                .uri(&format!("/register_user/{user_id}"))
+                .insert_header(("Authorization", format!("Bearer {ADMIN_TOKEN}")))
+                .to_request()
+        };
+        let registered: RegisteredUser =
+            test::call_and_read_body_json(&app, register_request()).await;
+        // user id can't be claimed twice
+        let resp = test::call_service(&app, register_request()).await;
+        assert_eq!(resp.status(), StatusCode::CONFLICT);
+
+        test::call_service(&app, send_message_request(user_id, "hello").to_request()).await;
+
+        let resp = test::call_service(&app, pop_message_request(user_id).to_request()).await;
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+        let req = pop_message_request(user_id)
+            .insert_header(("Authorization", "Bearer wrong-token"))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+
+        let req = pop_message_request(user_id)
+            .insert_header(("Authorization", format!("Bearer {}", registered.token)))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(body::to_bytes(resp.into_body()).await.unwrap(), "hello");
+    }
+
+    #[actix_web::test]
+    async fn test_registration_requires_admin_token() {
+        let register_request = |token: Option<&str>| {
+            let request = test::TestRequest::post().uri("/register_user/user1");
+            match token {
+                Some(token) => request.insert_header(("Authorization", format!("Bearer {token}"))),
+                None => request,
+            }
+            .to_request()
+        };
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(Relay::default()))
+                .service(register_user),
+        )
+        .await;
+        let resp = test::call_service(&app, register_request(Some(ADMIN_TOKEN))).await;
+        assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+
+        let relay = web::Data::new(Relay {
+            admin_token: Some(ADMIN_TOKEN.to_owned()),
+            ..Default::default()
+        });
+        let app = test::init_service(App::new().app_data(relay).service(register_user)).await;
+        let resp = test::call_service(&app, register_request(None)).await;
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+        let resp = test::call_service(&app, register_request(Some("wrong-token"))).await;
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+        let resp = test::call_service(&app, register_request(Some(ADMIN_TOKEN))).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+    }
+
+    #[actix_web::test]
+    async fn test_require_auth_rejects_unregistered_user() {
+        let relay = web::Data::new(Relay {
+            require_auth: true,
+            ..Default::default()
+        });
+        let app = test::init_service(App::new().app_data(relay).service(pop_user_message)).await;
+
+        let resp = test::call_service(&app, pop_message_request("user1").to_request()).await;
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[actix_web::test]
+    async fn test_batch_pop() {
+        let relay = web::Data::new(Relay::default());
+        let app = test::init_service(
+            App::new()
+                .app_data(relay)
+                .service(send_user_message)
+                .service(pop_user_messages),
+        )
+        .await;
+
+        let user_id = "user1";
+        for message in ["message1", "message2", "message3"] {
+            test::call_service(&app, send_message_request(user_id, message).to_request()).await;
+        }
+
+        let pop_request = || {
+            test::TestRequest::get()
+                .uri(&format!("/pop_user_messages/{user_id}?limit=2"))
+                .to_request()
+        };
+        let messages: Vec<String> = test::call_and_read_body_json(&app, pop_request()).await;
+        assert_eq!(
+            messages,
+            [STANDARD.encode("message1"), STANDARD.encode("message2")]
+        );
+        let messages: Vec<String> = test::call_and_read_body_json(&app, pop_request()).await;
+        assert_eq!(messages, [STANDARD.encode("message3")]);
+        let messages: Vec<String> = test::call_and_read_body_json(&app, pop_request()).await;
+        assert!(messages.is_empty());
+    }
+
+    #[actix_web::test]
+    async fn test_message_size_limit() {
+        let relay = web::Data::new(Relay::default());
+        let app = test::init_service(
+            App::new()
+                .app_data(relay)
+                .app_data(web::PayloadConfig::new(5))
+                .service(send_user_message),
+        )
+        .await;
+
+        let resp =
+            test::call_service(&app, send_message_request("user1", "small").to_request()).await;
+        assert!(resp.status().is_success());
+        let resp = test::call_service(
+            &app,
+            send_message_request("user1", "too large").to_request(),
+        )
+        .await;
+        assert_eq!(resp.status(), StatusCode::PAYLOAD_TOO_LARGE);
+    }
+
+    async fn assert_store_persists_messages(config: &str) {
+        let store = MessageStore::open(config).await.unwrap();
+        store.push("user1", b"message1".to_vec()).await.unwrap();
+        store.push("user1", b"message2".to_vec()).await.unwrap();
+        assert!(store.register_user("user1", "token").await.unwrap());
+        drop(store);
+
+        let store = MessageStore::open(config).await.unwrap();
+        assert_eq!(
+            store.get_user_token("user1").await.unwrap().as_deref(),
+            Some("token")
+        );
+        assert!(!store.register_user("user1", "other").await.unwrap());
+        assert_eq!(store.pop("user1", 1).await.unwrap(), [b"message1"]);
+        store.push("user1", b"message3".to_vec()).await.unwrap();
+        assert_eq!(
+            store.pop("user1", 10).await.unwrap(),
+            [b"message2", b"message3"]
+        );
+        assert!(store.pop("user1", 10).await.unwrap().is_empty());
+        assert!(store.pop("user2", 10).await.unwrap().is_empty());
+    }
+
+    #[actix_web::test]
+    async fn test_file_store_persists_messages() {
+        let dir = std::env::temp_dir().join(format!("relay-{}", uuid::Uuid::new_v4()));
+        assert_store_persists_messages(&format!("file:{}", dir.display())).await;
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[cfg(feature = "sqlite")]
+    #[actix_web::test]
+    async fn test_sqlite_store_persists_messages() {
+        let path = std::env::temp_dir().join(format!("relay-{}.db", uuid::Uuid::new_v4()));
+        assert_store_persists_messages(&format!("sqlite:{}", path.display())).await;
+        std::fs::remove_file(path).unwrap();
+    }
 }
@@ -0,0 +1,110 @@
+use std::{
+    fmt::Write,
+    fs,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+use super::StorageError;
+
+/// Store keeping each message in its own file, under `<root>/messages/<user>/<sequence number>`.
+/// Tokens are kept in `<root>/tokens/<user>`.
+pub struct FileStore {
+    root: PathBuf,
+    // serializes access, so that sequence numbers are not handed out twice
+    lock: Mutex<()>,
+}
+
+impl FileStore {
+    pub fn open(root: impl AsRef<Path>) -> Result<Self, StorageError> {
+        let root = root.as_ref().to_path_buf();
+        fs::create_dir_all(root.join("messages"))?;
+        fs::create_dir_all(root.join("tokens"))?;
+        Ok(Self {
+            root,
+            lock: Mutex::new(()),
+        })
+    }
+
+    pub fn push(&self, user_id: &str, message: &[u8]) -> Result<(), StorageError> {
+        let _guard = self.lock.lock().unwrap();
+        let user_dir = self.user_messages_dir(user_id);
+        fs::create_dir_all(&user_dir)?;
+        let next_sequence = read_sequence_numbers(&user_dir)?
+            .last()
+            .map_or(0, |last| last + 1);
+        // write under temporary name first, so a crash never leaves a truncated message behind
+        let tmp_path = user_dir.join(format!("{next_sequence}.tmp"));
+        fs::write(&tmp_path, message)?;
+        fs::rename(tmp_path, user_dir.join(next_sequence.to_string()))?;
+        Ok(())
+    }
+
+    pub fn pop(&self, user_id: &str, limit: usize) -> Result<Vec<Vec<u8>>, StorageError> {
+        let _guard = self.lock.lock().unwrap();
+        let user_dir = self.user_messages_dir(user_id);
+        let mut messages = vec![];
+        for sequence in read_sequence_numbers(&user_dir)?.into_iter().take(limit) {
+            let path = user_dir.join(sequence.to_string());
+            messages.push(fs::read(&path)?);
+            fs::remove_file(path)?;
+        }
+        Ok(messages)
+    }
+
+    pub fn register_user(&self, user_id: &str, token: &str) -> Result<bool, StorageError> {
+        let _guard = self.lock.lock().unwrap();
+        let path = self.root.join("tokens").join(encode_user_id(user_id));
+        if path.exists() {
+            return Ok(false);
+        }
+        fs::write(path, token)?;
+        Ok(true)
+    }
+
+    pub fn get_user_token(&self, user_id: &str) -> Result<Option<String>, StorageError> {
+        let path = self.root.join("tokens").join(encode_user_id(user_id));
+        match fs::read_to_string(path) {
+            Ok(token) => Ok(Some(token)),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    fn user_messages_dir(&self, user_id: &str) -> PathBuf {
+        self.root.join("messages").join(encode_user_id(user_id))
+    }
+}
+
+// User ids come from request paths, hex encoding keeps them from escaping the store directory
+fn encode_user_id(user_id: &str) -> String {
+    user_id
+        .bytes()
+        .fold(String::with_capacity(user_id.len() * 2), |mut acc, byte| {
+            let _ = write!(acc, "{byte:02x}");
+            acc
+        })
+}
+
+/// Sequence numbers of messages stored in the directory, in ascending order
+fn read_sequence_numbers(dir: &Path) -> Result<Vec<u64>, StorageError> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == ErrorKind::NotFound => return Ok(vec![]),
+        Err(err) => return Err(err.into()),
+    };
+    let mut sequence_numbers = vec![];
+    for entry in entries {
+        // skips leftover temporary files
+        if let Some(sequence) = entry?
+            .file_name()
+            .to_str()
+            .and_then(|name| name.parse().ok())
+        {
+            sequence_numbers.push(sequence);
+        }
+    }
+    sequence_numbers.sort_unstable();
+    Ok(sequence_numbers)
+}
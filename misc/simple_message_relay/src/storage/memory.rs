@@ -0,0 +1,44 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::Mutex,
+};
+
+/// Store keeping everything in memory, all messages are lost on restart
+#[derive(Default)]
+pub struct MemoryStore {
+    messages_by_user_id: Mutex<HashMap<String, VecDeque<Vec<u8>>>>,
+    tokens_by_user_id: Mutex<HashMap<String, String>>,
+}
+
+impl MemoryStore {
+    pub fn push(&self, user_id: &str, message: Vec<u8>) {
+        self.messages_by_user_id
+            .lock()
+            .unwrap()
+            .entry(user_id.to_owned())
+            .or_default()
+            .push_back(message);
+    }
+
+    pub fn pop(&self, user_id: &str, limit: usize) -> Vec<Vec<u8>> {
+        let mut messages_by_user_id = self.messages_by_user_id.lock().unwrap();
+        let Some(messages) = messages_by_user_id.get_mut(user_id) else {
+            return vec![];
+        };
+        let count = limit.min(messages.len());
+        messages.drain(..count).collect()
+    }
+
+    pub fn register_user(&self, user_id: &str, token: &str) -> bool {
+        let mut tokens_by_user_id = self.tokens_by_user_id.lock().unwrap();
+        if tokens_by_user_id.contains_key(user_id) {
+            return false;
+        }
+        tokens_by_user_id.insert(user_id.to_owned(), token.to_owned());
+        true
+    }
+
+    pub fn get_user_token(&self, user_id: &str) -> Option<String> {
+        self.tokens_by_user_id.lock().unwrap().get(user_id).cloned()
+    }
+}
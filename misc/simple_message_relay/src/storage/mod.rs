@@ -0,0 +1,99 @@
+mod file;
+mod memory;
+#[cfg(feature = "sqlite")]
+mod sqlite;
+
+#[cfg(feature = "sqlite")]
+pub use self::sqlite::SqliteStore;
+pub use self::{file::FileStore, memory::MemoryStore};
+
+#[derive(Debug, thiserror::Error)]
+pub enum StorageError {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+    #[cfg(feature = "sqlite")]
+    #[error("SQLite error: {0}")]
+    Sqlite(#[from] sqlx::Error),
+    #[error("Invalid storage configuration: {0}")]
+    InvalidConfig(String),
+}
+
+/// Queues of messages for each user, along with tokens of users who registered
+pub enum MessageStore {
+    Memory(MemoryStore),
+    File(FileStore),
+    #[cfg(feature = "sqlite")]
+    Sqlite(SqliteStore),
+}
+
+impl Default for MessageStore {
+    fn default() -> Self {
+        Self::Memory(MemoryStore::default())
+    }
+}
+
+impl MessageStore {
+    /// Opens store described by `config`, which is one of `memory`, `file:<directory>` or
+    /// `sqlite:<database file>`
+    pub async fn open(config: &str) -> Result<Self, StorageError> {
+        if config == "memory" {
+            return Ok(Self::default());
+        }
+        if let Some(directory) = config.strip_prefix("file:") {
+            return Ok(Self::File(FileStore::open(directory)?));
+        }
+        if let Some(path) = config.strip_prefix("sqlite:") {
+            #[cfg(feature = "sqlite")]
+            return Ok(Self::Sqlite(SqliteStore::open(path).await?));
+            #[cfg(not(feature = "sqlite"))]
+            return Err(StorageError::InvalidConfig(format!(
+                "can't open {path}, relay was built without sqlite feature"
+            )));
+        }
+        Err(StorageError::InvalidConfig(format!(
+            "unknown storage {config}, expected memory, file:<directory> or sqlite:<file>"
+        )))
+    }
+
+    pub async fn push(&self, user_id: &str, message: Vec<u8>) -> Result<(), StorageError> {
+        match self {
+            Self::Memory(store) => {
+                store.push(user_id, message);
+                Ok(())
+            }
+            Self::File(store) => store.push(user_id, &message),
+            #[cfg(feature = "sqlite")]
+            Self::Sqlite(store) => store.push(user_id, &message).await,
+        }
+    }
+
+    /// Removes and returns up to `limit` oldest messages of the user
+    pub async fn pop(&self, user_id: &str, limit: usize) -> Result<Vec<Vec<u8>>, StorageError> {
+        match self {
+            Self::Memory(store) => Ok(store.pop(user_id, limit)),
+            Self::File(store) => store.pop(user_id, limit),
+            #[cfg(feature = "sqlite")]
+            Self::Sqlite(store) => store.pop(user_id, limit).await,
+        }
+    }
+
+    /// Stores token of the user, unless the user is already registered.
+    /// Returns whether the token was stored.
+    pub async fn register_user(&self, user_id: &str, token: &str) -> Result<bool, StorageError> {
+        match self {
+            Self::Memory(store) => Ok(store.register_user(user_id, token)),
+            Self::File(store) => store.register_user(user_id, token),
+            #[cfg(feature = "sqlite")]
+            Self::Sqlite(store) => store.register_user(user_id, token).await,
+        }
+    }
+
+    pub async fn get_user_token(&self, user_id: &str) -> Result<Option<String>, StorageError> {
+        match self {
+            Self::Memory(store) => Ok(store.get_user_token(user_id)),
+            Self::File(store) => store.get_user_token(user_id),
+            #[cfg(feature = "sqlite")]
+            Self::Sqlite(store) => store.get_user_token(user_id).await,
+        }
+    }
+}
@@ -0,0 +1,90 @@
+use std::path::Path;
+
+use sqlx::{
+    sqlite::{SqliteConnectOptions, SqlitePoolOptions},
+    Row, SqlitePool,
+};
+
+use super::StorageError;
+
+/// Store backed by SQLite database file, created on first use
+pub struct SqliteStore {
+    pool: SqlitePool,
+}
+
+impl SqliteStore {
+    pub async fn open(path: impl AsRef<Path>) -> Result<Self, StorageError> {
+        let options = SqliteConnectOptions::new()
+            .filename(path)
+            .create_if_missing(true);
+        let pool = SqlitePoolOptions::new().connect_with(options).await?;
+        sqlx::query(
+            "CREATE TABLE IF NOT EXISTS messages (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                user_id TEXT NOT NULL,
+                body BLOB NOT NULL
+            )",
+        )
+        .execute(&pool)
+        .await?;
+        sqlx::query("CREATE INDEX IF NOT EXISTS messages_user_id ON messages (user_id, id)")
+            .execute(&pool)
+            .await?;
+        sqlx::query(
+            "CREATE TABLE IF NOT EXISTS users (
+                user_id TEXT PRIMARY KEY,
+                token TEXT NOT NULL
+            )",
+        )
+        .execute(&pool)
+        .await?;
+        Ok(Self { pool })
+    }
+
+    pub async fn push(&self, user_id: &str, message: &[u8]) -> Result<(), StorageError> {
+        sqlx::query("INSERT INTO messages (user_id, body) VALUES (?, ?)")
+            .bind(user_id)
+            .bind(message)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn pop(&self, user_id: &str, limit: usize) -> Result<Vec<Vec<u8>>, StorageError> {
+        let limit = i64::try_from(limit).unwrap_or(i64::MAX);
+        // single statement, so concurrent pops never hand out the same message twice
+        let rows = sqlx::query(
+            "DELETE FROM messages WHERE id IN (
+                SELECT id FROM messages WHERE user_id = ? ORDER BY id LIMIT ?
+            ) RETURNING id, body",
+        )
+        .bind(user_id)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+        let mut messages = rows
+            .into_iter()
+            .map(|row| Ok((row.try_get::<i64, _>("id")?, row.try_get("body")?)))
+            .collect::<Result<Vec<(i64, Vec<u8>)>, sqlx::Error>>()?;
+        // RETURNING doesn't guarantee order
+        messages.sort_unstable_by_key(|(id, _)| *id);
+        Ok(messages.into_iter().map(|(_, body)| body).collect())
+    }
+
+    pub async fn register_user(&self, user_id: &str, token: &str) -> Result<bool, StorageError> {
+        let result = sqlx::query("INSERT OR IGNORE INTO users (user_id, token) VALUES (?, ?)")
+            .bind(user_id)
+            .bind(token)
+            .execute(&self.pool)
+            .await?;
+        Ok(result.rows_affected() == 1)
+    }
+
+    pub async fn get_user_token(&self, user_id: &str) -> Result<Option<String>, StorageError> {
+        let token = sqlx::query_scalar("SELECT token FROM users WHERE user_id = ?")
+            .bind(user_id)
+            .fetch_optional(&self.pool)
+            .await?;
+        Ok(token)
+    }
+}